@@ -1,28 +1,99 @@
 //! Command-line argument definitions for the Orrery CLI.
 //!
 //! This module defines the [`Args`] structure parsed from the command line
-//! using [`clap`]. Arguments control input/output paths, configuration file
-//! selection, and logging verbosity.
+//! using [`clap`]. Arguments control input/output paths, configuration and
+//! palette file selection, input format, diagram statistics output,
+//! configuration dumping, warning handling, diagnostic format, and logging
+//! verbosity.
+//! The optional [`Command`] selects a subcommand instead of rendering.
 
-use clap::Parser;
+use clap::{Parser, Subcommand, ValueEnum};
 
 /// Command-line arguments for the Orrery diagram tool
 #[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
+#[command(
+    author,
+    version,
+    about,
+    long_about = None,
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true
+)]
 pub struct Args {
+    /// Subcommand to run instead of rendering
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     /// Path to the input Orrery file
-    #[arg(help = "Path to the input file")]
-    pub input: String,
+    #[arg(
+        help = "Path to the input file",
+        required_unless_present = "dump_config"
+    )]
+    pub input: Option<String>,
 
     /// Path to the output SVG file
     #[arg(short, long, default_value = "out.svg")]
     pub output: String,
 
     /// Path to configuration file (TOML)
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     pub config: Option<String>,
 
+    /// Path to a palette file (TOML) defining named colors such as `brand.primary`
+    #[arg(long, global = true)]
+    pub palette: Option<String>,
+
+    /// Format of the input file
+    #[arg(long, value_enum, default_value_t)]
+    pub input_format: InputFormat,
+
+    /// Path to write diagram statistics (JSON), or `-` for stdout
+    #[arg(long)]
+    pub stats: Option<String>,
+
+    /// Print the effective configuration (TOML) to stdout instead of rendering
+    #[arg(long)]
+    pub dump_config: bool,
+
+    /// Treat warnings as errors
+    #[arg(long, global = true)]
+    pub deny_warnings: bool,
+
+    /// Format of reported errors and warnings
+    #[arg(long, value_enum, default_value_t, global = true)]
+    pub error_format: ErrorFormat,
+
     /// Log level (off, error, warn, info, debug, trace)
-    #[arg(long, default_value = "info")]
+    #[arg(long, default_value = "info", global = true)]
     pub log_level: String,
 }
+
+/// Formats of the input file
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum InputFormat {
+    /// Orrery source text, parsed and elaborated
+    #[default]
+    Orr,
+    /// A serialized semantic diagram, rendered without parsing
+    Json,
+}
+
+/// Output formats for errors and warnings
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ErrorFormat {
+    /// Rendered source snippets, logged for humans
+    #[default]
+    Human,
+    /// One JSON object per diagnostic on stderr, for editors and other tools
+    Json,
+}
+
+/// Subcommands of the Orrery diagram tool
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Check a diagram against the lint rules of the `[lint]` config section
+    Lint {
+        /// Path to the input Orrery file
+        input: String,
+    },
+}
@@ -1,7 +1,8 @@
 //! Configuration file loading for the CLI
 //!
 //! This module handles finding and loading TOML configuration files
-//! from various locations (explicit path, local directory, system directory).
+//! from various locations (explicit path, local directory, system directory),
+//! and loading palette files of named colors.
 
 use std::{
     fs,
@@ -9,10 +10,14 @@ use std::{
 };
 
 use directories::ProjectDirs;
-use log::{debug, info};
+use log::{debug, info, warn};
 use thiserror::Error;
 
-use orrery::{RenderError, config::AppConfig};
+use orrery::{
+    RenderError,
+    color::{Color, Palette},
+    config::AppConfig,
+};
 
 /// Configuration-related errors for CLI
 #[derive(Debug, Error)]
@@ -101,6 +106,9 @@ pub fn load_config(explicit_path: Option<impl AsRef<Path>>) -> Result<AppConfig,
 /// - File doesn't exist
 /// - File cannot be read
 /// - TOML parsing fails
+///
+/// Keys that are not part of the configuration schema are reported as
+/// warnings and otherwise ignored.
 fn load_config_file(path: impl AsRef<Path>) -> Result<AppConfig, RenderError> {
     let path = path.as_ref();
 
@@ -113,8 +121,203 @@ fn load_config_file(path: impl AsRef<Path>) -> Result<AppConfig, RenderError> {
     let content = fs::read_to_string(path)?;
 
     // Parse TOML content
-    let config: AppConfig =
+    let table: toml::Table =
         toml::from_str(&content).map_err(|err| ConfigError::Parse(err.to_string()))?;
 
+    for key in AppConfig::unknown_keys(&table) {
+        warn!(path = path.display().to_string(), key; "Ignoring unknown configuration key");
+    }
+
+    let config: AppConfig = table
+        .try_into()
+        .map_err(|err: toml::de::Error| ConfigError::Parse(err.to_string()))?;
+
     Ok(config)
 }
+
+/// Load a palette of named colors from a TOML file
+///
+/// Each string value becomes a named color. Nested tables are flattened into
+/// dot-separated names, so the following file defines `brand.primary`:
+///
+/// ```toml
+/// [brand]
+/// primary = "#1e88e5"
+/// ```
+///
+/// # Arguments
+///
+/// * `path` - Path to the TOML palette file
+///
+/// # Errors
+///
+/// Returns error if:
+/// - File doesn't exist or cannot be read
+/// - TOML parsing fails
+/// - A value is neither a table nor a valid CSS color string
+pub fn load_palette(path: impl AsRef<Path>) -> Result<Palette, RenderError> {
+    let path = path.as_ref();
+    info!(path = path.display().to_string(); "Loading palette");
+
+    if !path.exists() {
+        return Err(ConfigError::MissingFile(path.to_path_buf()).into());
+    }
+
+    let content = fs::read_to_string(path)?;
+    let table: toml::Table =
+        toml::from_str(&content).map_err(|err| ConfigError::Parse(err.to_string()))?;
+
+    let mut palette = Palette::new();
+    collect_palette_colors(&mut palette, "", &table)?;
+    debug!(colors_len = palette.len(); "Palette loaded");
+
+    Ok(palette)
+}
+
+/// Recursively insert the colors of `table` into `palette`, prefixing each
+/// name with `prefix`.
+fn collect_palette_colors(
+    palette: &mut Palette,
+    prefix: &str,
+    table: &toml::Table,
+) -> Result<(), ConfigError> {
+    for (key, value) in table {
+        let name = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{prefix}.{key}")
+        };
+
+        match value {
+            toml::Value::String(color_str) => {
+                let color = Color::new(color_str).map_err(|err| {
+                    ConfigError::Validation(format!("palette color `{name}`: {err}"))
+                })?;
+                palette.insert(name, color);
+            }
+            toml::Value::Table(nested) => collect_palette_colors(palette, &name, nested)?,
+            _ => {
+                return Err(ConfigError::Validation(format!(
+                    "palette entry `{name}` must be a color string or a table"
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use orrery::{color::Theme, draw::Density};
+
+    use super::*;
+
+    #[test]
+    fn test_load_config_reads_style() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(
+            &path,
+            "[style]\ntheme = \"dark\"\nstroke_color = \"orange\"\n",
+        )
+        .unwrap();
+
+        let theme = load_config_file(&path).unwrap().style().theme();
+
+        assert_eq!(theme.stroke(), Color::new("orange").unwrap());
+        assert_eq!(theme.background(), Theme::dark().background());
+        assert_eq!(theme.text(), Theme::dark().text());
+    }
+
+    #[test]
+    fn test_load_config_ignores_unknown_keys() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(&path, "[layout]\ncompact = true\npadingg = 4.0\n").unwrap();
+
+        let config = load_config_file(&path).unwrap();
+
+        assert!(config.layout().compact());
+        assert_eq!(config.layout().padding(), None);
+    }
+
+    #[test]
+    fn test_load_config_reads_dumped_config() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        let dumped = AppConfig::default().to_toml_string();
+        fs::write(&path, &dumped).unwrap();
+
+        let config = load_config_file(&path).unwrap();
+
+        assert_eq!(config.to_toml_string(), dumped);
+        assert_eq!(config.layout().padding(), Some(35.0));
+    }
+
+    #[test]
+    fn test_load_config_rejects_invalid_style_color() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(&path, "[style]\nfill_color = \"not-a-color\"\n").unwrap();
+
+        assert!(load_config_file(&path).is_err());
+    }
+
+    #[test]
+    fn test_load_config_reads_density() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(&path, "[layout]\ncompact = true\ndensity = \"spacious\"\n").unwrap();
+
+        let config = load_config_file(&path).unwrap();
+        assert_eq!(config.layout().density(), Density::Spacious);
+
+        fs::write(&path, "[layout]\ndensity = \"roomy\"\n").unwrap();
+        assert!(load_config_file(&path).is_err());
+    }
+
+    #[test]
+    fn test_load_palette_flattens_tables() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("brand.toml");
+        fs::write(
+            &path,
+            r##"
+            accent = "orange"
+
+            [brand]
+            primary = "#1e88e5"
+
+            [brand.dark]
+            bg = "#101010"
+            "##,
+        )
+        .unwrap();
+
+        let palette = load_palette(&path).unwrap();
+
+        assert_eq!(palette.len(), 3);
+        assert_eq!(palette.get("accent"), Color::new("orange").ok());
+        assert_eq!(palette.get("brand.primary"), Color::new("#1e88e5").ok());
+        assert_eq!(palette.get("brand.dark.bg"), Color::new("#101010").ok());
+    }
+
+    #[test]
+    fn test_load_palette_rejects_invalid_color() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("bad.toml");
+        fs::write(&path, "[brand]\nprimary = \"not-a-color\"\n").unwrap();
+
+        let err = load_palette(&path).unwrap_err();
+        assert!(err.to_string().contains("brand.primary"));
+    }
+
+    #[test]
+    fn test_load_palette_missing_file() {
+        let dir = TempDir::new().unwrap();
+        assert!(load_palette(dir.path().join("missing.toml")).is_err());
+    }
+}
@@ -7,6 +7,8 @@
 //! The [`Error::reportables()`] method converts an error into individually
 //! renderable miette diagnostics. For parse errors that contain multiple
 //! diagnostics, each one becomes a separate reportable item.
+//! [`Error::to_json_lines()`] splits an error the same way but serializes
+//! each diagnostic as JSON for machine consumption.
 //!
 //! # Multi-File Support
 //!
@@ -31,7 +33,7 @@ use miette::{
 use orrery::RenderError;
 use orrery_parser::{
     Span,
-    error::{Diagnostic, ParseError},
+    error::{Diagnostic, ParseError, Severity},
     source_map::SourceMap,
 };
 
@@ -86,6 +88,27 @@ impl<'a> Error<'a> {
             }
         }
     }
+
+    /// Serialize this error as one JSON object per diagnostic.
+    ///
+    /// Splits the error like [`reportables()`](Self::reportables); see
+    /// [`Diagnostic::to_json()`] for the shape of each object. A render error
+    /// becomes a single error without a location.
+    pub fn to_json_lines(&self) -> Vec<String> {
+        match self {
+            Error::Parse(parse_err) => {
+                let source_map = parse_err.source_map();
+                parse_err
+                    .diagnostics()
+                    .iter()
+                    .map(|d| d.to_json(source_map))
+                    .collect()
+            }
+            Error::Render(render_err) => {
+                vec![Diagnostic::error(render_err.to_string()).to_json(&SourceMap::new())]
+            }
+        }
+    }
 }
 
 /// Newtype wrapper around [`SourceMap`] that implements [`miette::SourceCode`].
@@ -199,6 +222,13 @@ impl MietteDiagnostic for DiagnosticAdapter<'_> {
             .map(|c| Box::new(c) as Box<dyn fmt::Display>)
     }
 
+    fn severity(&self) -> Option<miette::Severity> {
+        Some(match self.diag.severity() {
+            Severity::Error => miette::Severity::Error,
+            Severity::Warning => miette::Severity::Warning,
+        })
+    }
+
     fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
         self.diag
             .help()
@@ -296,6 +326,7 @@ impl MietteDiagnostic for RenderErrorAdapter<'_> {
             RenderError::Graph(_) => "orrery::graph",
             RenderError::Layout(_) => "orrery::layout",
             RenderError::Export(_) => "orrery::export",
+            RenderError::Input(_) => "orrery::input",
         };
         Some(Box::new(code))
     }
@@ -370,6 +401,35 @@ mod tests {
         assert_eq!(reportables[0].to_string(), "Graph error: graph error");
     }
 
+    #[test]
+    fn test_to_json_lines_one_per_diagnostic() {
+        let source = "source code that is long enough for spans";
+        let sm = single_file_source_map("test.orr", source);
+        let diags = vec![
+            Diagnostic::error("first error").with_label(Span::new(0..5), "first"),
+            Diagnostic::warning("second warning").with_label(Span::new(10..15), "second"),
+        ];
+        let err = Error::Parse(ParseError::new(diags, sm));
+
+        let lines = err.to_json_lines();
+
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains(r#""message":"first error""#));
+        assert!(lines[1].contains(r#""severity":"warning""#));
+        assert!(lines.iter().all(|line| !line.contains('\n')));
+    }
+
+    #[test]
+    fn test_to_json_lines_render_error() {
+        let err = Error::Render(RenderError::Graph("graph error".to_string()));
+
+        let lines = err.to_json_lines();
+
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].contains(r#""message":"Graph error: graph error""#));
+        assert!(lines[0].contains(r#""span":null"#));
+    }
+
     #[test]
     fn test_all_labels_returned() {
         let source = "some source code text";
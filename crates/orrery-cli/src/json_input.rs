@@ -0,0 +1,163 @@
+//! JSON diagram input for the CLI
+//!
+//! This module loads a serialized semantic [`Diagram`] from a JSON file, so
+//! that diagrams generated by other tools can be rendered without going
+//! through the `.orr` parser. Errors name the JSON path of the offending
+//! value.
+
+use std::{fs, path::Path};
+
+use thiserror::Error;
+
+use orrery::{RenderError, semantic::Diagram};
+
+/// JSON input errors for CLI
+#[derive(Debug, Error)]
+pub enum JsonInputError {
+    /// The input file could not be read.
+    #[error("Failed to read diagram JSON {path}: {message}")]
+    Read { path: String, message: String },
+
+    /// The JSON does not deserialize to a diagram.
+    #[error("Invalid diagram JSON{}: {message}", at_path(path))]
+    Parse { path: String, message: String },
+
+    /// The diagram failed the structural checks.
+    #[error("Invalid diagram: {0}")]
+    Validation(String),
+}
+
+/// Converts a `JsonInputError` into a [`RenderError::Input`].
+impl From<JsonInputError> for RenderError {
+    fn from(err: JsonInputError) -> Self {
+        RenderError::Input(Box::new(err))
+    }
+}
+
+/// Formats the ` at `path`` suffix of a parse error, or nothing at the root.
+fn at_path(path: &str) -> String {
+    if path.is_empty() {
+        String::new()
+    } else {
+        format!(" at `{path}`")
+    }
+}
+
+/// Load a semantic diagram from the JSON file at `path`
+///
+/// The diagram is validated with [`Diagram::validate`] before it is
+/// returned, so it is safe to hand to layout and rendering.
+///
+/// # Errors
+///
+/// Returns error if:
+/// - The file cannot be read
+/// - The JSON does not describe a diagram
+/// - The diagram fails validation
+pub fn load_diagram(path: &Path) -> Result<Diagram, JsonInputError> {
+    let content = fs::read_to_string(path).map_err(|err| JsonInputError::Read {
+        path: path.display().to_string(),
+        message: err.to_string(),
+    })?;
+    parse_diagram(&content)
+}
+
+/// Parse and validate a semantic diagram from JSON text.
+fn parse_diagram(content: &str) -> Result<Diagram, JsonInputError> {
+    let mut deserializer = serde_json::Deserializer::from_str(content);
+    let diagram: Diagram = serde_path_to_error::deserialize(&mut deserializer).map_err(|err| {
+        JsonInputError::Parse {
+            // The root path displays as `.`; report it as no path.
+            path: match err.path().iter().next() {
+                Some(_) => err.path().to_string(),
+                None => String::new(),
+            },
+            message: err.inner().to_string(),
+        }
+    })?;
+    deserializer.end().map_err(|err| JsonInputError::Parse {
+        path: String::new(),
+        message: err.to_string(),
+    })?;
+    diagram.validate().map_err(JsonInputError::Validation)?;
+    Ok(diagram)
+}
+
+#[cfg(test)]
+mod tests {
+    use orrery::semantic::{DiagramBuilder, DiagramKind};
+
+    use super::*;
+
+    /// Helper: pretty JSON of a component diagram with a single relation.
+    fn diagram_json() -> String {
+        let diagram = DiagramBuilder::new(DiagramKind::Component)
+            .component("a")
+            .component("b")
+            .relation("a", "b")
+            .build()
+            .unwrap();
+        serde_json::to_string_pretty(&diagram).unwrap()
+    }
+
+    #[test]
+    fn test_parse_diagram_round_trips() {
+        let diagram = parse_diagram(&diagram_json()).unwrap();
+
+        assert_eq!(diagram.kind(), DiagramKind::Component);
+        assert_eq!(diagram.scope().elements().len(), 3);
+    }
+
+    #[test]
+    fn test_parse_error_reports_json_path() {
+        let json = diagram_json().replacen("\"source\": \"a\"", "\"source\": 5", 1);
+
+        let err = parse_diagram(&json).unwrap_err();
+
+        let message = err.to_string();
+        assert!(
+            message.starts_with(
+                "Invalid diagram JSON at `scope.elements[2].relation.source`: invalid type"
+            ),
+            "{message}"
+        );
+    }
+
+    #[test]
+    fn test_trailing_characters_are_rejected() {
+        let json = format!("{} x", diagram_json());
+
+        let err = parse_diagram(&json).unwrap_err();
+
+        assert!(
+            err.to_string()
+                .starts_with("Invalid diagram JSON: trailing characters"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_missing_field_reports_enclosing_object() {
+        let json = diagram_json().replacen("\"kind\": \"component\",", "", 1);
+
+        let err = parse_diagram(&json).unwrap_err();
+
+        assert!(
+            err.to_string()
+                .starts_with("Invalid diagram JSON: missing field `kind`"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_validation_error_reports_path() {
+        let json = diagram_json().replacen("\"target\": \"b\"", "\"target\": \"missing\"", 1);
+
+        let err = parse_diagram(&json).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Invalid diagram: scope.elements[2].relation.target: component `missing` not found"
+        );
+    }
+}
@@ -1,29 +1,43 @@
 //! CLI logic for the Orrery diagram tool.
 //!
 //! Wires together configuration loading, the [`DiagramBuilder`] pipeline,
-//! and file I/O to turn a `.orr` source file into an SVG on disk.
+//! and file I/O to turn a `.orr` source file (or a JSON-serialized semantic
+//! diagram) into an SVG on disk, or to lint it with the `lint` subcommand.
 
 mod args;
 mod config;
 mod error;
+mod json_input;
 mod source_provider;
 
-pub use args::Args;
+pub use args::{Args, Command, ErrorFormat, InputFormat};
 pub use error::Error;
 
-use std::{fs, path::Path};
+use std::{fs, io, path::Path};
 
 use bumpalo::Bump;
-use log::info;
+use log::{info, warn};
 
-use orrery::DiagramBuilder;
+use orrery::{DiagramBuilder, ParseError, RenderError, config::AppConfig, semantic::Diagram};
+use orrery_parser::{
+    error::{Diagnostic, Severity},
+    source_map::SourceMap,
+};
 
 use source_provider::FsSourceProvider;
 
 /// Runs the Orrery CLI application.
 ///
-/// Loads configuration, parses the input `.orr` file, renders the
-/// resulting diagram to SVG, and writes it to the output path.
+/// Loads configuration (and the palette, if one is given), parses the input
+/// `.orr` file, renders the resulting diagram to SVG, and writes it to the
+/// output path. With `--input-format json`, the input is read as a serialized
+/// semantic diagram instead and rendered without parsing. When requested, diagram statistics are also written as JSON.
+/// With the `lint` subcommand, the input is linted instead of rendered, and
+/// with `--dump-config` the effective configuration is printed instead.
+///
+/// Warnings are printed without failing the run, unless `--deny-warnings` is
+/// given, in which case they are returned as errors. With `--error-format
+/// json`, warnings are printed as JSON lines on stderr instead.
 ///
 /// # Arguments
 ///
@@ -33,23 +47,65 @@ use source_provider::FsSourceProvider;
 ///
 /// # Errors
 ///
-/// Returns [`Error::Parse`] for syntax/validation errors with rich
-/// diagnostics, or [`Error::Render`] for I/O, layout, or export errors.
+/// Returns [`Error::Parse`] for syntax/validation errors (and for warnings
+/// with `--deny-warnings`) with rich diagnostics, or [`Error::Render`] for
+/// I/O, layout, or export errors.
 pub fn run<'a>(args: &Args, arena: &'a Bump) -> Result<(), Error<'a>> {
+    // Load configuration
+    let mut app_config = config::load_config(args.config.as_ref())?;
+    if let Some(palette_path) = &args.palette {
+        app_config = app_config.with_palette(config::load_palette(palette_path)?);
+    }
+
+    if args.dump_config {
+        print!("{}", app_config.to_toml_string());
+        return Ok(());
+    }
+
+    match &args.command {
+        Some(Command::Lint { input }) => lint(app_config, args, input, arena),
+        None => {
+            let input = args
+                .input
+                .as_deref()
+                .ok_or_else(|| io::Error::other("missing input file"))?;
+            render(app_config, args, input, arena)
+        }
+    }
+}
+
+/// Parses (or, for JSON input, deserializes) `input` and renders it to the
+/// output path given in `args`.
+fn render<'a>(
+    app_config: AppConfig,
+    args: &Args,
+    input: &str,
+    arena: &'a Bump,
+) -> Result<(), Error<'a>> {
     info!(
-        input_path = args.input,
+        input_path = input,
         output_path = args.output;
         "Processing diagram"
     );
 
-    // Load configuration
-    let app_config = config::load_config(args.config.as_ref())?;
-
     // Process diagram using DiagramBuilder API
-    let root_path = Path::new(&args.input);
+    let root_path = Path::new(input);
     let provider = FsSourceProvider::new();
     let builder = DiagramBuilder::new(app_config, &provider);
-    let diagram = builder.parse(arena, root_path)?;
+    let diagram = match args.input_format {
+        InputFormat::Orr => {
+            let (diagram, warnings, source_map) =
+                builder.parse_with_warnings(arena, root_path)?.into_parts();
+            report_warnings(warnings, source_map, args)?;
+            diagram
+        }
+        InputFormat::Json => json_input::load_diagram(root_path).map_err(RenderError::from)?,
+    };
+
+    if let Some(stats_path) = &args.stats {
+        write_stats(&diagram, stats_path)?;
+    }
+
     let svg = builder.render_svg(&diagram)?;
 
     // Write output file
@@ -59,3 +115,89 @@ pub fn run<'a>(args: &Args, arena: &'a Bump) -> Result<(), Error<'a>> {
 
     Ok(())
 }
+
+/// Lints `input`, failing when a rule configured as `error` fires.
+///
+/// Findings of rules configured as `warn` are reported as warnings and do
+/// not fail the run, unless `--deny-warnings` is given.
+fn lint<'a>(
+    app_config: AppConfig,
+    args: &Args,
+    input: &str,
+    arena: &'a Bump,
+) -> Result<(), Error<'a>> {
+    info!(input_path = input; "Linting diagram");
+
+    let provider = FsSourceProvider::new();
+    let builder = DiagramBuilder::new(app_config, &provider);
+    let report = builder.lint(arena, Path::new(input))?;
+
+    let has_errors = report.has_errors();
+    let (diagnostics, source_map) = report.into_parts();
+    if diagnostics.is_empty() {
+        info!("No lint findings");
+        return Ok(());
+    }
+    if has_errors {
+        return Err(Error::Parse(ParseError::new(diagnostics, source_map)));
+    }
+
+    report_warnings(diagnostics, source_map, args)
+}
+
+/// Prints `warnings` in the format selected by `--error-format`.
+///
+/// With `--deny-warnings`, the warnings are instead promoted to errors and
+/// returned.
+fn report_warnings<'a>(
+    warnings: Vec<Diagnostic>,
+    source_map: SourceMap<'a>,
+    args: &Args,
+) -> Result<(), Error<'a>> {
+    if warnings.is_empty() {
+        return Ok(());
+    }
+    if args.deny_warnings {
+        let errors = warnings
+            .into_iter()
+            .map(|warning| warning.with_severity(Severity::Error))
+            .collect();
+        return Err(Error::Parse(ParseError::new(errors, source_map)));
+    }
+
+    let err = Error::Parse(ParseError::new(warnings, source_map));
+    if args.error_format == ErrorFormat::Json {
+        for line in err.to_json_lines() {
+            eprintln!("{line}");
+        }
+        return Ok(());
+    }
+
+    let reporter = miette::GraphicalReportHandler::new();
+    for reportable in err.reportables() {
+        let mut writer = String::new();
+        reporter
+            .render_report(&mut writer, &*reportable)
+            .expect("Writing to String buffer is infallible");
+
+        warn!("{writer}");
+    }
+
+    Ok(())
+}
+
+/// Writes the statistics of `diagram` as JSON to `path`, or to stdout when
+/// `path` is `-`.
+fn write_stats<'a>(diagram: &Diagram, path: &str) -> Result<(), Error<'a>> {
+    let json = serde_json::to_string_pretty(&diagram.stats())
+        .map_err(|err| RenderError::Export(Box::new(err)))?;
+
+    if path == "-" {
+        println!("{json}");
+    } else {
+        fs::write(path, json)?;
+        info!(stats_file = path; "Diagram statistics written");
+    }
+
+    Ok(())
+}
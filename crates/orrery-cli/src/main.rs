@@ -6,7 +6,7 @@ use bumpalo::Bump;
 use clap::Parser;
 use log::{LevelFilter, debug, error, info};
 
-use orrery_cli::Args;
+use orrery_cli::{Args, ErrorFormat};
 
 fn main() {
     // Install miette's pretty panic hook early for better panic reports
@@ -36,6 +36,13 @@ fn main() {
 
     // Run the application
     if let Err(err) = orrery_cli::run(&args, &arena) {
+        if args.error_format == ErrorFormat::Json {
+            for line in err.to_json_lines() {
+                eprintln!("{line}");
+            }
+            process::exit(1);
+        }
+
         let reporter = miette::GraphicalReportHandler::new();
 
         // Render each diagnostic independently
@@ -8,7 +8,8 @@ use std::{fs, path::PathBuf};
 use bumpalo::Bump;
 use tempfile::tempdir;
 
-use orrery_cli::Args;
+use orrery::semantic::{DiagramBuilder, DiagramKind};
+use orrery_cli::{Args, Command, Error, ErrorFormat, InputFormat};
 
 /// Collects all `.orr` files from a directory.
 fn collect_orr_files(dir: PathBuf) -> Vec<PathBuf> {
@@ -64,9 +65,16 @@ fn e2e_smoke_test_valid_examples() {
         let output_path = temp_dir.path().join(output_filename);
 
         let args = Args {
-            input: example_path.to_string_lossy().to_string(),
+            command: None,
+            input: Some(example_path.to_string_lossy().to_string()),
             output: output_path.to_string_lossy().to_string(),
             config: None,
+            palette: None,
+            input_format: InputFormat::Orr,
+            stats: None,
+            dump_config: false,
+            deny_warnings: false,
+            error_format: ErrorFormat::Human,
             log_level: "off".to_string(),
         };
 
@@ -120,9 +128,16 @@ fn e2e_smoke_test_error_examples() {
         let output_path = temp_dir.path().join(output_filename);
 
         let args = Args {
-            input: example_path.to_string_lossy().to_string(),
+            command: None,
+            input: Some(example_path.to_string_lossy().to_string()),
             output: output_path.to_string_lossy().to_string(),
             config: None,
+            palette: None,
+            input_format: InputFormat::Orr,
+            stats: None,
+            dump_config: false,
+            deny_warnings: false,
+            error_format: ErrorFormat::Human,
             log_level: "off".to_string(),
         };
 
@@ -148,3 +163,154 @@ fn e2e_smoke_test_error_examples() {
         error_examples.len()
     );
 }
+
+/// Runs `orrery lint` on a diagram with an orphan component, using a config
+/// file that sets the `orphan_components` rule to `level`.
+fn lint_orphan_with_level(level: &str, deny_warnings: bool) -> Result<(), String> {
+    let temp_dir = tempdir().expect("Failed to create temp directory");
+    let input_path = temp_dir.path().join("orphan.orr");
+    fs::write(
+        &input_path,
+        "diagram component;\na: Rectangle;\nb: Rectangle;\nidle: Rectangle;\na -> b;\n",
+    )
+    .expect("Failed to write input");
+    let config_path = temp_dir.path().join("config.toml");
+    fs::write(
+        &config_path,
+        format!("[lint]\norphan_components = \"{level}\"\n"),
+    )
+    .expect("Failed to write config");
+
+    let args = Args {
+        command: Some(Command::Lint {
+            input: input_path.to_string_lossy().to_string(),
+        }),
+        input: None,
+        output: temp_dir
+            .path()
+            .join("out.svg")
+            .to_string_lossy()
+            .to_string(),
+        config: Some(config_path.to_string_lossy().to_string()),
+        palette: None,
+        input_format: InputFormat::Orr,
+        stats: None,
+        dump_config: false,
+        deny_warnings,
+        error_format: ErrorFormat::Human,
+        log_level: "off".to_string(),
+    };
+
+    run_for_message(&args)
+}
+
+/// Runs the CLI, mapping a parse failure to the message of its first
+/// diagnostic.
+fn run_for_message(args: &Args) -> Result<(), String> {
+    let arena = Bump::new();
+    orrery_cli::run(args, &arena).map_err(|err| match err {
+        Error::Parse(parse_err) => parse_err.diagnostics()[0].message().to_string(),
+        Error::Render(render_err) => panic!("Unexpected render error: {render_err}"),
+    })
+}
+
+#[test]
+fn e2e_lint_rule_levels() {
+    let err =
+        lint_orphan_with_level("error", false).expect_err("`error` level should fail the lint");
+    assert!(err.contains("`idle`"), "Unexpected lint finding: {err}");
+
+    assert!(lint_orphan_with_level("warn", false).is_ok());
+    assert!(lint_orphan_with_level("off", false).is_ok());
+
+    let err = lint_orphan_with_level("warn", true)
+        .expect_err("`--deny-warnings` should fail on `warn` findings");
+    assert!(err.contains("`idle`"), "Unexpected lint finding: {err}");
+    assert!(lint_orphan_with_level("off", true).is_ok());
+}
+
+#[test]
+fn e2e_deny_warnings() {
+    let temp_dir = tempdir().expect("Failed to create temp directory");
+    let input_path = temp_dir.path().join("warning.orr");
+    fs::write(
+        &input_path,
+        "diagram sequence;\na: Rectangle;\nb: Rectangle;\na -> b: \"   \";\n",
+    )
+    .expect("Failed to write input");
+
+    let args = |deny_warnings| Args {
+        command: None,
+        input: Some(input_path.to_string_lossy().to_string()),
+        output: temp_dir
+            .path()
+            .join("out.svg")
+            .to_string_lossy()
+            .to_string(),
+        config: None,
+        palette: None,
+        input_format: InputFormat::Orr,
+        stats: None,
+        dump_config: false,
+        deny_warnings,
+        error_format: ErrorFormat::Human,
+        log_level: "off".to_string(),
+    };
+
+    assert!(run_for_message(&args(false)).is_ok());
+
+    let err = run_for_message(&args(true)).expect_err("`--deny-warnings` should fail the render");
+    assert!(
+        err.contains("relation label contains only whitespace"),
+        "Unexpected error: {err}"
+    );
+}
+
+#[test]
+fn e2e_json_input() {
+    let temp_dir = tempdir().expect("Failed to create temp directory");
+    let input_path = temp_dir.path().join("diagram.json");
+    let output_path = temp_dir.path().join("out.svg");
+
+    let diagram = DiagramBuilder::new(DiagramKind::Sequence)
+        .component("client")
+        .component("server")
+        .relation("client", "server")
+        .build()
+        .expect("Failed to build diagram");
+    let json = serde_json::to_string(&diagram).expect("Failed to serialize diagram");
+
+    let args = || Args {
+        command: None,
+        input: Some(input_path.to_string_lossy().to_string()),
+        output: output_path.to_string_lossy().to_string(),
+        config: None,
+        palette: None,
+        input_format: InputFormat::Json,
+        stats: None,
+        dump_config: false,
+        deny_warnings: false,
+        error_format: ErrorFormat::Human,
+        log_level: "off".to_string(),
+    };
+
+    fs::write(&input_path, &json).expect("Failed to write input");
+    run_for_message(&args()).expect("JSON input should render");
+    let svg = fs::read_to_string(&output_path).expect("Failed to read output");
+    assert!(svg.contains("<svg"), "Output is not an SVG");
+
+    fs::write(
+        &input_path,
+        json.replace("\"target\":\"server\"", "\"target\":\"db\""),
+    )
+    .expect("Failed to write input");
+    let arena = Bump::new();
+    let Err(Error::Render(err)) = orrery_cli::run(&args(), &arena) else {
+        panic!("Invalid JSON input should fail with a render error");
+    };
+    assert!(
+        err.to_string()
+            .contains("relation.target: component `db` not found"),
+        "Unexpected error: {err}"
+    );
+}
@@ -1,14 +1,20 @@
 //! Color handling for Orrery diagrams.
 //!
-//! [`Color`] parses CSS color strings (hex, named, `rgb(...)`, etc.) and supports
-//! alpha manipulation, ID-safe serialization, and hashing.
+//! [`Color`] parses CSS color strings (hex, named, `rgb(...)`, `hsl(...)`, etc.)
+//! and supports alpha manipulation, HSL conversion, ID-safe serialization, and
+//! hashing. [`Palette`] holds
+//! user-defined named colors (e.g. `brand.primary`) loaded from an external file.
+//! [`Theme`] holds the default colors of the built-in element styles.
 
 use std::{
+    collections::HashMap,
     hash::{Hash, Hasher},
     str::FromStr,
 };
 
-use color::DynamicColor;
+use color::{AlphaColor, ColorSpaceTag, DynamicColor, Hsl, Srgb};
+
+use crate::draw::FontStyle;
 
 /// Wrapper around the `DynamicColor` type from the color crate
 /// This provides convenience methods for working with colors in the Orrery project
@@ -29,6 +35,10 @@ impl Color {
     /// Create a new `Color` from a string
     /// This will parse CSS color strings such as "#ff0000", "rgb(255, 0, 0)", "red", etc.
     ///
+    /// `hsl(h, s%, l%)` and `hsla(h, s%, l%, a)` are accepted as well. The hue
+    /// wraps around 360 degrees, and saturation and lightness outside
+    /// `0%..=100%` are clamped, as in [`from_hsl`](Self::from_hsl).
+    ///
     /// # Examples
     ///
     /// ```
@@ -36,14 +46,68 @@ impl Color {
     ///
     /// let red = Color::new("#ff0000").unwrap();
     /// let blue = Color::new("blue").unwrap();
+    /// let green = Color::new("hsla(120, 100%, 25%, 0.5)").unwrap();
+    /// assert_eq!(green.alpha(), 0.5);
     /// ```
     pub fn new(color_str: &str) -> Result<Self, String> {
+        if let Some(args) = hsl_arguments(color_str) {
+            return parse_hsl(args).map_err(|err| format!("invalid color `{color_str}`: {err}"));
+        }
         match DynamicColor::from_str(color_str) {
             Ok(color) => Ok(Self { color }),
             Err(err) => Err(format!("invalid color `{color_str}`: {err}")),
         }
     }
 
+    /// Creates an opaque color from hue, saturation, and lightness.
+    ///
+    /// # Arguments
+    ///
+    /// * `h` - Hue in degrees; wraps around, so `-30.0` and `330.0` are the same hue.
+    /// * `s` - Saturation in percent, clamped to `0.0..=100.0`.
+    /// * `l` - Lightness in percent, clamped to `0.0..=100.0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orrery_core::color::Color;
+    ///
+    /// let base = Color::new("#1e88e5").unwrap();
+    /// let (h, s, l) = base.to_hsl();
+    /// let complement = Color::from_hsl(h + 180.0, s, l);
+    /// assert_ne!(complement, base);
+    /// ```
+    pub fn from_hsl(h: f32, s: f32, l: f32) -> Self {
+        Self::from_hsla(h, s, l, 1.0)
+    }
+
+    /// Creates a color from hue, saturation, lightness, and alpha, wrapping
+    /// and clamping the components like [`from_hsl`](Self::from_hsl).
+    fn from_hsla(h: f32, s: f32, l: f32, alpha: f32) -> Self {
+        let hsl = AlphaColor::<Hsl>::new([
+            h.rem_euclid(360.0),
+            s.clamp(0.0, 100.0),
+            l.clamp(0.0, 100.0),
+            alpha.clamp(0.0, 1.0),
+        ]);
+        // Start from a parsed `rgb(...)` color so that the result keeps the
+        // legacy `rgb(...)` notation when displayed, like other parsed colors.
+        let mut color = DynamicColor::from_str("rgb(0, 0, 0)").expect("valid rgb color");
+        color.components = hsl.convert::<Srgb>().components;
+        Self { color }
+    }
+
+    /// Returns the hue, saturation, and lightness of this color.
+    ///
+    /// The hue is in degrees within `0.0..360.0`, and saturation and lightness
+    /// are in percent. Gray colors have no hue and report `0.0`. The alpha
+    /// component is not part of the result.
+    pub fn to_hsl(&self) -> (f32, f32, f32) {
+        let [h, s, l, _] = self.color.to_alpha_color::<Hsl>().components;
+        let h = if h.is_nan() { 0.0 } else { h.rem_euclid(360.0) };
+        (h, s, l)
+    }
+
     /// Returns a sanitized, ID-safe string representation of this color.
     ///
     /// Converts the color to a string suitable for use as an SVG ID attribute
@@ -111,6 +175,82 @@ impl Color {
     pub fn alpha(&self) -> f32 {
         self.color.components[3]
     }
+
+    /// Returns a CSS string that parses back to exactly this color.
+    ///
+    /// The [`Display`](std::fmt::Display) form writes `rgb(...)` components
+    /// scaled to `0..=255` in single precision, which may parse back to a
+    /// slightly different color. This scales them in double precision instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orrery_core::color::Color;
+    ///
+    /// let color = Color::from_hsl(210.0, 60.0, 82.0);
+    /// assert_eq!(Color::new(&color.to_css_string()).unwrap(), color);
+    /// ```
+    pub fn to_css_string(&self) -> String {
+        let flags = self.color.flags;
+        if !flags.named() || flags.color_name().is_some() || !flags.missing().is_empty() {
+            return self.to_string();
+        }
+        let [r, g, b, alpha] = self.color.convert(ColorSpaceTag::Srgb).components;
+        let [r, g, b] = [r, g, b].map(|component| f64::from(component) * 255.0);
+        if alpha < 1.0 {
+            format!("rgba({r}, {g}, {b}, {alpha})")
+        } else {
+            format!("rgb({r}, {g}, {b})")
+        }
+    }
+}
+
+/// Returns the argument list of an `hsl(...)` or `hsla(...)` color string, or
+/// `None` if the string uses another notation.
+fn hsl_arguments(color_str: &str) -> Option<&str> {
+    let color_str = color_str.trim();
+    let (name, rest) = color_str.split_once('(')?;
+    if !name.eq_ignore_ascii_case("hsl") && !name.eq_ignore_ascii_case("hsla") {
+        return None;
+    }
+    rest.strip_suffix(')')
+}
+
+/// Parses the arguments of an HSL color, in either the comma-separated or
+/// the space-separated (`h s l / a`) form.
+fn parse_hsl(args: &str) -> Result<Color, String> {
+    let values: Vec<&str> = args
+        .split([',', ' ', '/'])
+        .filter(|value| !value.is_empty())
+        .collect();
+    let [h, s, l, alpha @ ..] = values.as_slice() else {
+        return Err("expected hue, saturation and lightness".to_string());
+    };
+    let alpha = match alpha {
+        [] => 1.0,
+        [alpha] => match alpha.strip_suffix('%') {
+            Some(percent) => parse_hsl_number(percent)? / 100.0,
+            None => parse_hsl_number(alpha)?,
+        },
+        _ => return Err("too many components".to_string()),
+    };
+
+    let hue = h.strip_suffix("deg").unwrap_or(h);
+    Ok(Color::from_hsla(
+        parse_hsl_number(hue)?,
+        parse_hsl_number(s.strip_suffix('%').unwrap_or(s))?,
+        parse_hsl_number(l.strip_suffix('%').unwrap_or(l))?,
+        alpha,
+    ))
+}
+
+/// Parses one numeric HSL component.
+fn parse_hsl_number(value: &str) -> Result<f32, String> {
+    value
+        .parse::<f32>()
+        .ok()
+        .filter(|number| number.is_finite())
+        .ok_or_else(|| format!("`{value}` is not a number"))
 }
 
 impl Default for Color {
@@ -132,6 +272,340 @@ impl From<&Color> for svg::node::Value {
     }
 }
 
+/// Serializes the color as its CSS string.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Color {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Deserializes a color from a CSS string.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Color {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let color = String::deserialize(deserializer)?;
+        Self::new(&color).map_err(serde::de::Error::custom)
+    }
+}
+
+/// A set of named colors that diagrams can reference by name.
+///
+/// Palettes decouple brand colors from diagram sources: a diagram writes
+/// `fill_color="brand.primary"` and the palette supplies the actual value.
+/// Names are dot-separated paths, mirroring nested tables in a palette file.
+///
+/// # Examples
+///
+/// ```
+/// use orrery_core::color::{Color, Palette};
+///
+/// let mut palette = Palette::new();
+/// palette.insert("brand.primary", Color::new("#1e88e5").unwrap());
+///
+/// assert_eq!(palette.get("brand.primary"), Color::new("#1e88e5").ok());
+/// assert!(palette.get("brand.secondary").is_none());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Palette {
+    colors: HashMap<String, Color>,
+}
+
+impl Palette {
+    /// Creates an empty palette.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a named color, replacing any existing color with the same name.
+    pub fn insert(&mut self, name: impl Into<String>, color: Color) {
+        self.colors.insert(name.into(), color);
+    }
+
+    /// Returns the color registered under `name`, if any.
+    pub fn get(&self, name: &str) -> Option<Color> {
+        self.colors.get(name).copied()
+    }
+
+    /// Returns the number of named colors in the palette.
+    pub fn len(&self) -> usize {
+        self.colors.len()
+    }
+
+    /// Returns `true` if the palette defines no colors.
+    pub fn is_empty(&self) -> bool {
+        self.colors.is_empty()
+    }
+
+    /// Returns `true` if `value` is shaped like a palette reference.
+    ///
+    /// A palette reference is two or more identifier segments joined by dots,
+    /// such as `brand.primary`. CSS color syntax never matches this shape, so
+    /// it is safe to report such values as undefined palette colors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orrery_core::color::Palette;
+    ///
+    /// assert!(Palette::is_reference("brand.primary"));
+    /// assert!(!Palette::is_reference("red"));
+    /// assert!(!Palette::is_reference("rgba(0, 0, 0, 0.5)"));
+    /// ```
+    pub fn is_reference(value: &str) -> bool {
+        let mut segments = value.split('.');
+        let is_identifier = |segment: &str| {
+            let mut chars = segment.chars();
+            chars
+                .next()
+                .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+                && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        };
+        segments.next().is_some_and(is_identifier)
+            && value.contains('.')
+            && segments.all(is_identifier)
+    }
+}
+
+/// The default colors and font of the built-in element styles.
+///
+/// Built-in types take their fill, stroke, and text colors from the theme, so
+/// switching themes restyles every element that does not set its own colors.
+/// The theme may also override the font family and size of text; when unset,
+/// text keeps the [`TextDefinition`](crate::draw::TextDefinition) defaults.
+/// Relation labels can be given their own font and color, which replace the
+/// shared text settings for relation labels only.
+/// A minimum width and height can be set for boxed shapes (rectangles, ovals,
+/// and components) so that short labels do not produce tiny boxes.
+///
+/// # Examples
+///
+/// ```
+/// use orrery_core::color::{Color, Theme};
+///
+/// let theme = Theme::dark().with_stroke(Color::new("orange").unwrap());
+/// assert!(theme.background().is_some());
+/// assert_eq!(theme.stroke(), Color::new("orange").unwrap());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    background: Option<Color>,
+    fill: Color,
+    stroke: Color,
+    text: Color,
+    note_fill: Color,
+    label_background: Option<Color>,
+    font_family: Option<String>,
+    font_size: Option<u16>,
+    relation_font_family: Option<String>,
+    relation_font_size: Option<u16>,
+    relation_font_style: Option<FontStyle>,
+    relation_text: Option<Color>,
+    min_width: Option<f32>,
+    min_height: Option<f32>,
+}
+
+impl Theme {
+    /// Returns the light theme: dark lines and text on white shapes, with a
+    /// transparent canvas.
+    pub fn light() -> Self {
+        Self {
+            background: None,
+            fill: Color::new("white").expect("Invalid color"),
+            stroke: Color::default(),
+            text: Color::default(),
+            note_fill: Color::new("lightyellow").expect("Invalid color"),
+            label_background: Some(Color::new("rgba(255, 255, 255, 0.85)").expect("Invalid color")),
+            font_family: None,
+            font_size: None,
+            relation_font_family: None,
+            relation_font_size: None,
+            relation_font_style: None,
+            relation_text: None,
+            min_width: None,
+            min_height: None,
+        }
+    }
+
+    /// Returns the dark theme: light lines and text on dark shapes and canvas.
+    pub fn dark() -> Self {
+        Self {
+            background: Some(Color::new("#1e1e1e").expect("Invalid color")),
+            fill: Color::new("#2d2d30").expect("Invalid color"),
+            stroke: Color::new("#d4d4d4").expect("Invalid color"),
+            text: Color::new("#e8e8e8").expect("Invalid color"),
+            note_fill: Color::new("#4a4424").expect("Invalid color"),
+            label_background: Some(Color::new("rgba(30, 30, 30, 0.85)").expect("Invalid color")),
+            font_family: None,
+            font_size: None,
+            relation_font_family: None,
+            relation_font_size: None,
+            relation_font_style: None,
+            relation_text: None,
+            min_width: None,
+            min_height: None,
+        }
+    }
+
+    /// Sets the canvas background color.
+    pub fn with_background(mut self, color: Color) -> Self {
+        self.background = Some(color);
+        self
+    }
+
+    /// Sets the fill color of shapes and activation boxes.
+    pub fn with_fill(mut self, color: Color) -> Self {
+        self.fill = color;
+        self
+    }
+
+    /// Sets the color of outlines and lines.
+    pub fn with_stroke(mut self, color: Color) -> Self {
+        self.stroke = color;
+        self
+    }
+
+    /// Sets the text color.
+    pub fn with_text(mut self, color: Color) -> Self {
+        self.text = color;
+        self
+    }
+
+    /// Sets the background color behind relation labels, or `None` to draw
+    /// labels without a background.
+    pub fn with_label_background(mut self, color: Option<Color>) -> Self {
+        self.label_background = color;
+        self
+    }
+
+    /// Sets the font family of text.
+    pub fn with_font_family(mut self, family: impl Into<String>) -> Self {
+        self.font_family = Some(family.into());
+        self
+    }
+
+    /// Sets the font size of text, in points.
+    pub fn with_font_size(mut self, size: u16) -> Self {
+        self.font_size = Some(size);
+        self
+    }
+
+    /// Sets the font family of relation labels.
+    pub fn with_relation_font_family(mut self, family: impl Into<String>) -> Self {
+        self.relation_font_family = Some(family.into());
+        self
+    }
+
+    /// Sets the font size of relation labels, in points.
+    pub fn with_relation_font_size(mut self, size: u16) -> Self {
+        self.relation_font_size = Some(size);
+        self
+    }
+
+    /// Sets whether relation labels are drawn upright or in italics.
+    pub fn with_relation_font_style(mut self, style: FontStyle) -> Self {
+        self.relation_font_style = Some(style);
+        self
+    }
+
+    /// Sets the color of relation labels.
+    pub fn with_relation_text(mut self, color: Color) -> Self {
+        self.relation_text = Some(color);
+        self
+    }
+
+    /// Sets the minimum width of boxed shapes.
+    pub fn with_min_width(mut self, width: f32) -> Self {
+        self.min_width = Some(width);
+        self
+    }
+
+    /// Sets the minimum height of boxed shapes.
+    pub fn with_min_height(mut self, height: f32) -> Self {
+        self.min_height = Some(height);
+        self
+    }
+
+    /// Returns the canvas background color, or `None` for a transparent canvas.
+    pub fn background(&self) -> Option<Color> {
+        self.background
+    }
+
+    /// Returns the fill color of shapes and activation boxes.
+    pub fn fill(&self) -> Color {
+        self.fill
+    }
+
+    /// Returns the color of outlines and lines.
+    pub fn stroke(&self) -> Color {
+        self.stroke
+    }
+
+    /// Returns the text color.
+    pub fn text(&self) -> Color {
+        self.text
+    }
+
+    /// Returns the background color of notes.
+    pub fn note_fill(&self) -> Color {
+        self.note_fill
+    }
+
+    /// Returns the background color behind relation labels, or `None` when
+    /// labels have no background.
+    pub fn label_background(&self) -> Option<Color> {
+        self.label_background
+    }
+
+    /// Returns the font family of text, or `None` to keep the default.
+    pub fn font_family(&self) -> Option<&str> {
+        self.font_family.as_deref()
+    }
+
+    /// Returns the font size of text in points, or `None` to keep the default.
+    pub fn font_size(&self) -> Option<u16> {
+        self.font_size
+    }
+
+    /// Returns the font family of relation labels, or `None` to use the
+    /// font family of text.
+    pub fn relation_font_family(&self) -> Option<&str> {
+        self.relation_font_family.as_deref()
+    }
+
+    /// Returns the font size of relation labels in points, or `None` to use
+    /// the font size of text.
+    pub fn relation_font_size(&self) -> Option<u16> {
+        self.relation_font_size
+    }
+
+    /// Returns the font style of relation labels, or `None` to keep the default.
+    pub fn relation_font_style(&self) -> Option<FontStyle> {
+        self.relation_font_style
+    }
+
+    /// Returns the color of relation labels, or `None` to use the text color.
+    pub fn relation_text(&self) -> Option<Color> {
+        self.relation_text
+    }
+
+    /// Returns the minimum width of boxed shapes, or `None` for no minimum.
+    pub fn min_width(&self) -> Option<f32> {
+        self.min_width
+    }
+
+    /// Returns the minimum height of boxed shapes, or `None` for no minimum.
+    pub fn min_height(&self) -> Option<f32> {
+        self.min_height
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::light()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -145,6 +619,16 @@ mod tests {
         assert!(invalid.is_err());
     }
 
+    #[test]
+    fn test_color_parses_hex_with_alpha() {
+        let color = Color::new("#ff000080").unwrap();
+        assert!((color.alpha() - 128.0 / 255.0).abs() < 0.001);
+        assert_eq!(color.to_hsl(), Color::new("#ff0000").unwrap().to_hsl());
+
+        let short = Color::new("#f008").unwrap();
+        assert!((short.alpha() - 136.0 / 255.0).abs() < 0.001);
+    }
+
     #[test]
     fn test_color_default() {
         let color = Color::default();
@@ -192,4 +676,83 @@ mod tests {
         assert!(set.contains(&color2));
         assert!(!set.contains(&color3));
     }
+
+    #[test]
+    fn test_palette_insert_and_get() {
+        let mut palette = Palette::new();
+        assert!(palette.is_empty());
+
+        let primary = Color::new("#1e88e5").unwrap();
+        palette.insert("brand.primary", primary);
+        assert_eq!(palette.len(), 1);
+        assert_eq!(palette.get("brand.primary"), Some(primary));
+        assert_eq!(palette.get("brand.accent"), None);
+
+        // Re-inserting replaces the previous color.
+        let replacement = Color::new("red").unwrap();
+        palette.insert("brand.primary", replacement);
+        assert_eq!(palette.len(), 1);
+        assert_eq!(palette.get("brand.primary"), Some(replacement));
+    }
+
+    #[test]
+    fn test_palette_is_reference() {
+        assert!(Palette::is_reference("brand.primary"));
+        assert!(Palette::is_reference("brand.dark.bg_2"));
+        assert!(!Palette::is_reference("brand"));
+        assert!(!Palette::is_reference("brand."));
+        assert!(!Palette::is_reference(".primary"));
+        assert!(!Palette::is_reference("#1e88e5"));
+        assert!(!Palette::is_reference("rgba(255, 255, 255, 0.85)"));
+        assert!(!Palette::is_reference("1.5"));
+    }
+
+    #[test]
+    fn test_color_parses_hsl() {
+        let green = Color::new("hsl(120, 100%, 25%)").unwrap();
+        assert_eq!(green, Color::new("rgb(0, 127.5, 0)").unwrap());
+
+        let translucent = Color::new("HSLA(120deg, 100%, 25%, 50%)").unwrap();
+        assert_eq!(translucent, Color::new("rgba(0, 127.5, 0, 0.5)").unwrap());
+        assert_eq!(Color::new("hsl(120 100% 25% / 0.5)").unwrap(), translucent);
+
+        assert!(Color::new("hsl(120, 100%)").is_err());
+        assert!(Color::new("hsl(red, 100%, 25%)").is_err());
+    }
+
+    #[test]
+    fn test_color_hsl_wraps_hue_and_clamps() {
+        assert_eq!(
+            Color::new("hsl(480, 150%, -5%)").unwrap(),
+            Color::from_hsl(120.0, 100.0, 0.0)
+        );
+        assert_eq!(
+            Color::from_hsl(-30.0, 50.0, 50.0),
+            Color::from_hsl(330.0, 50.0, 50.0)
+        );
+    }
+
+    #[test]
+    fn test_color_hsl_round_trip() {
+        let (h, s, l) = Color::new("#1e88e5").unwrap().to_hsl();
+        let round_trip = Color::from_hsl(h, s, l).to_hsl();
+        assert!((round_trip.0 - h).abs() < 1e-3);
+        assert!((round_trip.1 - s).abs() < 1e-3);
+        assert!((round_trip.2 - l).abs() < 1e-3);
+
+        let (h, s, _) = Color::new("gray").unwrap().to_hsl();
+        assert_eq!((h, s), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_theme_overrides_keep_other_colors() {
+        let orange = Color::new("orange").unwrap();
+        let theme = Theme::dark().with_stroke(orange);
+
+        assert_eq!(theme.stroke(), orange);
+        assert_eq!(theme.fill(), Theme::dark().fill());
+        assert_eq!(theme.background(), Theme::dark().background());
+        assert_eq!(Theme::default(), Theme::light());
+        assert_eq!(Theme::light().background(), None);
+    }
 }
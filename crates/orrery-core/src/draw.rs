@@ -13,10 +13,15 @@ mod arrow;
 mod arrow_with_text;
 mod diagram;
 mod fragment;
+mod group;
+mod highlight;
+mod image;
 mod layer;
 mod lifeline;
+mod link;
 mod note;
 mod positioned;
+mod shadow;
 mod shape;
 mod shape_with_text;
 mod stroke;
@@ -24,22 +29,35 @@ mod text;
 mod text_positioning;
 
 pub use activation_box::{ActivationBox, ActivationBoxDefinition};
-pub use arrow::{Arrow, ArrowDefinition, ArrowDirection, ArrowDrawer, ArrowPath, ArrowStyle};
+pub use arrow::{
+    Arrow, ArrowDefinition, ArrowDirection, ArrowDrawer, ArrowPath, ArrowSegment, ArrowStyle,
+    Arrowhead, LabelPosition,
+};
 pub use arrow_with_text::{ArrowWithText, ArrowWithTextDrawer, PositionedArrowWithText};
-pub use diagram::DiagramDefinition;
+pub use diagram::{CanvasFit, Density, DiagramDefinition, EdgeRouting};
 pub use fragment::{Fragment, FragmentDefinition, FragmentSection};
+pub use group::{Group, GroupDefinition};
+pub use highlight::{Highlight, HighlightDefinition};
+pub use image::ImageDefinition;
 pub use layer::{LayeredOutput, RenderLayer};
 pub use lifeline::{Lifeline, LifelineDefinition};
+pub use link::LinkDefinition;
 pub use note::{Note, NoteDefinition};
 pub use positioned::PositionedDrawable;
+pub use shadow::ShadowDefinition;
+#[cfg(feature = "serde")]
+pub(crate) use shape::shape_definition_serde;
 pub use shape::{
     ActorDefinition, BoundaryDefinition, ComponentDefinition, ControlDefinition, EntityDefinition,
     InterfaceDefinition, OvalDefinition, RectangleDefinition, Shape, ShapeDefinition,
 };
 pub use shape_with_text::ShapeWithText;
 pub use stroke::{StrokeCap, StrokeDefinition, StrokeJoin, StrokeStyle};
-pub use text::{Text, TextDefinition};
-pub use text_positioning::TextPositioningStrategy;
+pub use text::{
+    FontStyle, FontTextMeasurer, HeuristicTextMeasurer, Text, TextDefinition, TextMeasurer,
+    with_text_measurer,
+};
+pub use text_positioning::{ContentAlign, HorizontalAlign, TextPositioningStrategy, VerticalAlign};
 
 use crate::geometry::{Point, Size};
 
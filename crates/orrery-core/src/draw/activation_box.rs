@@ -22,8 +22,11 @@ use std::rc::Rc;
 
 use svg::{self, node::element as svg_element};
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 use crate::{
-    color::Color,
+    color::{Color, Theme},
     draw::{Drawable, LayeredOutput, RenderLayer, StrokeDefinition},
     geometry::{Bounds, Point, Size},
 };
@@ -41,6 +44,7 @@ use crate::{
 /// - `fill_color`: white - Background color of the activation box
 /// - `stroke`: black color, 1.0 width, solid style - Border styling
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ActivationBoxDefinition {
     width: f32,
     nesting_offset: f32,
@@ -70,17 +74,17 @@ impl ActivationBoxDefinition {
     }
 
     /// Gets the activation box width
-    fn width(&self) -> f32 {
+    pub fn width(&self) -> f32 {
         self.width
     }
 
     /// Gets the nesting offset (used by layout system)
-    fn nesting_offset(&self) -> f32 {
+    pub fn nesting_offset(&self) -> f32 {
         self.nesting_offset
     }
 
     /// Gets the fill color
-    fn fill_color(&self) -> Color {
+    pub fn fill_color(&self) -> Color {
         self.fill_color
     }
 
@@ -95,17 +99,26 @@ impl ActivationBoxDefinition {
     }
 }
 
-impl Default for ActivationBoxDefinition {
-    fn default() -> Self {
+impl ActivationBoxDefinition {
+    /// Creates an activation box definition with the colors of `theme`.
+    pub fn from_theme(theme: &Theme) -> Self {
+        let mut stroke = StrokeDefinition::default();
+        stroke.set_color(theme.stroke());
         Self {
             width: 8.0,
             nesting_offset: 4.0,
-            fill_color: Color::new("white").expect("Invalid default fill color"),
-            stroke: Rc::new(StrokeDefinition::default()),
+            fill_color: theme.fill(),
+            stroke: Rc::new(stroke),
         }
     }
 }
 
+impl Default for ActivationBoxDefinition {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
 /// A drawable activation box for sequence diagrams.
 ///
 /// This is the main drawable component that represents periods of activity on a participant's
@@ -3,14 +3,17 @@
 //! This module provides types for defining and rendering arrows in diagrams,
 //! including stroke styling, path shapes, direction markers, and SVG output.
 
-use std::{collections::HashMap, fmt, iter, rc::Rc, str::FromStr};
+use std::{collections::BTreeMap, fmt, iter, mem, rc::Rc, str::FromStr};
 
 use itertools::Itertools;
 use svg::{self, node::element as svg_element};
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 use crate::{
-    color::Color,
-    draw::{StrokeDefinition, TextDefinition},
+    color::{Color, Theme},
+    draw::{LinkDefinition, StrokeDefinition, StrokeStyle, TextDefinition},
     geometry::{Point, Size},
 };
 
@@ -22,6 +25,8 @@ use crate::{
 /// - `Curved`: Creates smooth bezier curves between points
 /// - `Orthogonal`: Creates only horizontal and vertical line segments
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum ArrowStyle {
     Straight,
     #[default]
@@ -42,18 +47,213 @@ impl FromStr for ArrowStyle {
     }
 }
 
+impl fmt::Display for ArrowStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Straight => write!(f, "straight"),
+            Self::Curved => write!(f, "curved"),
+            Self::Orthogonal => write!(f, "orthogonal"),
+        }
+    }
+}
+
+/// The shape of the marker drawn at an end of an arrow.
+///
+/// # Variants
+///
+/// - `Triangle`: A filled triangle, the default arrowhead
+/// - `Open`: An unfilled chevron, the default for asynchronous messages
+/// - `Diamond`: A filled diamond, e.g. for composition
+/// - `Circle`: A filled circle
+/// - `None`: No marker
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Arrowhead {
+    Triangle,
+    Open,
+    Diamond,
+    Circle,
+    None,
+}
+
+impl Arrowhead {
+    fn to_string(self) -> &'static str {
+        match self {
+            Self::Triangle => "triangle",
+            Self::Open => "open",
+            Self::Diamond => "diamond",
+            Self::Circle => "circle",
+            Self::None => "none",
+        }
+    }
+}
+
+impl FromStr for Arrowhead {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "triangle" => Ok(Self::Triangle),
+            "open" => Ok(Self::Open),
+            "diamond" => Ok(Self::Diamond),
+            "circle" => Ok(Self::Circle),
+            "none" => Ok(Self::None),
+            _ => Err("Invalid arrowhead"),
+        }
+    }
+}
+
+impl fmt::Display for Arrowhead {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str((*self).to_string())
+    }
+}
+
+/// Where an arrow's label sits along its path.
+///
+/// # Variants
+///
+/// - `Start`: Near the source end
+/// - `Middle`: Halfway along the path, the default
+/// - `End`: Near the destination end
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum LabelPosition {
+    Start,
+    #[default]
+    Middle,
+    End,
+}
+
+impl FromStr for LabelPosition {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "start" => Ok(Self::Start),
+            "middle" => Ok(Self::Middle),
+            "end" => Ok(Self::End),
+            _ => Err("Invalid label position"),
+        }
+    }
+}
+
+impl fmt::Display for LabelPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Start => write!(f, "start"),
+            Self::Middle => write!(f, "middle"),
+            Self::End => write!(f, "end"),
+        }
+    }
+}
+
+/// A portion of an arrow's path drawn with its own stroke style.
+///
+/// Segments split the path by length, in order from source to destination.
+/// Each covers [`fraction`](Self::fraction) of the total length and replaces
+/// the [`StrokeStyle`] of the arrow's stroke for that portion; all other
+/// stroke properties are shared.
+///
+/// # Examples
+///
+/// ```
+/// # use orrery_core::draw::{ArrowSegment, StrokeStyle};
+/// let segment: ArrowSegment = "dashed:0.25".parse().unwrap();
+/// assert_eq!(*segment.style(), StrokeStyle::Dashed);
+/// assert_eq!(segment.fraction(), 0.25);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ArrowSegment {
+    style: StrokeStyle,
+    fraction: f32,
+}
+
+impl ArrowSegment {
+    /// Creates a segment covering `fraction` of the path length.
+    pub fn new(style: StrokeStyle, fraction: f32) -> Self {
+        Self { style, fraction }
+    }
+
+    /// Returns the stroke style of this segment.
+    pub fn style(&self) -> &StrokeStyle {
+        &self.style
+    }
+
+    /// Returns the share of the total path length covered by this segment.
+    pub fn fraction(&self) -> f32 {
+        self.fraction
+    }
+}
+
+impl FromStr for ArrowSegment {
+    type Err = String;
+
+    /// Parses a segment written as `style:fraction`, e.g. `dashed:0.5`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (style, fraction) = s
+            .rsplit_once(':')
+            .ok_or_else(|| format!("segment `{s}` must be written as `style:fraction`"))?;
+        let fraction: f32 = fraction
+            .trim()
+            .parse()
+            .map_err(|_| format!("invalid segment fraction `{}`", fraction.trim()))?;
+        if !(fraction > 0.0 && fraction <= 1.0) {
+            return Err(format!(
+                "segment fraction `{fraction}` must be greater than 0 and at most 1"
+            ));
+        }
+        let style = StrokeStyle::from_str(style.trim())?;
+        Ok(Self::new(style, fraction))
+    }
+}
+
 /// Defines the visual properties of an arrow.
 ///
 /// This struct encapsulates all the styling information needed to render
 /// an arrow, including stroke properties and path style.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ArrowDefinition {
     stroke: Rc<StrokeDefinition>,
     style: ArrowStyle,
     text: Rc<TextDefinition>,
+    segments: Vec<ArrowSegment>,
+    arrowhead: Option<Arrowhead>,
+    arrowhead_start: Option<Arrowhead>,
+    arrowhead_end: Option<Arrowhead>,
+    label_position: LabelPosition,
+    label_offset: f32,
+    #[cfg_attr(feature = "serde", serde(default = "default_curvature"))]
+    curvature: f32,
+    #[cfg_attr(feature = "serde", serde(default = "default_min_length"))]
+    min_length: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    tooltip: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    link: Option<LinkDefinition>,
+}
+
+#[cfg(feature = "serde")]
+fn default_curvature() -> f32 {
+    ArrowDefinition::DEFAULT_CURVATURE
+}
+
+#[cfg(feature = "serde")]
+fn default_min_length() -> usize {
+    ArrowDefinition::DEFAULT_MIN_LENGTH
 }
 
 impl ArrowDefinition {
+    /// The curvature of arrows that do not set one.
+    pub const DEFAULT_CURVATURE: f32 = 0.5;
+
+    /// The minimum length, in ranks, of arrows that do not set one.
+    pub const DEFAULT_MIN_LENGTH: usize = 1;
+
     /// Creates a new ArrowDefinition with the given stroke
     /// Style defaults to Straight and can be changed with set_style()
     pub fn new(stroke: Rc<StrokeDefinition>) -> Self {
@@ -61,6 +261,16 @@ impl ArrowDefinition {
             stroke,
             style: ArrowStyle::default(),
             text: Rc::new(TextDefinition::default()),
+            segments: Vec::new(),
+            arrowhead: None,
+            arrowhead_start: None,
+            arrowhead_end: None,
+            label_position: LabelPosition::default(),
+            label_offset: 0.0,
+            curvature: Self::DEFAULT_CURVATURE,
+            min_length: Self::DEFAULT_MIN_LENGTH,
+            tooltip: None,
+            link: None,
         }
     }
 
@@ -93,43 +303,221 @@ impl ArrowDefinition {
     pub fn set_stroke(&mut self, stroke: Rc<StrokeDefinition>) {
         self.stroke = stroke;
     }
+
+    /// Gets the styled segments of the arrow stroke.
+    ///
+    /// Empty when the whole arrow uses the stroke's own style.
+    pub fn segments(&self) -> &[ArrowSegment] {
+        &self.segments
+    }
+
+    /// Sets the styled segments of the arrow stroke.
+    ///
+    /// Segment fractions are expected to sum to 1; the last segment always
+    /// extends to the destination.
+    pub fn set_segments(&mut self, segments: Vec<ArrowSegment>) {
+        self.segments = segments;
+    }
+
+    /// Gets the arrowhead drawn at the ends marked by the arrow's direction.
+    ///
+    /// `None` keeps the direction's own arrowhead.
+    pub fn arrowhead(&self) -> Option<Arrowhead> {
+        self.arrowhead
+    }
+
+    /// Sets the arrowhead drawn at the ends marked by the arrow's direction.
+    pub fn set_arrowhead(&mut self, arrowhead: Arrowhead) {
+        self.arrowhead = Some(arrowhead);
+    }
+
+    /// Gets the arrowhead drawn at the source end, regardless of direction.
+    pub fn arrowhead_start(&self) -> Option<Arrowhead> {
+        self.arrowhead_start
+    }
+
+    /// Sets the arrowhead drawn at the source end, regardless of direction.
+    pub fn set_arrowhead_start(&mut self, arrowhead: Arrowhead) {
+        self.arrowhead_start = Some(arrowhead);
+    }
+
+    /// Gets the arrowhead drawn at the destination end, regardless of
+    /// direction.
+    pub fn arrowhead_end(&self) -> Option<Arrowhead> {
+        self.arrowhead_end
+    }
+
+    /// Sets the arrowhead drawn at the destination end, regardless of
+    /// direction.
+    pub fn set_arrowhead_end(&mut self, arrowhead: Arrowhead) {
+        self.arrowhead_end = Some(arrowhead);
+    }
+
+    /// Gets where the label sits along the arrow's path.
+    pub fn label_position(&self) -> LabelPosition {
+        self.label_position
+    }
+
+    /// Sets where the label sits along the arrow's path.
+    pub fn set_label_position(&mut self, label_position: LabelPosition) {
+        self.label_position = label_position;
+    }
+
+    /// Gets the distance the label is moved away from the path.
+    ///
+    /// The label moves perpendicular to the path's direction at the label's
+    /// position; positive values move it to the left of the direction of
+    /// travel, which is above a left-to-right arrow.
+    pub fn label_offset(&self) -> f32 {
+        self.label_offset
+    }
+
+    /// Sets the distance the label is moved away from the path.
+    pub fn set_label_offset(&mut self, label_offset: f32) {
+        self.label_offset = label_offset;
+    }
+
+    /// Gets how far curved parallel arrows bow out, from `0` (straight) to
+    /// `1` (strongly curved).
+    pub fn curvature(&self) -> f32 {
+        self.curvature
+    }
+
+    /// Sets how far curved parallel arrows bow out, clamped to `0..=1`.
+    pub fn set_curvature(&mut self, curvature: f32) {
+        self.curvature = curvature.clamp(0.0, 1.0);
+    }
+
+    /// Gets the minimum number of ranks (layers) that layered layout engines
+    /// keep between the endpoints of the arrow.
+    pub fn min_length(&self) -> usize {
+        self.min_length
+    }
+
+    /// Sets the minimum number of ranks between the endpoints, at least `1`.
+    pub fn set_min_length(&mut self, min_length: usize) {
+        self.min_length = min_length.max(1);
+    }
+
+    /// Gets the hover tooltip of the arrow.
+    pub fn tooltip(&self) -> Option<&str> {
+        self.tooltip.as_deref()
+    }
+
+    /// Sets the hover tooltip of the arrow.
+    pub fn set_tooltip(&mut self, tooltip: Option<String>) {
+        self.tooltip = tooltip;
+    }
+
+    /// Gets the hyperlink the arrow opens when clicked.
+    pub fn link(&self) -> Option<&LinkDefinition> {
+        self.link.as_ref()
+    }
+
+    /// Sets the hyperlink the arrow opens when clicked.
+    pub fn set_link(&mut self, link: Option<LinkDefinition>) {
+        self.link = link;
+    }
 }
 
-impl Default for ArrowDefinition {
-    fn default() -> Self {
-        let mut text_def = TextDefinition::default();
-        text_def.set_background_color(Some(
-            Color::new("rgba(255, 255, 255, 0.85)").expect("valid color"),
-        ));
+impl ArrowDefinition {
+    /// Creates an arrow definition with the colors of `theme`.
+    pub fn from_theme(theme: &Theme) -> Self {
+        let mut stroke = StrokeDefinition::default();
+        stroke.set_color(theme.stroke());
+        let mut text_def = TextDefinition::from_theme(theme);
+        text_def.set_background_color(theme.label_background());
+        if let Some(family) = theme.relation_font_family() {
+            text_def.set_font_family(family);
+        }
+        if let Some(size) = theme.relation_font_size() {
+            text_def.set_font_size(size);
+        }
+        if let Some(style) = theme.relation_font_style() {
+            text_def.set_font_style(style);
+        }
+        if let Some(color) = theme.relation_text() {
+            text_def.set_color(Some(color));
+        }
         Self {
-            stroke: Rc::new(StrokeDefinition::default()),
+            stroke: Rc::new(stroke),
             style: ArrowStyle::default(),
             text: Rc::new(text_def),
+            segments: Vec::new(),
+            arrowhead: None,
+            arrowhead_start: None,
+            arrowhead_end: None,
+            label_position: LabelPosition::default(),
+            label_offset: 0.0,
+            curvature: Self::DEFAULT_CURVATURE,
+            min_length: Self::DEFAULT_MIN_LENGTH,
+            tooltip: None,
+            link: None,
         }
     }
 }
 
+impl Default for ArrowDefinition {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
 /// Defines the direction of arrow markers.
 ///
 /// - `Forward`: Creates `->` arrows pointing from source to destination
 /// - `Backward`: Creates `<-` arrows pointing from destination to source
 /// - `Bidirectional`: Creates `<->` arrows with markers at both ends
 /// - `Plain`: Creates `-` simple lines without arrow markers
+/// - `AsyncForward`: Creates `->>` asynchronous messages with an open arrowhead
+///   at the destination
+/// - `AsyncBackward`: Creates `<<-` asynchronous messages with an open arrowhead
+///   at the source
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum ArrowDirection {
     Forward,       // ->
     Backward,      // <-
     Bidirectional, // <->
     Plain,         // -
+    AsyncForward,  // ->>
+    AsyncBackward, // <<-
 }
 
 impl ArrowDirection {
+    /// Returns `true` for asynchronous message directions, which are drawn
+    /// with open arrowheads.
+    pub fn is_async(self) -> bool {
+        matches!(self, Self::AsyncForward | Self::AsyncBackward)
+    }
+
+    /// Returns `true` for directions that point from the target back to the
+    /// source (`<-` and `<<-`), so the relation flows from target to source.
+    pub fn is_backward(self) -> bool {
+        matches!(self, Self::Backward | Self::AsyncBackward)
+    }
+
+    /// Returns the default arrowheads at the source and destination ends.
+    fn arrowheads(self) -> (Option<Arrowhead>, Option<Arrowhead>) {
+        match self {
+            Self::Forward => (None, Some(Arrowhead::Triangle)),
+            Self::Backward => (Some(Arrowhead::Triangle), None),
+            Self::Bidirectional => (Some(Arrowhead::Triangle), Some(Arrowhead::Triangle)),
+            Self::Plain => (None, None),
+            Self::AsyncForward => (None, Some(Arrowhead::Open)),
+            Self::AsyncBackward => (Some(Arrowhead::Open), None),
+        }
+    }
+
     fn to_string(self) -> &'static str {
         match self {
             Self::Forward => "->",
             Self::Backward => "<-",
             Self::Bidirectional => "<->",
             Self::Plain => "-",
+            Self::AsyncForward => "->>",
+            Self::AsyncBackward => "<<-",
         }
     }
 }
@@ -143,6 +531,8 @@ impl FromStr for ArrowDirection {
             "<-" => Ok(Self::Backward),
             "<->" => Ok(Self::Bidirectional),
             "-" => Ok(Self::Plain),
+            "->>" => Ok(Self::AsyncForward),
+            "<<-" => Ok(Self::AsyncBackward),
             _ => Err("Invalid arrow direction"),
         }
     }
@@ -179,6 +569,7 @@ pub struct ArrowPath {
     source: Point,
     destination: Point,
     control_points: Vec<Point>,
+    corner_radius: f32,
 }
 
 impl ArrowPath {
@@ -194,6 +585,7 @@ impl ArrowPath {
             source,
             destination,
             control_points,
+            corner_radius: 0.0,
         }
     }
 
@@ -208,6 +600,7 @@ impl ArrowPath {
             source,
             destination,
             control_points: Vec::new(),
+            corner_radius: 0.0,
         }
     }
 
@@ -225,6 +618,45 @@ impl ArrowPath {
     pub fn control_points(&self) -> &[Point] {
         &self.control_points
     }
+
+    /// Sets the radius used to round the corners of a polyline path.
+    ///
+    /// Only arrows drawn as polylines round their corners; the radius is
+    /// shortened where a segment is too short to fit it.
+    pub fn with_corner_radius(mut self, radius: f32) -> Self {
+        self.corner_radius = radius;
+        self
+    }
+
+    /// Returns the radius used to round the corners of a polyline path.
+    pub fn corner_radius(&self) -> f32 {
+        self.corner_radius
+    }
+
+    /// Returns the straight segments of the path when drawn as a polyline.
+    ///
+    /// The path runs from [`source`](Self::source) through each control point
+    /// to [`destination`](Self::destination); each yielded pair is the start
+    /// and end of one segment.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use orrery_core::geometry::Point;
+    /// # use orrery_core::draw::ArrowPath;
+    /// let path = ArrowPath::new(
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(10.0, 10.0),
+    ///     vec![Point::new(10.0, 0.0)],
+    /// );
+    /// assert_eq!(path.segments().count(), 2);
+    /// ```
+    pub fn segments(&self) -> impl Iterator<Item = (Point, Point)> + '_ {
+        iter::once(self.source)
+            .chain(self.control_points.iter().copied())
+            .chain(iter::once(self.destination))
+            .tuple_windows()
+    }
 }
 
 /// A drawable arrow with styling and direction markers.
@@ -245,8 +677,8 @@ pub struct Arrow {
 /// referenced by individual arrow elements.
 #[derive(Debug, Default)]
 pub struct ArrowDrawer {
-    heads: HashMap<String, Color>,
-    tails: HashMap<String, Color>,
+    /// Markers in use, keyed by their SVG id.
+    markers: BTreeMap<String, (Arrowhead, ArrowEnd, Color)>,
 }
 
 impl ArrowDrawer {
@@ -264,35 +696,51 @@ impl ArrowDrawer {
         arrow.render_to_svg(path)
     }
 
-    /// Generates SVG marker definitions for all collected arrow colors.
+    /// Generates SVG marker definitions for all collected arrowheads and colors.
     pub fn draw_marker_definitions(&self) -> Box<dyn svg::Node> {
-        let mut defs = svg_element::Definitions::new();
-        for color in self.heads.values() {
-            defs = defs.add(Arrow::create_arrow_left(*color));
-        }
-        for color in self.tails.values() {
-            defs = defs.add(Arrow::create_arrow_right(*color));
-        }
-        defs.into()
+        self.markers
+            .iter()
+            .fold(
+                svg_element::Definitions::new(),
+                |defs, (id, &(arrowhead, end, color))| {
+                    defs.add(Arrow::create_marker(id, arrowhead, end, color))
+                },
+            )
+            .into()
     }
 
     fn register_arrow_markers(&mut self, arrow: &Arrow) {
         let color = arrow.definition.stroke().color();
-        let (head, tail) = Arrow::get_markers(arrow.direction, color);
-        if let Some(head) = head {
-            self.heads.insert(head, color);
-        }
-        if let Some(tail) = tail {
-            self.tails.insert(tail, color);
+        let (start, end) = arrow.arrowheads();
+        for (arrowhead, arrow_end) in [(start, ArrowEnd::Start), (end, ArrowEnd::End)] {
+            if let Some(arrowhead) = arrowhead {
+                self.markers.insert(
+                    Arrow::marker_id(arrowhead, arrow_end, color),
+                    (arrowhead, arrow_end, color),
+                );
+            }
         }
     }
 }
 
+/// An end of an arrow's path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArrowEnd {
+    /// The source end, where markers point back towards the source.
+    Start,
+    /// The destination end.
+    End,
+}
+
 /// Size of the SVG arrow markers (matches `markerWidth`/`markerHeight` attributes).
 ///
 /// Markers are square, so this applies to both dimensions.
 const MARKER_SIZE: f32 = 6.0;
 
+/// Number of straight steps used to approximate each Bézier curve when a
+/// curved arrow is split into styled segments.
+const CURVE_SAMPLES: usize = 16;
+
 impl Arrow {
     /// Creates a new [`Arrow`] with the given definition and direction.
     pub fn new(definition: Rc<ArrowDefinition>, direction: ArrowDirection) -> Self {
@@ -307,13 +755,38 @@ impl Arrow {
         self.definition.style
     }
 
+    /// Returns the arrow's [`ArrowDefinition`].
+    pub fn definition(&self) -> &Rc<ArrowDefinition> {
+        &self.definition
+    }
+
+    /// Returns the arrowheads drawn at the source and destination ends.
+    ///
+    /// An explicit [`arrowhead_start`](ArrowDefinition::arrowhead_start) or
+    /// [`arrowhead_end`](ArrowDefinition::arrowhead_end) wins. Otherwise the
+    /// ends marked by the direction use the definition's
+    /// [`arrowhead`](ArrowDefinition::arrowhead), or the direction's own.
+    /// [`Arrowhead::None`] is returned as `None`.
+    pub fn arrowheads(&self) -> (Option<Arrowhead>, Option<Arrowhead>) {
+        let definition = &self.definition;
+        let resolve = |explicit: Option<Arrowhead>, default: Option<Arrowhead>| {
+            explicit
+                .or_else(|| default.map(|default| definition.arrowhead.unwrap_or(default)))
+                .filter(|&arrowhead| arrowhead != Arrowhead::None)
+        };
+        let (start, end) = self.direction.arrowheads();
+        (
+            resolve(definition.arrowhead_start, start),
+            resolve(definition.arrowhead_end, end),
+        )
+    }
+
     /// Returns the minimum [`Size`] needed to render this arrow.
     pub fn min_size(&self) -> Size {
-        let (marker_width, marker_height) = match self.direction {
-            ArrowDirection::Forward | ArrowDirection::Backward => (MARKER_SIZE, MARKER_SIZE),
-            ArrowDirection::Bidirectional => (2.0 * MARKER_SIZE, MARKER_SIZE),
-            ArrowDirection::Plain => (0.0, 0.0),
-        };
+        let (start, end) = self.arrowheads();
+        let markers = usize::from(start.is_some()) + usize::from(end.is_some());
+        let marker_width = markers as f32 * MARKER_SIZE;
+        let marker_height = if markers > 0 { MARKER_SIZE } else { 0.0 };
         let stroke_width = self.definition.stroke().width();
         Size::new(marker_width, marker_height.max(stroke_width))
     }
@@ -324,13 +797,15 @@ impl Arrow {
     /// no control points are provided, it falls back to a straight line.
     /// Other styles (`Straight`, `Orthogonal`) ignore control points entirely.
     fn render_to_svg(&self, path: &ArrowPath) -> Box<dyn svg::Node> {
+        if !self.definition.segments.is_empty() {
+            return self.render_segments_to_svg(path);
+        }
+
         let path_data = match self.definition.style {
             ArrowStyle::Curved => Self::curved_path_data(path),
             ArrowStyle::Straight | ArrowStyle::Orthogonal => Self::polyline_path_data(path),
         };
 
-        let color = self.definition.stroke().color();
-
         // Create the base path
         let path = svg_element::Path::new()
             .set("d", path_data)
@@ -338,8 +813,8 @@ impl Arrow {
 
         let mut path = crate::apply_stroke!(path, self.definition.stroke());
 
-        // Get marker references for this specific color and direction
-        let (start_marker, end_marker) = Self::get_markers(self.direction, color);
+        // Get marker references for this arrow's arrowheads and color
+        let (start_marker, end_marker) = self.marker_urls();
 
         // Add markers if they exist
         if let Some(marker) = start_marker {
@@ -353,30 +828,169 @@ impl Arrow {
         Box::new(path)
     }
 
-    fn marker_left_id(color: Color) -> String {
-        format!("arrow-left-{}", color.to_id_safe_string())
+    /// Renders this arrow as one sub-path per [`ArrowSegment`], grouped together.
+    ///
+    /// Each sub-path uses the arrow's stroke with the segment's style. The
+    /// start marker is placed on the first sub-path and the end marker on the
+    /// last. Curved paths are approximated by straight steps.
+    fn render_segments_to_svg(&self, path: &ArrowPath) -> Box<dyn svg::Node> {
+        let segments = &self.definition.segments;
+        let points = self.flattened_points(path);
+        let pieces = Self::split_polyline(&points, segments);
+
+        let (start_marker, end_marker) = self.marker_urls();
+        let last = pieces.len() - 1;
+
+        let mut group = svg_element::Group::new();
+        for (i, (segment, piece)) in segments.iter().zip(pieces).enumerate() {
+            let mut stroke = StrokeDefinition::clone(self.definition.stroke());
+            stroke.set_style(segment.style().clone());
+
+            let piece_path = ArrowPath::new(
+                piece[0],
+                piece[piece.len() - 1],
+                piece[1..piece.len() - 1].to_vec(),
+            )
+            .with_corner_radius(path.corner_radius());
+            let sub_path = svg_element::Path::new()
+                .set("d", Self::polyline_path_data(&piece_path))
+                .set("fill", "none");
+            let mut sub_path = crate::apply_stroke!(sub_path, &stroke);
+
+            if i == 0
+                && let Some(marker) = &start_marker
+            {
+                sub_path = sub_path.set("marker-start", marker.as_str());
+            }
+            if i == last
+                && let Some(marker) = &end_marker
+            {
+                sub_path = sub_path.set("marker-end", marker.as_str());
+            }
+
+            group = group.add(sub_path);
+        }
+
+        Box::new(group)
+    }
+
+    /// Returns the points of the drawn path as a polyline.
+    ///
+    /// Polyline styles use the path's own points. Curved paths are sampled
+    /// along the same Bézier groups produced by [`Self::curved_path_data`].
+    fn flattened_points(&self, path: &ArrowPath) -> Vec<Point> {
+        let control_points = path.control_points();
+        if self.definition.style != ArrowStyle::Curved || control_points.is_empty() {
+            return iter::once(path.source())
+                .chain(control_points.iter().copied())
+                .chain(iter::once(path.destination()))
+                .collect();
+        }
+
+        let mut points = vec![path.source()];
+        let mut i = 0;
+        while i < control_points.len() {
+            let start = points[points.len() - 1];
+            let (controls, end, consumed) = match control_points.len() - i {
+                1 => (&control_points[i..i + 1], path.destination(), 1),
+                2 => (&control_points[i..i + 2], path.destination(), 2),
+                3 => (&control_points[i..i + 1], control_points[i + 1], 2),
+                _ => (&control_points[i..i + 2], control_points[i + 2], 3),
+            };
+            let curve: Vec<Point> = iter::once(start)
+                .chain(controls.iter().copied())
+                .chain(iter::once(end))
+                .collect();
+            points.extend(
+                (1..=CURVE_SAMPLES)
+                    .map(|step| Self::bezier_point(&curve, step as f32 / CURVE_SAMPLES as f32)),
+            );
+            i += consumed;
+        }
+        points
     }
 
-    fn marker_right_id(color: Color) -> String {
-        format!("arrow-right-{}", color.to_id_safe_string())
+    /// Evaluates a Bézier curve of any degree at `t` using de Casteljau's algorithm.
+    fn bezier_point(curve: &[Point], t: f32) -> Point {
+        let mut points = curve.to_vec();
+        while points.len() > 1 {
+            points = points
+                .iter()
+                .tuple_windows()
+                .map(|(a, b)| a.add_point(b.sub_point(*a).scale(t)))
+                .collect();
+        }
+        points[0]
     }
 
-    /// Get marker references for a specific relation type and color
-    fn get_markers(direction: ArrowDirection, color: Color) -> (Option<String>, Option<String>) {
-        match direction {
-            ArrowDirection::Forward => (
-                None,
-                Some(format!("url(#{})", Self::marker_right_id(color))),
-            ),
-            ArrowDirection::Backward => {
-                (Some(format!("url(#{})", Self::marker_left_id(color))), None)
+    /// Splits a polyline into one piece per segment, by length.
+    ///
+    /// Every piece but the last ends where its cumulative fraction of the
+    /// total length is reached; the last piece always runs to the final
+    /// point. Consecutive pieces share their boundary point.
+    fn split_polyline(points: &[Point], segments: &[ArrowSegment]) -> Vec<Vec<Point>> {
+        let total: f32 = points
+            .iter()
+            .tuple_windows()
+            .map(|(a, b)| b.sub_point(*a).hypot())
+            .sum();
+
+        let mut pieces = Vec::with_capacity(segments.len());
+        let mut current = vec![points[0]];
+        let mut idx = 0;
+        let mut walked = 0.0;
+        let mut boundary = 0.0;
+
+        for segment in &segments[..segments.len() - 1] {
+            boundary += segment.fraction() * total;
+            while idx + 1 < points.len() {
+                let (a, b) = (points[idx], points[idx + 1]);
+                let len = b.sub_point(a).hypot();
+                if walked + len >= boundary {
+                    let t = if len > 0.0 {
+                        (boundary - walked) / len
+                    } else {
+                        0.0
+                    };
+                    let cut = a.add_point(b.sub_point(a).scale(t));
+                    current.push(cut);
+                    pieces.push(mem::replace(&mut current, vec![cut]));
+                    break;
+                }
+                walked += len;
+                idx += 1;
+                current.push(b);
             }
-            ArrowDirection::Bidirectional => (
-                Some(format!("url(#{})", Self::marker_left_id(color))),
-                Some(format!("url(#{})", Self::marker_right_id(color))),
-            ),
-            ArrowDirection::Plain => (None, None),
         }
+
+        current.extend_from_slice(&points[idx + 1..]);
+        pieces.push(current);
+        pieces
+    }
+
+    /// Returns the SVG id of the marker for `arrowhead` at `end` in `color`.
+    fn marker_id(arrowhead: Arrowhead, end: ArrowEnd, color: Color) -> String {
+        let shape = match arrowhead {
+            Arrowhead::Triangle | Arrowhead::None => "",
+            Arrowhead::Open => "open-",
+            Arrowhead::Diamond => "diamond-",
+            Arrowhead::Circle => "circle-",
+        };
+        let side = match end {
+            ArrowEnd::Start => "left",
+            ArrowEnd::End => "right",
+        };
+        format!("arrow-{shape}{side}-{}", color.to_id_safe_string())
+    }
+
+    /// Returns the `marker-start` and `marker-end` references of this arrow.
+    fn marker_urls(&self) -> (Option<String>, Option<String>) {
+        let color = self.definition.stroke().color();
+        let url = |arrowhead: Option<Arrowhead>, end| {
+            arrowhead.map(|arrowhead| format!("url(#{})", Self::marker_id(arrowhead, end, color)))
+        };
+        let (start, end) = self.arrowheads();
+        (url(start, ArrowEnd::Start), url(end, ArrowEnd::End))
     }
 
     /// Creates an SVG path data string for an [`ArrowPath`].
@@ -485,7 +1099,9 @@ impl Arrow {
     /// [`control point`](ArrowPath::control_points) to
     /// [`destination`](ArrowPath::destination) as straight `L` segments. With
     /// no control points this is a single straight line; with them it produces
-    /// a multi-segment polyline.
+    /// a multi-segment polyline. A non-zero
+    /// [`corner_radius`](ArrowPath::corner_radius) replaces each corner with a
+    /// quadratic curve.
     ///
     /// # Examples
     ///
@@ -501,6 +1117,11 @@ impl Arrow {
     /// assert_eq!(Arrow::polyline_path_data(&path), "M 0 0 L 10 0 L 10 10");
     /// ```
     pub fn polyline_path_data(path: &ArrowPath) -> String {
+        let radius = path.corner_radius();
+        if radius > 0.0 && !path.control_points().is_empty() {
+            return Self::rounded_polyline_path_data(path, radius);
+        }
+
         let l_parts = path
             .control_points()
             .iter()
@@ -513,38 +1134,118 @@ impl Arrow {
             .join(" ")
     }
 
-    fn create_arrow_right(color: Color) -> svg_element::Marker {
-        svg_element::Marker::new()
-            .set("id", Self::marker_right_id(color))
-            .set("viewBox", "0 0 10 10")
-            .set("refX", 9)
-            .set("refY", 5)
-            .set("markerWidth", MARKER_SIZE)
-            .set("markerHeight", MARKER_SIZE)
-            .set("orient", "auto")
-            .add(
-                svg_element::Path::new()
-                    .set("d", "M 0 0 L 10 5 L 0 10 z")
-                    .set("fill", color.to_string())
-                    .set("fill-opacity", color.alpha()),
-            )
+    /// Creates polyline path data with each corner rounded by `radius`.
+    ///
+    /// The radius at a corner is capped at half of the shorter adjacent
+    /// segment, so neighbouring curves never overlap.
+    fn rounded_polyline_path_data(path: &ArrowPath, radius: f32) -> String {
+        let points: Vec<Point> = iter::once(path.source())
+            .chain(path.control_points().iter().copied())
+            .chain(iter::once(path.destination()))
+            .collect();
+
+        let mut d = format!("M {} {}", points[0].x(), points[0].y());
+        for (prev, corner, next) in points.iter().copied().tuple_windows() {
+            let incoming = corner.sub_point(prev);
+            let outgoing = next.sub_point(corner);
+            let (in_len, out_len) = (incoming.hypot(), outgoing.hypot());
+            if in_len == 0.0 || out_len == 0.0 {
+                d.push_str(&format!(" L {} {}", corner.x(), corner.y()));
+                continue;
+            }
+            let r = radius.min(in_len / 2.0).min(out_len / 2.0);
+            let start = corner.sub_point(incoming.scale(r / in_len));
+            let end = corner.add_point(outgoing.scale(r / out_len));
+            d.push_str(&format!(
+                " L {} {} Q {} {}, {} {}",
+                start.x(),
+                start.y(),
+                corner.x(),
+                corner.y(),
+                end.x(),
+                end.y()
+            ));
+        }
+        let end = path.destination();
+        d.push_str(&format!(" L {} {}", end.x(), end.y()));
+        d
     }
 
-    fn create_arrow_left(color: Color) -> svg_element::Marker {
+    /// Creates the SVG marker `id` drawing `arrowhead` at `end` in `color`.
+    ///
+    /// Markers share a 10×10 view box centered on the path; start markers are
+    /// mirrored so that they point back towards the source.
+    fn create_marker(
+        id: &str,
+        arrowhead: Arrowhead,
+        end: ArrowEnd,
+        color: Color,
+    ) -> svg_element::Marker {
+        let at_start = end == ArrowEnd::Start;
+        let (ref_x, shape): (f32, Box<dyn svg::Node>) = match arrowhead {
+            Arrowhead::Triangle | Arrowhead::None => {
+                let path_data = if at_start {
+                    "M 10 0 L 0 5 L 10 10 z"
+                } else {
+                    "M 0 0 L 10 5 L 0 10 z"
+                };
+                (1.0, Box::new(Self::filled_arrowhead(path_data, color)))
+            }
+            Arrowhead::Open => {
+                let path_data = if at_start {
+                    "M 10 0 L 0 5 L 10 10"
+                } else {
+                    "M 0 0 L 10 5 L 0 10"
+                };
+                (1.0, Box::new(Self::open_arrowhead(path_data, color)))
+            }
+            Arrowhead::Diamond => (
+                0.0,
+                Box::new(Self::filled_arrowhead("M 0 5 L 5 0 L 10 5 L 5 10 z", color)),
+            ),
+            Arrowhead::Circle => (
+                1.0,
+                Box::new(
+                    svg_element::Circle::new()
+                        .set("cx", 5)
+                        .set("cy", 5)
+                        .set("r", 4)
+                        .set("fill", color.to_string())
+                        .set("fill-opacity", color.alpha()),
+                ),
+            ),
+        };
+        // The tip of an end marker touches the destination.
+        let ref_x = if at_start { ref_x } else { 10.0 - ref_x };
+
         svg_element::Marker::new()
-            .set("id", Self::marker_left_id(color))
+            .set("id", id)
             .set("viewBox", "0 0 10 10")
-            .set("refX", 1)
+            .set("refX", ref_x)
             .set("refY", 5)
             .set("markerWidth", MARKER_SIZE)
             .set("markerHeight", MARKER_SIZE)
             .set("orient", "auto")
-            .add(
-                svg_element::Path::new()
-                    .set("d", "M 10 0 L 0 5 L 10 10 z")
-                    .set("fill", color.to_string())
-                    .set("fill-opacity", color.alpha()),
-            )
+            .add(shape)
+    }
+
+    /// A filled arrowhead outline.
+    fn filled_arrowhead(path_data: &str, color: Color) -> svg_element::Path {
+        svg_element::Path::new()
+            .set("d", path_data)
+            .set("fill", color.to_string())
+            .set("fill-opacity", color.alpha())
+    }
+
+    /// An unfilled chevron, drawn with a stroke so it stays open.
+    fn open_arrowhead(path_data: &str, color: Color) -> svg_element::Path {
+        svg_element::Path::new()
+            .set("d", path_data)
+            .set("fill", "none")
+            .set("stroke", color.to_string())
+            .set("stroke-opacity", color.alpha())
+            .set("stroke-width", 1.5)
+            .set("stroke-linejoin", "miter")
     }
 }
 
@@ -605,6 +1306,12 @@ mod tests {
 
         let plain: ArrowDirection = "-".parse().unwrap();
         assert_eq!(plain, ArrowDirection::Plain);
+
+        let async_forward: ArrowDirection = "->>".parse().unwrap();
+        assert_eq!(async_forward, ArrowDirection::AsyncForward);
+
+        let async_backward: ArrowDirection = "<<-".parse().unwrap();
+        assert_eq!(async_backward, ArrowDirection::AsyncBackward);
     }
 
     #[test]
@@ -650,6 +1357,8 @@ mod tests {
         assert_eq!(format!("{}", ArrowDirection::Backward), "<-");
         assert_eq!(format!("{}", ArrowDirection::Bidirectional), "<->");
         assert_eq!(format!("{}", ArrowDirection::Plain), "-");
+        assert_eq!(format!("{}", ArrowDirection::AsyncForward), "->>");
+        assert_eq!(format!("{}", ArrowDirection::AsyncBackward), "<<-");
     }
 
     #[test]
@@ -675,6 +1384,49 @@ mod tests {
         assert_eq!(data, "M 0 0 L 50 0 L 50 20 L 100 20");
     }
 
+    #[test]
+    fn test_polyline_path_data_with_corner_radius() {
+        let path = ArrowPath::new(
+            Point::new(0.0, 0.0),
+            Point::new(100.0, 20.0),
+            vec![Point::new(50.0, 0.0), Point::new(50.0, 20.0)],
+        )
+        .with_corner_radius(6.0);
+
+        let data = Arrow::polyline_path_data(&path);
+
+        // The 20px vertical segment caps both radii at 10, so 6 fits.
+        assert_eq!(
+            data,
+            "M 0 0 L 44 0 Q 50 0, 50 6 L 50 14 Q 50 20, 56 20 L 100 20"
+        );
+    }
+
+    #[test]
+    fn test_arrow_path_segments() {
+        let path = ArrowPath::straight(Point::new(0.0, 0.0), Point::new(10.0, 0.0));
+        let segments: Vec<_> = path.segments().collect();
+        assert_eq!(
+            segments,
+            vec![(Point::new(0.0, 0.0), Point::new(10.0, 0.0))]
+        );
+
+        let path = ArrowPath::new(
+            Point::new(0.0, 0.0),
+            Point::new(100.0, 20.0),
+            vec![Point::new(50.0, 0.0), Point::new(50.0, 20.0)],
+        );
+        let segments: Vec<_> = path.segments().collect();
+        assert_eq!(
+            segments,
+            vec![
+                (Point::new(0.0, 0.0), Point::new(50.0, 0.0)),
+                (Point::new(50.0, 0.0), Point::new(50.0, 20.0)),
+                (Point::new(50.0, 20.0), Point::new(100.0, 20.0)),
+            ]
+        );
+    }
+
     #[test]
     fn test_curved_path_data_empty_falls_back_to_straight() {
         let path = ArrowPath::straight(Point::new(0.0, 0.0), Point::new(100.0, 50.0));
@@ -745,6 +1497,81 @@ mod tests {
         assert_eq!(result, "M 0 0 C 10 20, 30 40, 60 40 Q 80 20, 100 0");
     }
 
+    #[test]
+    fn test_arrow_segment_from_str() {
+        let segment: ArrowSegment = "solid:0.4".parse().unwrap();
+        assert_eq!(segment, ArrowSegment::new(StrokeStyle::Solid, 0.4));
+
+        let segment: ArrowSegment = " dash-dot : 1 ".parse().unwrap();
+        assert_eq!(segment, ArrowSegment::new(StrokeStyle::DashDot, 1.0));
+
+        assert!("dashed".parse::<ArrowSegment>().is_err());
+        assert!("dashed:half".parse::<ArrowSegment>().is_err());
+        assert!("dashed:0".parse::<ArrowSegment>().is_err());
+        assert!("dashed:1.5".parse::<ArrowSegment>().is_err());
+    }
+
+    #[test]
+    fn test_split_polyline_across_corner() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(30.0, 0.0),
+            Point::new(30.0, 10.0),
+        ];
+        let segments = [
+            ArrowSegment::new(StrokeStyle::Solid, 0.5),
+            ArrowSegment::new(StrokeStyle::Dashed, 0.5),
+        ];
+
+        let pieces = Arrow::split_polyline(&points, &segments);
+
+        assert_eq!(
+            pieces,
+            vec![
+                vec![Point::new(0.0, 0.0), Point::new(20.0, 0.0)],
+                vec![
+                    Point::new(20.0, 0.0),
+                    Point::new(30.0, 0.0),
+                    Point::new(30.0, 10.0)
+                ],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bezier_point_quadratic() {
+        let curve = [
+            Point::new(0.0, 0.0),
+            Point::new(50.0, -30.0),
+            Point::new(100.0, 0.0),
+        ];
+
+        assert_eq!(Arrow::bezier_point(&curve, 0.0), Point::new(0.0, 0.0));
+        assert_eq!(Arrow::bezier_point(&curve, 0.5), Point::new(50.0, -15.0));
+        assert_eq!(Arrow::bezier_point(&curve, 1.0), Point::new(100.0, 0.0));
+    }
+
+    #[test]
+    fn test_draw_arrow_with_segments() {
+        let mut drawer = ArrowDrawer::default();
+        let mut def = ArrowDefinition::new(Rc::new(StrokeDefinition::default()));
+        def.set_style(ArrowStyle::Straight);
+        def.set_segments(vec![
+            ArrowSegment::new(StrokeStyle::Solid, 0.5),
+            ArrowSegment::new(StrokeStyle::Dashed, 0.5),
+        ]);
+        let arrow = Arrow::new(Rc::new(def), ArrowDirection::Forward);
+
+        let path = ArrowPath::straight(Point::new(0.0, 0.0), Point::new(100.0, 0.0));
+        let svg = drawer.draw_arrow(&arrow, &path).to_string();
+
+        assert_eq!(svg.matches("<path").count(), 2);
+        assert!(svg.contains(r#"d="M 0 0 L 50 0""#));
+        assert!(svg.contains(r#"d="M 50 0 L 100 0""#));
+        assert_eq!(svg.matches("stroke-dasharray=\"5,5\"").count(), 1);
+        assert_eq!(svg.matches("marker-end").count(), 1);
+    }
+
     #[test]
     fn test_draw_arrow_with_control_points() {
         let mut drawer = ArrowDrawer::default();
@@ -764,4 +1591,107 @@ mod tests {
         let path = ArrowPath::straight(source, destination);
         let _node = drawer.draw_arrow(&arrow, &path);
     }
+
+    #[test]
+    fn test_draw_async_arrow_uses_open_marker() {
+        let mut drawer = ArrowDrawer::default();
+        let def = Rc::new(ArrowDefinition::new(Rc::new(StrokeDefinition::default())));
+        let arrow = Arrow::new(def, ArrowDirection::AsyncForward);
+
+        let path = ArrowPath::straight(Point::new(0.0, 0.0), Point::new(100.0, 0.0));
+        let svg = drawer.draw_arrow(&arrow, &path).to_string();
+        assert!(svg.contains("marker-end=\"url(#arrow-open-right-"));
+
+        let defs = drawer.draw_marker_definitions().to_string();
+        assert!(defs.contains("id=\"arrow-open-right-"));
+        assert!(defs.contains("fill=\"none\""));
+        assert!(!defs.contains("id=\"arrow-right-"));
+    }
+
+    #[test]
+    fn test_curvature_is_clamped() {
+        let mut definition = ArrowDefinition::default();
+        assert_eq!(definition.curvature(), ArrowDefinition::DEFAULT_CURVATURE);
+
+        definition.set_curvature(3.0);
+        assert_eq!(definition.curvature(), 1.0);
+        definition.set_curvature(-1.0);
+        assert_eq!(definition.curvature(), 0.0);
+        definition.set_curvature(0.25);
+        assert_eq!(definition.curvature(), 0.25);
+    }
+
+    #[test]
+    fn test_min_length_is_at_least_one() {
+        let mut definition = ArrowDefinition::default();
+        assert_eq!(definition.min_length(), ArrowDefinition::DEFAULT_MIN_LENGTH);
+
+        definition.set_min_length(4);
+        assert_eq!(definition.min_length(), 4);
+        definition.set_min_length(0);
+        assert_eq!(definition.min_length(), 1);
+    }
+
+    #[test]
+    fn test_arrowhead_from_str() {
+        assert_eq!("diamond".parse::<Arrowhead>(), Ok(Arrowhead::Diamond));
+        assert_eq!("none".parse::<Arrowhead>(), Ok(Arrowhead::None));
+        assert!("square".parse::<Arrowhead>().is_err());
+        assert_eq!(Arrowhead::Circle.to_string(), "circle");
+    }
+
+    #[test]
+    fn test_arrowheads_override_direction() {
+        let mut def = ArrowDefinition::new(Rc::new(StrokeDefinition::default()));
+        def.set_arrowhead(Arrowhead::Diamond);
+        def.set_arrowhead_start(Arrowhead::Circle);
+        let def = Rc::new(def);
+
+        // `arrowhead_start` wins over `arrowhead` at the source end
+        let arrow = Arrow::new(Rc::clone(&def), ArrowDirection::Bidirectional);
+        assert_eq!(
+            arrow.arrowheads(),
+            (Some(Arrowhead::Circle), Some(Arrowhead::Diamond))
+        );
+
+        // `arrowhead` only applies to the ends marked by the direction
+        let arrow = Arrow::new(Rc::clone(&def), ArrowDirection::Forward);
+        assert_eq!(
+            arrow.arrowheads(),
+            (Some(Arrowhead::Circle), Some(Arrowhead::Diamond))
+        );
+        let arrow = Arrow::new(def, ArrowDirection::Plain);
+        assert_eq!(arrow.arrowheads(), (Some(Arrowhead::Circle), None));
+    }
+
+    #[test]
+    fn test_arrowhead_none_removes_marker() {
+        let mut def = ArrowDefinition::new(Rc::new(StrokeDefinition::default()));
+        def.set_arrowhead_end(Arrowhead::None);
+        let arrow = Arrow::new(Rc::new(def), ArrowDirection::Bidirectional);
+        assert_eq!(arrow.min_size(), Size::new(MARKER_SIZE, MARKER_SIZE));
+
+        let mut drawer = ArrowDrawer::default();
+        let path = ArrowPath::straight(Point::new(0.0, 0.0), Point::new(100.0, 0.0));
+        let svg = drawer.draw_arrow(&arrow, &path).to_string();
+        assert!(svg.contains("marker-start=\"url(#arrow-left-"));
+        assert!(!svg.contains("marker-end"));
+    }
+
+    #[test]
+    fn test_draw_diamond_arrowhead_marker() {
+        let mut def = ArrowDefinition::new(Rc::new(StrokeDefinition::default()));
+        def.set_arrowhead(Arrowhead::Diamond);
+        let arrow = Arrow::new(Rc::new(def), ArrowDirection::Forward);
+
+        let mut drawer = ArrowDrawer::default();
+        let path = ArrowPath::straight(Point::new(0.0, 0.0), Point::new(100.0, 0.0));
+        let svg = drawer.draw_arrow(&arrow, &path).to_string();
+        assert!(svg.contains("marker-end=\"url(#arrow-diamond-right-"));
+
+        let defs = drawer.draw_marker_definitions().to_string();
+        assert!(defs.contains("id=\"arrow-diamond-right-"));
+        assert!(defs.contains("M 0 5 L 5 0 L 10 5 L 5 10 z"));
+        assert!(!defs.contains("id=\"arrow-right-"));
+    }
 }
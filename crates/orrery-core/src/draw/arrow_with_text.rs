@@ -4,7 +4,10 @@
 //! text along the arrow path.
 
 use crate::{
-    draw::{Arrow, ArrowDrawer, ArrowPath, ArrowStyle, Drawable, LayeredOutput, RenderLayer, Text},
+    draw::{
+        Arrow, ArrowDrawer, ArrowPath, ArrowStyle, Drawable, LabelPosition, LayeredOutput,
+        LinkDefinition, RenderLayer, Text,
+    },
     geometry::{Point, Size},
 };
 
@@ -24,6 +27,11 @@ impl<'a> ArrowWithText<'a> {
         Self { arrow, text }
     }
 
+    /// Returns the text label of the arrow, if any.
+    pub fn text(&self) -> Option<&Text<'a>> {
+        self.text.as_ref()
+    }
+
     /// Returns the minimum [`Size`] needed to render this arrow with its text.
     ///
     /// Combines the arrow's minimum size with the text label size.
@@ -70,12 +78,37 @@ impl<'a> ArrowWithText<'a> {
         output
     }
 
+    /// Returns the center position of the text label along `path`, or `None`
+    /// if this arrow has no label.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The geometric path of the arrow.
+    /// * `text_position_override` - Explicit label position. When `None`, the
+    ///   position is computed from `path`'s geometry.
+    pub fn text_position(
+        &self,
+        path: &ArrowPath,
+        text_position_override: Option<Point>,
+    ) -> Option<Point> {
+        self.text
+            .as_ref()
+            .map(|_| self.calculate_text_position(path, text_position_override))
+    }
+
     /// Calculates the position where text should be rendered relative to the arrow.
     ///
-    /// If `text_position_override` is `Some`, that explicit position is returned
-    /// verbatim. Otherwise, only [`ArrowStyle::Curved`] uses control points for
-    /// positioning; other styles always place text at the geometric midpoint of
-    /// source and destination.
+    /// The definition's [`LabelPosition`] picks the point along `path`: near
+    /// the source, halfway, or near the destination. [`ArrowStyle::Curved`]
+    /// measures along the curve defined by the control points, and
+    /// [`ArrowStyle::Orthogonal`] places a middle label at the midpoint of the
+    /// longest visible segment so it never sits on a corner.
+    /// [`ArrowStyle::Straight`] always measures from source to destination.
+    ///
+    /// If `text_position_override` is `Some`, it replaces the middle point;
+    /// start and end labels are still measured along `path`. The label offset
+    /// then moves the point perpendicular to the path's direction there, so a
+    /// label on a curve follows the curve's tangent.
     fn calculate_text_position(
         &self,
         path: &ArrowPath,
@@ -85,23 +118,52 @@ impl<'a> ArrowWithText<'a> {
             return Point::zero();
         }
 
-        if let Some(position) = text_position_override {
-            return position;
+        let definition = self.arrow.definition();
+        let label_position = definition.label_position();
+        let (anchor, tangent) = match text_position_override {
+            Some(position) if label_position == LabelPosition::Middle => {
+                (position, self.point_along(path, label_position).1)
+            }
+            _ => self.point_along(path, label_position),
+        };
+
+        let length = tangent.hypot();
+        if definition.label_offset() == 0.0 || length == 0.0 {
+            return anchor;
         }
+        let normal = Point::new(tangent.y(), -tangent.x()).scale(1.0 / length);
+        anchor.add_point(normal.scale(definition.label_offset()))
+    }
 
+    /// Returns the point at `label_position` along `path` and the direction
+    /// of the path there.
+    fn point_along(&self, path: &ArrowPath, label_position: LabelPosition) -> (Point, Point) {
+        let t = match label_position {
+            LabelPosition::Start => LABEL_START_FRACTION,
+            LabelPosition::Middle => 0.5,
+            LabelPosition::End => 1.0 - LABEL_START_FRACTION,
+        };
         let source = path.source();
         let destination = path.destination();
-
-        if self.arrow.style() != ArrowStyle::Curved {
-            return source.midpoint(destination);
+        let straight = || {
+            (
+                source.add_point(destination.sub_point(source).scale(t)),
+                destination.sub_point(source),
+            )
         };
 
         let control_points = path.control_points();
-        match control_points {
-            [] => source.midpoint(destination),
-            [cp] => quadratic_bezier_midpoint(source, *cp, destination),
-            [cp1, cp2] => cubic_bezier_midpoint(source, *cp1, *cp2, destination),
-            _ => {
+        match (self.arrow.style(), control_points) {
+            (ArrowStyle::Straight, _) | (ArrowStyle::Curved, []) => straight(),
+            (ArrowStyle::Orthogonal, _) if label_position == LabelPosition::Middle => {
+                longest_segment(path).map_or_else(straight, |(start, end)| {
+                    (start.midpoint(end), end.sub_point(start))
+                })
+            }
+            (ArrowStyle::Orthogonal, _) => polyline_point(path, t),
+            (ArrowStyle::Curved, [cp]) => quadratic_bezier_at(source, *cp, destination, t),
+            (ArrowStyle::Curved, [cp1, cp2]) => cubic_bezier_at(source, *cp1, *cp2, destination, t),
+            (ArrowStyle::Curved, _) if label_position == LabelPosition::Middle => {
                 // For chained curves, approximate by evaluating the midpoint
                 // of the middle segment's local neighborhood.
                 let mid_idx = control_points.len() / 2;
@@ -109,12 +171,17 @@ impl<'a> ArrowWithText<'a> {
                 // unwrap_or cases are unreachable when control_points has 3+ elements.
                 let before = control_points.get(mid_idx - 1).unwrap_or(&source);
                 let after = control_points.get(mid_idx + 1).unwrap_or(&destination);
-                quadratic_bezier_midpoint(*before, mid_cp, *after)
+                quadratic_bezier_at(*before, mid_cp, *after, 0.5)
             }
+            (ArrowStyle::Curved, _) => polyline_point(path, t),
         }
     }
 }
 
+/// Fraction of the path before a [`LabelPosition::Start`] label, and after a
+/// [`LabelPosition::End`] one.
+const LABEL_START_FRACTION: f32 = 0.2;
+
 /// ArrowWithTextDrawer manages arrow rendering with text and marker generation.
 ///
 /// The ArrowWithTextDrawer collects color information from arrows to generate
@@ -217,6 +284,29 @@ impl<'a> PositionedArrowWithText<'a> {
         &self.path
     }
 
+    /// Returns the content of the text label, or `None` if the arrow has no
+    /// label.
+    pub fn label(&self) -> Option<&str> {
+        self.arrow_with_text.text().map(|text| text.content())
+    }
+
+    /// Returns the hover tooltip of the arrow, if any.
+    pub fn tooltip(&self) -> Option<&str> {
+        self.arrow_with_text.arrow.definition().tooltip()
+    }
+
+    /// Returns the hyperlink of the arrow, if any.
+    pub fn link(&self) -> Option<&LinkDefinition> {
+        self.arrow_with_text.arrow.definition().link()
+    }
+
+    /// Returns the center position of the text label, or `None` if the arrow
+    /// has no label.
+    pub fn text_position(&self) -> Option<Point> {
+        self.arrow_with_text
+            .text_position(&self.path, self.text_position)
+    }
+
     /// Renders this positioned arrow to layered SVG output.
     ///
     /// # Arguments
@@ -227,26 +317,85 @@ impl<'a> PositionedArrowWithText<'a> {
     }
 }
 
-/// Evaluates a quadratic bezier curve at t=0.5 (parametric midpoint).
-fn quadratic_bezier_midpoint(start: Point, cp: Point, end: Point) -> Point {
-    Point::new(
-        0.25 * start.x() + 0.5 * cp.x() + 0.25 * end.x(),
-        0.25 * start.y() + 0.5 * cp.y() + 0.25 * end.y(),
-    )
+/// Returns the start and end of the longest straight segment of `path`.
+///
+/// Ties resolve to the earliest segment, so a symmetric route keeps its label
+/// near the source side.
+fn longest_segment(path: &ArrowPath) -> Option<(Point, Point)> {
+    path.segments()
+        .map(|(start, end)| (end.sub_point(start).hypot(), start, end))
+        .reduce(|longest, segment| {
+            if segment.0 > longest.0 {
+                segment
+            } else {
+                longest
+            }
+        })
+        .map(|(_, start, end)| (start, end))
+}
+
+/// Returns the point at fraction `t` of the length of `path` drawn as a
+/// polyline, and the direction of the segment it lies on.
+fn polyline_point(path: &ArrowPath, t: f32) -> (Point, Point) {
+    let total: f32 = path
+        .segments()
+        .map(|(start, end)| end.sub_point(start).hypot())
+        .sum();
+    let mut remaining = total * t;
+    let mut last = (path.source(), path.destination());
+    for (start, end) in path.segments() {
+        let length = end.sub_point(start).hypot();
+        if length > 0.0 && remaining <= length {
+            let direction = end.sub_point(start);
+            return (
+                start.add_point(direction.scale(remaining / length)),
+                direction,
+            );
+        }
+        remaining -= length;
+        last = (start, end);
+    }
+    (last.1, last.1.sub_point(last.0))
+}
+
+/// Evaluates a quadratic bezier curve at `t`, returning the point and the
+/// tangent there.
+fn quadratic_bezier_at(start: Point, cp: Point, end: Point, t: f32) -> (Point, Point) {
+    let u = 1.0 - t;
+    let point = start
+        .scale(u * u)
+        .add_point(cp.scale(2.0 * u * t))
+        .add_point(end.scale(t * t));
+    let tangent = cp
+        .sub_point(start)
+        .scale(2.0 * u)
+        .add_point(end.sub_point(cp).scale(2.0 * t));
+    (point, tangent)
 }
 
-/// Evaluates a cubic bezier curve at t=0.5 (parametric midpoint).
-fn cubic_bezier_midpoint(start: Point, cp1: Point, cp2: Point, end: Point) -> Point {
-    Point::new(
-        0.125 * start.x() + 0.375 * cp1.x() + 0.375 * cp2.x() + 0.125 * end.x(),
-        0.125 * start.y() + 0.375 * cp1.y() + 0.375 * cp2.y() + 0.125 * end.y(),
-    )
+/// Evaluates a cubic bezier curve at `t`, returning the point and the tangent
+/// there.
+fn cubic_bezier_at(start: Point, cp1: Point, cp2: Point, end: Point, t: f32) -> (Point, Point) {
+    let u = 1.0 - t;
+    let point = start
+        .scale(u * u * u)
+        .add_point(cp1.scale(3.0 * u * u * t))
+        .add_point(cp2.scale(3.0 * u * t * t))
+        .add_point(end.scale(t * t * t));
+    let tangent = cp1
+        .sub_point(start)
+        .scale(3.0 * u * u)
+        .add_point(cp2.sub_point(cp1).scale(6.0 * u * t))
+        .add_point(end.sub_point(cp2).scale(3.0 * t * t));
+    (point, tangent)
 }
 
 #[cfg(test)]
 mod tests {
     use std::rc::Rc;
 
+    use float_cmp::assert_approx_eq;
+
     use super::*;
     use crate::draw::{ArrowDefinition, ArrowDirection, StrokeDefinition, TextDefinition};
 
@@ -379,6 +528,27 @@ mod tests {
         assert_eq!(pos, override_pos);
     }
 
+    #[test]
+    fn test_positioned_arrow_with_text_text_position() {
+        let path = ArrowPath::straight(Point::new(0.0, 0.0), Point::new(100.0, 50.0));
+
+        // Without text there is no label position
+        let arrow = create_test_arrow(ArrowDirection::Forward);
+        let positioned =
+            PositionedArrowWithText::new(ArrowWithText::new(arrow, None), path.clone());
+        assert_eq!(positioned.text_position(), None);
+
+        // With text the label sits at the path's midpoint unless overridden
+        let arrow = create_test_arrow(ArrowDirection::Forward);
+        let text_def = TextDefinition::default();
+        let text = Text::new(&text_def, "Label");
+        let positioned = PositionedArrowWithText::new(ArrowWithText::new(arrow, Some(text)), path);
+        assert_eq!(positioned.text_position(), Some(Point::new(50.0, 25.0)));
+
+        let positioned = positioned.with_text_position(Some(Point::new(10.0, 10.0)));
+        assert_eq!(positioned.text_position(), Some(Point::new(10.0, 10.0)));
+    }
+
     #[test]
     fn test_calculate_text_position_straight_ignores_control_points() {
         let arrow = create_test_arrow_with_style(ArrowDirection::Forward, ArrowStyle::Straight);
@@ -397,20 +567,126 @@ mod tests {
     }
 
     #[test]
-    fn test_calculate_text_position_orthogonal_ignores_control_points() {
+    fn test_calculate_text_position_orthogonal_uses_longest_segment() {
+        let arrow = create_test_arrow_with_style(ArrowDirection::Forward, ArrowStyle::Orthogonal);
+        let text_def = TextDefinition::default();
+        let text = Text::new(&text_def, "Label");
+        let arrow_with_text = ArrowWithText::new(arrow, Some(text));
+
+        // Z-shaped route: the middle horizontal leg is the longest.
+        let path = ArrowPath::new(
+            Point::new(0.0, 0.0),
+            Point::new(100.0, 20.0),
+            vec![Point::new(0.0, 10.0), Point::new(100.0, 10.0)],
+        );
+        let pos = arrow_with_text.calculate_text_position(&path, None);
+        assert_eq!(pos, Point::new(50.0, 10.0));
+    }
+
+    #[test]
+    fn test_calculate_text_position_orthogonal_l_shape_avoids_corner() {
         let arrow = create_test_arrow_with_style(ArrowDirection::Forward, ArrowStyle::Orthogonal);
         let text_def = TextDefinition::default();
         let text = Text::new(&text_def, "Label");
         let arrow_with_text = ArrowWithText::new(arrow, Some(text));
 
-        // Even with control points, Orthogonal style should use geometric midpoint
+        // L-shaped route: a short vertical leg then a long horizontal leg.
+        let corner = Point::new(0.0, 20.0);
+        let path = ArrowPath::new(Point::new(0.0, 0.0), Point::new(120.0, 20.0), vec![corner]);
+        let pos = arrow_with_text.calculate_text_position(&path, None);
+
+        // The label sits on the longer horizontal leg, not on the corner.
+        assert_eq!(pos, Point::new(60.0, 20.0));
+        assert_ne!(pos, corner);
+    }
+
+    #[test]
+    fn test_calculate_text_position_label_position() {
+        let text_def = TextDefinition::default();
+        let labelled = |label_position: LabelPosition, style: ArrowStyle| {
+            let mut definition = ArrowDefinition::new(Rc::new(StrokeDefinition::default()));
+            definition.set_style(style);
+            definition.set_label_position(label_position);
+            let arrow = Arrow::new(Rc::new(definition), ArrowDirection::Forward);
+            ArrowWithText::new(arrow, Some(Text::new(&text_def, "Label")))
+        };
+
+        let path = ArrowPath::straight(Point::new(0.0, 0.0), Point::new(100.0, 0.0));
+        let start = labelled(LabelPosition::Start, ArrowStyle::Straight);
+        assert_eq!(
+            start.calculate_text_position(&path, None),
+            Point::new(20.0, 0.0)
+        );
+        let end = labelled(LabelPosition::End, ArrowStyle::Straight);
+        assert_eq!(
+            end.calculate_text_position(&path, None),
+            Point::new(80.0, 0.0)
+        );
+
+        // Start and end labels ignore an override of the middle point.
+        assert_eq!(
+            start.calculate_text_position(&path, Some(Point::new(50.0, 50.0))),
+            Point::new(20.0, 0.0)
+        );
+
+        // Orthogonal routes measure along their segments.
+        let path = ArrowPath::new(
+            Point::new(0.0, 0.0),
+            Point::new(100.0, 100.0),
+            vec![Point::new(0.0, 100.0)],
+        );
+        let end = labelled(LabelPosition::End, ArrowStyle::Orthogonal);
+        let pos = end.calculate_text_position(&path, None);
+        assert_approx_eq!(f32, pos.x(), 60.0, epsilon = 1e-4);
+        assert_approx_eq!(f32, pos.y(), 100.0);
+    }
+
+    #[test]
+    fn test_calculate_text_position_offset_follows_tangent() {
+        let text_def = TextDefinition::default();
+        let mut definition = ArrowDefinition::new(Rc::new(StrokeDefinition::default()));
+        definition.set_label_offset(10.0);
+        let arrow = Arrow::new(Rc::new(definition), ArrowDirection::Forward);
+        let arrow_with_text = ArrowWithText::new(arrow, Some(Text::new(&text_def, "Label")));
+
+        // A left-to-right arrow moves its label up.
+        let path = ArrowPath::straight(Point::new(0.0, 0.0), Point::new(100.0, 0.0));
+        assert_eq!(
+            arrow_with_text.calculate_text_position(&path, None),
+            Point::new(50.0, -10.0)
+        );
+
+        // The tangent at the top of a symmetric curve is horizontal, so the
+        // label moves straight up from the curve, including from an override.
         let path = ArrowPath::new(
             Point::new(0.0, 0.0),
             Point::new(100.0, 0.0),
             vec![Point::new(30.0, -40.0), Point::new(70.0, -40.0)],
         );
-        let pos = arrow_with_text.calculate_text_position(&path, None);
-        assert_eq!(pos, Point::new(50.0, 0.0));
+        assert_eq!(
+            arrow_with_text.calculate_text_position(&path, None),
+            Point::new(50.0, -40.0)
+        );
+        assert_eq!(
+            arrow_with_text.calculate_text_position(&path, Some(Point::new(50.0, -20.0))),
+            Point::new(50.0, -30.0)
+        );
+
+        // A downward arrow moves its label to the right.
+        let path = ArrowPath::straight(Point::new(0.0, 0.0), Point::new(0.0, 100.0));
+        assert_eq!(
+            arrow_with_text.calculate_text_position(&path, None),
+            Point::new(10.0, 50.0)
+        );
+    }
+
+    #[test]
+    fn test_longest_segment_without_control_points() {
+        let path = ArrowPath::straight(Point::new(0.0, 0.0), Point::new(100.0, 50.0));
+        assert_eq!(
+            longest_segment(&path),
+            Some((Point::new(0.0, 0.0), Point::new(100.0, 50.0)))
+        );
     }
 
     #[test]
@@ -464,6 +740,8 @@ mod tests {
             ArrowDirection::Backward,
             ArrowDirection::Bidirectional,
             ArrowDirection::Plain,
+            ArrowDirection::AsyncForward,
+            ArrowDirection::AsyncBackward,
         ];
 
         for direction in directions {
@@ -489,21 +767,21 @@ mod tests {
         let cp = Point::new(50.0, -60.0);
         let end = Point::new(100.0, 0.0);
 
-        let mid = quadratic_bezier_midpoint(start, cp, end);
+        let mid = quadratic_bezier_at(start, cp, end, 0.5).0;
         // 0.25*0 + 0.5*50 + 0.25*100 = 50
         // 0.25*0 + 0.5*(-60) + 0.25*0 = -30
         assert_eq!(mid, Point::new(50.0, -30.0));
 
         // Control point on the line: midpoint should equal the straight midpoint
         let cp_on_line = Point::new(50.0, 25.0);
-        let mid = quadratic_bezier_midpoint(start, cp_on_line, Point::new(100.0, 50.0));
+        let mid = quadratic_bezier_at(start, cp_on_line, Point::new(100.0, 50.0), 0.5).0;
         // 0.25*0 + 0.5*50 + 0.25*100 = 50
         // 0.25*0 + 0.5*25 + 0.25*50 = 25
         assert_eq!(mid, Point::new(50.0, 25.0));
 
         // Degenerate: all points the same
         let p = Point::new(10.0, 20.0);
-        let mid = quadratic_bezier_midpoint(p, p, p);
+        let mid = quadratic_bezier_at(p, p, p, 0.5).0;
         assert_eq!(mid, p);
     }
 
@@ -515,7 +793,7 @@ mod tests {
         let cp2 = Point::new(70.0, -40.0);
         let end = Point::new(100.0, 0.0);
 
-        let mid = cubic_bezier_midpoint(start, cp1, cp2, end);
+        let mid = cubic_bezier_at(start, cp1, cp2, end, 0.5).0;
         // 0.125*0 + 0.375*30 + 0.375*70 + 0.125*100 = 11.25 + 26.25 + 12.5 = 50
         // 0.125*0 + 0.375*(-40) + 0.375*(-40) + 0.125*0 = -15 + -15 = -30
         assert_eq!(mid, Point::new(50.0, -30.0));
@@ -526,13 +804,13 @@ mod tests {
         let cp1 = Point::new(25.0, 25.0);
         let cp2 = Point::new(75.0, 75.0);
 
-        let mid = cubic_bezier_midpoint(start, cp1, cp2, end);
+        let mid = cubic_bezier_at(start, cp1, cp2, end, 0.5).0;
         // 0.125*0 + 0.375*25 + 0.375*75 + 0.125*100 = 9.375 + 28.125 + 12.5 = 50
         assert_eq!(mid, Point::new(50.0, 50.0));
 
         // Degenerate: all points the same
         let p = Point::new(5.0, 5.0);
-        let mid = cubic_bezier_midpoint(p, p, p, p);
+        let mid = cubic_bezier_at(p, p, p, p, 0.5).0;
         assert_eq!(mid, p);
     }
 }
@@ -2,20 +2,163 @@
 //!
 //! Unlike most types in this module, [`DiagramDefinition`] is a configuration
 //! container, not a [`Drawable`](crate::draw::Drawable): there is no diagram
-//! shape to render, only settings (canvas color, lifeline) that apply diagram-wide.
+//! shape to render, only settings (canvas color, padding and size, title and
+//! caption, lifeline, rank spacing, edge routing, grid columns, density, autocolor) that
+//! apply diagram-wide.
 
-use std::rc::Rc;
+use std::{fmt, rc::Rc, str::FromStr};
 
-use crate::{color::Color, draw::LifelineDefinition};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    color::Color,
+    draw::{LifelineDefinition, TextDefinition},
+    geometry::Insets,
+};
+
+/// How the canvas is fitted into an explicit SVG `width` and `height`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum CanvasFit {
+    /// Scale the whole canvas to fit, keeping its aspect ratio
+    #[default]
+    Contain,
+    /// Keep the canvas at its natural scale, centered and cropped to the size
+    None,
+}
+
+impl FromStr for CanvasFit {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "contain" => Ok(Self::Contain),
+            "none" => Ok(Self::None),
+            _ => Err("Invalid canvas fit"),
+        }
+    }
+}
+
+impl fmt::Display for CanvasFit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Contain => write!(f, "contain"),
+            Self::None => write!(f, "none"),
+        }
+    }
+}
+
+/// How the relations of a component diagram are routed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum EdgeRouting {
+    /// Route each relation according to its own arrow style
+    #[default]
+    Straight,
+    /// Route every relation with horizontal and vertical segments around
+    /// the other components, rounding the bends
+    Orthogonal,
+}
+
+impl FromStr for EdgeRouting {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "straight" => Ok(Self::Straight),
+            "orthogonal" => Ok(Self::Orthogonal),
+            _ => Err("Invalid edge routing"),
+        }
+    }
+}
+
+impl fmt::Display for EdgeRouting {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Straight => write!(f, "straight"),
+            Self::Orthogonal => write!(f, "orthogonal"),
+        }
+    }
+}
+
+/// How much space a layout leaves inside shapes, between elements, and
+/// around the canvas.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Density {
+    /// Minimal padding, spacing, and margins, e.g. to embed diagrams inline
+    Compact,
+    /// Balanced padding, spacing, and margins
+    #[default]
+    Comfortable,
+    /// Generous padding, spacing, and margins, e.g. for presentations
+    Spacious,
+}
+
+impl fmt::Display for Density {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Density::Compact => write!(f, "compact"),
+            Density::Comfortable => write!(f, "comfortable"),
+            Density::Spacious => write!(f, "spacious"),
+        }
+    }
+}
+
+impl FromStr for Density {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "compact" => Ok(Self::Compact),
+            "comfortable" => Ok(Self::Comfortable),
+            "spacious" => Ok(Self::Spacious),
+            _ => Err("Invalid density"),
+        }
+    }
+}
 
 /// Diagram-wide styling configuration.
 ///
-/// Defaults to a transparent canvas (`canvas_color` is `None`) and a default
-/// [`LifelineDefinition`].
+/// Defaults to a transparent canvas (`canvas_color` is `None`) padded by the
+/// exporter's margin and sized to its content, no title or caption, a default
+/// [`LifelineDefinition`], no rank spacing overrides, straight edge routing,
+/// an automatic number of grid columns, no density override, and autocolor off.
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct DiagramDefinition {
     canvas_color: Option<Color>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    canvas_padding: Option<Insets>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    width: Option<f32>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    height: Option<f32>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    fit: CanvasFit,
+    #[cfg_attr(feature = "serde", serde(default))]
+    title: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    title_text: Rc<TextDefinition>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    caption: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    caption_text: Rc<TextDefinition>,
     lifeline: Rc<LifelineDefinition>,
+    rank_spacing: Option<f32>,
+    intra_rank_spacing: Option<f32>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    edge_routing: EdgeRouting,
+    #[cfg_attr(feature = "serde", serde(default))]
+    columns: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    density: Option<Density>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    autocolor: bool,
 }
 
 impl DiagramDefinition {
@@ -31,11 +174,84 @@ impl DiagramDefinition {
         self.canvas_color
     }
 
+    /// Returns the space between the diagram content and the canvas edges,
+    /// if overridden.
+    pub fn canvas_padding(&self) -> Option<Insets> {
+        self.canvas_padding
+    }
+
+    /// Returns the explicit width of the rendered SVG, if set.
+    pub fn width(&self) -> Option<f32> {
+        self.width
+    }
+
+    /// Returns the explicit height of the rendered SVG, if set.
+    pub fn height(&self) -> Option<f32> {
+        self.height
+    }
+
+    /// Returns how the canvas is fitted into an explicit width and height.
+    pub fn fit(&self) -> CanvasFit {
+        self.fit
+    }
+
+    /// Returns the title drawn above the diagram, if any.
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /// Returns the text definition of the title.
+    pub fn title_text(&self) -> &Rc<TextDefinition> {
+        &self.title_text
+    }
+
+    /// Returns the caption drawn below the diagram, if any.
+    pub fn caption(&self) -> Option<&str> {
+        self.caption.as_deref()
+    }
+
+    /// Returns the text definition of the caption.
+    pub fn caption_text(&self) -> &Rc<TextDefinition> {
+        &self.caption_text
+    }
+
     /// Returns the lifeline definition.
     pub fn lifeline(&self) -> &Rc<LifelineDefinition> {
         &self.lifeline
     }
 
+    /// Returns the spacing between ranks of a layered layout, if overridden.
+    pub fn rank_spacing(&self) -> Option<f32> {
+        self.rank_spacing
+    }
+
+    /// Returns the spacing between nodes in the same rank of a layered
+    /// layout, if overridden.
+    pub fn intra_rank_spacing(&self) -> Option<f32> {
+        self.intra_rank_spacing
+    }
+
+    /// Returns how the relations of a component diagram are routed.
+    pub fn edge_routing(&self) -> EdgeRouting {
+        self.edge_routing
+    }
+
+    /// Returns the number of columns of a grid layout, if overridden.
+    pub fn columns(&self) -> Option<usize> {
+        self.columns
+    }
+
+    /// Returns the spacing density of the rendered diagram, if overridden.
+    pub fn density(&self) -> Option<Density> {
+        self.density
+    }
+
+    /// Returns `true` if top-level components without an explicit fill are
+    /// given a distinct generated fill color.
+    pub fn autocolor(&self) -> bool {
+        self.autocolor
+    }
+
     /// Sets the canvas (background) color.
     ///
     /// Use `None` to leave the diagram background transparent.
@@ -43,10 +259,95 @@ impl DiagramDefinition {
         self.canvas_color = color;
     }
 
+    /// Sets the space between the diagram content and the canvas edges.
+    ///
+    /// Use `None` to keep the exporter's margin.
+    pub fn set_canvas_padding(&mut self, padding: Option<Insets>) {
+        self.canvas_padding = padding;
+    }
+
+    /// Sets the explicit width of the rendered SVG.
+    ///
+    /// Use `None` to derive it from the height, or from the content.
+    pub fn set_width(&mut self, width: Option<f32>) {
+        self.width = width;
+    }
+
+    /// Sets the explicit height of the rendered SVG.
+    ///
+    /// Use `None` to derive it from the width, or from the content.
+    pub fn set_height(&mut self, height: Option<f32>) {
+        self.height = height;
+    }
+
+    /// Sets how the canvas is fitted into an explicit width and height.
+    pub fn set_fit(&mut self, fit: CanvasFit) {
+        self.fit = fit;
+    }
+
+    /// Sets the title drawn above the diagram.
+    pub fn set_title(&mut self, title: Option<String>) {
+        self.title = title;
+    }
+
+    /// Sets the text definition of the title.
+    pub fn set_title_text(&mut self, text: Rc<TextDefinition>) {
+        self.title_text = text;
+    }
+
+    /// Sets the caption drawn below the diagram.
+    pub fn set_caption(&mut self, caption: Option<String>) {
+        self.caption = caption;
+    }
+
+    /// Sets the text definition of the caption.
+    pub fn set_caption_text(&mut self, text: Rc<TextDefinition>) {
+        self.caption_text = text;
+    }
+
     /// Sets the lifeline definition.
     pub fn set_lifeline(&mut self, lifeline: Rc<LifelineDefinition>) {
         self.lifeline = lifeline;
     }
+
+    /// Sets the spacing between ranks of a layered layout.
+    ///
+    /// Use `None` to keep the layout engine's default.
+    pub fn set_rank_spacing(&mut self, spacing: Option<f32>) {
+        self.rank_spacing = spacing;
+    }
+
+    /// Sets the spacing between nodes in the same rank of a layered layout.
+    ///
+    /// Use `None` to keep the layout engine's default.
+    pub fn set_intra_rank_spacing(&mut self, spacing: Option<f32>) {
+        self.intra_rank_spacing = spacing;
+    }
+
+    /// Sets how the relations of a component diagram are routed.
+    pub fn set_edge_routing(&mut self, routing: EdgeRouting) {
+        self.edge_routing = routing;
+    }
+
+    /// Sets the number of columns of a grid layout.
+    ///
+    /// Use `None` to let the layout engine pick a roughly square grid.
+    pub fn set_columns(&mut self, columns: Option<usize>) {
+        self.columns = columns;
+    }
+
+    /// Sets the spacing density of the rendered diagram.
+    ///
+    /// Use `None` to keep the density of the layout configuration.
+    pub fn set_density(&mut self, density: Option<Density>) {
+        self.density = density;
+    }
+
+    /// Sets whether top-level components without an explicit fill are given
+    /// a generated fill color.
+    pub fn set_autocolor(&mut self, autocolor: bool) {
+        self.autocolor = autocolor;
+    }
 }
 
 #[cfg(test)]
@@ -66,6 +367,51 @@ mod tests {
         assert!(def.canvas_color().is_none());
     }
 
+    #[test]
+    fn test_set_title_and_caption() {
+        let mut def = DiagramDefinition::new();
+        assert_eq!(def.title(), None);
+        assert_eq!(def.caption(), None);
+
+        def.set_title(Some("Checkout".to_string()));
+        def.set_caption(Some("Figure 1".to_string()));
+        assert_eq!(def.title(), Some("Checkout"));
+        assert_eq!(def.caption(), Some("Figure 1"));
+    }
+
+    #[test]
+    fn test_set_rank_spacing() {
+        let mut def = DiagramDefinition::new();
+        assert_eq!(def.rank_spacing(), None);
+        assert_eq!(def.intra_rank_spacing(), None);
+
+        def.set_rank_spacing(Some(120.0));
+        def.set_intra_rank_spacing(Some(30.0));
+        assert_eq!(def.rank_spacing(), Some(120.0));
+        assert_eq!(def.intra_rank_spacing(), Some(30.0));
+    }
+
+    #[test]
+    fn test_edge_routing() {
+        let mut def = DiagramDefinition::new();
+        assert_eq!(def.edge_routing(), EdgeRouting::Straight);
+
+        def.set_edge_routing("orthogonal".parse().expect("valid edge routing"));
+        assert_eq!(def.edge_routing(), EdgeRouting::Orthogonal);
+        assert!("diagonal".parse::<EdgeRouting>().is_err());
+    }
+
+    #[test]
+    fn test_density() {
+        let mut def = DiagramDefinition::new();
+        assert_eq!(def.density(), None);
+
+        def.set_density(Some("spacious".parse().expect("valid density")));
+        assert_eq!(def.density(), Some(Density::Spacious));
+        assert_eq!(Density::Compact.to_string(), "compact");
+        assert!("roomy".parse::<Density>().is_err());
+    }
+
     #[test]
     fn test_set_lifeline() {
         let mut def = DiagramDefinition::new();
@@ -22,8 +22,11 @@ use std::rc::Rc;
 
 use svg::{self, node::element as svg_element};
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 use crate::{
-    color::Color,
+    color::{Color, Theme},
     draw::{Drawable, LayeredOutput, RenderLayer, StrokeDefinition, Text, TextDefinition},
     geometry::{Bounds, Insets, Point, Size},
 };
@@ -38,6 +41,7 @@ use crate::draw::StrokeStyle;
 /// and section separators. It follows the same pattern as other definition
 /// structs in the codebase (e.g., `ActivationBoxDefinition`, `LifelineDefinition`).
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct FragmentDefinition {
     /// The stroke styling for the fragment border
     border_stroke: Rc<StrokeDefinition>,
@@ -106,7 +110,7 @@ impl FragmentDefinition {
     }
 
     /// Gets the background color.
-    fn background_color(&self) -> Option<&Color> {
+    pub fn background_color(&self) -> Option<&Color> {
         self.background_color.as_ref()
     }
 
@@ -227,37 +231,57 @@ impl FragmentDefinition {
     }
 }
 
-impl Default for FragmentDefinition {
-    fn default() -> Self {
+impl FragmentDefinition {
+    /// Creates a fragment definition with the colors of `theme`.
+    ///
+    /// Section titles use a translucent text color so they read as secondary
+    /// to the operation label.
+    pub fn from_theme(theme: &Theme) -> Self {
         // Create default text definition for operation label
         let mut operation_label_text_definition = TextDefinition::new();
         operation_label_text_definition.set_font_size(9);
-        operation_label_text_definition.set_color(Some(Color::default()));
+        if let Some(family) = theme.font_family() {
+            operation_label_text_definition.set_font_family(family);
+        }
+        operation_label_text_definition.set_color(Some(theme.text()));
         operation_label_text_definition.set_padding(Insets::new(4.0, 8.0, 4.0, 8.0));
 
         // Create default text definition for section titles
         let mut section_title_text_definition = TextDefinition::new();
         section_title_text_definition.set_font_size(8);
-        section_title_text_definition
-            .set_color(Some(Color::new("#666666").expect("Invalid color")));
+        if let Some(family) = theme.font_family() {
+            section_title_text_definition.set_font_family(family);
+        }
+        section_title_text_definition.set_color(Some(theme.text().with_alpha(0.6)));
         section_title_text_definition.set_padding(Insets::new(2.0, 4.0, 2.0, 20.0));
 
+        let mut border_stroke = StrokeDefinition::default();
+        border_stroke.set_color(theme.stroke());
+        let mut separator_stroke = StrokeDefinition::default_dashed();
+        separator_stroke.set_color(theme.stroke());
+
         Self {
-            border_stroke: Rc::new(StrokeDefinition::default()),
+            border_stroke: Rc::new(border_stroke),
             background_color: None,
 
             operation_label_text_definition: Rc::new(operation_label_text_definition),
             section_title_text_definition: Rc::new(section_title_text_definition),
 
-            separator_stroke: Rc::new(StrokeDefinition::default_dashed()),
+            separator_stroke: Rc::new(separator_stroke),
 
-            pentagon_fill_color: Color::new("white").expect("Invalid color"),
+            pentagon_fill_color: theme.fill(),
 
             bounds_padding: Insets::new(0.0, 20.0, 0.0, 20.0),
         }
     }
 }
 
+impl Default for FragmentDefinition {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
 /// A section within a fragment block.
 ///
 /// Each section can have an optional title and a specific height
@@ -0,0 +1,260 @@
+//! Group boundary drawable for component diagrams.
+//!
+//! A group is a dashed, unfilled rectangle drawn around a set of components
+//! that are laid out together, with a title tab in its upper-left corner.
+//! Unlike a nested component, a group has no shape of its own: its members
+//! take part in the layout of the enclosing scope, and the boundary is sized
+//! afterwards to enclose them.
+//!
+//! The boundary is rendered on the [`RenderLayer::Background`] layer so that
+//! components and relations remain visible on top of it.
+
+use std::rc::Rc;
+
+use svg::{self, node::element as svg_element};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    color::{Color, Theme},
+    draw::{Drawable, LayeredOutput, RenderLayer, StrokeDefinition, Text, TextDefinition},
+    geometry::{Insets, Point, Size},
+};
+
+/// Styling configuration for group boundaries.
+///
+/// # Default Values
+///
+/// - **Stroke**: Dashed, in the theme's stroke color
+/// - **Tab fill color**: The theme's fill color
+/// - **Text**: Theme text definition
+/// - **Padding**: 15px between the boundary and its members
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GroupDefinition {
+    stroke: Rc<StrokeDefinition>,
+    text: Rc<TextDefinition>,
+    tab_fill_color: Color,
+    padding: Insets,
+    label_padding: Insets,
+}
+
+impl GroupDefinition {
+    /// Creates a new group definition with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a group definition with the colors of `theme`.
+    pub fn from_theme(theme: &Theme) -> Self {
+        let mut stroke = StrokeDefinition::default_dashed();
+        stroke.set_color(theme.stroke());
+        Self {
+            stroke: Rc::new(stroke),
+            text: Rc::new(TextDefinition::from_theme(theme)),
+            tab_fill_color: theme.fill(),
+            padding: Insets::uniform(15.0),
+            label_padding: Insets::new(4.0, 8.0, 4.0, 8.0),
+        }
+    }
+
+    /// Gets the stroke definition of the boundary.
+    pub fn stroke(&self) -> &Rc<StrokeDefinition> {
+        &self.stroke
+    }
+
+    /// Set stroke definition using Rc.
+    pub fn set_stroke(&mut self, stroke: Rc<StrokeDefinition>) {
+        self.stroke = stroke;
+    }
+
+    /// Gets the text definition used for the title.
+    pub fn text(&self) -> &Rc<TextDefinition> {
+        &self.text
+    }
+
+    /// Set text definition using Rc.
+    pub fn set_text(&mut self, text: Rc<TextDefinition>) {
+        self.text = text;
+    }
+
+    /// Returns the fill color of the title tab.
+    pub fn tab_fill_color(&self) -> Color {
+        self.tab_fill_color
+    }
+
+    /// Sets the fill color of the title tab.
+    pub fn set_tab_fill_color(&mut self, color: Color) {
+        self.tab_fill_color = color;
+    }
+
+    /// Returns the space between the boundary and its members.
+    pub fn padding(&self) -> Insets {
+        self.padding
+    }
+
+    /// Sets the space between the boundary and its members.
+    pub fn set_padding(&mut self, padding: Insets) {
+        self.padding = padding;
+    }
+
+    /// Returns the size of the title tab.
+    ///
+    /// An empty label has no tab.
+    pub fn label_size(&self, label: &str) -> Size {
+        if label.is_empty() {
+            return Size::zero();
+        }
+        Text::new(&self.text, label)
+            .size()
+            .add_padding(self.label_padding)
+    }
+
+    /// Returns the insets between the boundary and the bounds of its members.
+    ///
+    /// This is the padding with the title tab's height added on top.
+    pub fn content_insets(&self, label: &str) -> Insets {
+        let padding = self.padding;
+        Insets::new(
+            padding.top() + self.label_size(label).height(),
+            padding.right(),
+            padding.bottom(),
+            padding.left(),
+        )
+    }
+}
+
+impl Default for GroupDefinition {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+/// A dashed boundary rectangle with a title tab.
+///
+/// Like a highlight band, the size of a group is determined by the layout
+/// engine from the bounds of its members rather than by its content.
+///
+/// # Examples
+///
+/// ```
+/// # use std::rc::Rc;
+/// # use orrery_core::draw::{Drawable, Group, GroupDefinition};
+/// # use orrery_core::geometry::{Point, Size};
+/// let group = Group::new(
+///     Rc::new(GroupDefinition::new()),
+///     "Backend".to_string(),
+///     Size::new(300.0, 200.0),
+/// );
+/// let output = group.render_to_layers(Point::new(150.0, 100.0));
+/// let svg_nodes = output.render();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Group {
+    definition: Rc<GroupDefinition>,
+    label: String,
+    size: Size,
+}
+
+impl Group {
+    /// Creates a new group boundary.
+    ///
+    /// # Arguments
+    ///
+    /// * `definition` - The styling definition for the boundary
+    /// * `label` - The title displayed in the tab
+    /// * `size` - The total size of the boundary
+    pub fn new(definition: Rc<GroupDefinition>, label: String, size: Size) -> Self {
+        Self {
+            definition,
+            label,
+            size,
+        }
+    }
+
+    /// Returns the label of the group.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+}
+
+impl Drawable for Group {
+    fn render_to_layers(&self, position: Point) -> LayeredOutput {
+        let mut output = LayeredOutput::new();
+        let bounds = position.to_bounds(self.size);
+
+        let boundary = svg_element::Rectangle::new()
+            .set("x", bounds.min_x())
+            .set("y", bounds.min_y())
+            .set("width", self.size.width())
+            .set("height", self.size.height())
+            .set("fill", "none");
+        let boundary = crate::apply_stroke!(boundary, self.definition.stroke());
+        output.add_to_layer(RenderLayer::Background, Box::new(boundary));
+
+        if !self.label.is_empty() {
+            let label_size = self.definition.label_size(&self.label);
+            let tab_fill_color = self.definition.tab_fill_color();
+            let tab = svg_element::Rectangle::new()
+                .set("x", bounds.min_x())
+                .set("y", bounds.min_y())
+                .set("width", label_size.width())
+                .set("height", label_size.height())
+                .set("fill", tab_fill_color.to_string())
+                .set("fill-opacity", tab_fill_color.alpha());
+            let tab = crate::apply_stroke!(tab, self.definition.stroke());
+            output.add_to_layer(RenderLayer::Background, Box::new(tab));
+
+            let label_position = Point::new(
+                bounds.min_x() + label_size.width() / 2.0,
+                bounds.min_y() + label_size.height() / 2.0,
+            );
+            let text = Text::new(self.definition.text(), &self.label);
+            output.merge(text.render_to_layers(label_position));
+        }
+
+        output
+    }
+
+    fn size(&self) -> Size {
+        self.size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_group_definition_content_insets() {
+        let definition = GroupDefinition::new();
+        let padding = definition.padding();
+        assert_eq!(definition.content_insets(""), padding);
+
+        let insets = definition.content_insets("Backend");
+        let tab = definition.label_size("Backend");
+        assert_eq!(insets.top(), padding.top() + tab.height());
+        assert_eq!(insets.left(), padding.left());
+    }
+
+    #[test]
+    fn test_group_renders_dashed_unfilled_boundary() {
+        let group = Group::new(
+            Rc::new(GroupDefinition::new()),
+            "Backend".to_string(),
+            Size::new(200.0, 100.0),
+        );
+        assert_eq!(group.size(), Size::new(200.0, 100.0));
+
+        let output = group.render_to_layers(Point::new(100.0, 50.0));
+        let svg = output
+            .render()
+            .iter()
+            .map(|node| node.to_string())
+            .collect::<String>();
+        assert!(svg.contains(r#"fill="none""#));
+        assert!(svg.contains("stroke-dasharray"));
+        assert!(svg.contains("Backend"));
+    }
+}
@@ -0,0 +1,197 @@
+//! Highlight band drawable for sequence diagrams.
+//!
+//! A highlight band is a tinted background rectangle that marks a time window
+//! in a sequence diagram (e.g., "during transaction"). The band spans all
+//! lifelines horizontally and the enclosed messages vertically, with an
+//! optional label in its upper-left corner.
+//!
+//! The band is rendered on the [`RenderLayer::Background`] layer so that
+//! lifelines, messages, and other content remain visible on top of it.
+
+use std::rc::Rc;
+
+use svg::{self, node::element as svg_element};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    color::Color,
+    draw::{Drawable, LayeredOutput, RenderLayer, Text, TextDefinition},
+    geometry::{Insets, Point, Size},
+};
+
+/// Styling configuration for highlight bands.
+///
+/// # Default Values
+///
+/// - **Fill color**: Translucent yellow (`rgba(255, 230, 128, 0.35)`)
+/// - **Text**: Default text definition
+/// - **Label padding**: 5px on all sides
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HighlightDefinition {
+    fill_color: Color,
+    text: Rc<TextDefinition>,
+    label_padding: Insets,
+}
+
+impl HighlightDefinition {
+    /// Creates a new highlight definition with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the fill color of the band.
+    pub fn fill_color(&self) -> Color {
+        self.fill_color
+    }
+
+    /// Sets the fill color of the band.
+    pub fn set_fill_color(&mut self, color: Color) {
+        self.fill_color = color;
+    }
+
+    /// Gets the text definition used for the label.
+    pub fn text(&self) -> &Rc<TextDefinition> {
+        &self.text
+    }
+
+    /// Set text definition using Rc.
+    pub fn set_text(&mut self, text: Rc<TextDefinition>) {
+        self.text = text;
+    }
+
+    /// Returns the size reserved for the label at the top of the band.
+    ///
+    /// An empty label reserves no space.
+    pub fn label_size(&self, label: &str) -> Size {
+        if label.is_empty() {
+            return Size::zero();
+        }
+        Text::new(&self.text, label)
+            .size()
+            .add_padding(self.label_padding)
+    }
+}
+
+impl Default for HighlightDefinition {
+    fn default() -> Self {
+        Self {
+            fill_color: Color::new("rgba(255, 230, 128, 0.35)").expect("Invalid color"),
+            text: Rc::new(TextDefinition::default()),
+            label_padding: Insets::uniform(5.0),
+        }
+    }
+}
+
+/// A tinted background band with an optional label.
+///
+/// Unlike most drawables, the size of a highlight band is determined by the
+/// layout engine rather than by its content.
+///
+/// # Examples
+///
+/// ```
+/// # use std::rc::Rc;
+/// # use orrery_core::draw::{Drawable, Highlight, HighlightDefinition};
+/// # use orrery_core::geometry::{Point, Size};
+/// let highlight = Highlight::new(
+///     Rc::new(HighlightDefinition::new()),
+///     "during transaction".to_string(),
+///     Size::new(300.0, 120.0),
+/// );
+/// let output = highlight.render_to_layers(Point::new(150.0, 60.0));
+/// let svg_nodes = output.render();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Highlight {
+    definition: Rc<HighlightDefinition>,
+    label: String,
+    size: Size,
+}
+
+impl Highlight {
+    /// Creates a new highlight band.
+    ///
+    /// # Arguments
+    ///
+    /// * `definition` - The styling definition for the band
+    /// * `label` - The label displayed in the upper-left corner
+    /// * `size` - The total size of the band
+    pub fn new(definition: Rc<HighlightDefinition>, label: String, size: Size) -> Self {
+        Self {
+            definition,
+            label,
+            size,
+        }
+    }
+
+    /// Returns the label of the band.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+}
+
+impl Drawable for Highlight {
+    fn render_to_layers(&self, position: Point) -> LayeredOutput {
+        let mut output = LayeredOutput::new();
+        let bounds = position.to_bounds(self.size);
+        let fill_color = self.definition.fill_color();
+
+        let band = svg_element::Rectangle::new()
+            .set("x", bounds.min_x())
+            .set("y", bounds.min_y())
+            .set("width", self.size.width())
+            .set("height", self.size.height())
+            .set("fill", fill_color.to_string())
+            .set("fill-opacity", fill_color.alpha())
+            .set("stroke", "none");
+        output.add_to_layer(RenderLayer::Background, Box::new(band));
+
+        if !self.label.is_empty() {
+            let label_size = self.definition.label_size(&self.label);
+            let label_position = Point::new(
+                bounds.min_x() + label_size.width() / 2.0,
+                bounds.min_y() + label_size.height() / 2.0,
+            );
+            let text = Text::new(self.definition.text(), &self.label);
+            output.merge(text.render_to_layers(label_position));
+        }
+
+        output
+    }
+
+    fn size(&self) -> Size {
+        self.size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_highlight_definition_label_size() {
+        let definition = HighlightDefinition::new();
+        assert_eq!(definition.label_size(""), Size::zero());
+
+        let size = definition.label_size("during transaction");
+        assert!(size.width() > 10.0);
+        assert!(size.height() > 10.0);
+    }
+
+    #[test]
+    fn test_highlight_renders_to_background_layer() {
+        let highlight = Highlight::new(
+            Rc::new(HighlightDefinition::new()),
+            "tx".to_string(),
+            Size::new(200.0, 100.0),
+        );
+        assert_eq!(highlight.size(), Size::new(200.0, 100.0));
+
+        let output = highlight.render_to_layers(Point::new(100.0, 50.0));
+        // One group for the band and one for the label
+        assert_eq!(output.render().len(), 2);
+    }
+}
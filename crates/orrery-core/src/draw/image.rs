@@ -0,0 +1,123 @@
+//! Image definitions for icons drawn inside shapes.
+//!
+//! An [`ImageDefinition`] only records where an image comes from and where it
+//! sits inside its shape. Loading the image is left to the exporter, and the
+//! image never changes the shape's size.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    draw::text_positioning::{ContentAlign, HorizontalAlign, VerticalAlign},
+    geometry::{Bounds, Point, Size},
+};
+
+/// Gap between an image and the edge of its shape.
+const IMAGE_MARGIN: f32 = 4.0;
+
+/// A small image, such as a logo, drawn inside a shape.
+///
+/// # Examples
+///
+/// ```
+/// use orrery_core::{
+///     draw::{ContentAlign, ImageDefinition},
+///     geometry::{Bounds, Point, Size},
+/// };
+///
+/// let mut image = ImageDefinition::new("logo.png");
+/// image.set_align("top-right".parse::<ContentAlign>().unwrap());
+///
+/// let shape = Bounds::new_from_top_left(Point::new(0.0, 0.0), Size::new(100.0, 50.0));
+/// let bounds = image.bounds_in(shape);
+/// assert_eq!(bounds.min_point(), Point::new(80.0, 4.0));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ImageDefinition {
+    source: String,
+    align: ContentAlign,
+    size: f32,
+}
+
+impl ImageDefinition {
+    /// Create an image from a file path or `data:` URI, placed in the
+    /// top-left corner at 16 units square
+    pub fn new(source: impl Into<String>) -> Self {
+        Self {
+            source: source.into(),
+            align: ContentAlign::new(VerticalAlign::Top, HorizontalAlign::Left),
+            size: 16.0,
+        }
+    }
+
+    /// Get the file path or `data:` URI of the image
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Get the placement of the image inside its shape
+    pub fn align(&self) -> ContentAlign {
+        self.align
+    }
+
+    /// Get the width and height of the image
+    pub fn size(&self) -> f32 {
+        self.size
+    }
+
+    /// Set the file path or `data:` URI of the image
+    pub fn set_source(&mut self, source: impl Into<String>) {
+        self.source = source.into();
+    }
+
+    /// Set the placement of the image inside its shape
+    pub fn set_align(&mut self, align: ContentAlign) {
+        self.align = align;
+    }
+
+    /// Set the width and height of the image
+    pub fn set_size(&mut self, size: f32) {
+        self.size = size;
+    }
+
+    /// Returns where the image is drawn inside a shape occupying `shape_bounds`.
+    pub fn bounds_in(&self, shape_bounds: Bounds) -> Bounds {
+        let x = match self.align.horizontal() {
+            HorizontalAlign::Left => shape_bounds.min_x() + IMAGE_MARGIN,
+            HorizontalAlign::Center => shape_bounds.center().x() - self.size / 2.0,
+            HorizontalAlign::Right => shape_bounds.max_x() - IMAGE_MARGIN - self.size,
+        };
+        let y = match self.align.vertical() {
+            VerticalAlign::Top => shape_bounds.min_y() + IMAGE_MARGIN,
+            VerticalAlign::Center => shape_bounds.center().y() - self.size / 2.0,
+            VerticalAlign::Bottom => shape_bounds.max_y() - IMAGE_MARGIN - self.size,
+        };
+        Bounds::new_from_top_left(Point::new(x, y), Size::new(self.size, self.size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bounds_in_follows_alignment() {
+        let shape = Bounds::new_from_top_left(Point::new(10.0, 20.0), Size::new(100.0, 60.0));
+        let mut image = ImageDefinition::new("logo.png");
+
+        // Default: top-left corner
+        let bounds = image.bounds_in(shape);
+        assert_eq!(bounds.min_point(), Point::new(14.0, 24.0));
+        assert_eq!(bounds.to_size(), Size::new(16.0, 16.0));
+
+        image.set_align("bottom-left".parse().unwrap());
+        image.set_size(20.0);
+        let bounds = image.bounds_in(shape);
+        assert_eq!(bounds.min_point(), Point::new(14.0, 56.0));
+
+        image.set_align("center".parse().unwrap());
+        let bounds = image.bounds_in(shape);
+        assert_eq!(bounds.min_point(), Point::new(50.0, 40.0));
+    }
+}
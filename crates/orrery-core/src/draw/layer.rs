@@ -159,6 +159,44 @@ impl LayeredOutput {
         self.items.extend(other.items);
     }
 
+    /// Moves all nodes of `layer` into `wrapper`, such as a `<g>` or `<a>`
+    /// element, in order.
+    ///
+    /// The wrapper takes the place of the first moved node, so it renders at
+    /// the same depth as the nodes it wraps. Does nothing if the layer is
+    /// empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use orrery_core::draw::{RenderLayer, LayeredOutput};
+    /// # use svg::node::element::{Group, Rectangle, Title};
+    ///
+    /// let mut output = LayeredOutput::new();
+    /// output.add_to_layer(RenderLayer::Content, Box::new(Rectangle::new()));
+    ///
+    /// output.wrap_layer(RenderLayer::Content, Group::new().add(Title::new("api")));
+    ///
+    /// let svg = output.render()[0].to_string();
+    /// assert!(svg.contains("<g>\n<title>api</title>\n<rect/>\n</g>"));
+    /// ```
+    pub fn wrap_layer<N: svg::Node>(&mut self, layer: RenderLayer, mut wrapper: N) {
+        let Some(index) = self.items.iter().position(|(l, _)| *l == layer) else {
+            return;
+        };
+        let (wrapped, rest): (Vec<_>, Vec<_>) = std::mem::take(&mut self.items)
+            .into_iter()
+            .partition(|(l, _)| *l == layer);
+        for (_, node) in wrapped {
+            wrapper.append(node);
+        }
+
+        // Every node before `index` belongs to another layer, so the index is
+        // unchanged in the remaining nodes
+        self.items = rest;
+        self.items.insert(index, (layer, Box::new(wrapper)));
+    }
+
     /// Returns `true` if there are no nodes in any layer.
     ///
     /// # Example
@@ -205,13 +243,45 @@ impl LayeredOutput {
     /// // Background layer renders first, then Content
     /// assert_eq!(svg_nodes.len(), 2);
     /// ```
-    pub fn render(mut self) -> Vec<SvgNode> {
+    pub fn render(self) -> Vec<SvgNode> {
+        self.render_with_layer_order(|layer| layer)
+    }
+
+    /// Renders all layers to SVG groups in the order given by `key`.
+    ///
+    /// Behaves like [`render`](Self::render), except that layers are sorted
+    /// by the value `key` returns for each [`RenderLayer`] instead of by the
+    /// declaration order. Layers sharing a key keep their declaration order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use orrery_core::draw::{RenderLayer, LayeredOutput};
+    /// # use svg::node::element::Rectangle;
+    ///
+    /// let mut output = LayeredOutput::new();
+    /// output.add_to_layer(RenderLayer::Content, Box::new(Rectangle::new()));
+    /// output.add_to_layer(RenderLayer::Arrow, Box::new(Rectangle::new()));
+    ///
+    /// // Draw arrows below content
+    /// let svg_nodes = output.render_with_layer_order(|layer| match layer {
+    ///     RenderLayer::Arrow => (RenderLayer::Content, 0),
+    ///     layer => (layer, 1),
+    /// });
+    /// assert_eq!(svg_nodes.len(), 2);
+    /// ```
+    pub fn render_with_layer_order<K, F>(mut self, key: F) -> Vec<SvgNode>
+    where
+        K: Ord,
+        F: Fn(RenderLayer) -> K,
+    {
         if self.is_empty() {
             return Vec::new();
         }
 
-        // Sort all items by layer - Stable sorting
-        self.items.sort_by_key(|(layer, _)| *layer);
+        // Sort all items by layer key, then layer - Stable sorting
+        self.items
+            .sort_by(|(a, _), (b, _)| key(*a).cmp(&key(*b)).then(a.cmp(b)));
 
         let mut result = Vec::new();
         let mut current_layer = self.items[0].0;
@@ -309,4 +379,56 @@ mod tests {
         // Should have 1 group with both Content layer
         assert_eq!(nodes.len(), 1);
     }
+
+    #[test]
+    fn test_layered_output_render_with_layer_order() {
+        let mut output = LayeredOutput::new();
+        output.add_to_layer(RenderLayer::Arrow, Box::new(Rectangle::new()));
+        output.add_to_layer(RenderLayer::Content, Box::new(Rectangle::new()));
+        output.add_to_layer(RenderLayer::Text, Box::new(Rectangle::new()));
+
+        let layer_names = |nodes: Vec<SvgNode>| -> Vec<String> {
+            nodes
+                .iter()
+                .map(|node| {
+                    let rendered = node.to_string();
+                    let start = rendered.find("data-layer=\"").unwrap() + 12;
+                    let end = rendered[start..].find('"').unwrap();
+                    rendered[start..start + end].to_string()
+                })
+                .collect()
+        };
+
+        let nodes = output.render_with_layer_order(|layer| match layer {
+            RenderLayer::Arrow => (RenderLayer::Content, 0),
+            layer => (layer, 1),
+        });
+        assert_eq!(layer_names(nodes), ["arrow", "content", "text"]);
+    }
+
+    #[test]
+    fn test_wrap_layer_keeps_other_layers_and_order() {
+        let mut output = LayeredOutput::new();
+        output.add_to_layer(RenderLayer::Text, Box::new(Rectangle::new().set("id", "t")));
+        output.add_to_layer(
+            RenderLayer::Content,
+            Box::new(Rectangle::new().set("id", "a")),
+        );
+        output.add_to_layer(
+            RenderLayer::Content,
+            Box::new(Rectangle::new().set("id", "b")),
+        );
+
+        output.wrap_layer(RenderLayer::Content, svg_element::Group::new());
+        output.wrap_layer(RenderLayer::Arrow, svg_element::Group::new());
+
+        let nodes = output.render();
+        assert_eq!(nodes.len(), 2);
+        let content = nodes[0].to_string();
+        assert!(content.contains("data-layer=\"content\""));
+        let a = content.find("id=\"a\"").unwrap();
+        let b = content.find("id=\"b\"").unwrap();
+        assert!(content.matches("<g").count() == 2 && a < b, "{content}");
+        assert!(nodes[1].to_string().contains("id=\"t\""));
+    }
 }
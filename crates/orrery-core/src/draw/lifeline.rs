@@ -22,6 +22,9 @@ use std::rc::Rc;
 
 use svg::node::element as svg_element;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 use crate::{
     draw::{Drawable, LayeredOutput, RenderLayer, StrokeDefinition},
     geometry::{Point, Size},
@@ -37,6 +40,7 @@ use crate::{
 /// The default values match the original hardcoded implementation:
 /// - `stroke`: dashed style (4px dash pattern) with black color and 1.0 width
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct LifelineDefinition {
     /// The stroke styling for the lifeline
     stroke: Rc<StrokeDefinition>,
@@ -0,0 +1,96 @@
+//! Hyperlink definitions for shapes and arrows.
+//!
+//! A [`LinkDefinition`] turns an element into a clickable link by wrapping its
+//! SVG output in an `<a>` element. Like shadows, links are purely a rendering
+//! concern and never change an element's size.
+
+use svg::node::element as svg_element;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Target URL and browsing context of a hyperlink.
+///
+/// The `href` and `target` are set independently, since they come from
+/// separate attributes; a link without an `href` is not rendered.
+///
+/// # Examples
+///
+/// ```
+/// use orrery_core::draw::LinkDefinition;
+///
+/// let mut link = LinkDefinition::default();
+/// link.set_href("https://example.com/api");
+/// link.set_target("_blank");
+///
+/// let anchor = link.to_anchor().expect("link has an href").to_string();
+/// assert_eq!(
+///     anchor,
+///     r#"<a target="_blank" xlink:href="https://example.com/api"/>"#
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LinkDefinition {
+    href: Option<String>,
+    target: Option<String>,
+}
+
+impl LinkDefinition {
+    /// Get the URL the link points to
+    pub fn href(&self) -> Option<&str> {
+        self.href.as_deref()
+    }
+
+    /// Get the browsing context the link opens in, such as `_blank`
+    pub fn target(&self) -> Option<&str> {
+        self.target.as_deref()
+    }
+
+    /// Set the URL the link points to
+    pub fn set_href(&mut self, href: impl Into<String>) {
+        self.href = Some(href.into());
+    }
+
+    /// Set the browsing context the link opens in
+    pub fn set_target(&mut self, target: impl Into<String>) {
+        self.target = Some(target.into());
+    }
+
+    /// Returns the empty `<a>` element to wrap the linked element in, or
+    /// `None` if the link has no `href`.
+    ///
+    /// Attribute values are escaped when the SVG is written, so URLs may
+    /// contain quotes and ampersands.
+    pub fn to_anchor(&self) -> Option<svg_element::Anchor> {
+        let href = self.href.as_deref()?;
+        let anchor = svg_element::Anchor::new().set("xlink:href", href);
+        Some(match &self.target {
+            Some(target) => anchor.set("target", target.as_str()),
+            None => anchor,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_anchor_requires_href() {
+        let mut link = LinkDefinition::default();
+        link.set_target("_blank");
+        assert!(link.to_anchor().is_none());
+    }
+
+    #[test]
+    fn test_to_anchor_escapes_href() {
+        let mut link = LinkDefinition::default();
+        link.set_href(r#"https://example.com/?a=1&b="2""#);
+
+        assert_eq!(
+            link.to_anchor().unwrap().to_string(),
+            r#"<a xlink:href="https://example.com/?a=1&amp;b=&quot;2&quot;"/>"#
+        );
+    }
+}
@@ -60,8 +60,11 @@ use std::rc::Rc;
 
 use svg::{self, node::element as svg_element};
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 use crate::{
-    color::Color,
+    color::{Color, Theme},
     draw::{Drawable, LayeredOutput, RenderLayer, StrokeDefinition, Text, TextDefinition},
     geometry::{Insets, Point, Size},
 };
@@ -108,6 +111,7 @@ const CORNER_FOLD_SIZE: f32 = 12.0;
 /// # }
 /// ```
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct NoteDefinition {
     background_color: Option<Color>,
     stroke: Rc<StrokeDefinition>,
@@ -167,7 +171,7 @@ impl NoteDefinition {
     }
 
     /// Returns the background color of the note.
-    fn background_color(&self) -> Option<Color> {
+    pub fn background_color(&self) -> Option<Color> {
         self.background_color
     }
 
@@ -192,17 +196,27 @@ impl NoteDefinition {
     }
 }
 
-impl Default for NoteDefinition {
-    fn default() -> Self {
+impl NoteDefinition {
+    /// Creates a note definition with the colors of `theme`.
+    pub fn from_theme(theme: &Theme) -> Self {
+        let mut stroke = StrokeDefinition::default();
+        stroke.set_color(theme.stroke());
+        let text = TextDefinition::from_theme(theme);
         Self {
-            background_color: Some(Color::new("lightyellow").expect("Invalid color")),
-            stroke: Rc::new(StrokeDefinition::default()),
-            text: Rc::new(TextDefinition::default()),
+            background_color: Some(theme.note_fill()),
+            stroke: Rc::new(stroke),
+            text: Rc::new(text),
             min_width: None,
         }
     }
 }
 
+impl Default for NoteDefinition {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
 /// A note drawable with a dog-eared corner.
 ///
 /// `Note` represents a renderable note that combines a [`NoteDefinition`] with text content.
@@ -0,0 +1,162 @@
+//! Drop-shadow definitions for shapes.
+//!
+//! A [`ShadowDefinition`] is purely visual: it renders as an SVG `<filter>`
+//! referenced by the shape and never changes the shape's size, so shadows are
+//! allowed to overflow the layout bounds.
+
+use svg::node::element as svg_element;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::color::Color;
+
+/// Blur radius, offset and color of a drop shadow.
+///
+/// # Examples
+///
+/// ```
+/// use orrery_core::draw::ShadowDefinition;
+///
+/// let mut shadow = ShadowDefinition::default();
+/// shadow.set_blur(6.0);
+/// assert!(shadow.filter_id().starts_with("shadow-"));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ShadowDefinition {
+    blur: f32,
+    dx: f32,
+    dy: f32,
+    color: Color,
+}
+
+impl ShadowDefinition {
+    /// Get the standard deviation of the shadow blur
+    pub fn blur(&self) -> f32 {
+        self.blur
+    }
+
+    /// Get the horizontal offset of the shadow
+    pub fn dx(&self) -> f32 {
+        self.dx
+    }
+
+    /// Get the vertical offset of the shadow
+    pub fn dy(&self) -> f32 {
+        self.dy
+    }
+
+    /// Get the color of the shadow
+    pub fn color(&self) -> Color {
+        self.color
+    }
+
+    /// Set the standard deviation of the shadow blur
+    pub fn set_blur(&mut self, blur: f32) {
+        self.blur = blur;
+    }
+
+    /// Set the horizontal offset of the shadow
+    pub fn set_dx(&mut self, dx: f32) {
+        self.dx = dx;
+    }
+
+    /// Set the vertical offset of the shadow
+    pub fn set_dy(&mut self, dy: f32) {
+        self.dy = dy;
+    }
+
+    /// Set the color of the shadow
+    pub fn set_color(&mut self, color: Color) {
+        self.color = color;
+    }
+
+    /// Returns the SVG id of the filter drawing this shadow.
+    ///
+    /// Shadows with the same parameters share an id, so each distinct filter
+    /// only needs to be defined once per document.
+    pub fn filter_id(&self) -> String {
+        let id = format!(
+            "shadow-{}-{}-{}-{}-{}",
+            self.blur,
+            self.dx,
+            self.dy,
+            self.color.to_id_safe_string(),
+            self.color.alpha()
+        );
+        id.replace('.', "_")
+    }
+
+    /// Creates the `<filter>` element drawing this shadow under its source.
+    pub fn to_filter(&self) -> svg_element::Filter {
+        let blur = svg_element::FilterEffectGaussianBlur::new()
+            .set("in", "SourceAlpha")
+            .set("stdDeviation", self.blur)
+            .set("result", "blur");
+        let offset = svg_element::FilterEffectOffset::new()
+            .set("in", "blur")
+            .set("dx", self.dx)
+            .set("dy", self.dy)
+            .set("result", "offset");
+        let flood = svg_element::FilterEffectFlood::new()
+            .set("flood-color", self.color.to_string())
+            .set("flood-opacity", self.color.alpha());
+        let composite = svg_element::FilterEffectComposite::new()
+            .set("in2", "offset")
+            .set("operator", "in")
+            .set("result", "shadow");
+        let merge = svg_element::FilterEffectMerge::new()
+            .add(svg_element::FilterEffectMergeNode::new().set("in", "shadow"))
+            .add(svg_element::FilterEffectMergeNode::new().set("in", "SourceGraphic"));
+
+        // Widen the filter region so the blur is not clipped at the shape's edges.
+        svg_element::Filter::new()
+            .set("id", self.filter_id())
+            .set("x", "-50%")
+            .set("y", "-50%")
+            .set("width", "200%")
+            .set("height", "200%")
+            .add(blur)
+            .add(offset)
+            .add(flood)
+            .add(composite)
+            .add(merge)
+    }
+}
+
+impl Default for ShadowDefinition {
+    fn default() -> Self {
+        Self {
+            blur: 4.0,
+            dx: 2.0,
+            dy: 2.0,
+            color: Color::new("rgba(0, 0, 0, 0.2)").expect("valid shadow color"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_id_depends_on_parameters() {
+        let shadow = ShadowDefinition::default();
+        assert_eq!(shadow.filter_id(), ShadowDefinition::default().filter_id());
+        assert!(!shadow.filter_id().contains('.'));
+
+        let mut blurred = shadow.clone();
+        blurred.set_blur(8.0);
+        assert_ne!(shadow.filter_id(), blurred.filter_id());
+    }
+
+    #[test]
+    fn test_to_filter_blurs_and_offsets() {
+        let filter = ShadowDefinition::default().to_filter().to_string();
+        assert!(filter.contains("<feGaussianBlur"));
+        assert!(filter.contains("stdDeviation=\"4\""));
+        assert!(filter.contains("<feOffset"));
+        assert!(filter.contains("dx=\"2\""));
+    }
+}
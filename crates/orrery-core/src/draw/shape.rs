@@ -21,19 +21,20 @@ pub use interface::InterfaceDefinition;
 pub use oval::OvalDefinition;
 pub use rectangle::RectangleDefinition;
 
-use std::rc::Rc;
+use std::{any::Any, rc::Rc};
 
 use crate::{
     color::Color,
     draw::{
-        Drawable, LayeredOutput, RenderLayer, StrokeDefinition, TextDefinition,
-        text_positioning::TextPositioningStrategy,
+        Drawable, ImageDefinition, LayeredOutput, LinkDefinition, RenderLayer, ShadowDefinition,
+        StrokeDefinition, TextDefinition,
+        text_positioning::{ContentAlign, TextPositioningStrategy},
     },
     geometry::{Insets, Point, Size},
 };
 
 /// A trait for shape definitions that provide stateless calculations.
-pub trait ShapeDefinition: std::fmt::Debug {
+pub trait ShapeDefinition: std::fmt::Debug + Any {
     /// Returns true if this shape supports containing content
     /// Default implementation returns false for safety
     fn supports_content(&self) -> bool {
@@ -81,11 +82,91 @@ pub trait ShapeDefinition: std::fmt::Debug {
         Err("fill_color is not supported for this shape")
     }
 
-    /// Set the corner rounding for the rectangle
-    fn set_rounded(&mut self, _radius: usize) -> Result<(), &'static str> {
+    /// Get the fill color of the shape
+    fn fill_color(&self) -> Option<Color> {
+        None
+    }
+
+    /// Set the opacity of the fill, multiplied with the fill color's alpha
+    fn set_fill_opacity(&mut self, _opacity: f32) -> Result<(), &'static str> {
+        Err("fill_opacity is not supported for this shape")
+    }
+
+    /// Get the opacity of the fill
+    fn fill_opacity(&self) -> f32 {
+        1.0
+    }
+
+    /// Set the corner radius for the rectangle
+    fn set_rounded(&mut self, _radius: f32) -> Result<(), &'static str> {
         Err("rounded corners are not supported for this shape")
     }
 
+    /// Get the corner radius of the rectangle
+    fn rounded(&self) -> f32 {
+        0.0
+    }
+
+    /// Set the placement of the label inside the shape
+    fn set_content_align(&mut self, _align: ContentAlign) -> Result<(), &'static str> {
+        Err("content_align is not supported for this shape")
+    }
+
+    /// Get the placement of the label inside the shape
+    fn content_align(&self) -> ContentAlign {
+        ContentAlign::default()
+    }
+
+    /// Set the minimum inner size of the shape
+    fn set_min_size(&mut self, _size: Size) -> Result<(), &'static str> {
+        Err("min_width and min_height are not supported for this shape")
+    }
+
+    /// Get the minimum inner size of the shape
+    fn min_size(&self) -> Size {
+        Size::zero()
+    }
+
+    /// Set the drop shadow drawn under the shape
+    fn set_shadow(&mut self, _shadow: Option<ShadowDefinition>) -> Result<(), &'static str> {
+        Err("shadow is not supported for this shape")
+    }
+
+    /// Get the drop shadow drawn under the shape
+    fn shadow(&self) -> Option<&ShadowDefinition> {
+        None
+    }
+
+    /// Set the image drawn inside the shape
+    fn set_image(&mut self, _image: Option<ImageDefinition>) -> Result<(), &'static str> {
+        Err("icon is not supported for this shape")
+    }
+
+    /// Get the image drawn inside the shape
+    fn image(&self) -> Option<&ImageDefinition> {
+        None
+    }
+
+    /// Set the hover tooltip of the shape
+    fn set_tooltip(&mut self, _tooltip: Option<String>) -> Result<(), &'static str> {
+        Err("tooltip is not supported for this shape")
+    }
+
+    /// Get the hover tooltip of the shape
+    fn tooltip(&self) -> Option<&str> {
+        None
+    }
+
+    /// Set the hyperlink the shape opens when clicked
+    fn set_link(&mut self, _link: Option<LinkDefinition>) -> Result<(), &'static str> {
+        Err("href is not supported for this shape")
+    }
+
+    /// Get the hyperlink the shape opens when clicked
+    fn link(&self) -> Option<&LinkDefinition> {
+        None
+    }
+
     /// Get the stroke definition for the shape.
     fn stroke(&self) -> &Rc<StrokeDefinition>;
 
@@ -125,6 +206,95 @@ impl Clone for Box<dyn ShapeDefinition> {
     }
 }
 
+/// Serde support for `Rc<Box<dyn ShapeDefinition>>`.
+///
+/// Shape definitions are trait objects, so they are serialized as a
+/// definition tagged with the name of its shape.
+#[cfg(feature = "serde")]
+pub(crate) mod shape_definition_serde {
+    use std::{any::Any, rc::Rc};
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer, ser};
+
+    use super::{
+        ActorDefinition, BoundaryDefinition, ComponentDefinition, ControlDefinition,
+        EntityDefinition, InterfaceDefinition, OvalDefinition, RectangleDefinition,
+        ShapeDefinition,
+    };
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(tag = "shape", rename_all = "snake_case")]
+    enum TaggedDefinition {
+        Rectangle(RectangleDefinition),
+        Oval(OvalDefinition),
+        Component(ComponentDefinition),
+        Actor(ActorDefinition),
+        Boundary(BoundaryDefinition),
+        Control(ControlDefinition),
+        Entity(EntityDefinition),
+        Interface(InterfaceDefinition),
+    }
+
+    impl TaggedDefinition {
+        /// Tags a built-in shape definition, returning `None` for other
+        /// implementations of [`ShapeDefinition`].
+        fn from_definition(definition: &dyn ShapeDefinition) -> Option<Self> {
+            let definition: &dyn Any = definition;
+            let tagged = if let Some(d) = definition.downcast_ref::<RectangleDefinition>() {
+                Self::Rectangle(d.clone())
+            } else if let Some(d) = definition.downcast_ref::<OvalDefinition>() {
+                Self::Oval(d.clone())
+            } else if let Some(d) = definition.downcast_ref::<ComponentDefinition>() {
+                Self::Component(d.clone())
+            } else if let Some(d) = definition.downcast_ref::<ActorDefinition>() {
+                Self::Actor(d.clone())
+            } else if let Some(d) = definition.downcast_ref::<BoundaryDefinition>() {
+                Self::Boundary(d.clone())
+            } else if let Some(d) = definition.downcast_ref::<ControlDefinition>() {
+                Self::Control(d.clone())
+            } else if let Some(d) = definition.downcast_ref::<EntityDefinition>() {
+                Self::Entity(d.clone())
+            } else if let Some(d) = definition.downcast_ref::<InterfaceDefinition>() {
+                Self::Interface(d.clone())
+            } else {
+                return None;
+            };
+            Some(tagged)
+        }
+
+        fn into_definition(self) -> Box<dyn ShapeDefinition> {
+            match self {
+                Self::Rectangle(d) => Box::new(d),
+                Self::Oval(d) => Box::new(d),
+                Self::Component(d) => Box::new(d),
+                Self::Actor(d) => Box::new(d),
+                Self::Boundary(d) => Box::new(d),
+                Self::Control(d) => Box::new(d),
+                Self::Entity(d) => Box::new(d),
+                Self::Interface(d) => Box::new(d),
+            }
+        }
+    }
+
+    pub(crate) fn serialize<S: Serializer>(
+        definition: &Rc<Box<dyn ShapeDefinition>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        TaggedDefinition::from_definition(definition.as_ref().as_ref())
+            .ok_or_else(|| {
+                ser::Error::custom(format!("unsupported shape definition: {definition:?}"))
+            })?
+            .serialize(serializer)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Rc<Box<dyn ShapeDefinition>>, D::Error> {
+        let tagged = TaggedDefinition::deserialize(deserializer)?;
+        Ok(Rc::new(tagged.into_definition()))
+    }
+}
+
 /// A shape instance that combines a definition with content size and padding
 #[derive(Debug, Clone)]
 pub struct Shape {
@@ -158,6 +328,21 @@ impl Shape {
         self.definition.text_positioning_strategy()
     }
 
+    /// Get the placement of the label inside this shape
+    pub fn content_align(&self) -> ContentAlign {
+        self.definition.content_align()
+    }
+
+    /// Get the hover tooltip of this shape
+    pub fn tooltip(&self) -> Option<&str> {
+        self.definition.tooltip()
+    }
+
+    /// Get the hyperlink of this shape
+    pub fn link(&self) -> Option<&LinkDefinition> {
+        self.definition.link()
+    }
+
     /// Returns the inner size of the shape boundary, excluding stroke.
     /// This is the size needed to contain the content with padding.
     pub fn inner_size(&self) -> Size {
@@ -193,6 +378,16 @@ impl Shape {
         self.padding
     }
 
+    /// Get the drop shadow drawn under this shape
+    pub fn shadow(&self) -> Option<&ShadowDefinition> {
+        self.definition.shadow()
+    }
+
+    /// Get the image drawn inside this shape
+    pub fn image(&self) -> Option<&ImageDefinition> {
+        self.definition.image()
+    }
+
     /// Find the intersection point where a line from point a to point b intersects with this shape
     pub fn find_intersection(&self, a: Point, b: Point, a_size: Size) -> Point {
         self.definition.find_intersection(a, b, a_size)
@@ -236,7 +431,14 @@ impl Drawable for Shape {
     fn render_to_layers(&self, position: Point) -> LayeredOutput {
         let mut output = LayeredOutput::new();
         let size = self.inner_size();
-        let node = self.definition.render_to_svg(size, position);
+        let mut node = self.definition.render_to_svg(size, position);
+        if let Some(shadow) = self.shadow() {
+            node = Box::new(
+                svg::node::element::Group::new()
+                    .set("filter", format!("url(#{})", shadow.filter_id()))
+                    .add(node),
+            );
+        }
         output.add_to_layer(RenderLayer::Content, node);
         output
     }
@@ -436,6 +638,24 @@ mod tests {
         // Distance ≈ 0.000707 < 0.001 threshold, so returns b
         assert_point_eq(result, b);
     }
+
+    #[test]
+    fn test_fill_opacity_multiplies_fill_color_alpha() {
+        let mut rect = RectangleDefinition::new();
+        rect.set_fill_color(Some(Color::new("#ff000080").unwrap()))
+            .unwrap();
+        rect.set_fill_opacity(0.5).unwrap();
+
+        let svg = rect
+            .render_to_svg(Size::new(40.0, 20.0), Point::new(50.0, 50.0))
+            .to_string();
+
+        let expected = 128.0_f32 / 255.0 * 0.5;
+        assert!(
+            svg.contains(&format!("fill-opacity=\"{expected}\"")),
+            "{svg}"
+        );
+    }
 }
 
 #[cfg(test)]
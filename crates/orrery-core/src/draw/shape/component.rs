@@ -4,10 +4,16 @@ use std::{fmt, rc::Rc};
 
 use svg::{self, node::element as svg_element};
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 use super::{RectangleDefinition, ShapeDefinition, rectangle};
 use crate::{
     color::Color,
-    draw::{StrokeDefinition, TextDefinition, text_positioning::TextPositioningStrategy},
+    draw::{
+        ImageDefinition, LinkDefinition, ShadowDefinition, StrokeDefinition, TextDefinition,
+        text_positioning::{ContentAlign, TextPositioningStrategy},
+    },
     geometry::{Insets, Point, Size},
 };
 
@@ -39,7 +45,7 @@ impl Default for ComponentDefinition {
             ))
             .expect("Failed to set fill color");
         rectangle_definition
-            .set_rounded(10)
+            .set_rounded(10.0)
             .expect("Failed to set rounded");
 
         Self {
@@ -59,6 +65,7 @@ pub trait Icon {
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ComponentIcon;
 
 impl Icon for ComponentIcon {
@@ -92,6 +99,7 @@ impl Icon for ComponentIcon {
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct RectangleWithIconDefinition<I>
 where
     I: Icon + fmt::Debug + Clone + 'static,
@@ -104,14 +112,9 @@ impl<I> RectangleWithIconDefinition<I>
 where
     I: Icon + fmt::Debug + Clone + 'static,
 {
-    /// Get the fill color of the shape
-    fn fill_color(&self) -> Option<Color> {
-        self.rectangle_definition.fill_color()
-    }
-
-    /// Get the corner rounding of the shape
-    fn rounded(&self) -> usize {
-        self.rectangle_definition.rounded()
+    /// Get the corner radius used when drawing the shape at `size`
+    fn corner_radius(&self, size: Size) -> f32 {
+        self.rectangle_definition.corner_radius(size)
     }
 }
 
@@ -128,7 +131,11 @@ where
     }
 
     fn calculate_inner_size(&self, content_size: Size, padding: Insets) -> Size {
-        let min_size = self.icon.size().add_padding(Insets::uniform(10.0));
+        let min_size = self
+            .icon
+            .size()
+            .add_padding(Insets::uniform(10.0))
+            .max(self.rectangle_definition.min_size());
         let padded_icon_size = self
             .icon
             .size()
@@ -159,14 +166,78 @@ where
         self.rectangle_definition.set_stroke(stroke);
     }
 
+    fn set_shadow(&mut self, shadow: Option<ShadowDefinition>) -> Result<(), &'static str> {
+        self.rectangle_definition.set_shadow(shadow)
+    }
+
+    fn shadow(&self) -> Option<&ShadowDefinition> {
+        self.rectangle_definition.shadow()
+    }
+
+    fn set_tooltip(&mut self, tooltip: Option<String>) -> Result<(), &'static str> {
+        self.rectangle_definition.set_tooltip(tooltip)
+    }
+
+    fn tooltip(&self) -> Option<&str> {
+        self.rectangle_definition.tooltip()
+    }
+
+    fn set_link(&mut self, link: Option<LinkDefinition>) -> Result<(), &'static str> {
+        self.rectangle_definition.set_link(link)
+    }
+
+    fn link(&self) -> Option<&LinkDefinition> {
+        self.rectangle_definition.link()
+    }
+
+    fn set_image(&mut self, image: Option<ImageDefinition>) -> Result<(), &'static str> {
+        self.rectangle_definition.set_image(image)
+    }
+
+    fn image(&self) -> Option<&ImageDefinition> {
+        self.rectangle_definition.image()
+    }
+
+    fn fill_color(&self) -> Option<Color> {
+        self.rectangle_definition.fill_color()
+    }
+
     fn set_fill_color(&mut self, color: Option<Color>) -> Result<(), &'static str> {
         self.rectangle_definition.set_fill_color(color)
     }
 
-    fn set_rounded(&mut self, radius: usize) -> Result<(), &'static str> {
+    fn set_fill_opacity(&mut self, opacity: f32) -> Result<(), &'static str> {
+        self.rectangle_definition.set_fill_opacity(opacity)
+    }
+
+    fn fill_opacity(&self) -> f32 {
+        self.rectangle_definition.fill_opacity()
+    }
+
+    fn rounded(&self) -> f32 {
+        self.rectangle_definition.rounded()
+    }
+
+    fn set_rounded(&mut self, radius: f32) -> Result<(), &'static str> {
         self.rectangle_definition.set_rounded(radius)
     }
 
+    fn set_content_align(&mut self, align: ContentAlign) -> Result<(), &'static str> {
+        self.rectangle_definition.set_content_align(align)
+    }
+
+    fn set_min_size(&mut self, size: Size) -> Result<(), &'static str> {
+        self.rectangle_definition.set_min_size(size)
+    }
+
+    fn min_size(&self) -> Size {
+        self.rectangle_definition.min_size()
+    }
+
+    fn content_align(&self) -> ContentAlign {
+        self.rectangle_definition.content_align()
+    }
+
     fn text_positioning_strategy(&self) -> TextPositioningStrategy {
         TextPositioningStrategy::InContent
     }
@@ -194,20 +265,28 @@ where
             .set("y", bounds.min_y())
             .set("width", size.width())
             .set("height", size.height())
-            .set("fill", "white")
-            .set("rx", self.rounded());
+            .set("fill", "white");
+        let radius = self.corner_radius(size);
+        let rect = if radius > 0.0 {
+            rect.set("rx", radius).set("ry", radius)
+        } else {
+            rect
+        };
 
         let mut rect = crate::apply_stroke!(rect, self.stroke());
 
         if let Some(fill_color) = self.fill_color() {
             rect = rect
                 .set("fill", fill_color.to_string())
-                .set("fill-opacity", fill_color.alpha());
+                .set("fill-opacity", fill_color.alpha() * self.fill_opacity());
         }
 
         group = group.add(rect);
 
-        let component_icon = self.icon.render_to_svg(self.stroke(), self.fill_color());
+        let icon_fill = self
+            .fill_color()
+            .map(|color| color.with_alpha(color.alpha() * self.fill_opacity()));
+        let component_icon = self.icon.render_to_svg(self.stroke(), icon_fill);
 
         // Create a group for the icon with transform to position it
         let icon_group = svg_element::Group::new()
@@ -4,19 +4,33 @@ use std::rc::Rc;
 
 use svg::{self, node::element as svg_element};
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 use super::ShapeDefinition;
 use crate::{
     color::Color,
-    draw::{StrokeDefinition, TextDefinition},
+    draw::{LinkDefinition, ShadowDefinition, StrokeDefinition, TextDefinition},
     geometry::{Insets, Point, Size},
 };
 
 /// UML Control shape definition - a circle with an arrow pointing right
 /// This is a content-free shape that cannot contain nested elements
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ControlDefinition {
     fill_color: Option<Color>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "crate::draw::stroke::default_opacity")
+    )]
+    fill_opacity: f32,
     stroke: Rc<StrokeDefinition>,
+    shadow: Option<ShadowDefinition>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    tooltip: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    link: Option<LinkDefinition>,
     text: Rc<TextDefinition>,
 }
 
@@ -25,18 +39,17 @@ impl ControlDefinition {
     pub fn new() -> Self {
         Self::default()
     }
-
-    /// Get the fill color of the control
-    fn fill_color(&self) -> Option<Color> {
-        self.fill_color
-    }
 }
 
 impl Default for ControlDefinition {
     fn default() -> Self {
         Self {
             fill_color: Some(Color::new("white").expect("Failed to create white color")),
+            fill_opacity: 1.0,
             stroke: Rc::new(StrokeDefinition::default_solid()),
+            shadow: None,
+            tooltip: None,
+            link: None,
             text: Rc::new(TextDefinition::default()),
         }
     }
@@ -55,11 +68,24 @@ impl ShapeDefinition for ControlDefinition {
         &self.stroke
     }
 
+    fn fill_color(&self) -> Option<Color> {
+        self.fill_color
+    }
+
     fn set_fill_color(&mut self, color: Option<Color>) -> Result<(), &'static str> {
         self.fill_color = color;
         Ok(())
     }
 
+    fn set_fill_opacity(&mut self, opacity: f32) -> Result<(), &'static str> {
+        self.fill_opacity = opacity;
+        Ok(())
+    }
+
+    fn fill_opacity(&self) -> f32 {
+        self.fill_opacity
+    }
+
     fn text(&self) -> &Rc<TextDefinition> {
         &self.text
     }
@@ -72,6 +98,33 @@ impl ShapeDefinition for ControlDefinition {
         self.stroke = stroke;
     }
 
+    fn set_shadow(&mut self, shadow: Option<ShadowDefinition>) -> Result<(), &'static str> {
+        self.shadow = shadow;
+        Ok(())
+    }
+
+    fn shadow(&self) -> Option<&ShadowDefinition> {
+        self.shadow.as_ref()
+    }
+
+    fn set_tooltip(&mut self, tooltip: Option<String>) -> Result<(), &'static str> {
+        self.tooltip = tooltip;
+        Ok(())
+    }
+
+    fn tooltip(&self) -> Option<&str> {
+        self.tooltip.as_deref()
+    }
+
+    fn set_link(&mut self, link: Option<LinkDefinition>) -> Result<(), &'static str> {
+        self.link = link;
+        Ok(())
+    }
+
+    fn link(&self) -> Option<&LinkDefinition> {
+        self.link.as_ref()
+    }
+
     fn render_to_svg(&self, _size: Size, position: Point) -> Box<dyn svg::Node> {
         // Create group element to contain circle and arrow
         let mut group = svg_element::Group::new().set("id", "control-group");
@@ -90,7 +143,7 @@ impl ShapeDefinition for ControlDefinition {
         if let Some(fill_color) = self.fill_color() {
             circle = circle
                 .set("fill", fill_color.to_string())
-                .set("fill-opacity", fill_color.alpha());
+                .set("fill-opacity", fill_color.alpha() * self.fill_opacity);
         }
 
         group = group.add(circle);
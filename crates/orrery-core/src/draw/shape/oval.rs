@@ -4,19 +4,41 @@ use std::rc::Rc;
 
 use svg::{self, node::element as svg_element};
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 use super::ShapeDefinition;
 use crate::{
     color::Color,
-    draw::{StrokeDefinition, TextDefinition, text_positioning::TextPositioningStrategy},
+    draw::{
+        LinkDefinition, ShadowDefinition, StrokeDefinition, TextDefinition,
+        text_positioning::{ContentAlign, TextPositioningStrategy},
+    },
     geometry::{Insets, Point, Size},
 };
 
 /// Oval shape definition
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct OvalDefinition {
     fill_color: Option<Color>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "crate::draw::stroke::default_opacity")
+    )]
+    fill_opacity: f32,
     stroke: Rc<StrokeDefinition>,
+    shadow: Option<ShadowDefinition>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    tooltip: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    link: Option<LinkDefinition>,
     text: Rc<TextDefinition>,
+    content_align: ContentAlign,
+    #[cfg_attr(feature = "serde", serde(default))]
+    min_width: f32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    min_height: f32,
 }
 
 impl OvalDefinition {
@@ -24,19 +46,21 @@ impl OvalDefinition {
     pub fn new() -> Self {
         Self::default()
     }
-
-    /// Get the fill color of the oval
-    fn fill_color(&self) -> Option<Color> {
-        self.fill_color
-    }
 }
 
 impl Default for OvalDefinition {
     fn default() -> Self {
         Self {
             fill_color: None,
+            fill_opacity: 1.0,
             stroke: Rc::new(StrokeDefinition::default_solid()),
+            shadow: None,
+            tooltip: None,
+            link: None,
             text: Rc::new(TextDefinition::default()),
+            content_align: ContentAlign::default(),
+            min_width: 0.0,
+            min_height: 0.0,
         }
     }
 }
@@ -92,7 +116,7 @@ impl ShapeDefinition for OvalDefinition {
         // The largest rectangle that fits in an ellipse with semi-axes (a,b) has dimensions:
         // width = a√2, height = b√2
         // So we need to scale up the content to create an ellipse that can contain it
-        let min_size = Size::new(10.0, 10.0);
+        let min_size = Size::new(10.0, 10.0).max(self.min_size());
         let sqrt_2 = 2.0_f32.sqrt();
         content_size
             .scale(sqrt_2)
@@ -108,11 +132,43 @@ impl ShapeDefinition for OvalDefinition {
         &self.stroke
     }
 
+    fn fill_color(&self) -> Option<Color> {
+        self.fill_color
+    }
+
     fn set_fill_color(&mut self, color: Option<Color>) -> Result<(), &'static str> {
         self.fill_color = color;
         Ok(())
     }
 
+    fn set_fill_opacity(&mut self, opacity: f32) -> Result<(), &'static str> {
+        self.fill_opacity = opacity;
+        Ok(())
+    }
+
+    fn fill_opacity(&self) -> f32 {
+        self.fill_opacity
+    }
+
+    fn set_content_align(&mut self, align: ContentAlign) -> Result<(), &'static str> {
+        self.content_align = align;
+        Ok(())
+    }
+
+    fn content_align(&self) -> ContentAlign {
+        self.content_align
+    }
+
+    fn set_min_size(&mut self, size: Size) -> Result<(), &'static str> {
+        self.min_width = size.width();
+        self.min_height = size.height();
+        Ok(())
+    }
+
+    fn min_size(&self) -> Size {
+        Size::new(self.min_width, self.min_height)
+    }
+
     fn text(&self) -> &Rc<TextDefinition> {
         &self.text
     }
@@ -125,6 +181,33 @@ impl ShapeDefinition for OvalDefinition {
         self.stroke = stroke;
     }
 
+    fn set_shadow(&mut self, shadow: Option<ShadowDefinition>) -> Result<(), &'static str> {
+        self.shadow = shadow;
+        Ok(())
+    }
+
+    fn shadow(&self) -> Option<&ShadowDefinition> {
+        self.shadow.as_ref()
+    }
+
+    fn set_tooltip(&mut self, tooltip: Option<String>) -> Result<(), &'static str> {
+        self.tooltip = tooltip;
+        Ok(())
+    }
+
+    fn tooltip(&self) -> Option<&str> {
+        self.tooltip.as_deref()
+    }
+
+    fn set_link(&mut self, link: Option<LinkDefinition>) -> Result<(), &'static str> {
+        self.link = link;
+        Ok(())
+    }
+
+    fn link(&self) -> Option<&LinkDefinition> {
+        self.link.as_ref()
+    }
+
     fn text_positioning_strategy(&self) -> TextPositioningStrategy {
         TextPositioningStrategy::InContent
     }
@@ -146,7 +229,7 @@ impl ShapeDefinition for OvalDefinition {
         if let Some(fill_color) = self.fill_color() {
             ellipse = ellipse
                 .set("fill", fill_color.to_string())
-                .set("fill-opacity", fill_color.alpha());
+                .set("fill-opacity", fill_color.alpha() * self.fill_opacity);
         }
 
         ellipse.into()
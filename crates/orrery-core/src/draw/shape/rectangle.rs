@@ -4,20 +4,43 @@ use std::rc::Rc;
 
 use svg::{self, node::element as svg_element};
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 use super::ShapeDefinition;
 use crate::{
     color::Color,
-    draw::{StrokeDefinition, TextDefinition, text_positioning::TextPositioningStrategy},
+    draw::{
+        ImageDefinition, LinkDefinition, ShadowDefinition, StrokeDefinition, TextDefinition,
+        text_positioning::{ContentAlign, TextPositioningStrategy},
+    },
     geometry::{Insets, Point, Size},
 };
 
 /// Rectangle shape definition
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct RectangleDefinition {
     fill_color: Option<Color>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "crate::draw::stroke::default_opacity")
+    )]
+    fill_opacity: f32,
     stroke: Rc<StrokeDefinition>,
-    rounded: usize,
+    shadow: Option<ShadowDefinition>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    tooltip: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    link: Option<LinkDefinition>,
+    image: Option<ImageDefinition>,
+    rounded: f32,
     text: Rc<TextDefinition>,
+    content_align: ContentAlign,
+    #[cfg_attr(feature = "serde", serde(default))]
+    min_width: f32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    min_height: f32,
 }
 
 impl RectangleDefinition {
@@ -26,14 +49,12 @@ impl RectangleDefinition {
         Self::default()
     }
 
-    /// Get the fill color of the rectangle
-    pub fn fill_color(&self) -> Option<Color> {
-        self.fill_color
-    }
-
-    /// Get the corner rounding of the rectangle
-    pub fn rounded(&self) -> usize {
-        self.rounded
+    /// Get the corner radius used when drawing the rectangle at `size`.
+    ///
+    /// The radius is clamped to half of the smaller side, so a very large
+    /// radius yields a pill shape rather than a malformed one.
+    pub fn corner_radius(&self, size: Size) -> f32 {
+        self.rounded.min(size.width().min(size.height()) / 2.0)
     }
 }
 
@@ -41,9 +62,17 @@ impl Default for RectangleDefinition {
     fn default() -> Self {
         Self {
             fill_color: None,
+            fill_opacity: 1.0,
             stroke: Rc::new(StrokeDefinition::default_solid()),
-            rounded: 0,
+            shadow: None,
+            tooltip: None,
+            link: None,
+            image: None,
+            rounded: 0.0,
             text: Rc::new(TextDefinition::default()),
+            content_align: ContentAlign::default(),
+            min_width: 0.0,
+            min_height: 0.0,
         }
     }
 }
@@ -54,7 +83,7 @@ impl ShapeDefinition for RectangleDefinition {
     }
 
     fn calculate_inner_size(&self, content_size: Size, padding: Insets) -> Size {
-        let min_size = Size::new(10.0, 10.0);
+        let min_size = Size::new(10.0, 10.0).max(self.min_size());
         content_size.add_padding(padding).max(min_size)
     }
 
@@ -66,16 +95,52 @@ impl ShapeDefinition for RectangleDefinition {
         &self.stroke
     }
 
+    fn fill_color(&self) -> Option<Color> {
+        self.fill_color
+    }
+
     fn set_fill_color(&mut self, color: Option<Color>) -> Result<(), &'static str> {
         self.fill_color = color;
         Ok(())
     }
 
-    fn set_rounded(&mut self, radius: usize) -> Result<(), &'static str> {
+    fn set_fill_opacity(&mut self, opacity: f32) -> Result<(), &'static str> {
+        self.fill_opacity = opacity;
+        Ok(())
+    }
+
+    fn fill_opacity(&self) -> f32 {
+        self.fill_opacity
+    }
+
+    fn rounded(&self) -> f32 {
+        self.rounded
+    }
+
+    fn set_rounded(&mut self, radius: f32) -> Result<(), &'static str> {
         self.rounded = radius;
         Ok(())
     }
 
+    fn set_content_align(&mut self, align: ContentAlign) -> Result<(), &'static str> {
+        self.content_align = align;
+        Ok(())
+    }
+
+    fn content_align(&self) -> ContentAlign {
+        self.content_align
+    }
+
+    fn set_min_size(&mut self, size: Size) -> Result<(), &'static str> {
+        self.min_width = size.width();
+        self.min_height = size.height();
+        Ok(())
+    }
+
+    fn min_size(&self) -> Size {
+        Size::new(self.min_width, self.min_height)
+    }
+
     fn text(&self) -> &Rc<TextDefinition> {
         &self.text
     }
@@ -88,6 +153,42 @@ impl ShapeDefinition for RectangleDefinition {
         self.stroke = stroke;
     }
 
+    fn set_shadow(&mut self, shadow: Option<ShadowDefinition>) -> Result<(), &'static str> {
+        self.shadow = shadow;
+        Ok(())
+    }
+
+    fn shadow(&self) -> Option<&ShadowDefinition> {
+        self.shadow.as_ref()
+    }
+
+    fn set_tooltip(&mut self, tooltip: Option<String>) -> Result<(), &'static str> {
+        self.tooltip = tooltip;
+        Ok(())
+    }
+
+    fn tooltip(&self) -> Option<&str> {
+        self.tooltip.as_deref()
+    }
+
+    fn set_link(&mut self, link: Option<LinkDefinition>) -> Result<(), &'static str> {
+        self.link = link;
+        Ok(())
+    }
+
+    fn link(&self) -> Option<&LinkDefinition> {
+        self.link.as_ref()
+    }
+
+    fn set_image(&mut self, image: Option<ImageDefinition>) -> Result<(), &'static str> {
+        self.image = image;
+        Ok(())
+    }
+
+    fn image(&self) -> Option<&ImageDefinition> {
+        self.image.as_ref()
+    }
+
     fn text_positioning_strategy(&self) -> TextPositioningStrategy {
         TextPositioningStrategy::InContent
     }
@@ -103,15 +204,20 @@ impl ShapeDefinition for RectangleDefinition {
             .set("y", bounds.min_y())
             .set("width", size.width())
             .set("height", size.height())
-            .set("fill", "white")
-            .set("rx", self.rounded());
+            .set("fill", "white");
+        let radius = self.corner_radius(size);
+        let rect = if radius > 0.0 {
+            rect.set("rx", radius).set("ry", radius)
+        } else {
+            rect
+        };
 
         let mut rect = crate::apply_stroke!(rect, &self.stroke);
 
         if let Some(fill_color) = self.fill_color() {
             rect = rect
                 .set("fill", fill_color.to_string())
-                .set("fill-opacity", fill_color.alpha());
+                .set("fill-opacity", fill_color.alpha() * self.fill_opacity);
         }
 
         rect.into()
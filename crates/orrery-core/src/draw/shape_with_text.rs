@@ -4,8 +4,11 @@
 //! optional header [`Text`] label, handling size calculation and positioning.
 
 use crate::{
-    draw::{Drawable, LayeredOutput, Shape, Text, text_positioning::TextPositioningStrategy},
-    geometry::{Point, Size},
+    draw::{
+        Drawable, ImageDefinition, LayeredOutput, LinkDefinition, ShadowDefinition, Shape, Text,
+        text_positioning::{HorizontalAlign, TextPositioningStrategy, VerticalAlign},
+    },
+    geometry::{Bounds, Point, Size},
 };
 
 /// A drawable that combines a shape with optional header text.
@@ -78,6 +81,11 @@ impl<'a> ShapeWithText<'a> {
         Ok(())
     }
 
+    /// Returns the text drawn with the shape, if any.
+    pub fn text(&self) -> Option<&Text<'a>> {
+        self.text.as_ref()
+    }
+
     /// Returns the size of the text component, or zero size if no text is present.
     pub fn text_size(&self) -> Size {
         self.text.as_ref().map(|t| t.size()).unwrap_or_default()
@@ -97,6 +105,39 @@ impl<'a> ShapeWithText<'a> {
         self.inner_content_size
     }
 
+    /// Get the drop shadow drawn under the shape
+    pub fn shadow(&self) -> Option<&ShadowDefinition> {
+        self.shape.shadow()
+    }
+
+    /// Get the hover tooltip of the shape
+    pub fn tooltip(&self) -> Option<&str> {
+        self.shape.tooltip()
+    }
+
+    /// Get the hyperlink of the shape
+    pub fn link(&self) -> Option<&LinkDefinition> {
+        self.shape.link()
+    }
+
+    /// Get the image drawn inside the shape
+    pub fn image(&self) -> Option<&ImageDefinition> {
+        self.shape.image()
+    }
+
+    /// Returns where the shape's image is drawn when this shape is drawn at
+    /// `position`, or `None` if the shape has no image.
+    pub fn image_bounds(&self, position: Point) -> Option<Bounds> {
+        let image = self.image()?;
+        let shape_size = self.shape.inner_size();
+        let shape_position = self.text_positioning_strategy.calculate_shape_position(
+            position,
+            shape_size,
+            self.text_size(),
+        );
+        Some(image.bounds_in(shape_position.to_bounds(shape_size)))
+    }
+
     /// Finds the intersection point of a line (from point a to point b) with the shape boundary.
     pub fn find_intersection(&self, a: Point, b: Point) -> Point {
         self.shape.find_intersection(a, b, self.size())
@@ -122,6 +163,14 @@ impl<'a> ShapeWithText<'a> {
         )
     }
 
+    /// Returns the center position of the text label when this shape is drawn
+    /// at `position`, or `None` if the shape has no label.
+    pub fn text_position(&self, position: Point) -> Option<Point> {
+        self.text
+            .as_ref()
+            .map(|_| self.calculate_text_position(position))
+    }
+
     /// Calculates the position where text should be rendered relative to the shape.
     fn calculate_text_position(&self, total_position: Point) -> Point {
         if self.text.is_none() {
@@ -132,14 +181,37 @@ impl<'a> ShapeWithText<'a> {
         let text_size = self.text_size();
         let has_inner_content = self.inner_content_size.is_some_and(|s| !s.is_zero());
 
-        self.text_positioning_strategy.calculate_text_position(
+        let position = self.text_positioning_strategy.calculate_text_position(
             total_position,
             shape_size,
             text_size,
             self.shape.shape_to_container_min_point(),
             self.shape_to_container_min_point_no_top_padding(),
             has_inner_content,
-        )
+        );
+        if self.text_positioning_strategy != TextPositioningStrategy::InContent {
+            return position;
+        }
+
+        // Move the centered label to the shape's content alignment, inset by its padding
+        let align = self.shape.content_align();
+        let bounds = total_position.to_bounds(shape_size);
+        let padding = self.shape.padding();
+
+        let x = match align.horizontal() {
+            HorizontalAlign::Left => bounds.min_x() + padding.left() + text_size.width() / 2.0,
+            HorizontalAlign::Center => position.x(),
+            HorizontalAlign::Right => bounds.max_x() - padding.right() - text_size.width() / 2.0,
+        };
+        // With inner content the label always stays above the nested elements
+        let y = match align.vertical() {
+            _ if has_inner_content => position.y(),
+            VerticalAlign::Top => bounds.min_y() + padding.top() + text_size.height() / 2.0,
+            VerticalAlign::Center => position.y(),
+            VerticalAlign::Bottom => bounds.max_y() - padding.bottom() - text_size.height() / 2.0,
+        };
+
+        Point::new(x, y)
     }
 }
 
@@ -180,9 +252,12 @@ mod tests {
     use std::rc::Rc;
 
     use super::*;
-    use crate::draw::{
-        TextDefinition,
-        shape::{ActorDefinition, RectangleDefinition, ShapeDefinition},
+    use crate::{
+        draw::{
+            TextDefinition,
+            shape::{ActorDefinition, RectangleDefinition, ShapeDefinition},
+        },
+        geometry::Insets,
     };
 
     /// Helper function to create a Rectangle shape (content-supporting)
@@ -367,6 +442,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_shape_with_text_content_align_top_left() {
+        let mut rect_def = RectangleDefinition::new();
+        rect_def
+            .set_content_align("top-left".parse().unwrap())
+            .unwrap();
+        let rect_def: Rc<Box<dyn ShapeDefinition>> = Rc::new(Box::new(rect_def));
+        let mut shape = Shape::new(rect_def);
+        shape.set_padding(Insets::uniform(10.0));
+
+        let text_def = TextDefinition::default();
+        let text = Text::new(&text_def, "Header");
+        let text_size = text.size();
+
+        let mut shape_with_text = ShapeWithText::new(shape, Some(text));
+        shape_with_text
+            .set_inner_content_size(Size::new(200.0, 100.0))
+            .unwrap();
+
+        let position = Point::new(300.0, 200.0);
+        let bounds = position.to_bounds(shape_with_text.shape.inner_size());
+        let text_position = shape_with_text.calculate_text_position(position);
+
+        // Label hugs the left inset instead of the horizontal center
+        assert_eq!(
+            text_position.x(),
+            bounds.min_x() + 10.0 + text_size.width() / 2.0
+        );
+        assert!(text_position.x() < position.x());
+        // With inner content the label stays at the top of the box
+        assert_eq!(text_position.y(), bounds.min_y() + text_size.height() / 2.0);
+    }
+
     #[test]
     fn test_shape_with_text_set_inner_content_size_error() {
         // Content-free shape should reject inner content
@@ -6,7 +6,7 @@
 //! # Overview
 //!
 //! Exported types:
-//! - [`StrokeDefinition`]: The main struct containing all stroke properties (color, width, style, cap, join)
+//! - [`StrokeDefinition`]: The main struct containing all stroke properties (color, opacity, width, style, cap, join)
 //! - [`StrokeStyle`]: Enum defining line patterns (solid, dashed, dotted, etc.)
 //! - [`StrokeCap`]: Enum defining how line endpoints are rendered (butt, round, square)
 //! - [`StrokeJoin`]: Enum defining how line corners are rendered (miter, round, bevel)
@@ -62,12 +62,16 @@
 //! | Rust Property | SVG Attribute | Example Values |
 //! |--------------|---------------|----------------|
 //! | `color` | `stroke`, `stroke-opacity` | `"#000000"`, `0.5` |
+//! | `opacity` | `stroke-opacity` (multiplied with the color's alpha) | `0.5` |
 //! | `width` | `stroke-width` | `2.0` |
 //! | `style` | `stroke-dasharray` | `"5,5"`, `"10,5,2,5"` |
 //! | `cap` | `stroke-linecap` | `"butt"`, `"round"`, `"square"` |
 //! | `join` | `stroke-linejoin` | `"miter"`, `"round"`, `"bevel"` |
 
-use std::str::FromStr;
+use std::{fmt, str::FromStr};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 use crate::color::Color;
 
@@ -90,6 +94,8 @@ use crate::color::Color;
 /// - `DashDotDot`: "10,5,2,5,2,5"
 /// - `Custom(pattern)`: Uses the provided pattern string
 #[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum StrokeStyle {
     /// Solid continuous line (default)
     #[default]
@@ -124,7 +130,65 @@ impl FromStr for StrokeStyle {
     }
 }
 
+/// Writes the style as [`FromStr`] reads it: its name, or the dash pattern
+/// of a custom style.
+impl fmt::Display for StrokeStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Solid => write!(f, "solid"),
+            Self::Dashed => write!(f, "dashed"),
+            Self::Dotted => write!(f, "dotted"),
+            Self::DashDot => write!(f, "dash-dot"),
+            Self::DashDotDot => write!(f, "dash-dot-dot"),
+            Self::Custom(pattern) => f.write_str(pattern),
+        }
+    }
+}
+
 impl StrokeStyle {
+    /// Creates a [`StrokeStyle::Custom`] style from a raw dash pattern.
+    ///
+    /// The pattern is a list of dash and gap lengths separated by whitespace
+    /// and/or commas, e.g. `"4 2"` or `"10,5,2,5"`. Unlike [`FromStr`], the
+    /// lengths are validated: each must be a finite, non-negative number and
+    /// at least one must be positive. The stored pattern is normalized to
+    /// comma-separated form.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orrery_core::draw::StrokeStyle;
+    ///
+    /// let style = StrokeStyle::from_dash_pattern("4 2").unwrap();
+    /// assert_eq!(style.to_svg_value(), Some("4,2".to_string()));
+    /// assert!(StrokeStyle::from_dash_pattern("4 x").is_err());
+    /// ```
+    pub fn from_dash_pattern(pattern: &str) -> Result<Self, String> {
+        let lengths = pattern
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|part| !part.is_empty())
+            .map(|part| match part.parse::<f32>() {
+                Ok(length) if length.is_finite() && length >= 0.0 => Ok(length),
+                _ => Err(format!(
+                    "invalid dash length `{part}`, expected a non-negative number"
+                )),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if !lengths.iter().any(|&length| length > 0.0) {
+            return Err(format!(
+                "dash pattern `{pattern}` must contain a positive length"
+            ));
+        }
+
+        let pattern = lengths
+            .iter()
+            .map(f32::to_string)
+            .collect::<Vec<_>>()
+            .join(",");
+        Ok(Self::Custom(pattern))
+    }
+
     /// Returns the SVG dasharray value for this style, or None for solid lines
     pub fn to_svg_value(&self) -> Option<String> {
         match self {
@@ -142,6 +206,8 @@ impl StrokeStyle {
 ///
 /// Maps directly to SVG `stroke-linecap` attribute values.
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum StrokeCap {
     /// Flat cap at the exact endpoint (SVG default)
     #[default]
@@ -182,6 +248,8 @@ impl FromStr for StrokeCap {
 ///
 /// Maps directly to SVG `stroke-linejoin` attribute values.
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum StrokeJoin {
     /// Sharp corner with mitered point (SVG default)
     #[default]
@@ -254,8 +322,11 @@ impl FromStr for StrokeJoin {
 /// stroke.set_join(StrokeJoin::Round);
 /// ```
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct StrokeDefinition {
     color: Color,
+    #[cfg_attr(feature = "serde", serde(default = "default_opacity"))]
+    opacity: f32,
     width: f32,
     style: StrokeStyle,
     cap: StrokeCap,
@@ -271,6 +342,7 @@ impl StrokeDefinition {
     pub fn default_solid() -> Self {
         Self {
             color: Color::default(),
+            opacity: 1.0,
             width: 2.0,
             style: StrokeStyle::Solid,
             cap: StrokeCap::Butt,
@@ -286,6 +358,7 @@ impl StrokeDefinition {
     pub fn default_dashed() -> Self {
         Self {
             color: Color::default(),
+            opacity: 1.0,
             width: 1.0,
             style: StrokeStyle::Dashed,
             cap: StrokeCap::Butt,
@@ -374,6 +447,11 @@ impl StrokeDefinition {
         self.color
     }
 
+    /// Returns the stroke opacity, applied on top of the color's alpha.
+    pub fn opacity(&self) -> f32 {
+        self.opacity
+    }
+
     /// Returns the stroke width.
     pub fn width(&self) -> f32 {
         self.width
@@ -399,6 +477,11 @@ impl StrokeDefinition {
         self.color = color;
     }
 
+    /// Sets the stroke opacity, applied on top of the color's alpha.
+    pub fn set_opacity(&mut self, opacity: f32) {
+        self.opacity = opacity;
+    }
+
     /// Sets the stroke width.
     pub fn set_width(&mut self, width: f32) {
         self.width = width;
@@ -420,10 +503,18 @@ impl StrokeDefinition {
     }
 }
 
+/// Default opacity for deserialized strokes and shape fills, which is fully
+/// opaque.
+#[cfg(feature = "serde")]
+pub(super) fn default_opacity() -> f32 {
+    1.0
+}
+
 impl Default for StrokeDefinition {
     fn default() -> Self {
         Self {
             color: Color::default(),
+            opacity: 1.0,
             width: 1.0,
             style: StrokeStyle::default(),
             cap: StrokeCap::default(),
@@ -458,7 +549,10 @@ macro_rules! apply_stroke {
     ($element:expr, $stroke:expr) => {{
         let mut elem = $element
             .set("stroke", $stroke.color().to_string())
-            .set("stroke-opacity", $stroke.color().alpha())
+            .set(
+                "stroke-opacity",
+                $stroke.color().alpha() * $stroke.opacity(),
+            )
             .set("stroke-width", $stroke.width())
             .set("stroke-linecap", $stroke.cap().to_svg_value())
             .set("stroke-linejoin", $stroke.join().to_svg_value());
@@ -479,6 +573,7 @@ mod tests {
     fn test_stroke_default() {
         let stroke = StrokeDefinition::default();
         assert_eq!(stroke.width(), 1.0);
+        assert_eq!(stroke.opacity(), 1.0);
         assert_eq!(stroke.color().to_string(), "black");
         assert_eq!(*stroke.style(), StrokeStyle::Solid);
         assert_eq!(stroke.cap(), StrokeCap::Butt);
@@ -530,6 +625,20 @@ mod tests {
         assert_eq!(stroke.join(), StrokeJoin::Bevel);
     }
 
+    #[test]
+    fn test_apply_stroke_multiplies_opacity_with_color_alpha() {
+        let mut stroke = StrokeDefinition::new(Color::new("#0000ff80").unwrap(), 1.0);
+        stroke.set_opacity(0.5);
+
+        let rect = crate::apply_stroke!(svg::node::element::Rectangle::new(), &stroke);
+
+        let opacity: f32 = rect.get_attributes()["stroke-opacity"]
+            .to_string()
+            .parse()
+            .unwrap();
+        assert!((opacity - 64.0 / 255.0).abs() < 0.001);
+    }
+
     #[test]
     fn test_stroke_style_dasharray() {
         assert_eq!(StrokeStyle::Solid.to_svg_value(), None);
@@ -548,6 +657,23 @@ mod tests {
         assert_eq!(custom.to_svg_value(), Some("15,3,3,3".to_string()));
     }
 
+    #[test]
+    fn test_stroke_style_from_dash_pattern() {
+        assert_eq!(
+            StrokeStyle::from_dash_pattern("4 2").unwrap(),
+            StrokeStyle::Custom("4,2".to_string())
+        );
+        assert_eq!(
+            StrokeStyle::from_dash_pattern(" 10, 5 2.5,0 ").unwrap(),
+            StrokeStyle::Custom("10,5,2.5,0".to_string())
+        );
+
+        assert!(StrokeStyle::from_dash_pattern("").is_err());
+        assert!(StrokeStyle::from_dash_pattern("0 0").is_err());
+        assert!(StrokeStyle::from_dash_pattern("4 -2").is_err());
+        assert!(StrokeStyle::from_dash_pattern("dashed").is_err());
+    }
+
     #[test]
     fn test_stroke_cap_svg_values() {
         assert_eq!(StrokeCap::Butt.to_svg_value(), "butt");
@@ -8,6 +8,11 @@
 //!
 //! - [`TextDefinition`] - Reusable text style configuration
 //! - [`Text`] - A renderable text element combining content with a [`TextDefinition`]
+//! - [`TextMeasurer`] - Measures text; implemented by [`FontTextMeasurer`],
+//!   which shapes text with the system fonts and any loaded font file, and
+//!   [`HeuristicTextMeasurer`], which estimates from character counts
+//! - [`with_text_measurer`] - Selects the measurer [`Text`] uses on the
+//!   current thread
 //!
 //! # Quick Start
 //!
@@ -32,14 +37,24 @@
 //!   [`Background`](crate::draw::RenderLayer::Background) layer (depending on
 //!   the [`TextDefinition`] configuration)
 
-use std::sync::{Arc, Mutex, OnceLock};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fmt, io,
+    rc::Rc,
+    str::FromStr,
+    sync::{Arc, Mutex, OnceLock},
+};
 
-use cosmic_text::{Attrs, Buffer, Family, FontSystem, Metrics, Shaping};
+use cosmic_text::{Attrs, Buffer, Family, FontSystem, Metrics, Shaping, Style, fontdb};
 use log::info;
 use svg::{self, node::Text as SvgText, node::element as svg_element};
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 use crate::{
-    color::Color,
+    color::{Color, Theme},
     draw::{Drawable, LayeredOutput, RenderLayer},
     geometry::{Insets, Point, Size},
 };
@@ -75,6 +90,7 @@ static DEFAULT_TEXT: OnceLock<TextDefinition> = OnceLock::new();
 /// |----------|---------|
 /// | Font family | `"Arial"` |
 /// | Font size | `11` |
+/// | Font style | [`FontStyle::Normal`] |
 /// | Background color | `None` |
 /// | Text color | `None` (SVG default, typically black) |
 /// | Padding | Zero on all sides |
@@ -93,14 +109,55 @@ static DEFAULT_TEXT: OnceLock<TextDefinition> = OnceLock::new();
 /// # }
 /// ```
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TextDefinition {
     font_family: String,
     font_size: u16,
+    #[cfg_attr(feature = "serde", serde(default))]
+    font_style: FontStyle,
     background_color: Option<Color>,
     color: Option<Color>,
     padding: Insets,
 }
 
+/// Defines whether text is drawn upright or slanted.
+///
+/// Maps directly to SVG `font-style` attribute values.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum FontStyle {
+    /// Upright glyphs (SVG default)
+    #[default]
+    Normal,
+    /// Slanted glyphs from the italic face of the font
+    Italic,
+}
+
+impl FontStyle {
+    /// Returns the SVG font-style value
+    pub fn to_svg_value(&self) -> &'static str {
+        match self {
+            Self::Normal => "normal",
+            Self::Italic => "italic",
+        }
+    }
+}
+
+impl FromStr for FontStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "normal" => Ok(Self::Normal),
+            "italic" => Ok(Self::Italic),
+            _ => Err(format!(
+                "invalid font style `{s}`, valid values: normal, italic"
+            )),
+        }
+    }
+}
+
 impl TextDefinition {
     /// Returns a reference to the default text definition (borrowed from static).
     ///
@@ -110,6 +167,7 @@ impl TextDefinition {
         DEFAULT_TEXT.get_or_init(|| TextDefinition {
             font_family: String::from("sans-serif"),
             font_size: 9,
+            font_style: FontStyle::Normal,
             background_color: None,
             color: None,
             padding: Insets::uniform(4.0),
@@ -121,6 +179,19 @@ impl TextDefinition {
         Self::default()
     }
 
+    /// Creates a text definition in the theme's text color and font.
+    pub fn from_theme(theme: &Theme) -> Self {
+        let mut text = Self::default();
+        text.set_color(Some(theme.text()));
+        if let Some(family) = theme.font_family() {
+            text.set_font_family(family);
+        }
+        if let Some(size) = theme.font_size() {
+            text.set_font_size(size);
+        }
+        text
+    }
+
     /// Sets the font size in points.
     ///
     /// # Arguments
@@ -139,6 +210,11 @@ impl TextDefinition {
         self.font_family = family.to_string();
     }
 
+    /// Sets whether the text is drawn upright or in italics.
+    pub fn set_font_style(&mut self, style: FontStyle) {
+        self.font_style = style;
+    }
+
     /// Sets the background color for the text.
     ///
     /// When set to `Some(color)`, text will be rendered with a rounded rectangle background
@@ -176,33 +252,40 @@ impl TextDefinition {
         self.padding = padding;
     }
 
-    fn font_size(&self) -> u16 {
+    /// Returns the font size in points.
+    pub fn font_size(&self) -> u16 {
         self.font_size
     }
 
     /// Returns the font size in CSS pixels (1 pt = 96/72 px at 96 dpi).
     ///
     /// The size is stored in points (see [`Self::set_font_size`]).
-    fn font_size_px(&self) -> f32 {
+    pub fn font_size_px(&self) -> f32 {
         f32::from(self.font_size) * (96.0 / 72.0)
     }
 
-    fn font_family(&self) -> &str {
+    /// Returns the font family name.
+    pub fn font_family(&self) -> &str {
         &self.font_family
     }
 
+    /// Returns whether the text is drawn upright or in italics.
+    pub fn font_style(&self) -> FontStyle {
+        self.font_style
+    }
+
     /// Returns a reference to the background color, if set.
-    fn background_color(&self) -> Option<&Color> {
+    pub fn background_color(&self) -> Option<&Color> {
         self.background_color.as_ref()
     }
 
     /// Returns a reference to the text color, if set.
-    fn color(&self) -> Option<&Color> {
+    pub fn color(&self) -> Option<&Color> {
         self.color.as_ref()
     }
 
     /// Returns the current padding configuration.
-    fn padding(&self) -> Insets {
+    pub fn padding(&self) -> Insets {
         self.padding
     }
 }
@@ -211,6 +294,7 @@ impl Default for TextDefinition {
     fn default() -> Self {
         Self {
             font_size: 11,
+            font_style: FontStyle::default(),
             background_color: None,
             color: None,
             padding: Insets::default(),
@@ -265,23 +349,44 @@ impl<'a> Text<'a> {
         self.content
     }
 
+    /// Returns `true` if the text content is empty.
+    ///
+    /// Empty text occupies no space and renders nothing, not even its background.
+    pub fn is_empty(&self) -> bool {
+        self.content.is_empty()
+    }
+
     /// Calculate the total size required to display this text, including padding.
+    ///
+    /// Empty text has zero size; padding is not applied to it.
     pub fn calculate_size(&self) -> Size {
+        if self.is_empty() {
+            return Size::zero();
+        }
         let padding = self.definition.padding();
         self.calculate_size_without_padding().add_padding(padding)
     }
 
     /// Calculate the size required to display this text content without padding.
+    ///
+    /// Measures with the measurer installed by [`with_text_measurer`], or the
+    /// shared [`FontTextMeasurer`] when none is.
     fn calculate_size_without_padding(&self) -> Size {
-        TEXT_MANAGER
-            .get_or_init(TextManager::new)
-            .calculate_text_size(self.content, self.definition)
+        match TEXT_MEASURER.with_borrow(Option::clone) {
+            Some(measurer) => measurer.measure(self.content, self.definition),
+            None => DEFAULT_TEXT_MEASURER
+                .get_or_init(FontTextMeasurer::new)
+                .measure(self.content, self.definition),
+        }
     }
 }
 
 impl<'a> Drawable for Text<'a> {
     fn render_to_layers(&self, position: Point) -> LayeredOutput {
         let mut output = LayeredOutput::new();
+        if self.is_empty() {
+            return output;
+        }
         let text_size = self.calculate_size();
 
         let lines: Vec<&str> = self.content.lines().collect();
@@ -305,6 +410,11 @@ impl<'a> Drawable for Text<'a> {
             .set("font-family", self.definition.font_family())
             .set("font-size", format!("{}pt", self.definition.font_size()));
 
+        if self.definition.font_style() != FontStyle::Normal {
+            rendered_text =
+                rendered_text.set("font-style", self.definition.font_style().to_svg_value());
+        }
+
         // Set text color if specified
         if let Some(color) = self.definition.color() {
             rendered_text = rendered_text
@@ -312,12 +422,20 @@ impl<'a> Drawable for Text<'a> {
                 .set("fill-opacity", color.alpha());
         }
 
+        // An empty `<tspan>` does not move the baseline, so blank lines are
+        // folded into the offset of the next non-blank line.
+        let mut dy = 0.0;
         for line in lines.into_iter() {
+            dy += line_height;
+            if line.trim().is_empty() {
+                continue;
+            }
             let tspan = svg_element::TSpan::new("")
                 .set("x", position.x())
-                .set("dy", line_height)
+                .set("dy", dy)
                 .add(SvgText::new(line));
             rendered_text = rendered_text.add(tspan);
+            dy = 0.0;
         }
 
         // Add background rectangle if color is specified
@@ -347,36 +465,120 @@ impl<'a> Drawable for Text<'a> {
     }
 }
 
-/// TextManager handles text measurement and font operations
-/// It maintains a reusable FontSystem instance to avoid expensive recreation
-struct TextManager {
-    font_system: Arc<Mutex<FontSystem>>,
+/// Measures the space text content occupies.
+///
+/// Layout engines size labels, and the shapes around them, through [`Text`],
+/// which measures with the measurer installed by [`with_text_measurer`].
+pub trait TextMeasurer {
+    /// Returns the size of `text` drawn with `definition`, excluding padding.
+    ///
+    /// Empty text has [`Size::zero`].
+    fn measure(&self, text: &str, definition: &TextDefinition) -> Size;
+}
+
+/// Estimates text size from its characters, without reading any font.
+///
+/// Wide characters, such as CJK ideographs, kana, hangul and full-width
+/// forms, advance by one em; every other character by 0.55 em.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct HeuristicTextMeasurer;
+
+impl HeuristicTextMeasurer {
+    /// Returns the estimated advance of `c` at `font_size_px`.
+    fn advance(c: char, font_size_px: f32) -> f32 {
+        if is_wide(c) {
+            font_size_px
+        } else {
+            font_size_px * 0.55
+        }
+    }
+}
+
+impl TextMeasurer for HeuristicTextMeasurer {
+    fn measure(&self, text: &str, definition: &TextDefinition) -> Size {
+        if text.is_empty() {
+            return Size::zero();
+        }
+        let font_size_px = definition.font_size_px();
+        let (width, lines) = text.lines().fold((0.0f32, 0), |(width, lines), line| {
+            let line_width = line
+                .chars()
+                .map(|c| Self::advance(c, font_size_px))
+                .sum::<f32>();
+            (width.max(line_width), lines + 1)
+        });
+        Size::new(width, lines.max(1) as f32 * line_height(font_size_px))
+    }
+}
+
+/// Measures text by shaping it with the system fonts.
+///
+/// Shaping accounts for the actual advance widths of the configured font,
+/// ligatures and kerning. Characters no available font covers are measured
+/// with [`HeuristicTextMeasurer`] instead of the width of the placeholder
+/// glyph, so labels in scripts missing from the system are not undersized.
+pub struct FontTextMeasurer {
+    font_system: Mutex<FontSystem>,
+    /// Font families bound to a loaded font file, mapped to the family name
+    /// the file declares.
+    families: HashMap<String, String>,
+}
+
+impl fmt::Debug for FontTextMeasurer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FontTextMeasurer").finish_non_exhaustive()
+    }
 }
 
-impl Default for TextManager {
+impl Default for FontTextMeasurer {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl TextManager {
-    /// Create a new TextManager with a default FontSystem
-    fn new() -> Self {
+impl FontTextMeasurer {
+    /// Creates a measurer that loads the system fonts.
+    ///
+    /// Loading fonts is expensive; [`Text`] shares a single instance.
+    pub fn new() -> Self {
         info!("Initializing FontSystem");
         Self {
-            font_system: Arc::new(Mutex::new(FontSystem::new())),
+            font_system: Mutex::new(FontSystem::new()),
+            families: HashMap::new(),
         }
     }
 
-    /// Measures the actual size of text in pixels.
+    /// Creates a measurer that also loads the font file contents `data`, and
+    /// measures text of the font family `family` with it.
     ///
-    /// This provides an accurate measurement based on real font metrics and shaping,
-    /// including proper handling of ligatures, kerning, and other advanced typography features.
+    /// `family` may differ from the family name declared inside the file,
+    /// as when the file is embedded into SVG output under a chosen name.
     ///
-    /// # Returns
+    /// # Errors
     ///
-    /// The calculated size in pixels, or [`Size::zero`] for empty input.
-    fn calculate_text_size(&self, text: &str, text_def: &TextDefinition) -> Size {
+    /// Returns an [`io::ErrorKind::InvalidData`] error if `data` holds no font
+    /// that can be parsed, such as a WOFF2 file.
+    pub fn with_font_data(family: &str, data: Vec<u8>) -> io::Result<Self> {
+        let mut measurer = Self::new();
+        let font_system = measurer
+            .font_system
+            .get_mut()
+            .expect("failed to lock FontSystem");
+        let db = font_system.db_mut();
+        let ids = db.load_font_source(fontdb::Source::Binary(Arc::new(data)));
+        let declared = ids
+            .first()
+            .and_then(|&id| db.face(id))
+            .and_then(|face| face.families.first())
+            .map(|(name, _)| name.clone())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "no parsable font"))?;
+        measurer.families.insert(family.to_string(), declared);
+        Ok(measurer)
+    }
+}
+
+impl TextMeasurer for FontTextMeasurer {
+    fn measure(&self, text: &str, definition: &TextDefinition) -> Size {
         if text.is_empty() {
             return Size::zero();
         }
@@ -384,18 +586,23 @@ impl TextManager {
         // Lock the FontSystem for use
         let mut font_system = self.font_system.lock().expect("failed to lock FontSystem");
 
-        let font_size_px = text_def.font_size_px();
-
-        // Create metrics with font size and approximate line height
-        let line_height = font_size_px * 1.15;
-        let metrics = Metrics::new(font_size_px, line_height);
+        let font_size_px = definition.font_size_px();
+        let metrics = Metrics::new(font_size_px, line_height(font_size_px));
 
         // Create a buffer with the metrics
         let mut buffer = Buffer::new(&mut font_system, metrics);
         let mut buffer = buffer.borrow_with(&mut font_system);
 
         // Set up text attributes
-        let attrs = Attrs::new().family(Family::Name(text_def.font_family()));
+        let style = match definition.font_style() {
+            FontStyle::Normal => Style::Normal,
+            FontStyle::Italic => Style::Italic,
+        };
+        let family = self
+            .families
+            .get(definition.font_family())
+            .map_or(definition.font_family(), String::as_str);
+        let attrs = Attrs::new().family(Family::Name(family)).style(style);
 
         // Set the buffer's size to unlimited to allow text to flow naturally
         buffer.set_size(None, None);
@@ -410,31 +617,103 @@ impl TextManager {
         // Calculate bounds by examining layout runs to determine actual rendered size
         let mut max_width: f32 = 0.0;
         let mut total_height: f32 = 0.0;
-
-        // Get height from line metrics or use default
-        let layout_runs: Vec<_> = buffer.layout_runs().collect();
-        if !layout_runs.is_empty() {
-            for last in layout_runs.iter().map(|run| run.glyphs.last()) {
-                // Find rightmost glyph position
-                if let Some(last) = last {
-                    let run_width = last.x + last.w;
-                    max_width = max_width.max(run_width);
-                }
-                // Add line height for this run
-                total_height += metrics.line_height;
+        let mut has_runs = false;
+
+        for run in buffer.layout_runs() {
+            has_runs = true;
+            // Find rightmost glyph position
+            if let Some(last) = run.glyphs.last() {
+                // Glyph 0 is the placeholder drawn for characters missing
+                // from every font; widen it to the estimated advance.
+                let missing_width: f32 = run
+                    .glyphs
+                    .iter()
+                    .filter(|glyph| glyph.glyph_id == 0)
+                    .map(|glyph| {
+                        let estimate: f32 = run.text[glyph.start..glyph.end]
+                            .chars()
+                            .map(|c| HeuristicTextMeasurer::advance(c, font_size_px))
+                            .sum();
+                        (estimate - glyph.w).max(0.0)
+                    })
+                    .sum();
+                max_width = max_width.max(last.x + last.w + missing_width);
             }
-        } else {
-            // Default size if no runs available
-            max_width = text.len() as f32 * (font_size_px * 0.55);
-            total_height = metrics.line_height;
+            // Add line height for this run
+            total_height += metrics.line_height;
+        }
+
+        if !has_runs {
+            // Nothing was shaped, e.g. no fonts are installed
+            return HeuristicTextMeasurer.measure(text, definition);
         }
 
         Size::new(max_width, total_height)
     }
 }
 
-// Create a global instance for use throughout the application
-static TEXT_MANAGER: OnceLock<TextManager> = OnceLock::new();
+/// Returns the line height used to measure text of `font_size_px`.
+fn line_height(font_size_px: f32) -> f32 {
+    font_size_px * 1.15
+}
+
+/// Returns `true` for characters that typically render one em wide.
+fn is_wide(c: char) -> bool {
+    matches!(
+        u32::from(c),
+        0x1100..=0x115F // Hangul Jamo
+            | 0x2E80..=0x303E // CJK radicals and punctuation
+            | 0x3041..=0x33FF // Kana and CJK compatibility
+            | 0x3400..=0x4DBF // CJK extension A
+            | 0x4E00..=0x9FFF // CJK unified ideographs
+            | 0xA000..=0xA4CF // Yi
+            | 0xAC00..=0xD7A3 // Hangul syllables
+            | 0xF900..=0xFAFF // CJK compatibility ideographs
+            | 0xFE30..=0xFE4F // CJK compatibility forms
+            | 0xFF00..=0xFF60 // Full-width forms
+            | 0xFFE0..=0xFFE6
+            | 0x20000..=0x3FFFD // CJK extensions B and beyond
+    )
+}
+
+thread_local! {
+    /// Measurer installed on this thread by [`with_text_measurer`].
+    static TEXT_MEASURER: RefCell<Option<Rc<dyn TextMeasurer>>> = const { RefCell::new(None) };
+}
+
+/// Measurer used when none is installed, shared because loading the system
+/// fonts is expensive.
+static DEFAULT_TEXT_MEASURER: OnceLock<FontTextMeasurer> = OnceLock::new();
+
+/// Runs `f` with every [`Text`] on the current thread measured by `measurer`.
+///
+/// Text is measured deep inside drawables, so layout engines and exporters
+/// install their measurer for the duration of their work instead of passing
+/// it down. The previously installed measurer is restored afterwards.
+///
+/// # Examples
+///
+/// ```
+/// # use std::rc::Rc;
+/// # use orrery_core::draw::{HeuristicTextMeasurer, Text, TextDefinition, TextMeasurer, with_text_measurer};
+/// let def = TextDefinition::new();
+/// let measurer: Rc<dyn TextMeasurer> = Rc::new(HeuristicTextMeasurer);
+/// let size = with_text_measurer(Rc::clone(&measurer), || Text::new(&def, "abc").calculate_size());
+/// assert_eq!(size, measurer.measure("abc", &def).add_padding(def.padding()));
+/// ```
+pub fn with_text_measurer<R>(measurer: Rc<dyn TextMeasurer>, f: impl FnOnce() -> R) -> R {
+    /// Reinstates the previous measurer, also when `f` panics.
+    struct Restore(Option<Rc<dyn TextMeasurer>>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            TEXT_MEASURER.set(self.0.take());
+        }
+    }
+
+    let _restore = Restore(TEXT_MEASURER.replace(Some(measurer)));
+    f()
+}
 
 #[cfg(test)]
 mod tests {
@@ -443,6 +722,7 @@ mod tests {
     use float_cmp::assert_approx_eq;
 
     use super::*;
+    use crate::geometry::Bounds;
 
     #[test]
     fn test_text_definition_default_borrowed_returns_static() {
@@ -636,6 +916,56 @@ mod tests {
         assert!(!output.is_empty());
     }
 
+    #[test]
+    fn test_text_render_keeps_blank_lines() {
+        let def = TextDefinition::new();
+        let text = Text::new(&def, "One\n\nTwo");
+        let svg: String = text
+            .render_to_layers(Point::new(0.0, 0.0))
+            .render()
+            .iter()
+            .map(|node| node.to_string())
+            .collect();
+
+        let dys: Vec<f32> = svg
+            .split("dy=\"")
+            .skip(1)
+            .map(|rest| rest.split('"').next().unwrap().parse().unwrap())
+            .collect();
+        // The blank line has no `<tspan>`, its height moves the next line down.
+        assert_eq!(dys.len(), 2);
+        assert_approx_eq!(f32, dys[1], dys[0] * 2.0);
+    }
+
+    #[test]
+    fn test_text_render_sets_font_style_only_when_italic() {
+        let render = |def: &TextDefinition| -> String {
+            Text::new(def, "Hello")
+                .render_to_layers(Point::new(0.0, 0.0))
+                .render()
+                .iter()
+                .map(|node| node.to_string())
+                .collect()
+        };
+        let mut def = TextDefinition::new();
+        assert!(!render(&def).contains("font-style"));
+
+        def.set_font_style("italic".parse().unwrap());
+        assert!(render(&def).contains("font-style=\"italic\""));
+    }
+
+    #[test]
+    fn test_text_empty_renders_nothing() {
+        let mut def = TextDefinition::new();
+        def.set_background_color(Some(Color::new("yellow").unwrap()));
+        def.set_padding(Insets::uniform(8.0));
+        let text = Text::new(&def, "");
+
+        assert!(text.is_empty());
+        assert_eq!(text.calculate_size(), Size::zero());
+        assert!(text.render_to_layers(Point::new(0.0, 0.0)).is_empty());
+    }
+
     #[test]
     fn test_text_render_with_background_adds_layer() {
         let mut def = TextDefinition::new();
@@ -653,6 +983,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_text_background_encloses_text_bounds() {
+        let mut def = TextDefinition::new();
+        def.set_background_color(Some(Color::new("white").unwrap()));
+        def.set_padding(Insets::uniform(4.0));
+        let text = Text::new(&def, "Line one\nLine two");
+        let position = Point::new(50.0, 20.0);
+        let svg: String = text
+            .render_to_layers(position)
+            .render()
+            .iter()
+            .map(|node| node.to_string())
+            .collect();
+
+        let attribute = |name: &str| -> f32 {
+            let pattern = format!(" {name}=\"");
+            let start = svg.find(&pattern).unwrap() + pattern.len();
+            svg[start..].split('"').next().unwrap().parse().unwrap()
+        };
+        let background = Bounds::new_from_top_left(
+            Point::new(attribute("x"), attribute("y")),
+            Size::new(attribute("width"), attribute("height")),
+        );
+        let text_bounds = position.to_bounds(text.calculate_size_without_padding());
+
+        assert!(background.min_x() < text_bounds.min_x());
+        assert!(background.min_y() < text_bounds.min_y());
+        assert!(background.max_x() > text_bounds.max_x());
+        assert!(background.max_y() > text_bounds.max_y());
+    }
+
     #[test]
     fn test_text_content_accessor() {
         let def = TextDefinition::new();
@@ -665,4 +1026,57 @@ mod tests {
         let multiline = Text::new(&def, "Line 1\nLine 2");
         assert_eq!(multiline.content(), "Line 1\nLine 2");
     }
+
+    #[test]
+    fn test_heuristic_measurer_widens_cjk() {
+        let def = TextDefinition::default();
+        let em = def.font_size_px();
+
+        let size = HeuristicTextMeasurer.measure("数据库", &def);
+        assert_approx_eq!(f32, size.width(), 3.0 * em);
+
+        let size = HeuristicTextMeasurer.measure("a\n数", &def);
+        assert_approx_eq!(f32, size.width(), em);
+        assert_approx_eq!(f32, size.height(), 2.0 * line_height(em));
+
+        assert_eq!(HeuristicTextMeasurer.measure("", &def), Size::zero());
+    }
+
+    #[test]
+    fn test_cjk_label_is_at_least_one_em_per_character() {
+        let def = TextDefinition::default();
+        let label = "数据库服务器";
+
+        // Holds whether or not a CJK font is installed: missing glyphs fall
+        // back to the estimated advance instead of the placeholder's width
+        let size = Text::new(&def, label).calculate_size();
+        let em = def.font_size_px();
+        assert!(size.width() >= label.chars().count() as f32 * em);
+    }
+
+    #[test]
+    fn test_with_text_measurer_installs_and_restores_measurer() {
+        /// Measures every text as a fixed size.
+        struct FixedMeasurer;
+
+        impl TextMeasurer for FixedMeasurer {
+            fn measure(&self, _text: &str, _definition: &TextDefinition) -> Size {
+                Size::new(100.0, 10.0)
+            }
+        }
+
+        let mut def = TextDefinition::new();
+        def.set_padding(Insets::uniform(0.0));
+        let text = Text::new(&def, "abc");
+
+        let size = with_text_measurer(Rc::new(FixedMeasurer), || text.calculate_size());
+        assert_eq!(size, Size::new(100.0, 10.0));
+        assert_ne!(text.calculate_size(), Size::new(100.0, 10.0));
+    }
+
+    #[test]
+    fn test_font_data_that_cannot_be_parsed_is_an_error() {
+        let err = FontTextMeasurer::with_font_data("Brand", b"not a font".to_vec()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
 }
@@ -1,12 +1,124 @@
 //! Text positioning strategies for shape labels.
 //!
 //! This module defines [`TextPositioningStrategy`], which controls how
-//! text is positioned relative to a shape.
+//! text is positioned relative to a shape, and [`ContentAlign`], which places
+//! the text within a content-supporting shape.
+
+use std::{fmt, str::FromStr};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 use crate::geometry::{Insets, Point, Size};
 
 const BLOW_SHAPE_TEXT_GAP: f32 = 8.0;
 
+/// Horizontal placement of a label inside a content-supporting shape.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum HorizontalAlign {
+    /// Against the left padding edge
+    Left,
+    /// Centered horizontally
+    #[default]
+    Center,
+    /// Against the right padding edge
+    Right,
+}
+
+/// Vertical placement of a label inside a content-supporting shape.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum VerticalAlign {
+    /// Against the top padding edge
+    Top,
+    /// Centered vertically
+    #[default]
+    Center,
+    /// Against the bottom padding edge
+    Bottom,
+}
+
+/// Placement of a label inside a content-supporting shape.
+///
+/// Parsed from values such as `"center"`, `"top"`, `"left"` or
+/// `"top-left"`; the default centers the label. Vertical alignment only
+/// applies to shapes without inner content, where the label is otherwise
+/// kept above the nested elements.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ContentAlign {
+    vertical: VerticalAlign,
+    horizontal: HorizontalAlign,
+}
+
+impl ContentAlign {
+    /// Creates a new alignment from its vertical and horizontal parts.
+    pub fn new(vertical: VerticalAlign, horizontal: HorizontalAlign) -> Self {
+        Self {
+            vertical,
+            horizontal,
+        }
+    }
+
+    /// Returns the vertical part of the alignment.
+    pub fn vertical(&self) -> VerticalAlign {
+        self.vertical
+    }
+
+    /// Returns the horizontal part of the alignment.
+    pub fn horizontal(&self) -> HorizontalAlign {
+        self.horizontal
+    }
+}
+
+impl FromStr for ContentAlign {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (vertical, horizontal) = match s {
+            "center" => (VerticalAlign::Center, HorizontalAlign::Center),
+            "top" => (VerticalAlign::Top, HorizontalAlign::Center),
+            "bottom" => (VerticalAlign::Bottom, HorizontalAlign::Center),
+            "left" => (VerticalAlign::Center, HorizontalAlign::Left),
+            "right" => (VerticalAlign::Center, HorizontalAlign::Right),
+            "top-left" => (VerticalAlign::Top, HorizontalAlign::Left),
+            "top-right" => (VerticalAlign::Top, HorizontalAlign::Right),
+            "bottom-left" => (VerticalAlign::Bottom, HorizontalAlign::Left),
+            "bottom-right" => (VerticalAlign::Bottom, HorizontalAlign::Right),
+            _ => {
+                return Err(format!(
+                    "invalid content alignment `{s}`, valid values: center, top, bottom, left, \
+                     right, top-left, top-right, bottom-left, bottom-right"
+                ));
+            }
+        };
+        Ok(Self::new(vertical, horizontal))
+    }
+}
+
+impl fmt::Display for ContentAlign {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let vertical = match self.vertical {
+            VerticalAlign::Top => Some("top"),
+            VerticalAlign::Center => None,
+            VerticalAlign::Bottom => Some("bottom"),
+        };
+        let horizontal = match self.horizontal {
+            HorizontalAlign::Left => Some("left"),
+            HorizontalAlign::Center => None,
+            HorizontalAlign::Right => Some("right"),
+        };
+        match (vertical, horizontal) {
+            (Some(vertical), Some(horizontal)) => write!(f, "{vertical}-{horizontal}"),
+            (Some(side), None) | (None, Some(side)) => f.write_str(side),
+            (None, None) => f.write_str("center"),
+        }
+    }
+}
+
 /// Enum defining different text positioning strategies
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TextPositioningStrategy {
@@ -216,6 +328,23 @@ mod tests {
         assert_eq!(shape_position, Point::new(100.0, 91.0));
     }
 
+    #[test]
+    fn test_content_align_from_str() {
+        assert_eq!(
+            "center".parse::<ContentAlign>().unwrap(),
+            ContentAlign::default()
+        );
+        assert_eq!(
+            "top-left".parse::<ContentAlign>().unwrap(),
+            ContentAlign::new(VerticalAlign::Top, HorizontalAlign::Left)
+        );
+        assert_eq!(
+            "right".parse::<ContentAlign>().unwrap(),
+            ContentAlign::new(VerticalAlign::Center, HorizontalAlign::Right)
+        );
+        assert!("middle".parse::<ContentAlign>().is_err());
+    }
+
     #[test]
     fn test_calculate_inner_content_min_point() {
         let base_point = Point::new(10.0, 20.0);
@@ -9,6 +9,7 @@
 //! - [`Size`] - Width and height dimensions
 //! - [`Bounds`] - A rectangular bounding box defined by minimum and maximum coordinates
 //! - [`Insets`] - Padding/margin values for four sides
+//! - [`Angle`] - A rotation normalized to a full turn
 //!
 //! # Coordinate System
 //!
@@ -29,6 +30,9 @@
 //!
 //! This convention matches SVG and most screen coordinate systems.
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 /// A 2D point representing a position in diagram coordinate space.
 ///
 /// Points use `f32` coordinates and provide operations for basic vector math.
@@ -366,6 +370,42 @@ impl Bounds {
         }
     }
 
+    /// Returns `true` if `point` lies inside the bounds or on their edge.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use orrery_core::geometry::{Bounds, Point, Size};
+    /// let bounds = Bounds::new_from_top_left(Point::new(0.0, 0.0), Size::new(100.0, 50.0));
+    /// assert!(bounds.contains(Point::new(50.0, 25.0)));
+    /// assert!(bounds.contains(Point::new(100.0, 50.0)));
+    /// assert!(!bounds.contains(Point::new(50.0, 60.0)));
+    /// ```
+    pub fn contains(self, point: Point) -> bool {
+        (self.min_x..=self.max_x).contains(&point.x) && (self.min_y..=self.max_y).contains(&point.y)
+    }
+
+    /// Returns `true` if the bounds overlap `other` in an area.
+    ///
+    /// Bounds that only share an edge do not intersect.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use orrery_core::geometry::{Bounds, Point, Size};
+    /// let a = Bounds::new_from_top_left(Point::new(0.0, 0.0), Size::new(100.0, 50.0));
+    /// let b = Bounds::new_from_top_left(Point::new(80.0, 40.0), Size::new(100.0, 50.0));
+    /// let c = Bounds::new_from_top_left(Point::new(100.0, 0.0), Size::new(10.0, 10.0));
+    /// assert!(a.intersects(&b));
+    /// assert!(!a.intersects(&c));
+    /// ```
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.min_x < other.max_x
+            && other.min_x < self.max_x
+            && self.min_y < other.max_y
+            && other.min_y < self.max_y
+    }
+
     /// Merges two bounds to create a larger bounds that contains both.
     ///
     /// The resulting bounds will have the minimum values of both bounds for min_x and min_y,
@@ -384,6 +424,7 @@ impl Bounds {
     /// assert_eq!(combined.width(), 130.0); // Spans both (0 to 130)
     /// assert_eq!(combined.height(), 120.0); // Spans both (0 to 120)
     /// ```
+    #[doc(alias = "union")]
     pub fn merge(&self, other: &Self) -> Self {
         Self {
             min_x: self.min_x.min(other.min_x),
@@ -443,11 +484,34 @@ impl Bounds {
             max_y: self.max_y + insets.bottom(),
         }
     }
+
+    /// Shrinks the bounds by removing insets.
+    ///
+    /// This is the inverse of [`add_padding`](Self::add_padding).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use orrery_core::geometry::{Bounds, Insets, Point, Size};
+    /// let bounds = Bounds::new_from_top_left(Point::new(0.0, 0.0), Size::new(100.0, 50.0));
+    /// let content = bounds.inset(Insets::new(10.0, 5.0, 10.0, 5.0));
+    /// assert_eq!(content.min_point(), Point::new(5.0, 10.0));
+    /// assert_eq!(content.to_size(), Size::new(90.0, 30.0));
+    /// ```
+    pub fn inset(&self, insets: Insets) -> Self {
+        Self {
+            min_x: self.min_x + insets.left(),
+            min_y: self.min_y + insets.top(),
+            max_x: self.max_x - insets.right(),
+            max_y: self.max_y - insets.bottom(),
+        }
+    }
 }
 
 /// Represents spacing around an element (padding, margin, etc.)
 /// with potentially different values for each side
 #[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Insets {
     top: f32,
     right: f32,
@@ -512,6 +576,55 @@ impl Insets {
     }
 }
 
+/// An angle normalized to the canonical range `[0°, 360°)`.
+///
+/// Angles are stored in degrees and can be created from and converted to
+/// either unit. Angles follow the SVG convention, so positive values rotate
+/// clockwise on screen (see [module documentation](self)).
+///
+/// # Examples
+///
+/// ```
+/// # use orrery_core::geometry::Angle;
+/// let angle = Angle::from_degrees(370.0);
+/// assert_eq!(angle.degrees(), 10.0);
+///
+/// let negative = Angle::from_degrees(-90.0);
+/// assert_eq!(negative.degrees(), 270.0);
+///
+/// let half_turn = Angle::from_radians(std::f32::consts::PI);
+/// assert_eq!(half_turn.degrees(), 180.0);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Angle {
+    degrees: f32,
+}
+
+impl Angle {
+    /// Creates an angle from a value in degrees, normalized to `[0°, 360°)`.
+    pub fn from_degrees(degrees: f32) -> Self {
+        let normalized = degrees.rem_euclid(360.0);
+        // `rem_euclid` can round up to exactly 360 for tiny negative inputs
+        let degrees = if normalized >= 360.0 { 0.0 } else { normalized };
+        Self { degrees }
+    }
+
+    /// Creates an angle from a value in radians, normalized to `[0, 2π)`.
+    pub fn from_radians(radians: f32) -> Self {
+        Self::from_degrees(radians.to_degrees())
+    }
+
+    /// Returns the angle in degrees, in the range `[0°, 360°)`.
+    pub fn degrees(self) -> f32 {
+        self.degrees
+    }
+
+    /// Returns the angle in radians, in the range `[0, 2π)`.
+    pub fn radians(self) -> f32 {
+        self.degrees.to_radians()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -908,6 +1021,60 @@ mod tests {
         assert_eq!(padded.max_y(), 9.0);
     }
 
+    #[test]
+    fn test_bounds_inset() {
+        let bounds = Bounds {
+            min_x: 2.0,
+            min_y: 3.0,
+            max_x: 6.0,
+            max_y: 8.0,
+        };
+
+        let inset = bounds.inset(Insets::new(1.0, 2.0, 0.5, 1.5));
+
+        assert_eq!(inset.min_x(), 3.5);
+        assert_eq!(inset.min_y(), 4.0);
+        assert_eq!(inset.max_x(), 4.0);
+        assert_eq!(inset.max_y(), 7.5);
+        assert_eq!(inset.add_padding(Insets::new(1.0, 2.0, 0.5, 1.5)), bounds);
+    }
+
+    #[test]
+    fn test_bounds_contains() {
+        let bounds = Bounds {
+            min_x: 2.0,
+            min_y: 3.0,
+            max_x: 6.0,
+            max_y: 8.0,
+        };
+
+        assert!(bounds.contains(Point::new(4.0, 5.0)));
+        assert!(bounds.contains(Point::new(2.0, 3.0)));
+        assert!(bounds.contains(Point::new(6.0, 8.0)));
+        assert!(!bounds.contains(Point::new(1.9, 5.0)));
+        assert!(!bounds.contains(Point::new(4.0, 8.1)));
+    }
+
+    #[test]
+    fn test_bounds_intersects() {
+        let bounds = Bounds {
+            min_x: 2.0,
+            min_y: 3.0,
+            max_x: 6.0,
+            max_y: 8.0,
+        };
+        let overlapping = bounds.translate(Point::new(3.0, 3.0));
+        let inner = bounds.inset(Insets::uniform(1.0));
+        let touching = bounds.translate(Point::new(4.0, 0.0));
+        let apart = bounds.translate(Point::new(0.0, 10.0));
+
+        assert!(bounds.intersects(&overlapping));
+        assert!(overlapping.intersects(&bounds));
+        assert!(bounds.intersects(&inner));
+        assert!(!bounds.intersects(&touching));
+        assert!(!bounds.intersects(&apart));
+    }
+
     #[test]
     fn test_bounds_default() {
         let bounds = Bounds::default();
@@ -1111,6 +1278,32 @@ mod tests {
         assert_eq!(uniform_modified.bottom(), 5.0);
         assert_eq!(uniform_modified.left(), 5.0);
     }
+
+    #[test]
+    fn test_angle_from_degrees_normalizes() {
+        assert_eq!(Angle::from_degrees(370.0).degrees(), 10.0);
+        assert_eq!(Angle::from_degrees(360.0).degrees(), 0.0);
+        assert_eq!(Angle::from_degrees(-90.0).degrees(), 270.0);
+        assert_eq!(Angle::from_degrees(-720.0).degrees(), 0.0);
+        assert_eq!(Angle::from_degrees(-1e-8).degrees(), 0.0);
+    }
+
+    #[test]
+    fn test_angle_degree_radian_conversion() {
+        use std::f32::consts::{FRAC_PI_2, PI};
+
+        use float_cmp::assert_approx_eq;
+
+        assert_approx_eq!(f32, Angle::from_degrees(90.0).radians(), FRAC_PI_2);
+        assert_approx_eq!(f32, Angle::from_radians(PI).degrees(), 180.0);
+        assert_approx_eq!(f32, Angle::from_radians(-FRAC_PI_2).degrees(), 270.0);
+        assert_approx_eq!(
+            f32,
+            Angle::from_radians(5.0 * PI).degrees(),
+            180.0,
+            epsilon = 1e-3
+        );
+    }
 }
 
 #[cfg(test)]
@@ -5,8 +5,13 @@
 //! (everything before the last `::`), stored as separate interned symbols.
 //!
 //! Depends on the [`crate::interner`] module for global string storage and [`Symbol`] handles.
+//! An [`InternTable`] snapshot of the interner stores identifiers as compact [`IdIndex`]
+//! handles that can be rebuilt in another process.
 
-use std::fmt;
+use std::{fmt, iter};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 use crate::interner::{self, Symbol};
 
@@ -196,6 +201,144 @@ impl Id {
         self.namespace.map(interner::resolve)
     }
 
+    /// Returns the full path of this identifier as an interned string.
+    ///
+    /// Unlike [`to_string`](ToString::to_string), repeated calls do not
+    /// allocate: the full path of a namespaced identifier is interned on the
+    /// first call and resolved from the interner afterwards.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use orrery_core::identifier::Id;
+    /// assert_eq!(Id::new("Rectangle").as_str(), "Rectangle");
+    /// assert_eq!(Id::new("system::backend").as_str(), "system::backend");
+    /// ```
+    pub fn as_str(&self) -> &'static str {
+        let Some(namespace) = self.namespace else {
+            return interner::resolve(self.name);
+        };
+
+        let mut interner = interner::interner();
+        let path = format!(
+            "{}::{}",
+            interner.resolve(namespace),
+            interner.resolve(self.name)
+        );
+        let symbol = interner.get_or_intern(&path);
+        interner.resolve(symbol)
+    }
+
+    /// Returns the `::`-separated segments of this identifier, outermost
+    /// first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use orrery_core::identifier::Id;
+    /// let nested = Id::new("system::frontend::app");
+    /// assert_eq!(nested.parts().collect::<Vec<_>>(), ["system", "frontend", "app"]);
+    /// assert_eq!(Id::new("app").parts().count(), 1);
+    /// ```
+    pub fn parts(&self) -> impl Iterator<Item = &'static str> + use<> {
+        self.namespace
+            .map(interner::resolve)
+            .into_iter()
+            .flat_map(|namespace| namespace.split("::"))
+            .chain(iter::once(interner::resolve(self.name)))
+    }
+
+    /// Returns the identifier of the enclosing namespace.
+    ///
+    /// # Returns
+    ///
+    /// `None` if this identifier has no namespace.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use orrery_core::identifier::Id;
+    /// let nested = Id::new("system::frontend::app");
+    /// let parent = nested.parent().unwrap();
+    /// assert_eq!(parent, "system::frontend");
+    /// assert_eq!(parent.parent().unwrap(), "system");
+    /// assert_eq!(Id::new("system").parent(), None);
+    /// ```
+    pub fn parent(&self) -> Option<Id> {
+        self.namespace
+            .map(|namespace| Self::new(interner::resolve(namespace)))
+    }
+
+    /// Returns the final path segment as an identifier without a namespace.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use orrery_core::identifier::Id;
+    /// let nested = Id::new("system::backend");
+    /// assert_eq!(nested.leaf(), "backend");
+    /// assert_eq!(nested.leaf().namespace(), None);
+    /// ```
+    pub fn leaf(&self) -> Id {
+        Self {
+            name: self.name,
+            namespace: None,
+        }
+    }
+
+    /// Takes a snapshot of every string interned so far, in interning order.
+    ///
+    /// Identifiers interned before the snapshot can then be stored as
+    /// compact [`IdIndex`] handles into the table, see [`InternTable`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use orrery_core::identifier::Id;
+    /// let id = Id::new("system::backend");
+    /// let table = Id::intern_table_snapshot();
+    /// let index = table.index_of(id).unwrap();
+    /// assert_eq!(table.id(index), Some(id));
+    /// ```
+    pub fn intern_table_snapshot() -> InternTable {
+        let strings = interner::interner().strings().map(String::from).collect();
+        InternTable { strings }
+    }
+
+    /// Interns every string of `table` in order.
+    ///
+    /// Restoring a snapshot before interning anything else, such as at the
+    /// start of a process, puts every string at the position it had when the
+    /// snapshot was taken. Identifiers rebuilt from the table then map back
+    /// to the same [`IdIndex`] handles.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, without interning anything, if the interner already
+    /// holds a string the table does not have at the same position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use orrery_core::identifier::Id;
+    /// let table = Id::intern_table_snapshot();
+    /// assert!(Id::restore(&table).is_ok());
+    /// ```
+    pub fn restore(table: &InternTable) -> Result<(), &'static str> {
+        let mut interner = interner::interner();
+        let conflicts = interner
+            .strings()
+            .zip(&table.strings)
+            .any(|(held, restored)| held != restored);
+        if conflicts {
+            return Err("interner already holds different strings than the table");
+        }
+        for string in table.strings.iter().skip(interner.len()) {
+            interner.get_or_intern(string);
+        }
+        Ok(())
+    }
+
     /// Resolves the full path (`{namespace}::{name}` or just `{name}`).
     fn full_path(&self) -> String {
         self.full_path_with(&interner::interner())
@@ -227,6 +370,24 @@ impl fmt::Debug for Id {
     }
 }
 
+/// Serializes the full path, so serialized identifiers do not depend on the
+/// interner instance.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Id {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.full_path())
+    }
+}
+
+/// Deserializes a full path, interning it through [`Id::new`].
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Id {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let path = String::deserialize(deserializer)?;
+        Ok(Self::new(&path))
+    }
+}
+
 /// Parses a string into an [`Id`].
 ///
 /// Delegates to [`Id::new`].
@@ -309,8 +470,84 @@ impl PartialEq<&str> for Id {
     }
 }
 
+/// Compact handle of an [`Id`]: the positions of its name and namespace in
+/// an [`InternTable`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct IdIndex {
+    name: usize,
+    namespace: Option<usize>,
+}
+
+/// Snapshot of the global interner's strings, in interning order.
+///
+/// Taken with [`Id::intern_table_snapshot`]. A precompiled diagram can store
+/// its identifiers as [`IdIndex`] handles next to one table instead of
+/// repeating every path, and rebuild them in another process with
+/// [`InternTable::id`]. Serializes as a plain list of strings.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(transparent))]
+pub struct InternTable {
+    strings: Vec<String>,
+}
+
+impl InternTable {
+    /// Returns the number of strings in the table.
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Returns `true` if the table holds no strings.
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+
+    /// Returns the handle of `id` in this table.
+    ///
+    /// # Returns
+    ///
+    /// `None` if `id` was interned after the snapshot was taken, or if the
+    /// table came from another process and was not restored with
+    /// [`Id::restore`].
+    pub fn index_of(&self, id: Id) -> Option<IdIndex> {
+        let interner = interner::interner();
+        let position = |symbol: Symbol| {
+            let index = symbol.index();
+            (self.strings.get(index)? == interner.resolve(symbol)).then_some(index)
+        };
+        Some(IdIndex {
+            name: position(id.name)?,
+            namespace: match id.namespace {
+                Some(namespace) => Some(position(namespace)?),
+                None => None,
+            },
+        })
+    }
+
+    /// Rebuilds the identifier behind `index`, interning its strings.
+    ///
+    /// # Returns
+    ///
+    /// `None` if `index` points past the end of the table.
+    pub fn id(&self, index: IdIndex) -> Option<Id> {
+        let name = self.strings.get(index.name)?;
+        let namespace = match index.namespace {
+            Some(namespace) => Some(self.strings.get(namespace)?),
+            None => None,
+        };
+
+        let mut interner = interner::interner();
+        Some(Id {
+            name: interner.get_or_intern(name),
+            namespace: namespace.map(|namespace| interner.get_or_intern(namespace)),
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::thread;
+
     use super::*;
 
     #[test]
@@ -445,6 +682,31 @@ mod tests {
         assert!(id == name.as_str());
     }
 
+    #[test]
+    fn test_as_str() {
+        assert_eq!(Id::new("Rectangle").as_str(), "Rectangle");
+
+        let nested = Id::new("system").create_nested(Id::new("backend"));
+        assert_eq!(nested.as_str(), "system::backend");
+        assert_eq!(nested.as_str(), nested.to_string());
+    }
+
+    #[test]
+    fn test_parts_parent_and_leaf() {
+        let deep = Id::new("a::b::c");
+        assert_eq!(deep.parts().collect::<Vec<_>>(), ["a", "b", "c"]);
+        assert_eq!(deep.leaf(), Id::new("c"));
+
+        let parent = deep.parent().unwrap();
+        assert_eq!(parent, Id::new("a::b"));
+        assert_eq!(parent.create_nested(deep.leaf()), deep);
+
+        let simple = Id::new("a");
+        assert_eq!(simple.parts().collect::<Vec<_>>(), ["a"]);
+        assert_eq!(simple.parent(), None);
+        assert_eq!(simple.leaf(), simple);
+    }
+
     #[test]
     fn test_copy() {
         let id1 = Id::new("copy_test");
@@ -455,4 +717,72 @@ mod tests {
         assert_eq!(id1, id3);
         assert_eq!(id1, "copy_test");
     }
+
+    #[test]
+    fn test_intern_table_round_trip() {
+        let ids = [
+            Id::new("table_test"),
+            Id::new("table_test::nested::leaf"),
+            Id::from_anonymous(),
+        ];
+        let table = Id::intern_table_snapshot();
+        assert!(table.len() >= 4);
+
+        for id in ids {
+            let index = table.index_of(id).expect("id interned before the snapshot");
+            assert_eq!(table.id(index), Some(id));
+        }
+
+        let later = Id::new("table_test_interned_after_snapshot");
+        assert_eq!(table.index_of(later), None);
+        assert_eq!(InternTable::default().index_of(ids[0]), None);
+    }
+
+    #[test]
+    fn test_restore_requires_matching_prefix() {
+        Id::new("restore_test");
+        let table = Id::intern_table_snapshot();
+        assert!(Id::restore(&table).is_ok());
+
+        let conflicting = InternTable {
+            strings: vec!["restore_test_not_interned_first".to_string()],
+        };
+        assert!(Id::restore(&conflicting).is_err());
+        assert_eq!(
+            Id::intern_table_snapshot().index_of(Id::new("restore_test")),
+            table.index_of(Id::new("restore_test"))
+        );
+    }
+
+    #[test]
+    fn test_concurrent_interning() {
+        let handles: Vec<_> = (0..8)
+            .map(|thread| {
+                thread::spawn(move || {
+                    (0..100)
+                        .map(|i| {
+                            Id::new(&format!(
+                                "concurrent_{}::item_{}",
+                                i % 10,
+                                (i + thread) % 20
+                            ))
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        let per_thread: Vec<Vec<Id>> = handles
+            .into_iter()
+            .map(|handle| handle.join().expect("interning thread panicked"))
+            .collect();
+
+        let table = Id::intern_table_snapshot();
+        for (thread, ids) in per_thread.iter().enumerate() {
+            for (i, id) in ids.iter().enumerate() {
+                let path = format!("concurrent_{}::item_{}", i % 10, (i + thread) % 20);
+                assert_eq!(*id, Id::new(&path));
+                assert_eq!(table.id(table.index_of(*id).unwrap()), Some(*id));
+            }
+        }
+    }
 }
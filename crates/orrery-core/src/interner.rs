@@ -27,7 +27,7 @@
 
 use std::sync::{Mutex, MutexGuard, OnceLock};
 
-use string_interner::{DefaultSymbol, StringInterner, backend::BucketBackend};
+use string_interner::{DefaultSymbol, StringInterner, Symbol as _, backend::BucketBackend};
 
 type Inner = StringInterner<BucketBackend>;
 
@@ -42,6 +42,13 @@ static INTERNER: OnceLock<Mutex<Interner>> = OnceLock::new();
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Symbol(DefaultSymbol);
 
+impl Symbol {
+    /// Returns the position of the symbol's string in interning order.
+    pub fn index(self) -> usize {
+        self.0.to_usize()
+    }
+}
+
 /// Shared global string interner.
 ///
 /// # Examples
@@ -72,6 +79,22 @@ impl Interner {
         self.0.is_empty()
     }
 
+    /// Returns the symbol of the string at `index` in interning order, if
+    /// that many strings have been interned.
+    pub fn symbol_at(&self, index: usize) -> Option<Symbol> {
+        DefaultSymbol::try_from_usize(index)
+            .filter(|&symbol| self.0.resolve(symbol).is_some())
+            .map(Symbol)
+    }
+
+    /// Returns every interned string, in interning order.
+    pub fn strings(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.0.iter().map(|(_, s)| {
+            // SAFETY: See module-level safety documentation.
+            unsafe { &*(s as *const str) }
+        })
+    }
+
     /// Resolves a [`Symbol`] to a `&'static str`.
     ///
     /// # Panics
@@ -165,6 +188,18 @@ mod tests {
         assert_eq!(guard.len(), before + 1);
     }
 
+    #[test]
+    fn strings_follow_symbol_indices() {
+        let mut guard = interner();
+        let sym = guard.get_or_intern("interner_test_strings_order");
+        assert_eq!(guard.symbol_at(sym.index()), Some(sym));
+        assert_eq!(
+            guard.strings().nth(sym.index()),
+            Some("interner_test_strings_order")
+        );
+        assert_eq!(guard.symbol_at(guard.len()), None);
+    }
+
     #[test]
     fn len_stable_after_interning_duplicate() {
         let mut guard = interner();
@@ -9,6 +9,11 @@
 //! - **Geometry**: Basic geometric types ([`geometry`] module)
 //! - **Draw**: Visual definitions for diagram elements ([`draw`] module)
 //! - **Semantic**: Semantic model types for diagrams ([`semantic`] module)
+//!
+//! # Features
+//!
+//! - `serde`: Derives `Serialize`/`Deserialize` for [`semantic::Diagram`], its
+//!   elements, and the definitions they reference.
 
 pub mod color;
 pub mod draw;
@@ -26,9 +26,18 @@
 //!
 //! - [`diagram`] - Core diagram structures: [`Diagram`], [`Scope`], [`Block`], [`LayoutEngine`]
 //! - [`element`] - Diagram elements: [`Node`], [`Relation`], [`Fragment`], [`Note`], etc.
+//! - [`visitor`] - Traversal of the model: [`Visitor`] and [`Diagram::accept`]
+//! - [`builder`] - Programmatic construction: [`DiagramBuilder`], checked with
+//!   [`Diagram::validate`]
 
+pub mod builder;
 pub mod diagram;
 pub mod element;
+mod source;
+mod validate;
+pub mod visitor;
 
+pub use builder::*;
 pub use diagram::*;
 pub use element::*;
+pub use visitor::*;
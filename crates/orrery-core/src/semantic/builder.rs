@@ -0,0 +1,448 @@
+//! Fluent construction of semantic diagrams.
+//!
+//! [`DiagramBuilder`] assembles a [`Diagram`] from component and relation
+//! declarations without going through the parser. [`DiagramBuilder::build`]
+//! checks the same invariants the elaborator guarantees, so the result can be
+//! laid out and rendered directly.
+
+use std::{collections::HashSet, rc::Rc};
+
+use crate::{
+    draw::{
+        ArrowDefinition, ArrowDirection, DiagramDefinition, RectangleDefinition, ShapeDefinition,
+    },
+    identifier::Id,
+    semantic::{Block, Diagram, DiagramKind, Element, LayoutEngine, Node, Pin, Relation, Scope},
+};
+
+/// Builds a [`Diagram`] one component or relation at a time.
+///
+/// Elements keep their declaration order. Modifiers such as
+/// [`label`](Self::label) and [`shape`](Self::shape) apply to the element
+/// declared last. Components default to a plain rectangle and relations to a
+/// forward arrow with the default stroke.
+///
+/// Not to be confused with the pipeline `DiagramBuilder` of the `orrery`
+/// crate, which parses source text and renders diagrams.
+///
+/// # Examples
+///
+/// ```
+/// use orrery_core::semantic::{DiagramBuilder, DiagramKind, LayoutEngine};
+///
+/// let diagram = DiagramBuilder::new(DiagramKind::Component)
+///     .layout_engine(LayoutEngine::Basic)
+///     .component("client")
+///     .label("Web Client")
+///     .component("server")
+///     .component_in("server", "db")
+///     .relation("client", "server")
+///     .label("HTTP")
+///     .build()
+///     .unwrap();
+///
+/// let stats = diagram.stats();
+/// assert_eq!(stats.components(), 3);
+/// assert_eq!(stats.relations(), 1);
+/// ```
+#[derive(Debug)]
+pub struct DiagramBuilder {
+    kind: DiagramKind,
+    layout_engine: LayoutEngine,
+    definition: Rc<DiagramDefinition>,
+    entries: Vec<Entry>,
+    error: Option<String>,
+}
+
+#[derive(Debug)]
+enum Entry {
+    Component(ComponentEntry),
+    Relation(RelationEntry),
+}
+
+#[derive(Debug)]
+struct ComponentEntry {
+    id: Id,
+    parent: Option<Id>,
+    display_name: Option<String>,
+    shape_definition: Rc<Box<dyn ShapeDefinition>>,
+    pin: Pin,
+}
+
+#[derive(Debug)]
+struct RelationEntry {
+    source: Id,
+    target: Id,
+    arrow_direction: ArrowDirection,
+    label: Option<String>,
+    arrow_definition: Rc<ArrowDefinition>,
+}
+
+impl RelationEntry {
+    /// The innermost component enclosing both endpoints, or `None` for the
+    /// top-level scope.
+    fn scope(&self) -> Option<Id> {
+        let mut ancestor = self.source.parent();
+        while let Some(candidate) = ancestor {
+            let mut target_ancestor = self.target.parent();
+            while let Some(target_candidate) = target_ancestor {
+                if target_candidate == candidate {
+                    return Some(candidate);
+                }
+                target_ancestor = target_candidate.parent();
+            }
+            ancestor = candidate.parent();
+        }
+        None
+    }
+}
+
+impl DiagramBuilder {
+    /// Create a builder for an empty diagram of the given kind.
+    ///
+    /// The diagram uses the default layout engine and diagram definition
+    /// until [`layout_engine`](Self::layout_engine) or
+    /// [`definition`](Self::definition) override them.
+    pub fn new(kind: DiagramKind) -> Self {
+        Self {
+            kind,
+            layout_engine: LayoutEngine::default(),
+            definition: Rc::new(DiagramDefinition::default()),
+            entries: Vec::new(),
+            error: None,
+        }
+    }
+
+    /// Set the layout engine of the diagram.
+    pub fn layout_engine(mut self, layout_engine: LayoutEngine) -> Self {
+        self.layout_engine = layout_engine;
+        self
+    }
+
+    /// Set the diagram definition (background, spacing, and so on).
+    pub fn definition(mut self, definition: Rc<DiagramDefinition>) -> Self {
+        self.definition = definition;
+        self
+    }
+
+    /// Declare a top-level component.
+    ///
+    /// `id` must be a plain name; use [`component_in`](Self::component_in)
+    /// for nested components.
+    pub fn component(self, id: &str) -> Self {
+        self.push_component(None, id)
+    }
+
+    /// Declare a component nested inside `parent`.
+    ///
+    /// `parent` is the qualified id of an already declared component. The new
+    /// component's id is qualified by it, so
+    /// `component_in("server", "db")` declares `server::db`.
+    pub fn component_in(self, parent: &str, id: &str) -> Self {
+        self.push_component(Some(Id::new(parent)), id)
+    }
+
+    /// Declare a relation from `source` to `target`.
+    ///
+    /// Both endpoints are qualified component ids. The relation is placed in
+    /// the innermost component that encloses both of them.
+    pub fn relation(mut self, source: &str, target: &str) -> Self {
+        self.entries.push(Entry::Relation(RelationEntry {
+            source: Id::new(source),
+            target: Id::new(target),
+            arrow_direction: ArrowDirection::Forward,
+            label: None,
+            arrow_definition: Rc::new(ArrowDefinition::default()),
+        }));
+        self
+    }
+
+    /// Set the text of the last element: the display name of a component or
+    /// the label of a relation.
+    pub fn label(mut self, text: impl Into<String>) -> Self {
+        let text = text.into();
+        match self.entries.last_mut() {
+            Some(Entry::Component(component)) => component.display_name = Some(text),
+            Some(Entry::Relation(relation)) => relation.label = Some(text),
+            None => self.fail("`label` must follow a component or relation"),
+        }
+        self
+    }
+
+    /// Set the shape of the last declared component.
+    pub fn shape(mut self, shape_definition: Rc<Box<dyn ShapeDefinition>>) -> Self {
+        if let Some(component) = self.last_component("shape") {
+            component.shape_definition = shape_definition;
+        }
+        self
+    }
+
+    /// Pin the last declared component to a manual position.
+    pub fn pin(mut self, pin: Pin) -> Self {
+        if let Some(component) = self.last_component("pin") {
+            component.pin = pin;
+        }
+        self
+    }
+
+    /// Set the arrow definition of the last declared relation.
+    pub fn arrow(mut self, arrow_definition: Rc<ArrowDefinition>) -> Self {
+        if let Some(relation) = self.last_relation("arrow") {
+            relation.arrow_definition = arrow_definition;
+        }
+        self
+    }
+
+    /// Set the arrow direction of the last declared relation.
+    pub fn direction(mut self, arrow_direction: ArrowDirection) -> Self {
+        if let Some(relation) = self.last_relation("direction") {
+            relation.arrow_direction = arrow_direction;
+        }
+        self
+    }
+
+    /// Finish the diagram.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a modifier did not follow an element it applies
+    /// to, a component id is qualified or declared twice, a parent component
+    /// is not declared before its children, a sequence diagram nests
+    /// components, or a relation refers to an undeclared component.
+    pub fn build(self) -> Result<Diagram, String> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+
+        let mut components = HashSet::new();
+        for entry in &self.entries {
+            let Entry::Component(component) = entry else {
+                continue;
+            };
+            if let Some(parent) = component.parent {
+                if self.kind == DiagramKind::Sequence {
+                    return Err(format!(
+                        "sequence diagrams cannot nest component `{}`",
+                        component.id
+                    ));
+                }
+                if !components.contains(&parent) {
+                    return Err(format!("component `{parent}` not found"));
+                }
+            }
+            if !components.insert(component.id) {
+                return Err(format!("component `{}` already exists", component.id));
+            }
+        }
+        for entry in &self.entries {
+            let Entry::Relation(relation) = entry else {
+                continue;
+            };
+            for endpoint in [relation.source, relation.target] {
+                if !components.contains(&endpoint) {
+                    return Err(format!("component `{endpoint}` not found"));
+                }
+            }
+        }
+
+        let scope = Scope::new(self.scope_elements(None));
+        Ok(Diagram::new(
+            self.kind,
+            scope,
+            self.layout_engine,
+            self.definition,
+        ))
+    }
+
+    fn push_component(mut self, parent: Option<Id>, id: &str) -> Self {
+        if id.contains("::") {
+            self.fail(format!("component id `{id}` must not be qualified"));
+            return self;
+        }
+        let id = match parent {
+            Some(parent) => parent.create_nested(Id::new(id)),
+            None => Id::new(id),
+        };
+        self.entries.push(Entry::Component(ComponentEntry {
+            id,
+            parent,
+            display_name: None,
+            shape_definition: Rc::new(Box::new(RectangleDefinition::new())),
+            pin: Pin::default(),
+        }));
+        self
+    }
+
+    fn last_component(&mut self, modifier: &str) -> Option<&mut ComponentEntry> {
+        if !matches!(self.entries.last(), Some(Entry::Component(_))) {
+            self.fail(format!("`{modifier}` must follow a component"));
+            return None;
+        }
+        match self.entries.last_mut() {
+            Some(Entry::Component(component)) => Some(component),
+            _ => None,
+        }
+    }
+
+    fn last_relation(&mut self, modifier: &str) -> Option<&mut RelationEntry> {
+        if !matches!(self.entries.last(), Some(Entry::Relation(_))) {
+            self.fail(format!("`{modifier}` must follow a relation"));
+            return None;
+        }
+        match self.entries.last_mut() {
+            Some(Entry::Relation(relation)) => Some(relation),
+            _ => None,
+        }
+    }
+
+    /// Record the first misuse; [`build`](Self::build) reports it.
+    fn fail(&mut self, error: impl Into<String>) {
+        self.error.get_or_insert_with(|| error.into());
+    }
+
+    /// Assemble the elements of the scope belonging to `parent`.
+    fn scope_elements(&self, parent: Option<Id>) -> Vec<Element> {
+        self.entries
+            .iter()
+            .filter_map(|entry| match entry {
+                Entry::Component(component) if component.parent == parent => {
+                    let children = self.scope_elements(Some(component.id));
+                    let block = if children.is_empty() {
+                        Block::None
+                    } else {
+                        Block::Scope(Scope::new(children))
+                    };
+                    let node = Node::new(
+                        component.id,
+                        component.display_name.clone(),
+                        block,
+                        Rc::clone(&component.shape_definition),
+                    )
+                    .with_pin(component.pin);
+                    Some(Element::Node(node))
+                }
+                Entry::Relation(relation) if relation.scope() == parent => {
+                    Some(Element::Relation(Relation::new(
+                        relation.source,
+                        relation.target,
+                        relation.arrow_direction,
+                        relation.label.clone(),
+                        Rc::clone(&relation.arrow_definition),
+                    )))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(element: &Element) -> &Node {
+        let Element::Node(node) = element else {
+            panic!("Expected Node element");
+        };
+        node
+    }
+
+    fn relation(element: &Element) -> &Relation {
+        let Element::Relation(relation) = element else {
+            panic!("Expected Relation element");
+        };
+        relation
+    }
+
+    #[test]
+    fn test_builder_nests_components_and_relations() {
+        let diagram = DiagramBuilder::new(DiagramKind::Component)
+            .layout_engine(LayoutEngine::Sugiyama)
+            .component("client")
+            .label("Web Client")
+            .component("server")
+            .component_in("server", "api")
+            .component_in("server", "db")
+            .relation("client", "server::api")
+            .label("HTTP")
+            .relation("server::api", "server::db")
+            .direction(ArrowDirection::Bidirectional)
+            .build()
+            .unwrap();
+
+        assert_eq!(diagram.kind(), DiagramKind::Component);
+        assert_eq!(diagram.layout_engine(), LayoutEngine::Sugiyama);
+
+        let elements = diagram.scope().elements();
+        assert_eq!(elements.len(), 3);
+        assert_eq!(node(&elements[0]).display_text(), "Web Client");
+        let server = node(&elements[1]);
+        assert_eq!(server.id(), "server");
+        let client_to_api = relation(&elements[2]);
+        assert_eq!(client_to_api.target(), "server::api");
+        assert_eq!(client_to_api.label(), Some("HTTP"));
+
+        let Block::Scope(scope) = server.block() else {
+            panic!("Expected nested scope");
+        };
+        let nested = scope.elements();
+        assert_eq!(nested.len(), 3);
+        assert_eq!(node(&nested[0]).id(), "server::api");
+        assert_eq!(node(&nested[1]).id(), "server::db");
+        let api_to_db = relation(&nested[2]);
+        assert_eq!(api_to_db.source(), "server::api");
+        assert_eq!(api_to_db.arrow_direction(), ArrowDirection::Bidirectional);
+    }
+
+    #[test]
+    fn test_builder_rejects_broken_invariants() {
+        let build = |builder: DiagramBuilder| builder.build().unwrap_err();
+        let component = || DiagramBuilder::new(DiagramKind::Component);
+
+        assert_eq!(
+            build(component().component("a").relation("a", "b")),
+            "component `b` not found"
+        );
+        assert_eq!(
+            build(component().component("a").component("a")),
+            "component `a` already exists"
+        );
+        assert_eq!(
+            build(component().component_in("a", "b")),
+            "component `a` not found"
+        );
+        assert_eq!(
+            build(component().component("a::b")),
+            "component id `a::b` must not be qualified"
+        );
+        assert_eq!(
+            build(
+                DiagramBuilder::new(DiagramKind::Sequence)
+                    .component("a")
+                    .component_in("a", "b")
+            ),
+            "sequence diagrams cannot nest component `a::b`"
+        );
+        assert_eq!(
+            build(component().label("x")),
+            "`label` must follow a component or relation"
+        );
+        assert_eq!(
+            build(
+                component()
+                    .component("a")
+                    .direction(ArrowDirection::Backward)
+            ),
+            "`direction` must follow a relation"
+        );
+        assert_eq!(
+            build(
+                component()
+                    .component("a")
+                    .relation("a", "a")
+                    .pin(Pin::default())
+            ),
+            "`pin` must follow a component"
+        );
+    }
+}
@@ -3,18 +3,38 @@
 //! This module contains the fundamental building blocks of the semantic diagram model:
 //! - [`DiagramKind`] - The type of diagram (component or sequence)
 //! - [`Diagram`] - The root diagram type with kind, scope, and layout configuration
+//! - [`DiagramStats`] - Summary counts and nesting depth of a diagram
+//! - [`OutlineEntry`] - A component in the nested outline of a diagram
 //! - [`Scope`] - Container for diagram elements
 //! - [`Block`] - Represents nested content (none, scope, or embedded diagram)
 //! - [`LayoutEngine`] - Enumeration of available layout algorithms
 
-use std::{fmt, rc::Rc, str::FromStr};
+use std::{collections::HashSet, fmt, rc::Rc, str::FromStr};
 
 use serde::{Deserialize, Serialize};
 
-use crate::{draw::DiagramDefinition, semantic::element::Element};
+use crate::{
+    draw::DiagramDefinition,
+    identifier::Id,
+    semantic::{element::Element, visitor::Visitor},
+};
 
 /// The kind of a diagram: component or sequence.
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+///
+/// Converts to and from the keyword used in a diagram header.
+///
+/// # Examples
+///
+/// ```
+/// use orrery_core::semantic::DiagramKind;
+///
+/// let kind: DiagramKind = "sequence".parse().unwrap();
+/// assert_eq!(kind, DiagramKind::Sequence);
+/// assert_eq!(kind.to_string(), "sequence");
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Serialize)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+#[serde(rename_all = "snake_case")]
 pub enum DiagramKind {
     /// A component diagram showing structural relationships
     Component,
@@ -31,11 +51,24 @@ impl fmt::Display for DiagramKind {
     }
 }
 
+impl FromStr for DiagramKind {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "component" => Ok(Self::Component),
+            "sequence" => Ok(Self::Sequence),
+            _ => Err("Unsupported diagram kind"),
+        }
+    }
+}
+
 /// A scope containing a sequence of diagram elements.
 ///
 /// A scope represents a container for diagram elements (nodes, relations, notes, etc.)
 /// and forms the building block for both top-level diagrams and nested structures.
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Scope {
     elements: Vec<Element>,
 }
@@ -50,6 +83,42 @@ impl Scope {
     pub fn elements(&self) -> &[Element] {
         &self.elements
     }
+
+    /// Rename component `old` to `new` in all elements of this scope.
+    pub(crate) fn rename_component(&mut self, old: &Id, new: &Id) {
+        for element in &mut self.elements {
+            element.rename_component(old, new);
+        }
+    }
+
+    /// Collect the ids of all components declared in this scope, including
+    /// nested scopes, fragment sections, and highlight blocks.
+    fn collect_component_ids(&self, ids: &mut HashSet<Id>) {
+        fn collect(elements: &[Element], ids: &mut HashSet<Id>) {
+            for element in elements {
+                match element {
+                    Element::Node(node) => {
+                        ids.insert(node.id());
+                        if let Block::Scope(scope) = node.block() {
+                            collect(scope.elements(), ids);
+                        }
+                    }
+                    Element::Fragment(fragment) => {
+                        for section in fragment.sections() {
+                            collect(section.elements(), ids);
+                        }
+                    }
+                    Element::Highlight(highlight) => collect(highlight.elements(), ids),
+                    Element::Group(group) => collect(group.elements(), ids),
+                    Element::Relation(_)
+                    | Element::Activate(_)
+                    | Element::Deactivate(_)
+                    | Element::Note(_) => {}
+                }
+            }
+        }
+        collect(&self.elements, ids);
+    }
 }
 
 /// Available layout engines controlling automatic positioning for diagrams.
@@ -61,6 +130,7 @@ impl Scope {
 ///
 /// - [`Basic`](Self::Basic) - Simple layout algorithm (default without `graphviz` feature).
 /// - [`Sugiyama`](Self::Sugiyama) - Hierarchical layered layout using the Sugiyama method.
+/// - [`Grid`](Self::Grid) - Uniform rows and columns of components.
 /// - `Graphviz` - Graphviz-backed layout engine. Only present when the
 ///   `graphviz` Cargo feature is enabled. When enabled, this becomes the default layout engine.
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
@@ -73,6 +143,8 @@ pub enum LayoutEngine {
     Basic,
     /// Hierarchical layered layout using the Sugiyama method.
     Sugiyama,
+    /// Uniform grid layout packing components into rows and columns.
+    Grid,
     /// Graphviz-backed layout engine.
     ///
     /// Gated by the `graphviz` Cargo feature. When enabled, this becomes the default layout engine.
@@ -81,6 +153,17 @@ pub enum LayoutEngine {
     Graphviz,
 }
 
+impl LayoutEngine {
+    /// Every layout engine available in this build, in declaration order.
+    pub const ALL: &'static [Self] = &[
+        Self::Basic,
+        Self::Sugiyama,
+        Self::Grid,
+        #[cfg(feature = "graphviz")]
+        Self::Graphviz,
+    ];
+}
+
 impl FromStr for LayoutEngine {
     type Err = &'static str;
 
@@ -88,6 +171,7 @@ impl FromStr for LayoutEngine {
         match s {
             "basic" => Ok(Self::Basic),
             "sugiyama" => Ok(Self::Sugiyama),
+            "grid" => Ok(Self::Grid),
             #[cfg(feature = "graphviz")]
             "graphviz" => Ok(Self::Graphviz),
             _ => Err("Unsupported layout engine"),
@@ -100,6 +184,7 @@ impl From<LayoutEngine> for &'static str {
         match val {
             LayoutEngine::Basic => "basic",
             LayoutEngine::Sugiyama => "sugiyama",
+            LayoutEngine::Grid => "grid",
             #[cfg(feature = "graphviz")]
             LayoutEngine::Graphviz => "graphviz",
         }
@@ -120,6 +205,7 @@ impl fmt::Display for LayoutEngine {
 /// references have been resolved, attributes have been processed, and the diagram
 /// is ready to be transformed into a hierarchy and laid out for rendering.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Diagram {
     kind: DiagramKind,
     scope: Scope,
@@ -144,6 +230,9 @@ impl Diagram {
     }
 
     /// Get the diagram kind.
+    ///
+    /// The kind is known as soon as the diagram is parsed, so callers can
+    /// branch on it before laying out or rendering the diagram.
     pub fn kind(&self) -> DiagramKind {
         self.kind
     }
@@ -162,6 +251,215 @@ impl Diagram {
     pub fn definition(&self) -> &Rc<DiagramDefinition> {
         &self.definition
     }
+
+    /// Collect summary statistics for this diagram.
+    ///
+    /// Counts include elements in nested scopes, fragment sections, and
+    /// embedded diagrams. See [`DiagramStats`] for how depth is measured.
+    pub fn stats(&self) -> DiagramStats {
+        let mut stats = DiagramStats {
+            kind: self.kind,
+            components: 0,
+            relations: 0,
+            notes: 0,
+            max_depth: 0,
+        };
+        stats.collect(self.scope.elements(), 1);
+        stats
+    }
+
+    /// Build the component outline of this diagram.
+    ///
+    /// Each entry is a component; nested scopes and embedded diagrams become
+    /// its children, in declaration order.
+    pub fn outline(&self) -> Vec<OutlineEntry> {
+        OutlineEntry::collect(self.scope.elements())
+    }
+
+    /// Walk this diagram with `visitor`.
+    ///
+    /// Nested scopes, fragment sections, highlights, and embedded diagrams are
+    /// visited too, unless `visitor` overrides a method without walking into
+    /// its children. See the [`visitor`](super::visitor) module for an example.
+    pub fn accept(&self, visitor: &mut impl Visitor) {
+        visitor.visit_diagram(self);
+    }
+
+    /// Rename the component `old` to `new`, updating every reference to it.
+    ///
+    /// Relations, note attachments (`on`), and activations that reference
+    /// `old` are updated, and components nested inside it move along with it
+    /// (`old::child` becomes `new::child`). Embedded diagrams are separate
+    /// diagrams and are left untouched.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `old` is not a component of this diagram or if a
+    /// component named `new` already exists.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::rc::Rc;
+    /// # use orrery_core::{draw::DiagramDefinition, identifier::Id};
+    /// # use orrery_core::semantic::{Diagram, DiagramKind, LayoutEngine, Scope};
+    /// let mut diagram = Diagram::new(
+    ///     DiagramKind::Component,
+    ///     Scope::default(),
+    ///     LayoutEngine::Basic,
+    ///     Rc::new(DiagramDefinition::default()),
+    /// );
+    /// assert!(diagram.rename_component(&Id::new("a"), &Id::new("b")).is_err());
+    /// ```
+    pub fn rename_component(&mut self, old: &Id, new: &Id) -> Result<(), String> {
+        let mut ids = HashSet::new();
+        self.scope.collect_component_ids(&mut ids);
+        if !ids.contains(old) {
+            return Err(format!("component `{old}` does not exist"));
+        }
+        if ids.contains(new) {
+            return Err(format!("component `{new}` already exists"));
+        }
+        self.scope.rename_component(old, new);
+        Ok(())
+    }
+}
+
+/// Summary statistics for a [`Diagram`], as returned by [`Diagram::stats`].
+///
+/// Nesting depth counts component levels: a diagram whose components are all
+/// top-level has depth 1, each nested scope or embedded diagram adds one level,
+/// and a diagram without components has depth 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct DiagramStats {
+    kind: DiagramKind,
+    components: usize,
+    relations: usize,
+    notes: usize,
+    max_depth: usize,
+}
+
+impl DiagramStats {
+    /// Get the kind of the root diagram.
+    pub fn kind(&self) -> DiagramKind {
+        self.kind
+    }
+
+    /// Get the total number of components (nodes).
+    pub fn components(&self) -> usize {
+        self.components
+    }
+
+    /// Get the total number of relations.
+    pub fn relations(&self) -> usize {
+        self.relations
+    }
+
+    /// Get the total number of notes.
+    pub fn notes(&self) -> usize {
+        self.notes
+    }
+
+    /// Get the maximum component nesting depth.
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    fn collect(&mut self, elements: &[Element], depth: usize) {
+        for element in elements {
+            match element {
+                Element::Node(node) => {
+                    self.components += 1;
+                    self.max_depth = self.max_depth.max(depth);
+                    match node.block() {
+                        Block::None => {}
+                        Block::Scope(scope) => self.collect(scope.elements(), depth + 1),
+                        Block::Diagram(diagram) => {
+                            self.collect(diagram.scope().elements(), depth + 1)
+                        }
+                    }
+                }
+                Element::Relation(_) => self.relations += 1,
+                Element::Note(_) => self.notes += 1,
+                Element::Fragment(fragment) => {
+                    for section in fragment.sections() {
+                        self.collect(section.elements(), depth);
+                    }
+                }
+                Element::Highlight(highlight) => self.collect(highlight.elements(), depth),
+                Element::Group(group) => self.collect(group.elements(), depth),
+                Element::Activate(_) | Element::Deactivate(_) => {}
+            }
+        }
+    }
+}
+
+/// A component in the outline of a [`Diagram`], as returned by
+/// [`Diagram::outline`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OutlineEntry {
+    id: String,
+    label: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    diagram: Option<DiagramKind>,
+    children: Vec<OutlineEntry>,
+}
+
+impl OutlineEntry {
+    /// Get the fully qualified component identifier.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Get the display text of the component.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Get the kind of the diagram embedded in this component, if any.
+    pub fn diagram(&self) -> Option<DiagramKind> {
+        self.diagram
+    }
+
+    /// Borrow the entries nested inside this component.
+    pub fn children(&self) -> &[OutlineEntry] {
+        &self.children
+    }
+
+    fn collect(elements: &[Element]) -> Vec<Self> {
+        let mut entries = Vec::new();
+        for element in elements {
+            match element {
+                Element::Node(node) => {
+                    let (diagram, children) = match node.block() {
+                        Block::None => (None, Vec::new()),
+                        Block::Scope(scope) => (None, Self::collect(scope.elements())),
+                        Block::Diagram(diagram) => (Some(diagram.kind()), diagram.outline()),
+                    };
+                    entries.push(Self {
+                        id: node.id().to_string(),
+                        label: node.display_text().to_string(),
+                        diagram,
+                        children,
+                    });
+                }
+                Element::Fragment(fragment) => {
+                    for section in fragment.sections() {
+                        entries.extend(Self::collect(section.elements()));
+                    }
+                }
+                Element::Highlight(highlight) => {
+                    entries.extend(Self::collect(highlight.elements()))
+                }
+                Element::Group(group) => entries.extend(Self::collect(group.elements())),
+                Element::Relation(_)
+                | Element::Activate(_)
+                | Element::Deactivate(_)
+                | Element::Note(_) => {}
+            }
+        }
+        entries
+    }
 }
 
 /// A block wrapper representing empty content, a nested scope, or an embedded diagram.
@@ -173,6 +471,8 @@ impl Diagram {
 ///
 /// This enables hierarchical diagram structures where nodes can contain other diagrams.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum Block {
     /// No nested content
     None,
@@ -184,14 +484,219 @@ pub enum Block {
 
 #[cfg(test)]
 mod tests {
+    use crate::{
+        draw::{
+            ActivationBoxDefinition, ArrowDefinition, ArrowDirection, FragmentDefinition,
+            NoteDefinition, RectangleDefinition, ShapeDefinition,
+        },
+        semantic::{Activate, Fragment, FragmentSection, Node, Note, NoteAlign, Relation},
+    };
+
     use super::*;
 
+    fn node(id: &str, block: Block) -> Element {
+        let shape_def = Rc::new(Box::new(RectangleDefinition::new()) as Box<dyn ShapeDefinition>);
+        Element::Node(Node::new(Id::new(id), None, block, shape_def))
+    }
+
+    fn relation(source: &str, target: &str) -> Element {
+        Element::Relation(Relation::new(
+            Id::new(source),
+            Id::new(target),
+            ArrowDirection::Forward,
+            None,
+            Rc::new(ArrowDefinition::default()),
+        ))
+    }
+
+    fn rename_test_diagram() -> Diagram {
+        let elements = vec![
+            node(
+                "service",
+                Block::Scope(Scope::new(vec![node("service::db", Block::None)])),
+            ),
+            node("client", Block::None),
+            relation("client", "service"),
+            relation("client", "service::db"),
+            Element::Activate(Activate::new(
+                Id::new("service"),
+                Rc::new(ActivationBoxDefinition::default()),
+            )),
+            Element::Deactivate(Id::new("service")),
+            Element::Fragment(Fragment::new(
+                "opt".to_string(),
+                vec![FragmentSection::new(
+                    None,
+                    vec![relation("service", "client")],
+                )],
+                Rc::new(FragmentDefinition::default()),
+            )),
+            Element::Note(Note::new(
+                vec![Id::new("service"), Id::new("client")],
+                NoteAlign::Over,
+                "note".to_string(),
+                Rc::new(NoteDefinition::default()),
+            )),
+        ];
+        Diagram::new(
+            DiagramKind::Component,
+            Scope::new(elements),
+            LayoutEngine::Basic,
+            Rc::new(DiagramDefinition::default()),
+        )
+    }
+
+    #[test]
+    fn test_rename_component_updates_references() {
+        let mut diagram = rename_test_diagram();
+        diagram
+            .rename_component(&Id::new("service"), &Id::new("backend"))
+            .unwrap();
+
+        let elements = diagram.scope().elements();
+        let Element::Node(service) = &elements[0] else {
+            panic!("Expected Node element");
+        };
+        assert_eq!(service.id(), "backend");
+        let Block::Scope(scope) = service.block() else {
+            panic!("Expected nested scope");
+        };
+        let Element::Node(db) = &scope.elements()[0] else {
+            panic!("Expected nested Node element");
+        };
+        assert_eq!(db.id(), "backend::db");
+
+        let Element::Relation(relation) = &elements[2] else {
+            panic!("Expected Relation element");
+        };
+        assert_eq!(relation.target(), "backend");
+        let Element::Relation(relation) = &elements[3] else {
+            panic!("Expected Relation element");
+        };
+        assert_eq!(relation.target(), "backend::db");
+        let Element::Activate(activate) = &elements[4] else {
+            panic!("Expected Activate element");
+        };
+        assert_eq!(activate.component(), "backend");
+        let Element::Deactivate(id) = &elements[5] else {
+            panic!("Expected Deactivate element");
+        };
+        assert_eq!(*id, "backend");
+        let Element::Fragment(fragment) = &elements[6] else {
+            panic!("Expected Fragment element");
+        };
+        let Element::Relation(relation) = &fragment.sections()[0].elements()[0] else {
+            panic!("Expected Relation element");
+        };
+        assert_eq!(relation.source(), "backend");
+        let Element::Note(note) = &elements[7] else {
+            panic!("Expected Note element");
+        };
+        assert_eq!(note.on(), [Id::new("backend"), Id::new("client")]);
+    }
+
+    #[test]
+    fn test_rename_component_errors() {
+        let mut diagram = rename_test_diagram();
+
+        let err = diagram
+            .rename_component(&Id::new("service"), &Id::new("client"))
+            .unwrap_err();
+        assert_eq!(err, "component `client` already exists");
+
+        let err = diagram
+            .rename_component(&Id::new("missing"), &Id::new("other"))
+            .unwrap_err();
+        assert_eq!(err, "component `missing` does not exist");
+
+        // A failed rename leaves the diagram untouched.
+        let Element::Node(service) = &diagram.scope().elements()[0] else {
+            panic!("Expected Node element");
+        };
+        assert_eq!(service.id(), "service");
+    }
+
+    #[test]
+    fn test_outline_follows_nesting() {
+        let embedded = Diagram::new(
+            DiagramKind::Sequence,
+            Scope::new(vec![node("user", Block::None)]),
+            LayoutEngine::Basic,
+            Rc::new(DiagramDefinition::default()),
+        );
+        let mut diagram = rename_test_diagram();
+        diagram
+            .scope
+            .elements
+            .push(node("flow", Block::Diagram(embedded)));
+
+        let outline = diagram.outline();
+        let ids: Vec<_> = outline.iter().map(OutlineEntry::id).collect();
+        assert_eq!(ids, ["service", "client", "flow"]);
+
+        let [db] = outline[0].children() else {
+            panic!("Expected a single nested component");
+        };
+        assert_eq!(db.id(), "service::db");
+        assert_eq!(db.label(), "db");
+        assert!(db.children().is_empty());
+
+        assert_eq!(outline[2].diagram(), Some(DiagramKind::Sequence));
+        assert_eq!(outline[2].children()[0].id(), "user");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_diagram_json_round_trip() {
+        use crate::{color::Color, draw::OvalDefinition};
+
+        let mut oval: Box<dyn ShapeDefinition> = Box::new(OvalDefinition::new());
+        oval.set_fill_color(Some(Color::new("#1e88e5").unwrap()))
+            .unwrap();
+        let embedded = Diagram::new(
+            DiagramKind::Sequence,
+            Scope::new(vec![node("flow::user", Block::None)]),
+            LayoutEngine::Sugiyama,
+            Rc::new(DiagramDefinition::default()),
+        );
+        let mut elements = rename_test_diagram().scope.elements;
+        elements.push(Element::Node(Node::new(
+            Id::new("flow"),
+            Some("Flow".to_string()),
+            Block::Diagram(embedded),
+            Rc::new(oval),
+        )));
+        let diagram = Diagram::new(
+            DiagramKind::Component,
+            Scope::new(elements),
+            LayoutEngine::Basic,
+            Rc::new(DiagramDefinition::default()),
+        );
+
+        let json = serde_json::to_string(&diagram).unwrap();
+        let restored: Diagram = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(serde_json::to_string(&restored).unwrap(), json);
+        assert_eq!(restored.stats(), diagram.stats());
+        assert_eq!(restored.outline(), diagram.outline());
+        assert!(json.contains(r#""id":"service::db""#));
+        assert!(json.contains(r#""shape":"oval""#));
+    }
+
     #[test]
     fn test_diagram_kind_display() {
         assert_eq!(DiagramKind::Component.to_string(), "component");
         assert_eq!(DiagramKind::Sequence.to_string(), "sequence");
     }
 
+    #[test]
+    fn test_diagram_kind_from_str_round_trips() {
+        for kind in [DiagramKind::Component, DiagramKind::Sequence] {
+            assert_eq!(kind.to_string().parse::<DiagramKind>(), Ok(kind));
+        }
+        assert!("class".parse::<DiagramKind>().is_err());
+    }
+
     #[test]
     fn test_layout_engine_from_str() {
         assert_eq!(
@@ -202,6 +707,7 @@ mod tests {
             "sugiyama".parse::<LayoutEngine>().unwrap(),
             LayoutEngine::Sugiyama
         );
+        assert_eq!("grid".parse::<LayoutEngine>().unwrap(), LayoutEngine::Grid);
         #[cfg(feature = "graphviz")]
         assert_eq!(
             "graphviz".parse::<LayoutEngine>().unwrap(),
@@ -213,6 +719,14 @@ mod tests {
         assert_eq!(result.unwrap_err(), "Unsupported layout engine");
     }
 
+    #[test]
+    fn test_layout_engine_all_round_trips() {
+        for engine in LayoutEngine::ALL {
+            assert_eq!(engine.to_string().parse::<LayoutEngine>(), Ok(*engine));
+        }
+        assert!(LayoutEngine::ALL.contains(&LayoutEngine::default()));
+    }
+
     #[test]
     fn test_layout_engine_default() {
         #[cfg(feature = "graphviz")]
@@ -225,6 +739,7 @@ mod tests {
     fn test_layout_engine_display() {
         assert_eq!(LayoutEngine::Basic.to_string(), "basic");
         assert_eq!(LayoutEngine::Sugiyama.to_string(), "sugiyama");
+        assert_eq!(LayoutEngine::Grid.to_string(), "grid");
         #[cfg(feature = "graphviz")]
         assert_eq!(LayoutEngine::Graphviz.to_string(), "graphviz");
     }
@@ -2,22 +2,69 @@
 
 use std::{fmt, rc::Rc, str::FromStr};
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 use crate::{
     draw::{
         ActivationBoxDefinition, ArrowDefinition, ArrowDirection, FragmentDefinition,
-        NoteDefinition, ShapeDefinition, Text,
+        GroupDefinition, HighlightDefinition, NoteDefinition, ShapeDefinition, Text,
     },
+    geometry::Point,
     identifier::Id,
     semantic::diagram::Block,
 };
 
+/// A manual position override for a node, set through its `x` and `y` attributes.
+///
+/// Each axis is pinned independently; an axis without a value is left to the
+/// layout engine.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Pin {
+    x: Option<f32>,
+    y: Option<f32>,
+}
+
+impl Pin {
+    /// Create a new Pin with the given fixed coordinates.
+    pub fn new(x: Option<f32>, y: Option<f32>) -> Self {
+        Self { x, y }
+    }
+
+    /// Get the pinned x-coordinate, if any.
+    pub fn x(&self) -> Option<f32> {
+        self.x
+    }
+
+    /// Get the pinned y-coordinate, if any.
+    pub fn y(&self) -> Option<f32> {
+        self.y
+    }
+
+    /// Returns `true` if neither axis is pinned.
+    pub fn is_empty(&self) -> bool {
+        self.x.is_none() && self.y.is_none()
+    }
+
+    /// Replaces the pinned axes of `position`, keeping the others unchanged.
+    pub fn apply(&self, position: Point) -> Point {
+        let position = self.x.map_or(position, |x| position.with_x(x));
+        self.y.map_or(position, |y| position.with_y(y))
+    }
+}
+
 /// A diagram node (component/participant) with visual definition and nested content.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Node {
     id: Id,
     display_name: Option<String>,
     block: Block,
+    #[cfg_attr(feature = "serde", serde(with = "crate::draw::shape_definition_serde"))]
     shape_definition: Rc<Box<dyn ShapeDefinition>>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pin: Pin,
 }
 
 impl Node {
@@ -33,9 +80,16 @@ impl Node {
             display_name,
             block,
             shape_definition,
+            pin: Pin::default(),
         }
     }
 
+    /// Set the manual position override of this node.
+    pub fn with_pin(mut self, pin: Pin) -> Self {
+        self.pin = pin;
+        self
+    }
+
     /// Get the node identifier.
     pub fn id(&self) -> Id {
         self.id
@@ -51,6 +105,11 @@ impl Node {
         &self.shape_definition
     }
 
+    /// Get the node's manual position override.
+    pub fn pin(&self) -> Pin {
+        self.pin
+    }
+
     /// Returns the display text for this node.
     ///
     /// Uses `display_name` if present, otherwise falls back to the identifier name.
@@ -71,6 +130,7 @@ impl fmt::Display for Node {
 ///
 /// Relations represent connections between nodes in diagrams.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Relation {
     source: Id,
     target: Id,
@@ -98,9 +158,20 @@ impl Relation {
         }
     }
 
-    /// Build a Text drawable for the relation's label using its text definition, if a label exists.
+    /// Get the relation's label, if one was given.
+    ///
+    /// An explicitly empty label (`a -> b: "";`) is `Some("")`, distinct from
+    /// a relation without a label.
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    /// Build a Text drawable for the relation's label using its text definition.
+    ///
+    /// Returns `None` when there is no label or the label is empty, so empty
+    /// labels reserve no space and emit no text.
     pub fn text(&self) -> Option<Text<'_>> {
-        let label = self.label.as_ref()?;
+        let label = self.label().filter(|label| !label.is_empty())?;
         let text_def = self.arrow_definition.text();
         Some(Text::new(text_def, label))
     }
@@ -138,6 +209,8 @@ impl Relation {
 /// - Sequence diagrams: Over, Left, Right
 /// - Component diagrams: Left, Right, Top, Bottom
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum NoteAlign {
     Over,
     Left,
@@ -161,6 +234,18 @@ impl FromStr for NoteAlign {
     }
 }
 
+impl fmt::Display for NoteAlign {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            NoteAlign::Over => "over",
+            NoteAlign::Left => "left",
+            NoteAlign::Right => "right",
+            NoteAlign::Top => "top",
+            NoteAlign::Bottom => "bottom",
+        })
+    }
+}
+
 /// A note annotation in a diagram.
 ///
 /// Notes provide additional context or documentation without participating
@@ -194,6 +279,7 @@ impl FromStr for NoteAlign {
 /// assert_eq!(attached_note.on().len(), 1);
 /// ```
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Note {
     /// Element IDs this note is attached to. Empty vec means margin note.
     on: Vec<Id>,
@@ -244,6 +330,7 @@ impl Note {
 
 /// An activation box highlighting a participant's active period in a sequence diagram.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Activate {
     /// Component ID being activated
     component: Id,
@@ -277,6 +364,7 @@ impl Activate {
 /// complex message flows and illustrate alternatives, loops, parallel execution,
 /// and other control flow patterns.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Fragment {
     /// The operation string (e.g., "alt", "opt", "loop", "par")
     operation: String,
@@ -323,6 +411,7 @@ impl Fragment {
 /// Each section can have an optional title and contains a sequence of elements
 /// that represent one phase within the fragment.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct FragmentSection {
     /// Optional title for this section
     title: Option<String>,
@@ -347,11 +436,94 @@ impl FragmentSection {
     }
 }
 
+/// A highlight band that marks a time window in a sequence diagram.
+///
+/// The band spans all lifelines and the vertical range of its enclosed elements.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Highlight {
+    /// The label displayed in the band (e.g., "during transaction")
+    label: String,
+    /// Elements enclosed by the band
+    elements: Vec<Element>,
+    /// The highlight definition for this band's styling
+    definition: Rc<HighlightDefinition>,
+}
+
+impl Highlight {
+    /// Create a new Highlight.
+    pub fn new(label: String, elements: Vec<Element>, definition: Rc<HighlightDefinition>) -> Self {
+        Self {
+            label,
+            elements,
+            definition,
+        }
+    }
+
+    /// Get the label of this highlight band.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Get the elements enclosed by this highlight band.
+    pub fn elements(&self) -> &[Element] {
+        &self.elements
+    }
+
+    /// Get the highlight definition for this band.
+    pub fn definition(&self) -> &Rc<HighlightDefinition> {
+        &self.definition
+    }
+}
+
+/// A labelled boundary drawn around a set of elements in a component diagram.
+///
+/// A group does not introduce a scope: its nodes belong to the enclosing scope
+/// and are laid out with their siblings. Groups may be nested.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Group {
+    /// The label displayed in the title tab
+    label: String,
+    /// Elements enclosed by the group
+    elements: Vec<Element>,
+    /// The group definition for this boundary's styling
+    definition: Rc<GroupDefinition>,
+}
+
+impl Group {
+    /// Create a new Group.
+    pub fn new(label: String, elements: Vec<Element>, definition: Rc<GroupDefinition>) -> Self {
+        Self {
+            label,
+            elements,
+            definition,
+        }
+    }
+
+    /// Get the label of this group.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Get the elements enclosed by this group.
+    pub fn elements(&self) -> &[Element] {
+        &self.elements
+    }
+
+    /// Get the group definition for this boundary.
+    pub fn definition(&self) -> &Rc<GroupDefinition> {
+        &self.definition
+    }
+}
+
 /// Top-level elaborated element within a scope.
 ///
 /// The Element enum represents all possible diagram elements that can appear
 /// within a scope.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum Element {
     /// A diagram node
     Node(Node),
@@ -363,10 +535,74 @@ pub enum Element {
     Deactivate(Id),
     /// Fragment block
     Fragment(Fragment),
+    /// Highlight band
+    Highlight(Highlight),
+    /// Group boundary
+    Group(Group),
     /// Note annotation
     Note(Note),
 }
 
+impl Element {
+    /// Rename component `old` to `new` in this element and its nested elements.
+    ///
+    /// Components nested inside `old` are moved to the `new` namespace.
+    /// Embedded diagrams are not entered.
+    pub(crate) fn rename_component(&mut self, old: &Id, new: &Id) {
+        let rename = |id: &mut Id| *id = renamed_id(*id, old, new);
+        match self {
+            Element::Node(node) => {
+                rename(&mut node.id);
+                if let Block::Scope(scope) = &mut node.block {
+                    scope.rename_component(old, new);
+                }
+            }
+            Element::Relation(relation) => {
+                rename(&mut relation.source);
+                rename(&mut relation.target);
+            }
+            Element::Activate(activate) => rename(&mut activate.component),
+            Element::Deactivate(id) => rename(id),
+            Element::Fragment(fragment) => {
+                for section in &mut fragment.sections {
+                    for element in &mut section.elements {
+                        element.rename_component(old, new);
+                    }
+                }
+            }
+            Element::Highlight(highlight) => {
+                for element in &mut highlight.elements {
+                    element.rename_component(old, new);
+                }
+            }
+            Element::Group(group) => {
+                for element in &mut group.elements {
+                    element.rename_component(old, new);
+                }
+            }
+            Element::Note(note) => note.on.iter_mut().for_each(rename),
+        }
+    }
+}
+
+/// Returns `id` with `old` replaced by `new`, either as the whole id or as a
+/// prefix of its namespace.
+fn renamed_id(id: Id, old: &Id, new: &Id) -> Id {
+    if id == *old {
+        return *new;
+    }
+    let Some(namespace) = id.namespace() else {
+        return id;
+    };
+    let old_path = old.to_string();
+    match namespace.strip_prefix(old_path.as_str()) {
+        Some(rest) if rest.is_empty() || rest.starts_with("::") => {
+            Id::new(&format!("{new}{rest}::{}", id.name()))
+        }
+        _ => id,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -383,4 +619,44 @@ mod tests {
         assert!(result.is_err());
         assert_eq!(result.unwrap_err(), "Invalid alignment value");
     }
+
+    #[test]
+    fn test_relation_empty_label_has_no_text() {
+        let relation = |label: Option<&str>| {
+            Relation::new(
+                Id::new("a"),
+                Id::new("b"),
+                ArrowDirection::Forward,
+                label.map(str::to_string),
+                Rc::new(ArrowDefinition::default()),
+            )
+        };
+
+        let empty = relation(Some(""));
+        assert_eq!(empty.label(), Some(""));
+        assert!(empty.text().is_none());
+
+        let missing = relation(None);
+        assert_eq!(missing.label(), None);
+        assert!(missing.text().is_none());
+
+        let labeled = relation(Some("call"));
+        assert_eq!(labeled.text().unwrap().content(), "call");
+    }
+
+    #[test]
+    fn test_pin_apply_replaces_pinned_axes_only() {
+        let position = Point::new(10.0, 20.0);
+
+        assert!(Pin::default().is_empty());
+        assert_eq!(Pin::default().apply(position), position);
+        assert_eq!(
+            Pin::new(Some(5.0), None).apply(position),
+            Point::new(5.0, 20.0)
+        );
+        assert_eq!(
+            Pin::new(Some(5.0), Some(7.0)).apply(position),
+            Point::new(5.0, 7.0)
+        );
+    }
 }
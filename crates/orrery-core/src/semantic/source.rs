@@ -0,0 +1,756 @@
+//! Emitting `.orr` source text from the semantic model.
+//!
+//! [`Diagram::to_source`] writes a diagram back as source that parses to an
+//! equivalent diagram: the same components, shape types, relations, notes,
+//! activations, and blocks, in the same order, with the same styling. Styling
+//! is already folded into the element definitions at this point, so it is
+//! written as inline attributes on each element, listing only what differs
+//! from the built-in types of the default theme.
+
+use std::{any::Any, fmt, rc::Rc};
+
+use crate::{
+    color::{Color, Theme},
+    draw::{
+        ActivationBoxDefinition, ActorDefinition, ArrowDefinition, BoundaryDefinition,
+        ComponentDefinition, ControlDefinition, DiagramDefinition, EntityDefinition,
+        FragmentDefinition, GroupDefinition, HighlightDefinition, ImageDefinition,
+        InterfaceDefinition, LifelineDefinition, LinkDefinition, NoteDefinition, OvalDefinition,
+        RectangleDefinition, ShadowDefinition, ShapeDefinition, StrokeDefinition, StrokeStyle,
+        TextDefinition,
+    },
+    geometry::{Insets, Size},
+    identifier::Id,
+    semantic::{Block, Diagram, Element},
+};
+
+impl Diagram {
+    /// Emit `.orr` source text reproducing this diagram.
+    ///
+    /// Parsing the result with the default configuration yields an
+    /// equivalent diagram, though not necessarily the original text:
+    /// references are written fully qualified, activation blocks as explicit
+    /// statements, and type definitions as inline attributes on each element
+    /// that uses them. Attributes are only written where an element differs
+    /// from the built-in type of the default theme.
+    ///
+    /// Styling that no attribute can express is dropped: removing the fill of
+    /// a filled shape, the opacity of strokes other than a shape's outline,
+    /// and uneven text padding. Shape definitions that are not built in are
+    /// written as `Rectangle`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orrery_core::semantic::{DiagramBuilder, DiagramKind, LayoutEngine};
+    ///
+    /// let diagram = DiagramBuilder::new(DiagramKind::Component)
+    ///     .layout_engine(LayoutEngine::Basic)
+    ///     .component("client")
+    ///     .component("server")
+    ///     .relation("client", "server")
+    ///     .label("HTTP")
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     diagram.to_source(),
+    ///     "diagram component [layout_engine=\"basic\"];\n\
+    ///      \n\
+    ///      client: Rectangle;\n\
+    ///      server: Rectangle;\n\
+    ///      client -> server: \"HTTP\";\n",
+    /// );
+    /// ```
+    pub fn to_source(&self) -> String {
+        let mut writer = SourceWriter::default();
+        writer.header(self);
+        writer.line("");
+        writer.elements(self.scope().elements());
+        writer.out
+    }
+}
+
+#[derive(Default)]
+struct SourceWriter {
+    out: String,
+    depth: usize,
+    /// The component embedding the diagram being written, whose prefix the
+    /// ids of that diagram carry.
+    embedding: Option<Id>,
+    defaults: Defaults,
+}
+
+impl SourceWriter {
+    fn line(&mut self, text: &str) {
+        if !text.is_empty() {
+            for _ in 0..self.depth {
+                self.out.push_str("    ");
+            }
+            self.out.push_str(text);
+        }
+        self.out.push('\n');
+    }
+
+    /// Write a reference to `id`, relative to the embedding component.
+    ///
+    /// Qualified references resolve from the diagram root, and the root of an
+    /// embedded diagram is inside its embedding component.
+    fn reference(&self, id: Id) -> String {
+        let id = id.to_string();
+        self.embedding
+            .and_then(|embedding| {
+                id.strip_prefix(&format!("{embedding}::"))
+                    .map(str::to_string)
+            })
+            .unwrap_or(id)
+    }
+
+    fn header(&mut self, diagram: &Diagram) {
+        let mut attributes = Attributes::default();
+        attributes.push("layout_engine", quote(&diagram.layout_engine().to_string()));
+        attributes.diagram(diagram.definition(), &self.defaults);
+        self.line(&format!(
+            "diagram {}{};",
+            diagram.kind(),
+            attributes.bracketed()
+        ));
+    }
+
+    fn elements(&mut self, elements: &[Element]) {
+        for element in elements {
+            self.element(element);
+        }
+    }
+
+    /// Write `opening {`, the nested elements, and the closing `};`.
+    fn block(&mut self, opening: &str, elements: &[Element]) {
+        self.line(&format!("{opening} {{"));
+        self.depth += 1;
+        self.elements(elements);
+        self.depth -= 1;
+        self.line("};");
+    }
+
+    fn element(&mut self, element: &Element) {
+        match element {
+            Element::Node(node) => {
+                let id = node.id();
+                let mut declaration = id.name().to_string();
+                if node.display_text() != id.name() {
+                    declaration.push_str(&format!(" as {}", quote(node.display_text())));
+                }
+                let shape = node.shape_definition().as_ref().as_ref();
+                let type_name = shape_type_name(shape);
+                declaration.push_str(": ");
+                declaration.push_str(type_name);
+
+                let mut attributes = Attributes::default();
+                attributes.shape(shape, self.defaults.shape(type_name).as_ref());
+                let pin = node.pin();
+                for (axis, value) in [("x", pin.x()), ("y", pin.y())] {
+                    if let Some(value) = value {
+                        attributes.push(axis, value);
+                    }
+                }
+                declaration.push_str(&attributes.bracketed());
+
+                match node.block() {
+                    Block::None => self.line(&format!("{declaration};")),
+                    Block::Scope(scope) => self.block(&declaration, scope.elements()),
+                    Block::Diagram(diagram) => {
+                        self.line(&format!("{declaration} embed {{"));
+                        self.depth += 1;
+                        let outer = self.embedding.replace(id);
+                        self.header(diagram);
+                        self.line("");
+                        self.elements(diagram.scope().elements());
+                        self.embedding = outer;
+                        self.depth -= 1;
+                        self.line("};");
+                    }
+                }
+            }
+            Element::Relation(relation) => {
+                let mut attributes = Attributes::default();
+                attributes.arrow(relation.arrow_definition(), &self.defaults);
+                let mut statement = format!(
+                    "{} {}{} {}",
+                    self.reference(relation.source()),
+                    relation.arrow_direction(),
+                    attributes.bracketed(),
+                    self.reference(relation.target())
+                );
+                if let Some(label) = relation.label() {
+                    statement.push_str(&format!(": {}", quote(label)));
+                }
+                self.line(&format!("{statement};"));
+            }
+            Element::Activate(activate) => {
+                let mut attributes = Attributes::default();
+                attributes.activation_box(activate.definition(), &self.defaults);
+                let component = self.reference(activate.component());
+                self.line(&format!("activate{} {component};", attributes.bracketed()));
+            }
+            Element::Deactivate(id) => {
+                let component = self.reference(*id);
+                self.line(&format!("deactivate {component};"));
+            }
+            Element::Fragment(fragment) => {
+                let mut attributes = Attributes::default();
+                attributes.fragment(fragment.definition(), &self.defaults);
+                self.line(&format!(
+                    "fragment{} {} {{",
+                    attributes.bracketed(),
+                    quote(fragment.operation())
+                ));
+                self.depth += 1;
+                for section in fragment.sections() {
+                    let opening = match section.title() {
+                        Some(title) => format!("section {}", quote(title)),
+                        None => "section".to_string(),
+                    };
+                    self.block(&opening, section.elements());
+                }
+                self.depth -= 1;
+                self.line("};");
+            }
+            Element::Highlight(highlight) => {
+                let mut attributes = Attributes::default();
+                attributes.highlight(highlight.definition(), &self.defaults);
+                self.block(
+                    &format!(
+                        "highlight{} {}",
+                        attributes.bracketed(),
+                        quote(highlight.label())
+                    ),
+                    highlight.elements(),
+                );
+            }
+            Element::Group(group) => {
+                let mut attributes = Attributes::default();
+                attributes.group(group.definition(), &self.defaults);
+                self.block(
+                    &format!("group{} {}", attributes.bracketed(), quote(group.label())),
+                    group.elements(),
+                );
+            }
+            Element::Note(note) => {
+                let on: Vec<_> = note.on().iter().map(|id| self.reference(*id)).collect();
+                let mut attributes = Attributes::default();
+                attributes.push("on", format!("[{}]", on.join(", ")));
+                attributes.push("align", quote(&note.align().to_string()));
+                attributes.note(note.definition(), &self.defaults);
+                self.line(&format!(
+                    "note{}: {};",
+                    attributes.bracketed(),
+                    quote(note.content())
+                ));
+            }
+        }
+    }
+}
+
+/// The definitions of the built-in types under one theme, which elements are
+/// compared against to find the attributes they set.
+///
+/// These mirror the parser's built-in types together with the `Stroke` and
+/// `Text` types they reference: shape outlines are 2 wide, and the strokes of
+/// groups, fragment separators and lifelines are dashed.
+struct Defaults {
+    theme: Theme,
+    stroke: StrokeDefinition,
+    dashed_stroke: StrokeDefinition,
+    text: TextDefinition,
+    arrow: ArrowDefinition,
+    note: NoteDefinition,
+    activation_box: ActivationBoxDefinition,
+    fragment: FragmentDefinition,
+    highlight: HighlightDefinition,
+    group: GroupDefinition,
+}
+
+impl Defaults {
+    fn new(theme: &Theme) -> Self {
+        let mut stroke = StrokeDefinition::default();
+        stroke.set_color(theme.stroke());
+        let mut dashed_stroke = stroke.clone();
+        dashed_stroke.set_style(StrokeStyle::Dashed);
+        Self {
+            theme: theme.clone(),
+            stroke,
+            dashed_stroke,
+            text: TextDefinition::from_theme(theme),
+            arrow: ArrowDefinition::from_theme(theme),
+            note: NoteDefinition::from_theme(theme),
+            activation_box: ActivationBoxDefinition::from_theme(theme),
+            fragment: FragmentDefinition::from_theme(theme),
+            highlight: HighlightDefinition::new(),
+            group: GroupDefinition::from_theme(theme),
+        }
+    }
+
+    /// The definition of the built-in shape type `type_name`.
+    fn shape(&self, type_name: &str) -> Box<dyn ShapeDefinition> {
+        let mut shape: Box<dyn ShapeDefinition> = match type_name {
+            "Oval" => Box::new(OvalDefinition::new()),
+            "Component" => Box::new(ComponentDefinition::new()),
+            "Actor" => Box::new(ActorDefinition::new()),
+            "Boundary" => Box::new(BoundaryDefinition::new()),
+            "Control" => Box::new(ControlDefinition::new()),
+            "Entity" => Box::new(EntityDefinition::new()),
+            "Interface" => Box::new(InterfaceDefinition::new()),
+            _ => Box::new(RectangleDefinition::new()),
+        };
+        let mut stroke = self.stroke.clone();
+        stroke.set_width(2.0);
+        shape.set_stroke(Rc::new(stroke));
+        shape.set_text(Rc::new(self.text.clone()));
+        if matches!(type_name, "Rectangle" | "Oval" | "Component") {
+            let min_size = Size::new(
+                self.theme.min_width().unwrap_or_default(),
+                self.theme.min_height().unwrap_or_default(),
+            );
+            shape
+                .set_min_size(min_size)
+                .expect("boxed shapes support a minimum size");
+        } else {
+            shape
+                .set_fill_color(Some(self.theme.fill()))
+                .expect("built-in shapes support fill colors");
+        }
+        shape
+    }
+}
+
+impl Default for Defaults {
+    fn default() -> Self {
+        Self::new(&Theme::default())
+    }
+}
+
+/// The inline attributes of an element, as `name=value` pairs.
+#[derive(Default)]
+struct Attributes(Vec<String>);
+
+impl Attributes {
+    fn push(&mut self, name: &str, value: impl fmt::Display) {
+        self.0.push(format!("{name}={value}"));
+    }
+
+    /// Write the attributes as ` [...]`, or nothing if there are none.
+    fn bracketed(&self) -> String {
+        if self.0.is_empty() {
+            String::new()
+        } else {
+            format!(" [{}]", self.0.join(", "))
+        }
+    }
+
+    /// Add the number `value` if it differs from `default`.
+    fn number<T: PartialEq + fmt::Display>(&mut self, name: &str, value: T, default: T) {
+        if value != default {
+            self.push(name, value);
+        }
+    }
+
+    /// Add `value` as a string literal if it differs from `default`.
+    fn string<T: PartialEq + fmt::Display>(&mut self, name: &str, value: T, default: T) {
+        if value != default {
+            self.push(name, quote(&value.to_string()));
+        }
+    }
+
+    /// Add an optional `value` as a string literal if it is set and differs
+    /// from `default`.
+    fn optional<T: PartialEq + fmt::Display>(
+        &mut self,
+        name: &str,
+        value: Option<T>,
+        default: Option<T>,
+    ) {
+        if value != default
+            && let Some(value) = value
+        {
+            self.push(name, quote(&value.to_string()));
+        }
+    }
+
+    /// Add the color `value` as a string literal if it differs from `default`.
+    fn color(&mut self, name: &str, value: Color, default: Color) {
+        self.optional_color(name, Some(value), Some(default));
+    }
+
+    /// Add an optional color `value` as a string literal if it is set and
+    /// differs from `default`.
+    fn optional_color(&mut self, name: &str, value: Option<Color>, default: Option<Color>) {
+        if value != default
+            && let Some(value) = value
+        {
+            self.push(name, quote(&value.to_css_string()));
+        }
+    }
+
+    /// Add `nested` as an inline attribute list if it is not empty.
+    fn nested(&mut self, name: &str, nested: Attributes) {
+        if !nested.0.is_empty() {
+            self.push(name, format!("[{}]", nested.0.join(", ")));
+        }
+    }
+
+    /// Add `insets` as a single number if its sides are equal, and as an
+    /// inline `[top=..., right=..., bottom=..., left=...]` list otherwise.
+    fn insets(&mut self, name: &str, insets: Insets) {
+        let sides = [insets.top(), insets.right(), insets.bottom(), insets.left()];
+        if sides.iter().all(|&side| side == insets.top()) {
+            self.push(name, insets.top());
+        } else {
+            let mut nested = Attributes::default();
+            for (side, value) in ["top", "right", "bottom", "left"].into_iter().zip(sides) {
+                nested.number(side, value, 0.0);
+            }
+            self.push(name, format!("[{}]", nested.0.join(", ")));
+        }
+    }
+
+    fn stroke(&mut self, name: &str, stroke: &StrokeDefinition, default: &StrokeDefinition) {
+        let mut nested = Attributes::default();
+        nested.color("color", stroke.color(), default.color());
+        nested.number("width", stroke.width(), default.width());
+        nested.string("style", stroke.style(), default.style());
+        nested.string(
+            "cap",
+            stroke.cap().to_svg_value(),
+            default.cap().to_svg_value(),
+        );
+        nested.string(
+            "join",
+            stroke.join().to_svg_value(),
+            default.join().to_svg_value(),
+        );
+        self.nested(name, nested);
+    }
+
+    fn text(&mut self, name: &str, text: &TextDefinition, default: &TextDefinition) {
+        let mut nested = Attributes::default();
+        nested.number("font_size", text.font_size(), default.font_size());
+        nested.string("font_family", text.font_family(), default.font_family());
+        nested.string(
+            "font_style",
+            text.font_style().to_svg_value(),
+            default.font_style().to_svg_value(),
+        );
+        if text.background_color() != default.background_color() {
+            let color = text
+                .background_color()
+                .map_or_else(|| "none".to_string(), Color::to_css_string);
+            nested.push("background_color", quote(&color));
+        }
+        nested.optional_color("color", text.color().copied(), default.color().copied());
+        if text.padding() != default.padding() {
+            nested.push("padding", text.padding().top());
+        }
+        self.nested(name, nested);
+    }
+
+    fn link(&mut self, link: Option<&LinkDefinition>) {
+        if let Some(link) = link {
+            self.optional("href", link.href(), None);
+            self.optional("target", link.target(), None);
+        }
+    }
+
+    fn shape(&mut self, shape: &dyn ShapeDefinition, default: &dyn ShapeDefinition) {
+        self.optional_color("fill_color", shape.fill_color(), default.fill_color());
+        self.number("fill_opacity", shape.fill_opacity(), default.fill_opacity());
+        self.stroke("stroke", shape.stroke(), default.stroke());
+        self.number(
+            "stroke_opacity",
+            shape.stroke().opacity(),
+            default.stroke().opacity(),
+        );
+        self.number("corner_radius", shape.rounded(), default.rounded());
+        self.number(
+            "min_width",
+            shape.min_size().width(),
+            default.min_size().width(),
+        );
+        self.number(
+            "min_height",
+            shape.min_size().height(),
+            default.min_size().height(),
+        );
+        self.string(
+            "content_align",
+            shape.content_align(),
+            default.content_align(),
+        );
+        self.text("text", shape.text(), default.text());
+        if let Some(image) = shape.image().filter(|_| shape.image() != default.image()) {
+            let mut nested = Attributes::default();
+            let unset = ImageDefinition::new("");
+            nested.push("source", quote(image.source()));
+            nested.string("align", image.align(), unset.align());
+            nested.number("size", image.size(), unset.size());
+            self.nested("icon", nested);
+        }
+        if shape.shadow() != default.shadow() {
+            match shape.shadow() {
+                Some(shadow) => {
+                    let mut nested = Attributes::default();
+                    let unset = ShadowDefinition::default();
+                    nested.number("blur", shadow.blur(), unset.blur());
+                    nested.number("dx", shadow.dx(), unset.dx());
+                    nested.number("dy", shadow.dy(), unset.dy());
+                    nested.color("color", shadow.color(), unset.color());
+                    if nested.0.is_empty() {
+                        self.push("shadow", quote("true"));
+                    } else {
+                        self.nested("shadow", nested);
+                    }
+                }
+                None => self.push("shadow", quote("false")),
+            }
+        }
+        self.optional("tooltip", shape.tooltip(), default.tooltip());
+        if shape.link() != default.link() {
+            self.link(shape.link());
+        }
+    }
+
+    fn arrow(&mut self, arrow: &ArrowDefinition, defaults: &Defaults) {
+        let default = &defaults.arrow;
+        self.stroke("stroke", arrow.stroke(), &defaults.stroke);
+        self.string("style", arrow.style(), default.style());
+        self.text("text", arrow.text(), default.text());
+        if arrow.segments() != default.segments() {
+            let segments: Vec<_> = arrow
+                .segments()
+                .iter()
+                .map(|segment| format!("{}:{}", segment.style(), segment.fraction()))
+                .collect();
+            self.push("segments", quote(&segments.join(", ")));
+        }
+        self.optional("arrowhead", arrow.arrowhead(), default.arrowhead());
+        self.optional(
+            "arrowhead_start",
+            arrow.arrowhead_start(),
+            default.arrowhead_start(),
+        );
+        self.optional(
+            "arrowhead_end",
+            arrow.arrowhead_end(),
+            default.arrowhead_end(),
+        );
+        self.string(
+            "label_pos",
+            arrow.label_position(),
+            default.label_position(),
+        );
+        self.number("label_offset", arrow.label_offset(), default.label_offset());
+        self.number("curvature", arrow.curvature(), default.curvature());
+        self.number("min_length", arrow.min_length(), default.min_length());
+        self.optional("tooltip", arrow.tooltip(), default.tooltip());
+        if arrow.link() != default.link() {
+            self.link(arrow.link());
+        }
+    }
+
+    fn note(&mut self, note: &NoteDefinition, defaults: &Defaults) {
+        let default = &defaults.note;
+        self.optional_color(
+            "background_color",
+            note.background_color(),
+            default.background_color(),
+        );
+        self.stroke("stroke", note.stroke(), &defaults.stroke);
+        self.text("text", note.text(), &defaults.text);
+    }
+
+    fn activation_box(&mut self, activation_box: &ActivationBoxDefinition, defaults: &Defaults) {
+        let default = &defaults.activation_box;
+        self.number("width", activation_box.width(), default.width());
+        self.number(
+            "nesting_offset",
+            activation_box.nesting_offset(),
+            default.nesting_offset(),
+        );
+        self.color(
+            "fill_color",
+            activation_box.fill_color(),
+            default.fill_color(),
+        );
+        self.stroke("stroke", activation_box.stroke(), &defaults.stroke);
+    }
+
+    fn fragment(&mut self, fragment: &FragmentDefinition, defaults: &Defaults) {
+        let default = &defaults.fragment;
+        self.stroke("border_stroke", fragment.border_stroke(), &defaults.stroke);
+        self.stroke(
+            "separator_stroke",
+            fragment.separator_stroke(),
+            &defaults.dashed_stroke,
+        );
+        self.optional_color(
+            "background_color",
+            fragment.background_color().copied(),
+            default.background_color().copied(),
+        );
+        self.text(
+            "operation_label_text",
+            fragment.operation_label_text(),
+            default.operation_label_text(),
+        );
+        self.text(
+            "section_title_text",
+            fragment.section_title_text(),
+            default.section_title_text(),
+        );
+    }
+
+    fn highlight(&mut self, highlight: &HighlightDefinition, defaults: &Defaults) {
+        self.color(
+            "fill_color",
+            highlight.fill_color(),
+            defaults.highlight.fill_color(),
+        );
+        self.text("text", highlight.text(), &defaults.text);
+    }
+
+    fn group(&mut self, group: &GroupDefinition, defaults: &Defaults) {
+        let default = &defaults.group;
+        self.stroke("stroke", group.stroke(), &defaults.dashed_stroke);
+        self.text("text", group.text(), &defaults.text);
+        self.color(
+            "tab_fill_color",
+            group.tab_fill_color(),
+            default.tab_fill_color(),
+        );
+        if group.padding() != default.padding() {
+            self.insets("padding", group.padding());
+        }
+    }
+
+    fn lifeline(&mut self, lifeline: &LifelineDefinition, defaults: &Defaults) {
+        let mut nested = Attributes::default();
+        nested.stroke("stroke", lifeline.stroke(), &defaults.dashed_stroke);
+        self.nested("lifeline", nested);
+    }
+
+    fn diagram(&mut self, diagram: &DiagramDefinition, defaults: &Defaults) {
+        self.optional_color(
+            "canvas_color",
+            diagram.canvas_color(),
+            defaults.theme.background(),
+        );
+        if let Some(padding) = diagram.canvas_padding() {
+            self.insets("canvas_padding", padding);
+        }
+        if let Some(width) = diagram.width() {
+            self.push("width", width);
+        }
+        if let Some(height) = diagram.height() {
+            self.push("height", height);
+        }
+        self.string("fit", diagram.fit(), Default::default());
+        self.optional("title", diagram.title(), None);
+        self.optional("caption", diagram.caption(), None);
+        self.lifeline(diagram.lifeline(), defaults);
+        if let Some(spacing) = diagram.rank_spacing() {
+            self.push("rank_spacing", spacing);
+        }
+        if let Some(spacing) = diagram.intra_rank_spacing() {
+            self.push("intra_rank_spacing", spacing);
+        }
+        self.string("edge_routing", diagram.edge_routing(), Default::default());
+        if let Some(columns) = diagram.columns() {
+            self.push("columns", columns);
+        }
+        self.optional("density", diagram.density(), None);
+        self.string("autocolor", diagram.autocolor(), false);
+    }
+}
+
+/// The built-in type name for a shape definition, `Rectangle` for shapes
+/// that are not built in.
+fn shape_type_name(definition: &dyn ShapeDefinition) -> &'static str {
+    let definition: &dyn Any = definition;
+    if definition.is::<OvalDefinition>() {
+        "Oval"
+    } else if definition.is::<ComponentDefinition>() {
+        "Component"
+    } else if definition.is::<ActorDefinition>() {
+        "Actor"
+    } else if definition.is::<BoundaryDefinition>() {
+        "Boundary"
+    } else if definition.is::<ControlDefinition>() {
+        "Control"
+    } else if definition.is::<EntityDefinition>() {
+        "Entity"
+    } else if definition.is::<InterfaceDefinition>() {
+        "Interface"
+    } else {
+        "Rectangle"
+    }
+}
+
+/// Write `text` as a string literal, escaping it where needed.
+fn quote(text: &str) -> String {
+    let mut literal = String::with_capacity(text.len() + 2);
+    literal.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => literal.push_str("\\\""),
+            '\\' => literal.push_str("\\\\"),
+            '\n' => literal.push_str("\\n"),
+            '\r' => literal.push_str("\\r"),
+            '\t' => literal.push_str("\\t"),
+            '\0' => literal.push_str("\\0"),
+            c if c.is_control() => literal.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => literal.push(c),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::semantic::{DiagramBuilder, DiagramKind, LayoutEngine};
+
+    #[test]
+    fn test_to_source_nests_blocks() {
+        let diagram = DiagramBuilder::new(DiagramKind::Component)
+            .layout_engine(LayoutEngine::Basic)
+            .component("client")
+            .label("Web \"Client\"")
+            .component("server")
+            .component_in("server", "db")
+            .relation("client", "server::db")
+            .relation("client", "server")
+            .label("line 1\nline 2")
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            diagram.to_source(),
+            r#"diagram component [layout_engine="basic"];
+
+client as "Web \"Client\"": Rectangle;
+server: Rectangle {
+    db: Rectangle;
+};
+client -> server::db;
+client -> server: "line 1\nline 2";
+"#
+        );
+    }
+
+    #[test]
+    fn test_quote_escapes_special_characters() {
+        assert_eq!(quote("plain"), "\"plain\"");
+        assert_eq!(quote("a\\b\t\"c\""), r#""a\\b\t\"c\"""#);
+        assert_eq!(quote("\u{7}"), r#""\u{7}""#);
+    }
+}
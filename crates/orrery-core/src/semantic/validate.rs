@@ -0,0 +1,487 @@
+//! Structural checks for diagrams assembled outside the parser.
+//!
+//! The parser only produces diagrams that layout can handle. Diagrams built
+//! in code or deserialized from another tool carry no such guarantee, so
+//! [`Diagram::validate`] checks the invariants layout relies on before they
+//! are rendered.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    identifier::Id,
+    semantic::{Block, Diagram, DiagramKind, Element, NoteAlign},
+};
+
+impl Diagram {
+    /// Check that this diagram is structurally valid for layout.
+    ///
+    /// A valid diagram declares each component once, nests component ids
+    /// under the id of their enclosing component, only references components
+    /// declared in the same diagram, uses only the elements and note
+    /// alignments of its kind, and deactivates only active components.
+    /// Embedded diagrams are checked the same way.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first problem found, prefixed with the path of the
+    /// offending value in the serialized diagram, such as
+    /// `scope.elements[2].relation.target`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::rc::Rc;
+    ///
+    /// use orrery_core::{
+    ///     draw::{ArrowDefinition, ArrowDirection, DiagramDefinition},
+    ///     identifier::Id,
+    ///     semantic::{Diagram, DiagramKind, Element, LayoutEngine, Relation, Scope},
+    /// };
+    ///
+    /// let relation = Relation::new(
+    ///     Id::new("a"),
+    ///     Id::new("b"),
+    ///     ArrowDirection::Forward,
+    ///     None,
+    ///     Rc::new(ArrowDefinition::default()),
+    /// );
+    /// let diagram = Diagram::new(
+    ///     DiagramKind::Component,
+    ///     Scope::new(vec![Element::Relation(relation)]),
+    ///     LayoutEngine::Basic,
+    ///     Rc::new(DiagramDefinition::default()),
+    /// );
+    ///
+    /// assert_eq!(
+    ///     diagram.validate().unwrap_err(),
+    ///     "scope.elements[0].relation.source: component `a` not found",
+    /// );
+    /// ```
+    pub fn validate(&self) -> Result<(), String> {
+        validate_diagram(self, "", None)
+    }
+}
+
+/// Check `diagram`, found at `path`, whose top-level ids are nested under
+/// `embedding`.
+fn validate_diagram(diagram: &Diagram, path: &str, embedding: Option<Id>) -> Result<(), String> {
+    let mut validator = Validator {
+        kind: diagram.kind(),
+        declared: HashSet::new(),
+        active: HashMap::new(),
+    };
+    let path = format!("{path}scope.elements");
+    validator.declare(diagram.scope().elements(), &path, embedding)?;
+    validator.check(diagram.scope().elements(), &path)
+}
+
+struct Validator {
+    kind: DiagramKind,
+    declared: HashSet<Id>,
+    /// Activation depth of each component, for pairing deactivations.
+    active: HashMap<Id, usize>,
+}
+
+impl Validator {
+    /// Record the components declared in `elements`, nested in `container`,
+    /// and validate the diagrams they embed.
+    fn declare(
+        &mut self,
+        elements: &[Element],
+        path: &str,
+        container: Option<Id>,
+    ) -> Result<(), String> {
+        for (index, element) in elements.iter().enumerate() {
+            let path = format!("{path}[{index}]");
+            match element {
+                Element::Node(node) => {
+                    let path = format!("{path}.node");
+                    let id = node.id();
+                    if id.parent() != container {
+                        return Err(match container {
+                            Some(container) => format!(
+                                "{path}.id: component id `{id}` must be nested in `{container}`"
+                            ),
+                            None => {
+                                format!("{path}.id: component id `{id}` must not be qualified")
+                            }
+                        });
+                    }
+                    if !self.declared.insert(id) {
+                        return Err(format!("{path}.id: component `{id}` already exists"));
+                    }
+
+                    match node.block() {
+                        Block::None => {}
+                        Block::Scope(_) if self.kind == DiagramKind::Sequence => {
+                            return Err(format!(
+                                "{path}.block: sequence diagrams cannot nest components in `{id}`"
+                            ));
+                        }
+                        Block::Scope(scope) => self.declare(
+                            scope.elements(),
+                            &format!("{path}.block.scope.elements"),
+                            Some(id),
+                        )?,
+                        Block::Diagram(diagram) => {
+                            validate_diagram(diagram, &format!("{path}.block.diagram."), Some(id))?
+                        }
+                    }
+                }
+                Element::Fragment(fragment) => {
+                    for (index, section) in fragment.sections().iter().enumerate() {
+                        self.declare(
+                            section.elements(),
+                            &format!("{path}.fragment.sections[{index}].elements"),
+                            container,
+                        )?;
+                    }
+                }
+                Element::Highlight(highlight) => self.declare(
+                    highlight.elements(),
+                    &format!("{path}.highlight.elements"),
+                    container,
+                )?,
+                Element::Group(group) => self.declare(
+                    group.elements(),
+                    &format!("{path}.group.elements"),
+                    container,
+                )?,
+                Element::Relation(_)
+                | Element::Activate(_)
+                | Element::Deactivate(_)
+                | Element::Note(_) => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Check the elements and references in `elements` against the kind of
+    /// the diagram and its declared components.
+    fn check(&mut self, elements: &[Element], path: &str) -> Result<(), String> {
+        for (index, element) in elements.iter().enumerate() {
+            let path = format!("{path}[{index}]");
+            let unsupported = matches!(
+                (element, self.kind),
+                (Element::Group(_), DiagramKind::Sequence)
+                    | (
+                        Element::Activate(_)
+                            | Element::Deactivate(_)
+                            | Element::Fragment(_)
+                            | Element::Highlight(_),
+                        DiagramKind::Component,
+                    )
+            );
+            if unsupported {
+                return Err(format!(
+                    "{path}: `{}` is not supported in {} diagrams",
+                    element_name(element),
+                    self.kind
+                ));
+            }
+
+            match element {
+                Element::Node(node) => {
+                    if let Block::Scope(scope) = node.block() {
+                        self.check(
+                            scope.elements(),
+                            &format!("{path}.node.block.scope.elements"),
+                        )?;
+                    }
+                }
+                Element::Relation(relation) => {
+                    self.reference(relation.source(), &format!("{path}.relation.source"))?;
+                    self.reference(relation.target(), &format!("{path}.relation.target"))?;
+                }
+                Element::Activate(activate) => {
+                    let id = activate.component();
+                    self.reference(id, &format!("{path}.activate.component"))?;
+                    *self.active.entry(id).or_default() += 1;
+                }
+                Element::Deactivate(id) => {
+                    let path = format!("{path}.deactivate");
+                    self.reference(*id, &path)?;
+                    match self.active.get_mut(id) {
+                        Some(depth) if *depth > 0 => *depth -= 1,
+                        _ => return Err(format!("{path}: component `{id}` is not active")),
+                    }
+                }
+                Element::Fragment(fragment) => {
+                    for (index, section) in fragment.sections().iter().enumerate() {
+                        self.check(
+                            section.elements(),
+                            &format!("{path}.fragment.sections[{index}].elements"),
+                        )?;
+                    }
+                }
+                Element::Highlight(highlight) => {
+                    self.check(highlight.elements(), &format!("{path}.highlight.elements"))?;
+                }
+                Element::Group(group) => {
+                    self.check(group.elements(), &format!("{path}.group.elements"))?;
+                }
+                Element::Note(note) => {
+                    for (index, &id) in note.on().iter().enumerate() {
+                        self.reference(id, &format!("{path}.note.on[{index}]"))?;
+                    }
+                    let supported = match self.kind {
+                        DiagramKind::Sequence => {
+                            matches!(
+                                note.align(),
+                                NoteAlign::Over | NoteAlign::Left | NoteAlign::Right
+                            )
+                        }
+                        DiagramKind::Component => note.align() != NoteAlign::Over,
+                    };
+                    if !supported {
+                        return Err(format!(
+                            "{path}.note.align: alignment `{}` is not supported in {} diagrams",
+                            note.align(),
+                            self.kind
+                        ));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn reference(&self, id: Id, path: &str) -> Result<(), String> {
+        if self.declared.contains(&id) {
+            Ok(())
+        } else {
+            Err(format!("{path}: component `{id}` not found"))
+        }
+    }
+}
+
+/// The serialized name of an element's variant.
+fn element_name(element: &Element) -> &'static str {
+    match element {
+        Element::Node(_) => "node",
+        Element::Relation(_) => "relation",
+        Element::Activate(_) => "activate",
+        Element::Deactivate(_) => "deactivate",
+        Element::Fragment(_) => "fragment",
+        Element::Highlight(_) => "highlight",
+        Element::Group(_) => "group",
+        Element::Note(_) => "note",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::{
+        draw::{
+            ActivationBoxDefinition, ArrowDefinition, ArrowDirection, DiagramDefinition,
+            GroupDefinition, NoteDefinition, RectangleDefinition, ShapeDefinition,
+        },
+        semantic::{Activate, DiagramBuilder, Group, LayoutEngine, Node, Note, Relation, Scope},
+    };
+
+    fn node(id: &str, block: Block) -> Element {
+        let shape: Box<dyn ShapeDefinition> = Box::new(RectangleDefinition::new());
+        Element::Node(Node::new(Id::new(id), None, block, Rc::new(shape)))
+    }
+
+    fn relation(source: &str, target: &str) -> Element {
+        Element::Relation(Relation::new(
+            Id::new(source),
+            Id::new(target),
+            ArrowDirection::Forward,
+            None,
+            Rc::new(ArrowDefinition::default()),
+        ))
+    }
+
+    fn note(on: &[&str], align: NoteAlign) -> Element {
+        Element::Note(Note::new(
+            on.iter().map(|id| Id::new(id)).collect(),
+            align,
+            "note".to_string(),
+            Rc::new(NoteDefinition::new()),
+        ))
+    }
+
+    fn diagram(kind: DiagramKind, elements: Vec<Element>) -> Diagram {
+        Diagram::new(
+            kind,
+            Scope::new(elements),
+            LayoutEngine::Basic,
+            Rc::new(DiagramDefinition::default()),
+        )
+    }
+
+    #[test]
+    fn test_validate_accepts_built_diagrams() {
+        let built = DiagramBuilder::new(DiagramKind::Component)
+            .component("client")
+            .component("server")
+            .component_in("server", "db")
+            .relation("client", "server::db")
+            .build()
+            .unwrap();
+        assert_eq!(built.validate(), Ok(()));
+
+        let embedded = diagram(
+            DiagramKind::Component,
+            vec![node(
+                "flow",
+                Block::Diagram(diagram(
+                    DiagramKind::Sequence,
+                    vec![
+                        node("flow::a", Block::None),
+                        node("flow::b", Block::None),
+                        relation("flow::a", "flow::b"),
+                    ],
+                )),
+            )],
+        );
+        assert_eq!(embedded.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_rejects_misplaced_and_duplicate_ids() {
+        let qualified = diagram(DiagramKind::Component, vec![node("b::c", Block::None)]);
+        assert_eq!(
+            qualified.validate().unwrap_err(),
+            "scope.elements[0].node.id: component id `b::c` must not be qualified"
+        );
+
+        let unnested = diagram(
+            DiagramKind::Component,
+            vec![node(
+                "b",
+                Block::Scope(Scope::new(vec![node("c", Block::None)])),
+            )],
+        );
+        assert_eq!(
+            unnested.validate().unwrap_err(),
+            "scope.elements[0].node.block.scope.elements[0].node.id: \
+             component id `c` must be nested in `b`"
+        );
+
+        let duplicate = diagram(
+            DiagramKind::Component,
+            vec![node("a", Block::None), node("a", Block::None)],
+        );
+        assert_eq!(
+            duplicate.validate().unwrap_err(),
+            "scope.elements[1].node.id: component `a` already exists"
+        );
+    }
+
+    #[test]
+    fn test_validate_reports_path_of_unknown_reference() {
+        let nested = diagram(
+            DiagramKind::Component,
+            vec![
+                node("a", Block::None),
+                node(
+                    "b",
+                    Block::Scope(Scope::new(vec![
+                        node("b::c", Block::None),
+                        relation("b::c", "a"),
+                    ])),
+                ),
+                note(&["a", "b::d"], NoteAlign::Left),
+            ],
+        );
+        assert_eq!(
+            nested.validate().unwrap_err(),
+            "scope.elements[2].note.on[1]: component `b::d` not found"
+        );
+
+        // Embedded diagrams only see their own components.
+        let embedded = diagram(
+            DiagramKind::Component,
+            vec![
+                node("outer", Block::None),
+                node(
+                    "flow",
+                    Block::Diagram(diagram(
+                        DiagramKind::Sequence,
+                        vec![node("flow::a", Block::None), relation("flow::a", "outer")],
+                    )),
+                ),
+            ],
+        );
+        assert_eq!(
+            embedded.validate().unwrap_err(),
+            "scope.elements[1].node.block.diagram.scope.elements[1].relation.target: \
+             component `outer` not found"
+        );
+    }
+
+    #[test]
+    fn test_validate_rejects_elements_of_other_kind() {
+        let group = Element::Group(Group::new(
+            "g".to_string(),
+            vec![node("a", Block::None)],
+            Rc::new(GroupDefinition::default()),
+        ));
+        assert_eq!(
+            diagram(DiagramKind::Sequence, vec![group])
+                .validate()
+                .unwrap_err(),
+            "scope.elements[0]: `group` is not supported in sequence diagrams"
+        );
+
+        let activate = Element::Activate(Activate::new(
+            Id::new("a"),
+            Rc::new(ActivationBoxDefinition::default()),
+        ));
+        assert_eq!(
+            diagram(
+                DiagramKind::Component,
+                vec![node("a", Block::None), activate]
+            )
+            .validate()
+            .unwrap_err(),
+            "scope.elements[1]: `activate` is not supported in component diagrams"
+        );
+
+        let top = diagram(
+            DiagramKind::Sequence,
+            vec![node("a", Block::None), note(&["a"], NoteAlign::Top)],
+        );
+        assert_eq!(
+            top.validate().unwrap_err(),
+            "scope.elements[1].note.align: alignment `top` is not supported in sequence diagrams"
+        );
+    }
+
+    #[test]
+    fn test_validate_rejects_unpaired_deactivate() {
+        let activate = || {
+            Element::Activate(Activate::new(
+                Id::new("a"),
+                Rc::new(ActivationBoxDefinition::default()),
+            ))
+        };
+        let deactivate = || Element::Deactivate(Id::new("a"));
+
+        let paired = diagram(
+            DiagramKind::Sequence,
+            vec![node("a", Block::None), activate(), deactivate()],
+        );
+        assert_eq!(paired.validate(), Ok(()));
+
+        let unpaired = diagram(
+            DiagramKind::Sequence,
+            vec![
+                node("a", Block::None),
+                activate(),
+                deactivate(),
+                deactivate(),
+            ],
+        );
+        assert_eq!(
+            unpaired.validate().unwrap_err(),
+            "scope.elements[3].deactivate: component `a` is not active"
+        );
+    }
+}
@@ -0,0 +1,346 @@
+//! Traversal of the semantic model.
+//!
+//! [`Visitor`] has one method per kind of element. Every method has a default
+//! implementation: leaf elements do nothing, and elements with nested content
+//! call the matching `walk_*` function to visit their children. An
+//! implementation overrides the methods it cares about and, when overriding a
+//! method of an element with nested content, calls the `walk_*` function
+//! itself to keep descending.
+//!
+//! [`Diagram::accept`] starts a traversal at the root of a diagram. Elements
+//! are visited in declaration order, including the contents of nested scopes,
+//! fragment sections, highlights, groups and embedded diagrams.
+//!
+//! # Examples
+//!
+//! Counting the components of a diagram, including nested ones:
+//!
+//! ```
+//! # use std::rc::Rc;
+//! # use orrery_core::{
+//! #     draw::{DiagramDefinition, RectangleDefinition, ShapeDefinition},
+//! #     identifier::Id,
+//! # };
+//! use orrery_core::semantic::{
+//!     Block, Diagram, DiagramKind, Element, LayoutEngine, Node, Scope, Visitor, walk_component,
+//! };
+//!
+//! #[derive(Default)]
+//! struct ComponentCounter {
+//!     count: usize,
+//! }
+//!
+//! impl Visitor for ComponentCounter {
+//!     fn visit_component(&mut self, node: &Node) {
+//!         self.count += 1;
+//!         walk_component(self, node);
+//!     }
+//! }
+//!
+//! # let shape: Rc<Box<dyn ShapeDefinition>> = Rc::new(Box::new(RectangleDefinition::new()));
+//! # let node = |id: &str, block| Element::Node(Node::new(Id::new(id), None, block, Rc::clone(&shape)));
+//! let nested = Scope::new(vec![node("api::db", Block::None)]);
+//! let diagram = Diagram::new(
+//!     DiagramKind::Component,
+//!     Scope::new(vec![node("api", Block::Scope(nested)), node("client", Block::None)]),
+//!     LayoutEngine::Basic,
+//!     Rc::new(DiagramDefinition::default()),
+//! );
+//!
+//! let mut counter = ComponentCounter::default();
+//! diagram.accept(&mut counter);
+//! assert_eq!(counter.count, 3);
+//! ```
+
+use crate::{
+    identifier::Id,
+    semantic::{
+        Activate, Block, Diagram, Element, Fragment, FragmentSection, Group, Highlight, Node, Note,
+        Relation, Scope,
+    },
+};
+
+/// A read-only traversal of a [`Diagram`].
+///
+/// See the [module documentation](self) for how the default methods descend
+/// into nested content.
+pub trait Visitor {
+    /// Visit a diagram, either the root diagram or one embedded in a component.
+    fn visit_diagram(&mut self, diagram: &Diagram) {
+        walk_diagram(self, diagram);
+    }
+
+    /// Visit a scope of elements.
+    fn visit_scope(&mut self, scope: &Scope) {
+        walk_scope(self, scope);
+    }
+
+    /// Visit an element, dispatching to the method for its kind.
+    fn visit_element(&mut self, element: &Element) {
+        walk_element(self, element);
+    }
+
+    /// Visit a component and its nested scope or embedded diagram.
+    fn visit_component(&mut self, node: &Node) {
+        walk_component(self, node);
+    }
+
+    /// Visit a relation.
+    fn visit_relation(&mut self, _relation: &Relation) {}
+
+    /// Visit the start of an activation.
+    fn visit_activate(&mut self, _activate: &Activate) {}
+
+    /// Visit the end of the activation of `component`.
+    fn visit_deactivate(&mut self, _component: Id) {}
+
+    /// Visit a fragment and its sections.
+    fn visit_fragment(&mut self, fragment: &Fragment) {
+        walk_fragment(self, fragment);
+    }
+
+    /// Visit a fragment section and its elements.
+    fn visit_fragment_section(&mut self, section: &FragmentSection) {
+        walk_elements(self, section.elements());
+    }
+
+    /// Visit a highlight and its elements.
+    fn visit_highlight(&mut self, highlight: &Highlight) {
+        walk_elements(self, highlight.elements());
+    }
+
+    /// Visit a group and its elements.
+    fn visit_group(&mut self, group: &Group) {
+        walk_elements(self, group.elements());
+    }
+
+    /// Visit a note.
+    fn visit_note(&mut self, _note: &Note) {}
+}
+
+/// Visit the top-level scope of `diagram`.
+pub fn walk_diagram<V: Visitor + ?Sized>(visitor: &mut V, diagram: &Diagram) {
+    visitor.visit_scope(diagram.scope());
+}
+
+/// Visit the elements of `scope`.
+pub fn walk_scope<V: Visitor + ?Sized>(visitor: &mut V, scope: &Scope) {
+    walk_elements(visitor, scope.elements());
+}
+
+/// Visit each of `elements` in order.
+pub fn walk_elements<V: Visitor + ?Sized>(visitor: &mut V, elements: &[Element]) {
+    for element in elements {
+        visitor.visit_element(element);
+    }
+}
+
+/// Call the `visit_*` method matching the kind of `element`.
+pub fn walk_element<V: Visitor + ?Sized>(visitor: &mut V, element: &Element) {
+    match element {
+        Element::Node(node) => visitor.visit_component(node),
+        Element::Relation(relation) => visitor.visit_relation(relation),
+        Element::Activate(activate) => visitor.visit_activate(activate),
+        Element::Deactivate(component) => visitor.visit_deactivate(*component),
+        Element::Fragment(fragment) => visitor.visit_fragment(fragment),
+        Element::Highlight(highlight) => visitor.visit_highlight(highlight),
+        Element::Group(group) => visitor.visit_group(group),
+        Element::Note(note) => visitor.visit_note(note),
+    }
+}
+
+/// Visit the nested scope or embedded diagram of `node`, if it has one.
+pub fn walk_component<V: Visitor + ?Sized>(visitor: &mut V, node: &Node) {
+    match node.block() {
+        Block::None => {}
+        Block::Scope(scope) => visitor.visit_scope(scope),
+        Block::Diagram(diagram) => visitor.visit_diagram(diagram),
+    }
+}
+
+/// Visit the sections of `fragment` in order.
+pub fn walk_fragment<V: Visitor + ?Sized>(visitor: &mut V, fragment: &Fragment) {
+    for section in fragment.sections() {
+        visitor.visit_fragment_section(section);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use crate::{
+        draw::{
+            ActivationBoxDefinition, ArrowDefinition, ArrowDirection, DiagramDefinition,
+            FragmentDefinition, HighlightDefinition, NoteDefinition, RectangleDefinition,
+            ShapeDefinition,
+        },
+        semantic::{DiagramKind, LayoutEngine, NoteAlign},
+    };
+
+    use super::*;
+
+    /// Records every visited element as one line.
+    #[derive(Default)]
+    struct Recorder {
+        events: Vec<String>,
+    }
+
+    impl Visitor for Recorder {
+        fn visit_diagram(&mut self, diagram: &Diagram) {
+            self.events.push(format!("diagram {}", diagram.kind()));
+            walk_diagram(self, diagram);
+        }
+
+        fn visit_component(&mut self, node: &Node) {
+            self.events.push(format!("component {}", node.id()));
+            walk_component(self, node);
+        }
+
+        fn visit_relation(&mut self, relation: &Relation) {
+            let (source, target) = (relation.source(), relation.target());
+            self.events.push(format!("relation {source} {target}"));
+        }
+
+        fn visit_activate(&mut self, activate: &Activate) {
+            self.events
+                .push(format!("activate {}", activate.component()));
+        }
+
+        fn visit_deactivate(&mut self, component: Id) {
+            self.events.push(format!("deactivate {component}"));
+        }
+
+        fn visit_fragment_section(&mut self, section: &FragmentSection) {
+            let title = section.title().unwrap_or_default();
+            self.events.push(format!("section {title}"));
+            walk_elements(self, section.elements());
+        }
+
+        fn visit_highlight(&mut self, highlight: &Highlight) {
+            self.events.push(format!("highlight {}", highlight.label()));
+            walk_elements(self, highlight.elements());
+        }
+
+        fn visit_note(&mut self, note: &Note) {
+            self.events.push(format!("note {}", note.content()));
+        }
+    }
+
+    fn node(id: &str, block: Block) -> Element {
+        let shape_def = Rc::new(Box::new(RectangleDefinition::new()) as Box<dyn ShapeDefinition>);
+        Element::Node(Node::new(Id::new(id), None, block, shape_def))
+    }
+
+    fn relation(source: &str, target: &str) -> Element {
+        Element::Relation(Relation::new(
+            Id::new(source),
+            Id::new(target),
+            ArrowDirection::Forward,
+            None,
+            Rc::new(ArrowDefinition::default()),
+        ))
+    }
+
+    fn diagram(kind: DiagramKind, elements: Vec<Element>) -> Diagram {
+        Diagram::new(
+            kind,
+            Scope::new(elements),
+            LayoutEngine::Basic,
+            Rc::new(DiagramDefinition::default()),
+        )
+    }
+
+    #[test]
+    fn test_accept_visits_all_elements_in_order() {
+        let embedded = diagram(
+            DiagramKind::Sequence,
+            vec![
+                node("a", Block::None),
+                Element::Activate(Activate::new(
+                    Id::new("a"),
+                    Rc::new(ActivationBoxDefinition::default()),
+                )),
+                Element::Fragment(Fragment::new(
+                    "alt".to_string(),
+                    vec![
+                        FragmentSection::new(Some("ok".to_string()), vec![relation("a", "a")]),
+                        FragmentSection::new(None, vec![]),
+                    ],
+                    Rc::new(FragmentDefinition::default()),
+                )),
+                Element::Deactivate(Id::new("a")),
+            ],
+        );
+        let root = diagram(
+            DiagramKind::Component,
+            vec![
+                node(
+                    "service",
+                    Block::Scope(Scope::new(vec![node("service::db", Block::None)])),
+                ),
+                node("flow", Block::Diagram(embedded)),
+                Element::Highlight(Highlight::new(
+                    "hot".to_string(),
+                    vec![relation("service", "flow")],
+                    Rc::new(HighlightDefinition::default()),
+                )),
+                Element::Note(Note::new(
+                    vec![],
+                    NoteAlign::Over,
+                    "done".to_string(),
+                    Rc::new(NoteDefinition::default()),
+                )),
+            ],
+        );
+
+        let mut recorder = Recorder::default();
+        root.accept(&mut recorder);
+
+        assert_eq!(
+            recorder.events,
+            [
+                "diagram component",
+                "component service",
+                "component service::db",
+                "component flow",
+                "diagram sequence",
+                "component a",
+                "activate a",
+                "section ok",
+                "relation a a",
+                "section ",
+                "deactivate a",
+                "highlight hot",
+                "relation service flow",
+                "note done",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_overriding_without_walking_skips_nested_content() {
+        struct TopLevel(Vec<Id>);
+
+        impl Visitor for TopLevel {
+            fn visit_component(&mut self, node: &Node) {
+                self.0.push(node.id());
+            }
+        }
+
+        let root = diagram(
+            DiagramKind::Component,
+            vec![
+                node(
+                    "service",
+                    Block::Scope(Scope::new(vec![node("service::db", Block::None)])),
+                ),
+                node("client", Block::None),
+            ],
+        );
+
+        let mut top_level = TopLevel(Vec::new());
+        root.accept(&mut top_level);
+        assert_eq!(top_level.0, [Id::new("service"), Id::new("client")]);
+    }
+}
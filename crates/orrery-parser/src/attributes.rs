@@ -0,0 +1,604 @@
+//! Catalog of the attributes understood by the Orrery language.
+//!
+//! Elaboration accepts a different set of attributes for each kind of
+//! element. This module lists all of them in one place, together with the
+//! element kinds that accept them, the kind of value they take, and a short
+//! description, so that documentation and editor tooling can be generated
+//! from the same source as the parser.
+//!
+//! # Examples
+//!
+//! ```
+//! use orrery_parser::attributes::{self, AttributeTarget};
+//!
+//! let shape_attributes: Vec<&str> = attributes::supported_attributes()
+//!     .iter()
+//!     .filter(|attr| attr.accepts(AttributeTarget::Shape))
+//!     .map(|attr| attr.name())
+//!     .collect();
+//! assert!(shape_attributes.contains(&"fill_color"));
+//! ```
+
+use std::fmt;
+
+use AttributeTarget as T;
+use AttributeValueKind as V;
+
+/// The kind of element an attribute can be set on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AttributeTarget {
+    /// A `diagram` header or a `Diagram` type.
+    Diagram,
+    /// A shape type, such as `Rectangle`, and the components using it.
+    Shape,
+    /// A single component; these attributes cannot be part of a type definition.
+    Component,
+    /// A relation or an `Arrow` type.
+    Arrow,
+    /// A `note` or a `Note` type.
+    Note,
+    /// A fragment block or a `Fragment` type.
+    Fragment,
+    /// A `highlight` block or a `Highlight` type.
+    Highlight,
+    /// A `group` block or a `Group` type.
+    Group,
+    /// An activation or an `Activate` type.
+    Activate,
+    /// A `Lifeline` type.
+    Lifeline,
+    /// A `Stroke` type, or the `[...]` value of a stroke attribute.
+    Stroke,
+    /// A `Text` type, or the `[...]` value of a text attribute.
+    Text,
+    /// A `type` definition; these attributes are not inherited by the types
+    /// and elements using it.
+    Type,
+}
+
+impl fmt::Display for AttributeTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Diagram => "diagram",
+            Self::Shape => "shape",
+            Self::Component => "component",
+            Self::Arrow => "arrow",
+            Self::Note => "note",
+            Self::Fragment => "fragment",
+            Self::Highlight => "highlight",
+            Self::Group => "group",
+            Self::Activate => "activate",
+            Self::Lifeline => "lifeline",
+            Self::Stroke => "stroke",
+            Self::Text => "text",
+            Self::Type => "type",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// The kind of value an attribute takes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AttributeValueKind {
+    /// A string literal from a fixed set of keywords.
+    Keyword,
+    /// A free-form string literal.
+    String,
+    /// A color string: a CSS color or a palette name.
+    Color,
+    /// A non-negative number.
+    Float,
+    /// A non-negative whole number.
+    Integer,
+    /// A type reference and/or a nested `[...]` attribute list.
+    TypeSpec,
+    /// A `[...]` list of element identifiers.
+    Identifiers,
+}
+
+impl fmt::Display for AttributeValueKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Keyword => "keyword",
+            Self::String => "string",
+            Self::Color => "color",
+            Self::Float => "float",
+            Self::Integer => "integer",
+            Self::TypeSpec => "type",
+            Self::Identifiers => "identifiers",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Description of one supported attribute.
+///
+/// An attribute name can appear in several entries when it means different
+/// things on different element kinds, e.g. `style` on arrows and strokes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AttributeInfo {
+    name: &'static str,
+    targets: &'static [AttributeTarget],
+    value_kind: AttributeValueKind,
+    description: &'static str,
+}
+
+impl AttributeInfo {
+    const fn new(
+        name: &'static str,
+        targets: &'static [AttributeTarget],
+        value_kind: AttributeValueKind,
+        description: &'static str,
+    ) -> Self {
+        Self {
+            name,
+            targets,
+            value_kind,
+            description,
+        }
+    }
+
+    /// Returns the attribute name as written in source.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the element kinds that accept this attribute.
+    pub fn targets(&self) -> &'static [AttributeTarget] {
+        self.targets
+    }
+
+    /// Returns `true` if `target` accepts this attribute.
+    pub fn accepts(&self, target: AttributeTarget) -> bool {
+        self.targets.contains(&target)
+    }
+
+    /// Returns the kind of value the attribute takes.
+    pub fn value_kind(&self) -> AttributeValueKind {
+        self.value_kind
+    }
+
+    /// Returns a one-line description of the attribute.
+    pub fn description(&self) -> &'static str {
+        self.description
+    }
+}
+
+static ATTRIBUTES: &[AttributeInfo] = &[
+    AttributeInfo::new(
+        "layout_engine",
+        &[T::Diagram],
+        V::Keyword,
+        "Layout engine that arranges the diagram: `basic`, `sugiyama`, `grid`, or `graphviz`.",
+    ),
+    AttributeInfo::new(
+        "canvas_color",
+        &[T::Diagram],
+        V::Color,
+        "Background color of the diagram canvas.",
+    ),
+    AttributeInfo::new(
+        "canvas_padding",
+        &[T::Diagram],
+        V::TypeSpec,
+        "Space around the diagram content: one number, or inline `[top, right, bottom, left]` sides.",
+    ),
+    AttributeInfo::new(
+        "width",
+        &[T::Diagram],
+        V::Float,
+        "Width of the rendered SVG; the content scales into it.",
+    ),
+    AttributeInfo::new(
+        "height",
+        &[T::Diagram],
+        V::Float,
+        "Height of the rendered SVG; the content scales into it.",
+    ),
+    AttributeInfo::new(
+        "fit",
+        &[T::Diagram],
+        V::Keyword,
+        "How the content fits an explicit `width` and `height`: `contain` or `none`.",
+    ),
+    AttributeInfo::new(
+        "title",
+        &[T::Diagram],
+        V::String,
+        "Title drawn centered above the diagram and used as the SVG `<title>`.",
+    ),
+    AttributeInfo::new(
+        "caption",
+        &[T::Diagram],
+        V::String,
+        "Caption drawn centered below the diagram and used as the SVG `<desc>`.",
+    ),
+    AttributeInfo::new(
+        "lifeline",
+        &[T::Diagram],
+        V::TypeSpec,
+        "Lifeline style of the participants of a sequence diagram.",
+    ),
+    AttributeInfo::new(
+        "edge_routing",
+        &[T::Diagram],
+        V::Keyword,
+        "How relations of a component diagram are routed: `straight` or `orthogonal`.",
+    ),
+    AttributeInfo::new(
+        "columns",
+        &[T::Diagram],
+        V::Integer,
+        "Number of columns of the `grid` layout engine; defaults to a roughly square grid.",
+    ),
+    AttributeInfo::new(
+        "density",
+        &[T::Diagram],
+        V::Keyword,
+        "Spacing preset of the rendered diagram: `compact`, `comfortable` or `spacious`.",
+    ),
+    AttributeInfo::new(
+        "rank_spacing",
+        &[T::Diagram],
+        V::Float,
+        "Distance between ranks of the `sugiyama` engine.",
+    ),
+    AttributeInfo::new(
+        "intra_rank_spacing",
+        &[T::Diagram],
+        V::Float,
+        "Distance between components within a rank of the `sugiyama` engine.",
+    ),
+    AttributeInfo::new(
+        "autocolor",
+        &[T::Diagram],
+        V::Keyword,
+        "`true` to give top-level components without a fill a distinct generated color.",
+    ),
+    AttributeInfo::new(
+        "autonumber",
+        &[T::Diagram],
+        V::String,
+        "`true`, or a start number such as `10`, to prefix sequence diagram messages with an incrementing number.",
+    ),
+    AttributeInfo::new(
+        "implicit_nodes",
+        &[T::Diagram],
+        V::Keyword,
+        "`true` to draw undeclared relation endpoints as default rectangles, with a warning.",
+    ),
+    AttributeInfo::new(
+        "fill_color",
+        &[T::Shape, T::Highlight, T::Activate],
+        V::Color,
+        "Color filling the element's background.",
+    ),
+    AttributeInfo::new(
+        "fill_opacity",
+        &[T::Shape],
+        V::Float,
+        "Opacity of a shape's fill from 0 to 1, multiplied with the fill color's alpha.",
+    ),
+    AttributeInfo::new(
+        "stroke_opacity",
+        &[T::Shape],
+        V::Float,
+        "Opacity of a shape's outline from 0 to 1, multiplied with the stroke color's alpha.",
+    ),
+    AttributeInfo::new(
+        "stroke",
+        &[
+            T::Shape,
+            T::Arrow,
+            T::Note,
+            T::Group,
+            T::Activate,
+            T::Lifeline,
+        ],
+        V::TypeSpec,
+        "Style of the element's outline or line.",
+    ),
+    AttributeInfo::new(
+        "rounded",
+        &[T::Shape],
+        V::Integer,
+        "Corner radius of a rectangle.",
+    ),
+    AttributeInfo::new(
+        "corner_radius",
+        &[T::Shape],
+        V::Float,
+        "Corner radius of a rectangle, clamped to half of its smaller side.",
+    ),
+    AttributeInfo::new(
+        "min_width",
+        &[T::Shape],
+        V::Float,
+        "Minimum width of a rectangle, oval, or component, however short its label.",
+    ),
+    AttributeInfo::new(
+        "min_height",
+        &[T::Shape],
+        V::Float,
+        "Minimum height of a rectangle, oval, or component, however short its label.",
+    ),
+    AttributeInfo::new(
+        "icon",
+        &[T::Shape],
+        V::TypeSpec,
+        "Image file path or `data:` URI, or inline `[source, align, size]` attributes, drawn inside a rectangle.",
+    ),
+    AttributeInfo::new(
+        "shadow",
+        &[T::Shape],
+        V::TypeSpec,
+        "`true`, or inline `[blur, dx, dy, color]` attributes, to draw a drop shadow under the shape.",
+    ),
+    AttributeInfo::new(
+        "tooltip",
+        &[T::Shape, T::Arrow],
+        V::String,
+        "Hover tooltip of a component or relation, shown apart from its visible label.",
+    ),
+    AttributeInfo::new(
+        "href",
+        &[T::Shape, T::Arrow],
+        V::String,
+        "URL a component or relation links to, making it clickable in the SVG.",
+    ),
+    AttributeInfo::new(
+        "target",
+        &[T::Shape, T::Arrow],
+        V::String,
+        "Browsing context an `href` opens in, e.g. `_blank`.",
+    ),
+    AttributeInfo::new(
+        "content_align",
+        &[T::Shape],
+        V::Keyword,
+        "Placement of nested content inside a container, e.g. `center` or `top-left`.",
+    ),
+    AttributeInfo::new(
+        "text",
+        &[T::Shape, T::Arrow, T::Note, T::Highlight, T::Group],
+        V::TypeSpec,
+        "Style of the element's label.",
+    ),
+    AttributeInfo::new(
+        "x",
+        &[T::Component],
+        V::Float,
+        "Pinned horizontal center of a component in a component diagram.",
+    ),
+    AttributeInfo::new(
+        "y",
+        &[T::Component],
+        V::Float,
+        "Pinned vertical center of a component in a component diagram.",
+    ),
+    AttributeInfo::new(
+        "class",
+        &[T::Component],
+        V::String,
+        "Name of a `style` block whose attributes apply beneath the component's own.",
+    ),
+    AttributeInfo::new(
+        "style",
+        &[T::Arrow],
+        V::Keyword,
+        "Path routing of an arrow: `straight`, `curved`, or `orthogonal`.",
+    ),
+    AttributeInfo::new(
+        "segments",
+        &[T::Arrow],
+        V::String,
+        "Comma-separated `style:fraction` list splitting an arrow into differently stroked parts.",
+    ),
+    AttributeInfo::new(
+        "arrowhead",
+        &[T::Arrow],
+        V::Keyword,
+        "Arrowhead at the ends marked by the relation's direction: `triangle`, `open`, `diamond`, `circle`, or `none`.",
+    ),
+    AttributeInfo::new(
+        "arrowhead_start",
+        &[T::Arrow],
+        V::Keyword,
+        "Arrowhead at the source end of a relation, regardless of its direction.",
+    ),
+    AttributeInfo::new(
+        "arrowhead_end",
+        &[T::Arrow],
+        V::Keyword,
+        "Arrowhead at the destination end of a relation, regardless of its direction.",
+    ),
+    AttributeInfo::new(
+        "label_pos",
+        &[T::Arrow],
+        V::Keyword,
+        "Where a relation's label sits along its path: `start`, `middle`, or `end`.",
+    ),
+    AttributeInfo::new(
+        "label_offset",
+        &[T::Arrow],
+        V::Float,
+        "Distance a relation's label is moved away from its path, to the left of the direction of travel.",
+    ),
+    AttributeInfo::new(
+        "curvature",
+        &[T::Arrow],
+        V::Float,
+        "How far parallel relations bow out, from `0` (straight) to `1` (strongly curved).",
+    ),
+    AttributeInfo::new(
+        "min_length",
+        &[T::Arrow],
+        V::Integer,
+        "Minimum number of layout ranks between a relation's endpoints.",
+    ),
+    AttributeInfo::new(
+        "border_stroke",
+        &[T::Fragment],
+        V::TypeSpec,
+        "Style of a fragment's border.",
+    ),
+    AttributeInfo::new(
+        "separator_stroke",
+        &[T::Fragment],
+        V::TypeSpec,
+        "Style of the lines separating fragment sections.",
+    ),
+    AttributeInfo::new(
+        "background_color",
+        &[T::Diagram, T::Fragment, T::Note, T::Text],
+        V::Color,
+        "Background color behind the element or its text; \"none\" removes a text background.",
+    ),
+    AttributeInfo::new(
+        "operation_label_text",
+        &[T::Fragment],
+        V::TypeSpec,
+        "Style of a fragment's operation label, e.g. `alt`.",
+    ),
+    AttributeInfo::new(
+        "section_title_text",
+        &[T::Fragment],
+        V::TypeSpec,
+        "Style of the fragment section titles.",
+    ),
+    AttributeInfo::new(
+        "on",
+        &[T::Note],
+        V::Identifiers,
+        "Elements a note is attached to.",
+    ),
+    AttributeInfo::new(
+        "align",
+        &[T::Note],
+        V::Keyword,
+        "Placement of a note: `over`, `left`, `right`, `top`, or `bottom`.",
+    ),
+    AttributeInfo::new(
+        "width",
+        &[T::Activate],
+        V::Float,
+        "Width of an activation box.",
+    ),
+    AttributeInfo::new(
+        "nesting_offset",
+        &[T::Activate],
+        V::Float,
+        "Horizontal shift of each nested activation box.",
+    ),
+    AttributeInfo::new(
+        "color",
+        &[T::Stroke, T::Text],
+        V::Color,
+        "Color of the line or the text.",
+    ),
+    AttributeInfo::new("width", &[T::Stroke], V::Float, "Thickness of the line."),
+    AttributeInfo::new(
+        "style",
+        &[T::Stroke],
+        V::Keyword,
+        "Dash pattern of the line, e.g. `solid`, `dashed`, or `dotted`.",
+    ),
+    AttributeInfo::new(
+        "dash_pattern",
+        &[T::Stroke],
+        V::String,
+        "Raw dash and gap lengths of a stroke, e.g. `\"4 2\"`; overrides `style`.",
+    ),
+    AttributeInfo::new(
+        "cap",
+        &[T::Stroke],
+        V::Keyword,
+        "Shape of line ends: `butt`, `round`, or `square`.",
+    ),
+    AttributeInfo::new(
+        "join",
+        &[T::Stroke],
+        V::Keyword,
+        "Shape of line corners: `miter`, `round`, or `bevel`.",
+    ),
+    AttributeInfo::new("font_size", &[T::Text], V::Integer, "Font size in pixels."),
+    AttributeInfo::new("font_family", &[T::Text], V::String, "Font family name."),
+    AttributeInfo::new(
+        "font_style",
+        &[T::Text],
+        V::Keyword,
+        "Slant of the text: `normal` or `italic`.",
+    ),
+    AttributeInfo::new(
+        "tab_fill_color",
+        &[T::Group],
+        V::Color,
+        "Color filling the title tab of a group.",
+    ),
+    AttributeInfo::new(
+        "padding",
+        &[T::Group],
+        V::TypeSpec,
+        "Space between a group's boundary and its members: one number, or inline `[top, right, bottom, left]` sides.",
+    ),
+    AttributeInfo::new(
+        "padding",
+        &[T::Text],
+        V::Float,
+        "Space between the text and its background edge.",
+    ),
+    AttributeInfo::new(
+        "allow_unused",
+        &[T::Type],
+        V::Keyword,
+        "`true` to not warn when nothing uses the type, e.g. for types meant to be imported.",
+    ),
+];
+
+/// Returns every attribute supported by the language.
+///
+/// Entries are grouped by the element kinds that accept them, starting with
+/// diagram attributes.
+pub fn supported_attributes() -> &'static [AttributeInfo] {
+    ATTRIBUTES
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn targets_of(name: &str) -> Vec<AttributeTarget> {
+        supported_attributes()
+            .iter()
+            .filter(|attr| attr.name() == name)
+            .flat_map(|attr| attr.targets().iter().copied())
+            .collect()
+    }
+
+    #[test]
+    fn test_supported_attributes_targets() {
+        assert_eq!(
+            targets_of("fill_color"),
+            [T::Shape, T::Highlight, T::Activate]
+        );
+        assert_eq!(targets_of("style"), [T::Arrow, T::Stroke]);
+        assert_eq!(targets_of("align"), [T::Note]);
+        assert_eq!(targets_of("x"), [T::Component]);
+        assert!(targets_of("label_position").is_empty());
+    }
+
+    #[test]
+    fn test_supported_attributes_are_unique_per_target() {
+        let mut seen = std::collections::HashSet::new();
+        for attr in supported_attributes() {
+            assert!(!attr.description().is_empty());
+            for target in attr.targets() {
+                assert!(
+                    seen.insert((attr.name(), *target)),
+                    "`{}` is listed twice for {target}",
+                    attr.name()
+                );
+            }
+        }
+    }
+}
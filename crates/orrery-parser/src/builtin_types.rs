@@ -10,18 +10,22 @@
 //! - **Shapes**: Rectangle, Oval, Component, Boundary, Actor, Entity, Control, Interface
 //! - **Relations**: Arrow
 //! - **Fragments**: Fragment, FragmentAlt, FragmentOpt, FragmentLoop, FragmentPar
-//! - **Annotations**: Note
+//! - **Annotations**: Note, Highlight
+//! - **Groups**: Group
 //! - **Activations**: Activate
 
 use std::rc::Rc;
 
 use orrery_core::{
+    color::Theme,
     draw::{
         ActivationBoxDefinition, ActorDefinition, ArrowDefinition, BoundaryDefinition,
         ComponentDefinition, ControlDefinition, DiagramDefinition, EntityDefinition,
-        FragmentDefinition, InterfaceDefinition, LifelineDefinition, NoteDefinition,
-        OvalDefinition, RectangleDefinition, StrokeDefinition, TextDefinition,
+        FragmentDefinition, GroupDefinition, HighlightDefinition, InterfaceDefinition,
+        LifelineDefinition, NoteDefinition, OvalDefinition, RectangleDefinition, ShapeDefinition,
+        StrokeDefinition, TextDefinition,
     },
+    geometry::Size,
     identifier::Id,
 };
 
@@ -77,6 +81,12 @@ pub const FRAGMENT_PAR: &str = "FragmentPar";
 /// Built-in base type for notes
 pub const NOTE: &str = "Note";
 
+/// Built-in base type for highlight bands
+pub const HIGHLIGHT: &str = "Highlight";
+
+/// Built-in base type for group boundaries
+pub const GROUP: &str = "Group";
+
 /// Built-in base type for activations
 pub const ACTIVATE: &str = "Activate";
 
@@ -94,17 +104,18 @@ pub const TEXT: &str = "Text";
 
 /// Builds a slot that references another built-in by its constant `type_name`
 /// (e.g. [`STROKE`]), re-applying any `inline` overrides so the referencing
-/// built-in's own non-default styling survives the wiring.
+/// built-in's own non-default styling survives the wiring. Overrides that are
+/// `None` are left out.
 fn type_ref(
     name: &'static str,
     type_name: &str,
-    inline: Vec<Attribute<'static>>,
+    inline: Vec<Option<Attribute<'static>>>,
 ) -> Attribute<'static> {
     Attribute {
         name: Spanned::new(name, Span::empty()),
         value: AttributeValue::TypeSpec(TypeSpec {
             type_name: Some(Spanned::new(Id::new(type_name), Span::empty())),
-            attributes: inline,
+            attributes: inline.into_iter().flatten().collect(),
         }),
     }
 }
@@ -113,35 +124,51 @@ fn type_ref(
 ///
 /// This lets `builtin_types!` stay agnostic about the value's type: `&str`
 /// values build a string attribute (`name="value"`) and floating-point values
-/// build a float attribute (`name=value`), so the macro can emit a single
-/// uniform call per override.
+/// build a float attribute (`name=value`), and `Option` values build nothing
+/// when `None`, so the macro can emit a single uniform call per override.
 trait InlineValue {
-    /// Builds the override attribute named `name` from `self`.
-    fn into_attribute(self, name: &'static str) -> Attribute<'static>;
+    /// Builds the override attribute named `name` from `self`, if any.
+    fn into_attribute(self, name: &'static str) -> Option<Attribute<'static>>;
 }
 
 impl InlineValue for &str {
     /// Builds an inline string attribute (`name="value"`).
-    fn into_attribute(self, name: &'static str) -> Attribute<'static> {
-        Attribute {
+    fn into_attribute(self, name: &'static str) -> Option<Attribute<'static>> {
+        Some(Attribute {
             name: Spanned::new(name, Span::empty()),
             value: AttributeValue::String(Spanned::new(self.to_string(), Span::empty())),
-        }
+        })
+    }
+}
+
+impl InlineValue for String {
+    /// Builds an inline string attribute (`name="value"`).
+    fn into_attribute(self, name: &'static str) -> Option<Attribute<'static>> {
+        self.as_str().into_attribute(name)
     }
 }
 
 impl InlineValue for f64 {
     /// Builds an inline float attribute (`name=value`).
-    fn into_attribute(self, name: &'static str) -> Attribute<'static> {
-        Attribute {
+    fn into_attribute(self, name: &'static str) -> Option<Attribute<'static>> {
+        Some(Attribute {
             name: Spanned::new(name, Span::empty()),
             value: AttributeValue::Float(Spanned::new(self as f32, Span::empty())),
-        }
+        })
+    }
+}
+
+impl<T: InlineValue> InlineValue for Option<T> {
+    /// Builds the attribute of the value, or nothing when `None`.
+    fn into_attribute(self, name: &'static str) -> Option<Attribute<'static>> {
+        self.and_then(|value| value.into_attribute(name))
     }
 }
 
 /// Declares the full table of built-in types.
 ///
+/// The table opens with `|theme|`, naming the [`Theme`] parameter that the
+/// generated functions take; override values and definitions may use it.
 /// Each entry is `NAME => { parser: { .. }, elaborate: .. }`.
 ///
 /// The `parser` block lists references declaratively:
@@ -181,6 +208,7 @@ macro_rules! builtin_types {
 
     // Public: the built-in type table.
     (
+        |$theme:ident|
         $(
             $name:expr => {
                 parser: {
@@ -206,7 +234,7 @@ macro_rules! builtin_types {
         ///
         /// Inter-built-in references resolve by constant name, so a referenced
         /// target (e.g. `Stroke`) need not precede the type that uses it.
-        pub fn parser_type_definitions() -> Vec<ParserTypeDefinition<'static>> {
+        pub fn parser_type_definitions($theme: &Theme) -> Vec<ParserTypeDefinition<'static>> {
             vec![
                 $(
                     {
@@ -234,7 +262,7 @@ macro_rules! builtin_types {
         }
 
         /// The elaborated default definition for each built-in type.
-        pub fn elaborate_type_definitions() -> Vec<ElaborateTypeDefinition> {
+        pub fn elaborate_type_definitions($theme: &Theme) -> Vec<ElaborateTypeDefinition> {
             vec![
                 $(
                     builtin_types!(@elaborate $constructor, $definition, Id::new($name))
@@ -244,14 +272,66 @@ macro_rules! builtin_types {
     };
 }
 
+/// The default stroke, drawn in the theme's stroke color.
+fn themed_stroke(theme: &Theme) -> StrokeDefinition {
+    let mut stroke = StrokeDefinition::default();
+    stroke.set_color(theme.stroke());
+    stroke
+}
+
+/// The default text, drawn in the theme's text color and font.
+fn themed_text(theme: &Theme) -> TextDefinition {
+    TextDefinition::from_theme(theme)
+}
+
+/// Fills a built-in icon shape with the theme's fill color.
+///
+/// Only shapes that are filled by default use this; `Rectangle`, `Oval` and
+/// `Component` stay transparent.
+fn themed_shape<D: ShapeDefinition>(mut shape: D, theme: &Theme) -> D {
+    shape
+        .set_fill_color(Some(theme.fill()))
+        .expect("built-in shapes support fill colors");
+    shape
+}
+
+/// Applies the theme's minimum width and height to a boxed shape.
+///
+/// Unset dimensions are left at zero, so the shape is sized by its content.
+fn sized_shape<D: ShapeDefinition>(mut shape: D, theme: &Theme) -> D {
+    let min_size = Size::new(
+        theme.min_width().unwrap_or_default(),
+        theme.min_height().unwrap_or_default(),
+    );
+    shape
+        .set_min_size(min_size)
+        .expect("boxed shapes support a minimum size");
+    shape
+}
+
+/// The default diagram, drawn on the theme's background, with a title one
+/// and a half times the size of the theme's text and a caption the same size.
+fn themed_diagram(theme: &Theme) -> DiagramDefinition {
+    let mut diagram = DiagramDefinition::new();
+    diagram.set_canvas_color(theme.background());
+
+    let caption = themed_text(theme);
+    let mut title = caption.clone();
+    title.set_font_size(caption.font_size().saturating_mul(3) / 2);
+    diagram.set_title_text(Rc::new(title));
+    diagram.set_caption_text(Rc::new(caption));
+    diagram
+}
+
 builtin_types! {
+    |theme|
     STROKE => {
         parser: {},
-        elaborate: ElaborateTypeDefinition::new_stroke => StrokeDefinition::default(),
+        elaborate: ElaborateTypeDefinition::new_stroke => themed_stroke(theme),
     },
     TEXT => {
         parser: {},
-        elaborate: ElaborateTypeDefinition::new_text => TextDefinition::default(),
+        elaborate: ElaborateTypeDefinition::new_text => themed_text(theme),
     },
     LIFELINE => {
         parser: {
@@ -262,119 +342,140 @@ builtin_types! {
     ARROW => {
         parser: {
             "stroke" => STROKE,
-            "text" => TEXT { "background_color" = "rgba(255, 255, 255, 0.85)" },
+            "text" => TEXT {
+                "background_color" = theme
+                    .label_background()
+                    .map_or_else(|| "none".to_string(), |color| color.to_string()),
+                "font_family" = theme.relation_font_family(),
+                "font_size" = theme.relation_font_size().map(f64::from),
+                "font_style" = theme.relation_font_style().map(|style| style.to_svg_value()),
+                "color" = theme.relation_text().map(|color| color.to_string())
+            },
         },
-        elaborate: ElaborateTypeDefinition::new_arrow => ArrowDefinition::default(),
+        elaborate: ElaborateTypeDefinition::new_arrow => ArrowDefinition::from_theme(theme),
     },
     NOTE => {
         parser: {
             "stroke" => STROKE,
             "text" => TEXT,
         },
-        elaborate: ElaborateTypeDefinition::new_note => NoteDefinition::new(),
+        elaborate: ElaborateTypeDefinition::new_note => NoteDefinition::from_theme(theme),
+    },
+    HIGHLIGHT => {
+        parser: {
+            "text" => TEXT,
+        },
+        elaborate: ElaborateTypeDefinition::new_highlight => HighlightDefinition::new(),
+    },
+    GROUP => {
+        parser: {
+            "stroke" => STROKE { "style" = "dashed" },
+            "text" => TEXT,
+        },
+        elaborate: ElaborateTypeDefinition::new_group => GroupDefinition::from_theme(theme),
     },
     ACTIVATE => {
         parser: {
             "stroke" => STROKE,
         },
-        elaborate: ElaborateTypeDefinition::new_activation_box => ActivationBoxDefinition::new(),
+        elaborate: ElaborateTypeDefinition::new_activation_box => ActivationBoxDefinition::from_theme(theme),
     },
     FRAGMENT_ALT => {
         parser: {
             "border_stroke" => STROKE,
             "separator_stroke" => STROKE { "style" = "dashed" },
         },
-        elaborate: ElaborateTypeDefinition::new_fragment => FragmentDefinition::new(),
+        elaborate: ElaborateTypeDefinition::new_fragment => FragmentDefinition::from_theme(theme),
     },
     FRAGMENT_OPT => {
         parser: {
             "border_stroke" => STROKE,
             "separator_stroke" => STROKE { "style" = "dashed" },
         },
-        elaborate: ElaborateTypeDefinition::new_fragment => FragmentDefinition::new(),
+        elaborate: ElaborateTypeDefinition::new_fragment => FragmentDefinition::from_theme(theme),
     },
     FRAGMENT_LOOP => {
         parser: {
             "border_stroke" => STROKE,
             "separator_stroke" => STROKE { "style" = "dashed" },
         },
-        elaborate: ElaborateTypeDefinition::new_fragment => FragmentDefinition::new(),
+        elaborate: ElaborateTypeDefinition::new_fragment => FragmentDefinition::from_theme(theme),
     },
     FRAGMENT_PAR => {
         parser: {
             "border_stroke" => STROKE,
             "separator_stroke" => STROKE { "style" = "dashed" },
         },
-        elaborate: ElaborateTypeDefinition::new_fragment => FragmentDefinition::new(),
+        elaborate: ElaborateTypeDefinition::new_fragment => FragmentDefinition::from_theme(theme),
     },
     FRAGMENT => {
         parser: {
             "border_stroke" => STROKE,
             "separator_stroke" => STROKE { "style" = "dashed" },
         },
-        elaborate: ElaborateTypeDefinition::new_fragment => FragmentDefinition::new(),
+        elaborate: ElaborateTypeDefinition::new_fragment => FragmentDefinition::from_theme(theme),
     },
     RECTANGLE => {
         parser: {
             "stroke" => STROKE { "width" = 2.0 },
             "text" => TEXT,
         },
-        elaborate: ElaborateTypeDefinition::new_shape => RectangleDefinition::new(),
+        elaborate: ElaborateTypeDefinition::new_shape => sized_shape(RectangleDefinition::new(), theme),
     },
     OVAL => {
         parser: {
             "stroke" => STROKE { "width" = 2.0 },
             "text" => TEXT,
         },
-        elaborate: ElaborateTypeDefinition::new_shape => OvalDefinition::new(),
+        elaborate: ElaborateTypeDefinition::new_shape => sized_shape(OvalDefinition::new(), theme),
     },
     COMPONENT => {
         parser: {
             "stroke" => STROKE { "width" = 2.0 },
             "text" => TEXT,
         },
-        elaborate: ElaborateTypeDefinition::new_shape => ComponentDefinition::new(),
+        elaborate: ElaborateTypeDefinition::new_shape => sized_shape(ComponentDefinition::new(), theme),
     },
     BOUNDARY => {
         parser: {
             "stroke" => STROKE { "width" = 2.0 },
             "text" => TEXT,
         },
-        elaborate: ElaborateTypeDefinition::new_shape => BoundaryDefinition::new(),
+        elaborate: ElaborateTypeDefinition::new_shape => themed_shape(BoundaryDefinition::new(), theme),
     },
     ACTOR => {
         parser: {
             "stroke" => STROKE { "width" = 2.0 },
             "text" => TEXT,
         },
-        elaborate: ElaborateTypeDefinition::new_shape => ActorDefinition::new(),
+        elaborate: ElaborateTypeDefinition::new_shape => themed_shape(ActorDefinition::new(), theme),
     },
     ENTITY => {
         parser: {
             "stroke" => STROKE { "width" = 2.0 },
             "text" => TEXT,
         },
-        elaborate: ElaborateTypeDefinition::new_shape => EntityDefinition::new(),
+        elaborate: ElaborateTypeDefinition::new_shape => themed_shape(EntityDefinition::new(), theme),
     },
     CONTROL => {
         parser: {
             "stroke" => STROKE { "width" = 2.0 },
             "text" => TEXT,
         },
-        elaborate: ElaborateTypeDefinition::new_shape => ControlDefinition::new(),
+        elaborate: ElaborateTypeDefinition::new_shape => themed_shape(ControlDefinition::new(), theme),
     },
     INTERFACE => {
         parser: {
             "stroke" => STROKE { "width" = 2.0 },
             "text" => TEXT,
         },
-        elaborate: ElaborateTypeDefinition::new_shape => InterfaceDefinition::new(),
+        elaborate: ElaborateTypeDefinition::new_shape => themed_shape(InterfaceDefinition::new(), theme),
     },
     DIAGRAM => {
         parser: {
             "lifeline" => LIFELINE,
         },
-        elaborate: ElaborateTypeDefinition::new_diagram => DiagramDefinition::new(),
+        elaborate: ElaborateTypeDefinition::new_diagram => themed_diagram(theme),
     },
 }
 
@@ -384,7 +485,7 @@ mod tests {
 
     #[test]
     fn test_parser_type_definitions_wires_attribute_references() {
-        let parser_types = parser_type_definitions();
+        let parser_types = parser_type_definitions(&Theme::default());
 
         // Same types, in the same order, as `ids`.
         let names: Vec<Id> = parser_types
@@ -555,7 +656,7 @@ mod tests {
 
     #[test]
     fn test_elaborate_type_definitions_match_kinds() {
-        let types = elaborate_type_definitions();
+        let types = elaborate_type_definitions(&Theme::default());
 
         // Same types, in the same order, as `ids`.
         let names: Vec<Id> = types.iter().map(|type_def| type_def.id()).collect();
@@ -574,6 +675,8 @@ mod tests {
         assert!(find(LIFELINE).lifeline_definition().is_ok());
         assert!(find(ARROW).arrow_definition().is_ok());
         assert!(find(NOTE).note_definition().is_ok());
+        assert!(find(HIGHLIGHT).highlight_definition().is_ok());
+        assert!(find(GROUP).group_definition().is_ok());
         assert!(find(ACTIVATE).activation_box_definition().is_ok());
         assert!(find(FRAGMENT).fragment_definition().is_ok());
         assert!(find(RECTANGLE).shape_definition().is_ok());
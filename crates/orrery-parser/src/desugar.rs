@@ -14,6 +14,13 @@
 //! using a path stack that tracks the current namespace context during traversal.
 //! For example, a reference to "child1" inside "parent" becomes "parent::child1".
 //! This enables the validation phase to perform comprehensive cross-reference checks.
+//!
+//! ## Variable Substitution
+//!
+//! `$name` references in attribute values are replaced by the value of the
+//! matching `let` definition. A file sees its own variables and, for inline
+//! embedded diagrams, those of the enclosing file. Unknown references are left
+//! in place for the validation phase to report.
 
 use std::{
     cell::RefCell,
@@ -24,13 +31,14 @@ use std::{
 
 use indexmap::IndexMap;
 
-use orrery_core::identifier::Id;
+use orrery_core::{color::Theme, identifier::Id};
 
 use crate::{
     builtin_types,
     parser_types::{
         Attribute, AttributeValue, ComponentContent, DiagramSource, Element, FileAst, FileHeader,
-        Fragment, FragmentSection, Import, Note, TypeDefinition, TypeSpec,
+        Fragment, FragmentSection, Group, Highlight, Import, Note, StyleDefinition, TypeDefinition,
+        TypeSpec, VariableDefinition,
     },
     span::Spanned,
 };
@@ -73,6 +81,27 @@ impl PathStack {
             id
         }
     }
+
+    /// Number of levels entered
+    fn depth(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Qualify a name with the path at `depth` levels (the root at 0)
+    fn qualify_at(&self, id: Id, depth: usize) -> Id {
+        match depth.checked_sub(1) {
+            Some(level) => self.stack[level].create_nested(id),
+            None => id,
+        }
+    }
+
+    /// Qualify a name with every path from the current one out to `depth`
+    /// levels, innermost first
+    fn qualify_outwards(&self, id: Id, depth: usize) -> impl Iterator<Item = Id> + '_ {
+        (depth..=self.depth())
+            .rev()
+            .map(move |level| self.qualify_at(id, level))
+    }
 }
 
 /// The main trait for folding/rewriting AST nodes.
@@ -87,6 +116,8 @@ trait Folder<'a> {
             import_decls: file_ast.import_decls,
             imports: self.fold_imports(file_ast.imports),
             type_definitions: self.fold_type_definitions(file_ast.type_definitions),
+            variable_definitions: self.fold_variable_definitions(file_ast.variable_definitions),
+            style_definitions: self.fold_style_definitions(file_ast.style_definitions),
             elements: self.fold_elements(file_ast.elements),
         }
     }
@@ -151,6 +182,10 @@ trait Folder<'a> {
         match value {
             AttributeValue::String(s) => AttributeValue::String(self.fold_string_value(s)),
             AttributeValue::Float(f) => AttributeValue::Float(self.fold_float_value(f)),
+            AttributeValue::Dimension { value, unit } => AttributeValue::Dimension {
+                value: self.fold_float_value(value),
+                unit,
+            },
             AttributeValue::TypeSpec(type_spec) => {
                 AttributeValue::TypeSpec(self.fold_type_spec(type_spec))
             }
@@ -158,9 +193,43 @@ trait Folder<'a> {
                 AttributeValue::Identifiers(self.fold_identifiers(ids))
             }
             AttributeValue::Empty => AttributeValue::Empty,
+            AttributeValue::Variable(name) => self.fold_variable_reference(name),
         }
     }
 
+    /// Fold a `$name` variable reference
+    fn fold_variable_reference(&mut self, name: Spanned<Id>) -> AttributeValue<'a> {
+        AttributeValue::Variable(name)
+    }
+
+    /// Fold a list of variable definitions
+    fn fold_variable_definitions(
+        &mut self,
+        variable_definitions: Vec<VariableDefinition<'a>>,
+    ) -> Vec<VariableDefinition<'a>> {
+        variable_definitions
+            .into_iter()
+            .map(|vd| VariableDefinition {
+                name: vd.name,
+                value: self.fold_attribute_value(vd.value),
+            })
+            .collect()
+    }
+
+    /// Fold a list of style definitions
+    fn fold_style_definitions(
+        &mut self,
+        style_definitions: Vec<StyleDefinition<'a>>,
+    ) -> Vec<StyleDefinition<'a>> {
+        style_definitions
+            .into_iter()
+            .map(|sd| StyleDefinition {
+                name: sd.name,
+                attributes: self.fold_attributes(sd.attributes),
+            })
+            .collect()
+    }
+
     /// Fold a string attribute value
     fn fold_string_value(&mut self, value: Spanned<String>) -> Spanned<String> {
         value
@@ -241,6 +310,16 @@ trait Folder<'a> {
         self.fold_type_spec(type_spec)
     }
 
+    /// Fold a highlight's TypeSpec
+    fn fold_highlight_type_spec(&mut self, type_spec: TypeSpec<'a>) -> TypeSpec<'a> {
+        self.fold_type_spec(type_spec)
+    }
+
+    /// Fold a group's TypeSpec
+    fn fold_group_type_spec(&mut self, type_spec: TypeSpec<'a>) -> TypeSpec<'a> {
+        self.fold_type_spec(type_spec)
+    }
+
     /// Fold an activate block's TypeSpec
     fn fold_activate_type_spec(&mut self, type_spec: TypeSpec<'a>) -> TypeSpec<'a> {
         self.fold_type_spec(type_spec)
@@ -262,14 +341,16 @@ trait Folder<'a> {
                 display_name,
                 type_spec,
                 content,
-            } => self.fold_component(name, display_name, type_spec, content),
+                comments,
+            } => self.fold_component(name, display_name, type_spec, content, comments),
             Element::Relation {
                 source,
                 target,
                 relation_type,
                 type_spec,
                 label,
-            } => self.fold_relation(source, target, relation_type, type_spec, label),
+                comments,
+            } => self.fold_relation(source, target, relation_type, type_spec, label, comments),
             Element::ActivateBlock {
                 component,
                 elements,
@@ -287,6 +368,8 @@ trait Folder<'a> {
             },
             Element::Note(note) => Element::Note(self.fold_note(note)),
             Element::Fragment(fragment) => Element::Fragment(self.fold_fragment(fragment)),
+            Element::Highlight(highlight) => Element::Highlight(self.fold_highlight(highlight)),
+            Element::Group(group) => Element::Group(self.fold_group(group)),
             // Fragment sugar syntax - default behavior is to fold sections recursively
             Element::AltElseBlock {
                 keyword_span,
@@ -358,12 +441,14 @@ trait Folder<'a> {
         display_name: Option<Spanned<String>>,
         type_spec: TypeSpec<'a>,
         content: ComponentContent<'a>,
+        comments: Vec<Spanned<String>>,
     ) -> Element<'a> {
         Element::Component {
             name: self.fold_component_name(name),
             display_name: display_name.map(|dn| self.fold_display_name(dn)),
             type_spec: self.fold_component_type_spec(type_spec),
             content: self.fold_component_content(content),
+            comments,
         }
     }
 
@@ -406,6 +491,7 @@ trait Folder<'a> {
         relation_type: Spanned<&'a str>,
         type_spec: TypeSpec<'a>,
         label: Option<Spanned<String>>,
+        comments: Vec<Spanned<String>>,
     ) -> Element<'a> {
         Element::Relation {
             source: self.fold_relation_source(source),
@@ -413,6 +499,7 @@ trait Folder<'a> {
             relation_type: self.fold_relation_type(relation_type),
             type_spec: self.fold_relation_type_spec(type_spec),
             label: label.map(|l| self.fold_relation_label(l)),
+            comments,
         }
     }
 
@@ -471,6 +558,24 @@ trait Folder<'a> {
         }
     }
 
+    /// Fold a highlight block
+    fn fold_highlight(&mut self, highlight: Highlight<'a>) -> Highlight<'a> {
+        Highlight {
+            label: highlight.label,
+            type_spec: self.fold_highlight_type_spec(highlight.type_spec),
+            elements: self.fold_elements(highlight.elements),
+        }
+    }
+
+    /// Fold a group block
+    fn fold_group(&mut self, group: Group<'a>) -> Group<'a> {
+        Group {
+            label: group.label,
+            type_spec: self.fold_group_type_spec(group.type_spec),
+            elements: self.fold_elements(group.elements),
+        }
+    }
+
     /// Fold a note element
     fn fold_note(&mut self, note: Note<'a>) -> Note<'a> {
         Note {
@@ -509,16 +614,32 @@ trait Folder<'a> {
 /// ## Path Stack
 /// The `path_stack` field tracks the current position in the component hierarchy,
 /// allowing nested identifiers to be qualified with their full path from the root.
+///
+/// ## Name Resolution
+/// A component reference resolves to the closest declaration: the enclosing
+/// component's children first, then each outer scope up to the diagram root.
+/// A `::`-qualified reference is looked up from the diagram root before that.
+/// References that resolve to nothing are qualified with the current path,
+/// leaving validation to report them.
 pub struct Desugar<'a> {
     /// Tracks the current position in the component hierarchy for identifier
     /// qualification.
     path_stack: PathStack,
+    /// Qualified ids of the components declared in the diagram being folded.
+    declared_components: HashSet<Id>,
+    /// Depth of the [`PathStack`] at the root of the diagram being folded,
+    /// non-zero for inline embedded diagrams.
+    root_depth: usize,
     /// Set of built-in type [`Id`]s (e.g., `Rectangle`, `Stroke`) that must not
     /// receive a namespace prefix during import qualification.
     builtin_types: HashSet<Id>,
     /// Lookup map from namespace [`Id`] → `Rc<RefCell<FileAst>>` for diagram imports.
     /// Built during `fold_file_ast` and used to resolve `DiagramSource::Ref`.
     embed_refs: HashMap<Id, Rc<RefCell<FileAst<'a>>>>,
+    /// Values of the variables in scope, substituted for `$name` references.
+    variables: HashMap<Id, AttributeValue<'a>>,
+    /// Theme the built-in prelude takes its default colors from.
+    theme: Theme,
 }
 
 impl<'a> Desugar<'a> {
@@ -526,15 +647,81 @@ impl<'a> Desugar<'a> {
     ///
     /// Initializes the built-in type set from [`builtin_types::ids`] and
     /// starts with an empty `embed_refs` map and root-level path stack.
-    fn new() -> Self {
+    fn new(theme: Theme) -> Self {
         let type_ids = builtin_types::ids().into_iter().collect();
         Self {
             path_stack: PathStack::new(),
+            declared_components: HashSet::new(),
+            root_depth: 0,
             builtin_types: type_ids,
             embed_refs: HashMap::new(),
+            variables: HashMap::new(),
+            theme,
         }
     }
 
+    /// Collects the qualified ids of the components declared in `elements`,
+    /// their nested scopes and blocks, qualified relative to `path_stack`.
+    ///
+    /// Embedded diagrams are skipped, since their components are only visible
+    /// inside them.
+    fn collect_declared_components(
+        elements: &[Element<'a>],
+        path_stack: &mut PathStack,
+        declared: &mut HashSet<Id>,
+    ) {
+        for element in elements {
+            match element {
+                Element::Component { name, content, .. } => {
+                    declared.insert(path_stack.qualify(*name.inner()));
+                    if let ComponentContent::Scope(nested) = content {
+                        path_stack.push(*name.inner());
+                        Self::collect_declared_components(nested, path_stack, declared);
+                        path_stack.pop();
+                    }
+                }
+                Element::ActivateBlock { elements, .. }
+                | Element::Highlight(Highlight { elements, .. })
+                | Element::Group(Group { elements, .. }) => {
+                    Self::collect_declared_components(elements, path_stack, declared);
+                }
+                Element::Fragment(Fragment { sections, .. })
+                | Element::AltElseBlock { sections, .. }
+                | Element::ParBlock { sections, .. } => {
+                    for section in sections {
+                        Self::collect_declared_components(&section.elements, path_stack, declared);
+                    }
+                }
+                Element::OptBlock { section, .. }
+                | Element::LoopBlock { section, .. }
+                | Element::BreakBlock { section, .. }
+                | Element::CriticalBlock { section, .. } => {
+                    Self::collect_declared_components(&section.elements, path_stack, declared);
+                }
+                Element::Relation { .. }
+                | Element::Activate { .. }
+                | Element::Deactivate { .. }
+                | Element::Note(_) => {}
+            }
+        }
+    }
+
+    /// Resolves a component reference to the closest declared component.
+    ///
+    /// A `::`-qualified `id` is tried from the diagram root first, then every
+    /// `id` is tried from the current scope outward. Falls back to qualifying
+    /// `id` with the current path when no declaration matches.
+    fn resolve_component(&self, id: Id) -> Id {
+        let absolute = id
+            .namespace()
+            .map(|_| self.path_stack.qualify_at(id, self.root_depth));
+        absolute
+            .into_iter()
+            .chain(self.path_stack.qualify_outwards(id, self.root_depth))
+            .find(|candidate| self.declared_components.contains(candidate))
+            .unwrap_or_else(|| self.path_stack.qualify(id))
+    }
+
     /// Qualifies type references in a [`TypeSpec`] with a namespace prefix.
     ///
     /// Rewrites `type_name` to `namespace::type_name` unless the type is a
@@ -565,9 +752,12 @@ impl<'a> Desugar<'a> {
         self.qualify_type_spec(&mut type_def.type_spec, namespace);
     }
 
-    /// Yields the built-in prelude as parser-level [`TypeDefinition`]s.
-    fn prelude_type_definitions() -> impl DoubleEndedIterator<Item = TypeDefinition<'a>> {
-        builtin_types::parser_type_definitions().into_iter()
+    /// Yields the built-in prelude, drawn with `theme`, as parser-level
+    /// [`TypeDefinition`]s.
+    fn prelude_type_definitions(
+        theme: &Theme,
+    ) -> impl DoubleEndedIterator<Item = TypeDefinition<'a>> + use<'a> {
+        builtin_types::parser_type_definitions(theme).into_iter()
     }
 
     /// Extracts [`TypeDefinition`]s from resolved imports, qualifying each with
@@ -617,7 +807,7 @@ impl<'a> Desugar<'a> {
         inject_prelude: bool,
     ) -> Vec<TypeDefinition<'a>> {
         let prelude = inject_prelude
-            .then(Self::prelude_type_definitions)
+            .then(|| Self::prelude_type_definitions(&self.theme))
             .into_iter()
             .flatten();
 
@@ -660,7 +850,11 @@ impl<'a> Folder<'a> for Desugar<'a> {
         // inline embeds processed during fold_elements) don't clobber them.
         let saved_embed_refs = mem::take(&mut self.embed_refs);
 
+        // Imported files only see their own variables, while inline embeds
+        // (folded with the elements below) also see this file's.
+        let saved_variables = mem::take(&mut self.variables);
         let imports = self.fold_imports(file_ast.imports);
+        self.variables.clone_from(&saved_variables);
 
         // Library imports export type definitions — extract and merge them into
         // the root scope. Diagram imports are consumed into the embed_refs
@@ -690,21 +884,64 @@ impl<'a> Folder<'a> for Desugar<'a> {
             inject_prelude,
         );
 
+        // Each definition sees the ones before it; a redefinition replaces the
+        // value for everything that follows.
+        let variable_definitions = file_ast
+            .variable_definitions
+            .into_iter()
+            .map(|variable_def| {
+                let value = self.fold_attribute_value(variable_def.value);
+                self.variables
+                    .insert(*variable_def.name.inner(), value.clone());
+                VariableDefinition {
+                    name: variable_def.name,
+                    value,
+                }
+            })
+            .collect();
+
+        // Component references resolve against this diagram's declarations
+        // only; an inline embed's root is the component embedding it.
+        let mut declared_components = HashSet::new();
+        Self::collect_declared_components(
+            &file_ast.elements,
+            &mut self.path_stack,
+            &mut declared_components,
+        );
+        let saved_declared_components =
+            mem::replace(&mut self.declared_components, declared_components);
+        let saved_root_depth = mem::replace(&mut self.root_depth, self.path_stack.depth());
+
         let header = self.fold_header(file_ast.header);
         let type_definitions = self.fold_type_definitions(type_defs);
+        let style_definitions = self.fold_style_definitions(file_ast.style_definitions);
         let elements = self.fold_elements(file_ast.elements);
 
         self.embed_refs = saved_embed_refs;
+        self.variables = saved_variables;
+        self.declared_components = saved_declared_components;
+        self.root_depth = saved_root_depth;
 
         FileAst {
             header,
             import_decls: file_ast.import_decls,
             imports: vec![],
             type_definitions,
+            variable_definitions,
+            style_definitions,
             elements,
         }
     }
 
+    /// Replaces a `$name` reference with the value of the variable in scope.
+    fn fold_variable_reference(&mut self, name: Spanned<Id>) -> AttributeValue<'a> {
+        match self.variables.get(name.inner()) {
+            Some(value) => value.clone().with_span(name.span()),
+            // Leave unresolved — validate will report E207
+            None => AttributeValue::Variable(name),
+        }
+    }
+
     /// Resolves a [`DiagramSource`] reference against the `embed_refs` lookup table.
     fn fold_diagram_source(&mut self, source: DiagramSource<'a>) -> DiagramSource<'a> {
         match source {
@@ -731,6 +968,7 @@ impl<'a> Folder<'a> for Desugar<'a> {
         display_name: Option<Spanned<String>>,
         type_spec: TypeSpec<'a>,
         content: ComponentContent<'a>,
+        comments: Vec<Spanned<String>>,
     ) -> Element<'a> {
         // Enter this component's namespace
         self.path_stack.push(*name.inner());
@@ -746,14 +984,23 @@ impl<'a> Folder<'a> for Desugar<'a> {
             display_name: display_name.map(|dn| self.fold_display_name(dn)),
             type_spec: self.fold_component_type_spec(type_spec),
             content,
+            comments,
         }
     }
 
-    /// Override fold_identifier to qualify identifier with current path
+    /// Override fold_component_name to qualify the declared name with current path
+    fn fold_component_name(&mut self, name: Spanned<Id>) -> Spanned<Id> {
+        let original_span = name.span();
+        let qualified = self.path_stack.qualify(*name.inner());
+        Spanned::new(qualified, original_span)
+    }
+
+    /// Override fold_identifier to resolve a component reference through the
+    /// enclosing scopes
     fn fold_identifier(&mut self, identifier: Spanned<Id>) -> Spanned<Id> {
         let original_span = identifier.span();
-        let qualified = self.path_stack.qualify(*identifier.inner());
-        Spanned::new(qualified, original_span)
+        let resolved = self.resolve_component(*identifier.inner());
+        Spanned::new(resolved, original_span)
     }
 
     /// Folds a list of elements, desugaring [`ActivateBlock`](Element::ActivateBlock) in-place.
@@ -819,6 +1066,28 @@ impl<'a> Folder<'a> for Desugar<'a> {
         self.fold_type_spec(type_spec)
     }
 
+    /// Override fold_highlight_type_spec to inject default "Highlight" type for sugar syntax
+    fn fold_highlight_type_spec(&mut self, mut type_spec: TypeSpec<'a>) -> TypeSpec<'a> {
+        if type_spec.type_name.is_none() {
+            type_spec.type_name = Some(Spanned::new(
+                Id::new(builtin_types::HIGHLIGHT),
+                type_spec.span(),
+            ));
+        }
+        self.fold_type_spec(type_spec)
+    }
+
+    /// Override fold_group_type_spec to inject default "Group" type for sugar syntax
+    fn fold_group_type_spec(&mut self, mut type_spec: TypeSpec<'a>) -> TypeSpec<'a> {
+        if type_spec.type_name.is_none() {
+            type_spec.type_name = Some(Spanned::new(
+                Id::new(builtin_types::GROUP),
+                type_spec.span(),
+            ));
+        }
+        self.fold_type_spec(type_spec)
+    }
+
     /// Override fold_activate_type_spec to inject default "Activate" type for sugar syntax
     fn fold_activate_type_spec(&mut self, mut type_spec: TypeSpec<'a>) -> TypeSpec<'a> {
         if type_spec.type_name.is_none() {
@@ -841,14 +1110,16 @@ impl<'a> Folder<'a> for Desugar<'a> {
                 display_name,
                 type_spec,
                 content,
-            } => self.fold_component(name, display_name, type_spec, content),
+                comments,
+            } => self.fold_component(name, display_name, type_spec, content, comments),
             Element::Relation {
                 source,
                 target,
                 relation_type,
                 type_spec,
                 label,
-            } => self.fold_relation(source, target, relation_type, type_spec, label),
+                comments,
+            } => self.fold_relation(source, target, relation_type, type_spec, label, comments),
             Element::ActivateBlock {
                 component,
                 elements,
@@ -866,6 +1137,8 @@ impl<'a> Folder<'a> for Desugar<'a> {
                 component: self.fold_activate_component(component),
             },
             Element::Note(note) => Element::Note(self.fold_note(note)),
+            Element::Highlight(highlight) => Element::Highlight(self.fold_highlight(highlight)),
+            Element::Group(group) => Element::Group(self.fold_group(group)),
 
             // ========================================================================
             // DESUGARING TRANSFORMATIONS - Sugar syntax → Base syntax
@@ -972,12 +1245,13 @@ impl<'a> Folder<'a> for Desugar<'a> {
 /// # Arguments
 ///
 /// * `ast` - The root [`FileAst`] of the parsed file.
+/// * `theme` - The [`Theme`] the injected built-in prelude is drawn with.
 ///
 /// # Returns
 ///
 /// A desugared [`FileAst`] tree.
-pub fn desugar<'a>(ast: FileAst<'a>) -> FileAst<'a> {
-    let mut folder = Desugar::new();
+pub fn desugar<'a>(ast: FileAst<'a>, theme: &Theme) -> FileAst<'a> {
+    let mut folder = Desugar::new(theme.clone());
     folder.fold_file_ast(ast)
 }
 
@@ -1010,6 +1284,8 @@ mod tests {
             type_definitions: type_defs,
             elements: vec![],
             imports: vec![],
+            variable_definitions: vec![],
+            style_definitions: vec![],
         }
     }
 
@@ -1027,6 +1303,8 @@ mod tests {
             type_definitions: type_defs,
             elements,
             imports: vec![],
+            variable_definitions: vec![],
+            style_definitions: vec![],
         }
     }
 
@@ -1041,6 +1319,8 @@ mod tests {
             type_definitions: vec![],
             elements,
             imports: vec![],
+            variable_definitions: vec![],
+            style_definitions: vec![],
         }
     }
 
@@ -1079,6 +1359,16 @@ mod tests {
             stack.qualify(Id::new("grandchild")),
             "parent::child::grandchild"
         );
+        assert_eq!(stack.depth(), 2);
+        assert_eq!(stack.qualify_at(Id::new("x"), 0), "x");
+        assert_eq!(
+            stack
+                .qualify_outwards(Id::new("x"), 0)
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>(),
+            ["parent::child::x", "parent::x", "x"]
+        );
+        assert_eq!(stack.qualify_outwards(Id::new("x"), 2).count(), 1);
 
         stack.pop();
         assert_eq!(stack.current().unwrap(), "parent");
@@ -1127,6 +1417,8 @@ mod tests {
             type_definitions: vec![],
             elements: vec![],
             imports: vec![],
+            variable_definitions: vec![],
+            style_definitions: vec![],
         };
 
         let mut folder = IdentityFolder;
@@ -1195,6 +1487,7 @@ mod tests {
                     }],
                 },
                 content: ComponentContent::None,
+                comments: Vec::new(),
             }],
         );
 
@@ -1208,6 +1501,7 @@ mod tests {
                 display_name,
                 type_spec,
                 content,
+                ..
             } => {
                 assert_eq!(*name.inner(), "frontend");
                 assert_eq!(display_name.as_ref().unwrap().inner(), "Frontend App");
@@ -1231,6 +1525,7 @@ mod tests {
                 relation_type: spanned("->"),
                 type_spec: TypeSpec::default(),
                 label: Some(spanned("Request".to_string())),
+                comments: Vec::new(),
             }],
         }]);
 
@@ -1269,10 +1564,11 @@ mod tests {
                 relation_type: spanned("->"),
                 type_spec: TypeSpec::default(),
                 label: Some(spanned("Request".to_string())),
+                comments: Vec::new(),
             }],
         }]);
 
-        let mut folder = Desugar::new();
+        let mut folder = Desugar::new(Theme::default());
         let result = folder.fold_file_ast(file_ast);
 
         assert_eq!(
@@ -1312,12 +1608,13 @@ mod tests {
                     relation_type: spanned("->"),
                     type_spec: TypeSpec::default(),
                     label: Some(spanned("Load".to_string())),
+                    comments: Vec::new(),
                 }],
             },
             type_spec: TypeSpec::default(),
         };
 
-        let mut folder = Desugar::new();
+        let mut folder = Desugar::new(Theme::default());
         let result = folder.fold_element(opt_block);
 
         match result {
@@ -1346,12 +1643,13 @@ mod tests {
                     relation_type: spanned("->"),
                     type_spec: TypeSpec::default(),
                     label: Some(spanned("Process".to_string())),
+                    comments: Vec::new(),
                 }],
             },
             type_spec: TypeSpec::default(),
         };
 
-        let mut folder = Desugar::new();
+        let mut folder = Desugar::new(Theme::default());
         let result = folder.fold_element(loop_block);
 
         match result {
@@ -1379,12 +1677,13 @@ mod tests {
                     relation_type: spanned("->"),
                     type_spec: TypeSpec::default(),
                     label: Some(spanned("Cancel".to_string())),
+                    comments: Vec::new(),
                 }],
             },
             type_spec: TypeSpec::default(),
         };
 
-        let mut folder = Desugar::new();
+        let mut folder = Desugar::new(Theme::default());
         let result = folder.fold_element(break_block);
 
         match result {
@@ -1412,12 +1711,13 @@ mod tests {
                     relation_type: spanned("->"),
                     type_spec: TypeSpec::default(),
                     label: Some(spanned("UPDATE".to_string())),
+                    comments: Vec::new(),
                 }],
             },
             type_spec: TypeSpec::default(),
         };
 
-        let mut folder = Desugar::new();
+        let mut folder = Desugar::new(Theme::default());
         let result = folder.fold_element(critical_block);
 
         match result {
@@ -1446,6 +1746,7 @@ mod tests {
                         relation_type: spanned("->"),
                         type_spec: TypeSpec::default(),
                         label: None,
+                        comments: Vec::new(),
                     }],
                 },
                 FragmentSection {
@@ -1456,6 +1757,7 @@ mod tests {
                         relation_type: spanned("->"),
                         type_spec: TypeSpec::default(),
                         label: None,
+                        comments: Vec::new(),
                     }],
                 },
                 FragmentSection {
@@ -1466,13 +1768,14 @@ mod tests {
                         relation_type: spanned("->"),
                         type_spec: TypeSpec::default(),
                         label: None,
+                        comments: Vec::new(),
                     }],
                 },
             ],
             type_spec: TypeSpec::default(),
         };
 
-        let mut folder = Desugar::new();
+        let mut folder = Desugar::new(Theme::default());
         let result = folder.fold_element(alt_else_block);
 
         match result {
@@ -1506,6 +1809,7 @@ mod tests {
                         relation_type: spanned("->"),
                         type_spec: TypeSpec::default(),
                         label: None,
+                        comments: Vec::new(),
                     }],
                 },
                 FragmentSection {
@@ -1516,13 +1820,14 @@ mod tests {
                         relation_type: spanned("->"),
                         type_spec: TypeSpec::default(),
                         label: None,
+                        comments: Vec::new(),
                     }],
                 },
             ],
             type_spec: TypeSpec::default(),
         };
 
-        let mut folder = Desugar::new();
+        let mut folder = Desugar::new(Theme::default());
         let result = folder.fold_element(par_block);
 
         match result {
@@ -1565,7 +1870,7 @@ mod tests {
             },
         };
 
-        let mut folder = Desugar::new();
+        let mut folder = Desugar::new(Theme::default());
         let result = folder.fold_element(opt_block);
 
         match result {
@@ -1611,7 +1916,7 @@ mod tests {
             type_spec: TypeSpec::default(),
         };
 
-        let mut folder = Desugar::new();
+        let mut folder = Desugar::new(Theme::default());
         let result = folder.fold_element(opt_block);
 
         match result {
@@ -1644,7 +1949,7 @@ mod tests {
             type_spec: TypeSpec::default(),
         };
 
-        let mut folder = Desugar::new();
+        let mut folder = Desugar::new(Theme::default());
         let result = folder.fold_element(opt_block);
 
         match result {
@@ -1668,7 +1973,7 @@ mod tests {
             type_spec: TypeSpec::default(),
         };
 
-        let mut folder = Desugar::new();
+        let mut folder = Desugar::new(Theme::default());
         let result = folder.fold_element(opt_block);
 
         match result {
@@ -1688,7 +1993,7 @@ mod tests {
             content: spanned("Simple note".to_string()),
         });
 
-        let mut folder = Desugar::new();
+        let mut folder = Desugar::new(Theme::default());
         let result = folder.fold_element(note);
 
         match result {
@@ -1719,7 +2024,7 @@ mod tests {
             content: spanned("Note with attributes".to_string()),
         });
 
-        let mut folder = Desugar::new();
+        let mut folder = Desugar::new(Theme::default());
         let result = folder.fold_element(note);
 
         match result {
@@ -1752,6 +2057,7 @@ mod tests {
                         attributes: vec![],
                     },
                     content: ComponentContent::None,
+                    comments: Vec::new(),
                 },
                 Element::Component {
                     name: spanned(Id::new("child2")),
@@ -1761,6 +2067,7 @@ mod tests {
                         attributes: vec![],
                     },
                     content: ComponentContent::None,
+                    comments: Vec::new(),
                 },
                 Element::Relation {
                     source: spanned(Id::new("child1")),
@@ -1768,11 +2075,13 @@ mod tests {
                     relation_type: spanned("->"),
                     type_spec: TypeSpec::default(),
                     label: None,
+                    comments: Vec::new(),
                 },
             ]),
+            comments: Vec::new(),
         };
 
-        let mut folder = Desugar::new();
+        let mut folder = Desugar::new(Theme::default());
         let result = folder.fold_element(parent_component);
 
         // Extract the relation from the result
@@ -1823,6 +2132,7 @@ mod tests {
                             attributes: vec![],
                         },
                         content: ComponentContent::None,
+                        comments: Vec::new(),
                     },
                     Element::Relation {
                         source: spanned(Id::new("level3")),
@@ -1830,12 +2140,15 @@ mod tests {
                         relation_type: spanned("->"),
                         type_spec: TypeSpec::default(),
                         label: None,
+                        comments: Vec::new(),
                     },
                 ]),
+                comments: Vec::new(),
             }]),
+            comments: Vec::new(),
         };
 
-        let mut folder = Desugar::new();
+        let mut folder = Desugar::new(Theme::default());
         let result = folder.fold_element(level1);
 
         // Navigate to the deeply nested relation
@@ -1886,15 +2199,17 @@ mod tests {
                         attributes: vec![],
                     },
                     content: ComponentContent::None,
+                    comments: Vec::new(),
                 },
                 Element::Activate {
                     component: spanned(Id::new("child")),
                     type_spec: TypeSpec::default(),
                 },
             ]),
+            comments: Vec::new(),
         };
 
-        let mut folder = Desugar::new();
+        let mut folder = Desugar::new(Theme::default());
         let result = folder.fold_element(parent_component);
 
         if let Element::Component { content, .. } = result {
@@ -1935,6 +2250,7 @@ mod tests {
                         attributes: vec![],
                     },
                     content: ComponentContent::None,
+                    comments: Vec::new(),
                 },
                 Element::Note(Note {
                     type_spec: TypeSpec {
@@ -1947,9 +2263,10 @@ mod tests {
                     content: spanned("Note about child".to_string()),
                 }),
             ]),
+            comments: Vec::new(),
         };
 
-        let mut folder = Desugar::new();
+        let mut folder = Desugar::new(Theme::default());
         let result = folder.fold_element(parent_component);
 
         if let Element::Component { content, .. } = result {
@@ -1994,9 +2311,10 @@ mod tests {
             relation_type: spanned("->"),
             type_spec: TypeSpec::default(),
             label: None,
+            comments: Vec::new(),
         };
 
-        let mut folder = Desugar::new();
+        let mut folder = Desugar::new(Theme::default());
         let result = folder.fold_element(relation);
 
         if let Element::Relation { source, target, .. } = result {
@@ -2024,10 +2342,12 @@ mod tests {
                 relation_type: spanned("->"),
                 type_spec: TypeSpec::default(),
                 label: None,
+                comments: Vec::new(),
             }]),
+            comments: Vec::new(),
         };
 
-        let mut folder = Desugar::new();
+        let mut folder = Desugar::new(Theme::default());
         let result = folder.fold_element(parent_component);
 
         if let Element::Component { content, .. } = result {
@@ -2062,9 +2382,10 @@ mod tests {
                 }],
             },
             label: None,
+            comments: Vec::new(),
         };
 
-        let mut folder = Desugar::new();
+        let mut folder = Desugar::new(Theme::default());
         let result = folder.fold_element(relation);
 
         match result {
@@ -2099,7 +2420,7 @@ mod tests {
             },
         };
 
-        let mut folder = Desugar::new();
+        let mut folder = Desugar::new(Theme::default());
         let result = folder.fold_element(opt_block);
 
         match result {
@@ -2135,7 +2456,7 @@ mod tests {
             },
         };
 
-        let mut folder = Desugar::new();
+        let mut folder = Desugar::new(Theme::default());
         let result = folder.fold_element(activate);
 
         match result {
@@ -2179,7 +2500,7 @@ mod tests {
             },
         };
 
-        let mut folder = Desugar::new();
+        let mut folder = Desugar::new(Theme::default());
         let result = folder.fold_element(loop_block);
 
         match result {
@@ -2209,9 +2530,10 @@ mod tests {
                 attributes: vec![],
             },
             label: None,
+            comments: Vec::new(),
         };
 
-        let mut folder = Desugar::new();
+        let mut folder = Desugar::new(Theme::default());
         let result = folder.fold_element(relation);
 
         match result {
@@ -2239,9 +2561,10 @@ mod tests {
                 }],
             },
             label: Some(spanned("query".to_string())),
+            comments: Vec::new(),
         };
 
-        let mut folder = Desugar::new();
+        let mut folder = Desugar::new(Theme::default());
         let result = folder.fold_element(relation);
 
         match result {
@@ -2267,9 +2590,10 @@ mod tests {
                 attributes: vec![],
             },
             label: None,
+            comments: Vec::new(),
         };
 
-        let mut folder = Desugar::new();
+        let mut folder = Desugar::new(Theme::default());
         let result = folder.fold_element(relation);
 
         match result {
@@ -2291,7 +2615,7 @@ mod tests {
             content: spanned("Important message".to_string()),
         });
 
-        let mut folder = Desugar::new();
+        let mut folder = Desugar::new(Theme::default());
         let result = folder.fold_element(note);
 
         match result {
@@ -2321,7 +2645,7 @@ mod tests {
             content: spanned("Side note".to_string()),
         });
 
-        let mut folder = Desugar::new();
+        let mut folder = Desugar::new(Theme::default());
         let result = folder.fold_element(note);
 
         match result {
@@ -2353,7 +2677,7 @@ mod tests {
             },
         };
 
-        let mut folder = Desugar::new();
+        let mut folder = Desugar::new(Theme::default());
         let result = folder.fold_element(opt_block);
 
         match result {
@@ -2381,7 +2705,7 @@ mod tests {
             elements: vec![],
         };
 
-        let mut folder = Desugar::new();
+        let mut folder = Desugar::new(Theme::default());
         let result_elements = folder.fold_elements(vec![activate_block]);
 
         // Should desugar to: Activate, Deactivate
@@ -2409,7 +2733,7 @@ mod tests {
             },
         };
 
-        let mut folder = Desugar::new();
+        let mut folder = Desugar::new(Theme::default());
         let result = folder.fold_element(activate);
 
         match result {
@@ -2452,7 +2776,7 @@ mod tests {
         let mut main_ast = make_diagram_ast(vec![], vec![]);
         main_ast.imports = vec![make_import(Some(Id::new("styles")), lib_ast)];
 
-        let mut folder = Desugar::new();
+        let mut folder = Desugar::new(Theme::default());
         let result = folder.fold_file_ast(main_ast);
 
         let names: Vec<String> = user_type_definitions(&result.type_definitions)
@@ -2475,7 +2799,7 @@ mod tests {
         let mut main_ast = make_diagram_ast(vec![], vec![]);
         main_ast.imports = vec![make_import(Some(Id::new("styles")), lib_ast)];
 
-        let mut folder = Desugar::new();
+        let mut folder = Desugar::new(Theme::default());
         let result = folder.fold_file_ast(main_ast);
 
         let qualified: Vec<String> = result
@@ -2525,7 +2849,7 @@ mod tests {
         let mut main_ast = make_diagram_ast(vec![], vec![]);
         main_ast.imports = vec![make_import(Some(Id::new("styles")), lib_ast)];
 
-        let mut folder = Desugar::new();
+        let mut folder = Desugar::new(Theme::default());
         let result = folder.fold_file_ast(main_ast);
 
         let service = result
@@ -2559,7 +2883,7 @@ mod tests {
         let mut main_ast = make_diagram_ast(vec![], vec![]);
         main_ast.imports = vec![make_import(Some(Id::new("styles")), lib_ast)];
 
-        let mut folder = Desugar::new();
+        let mut folder = Desugar::new(Theme::default());
         let result = folder.fold_file_ast(main_ast);
 
         let service = result
@@ -2608,7 +2932,7 @@ mod tests {
         let mut main_ast = make_diagram_ast(vec![], vec![]);
         main_ast.imports = vec![make_import(Some(Id::new("styles")), lib_ast)];
 
-        let mut folder = Desugar::new();
+        let mut folder = Desugar::new(Theme::default());
         let result = folder.fold_file_ast(main_ast);
 
         let service = result
@@ -2661,7 +2985,7 @@ mod tests {
         let mut main_ast = make_diagram_ast(vec![], vec![]);
         main_ast.imports = vec![make_import(None, lib_a), make_import(None, lib_b)];
 
-        let mut folder = Desugar::new();
+        let mut folder = Desugar::new(Theme::default());
         let result = folder.fold_file_ast(main_ast);
 
         let type_defs = user_type_definitions(&result.type_definitions);
@@ -2706,7 +3030,7 @@ mod tests {
         );
         main_ast.imports = vec![make_import(None, lib_ast)];
 
-        let mut folder = Desugar::new();
+        let mut folder = Desugar::new(Theme::default());
         let result = folder.fold_file_ast(main_ast);
 
         let type_defs = user_type_definitions(&result.type_definitions);
@@ -2744,7 +3068,7 @@ mod tests {
         let mut main_ast = make_diagram_ast(vec![], vec![]);
         main_ast.imports = vec![make_import(Some(Id::new("ext")), ext_ast)];
 
-        let mut folder = Desugar::new();
+        let mut folder = Desugar::new(Theme::default());
         let result = folder.fold_file_ast(main_ast);
 
         // Should contain ext::base::Color
@@ -2791,7 +3115,7 @@ mod tests {
             make_import(Some(Id::new("flow")), diag_ast),
         ];
 
-        let mut folder = Desugar::new();
+        let mut folder = Desugar::new(Theme::default());
         let result = folder.fold_file_ast(main_ast);
 
         // Both library and diagram imports are consumed — none in output.
@@ -2832,7 +3156,7 @@ mod tests {
         let mut main_ast = make_diagram_ast(vec![], vec![]);
         main_ast.imports = vec![make_import(None, lib_ast)];
 
-        let mut folder = Desugar::new();
+        let mut folder = Desugar::new(Theme::default());
         let result = folder.fold_file_ast(main_ast);
 
         let type_defs = user_type_definitions(&result.type_definitions);
@@ -2873,14 +3197,17 @@ mod tests {
                 content: ComponentContent::Diagram(DiagramSource::Ref(spanned(Id::new(
                     "auth_flow",
                 )))),
+                comments: Vec::new(),
             }],
             imports: vec![Import {
                 namespace: Some(Id::new("auth_flow")),
                 file_ast: Rc::clone(&imported_rc),
             }],
+            variable_definitions: vec![],
+            style_definitions: vec![],
         };
 
-        let result = desugar(root_ast);
+        let result = desugar(root_ast, &Theme::default());
 
         // The component should now have DiagramSource::Inline
         match &result.elements[0] {
@@ -2929,11 +3256,14 @@ mod tests {
                 content: ComponentContent::Diagram(DiagramSource::Ref(spanned(Id::new(
                     "nonexistent",
                 )))),
+                comments: Vec::new(),
             }],
             imports: vec![],
+            variable_definitions: vec![],
+            style_definitions: vec![],
         };
 
-        let result = desugar(root_ast);
+        let result = desugar(root_ast, &Theme::default());
 
         match &result.elements[0] {
             Element::Component { content, .. } => {
@@ -2967,14 +3297,17 @@ mod tests {
                     attributes: vec![],
                 },
                 content: ComponentContent::Diagram(DiagramSource::Ref(spanned(Id::new("styles")))),
+                comments: Vec::new(),
             }],
             imports: vec![Import {
                 namespace: None, // glob import — no namespace
                 file_ast: imported_rc,
             }],
+            variable_definitions: vec![],
+            style_definitions: vec![],
         };
 
-        let result = desugar(root_ast);
+        let result = desugar(root_ast, &Theme::default());
 
         match &result.elements[0] {
             Element::Component { content, .. } => {
@@ -3017,6 +3350,7 @@ mod tests {
                     content: ComponentContent::Diagram(DiagramSource::Inline(Rc::new(
                         RefCell::new(inline_ast),
                     ))),
+                    comments: Vec::new(),
                 },
                 // Second: ref embed — must still resolve
                 Element::Component {
@@ -3029,15 +3363,18 @@ mod tests {
                     content: ComponentContent::Diagram(DiagramSource::Ref(spanned(Id::new(
                         "imported",
                     )))),
+                    comments: Vec::new(),
                 },
             ],
             imports: vec![Import {
                 namespace: Some(Id::new("imported")),
                 file_ast: Rc::clone(&imported_rc),
             }],
+            variable_definitions: vec![],
+            style_definitions: vec![],
         };
 
-        let result = desugar(root_ast);
+        let result = desugar(root_ast, &Theme::default());
 
         // box2's ref should have been resolved to Inline
         match &result.elements[1] {
@@ -3105,7 +3442,7 @@ mod tests {
             make_import(None, a_ast), // glob import of a
         ];
 
-        let mut folder = Desugar::new();
+        let mut folder = Desugar::new(Theme::default());
         let result = folder.fold_file_ast(main_ast);
 
         let type_defs = user_type_definitions(&result.type_definitions);
@@ -3162,7 +3499,7 @@ mod tests {
             vec![],
         );
 
-        let mut folder = Desugar::new();
+        let mut folder = Desugar::new(Theme::default());
         let result = folder.fold_file_ast(main_ast);
 
         // Chaining merges into a single definition.
@@ -3214,7 +3551,7 @@ mod tests {
             vec![],
         );
 
-        let mut folder = Desugar::new();
+        let mut folder = Desugar::new(Theme::default());
         let result = folder.fold_file_ast(main_ast);
 
         let type_defs = user_type_definitions(&result.type_definitions);
@@ -3252,7 +3589,7 @@ mod tests {
             vec![],
         );
 
-        let mut folder = Desugar::new();
+        let mut folder = Desugar::new(Theme::default());
         let result = folder.fold_file_ast(main_ast);
 
         let type_defs = user_type_definitions(&result.type_definitions);
@@ -3297,7 +3634,7 @@ mod tests {
         );
         main_ast.imports = vec![make_import(None, lib_ast)];
 
-        let mut folder = Desugar::new();
+        let mut folder = Desugar::new(Theme::default());
         let result = folder.fold_file_ast(main_ast);
 
         let type_defs = user_type_definitions(&result.type_definitions);
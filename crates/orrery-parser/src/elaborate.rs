@@ -4,20 +4,31 @@
 //! types ready for layout and rendering. It performs type resolution, validates
 //! semantic correctness, and builds the final representation.
 
-use std::{collections::HashMap, mem, rc::Rc, str::FromStr};
+use std::{
+    borrow::Cow,
+    cell::Cell,
+    collections::{HashMap, HashSet},
+    mem,
+    rc::Rc,
+    slice,
+    str::FromStr,
+};
 
 use log::{debug, info, trace};
+use serde::{Deserialize, Serialize};
 
 use orrery_core::{
-    color::Color,
+    color::{Color, Palette, Theme},
     draw::{
-        ArrowDirection, ArrowStyle, DiagramDefinition, LifelineDefinition, StrokeDefinition,
-        TextDefinition,
+        ArrowDirection, ArrowSegment, ArrowStyle, Arrowhead, CanvasFit, ContentAlign, Density,
+        DiagramDefinition, EdgeRouting, ImageDefinition, LabelPosition, LifelineDefinition,
+        LinkDefinition, ShadowDefinition, StrokeDefinition, TextDefinition,
     },
+    geometry::{Insets, Size},
     identifier::Id,
     semantic::{
-        Activate, Block, Diagram, DiagramKind, Element, Fragment, FragmentSection, LayoutEngine,
-        Node, Note, NoteAlign, Relation, Scope,
+        Activate, Block, Diagram, DiagramKind, Element, Fragment, FragmentSection, Group,
+        Highlight, LayoutEngine, Node, Note, NoteAlign, Pin, Relation, Scope,
     },
 };
 
@@ -29,26 +40,114 @@ use crate::{
     span::Spanned,
 };
 
+/// Number of evenly spaced hues auto-colored components are drawn from.
+const AUTOCOLOR_HUES: u64 = 12;
+
+/// How relation endpoints that are not declared as components are treated.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImplicitNodes {
+    /// Undeclared endpoints are errors unless the diagram sets
+    /// `implicit_nodes="true"`.
+    #[default]
+    OptIn,
+    /// Undeclared endpoints become implicit components unless the diagram
+    /// sets `implicit_nodes="false"`.
+    Enabled,
+    /// Undeclared endpoints are always errors, even in diagrams that set
+    /// `implicit_nodes="true"`.
+    Deny,
+}
+
+impl ImplicitNodes {
+    /// Returns whether a diagram creates implicit components, given the value
+    /// of its `implicit_nodes` attribute, if set.
+    pub(crate) fn resolve(self, attribute: Option<bool>) -> bool {
+        match self {
+            Self::OptIn => attribute.unwrap_or(false),
+            Self::Enabled => attribute.unwrap_or(true),
+            Self::Deny => false,
+        }
+    }
+}
+
 /// Configuration for the elaboration phase.
 ///
 /// This struct holds the default layout engine settings that are used
-/// when no explicit layout_engine attribute is specified in the diagram.
-#[derive(Debug, Clone, Default)]
+/// when no explicit layout_engine attribute is specified in the diagram,
+/// the [`Palette`] of named colors consulted when resolving colors, the
+/// [`Theme`] that supplies the default colors of the built-in types, and the
+/// defaults of the `implicit_nodes` and `autonumber` diagram attributes.
+#[derive(Debug, Clone)]
 pub struct ElaborateConfig {
     /// Default layout engine for component diagrams
     pub component_layout: LayoutEngine,
     /// Default layout engine for sequence diagrams
     pub sequence_layout: LayoutEngine,
+    /// Named colors that color attributes may reference (e.g. `brand.primary`)
+    pub palette: Palette,
+    /// Colors the built-in types are drawn with by default
+    pub theme: Theme,
+    /// Whether undeclared relation endpoints become implicit components
+    pub implicit_nodes: ImplicitNodes,
+    /// Type of implicit components, `Rectangle` by default
+    pub implicit_node_type: Id,
+    /// Number of the first message of sequence diagrams that do not set
+    /// `autonumber`, or `None` to leave their messages unnumbered
+    pub autonumber: Option<usize>,
 }
 
 impl ElaborateConfig {
-    /// Creates a new [`ElaborateConfig`] with the specified layout engines.
+    /// Creates a new [`ElaborateConfig`] with the specified layout engines
+    /// an empty palette and the light theme.
     pub fn new(component_layout: LayoutEngine, sequence_layout: LayoutEngine) -> Self {
         Self {
             component_layout,
             sequence_layout,
+            palette: Palette::default(),
+            theme: Theme::default(),
+            implicit_nodes: ImplicitNodes::default(),
+            implicit_node_type: Id::new(builtin_types::RECTANGLE),
+            autonumber: None,
         }
     }
+
+    /// Sets the [`Palette`] of named colors available to diagrams.
+    pub fn with_palette(mut self, palette: Palette) -> Self {
+        self.palette = palette;
+        self
+    }
+
+    /// Sets the [`Theme`] the built-in types take their default colors from.
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Sets how undeclared relation endpoints are treated.
+    pub fn with_implicit_nodes(mut self, implicit_nodes: ImplicitNodes) -> Self {
+        self.implicit_nodes = implicit_nodes;
+        self
+    }
+
+    /// Sets the type of implicit components.
+    pub fn with_implicit_node_type(mut self, type_name: Id) -> Self {
+        self.implicit_node_type = type_name;
+        self
+    }
+
+    /// Sets the number of the first message of sequence diagrams that do not
+    /// set `autonumber`, or `None` to leave them unnumbered.
+    pub fn with_autonumber(mut self, start: Option<usize>) -> Self {
+        self.autonumber = start;
+        self
+    }
+}
+
+impl Default for ElaborateConfig {
+    fn default() -> Self {
+        Self::new(LayoutEngine::default(), LayoutEngine::default())
+    }
 }
 
 /// Builds semantic diagrams from parser AST.
@@ -67,6 +166,19 @@ impl ElaborateConfig {
 pub struct Builder {
     cfg: ElaborateConfig,
     type_definitions: HashMap<Id, elaborate_utils::TypeDefinition>,
+    /// Number of anonymous types created so far, used to name the next one.
+    anonymous_types: Cell<usize>,
+    /// Types that set `fill_color`, directly or through their base type, while
+    /// the top-level components of an `autocolor` diagram are built; `None`
+    /// everywhere else.
+    autocolor_fill_types: Option<HashSet<Id>>,
+    /// Number of the next message of an `autonumber` sequence diagram; `None`
+    /// everywhere else.
+    next_message_number: Option<usize>,
+    /// Size of the element whose scope is being built, which percentage
+    /// lengths resolve against: the enclosing component's minimum size, or
+    /// the diagram's `width` and `height`. A zero side is unknown.
+    parent_size: Size,
 }
 
 impl Builder {
@@ -83,6 +195,10 @@ impl Builder {
         Self {
             cfg,
             type_definitions: HashMap::new(),
+            anonymous_types: Cell::new(0),
+            autocolor_fill_types: None,
+            next_message_number: None,
+            parent_size: Size::zero(),
         }
     }
 
@@ -142,18 +258,63 @@ impl Builder {
         trace!("Type definitions: {:?}", file_ast.type_definitions);
         trace!("Elements count: {}", file_ast.elements.len());
 
-        let saved_type_defs = mem::replace(
-            &mut self.type_definitions,
-            Self::builtin_type_definitions_map(),
-        );
+        let builtin_type_defs = self.builtin_type_definitions_map();
+        let saved_type_defs = mem::replace(&mut self.type_definitions, builtin_type_defs);
+        // Type definitions have no parent element to resolve percentages against.
+        let saved_parent_size = mem::replace(&mut self.parent_size, Size::zero());
 
         debug!("Updating type definitions");
         self.update_type_direct_definitions(&file_ast.type_definitions)?;
 
         let kind = **kind_spanned;
 
+        let (layout_engine, diagram_definition) =
+            self.resolve_diagram_header(kind, attributes, kind_spanned.span())?;
+        self.parent_size = Size::new(
+            diagram_definition.width().unwrap_or_default(),
+            diagram_definition.height().unwrap_or_default(),
+        );
+
+        let implicit_nodes = attributes
+            .iter()
+            .rfind(|attr| *attr.name.inner() == "implicit_nodes")
+            .map(|attr| Self::extract_bool(attr, "implicit_nodes"))
+            .transpose()?;
+        let elements = if self.cfg.implicit_nodes.resolve(implicit_nodes) {
+            Cow::Owned(Self::with_implicit_components(
+                &file_ast.elements,
+                self.cfg.implicit_node_type,
+            ))
+        } else {
+            Cow::Borrowed(file_ast.elements.as_slice())
+        };
+
+        let elements = if file_ast.style_definitions.is_empty() {
+            elements
+        } else {
+            Cow::Owned(Self::with_styles(&elements, &file_ast.style_definitions))
+        };
+
+        let first_message_number = match attributes
+            .iter()
+            .rfind(|attr| *attr.name.inner() == "autonumber")
+        {
+            Some(attr) => Self::extract_autonumber(attr, kind)?,
+            None if kind == DiagramKind::Sequence => self.cfg.autonumber,
+            None => None,
+        };
+
         debug!("Building block from elements");
-        let block = self.build_block_from_elements(&file_ast.elements, kind)?;
+        let fill_types = diagram_definition
+            .autocolor()
+            .then(|| Self::fill_type_ids(&file_ast.type_definitions));
+        let saved_fill_types = mem::replace(&mut self.autocolor_fill_types, fill_types);
+        let saved_message_number =
+            mem::replace(&mut self.next_message_number, first_message_number);
+        let block = self.build_block_from_elements(&elements, kind);
+        self.autocolor_fill_types = saved_fill_types;
+        self.next_message_number = saved_message_number;
+        let block = block?;
 
         let scope = match block {
             Block::None => {
@@ -175,17 +336,192 @@ impl Builder {
             }
         };
 
-        let (layout_engine, diagram_definition) =
-            self.resolve_diagram_header(kind, attributes, kind_spanned.span())?;
-
         info!(kind:?; "Diagram elaboration completed successfully");
 
         // Restore parent type definitions.
         self.type_definitions = saved_type_defs;
+        self.parent_size = saved_parent_size;
 
         Ok(Diagram::new(kind, scope, layout_engine, diagram_definition))
     }
 
+    /// Returns `elements` with a component of `type_name` for every relation
+    /// endpoint that is not declared, inserted before the top-level element
+    /// that first uses it.
+    ///
+    /// Used by `implicit_nodes` diagrams; validation has already warned about
+    /// each of these endpoints.
+    fn with_implicit_components<'a>(
+        elements: &[parser_types::Element<'a>],
+        type_name: Id,
+    ) -> Vec<parser_types::Element<'a>> {
+        let mut declared = HashSet::new();
+        Self::collect_component_ids(elements, &mut declared);
+
+        let mut with_implicit = Vec::new();
+        for element in elements {
+            Self::collect_implicit_components(
+                slice::from_ref(element),
+                type_name,
+                &mut declared,
+                &mut with_implicit,
+            );
+            with_implicit.push(element.clone());
+        }
+        with_implicit
+    }
+
+    /// Returns `elements` with the attributes of each component's `class`
+    /// style merged into its type spec.
+    ///
+    /// The style's attributes are placed before the component's own, so the
+    /// inline attributes win. Validation has already checked that every class
+    /// names one of `style_definitions`.
+    fn with_styles<'a>(
+        elements: &[parser_types::Element<'a>],
+        style_definitions: &[parser_types::StyleDefinition<'a>],
+    ) -> Vec<parser_types::Element<'a>> {
+        let styles: HashMap<Id, &[parser_types::Attribute<'a>]> = style_definitions
+            .iter()
+            .map(|style_def| (*style_def.name.inner(), style_def.attributes.as_slice()))
+            .collect();
+
+        let mut styled = elements.to_vec();
+        Self::apply_styles(&mut styled, &styles);
+        styled
+    }
+
+    /// Merges the `class` style of each component in `elements` and their
+    /// nested scopes. Embedded diagrams use their own styles.
+    fn apply_styles<'a>(
+        elements: &mut [parser_types::Element<'a>],
+        styles: &HashMap<Id, &[parser_types::Attribute<'a>]>,
+    ) {
+        for element in elements {
+            match element {
+                parser_types::Element::Component {
+                    type_spec, content, ..
+                } => {
+                    let mut class = None;
+                    type_spec.attributes.retain(|attr| {
+                        let is_class = *attr.name.inner() == "class";
+                        if is_class {
+                            class = attr.value.as_str().ok().map(Id::new);
+                        }
+                        !is_class
+                    });
+                    if let Some(style) = class.and_then(|class| styles.get(&class)) {
+                        type_spec.attributes.splice(0..0, style.iter().cloned());
+                    }
+                    if let parser_types::ComponentContent::Scope(nested) = content {
+                        Self::apply_styles(nested, styles);
+                    }
+                }
+                parser_types::Element::Fragment(fragment) => {
+                    for section in &mut fragment.sections {
+                        Self::apply_styles(&mut section.elements, styles);
+                    }
+                }
+                parser_types::Element::Highlight(highlight) => {
+                    Self::apply_styles(&mut highlight.elements, styles);
+                }
+                parser_types::Element::Group(group) => {
+                    Self::apply_styles(&mut group.elements, styles);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Collects the ids of the components declared in `elements` and their
+    /// nested scopes.
+    fn collect_component_ids(elements: &[parser_types::Element], declared: &mut HashSet<Id>) {
+        for element in elements {
+            match element {
+                parser_types::Element::Component { name, content, .. } => {
+                    declared.insert(*name.inner());
+                    if let parser_types::ComponentContent::Scope(nested) = content {
+                        Self::collect_component_ids(nested, declared);
+                    }
+                }
+                parser_types::Element::Fragment(fragment) => {
+                    for section in &fragment.sections {
+                        Self::collect_component_ids(&section.elements, declared);
+                    }
+                }
+                parser_types::Element::Highlight(highlight) => {
+                    Self::collect_component_ids(&highlight.elements, declared);
+                }
+                parser_types::Element::Group(group) => {
+                    Self::collect_component_ids(&group.elements, declared);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Appends a component of `type_name` to `implicit` for each relation
+    /// endpoint in `elements` that is not in `declared`, adding it to
+    /// `declared`.
+    fn collect_implicit_components<'a>(
+        elements: &[parser_types::Element<'a>],
+        type_name: Id,
+        declared: &mut HashSet<Id>,
+        implicit: &mut Vec<parser_types::Element<'a>>,
+    ) {
+        for element in elements {
+            match element {
+                parser_types::Element::Relation { source, target, .. } => {
+                    for endpoint in [source, target] {
+                        if declared.insert(*endpoint.inner()) {
+                            implicit.push(parser_types::Element::Component {
+                                name: *endpoint,
+                                display_name: None,
+                                type_spec: parser_types::TypeSpec {
+                                    type_name: Some(Spanned::new(type_name, endpoint.span())),
+                                    attributes: vec![],
+                                },
+                                content: parser_types::ComponentContent::None,
+                                comments: Vec::new(),
+                            });
+                        }
+                    }
+                }
+                parser_types::Element::Component {
+                    content: parser_types::ComponentContent::Scope(nested),
+                    ..
+                } => Self::collect_implicit_components(nested, type_name, declared, implicit),
+                parser_types::Element::Fragment(fragment) => {
+                    for section in &fragment.sections {
+                        Self::collect_implicit_components(
+                            &section.elements,
+                            type_name,
+                            declared,
+                            implicit,
+                        );
+                    }
+                }
+                parser_types::Element::Highlight(highlight) => {
+                    Self::collect_implicit_components(
+                        &highlight.elements,
+                        type_name,
+                        declared,
+                        implicit,
+                    );
+                }
+                parser_types::Element::Group(group) => {
+                    Self::collect_implicit_components(
+                        &group.elements,
+                        type_name,
+                        declared,
+                        implicit,
+                    );
+                }
+                _ => {}
+            }
+        }
+    }
+
     /// Builds a semantic diagram from a [`DiagramSource`](parser_types::DiagramSource).
     ///
     /// Dispatches on the source variant:
@@ -260,11 +596,17 @@ impl Builder {
 
     /// Extract and parse a color from an attribute value with contextual error.
     /// This performs both string extraction and color parsing in one step.
+    /// Named colors from `palette` take precedence over CSS color names.
     ///
     /// # Arguments
     /// * `attr` - The attribute containing the value
     /// * `key` - Display name for error messages (e.g., "fill_color", "background_color")
-    fn extract_color(attr: &parser_types::Attribute<'_>, key: &str) -> Result<Color> {
+    /// * `palette` - Named colors the value may reference
+    fn extract_color(
+        attr: &parser_types::Attribute<'_>,
+        key: &str,
+        palette: &Palette,
+    ) -> Result<Color> {
         let color_str = attr.value.as_str().map_err(|err| {
             Diagnostic::error(err.to_string())
                 .with_code(ErrorCode::E302)
@@ -272,7 +614,7 @@ impl Builder {
                 .with_help("color values must be strings")
         })?;
 
-        Color::new(color_str).map_err(|err| {
+        elaborate_utils::resolve_color(color_str, attr, palette, |err| {
             Diagnostic::error(format!("invalid {key} `{color_str}`: {err}"))
                 .with_code(ErrorCode::E302)
                 .with_label(attr.span(), "invalid color")
@@ -280,6 +622,22 @@ impl Builder {
         })
     }
 
+    /// Extract a `"true"` or `"false"` string from an attribute value with
+    /// contextual error.
+    ///
+    /// # Arguments
+    /// * `attr` - The attribute containing the value
+    /// * `key` - Display name for error messages (e.g., "autocolor")
+    fn extract_bool(attr: &parser_types::Attribute<'_>, key: &str) -> Result<bool> {
+        let value = Self::extract_string(attr, key)?;
+        value.parse().map_err(|_| {
+            Diagnostic::error(format!("invalid {key} value `{value}`"))
+                .with_code(ErrorCode::E302)
+                .with_label(attr.span(), format!("invalid {key} value"))
+                .with_help(format!("{key} must be \"true\" or \"false\""))
+        })
+    }
+
     /// Extract a positive float from an attribute value with contextual error.
     ///
     /// # Arguments
@@ -294,6 +652,64 @@ impl Builder {
         })
     }
 
+    /// Extract a positive length from an attribute value with contextual error,
+    /// resolving a percentage against `reference`.
+    ///
+    /// # Arguments
+    /// * `attr` - The attribute containing the value
+    /// * `key` - Display name for error messages (e.g., "width")
+    /// * `reference` - The parent element's size along the attribute's axis,
+    ///   which `100%` stands for
+    fn extract_positive_length(
+        attr: &parser_types::Attribute<'_>,
+        key: &str,
+        reference: f32,
+    ) -> Result<f32> {
+        let error = |message: String| {
+            Diagnostic::error(message)
+                .with_code(ErrorCode::E302)
+                .with_label(attr.span(), format!("invalid {key} value"))
+                .with_help(format!(
+                    "{key} must be a positive number; a percentage is relative to the \
+                     enclosing component's minimum size, or the diagram's `width` and \
+                     `height` at the top level"
+                ))
+        };
+        let val = attr
+            .value
+            .as_length(reference)
+            .map_err(|err| error(err.to_string()))?;
+        if val <= 0.0 {
+            return Err(error(format!("{key} `{}` is not positive", attr.value)));
+        }
+        Ok(val)
+    }
+
+    /// Extract an opacity between `0` and `1` from an attribute value with
+    /// contextual error. A percentage such as `50%` is read as a fraction.
+    ///
+    /// # Arguments
+    /// * `attr` - The attribute containing the value
+    /// * `key` - Display name for error messages (e.g., "fill_opacity")
+    fn extract_opacity(attr: &parser_types::Attribute<'_>, key: &str) -> Result<f32> {
+        let error = |message: String| {
+            Diagnostic::error(message)
+                .with_code(ErrorCode::E302)
+                .with_label(attr.span(), format!("invalid {key} value"))
+                .with_help(format!(
+                    "{key} must be a number between 0 and 1, or a percentage such as `50%`"
+                ))
+        };
+        let val = attr
+            .value
+            .as_length(1.0)
+            .map_err(|err| error(err.to_string()))?;
+        if !(0.0..=1.0).contains(&val) {
+            return Err(error(format!("{key} `{val}` is out of range")));
+        }
+        Ok(val)
+    }
+
     /// Extract a usize from an attribute value with contextual error.
     ///
     /// # Arguments
@@ -309,18 +725,425 @@ impl Builder {
         })
     }
 
+    /// Parse an arrowhead name such as `"diamond"`.
+    ///
+    /// # Arguments
+    /// * `attr` - The attribute holding the name, used for error spans
+    /// * `name` - The attribute name, for error messages
+    fn parse_arrowhead(attr: &parser_types::Attribute<'_>, name: &str) -> Result<Arrowhead> {
+        let value = Self::extract_string(attr, name)?;
+        Arrowhead::from_str(value).map_err(|_| {
+            Diagnostic::error(format!("invalid arrowhead `{value}`"))
+                .with_code(ErrorCode::E302)
+                .with_label(attr.span(), "invalid arrowhead")
+                .with_help("arrowhead must be `triangle`, `open`, `diamond`, `circle`, or `none`")
+        })
+    }
+
+    /// Extract insets from an attribute value: a single number for all sides,
+    /// or an inline `[top=..., right=..., bottom=..., left=...]` list where
+    /// omitted sides are zero.
+    ///
+    /// # Arguments
+    /// * `attr` - The attribute containing the value
+    /// * `key` - Display name for error messages (e.g., "canvas_padding")
+    fn extract_insets(attr: &parser_types::Attribute<'_>, key: &str) -> Result<Insets> {
+        let parser_types::AttributeValue::TypeSpec(type_spec) = &attr.value else {
+            return Self::extract_positive_float(attr, key).map(Insets::uniform);
+        };
+        if let Some(type_name) = &type_spec.type_name {
+            return Err(
+                Diagnostic::error(format!("{key} cannot reference type `{type_name}`"))
+                    .with_code(ErrorCode::E302)
+                    .with_label(type_name.span(), "unexpected type reference")
+                    .with_help(format!(
+                        "write {key} as a number or as inline sides, e.g. `{key}=[top=10, left=20]`"
+                    )),
+            );
+        }
+
+        let (mut top, mut right, mut bottom, mut left) = (0.0, 0.0, 0.0, 0.0);
+        for side_attr in &type_spec.attributes {
+            let side = match *side_attr.name.inner() {
+                "top" => &mut top,
+                "right" => &mut right,
+                "bottom" => &mut bottom,
+                "left" => &mut left,
+                name => {
+                    return Err(Diagnostic::error(format!("unknown {key} side `{name}`"))
+                        .with_code(ErrorCode::E303)
+                        .with_label(side_attr.span(), "unknown side")
+                        .with_help("valid sides are: `top`, `right`, `bottom`, `left`"));
+                }
+            };
+            *side = Self::extract_positive_float(side_attr, side_attr.name.inner())?;
+        }
+        Ok(Insets::new(top, right, bottom, left))
+    }
+
+    /// Parse a shape's `shadow` attribute, either `"true"`/`"false"` or an
+    /// inline `[blur=..., dx=..., dy=..., color=...]` list.
+    ///
+    /// # Arguments
+    /// * `attr` - The `shadow` attribute
+    /// * `current` - The shape's current shadow, which inline attributes refine
+    /// * `palette` - Named colors the shadow color may reference
+    fn extract_shadow(
+        attr: &parser_types::Attribute<'_>,
+        current: Option<&ShadowDefinition>,
+        palette: &Palette,
+    ) -> Result<Option<ShadowDefinition>> {
+        let type_spec = match &attr.value {
+            parser_types::AttributeValue::String(_) => {
+                let enabled = Self::extract_bool(attr, "shadow")?;
+                return Ok(enabled.then(|| current.cloned().unwrap_or_default()));
+            }
+            parser_types::AttributeValue::TypeSpec(type_spec) => type_spec,
+            _ => {
+                return Err(Diagnostic::error("invalid shadow value")
+                    .with_code(ErrorCode::E302)
+                    .with_label(attr.span(), "invalid shadow value")
+                    .with_help(
+                        "shadow must be \"true\", \"false\", or inline attributes such as `[blur=4, dx=2, dy=2]`",
+                    ));
+            }
+        };
+        if let Some(type_name) = &type_spec.type_name {
+            return Err(
+                Diagnostic::error(format!("shadow cannot reference type `{type_name}`"))
+                    .with_code(ErrorCode::E302)
+                    .with_label(type_name.span(), "unexpected type reference")
+                    .with_help("write the shadow as inline attributes, e.g. `shadow=[blur=4]`"),
+            );
+        }
+
+        let mut shadow = current.cloned().unwrap_or_default();
+        for shadow_attr in &type_spec.attributes {
+            match *shadow_attr.name.inner() {
+                "blur" => shadow.set_blur(Self::extract_positive_float(shadow_attr, "blur")?),
+                "dx" => shadow.set_dx(Self::extract_positive_float(shadow_attr, "dx")?),
+                "dy" => shadow.set_dy(Self::extract_positive_float(shadow_attr, "dy")?),
+                "color" => shadow.set_color(Self::extract_color(shadow_attr, "color", palette)?),
+                name => {
+                    return Err(
+                        Diagnostic::error(format!("unknown shadow attribute `{name}`"))
+                            .with_code(ErrorCode::E303)
+                            .with_label(shadow_attr.span(), "unknown attribute")
+                            .with_help("valid shadow attributes are: `blur`, `dx`, `dy`, `color`"),
+                    );
+                }
+            }
+        }
+        Ok(Some(shadow))
+    }
+
+    /// Apply a shape or arrow `href` or `target` attribute to its link.
+    ///
+    /// # Arguments
+    /// * `attr` - The `href` or `target` attribute
+    /// * `current` - The element's current link, which the attribute refines
+    fn extract_link(
+        attr: &parser_types::Attribute<'_>,
+        current: Option<&LinkDefinition>,
+    ) -> Result<LinkDefinition> {
+        let name = *attr.name.inner();
+        let value = Self::extract_string(attr, name)?;
+        let mut link = current.cloned().unwrap_or_default();
+        if name == "href" {
+            link.set_href(value);
+        } else {
+            link.set_target(value);
+        }
+        Ok(link)
+    }
+
+    /// Reject a `target` attribute on an element whose link, inline or
+    /// inherited from its type, has no `href`.
+    ///
+    /// # Arguments
+    /// * `attributes` - The attributes applied to the element
+    /// * `link` - The element's link after applying `attributes`
+    fn check_link_target(
+        attributes: &[parser_types::Attribute<'_>],
+        link: Option<&LinkDefinition>,
+    ) -> Result<()> {
+        let Some(attr) = attributes
+            .iter()
+            .find(|attr| *attr.name.inner() == "target")
+        else {
+            return Ok(());
+        };
+        if link.is_some_and(|link| link.href().is_some()) {
+            return Ok(());
+        }
+        Err(Diagnostic::error("`target` requires an `href`")
+            .with_code(ErrorCode::E302)
+            .with_label(attr.span(), "target without a link")
+            .with_help("add an `href` attribute with the URL to open"))
+    }
+
+    /// Parse a shape's `icon` attribute, either a file path or `data:` URI, or
+    /// an inline `[source=..., align=..., size=...]` list.
+    ///
+    /// # Arguments
+    /// * `attr` - The `icon` attribute
+    /// * `current` - The shape's current image, which inline attributes refine
+    fn extract_image(
+        attr: &parser_types::Attribute<'_>,
+        current: Option<&ImageDefinition>,
+    ) -> Result<ImageDefinition> {
+        let mut image = current.cloned().unwrap_or_else(|| ImageDefinition::new(""));
+        let attributes = match &attr.value {
+            parser_types::AttributeValue::String(source) => {
+                image.set_source(source.inner().as_str());
+                &[][..]
+            }
+            parser_types::AttributeValue::TypeSpec(type_spec) => {
+                if let Some(type_name) = &type_spec.type_name {
+                    return Err(Diagnostic::error(format!(
+                        "icon cannot reference type `{type_name}`"
+                    ))
+                    .with_code(ErrorCode::E302)
+                    .with_label(type_name.span(), "unexpected type reference")
+                    .with_help(
+                        "write the icon as inline attributes, e.g. `icon=[source=\"logo.png\"]`",
+                    ));
+                }
+                type_spec.attributes.as_slice()
+            }
+            _ => {
+                return Err(Diagnostic::error("invalid icon value")
+                    .with_code(ErrorCode::E302)
+                    .with_label(attr.span(), "invalid icon value")
+                    .with_help(
+                        "icon must be a file path, a data URI, or inline attributes such as `[source=\"logo.png\", align=\"top-right\"]`",
+                    ));
+            }
+        };
+        for icon_attr in attributes {
+            match *icon_attr.name.inner() {
+                "source" => image.set_source(Self::extract_string(icon_attr, "source")?),
+                "size" => image.set_size(Self::extract_positive_float(icon_attr, "size")?),
+                "align" => {
+                    let align_str = Self::extract_string(icon_attr, "align")?;
+                    let align = align_str.parse::<ContentAlign>().map_err(|_| {
+                        Diagnostic::error(format!("invalid icon alignment: `{align_str}`"))
+                            .with_code(ErrorCode::E302)
+                            .with_label(icon_attr.value.span(), "invalid alignment")
+                            .with_help(
+                                "valid values: center, top, bottom, left, right, top-left, top-right, bottom-left, bottom-right",
+                            )
+                    })?;
+                    image.set_align(align);
+                }
+                name => {
+                    return Err(
+                        Diagnostic::error(format!("unknown icon attribute `{name}`"))
+                            .with_code(ErrorCode::E303)
+                            .with_label(icon_attr.span(), "unknown attribute")
+                            .with_help("valid icon attributes are: `source`, `align`, `size`"),
+                    );
+                }
+            }
+        }
+
+        if image.source().is_empty() {
+            return Err(Diagnostic::error("icon has no source")
+                .with_code(ErrorCode::E302)
+                .with_label(attr.span(), "missing icon source")
+                .with_help("set the image with `source`, e.g. `icon=[source=\"logo.png\"]`"));
+        }
+        Ok(image)
+    }
+
+    /// Parse a diagram's `fit` attribute, `"contain"` or `"none"`.
+    ///
+    /// # Arguments
+    /// * `attr` - The `fit` attribute
+    fn parse_canvas_fit(attr: &parser_types::Attribute<'_>) -> Result<CanvasFit> {
+        let value = Self::extract_string(attr, "fit")?;
+        CanvasFit::from_str(value).map_err(|_| {
+            Diagnostic::error(format!("invalid fit `{value}`"))
+                .with_code(ErrorCode::E302)
+                .with_label(attr.span(), "invalid fit")
+                .with_help("fit must be `contain` or `none`")
+        })
+    }
+
+    /// Parse a diagram's `edge_routing` attribute, `"straight"` or
+    /// `"orthogonal"`.
+    ///
+    /// # Arguments
+    /// * `attr` - The `edge_routing` attribute
+    fn parse_edge_routing(attr: &parser_types::Attribute<'_>) -> Result<EdgeRouting> {
+        let value = Self::extract_string(attr, "edge_routing")?;
+        EdgeRouting::from_str(value).map_err(|_| {
+            Diagnostic::error(format!("invalid edge routing `{value}`"))
+                .with_code(ErrorCode::E302)
+                .with_label(attr.span(), "invalid edge routing")
+                .with_help("edge_routing must be `straight` or `orthogonal`")
+        })
+    }
+
+    /// Extract a diagram's `columns` attribute, the number of columns of a
+    /// grid layout.
+    ///
+    /// # Arguments
+    /// * `attr` - The `columns` attribute
+    fn extract_columns(attr: &parser_types::Attribute<'_>) -> Result<usize> {
+        const HINT: &str = "must be a positive whole number";
+        let columns = Self::extract_usize(attr, "columns", HINT)?;
+        if columns == 0 {
+            return Err(Diagnostic::error("columns must be at least 1")
+                .with_code(ErrorCode::E302)
+                .with_label(attr.span(), "invalid columns value")
+                .with_help(format!("columns {HINT}")));
+        }
+        Ok(columns)
+    }
+
+    /// Extract a relation's `min_length` attribute, the number of layout
+    /// ranks kept between its endpoints.
+    ///
+    /// # Arguments
+    /// * `attr` - The `min_length` attribute
+    fn extract_min_length(attr: &parser_types::Attribute<'_>) -> Result<usize> {
+        const HINT: &str = "must be a positive whole number";
+        let min_length = Self::extract_usize(attr, "min_length", HINT)?;
+        if min_length == 0 {
+            return Err(Diagnostic::error("min_length must be at least 1")
+                .with_code(ErrorCode::E302)
+                .with_label(attr.span(), "invalid min_length value")
+                .with_help(format!("min_length {HINT}")));
+        }
+        Ok(min_length)
+    }
+
+    /// Parse a diagram's `density` attribute, `"compact"`, `"comfortable"` or
+    /// `"spacious"`.
+    ///
+    /// # Arguments
+    /// * `attr` - The `density` attribute
+    fn parse_density(attr: &parser_types::Attribute<'_>) -> Result<Density> {
+        let value = Self::extract_string(attr, "density")?;
+        Density::from_str(value).map_err(|_| {
+            Diagnostic::error(format!("invalid density `{value}`"))
+                .with_code(ErrorCode::E302)
+                .with_label(attr.span(), "invalid density")
+                .with_help("density must be `compact`, `comfortable` or `spacious`")
+        })
+    }
+
+    /// Parse the comma-separated `style:fraction` list of an arrow's `segments`
+    /// attribute, checking that the fractions cover the whole path.
+    ///
+    /// # Arguments
+    /// * `segments_str` - The attribute value, e.g. `"solid:0.5, dashed:0.5"`
+    /// * `attr` - The attribute, used for error spans
+    fn parse_arrow_segments(
+        segments_str: &str,
+        attr: &parser_types::Attribute<'_>,
+    ) -> Result<Vec<ArrowSegment>> {
+        let segments = segments_str
+            .split(',')
+            .map(|segment| {
+                ArrowSegment::from_str(segment).map_err(|err| {
+                    Diagnostic::error(format!("invalid arrow segment: {err}"))
+                        .with_code(ErrorCode::E302)
+                        .with_label(attr.span(), "invalid segment")
+                        .with_help(
+                            "segments are written as `style:fraction`, e.g. `\"solid:0.5, dashed:0.5\"`",
+                        )
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let total: f32 = segments.iter().map(ArrowSegment::fraction).sum();
+        if (total - 1.0).abs() > 1e-3 {
+            return Err(Diagnostic::error(format!(
+                "arrow segment fractions sum to {total}, expected 1"
+            ))
+            .with_code(ErrorCode::E302)
+            .with_label(attr.span(), "fractions must sum to 1")
+            .with_help("adjust the fractions so the segments cover the whole relation"));
+        }
+
+        Ok(segments)
+    }
+
     // ============================================================================
     // Type Definition Methods
     // ============================================================================
 
-    /// Returns the built-in type definitions as an id-keyed map.
-    fn builtin_type_definitions_map() -> HashMap<Id, elaborate_utils::TypeDefinition> {
-        builtin_types::elaborate_type_definitions()
+    /// Returns the ids of the types in `type_definitions` that set
+    /// `fill_color`, either directly or through their base type.
+    ///
+    /// Definitions are visited in order, so a later redefinition of a name
+    /// replaces the earlier one.
+    fn fill_type_ids(type_definitions: &[parser_types::TypeDefinition]) -> HashSet<Id> {
+        let mut fill_types = HashSet::new();
+        for type_def in type_definitions {
+            let id = *type_def.name.inner();
+            if Self::sets_fill_color(&type_def.type_spec, &fill_types) {
+                fill_types.insert(id);
+            } else {
+                fill_types.remove(&id);
+            }
+        }
+        fill_types
+    }
+
+    /// Returns `true` if `type_spec` sets `fill_color` inline or names a type
+    /// in `fill_types`.
+    fn sets_fill_color(type_spec: &parser_types::TypeSpec, fill_types: &HashSet<Id>) -> bool {
+        type_spec
+            .attributes
+            .iter()
+            .any(|attr| *attr.name.inner() == "fill_color")
+            || type_spec
+                .type_name
+                .is_some_and(|name| fill_types.contains(name.inner()))
+    }
+
+    /// Returns the generated fill color of an auto-colored component.
+    ///
+    /// The hue is one of [`AUTOCOLOR_HUES`] evenly spaced hues, picked by a
+    /// stable hash of the component id, so a component keeps its color across
+    /// renders and when other components are added.
+    fn autocolor_fill(id: Id) -> Color {
+        // FNV-1a: unlike the std hasher, stable across Rust releases.
+        let hash = id
+            .to_string()
+            .bytes()
+            .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+            });
+        let slot = hash % AUTOCOLOR_HUES;
+        Color::from_hsl(slot as f32 * 360.0 / AUTOCOLOR_HUES as f32, 65.0, 82.0)
+    }
+
+    /// Returns the built-in type definitions, drawn with the configured
+    /// theme, as an id-keyed map.
+    fn builtin_type_definitions_map(&self) -> HashMap<Id, elaborate_utils::TypeDefinition> {
+        builtin_types::elaborate_type_definitions(&self.cfg.theme)
             .into_iter()
             .map(|def| (def.id(), def))
             .collect()
     }
 
+    /// Returns a fresh [`Id`] for an anonymous type created from inline
+    /// attributes.
+    ///
+    /// Names are numbered per elaboration rather than drawn from the global
+    /// interner size, so elaborating identical source again reuses the same
+    /// interned names instead of growing the interner. The `#` prefix cannot
+    /// appear in an identifier, so the names never clash with user types.
+    fn next_anonymous_type_id(&self) -> Id {
+        let index = self.anonymous_types.get();
+        self.anonymous_types.set(index + 1);
+        Id::new(&format!("#anon{index}"))
+    }
+
     /// Inserts a type definition, overriding any existing definition with the
     /// same id (last writer wins).
     fn insert_type_definition(
@@ -357,16 +1180,38 @@ impl Builder {
                 })?;
 
             // Try to create the type definition
-            let new_type_def = self.build_type_from_base(
-                *type_def.name.inner(),
-                base,
-                &type_def.type_spec.attributes,
-            )?;
+            let attributes = Self::strip_allow_unused(&type_def.type_spec.attributes)?;
+            let new_type_def =
+                self.build_type_from_base(*type_def.name.inner(), base, &attributes)?;
             self.insert_type_definition(new_type_def);
         }
         Ok(())
     }
 
+    /// Validates the `allow_unused` attributes of a type definition and
+    /// returns the remaining ones.
+    ///
+    /// `allow_unused` only silences the unused type warning, so it is not
+    /// applied to the type itself.
+    fn strip_allow_unused<'b, 'a>(
+        attributes: &'b [parser_types::Attribute<'a>],
+    ) -> Result<Cow<'b, [parser_types::Attribute<'a>]>> {
+        let is_allow_unused = |attr: &parser_types::Attribute| *attr.name.inner() == "allow_unused";
+        if !attributes.iter().any(is_allow_unused) {
+            return Ok(Cow::Borrowed(attributes));
+        }
+
+        let mut rest = Vec::with_capacity(attributes.len());
+        for attr in attributes {
+            if is_allow_unused(attr) {
+                Self::extract_bool(attr, "allow_unused")?;
+            } else {
+                rest.push(attr.clone());
+            }
+        }
+        Ok(Cow::Owned(rest))
+    }
+
     /// Converts a slice of parser elements into a semantic [`Block`].
     ///
     /// Returns `Block::None` for an empty slice, or wraps the elaborated elements
@@ -417,6 +1262,7 @@ impl Builder {
                     display_name,
                     type_spec,
                     content,
+                    ..
                 } => self.build_component_element(
                     name,
                     display_name,
@@ -431,6 +1277,7 @@ impl Builder {
                     relation_type,
                     type_spec,
                     label,
+                    ..
                 } => {
                     self.build_relation_element(source, target, relation_type, type_spec, label)?
                 }
@@ -460,6 +1307,12 @@ impl Builder {
                     );
                 }
                 parser_types::Element::Note(note) => self.build_note_element(note, diagram_kind)?,
+                parser_types::Element::Highlight(highlight) => {
+                    self.build_highlight_element(highlight, diagram_kind)?
+                }
+                parser_types::Element::Group(group) => {
+                    self.build_group_element(group, diagram_kind)?
+                }
             };
             elements.push(element);
         }
@@ -481,8 +1334,8 @@ impl Builder {
     /// # Errors
     ///
     /// Returns `E307` if the type is not a valid shape, `E308` if the shape does not
-    /// support content but content was provided, or propagates errors from nested
-    /// elaboration.
+    /// support content but content was provided, `E304` for a position outside a
+    /// component diagram, or propagates errors from nested elaboration.
     fn build_component_element(
         &mut self,
         name: &Spanned<Id>,
@@ -492,13 +1345,30 @@ impl Builder {
         parser_elm: &parser_types::Element,
         diagram_kind: DiagramKind,
     ) -> Result<Element> {
+        let (pin, type_spec) = Self::split_pin_attributes(type_spec, diagram_kind)?;
+        let type_spec = &type_spec;
         let type_def = self.build_type_definition(type_spec)?;
 
-        let shape_def = type_def.shape_definition().map_err(|err| {
-            Diagnostic::error(err)
-                .with_code(ErrorCode::E307)
-                .with_label(type_spec.span(), "invalid shape type")
-        })?;
+        let mut shape_def = type_def
+            .shape_definition()
+            .map_err(|err| {
+                Diagnostic::error(err)
+                    .with_code(ErrorCode::E307)
+                    .with_label(type_spec.span(), "invalid shape type")
+            })
+            .map(Rc::clone)?;
+
+        if let Some(fill_types) = &self.autocolor_fill_types
+            && !Self::sets_fill_color(type_spec, fill_types)
+        {
+            let mut auto_shape = (*shape_def).clone();
+            if auto_shape
+                .set_fill_color(Some(Self::autocolor_fill(*name.inner())))
+                .is_ok()
+            {
+                shape_def = Rc::new(auto_shape);
+            }
+        }
 
         if !matches!(content, parser_types::ComponentContent::None) && !shape_def.supports_content()
         {
@@ -516,26 +1386,88 @@ impl Builder {
             )));
         }
 
+        // Only top-level components are auto-colored; nested ones keep their fill.
+        let fill_types = self.autocolor_fill_types.take();
+        let saved_parent_size = mem::replace(&mut self.parent_size, shape_def.min_size());
         let block = match content {
-            parser_types::ComponentContent::None => Block::None,
+            parser_types::ComponentContent::None => Ok(Block::None),
             parser_types::ComponentContent::Scope(elements) => {
-                self.build_block_from_elements(elements, diagram_kind)?
-            }
-            parser_types::ComponentContent::Diagram(source) => {
-                Block::Diagram(self.build_diagram_from_diagram_source(source)?)
+                self.build_block_from_elements(elements, diagram_kind)
             }
+            parser_types::ComponentContent::Diagram(source) => self
+                .build_diagram_from_diagram_source(source)
+                .map(Block::Diagram),
         };
+        self.autocolor_fill_types = fill_types;
+        self.parent_size = saved_parent_size;
+        let block = block?;
 
         let node = Node::new(
             *name.inner(),
             display_name.as_ref().map(|n| n.to_string()),
             block,
-            Rc::clone(shape_def),
-        );
+            shape_def,
+        )
+        .with_pin(pin);
 
         Ok(Element::Node(node))
     }
 
+    /// Splits the `x` and `y` position attributes off a component's type spec.
+    ///
+    /// A position belongs to the component instance rather than to its shape
+    /// type, so it is read here and the remaining attributes build the type.
+    ///
+    /// # Errors
+    ///
+    /// Returns `E302` for a non-numeric coordinate, or `E304` if a position is
+    /// given outside a component diagram.
+    fn split_pin_attributes<'b>(
+        type_spec: &parser_types::TypeSpec<'b>,
+        diagram_kind: DiagramKind,
+    ) -> Result<(Pin, parser_types::TypeSpec<'b>)> {
+        let mut x = None;
+        let mut y = None;
+        let mut attributes = Vec::with_capacity(type_spec.attributes.len());
+
+        for attr in &type_spec.attributes {
+            let axis = match *attr.name.inner() {
+                "x" => &mut x,
+                "y" => &mut y,
+                _ => {
+                    attributes.push(attr.clone());
+                    continue;
+                }
+            };
+            if diagram_kind != DiagramKind::Component {
+                return Err(Diagnostic::error(format!(
+                    "attribute `{}` is only supported in component diagrams",
+                    attr.name
+                ))
+                .with_code(ErrorCode::E304)
+                .with_label(attr.span(), "unsupported attribute")
+                .with_help("participants of a sequence diagram are positioned by message order"));
+            }
+            let val = Self::extract_positive_float(attr, attr.name.inner())?;
+            if val < 0.0 {
+                return Err(Diagnostic::error(format!(
+                    "position `{}` cannot be negative",
+                    attr.name
+                ))
+                .with_code(ErrorCode::E302)
+                .with_label(attr.span(), format!("invalid {} value", attr.name))
+                .with_help(format!("{} must be a positive number", attr.name)));
+            }
+            *axis = Some(val);
+        }
+
+        let type_spec = parser_types::TypeSpec {
+            type_name: type_spec.type_name,
+            attributes,
+        };
+        Ok((Pin::new(x, y), type_spec))
+    }
+
     /// Builds a relation element from parser data.
     ///
     /// Resolves the arrow type definition, parses the arrow direction string
@@ -570,11 +1502,20 @@ impl Builder {
                 .with_help("arrow direction must be `->`, `<-`, `<->`, or `-`")
         })?;
 
+        let mut label = label.as_ref().map(|l| l.to_string());
+        if let Some(number) = self.next_message_number.as_mut() {
+            label = Some(match label.filter(|label| !label.is_empty()) {
+                Some(label) => format!("{number}. {label}"),
+                None => format!("{number}."),
+            });
+            *number += 1;
+        }
+
         Ok(Element::Relation(Relation::new(
             *source.inner(),
             *target.inner(),
             arrow_direction,
-            label.as_ref().map(|l| l.to_string()),
+            label,
             Rc::clone(arrow_def),
         )))
     }
@@ -670,44 +1611,140 @@ impl Builder {
                 "fragment blocks are only supported in sequence diagrams",
             )
             .with_code(ErrorCode::E304)
-            .with_label(fragment.span(), "fragment not allowed here")
-            .with_help("fragment blocks are used for grouping in sequence diagrams"));
+            .with_label(fragment.span(), "fragment not allowed here")
+            .with_help("fragment blocks are used for grouping in sequence diagrams"));
+        }
+
+        // Build the type definition for this fragment
+        let type_def = self
+            .build_type_definition(&fragment.type_spec)
+            .map_err(|_| {
+                Diagnostic::error(format!(
+                    "invalid fragment type for operation `{}`",
+                    fragment.operation.inner()
+                ))
+                .with_code(ErrorCode::E300)
+                .with_label(fragment.operation.span(), "invalid fragment type")
+                .with_help("fragment types must be defined in the type system")
+            })?;
+
+        let fragment_def = type_def.fragment_definition().map_err(|err| {
+            Diagnostic::error(err)
+                .with_code(ErrorCode::E307)
+                .with_label(fragment.type_spec.span(), "invalid fragment type")
+        })?;
+
+        let mut sections = Vec::new();
+        for parser_section in &fragment.sections {
+            let scope = self.build_scope_from_elements(&parser_section.elements, diagram_kind)?;
+            let elements_vec = scope.elements().to_vec();
+
+            sections.push(FragmentSection::new(
+                parser_section.title.as_ref().map(|t| t.inner().to_string()),
+                elements_vec,
+            ));
+        }
+
+        Ok(Element::Fragment(Fragment::new(
+            fragment.operation.inner().to_string(),
+            sections,
+            Rc::clone(fragment_def),
+        )))
+    }
+
+    /// Builds a highlight element from parser data.
+    ///
+    /// Validates that highlight bands are only used in sequence diagrams,
+    /// resolves the highlight type definition, and elaborates the enclosed
+    /// elements.
+    ///
+    /// # Errors
+    ///
+    /// Returns `E304` if the diagram is not a sequence diagram, `E300` if the
+    /// highlight type is invalid, or `E307` if the type is not a highlight definition.
+    fn build_highlight_element(
+        &mut self,
+        highlight: &parser_types::Highlight,
+        diagram_kind: DiagramKind,
+    ) -> Result<Element> {
+        if diagram_kind != DiagramKind::Sequence {
+            return Err(Diagnostic::error(
+                "highlight blocks are only supported in sequence diagrams",
+            )
+            .with_code(ErrorCode::E304)
+            .with_label(highlight.span(), "highlight not allowed here")
+            .with_help("highlight blocks mark a time window across all lifelines"));
         }
 
-        // Build the type definition for this fragment
         let type_def = self
-            .build_type_definition(&fragment.type_spec)
+            .build_type_definition(&highlight.type_spec)
             .map_err(|_| {
                 Diagnostic::error(format!(
-                    "invalid fragment type for operation `{}`",
-                    fragment.operation.inner()
+                    "invalid highlight type for label `{}`",
+                    highlight.label.inner()
                 ))
                 .with_code(ErrorCode::E300)
-                .with_label(fragment.operation.span(), "invalid fragment type")
-                .with_help("fragment types must be defined in the type system")
+                .with_label(highlight.label.span(), "invalid highlight type")
+                .with_help("highlight types must be defined in the type system")
             })?;
 
-        let fragment_def = type_def.fragment_definition().map_err(|err| {
+        let highlight_def = type_def.highlight_definition().map_err(|err| {
             Diagnostic::error(err)
                 .with_code(ErrorCode::E307)
-                .with_label(fragment.type_spec.span(), "invalid fragment type")
+                .with_label(highlight.type_spec.span(), "invalid highlight type")
         })?;
 
-        let mut sections = Vec::new();
-        for parser_section in &fragment.sections {
-            let scope = self.build_scope_from_elements(&parser_section.elements, diagram_kind)?;
-            let elements_vec = scope.elements().to_vec();
+        let scope = self.build_scope_from_elements(&highlight.elements, diagram_kind)?;
 
-            sections.push(FragmentSection::new(
-                parser_section.title.as_ref().map(|t| t.inner().to_string()),
-                elements_vec,
-            ));
+        Ok(Element::Highlight(Highlight::new(
+            highlight.label.inner().to_string(),
+            scope.elements().to_vec(),
+            Rc::clone(highlight_def),
+        )))
+    }
+
+    /// Builds a group element from parser data.
+    ///
+    /// # Errors
+    ///
+    /// Returns `E304` if the diagram is not a component diagram, `E300` if the
+    /// group type is invalid, or `E307` if the type is not a group definition.
+    fn build_group_element(
+        &mut self,
+        group: &parser_types::Group,
+        diagram_kind: DiagramKind,
+    ) -> Result<Element> {
+        if diagram_kind != DiagramKind::Component {
+            return Err(
+                Diagnostic::error("group blocks are only supported in component diagrams")
+                    .with_code(ErrorCode::E304)
+                    .with_label(group.span(), "group not allowed here")
+                    .with_help("group blocks draw a boundary around components laid out together"),
+            );
         }
 
-        Ok(Element::Fragment(Fragment::new(
-            fragment.operation.inner().to_string(),
-            sections,
-            Rc::clone(fragment_def),
+        let type_def = self.build_type_definition(&group.type_spec).map_err(|_| {
+            Diagnostic::error(format!(
+                "invalid group type for label `{}`",
+                group.label.inner()
+            ))
+            .with_code(ErrorCode::E300)
+            .with_label(group.label.span(), "invalid group type")
+            .with_help("group types must be defined in the type system")
+        })?;
+
+        let group_def = type_def.group_definition().map_err(|err| {
+            Diagnostic::error(err)
+                .with_code(ErrorCode::E307)
+                .with_label(group.type_spec.span(), "invalid group type")
+        })?;
+
+        let scope = self.build_scope_from_elements(&group.elements, diagram_kind)?;
+
+        Ok(Element::Group(Group::new(
+            group.label.inner().to_string(),
+            scope.elements().to_vec(),
+            Rc::clone(group_def),
         )))
     }
 
@@ -734,7 +1771,7 @@ impl Builder {
         }
 
         // Otherwise, create a new anonymous type based on the base type
-        let id = Id::from_anonymous();
+        let id = self.next_anonymous_type_id();
         let new_type = self.build_type_from_base(id, base, attributes)?;
         Ok(self.insert_type_definition(new_type))
     }
@@ -780,7 +1817,12 @@ impl Builder {
         // Step 2: If attributes exist, make mutable and apply them
         if !type_spec.attributes.is_empty() {
             let text_def_mut = Rc::make_mut(&mut text_rc);
-            TextAttributeExtractor::extract_text_attributes(text_def_mut, &type_spec.attributes)?;
+            TextAttributeExtractor::extract_text_attributes(
+                text_def_mut,
+                &type_spec.attributes,
+                &self.cfg.palette,
+                self.parent_size.width(),
+            )?;
         }
 
         Ok(text_rc)
@@ -830,6 +1872,7 @@ impl Builder {
             StrokeAttributeExtractor::extract_stroke_attributes(
                 stroke_def_mut,
                 &type_spec.attributes,
+                &self.cfg.palette,
             )?;
         }
 
@@ -931,34 +1974,138 @@ impl Builder {
 
                     match *name {
                         "fill_color" => {
-                            let color = Self::extract_color(attr, "fill_color")?;
+                            let color = Self::extract_color(attr, "fill_color", &self.cfg.palette)?;
                             shape_def_mut.set_fill_color(Some(color)).map_err(|err| {
                                 Diagnostic::error(err.to_string())
                                     .with_code(ErrorCode::E304)
                                     .with_label(attr.span(), "unsupported attribute")
                             })?;
                         }
+                        "fill_opacity" => {
+                            let val = Self::extract_opacity(attr, "fill_opacity")?;
+                            shape_def_mut.set_fill_opacity(val).map_err(|err| {
+                                Diagnostic::error(err.to_string())
+                                    .with_code(ErrorCode::E304)
+                                    .with_label(attr.span(), "unsupported attribute")
+                            })?;
+                        }
                         "stroke" => {
                             let type_spec = Self::extract_type_spec(attr, "stroke")?;
                             let stroke_rc = self
                                 .resolve_stroke_type_reference(type_spec, shape_def_mut.stroke())?;
                             shape_def_mut.set_stroke(stroke_rc);
                         }
+                        "stroke_opacity" => {
+                            let val = Self::extract_opacity(attr, "stroke_opacity")?;
+                            let mut stroke_rc = Rc::clone(shape_def_mut.stroke());
+                            Rc::make_mut(&mut stroke_rc).set_opacity(val);
+                            shape_def_mut.set_stroke(stroke_rc);
+                        }
                         "rounded" => {
                             let val =
                                 Self::extract_usize(attr, "rounded", "must be a positive number")?;
+                            shape_def_mut.set_rounded(val as f32).map_err(|err| {
+                                Diagnostic::error(err.to_string())
+                                    .with_code(ErrorCode::E304)
+                                    .with_label(attr.span(), "unsupported attribute")
+                            })?;
+                        }
+                        "corner_radius" => {
+                            let val = Self::extract_positive_float(attr, "corner_radius")?;
                             shape_def_mut.set_rounded(val).map_err(|err| {
                                 Diagnostic::error(err.to_string())
                                     .with_code(ErrorCode::E304)
                                     .with_label(attr.span(), "unsupported attribute")
                             })?;
                         }
+                        "min_width" => {
+                            let val = Self::extract_positive_length(
+                                attr,
+                                "min_width",
+                                self.parent_size.width(),
+                            )?;
+                            let min_size = Size::new(val, shape_def_mut.min_size().height());
+                            shape_def_mut.set_min_size(min_size).map_err(|err| {
+                                Diagnostic::error(err.to_string())
+                                    .with_code(ErrorCode::E304)
+                                    .with_label(attr.span(), "unsupported attribute")
+                            })?;
+                        }
+                        "min_height" => {
+                            let val = Self::extract_positive_length(
+                                attr,
+                                "min_height",
+                                self.parent_size.height(),
+                            )?;
+                            let min_size = Size::new(shape_def_mut.min_size().width(), val);
+                            shape_def_mut.set_min_size(min_size).map_err(|err| {
+                                Diagnostic::error(err.to_string())
+                                    .with_code(ErrorCode::E304)
+                                    .with_label(attr.span(), "unsupported attribute")
+                            })?;
+                        }
+                        "content_align" => {
+                            let align_str = Self::extract_string(attr, "content_align")?;
+                            let align = align_str.parse::<ContentAlign>().map_err(|_| {
+                                Diagnostic::error(format!(
+                                    "invalid content alignment: `{align_str}`"
+                                ))
+                                .with_code(ErrorCode::E302)
+                                .with_label(attr.value.span(), "invalid alignment")
+                                .with_help(
+                                    "valid values: center, top, bottom, left, right, top-left, top-right, bottom-left, bottom-right",
+                                )
+                            })?;
+                            shape_def_mut.set_content_align(align).map_err(|err| {
+                                Diagnostic::error(err.to_string())
+                                    .with_code(ErrorCode::E304)
+                                    .with_label(attr.span(), "unsupported attribute")
+                            })?;
+                        }
                         "text" => {
                             let type_spec = Self::extract_type_spec(attr, "text")?;
                             let text_rc =
                                 self.resolve_text_type_reference(type_spec, shape_def_mut.text())?;
                             shape_def_mut.set_text(text_rc);
                         }
+                        "icon" => {
+                            let image = Self::extract_image(attr, shape_def_mut.image())?;
+                            shape_def_mut.set_image(Some(image)).map_err(|err| {
+                                Diagnostic::error(err.to_string())
+                                    .with_code(ErrorCode::E304)
+                                    .with_label(attr.span(), "unsupported attribute")
+                            })?;
+                        }
+                        "shadow" => {
+                            let shadow = Self::extract_shadow(
+                                attr,
+                                shape_def_mut.shadow(),
+                                &self.cfg.palette,
+                            )?;
+                            shape_def_mut.set_shadow(shadow).map_err(|err| {
+                                Diagnostic::error(err.to_string())
+                                    .with_code(ErrorCode::E304)
+                                    .with_label(attr.span(), "unsupported attribute")
+                            })?;
+                        }
+                        "tooltip" => {
+                            let tooltip = Self::extract_string(attr, "tooltip")?;
+                            shape_def_mut
+                                .set_tooltip(Some(tooltip.to_string()))
+                                .map_err(|err| {
+                                    Diagnostic::error(err.to_string())
+                                        .with_code(ErrorCode::E304)
+                                        .with_label(attr.span(), "unsupported attribute")
+                                })?;
+                        }
+                        "href" | "target" => {
+                            let link = Self::extract_link(attr, shape_def_mut.link())?;
+                            shape_def_mut.set_link(Some(link)).map_err(|err| {
+                                Diagnostic::error(err.to_string())
+                                    .with_code(ErrorCode::E304)
+                                    .with_label(attr.span(), "unsupported attribute")
+                            })?;
+                        }
                         name => {
                             return Err(Diagnostic::error(format!(
                                 "unknown shape attribute `{name}`"
@@ -966,11 +2113,12 @@ impl Builder {
                             .with_code(ErrorCode::E303)
                             .with_label(attr.span(), "unknown attribute")
                             .with_help(
-                                "valid shape attributes are: `fill_color`, `stroke`=[...], `rounded`, `text`=[...]",
+                                "valid shape attributes are: `fill_color`, `fill_opacity`, `stroke`=[...], `stroke_opacity`, `rounded`, `corner_radius`, `min_width`, `min_height`, `content_align`, `shadow`, `icon`, `tooltip`, `href`, `target`, `text`=[...]",
                             ));
                         }
                     }
                 }
+                Self::check_link_target(attributes, shape_def_mut.link())?;
 
                 Ok(elaborate_utils::TypeDefinition::new_shape(
                     id,
@@ -1009,6 +2157,55 @@ impl Builder {
                                 self.resolve_text_type_reference(type_spec, arrow_def_mut.text())?;
                             arrow_def_mut.set_text(text_rc);
                         }
+                        "segments" => {
+                            let segments_str = Self::extract_string(attr, "segments")?;
+                            arrow_def_mut
+                                .set_segments(Self::parse_arrow_segments(segments_str, attr)?);
+                        }
+                        "arrowhead" => {
+                            arrow_def_mut.set_arrowhead(Self::parse_arrowhead(attr, "arrowhead")?);
+                        }
+                        "arrowhead_start" => {
+                            arrow_def_mut.set_arrowhead_start(Self::parse_arrowhead(
+                                attr,
+                                "arrowhead_start",
+                            )?);
+                        }
+                        "arrowhead_end" => {
+                            arrow_def_mut
+                                .set_arrowhead_end(Self::parse_arrowhead(attr, "arrowhead_end")?);
+                        }
+                        "label_pos" => {
+                            let position_str = Self::extract_string(attr, "label_pos")?;
+                            let val = LabelPosition::from_str(position_str).map_err(|_| {
+                                Diagnostic::error("invalid label position")
+                                    .with_code(ErrorCode::E302)
+                                    .with_label(attr.span(), "invalid label_pos")
+                                    .with_help("label_pos must be `start`, `middle`, or `end`")
+                            })?;
+                            arrow_def_mut.set_label_position(val);
+                        }
+                        "label_offset" => {
+                            arrow_def_mut.set_label_offset(Self::extract_positive_float(
+                                attr,
+                                "label_offset",
+                            )?);
+                        }
+                        "curvature" => {
+                            arrow_def_mut
+                                .set_curvature(Self::extract_positive_float(attr, "curvature")?);
+                        }
+                        "min_length" => {
+                            arrow_def_mut.set_min_length(Self::extract_min_length(attr)?);
+                        }
+                        "tooltip" => {
+                            let tooltip = Self::extract_string(attr, "tooltip")?;
+                            arrow_def_mut.set_tooltip(Some(tooltip.to_string()));
+                        }
+                        "href" | "target" => {
+                            let link = Self::extract_link(attr, arrow_def_mut.link())?;
+                            arrow_def_mut.set_link(Some(link));
+                        }
                         name => {
                             return Err(Diagnostic::error(format!(
                                 "unknown arrow attribute `{name}`"
@@ -1016,11 +2213,12 @@ impl Builder {
                             .with_code(ErrorCode::E303)
                             .with_label(attr.span(), "unknown attribute")
                             .with_help(
-                                "valid arrow attributes are: `stroke`=[...], `style`, `text`=[...]",
+                                "valid arrow attributes are: `stroke`=[...], `style`, `text`=[...], `segments`, `arrowhead`, `arrowhead_start`, `arrowhead_end`, `label_pos`, `label_offset`, `curvature`, `min_length`, `tooltip`, `href`, `target`",
                             ));
                         }
                     }
                 }
+                Self::check_link_target(attributes, arrow_def_mut.link())?;
 
                 Ok(elaborate_utils::TypeDefinition::new_arrow(
                     id,
@@ -1044,7 +2242,8 @@ impl Builder {
                             fragment_def_mut.set_border_stroke(stroke_rc);
                         }
                         "background_color" => {
-                            let color = Self::extract_color(attr, "background_color")?;
+                            let color =
+                                Self::extract_color(attr, "background_color", &self.cfg.palette)?;
                             fragment_def_mut.set_background_color(Some(color));
                         }
                         "separator_stroke" => {
@@ -1096,7 +2295,8 @@ impl Builder {
 
                     match *name {
                         "background_color" => {
-                            let color = Self::extract_color(attr, "background_color")?;
+                            let color =
+                                Self::extract_color(attr, "background_color", &self.cfg.palette)?;
                             note_def_mut.set_background_color(Some(color));
                         }
                         "stroke" => {
@@ -1130,6 +2330,89 @@ impl Builder {
 
                 Ok(elaborate_utils::TypeDefinition::new_note(id, new_note_def))
             }
+            elaborate_utils::DrawDefinition::Highlight(highlight_def) => {
+                let mut new_highlight_def = Rc::clone(highlight_def);
+                let highlight_def_mut = Rc::make_mut(&mut new_highlight_def);
+
+                for attr in attributes {
+                    let name = attr.name.inner();
+
+                    match *name {
+                        "fill_color" => {
+                            let color = Self::extract_color(attr, "fill_color", &self.cfg.palette)?;
+                            highlight_def_mut.set_fill_color(color);
+                        }
+                        "text" => {
+                            let type_spec = Self::extract_type_spec(attr, "text")?;
+                            let text_rc = self
+                                .resolve_text_type_reference(type_spec, highlight_def_mut.text())?;
+                            highlight_def_mut.set_text(text_rc);
+                        }
+                        name => {
+                            return Err(Diagnostic::error(format!(
+                                "unknown highlight attribute `{name}`"
+                            ))
+                            .with_code(ErrorCode::E303)
+                            .with_label(attr.span(), "unknown attribute")
+                            .with_help(
+                                "valid highlight attributes are: `fill_color`, `text`=[...]",
+                            ));
+                        }
+                    }
+                }
+
+                Ok(elaborate_utils::TypeDefinition::new_highlight(
+                    id,
+                    new_highlight_def,
+                ))
+            }
+            elaborate_utils::DrawDefinition::Group(group_def) => {
+                let mut new_group_def = Rc::clone(group_def);
+                let group_def_mut = Rc::make_mut(&mut new_group_def);
+
+                for attr in attributes {
+                    let name = attr.name.inner();
+
+                    match *name {
+                        "stroke" => {
+                            let type_spec = Self::extract_type_spec(attr, "stroke")?;
+                            let stroke_rc = self
+                                .resolve_stroke_type_reference(type_spec, group_def_mut.stroke())?;
+                            group_def_mut.set_stroke(stroke_rc);
+                        }
+                        "text" => {
+                            let type_spec = Self::extract_type_spec(attr, "text")?;
+                            let text_rc =
+                                self.resolve_text_type_reference(type_spec, group_def_mut.text())?;
+                            group_def_mut.set_text(text_rc);
+                        }
+                        "tab_fill_color" => {
+                            let color =
+                                Self::extract_color(attr, "tab_fill_color", &self.cfg.palette)?;
+                            group_def_mut.set_tab_fill_color(color);
+                        }
+                        "padding" => {
+                            let padding = Self::extract_insets(attr, "padding")?;
+                            group_def_mut.set_padding(padding);
+                        }
+                        name => {
+                            return Err(Diagnostic::error(format!(
+                                "unknown group attribute `{name}`"
+                            ))
+                            .with_code(ErrorCode::E303)
+                            .with_label(attr.span(), "unknown attribute")
+                            .with_help(
+                                "valid group attributes are: `stroke`=[...], `text`=[...], `tab_fill_color`, `padding`",
+                            ));
+                        }
+                    }
+                }
+
+                Ok(elaborate_utils::TypeDefinition::new_group(
+                    id,
+                    new_group_def,
+                ))
+            }
             elaborate_utils::DrawDefinition::ActivationBox(activation_box_def) => {
                 let mut new_activation_box_def = Rc::clone(activation_box_def);
                 let activation_box_def_mut = Rc::make_mut(&mut new_activation_box_def);
@@ -1139,15 +2422,23 @@ impl Builder {
 
                     match *name {
                         "width" => {
-                            let val = Self::extract_positive_float(attr, "width")?;
+                            let val = Self::extract_positive_length(
+                                attr,
+                                "width",
+                                self.parent_size.width(),
+                            )?;
                             activation_box_def_mut.set_width(val);
                         }
                         "nesting_offset" => {
-                            let val = Self::extract_positive_float(attr, "nesting_offset")?;
+                            let val = Self::extract_positive_length(
+                                attr,
+                                "nesting_offset",
+                                self.parent_size.width(),
+                            )?;
                             activation_box_def_mut.set_nesting_offset(val);
                         }
                         "fill_color" => {
-                            let color = Self::extract_color(attr, "fill_color")?;
+                            let color = Self::extract_color(attr, "fill_color", &self.cfg.palette)?;
                             activation_box_def_mut.set_fill_color(color);
                         }
                         "stroke" => {
@@ -1191,10 +2482,33 @@ impl Builder {
                     let name = attr.name.inner();
 
                     match *name {
-                        "canvas_color" => {
-                            let color = Self::extract_color(attr, "canvas_color")?;
+                        "canvas_color" | "background_color" => {
+                            let color = Self::extract_color(attr, name, &self.cfg.palette)?;
                             diagram_def_mut.set_canvas_color(Some(color));
                         }
+                        "canvas_padding" => {
+                            let padding = Self::extract_insets(attr, "canvas_padding")?;
+                            diagram_def_mut.set_canvas_padding(Some(padding));
+                        }
+                        "width" => {
+                            let width = Self::extract_positive_float(attr, "width")?;
+                            diagram_def_mut.set_width(Some(width));
+                        }
+                        "height" => {
+                            let height = Self::extract_positive_float(attr, "height")?;
+                            diagram_def_mut.set_height(Some(height));
+                        }
+                        "fit" => {
+                            diagram_def_mut.set_fit(Self::parse_canvas_fit(attr)?);
+                        }
+                        "title" => {
+                            let title = Self::extract_string(attr, "title")?;
+                            diagram_def_mut.set_title(Some(title.to_string()));
+                        }
+                        "caption" => {
+                            let caption = Self::extract_string(attr, "caption")?;
+                            diagram_def_mut.set_caption(Some(caption.to_string()));
+                        }
                         "lifeline" => {
                             let type_spec = Self::extract_type_spec(attr, "lifeline")?;
                             let lifeline_rc = self.resolve_lifeline_type_reference(
@@ -1203,6 +2517,26 @@ impl Builder {
                             )?;
                             diagram_def_mut.set_lifeline(lifeline_rc);
                         }
+                        "rank_spacing" => {
+                            let spacing = Self::extract_positive_float(attr, "rank_spacing")?;
+                            diagram_def_mut.set_rank_spacing(Some(spacing));
+                        }
+                        "intra_rank_spacing" => {
+                            let spacing = Self::extract_positive_float(attr, "intra_rank_spacing")?;
+                            diagram_def_mut.set_intra_rank_spacing(Some(spacing));
+                        }
+                        "edge_routing" => {
+                            diagram_def_mut.set_edge_routing(Self::parse_edge_routing(attr)?);
+                        }
+                        "columns" => {
+                            diagram_def_mut.set_columns(Some(Self::extract_columns(attr)?));
+                        }
+                        "density" => {
+                            diagram_def_mut.set_density(Some(Self::parse_density(attr)?));
+                        }
+                        "autocolor" => {
+                            diagram_def_mut.set_autocolor(Self::extract_bool(attr, "autocolor")?);
+                        }
                         name => {
                             return Err(Diagnostic::error(format!(
                                 "unknown diagram attribute `{name}`"
@@ -1210,7 +2544,7 @@ impl Builder {
                             .with_code(ErrorCode::E303)
                             .with_label(attr.span(), "unknown attribute")
                             .with_help(
-                                "valid diagram attributes are: `canvas_color`, `lifeline`=[...]",
+                                "valid diagram attributes are: `canvas_color`, `background_color`, `canvas_padding`, `width`, `height`, `fit`, `title`, `caption`, `lifeline`=[...], `rank_spacing`, `intra_rank_spacing`, `edge_routing`, `columns`, `density`, `autocolor`",
                             ));
                         }
                     }
@@ -1223,12 +2557,21 @@ impl Builder {
             }
             elaborate_utils::DrawDefinition::Stroke(stroke_def) => {
                 let mut new_stroke = (**stroke_def).clone();
-                StrokeAttributeExtractor::extract_stroke_attributes(&mut new_stroke, attributes)?;
+                StrokeAttributeExtractor::extract_stroke_attributes(
+                    &mut new_stroke,
+                    attributes,
+                    &self.cfg.palette,
+                )?;
                 Ok(elaborate_utils::TypeDefinition::new_stroke(id, new_stroke))
             }
             elaborate_utils::DrawDefinition::Text(text_def) => {
                 let mut new_text_def = (**text_def).clone();
-                TextAttributeExtractor::extract_text_attributes(&mut new_text_def, attributes)?;
+                TextAttributeExtractor::extract_text_attributes(
+                    &mut new_text_def,
+                    attributes,
+                    &self.cfg.palette,
+                    self.parent_size.width(),
+                )?;
                 Ok(elaborate_utils::TypeDefinition::new_text(id, new_text_def))
             }
         }
@@ -1284,16 +2627,77 @@ impl Builder {
                 "layout_engine" => {
                     layout_engine = Self::determine_layout_engine(attr)?;
                 }
-                "canvas_color" => {
-                    let color = Self::extract_color(attr, "canvas_color")?;
+                name @ ("canvas_color" | "background_color") => {
+                    let color = Self::extract_color(attr, name, &self.cfg.palette)?;
                     definition.set_canvas_color(Some(color));
                 }
+                "canvas_padding" => {
+                    let padding = Self::extract_insets(attr, "canvas_padding")?;
+                    definition.set_canvas_padding(Some(padding));
+                }
+                "width" => {
+                    let width = Self::extract_positive_float(attr, "width")?;
+                    definition.set_width(Some(width));
+                }
+                "height" => {
+                    let height = Self::extract_positive_float(attr, "height")?;
+                    definition.set_height(Some(height));
+                }
+                "fit" => {
+                    definition.set_fit(Self::parse_canvas_fit(attr)?);
+                }
+                "title" => {
+                    let title = Self::extract_string(attr, "title")?;
+                    definition.set_title(Some(title.to_string()));
+                }
+                "caption" => {
+                    let caption = Self::extract_string(attr, "caption")?;
+                    definition.set_caption(Some(caption.to_string()));
+                }
                 "lifeline" => {
                     let type_spec = Self::extract_type_spec(attr, "lifeline")?;
                     let lifeline =
                         self.resolve_lifeline_type_reference(type_spec, definition.lifeline())?;
                     definition.set_lifeline(lifeline);
                 }
+                "rank_spacing" => {
+                    let spacing = Self::extract_positive_float(attr, "rank_spacing")?;
+                    definition.set_rank_spacing(Some(spacing));
+                }
+                "intra_rank_spacing" => {
+                    let spacing = Self::extract_positive_float(attr, "intra_rank_spacing")?;
+                    definition.set_intra_rank_spacing(Some(spacing));
+                }
+                "edge_routing" => {
+                    if kind != DiagramKind::Component {
+                        return Err(Diagnostic::error(format!(
+                            "`edge_routing` is not supported in {kind} diagrams"
+                        ))
+                        .with_code(ErrorCode::E304)
+                        .with_label(attr.span(), "unsupported attribute")
+                        .with_help("`edge_routing` routes the relations of component diagrams"));
+                    }
+                    definition.set_edge_routing(Self::parse_edge_routing(attr)?);
+                }
+                "columns" => {
+                    if kind != DiagramKind::Component {
+                        return Err(Diagnostic::error(format!(
+                            "`columns` is not supported in {kind} diagrams"
+                        ))
+                        .with_code(ErrorCode::E304)
+                        .with_label(attr.span(), "unsupported attribute")
+                        .with_help("`columns` sets the grid layout of component diagrams"));
+                    }
+                    definition.set_columns(Some(Self::extract_columns(attr)?));
+                }
+                "density" => {
+                    definition.set_density(Some(Self::parse_density(attr)?));
+                }
+                "autocolor" => {
+                    definition.set_autocolor(Self::extract_bool(attr, "autocolor")?);
+                }
+                // Applied to the elements by `build_diagram_from_file_ast`.
+                "implicit_nodes" | "autonumber" => {}
                 _ => {
                     return Err(Diagnostic::error(format!(
                         "unsupported diagram attribute `{}`",
@@ -1308,14 +2712,57 @@ impl Builder {
         Ok((layout_engine, diagram_definition))
     }
 
+    /// Extracts the number of the first message from an `autonumber`
+    /// attribute, or `None` when numbering is turned off.
+    ///
+    /// Accepts `"true"` (start at 1), `"false"`, or a start number such as
+    /// `"10"`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `E302` for any other value, or `E304` if `kind` is not a
+    /// sequence diagram.
+    fn extract_autonumber(
+        attr: &parser_types::Attribute<'_>,
+        kind: DiagramKind,
+    ) -> Result<Option<usize>> {
+        if kind != DiagramKind::Sequence {
+            return Err(Diagnostic::error(format!(
+                "`autonumber` is not supported in {kind} diagrams"
+            ))
+            .with_code(ErrorCode::E304)
+            .with_label(attr.span(), "unsupported attribute")
+            .with_help("`autonumber` numbers the messages of sequence diagrams"));
+        }
+
+        let value = Self::extract_string(attr, "autonumber")?;
+        match value {
+            "true" => Ok(Some(1)),
+            "false" => Ok(None),
+            _ => value.parse().map(Some).map_err(|_| {
+                Diagnostic::error(format!("invalid autonumber value `{value}`"))
+                    .with_code(ErrorCode::E302)
+                    .with_label(attr.span(), "invalid autonumber value")
+                    .with_help(
+                        "autonumber must be \"true\", \"false\", or a start number such as \"10\"",
+                    )
+            }),
+        }
+    }
+
     /// Determines the layout engine from an attribute.
     fn determine_layout_engine(engine_attr: &parser_types::Attribute<'_>) -> Result<LayoutEngine> {
         let engine_str = Self::extract_string(engine_attr, "layout_engine")?;
         LayoutEngine::from_str(engine_str).map_err(|_| {
+            let supported = LayoutEngine::ALL
+                .iter()
+                .map(|engine| format!("`{engine}`"))
+                .collect::<Vec<_>>()
+                .join(", ");
             Diagnostic::error(format!("invalid `layout_engine` value: `{engine_str}`"))
                 .with_code(ErrorCode::E302)
                 .with_label(engine_attr.value.span(), "unsupported layout engine")
-                .with_help("supported layout engines are: `basic`, `sugiyama`")
+                .with_help(format!("supported layout engines are: {supported}"))
         })
     }
 
@@ -1425,13 +2872,15 @@ impl Builder {
 mod tests {
     use std::cell::RefCell;
 
+    use orrery_core::draw::StrokeStyle;
+
     use super::*;
     use crate::span::Span;
 
     /// Creates a [`Builder`] pre-populated with the built-in type definitions.
     fn builder_with_builtins() -> Builder {
         let mut builder = Builder::new(ElaborateConfig::default());
-        builder.type_definitions = Builder::builtin_type_definitions_map();
+        builder.type_definitions = builder.builtin_type_definitions_map();
         builder
     }
 
@@ -1454,6 +2903,8 @@ mod tests {
             type_definitions: vec![],
             elements,
             imports: vec![],
+            variable_definitions: vec![],
+            style_definitions: vec![],
         };
 
         let config = ElaborateConfig::default();
@@ -1482,6 +2933,7 @@ mod tests {
                     attributes: vec![],
                 },
                 label: Some(Spanned::new("Request".to_string(), Span::new(0..7))),
+                comments: Vec::new(),
             },
             parser_types::Element::Relation {
                 source: Spanned::new(Id::new("server"), Span::new(0..6)),
@@ -1492,6 +2944,7 @@ mod tests {
                     attributes: vec![],
                 },
                 label: Some(Spanned::new("Query".to_string(), Span::new(0..5))),
+                comments: Vec::new(),
             },
             parser_types::Element::Deactivate {
                 component: Spanned::new(Id::new("user"), Span::new(0..4)),
@@ -1507,6 +2960,8 @@ mod tests {
             type_definitions: vec![],
             elements,
             imports: vec![],
+            variable_definitions: vec![],
+            style_definitions: vec![],
         };
 
         let config = ElaborateConfig::default();
@@ -1562,6 +3017,7 @@ mod tests {
                     "Initial request".to_string(),
                     Span::new(0..16),
                 )),
+                comments: Vec::new(),
             },
             parser_types::Element::Activate {
                 component: Spanned::new(Id::new("user"), Span::new(0..4)),
@@ -1579,6 +3035,7 @@ mod tests {
                     attributes: vec![],
                 },
                 label: Some(Spanned::new("Direct query".to_string(), Span::new(0..12))),
+                comments: Vec::new(),
             },
             parser_types::Element::Deactivate {
                 component: Spanned::new(Id::new("user"), Span::new(0..4)),
@@ -1599,6 +3056,7 @@ mod tests {
                     attributes: vec![],
                 },
                 label: Some(Spanned::new("Cache lookup".to_string(), Span::new(0..12))),
+                comments: Vec::new(),
             },
             parser_types::Element::Deactivate {
                 component: Spanned::new(Id::new("server"), Span::new(0..6)),
@@ -1617,6 +3075,8 @@ mod tests {
             type_definitions: vec![],
             elements,
             imports: vec![],
+            variable_definitions: vec![],
+            style_definitions: vec![],
         };
 
         let config = ElaborateConfig::default();
@@ -1706,6 +3166,7 @@ mod tests {
                     attributes: vec![],
                 },
                 content: parser_types::ComponentContent::None,
+                comments: Vec::new(),
             },
             // Activate the component
             parser_types::Element::Activate {
@@ -1730,6 +3191,8 @@ mod tests {
             type_definitions: vec![],
             elements,
             imports: vec![],
+            variable_definitions: vec![],
+            style_definitions: vec![],
         };
 
         let result = builder.build(&diagram);
@@ -1787,6 +3250,7 @@ mod tests {
                     attributes: vec![],
                 },
                 content: parser_types::ComponentContent::None,
+                comments: Vec::new(),
             },
             // Try to activate the component (should fail)
             parser_types::Element::Activate {
@@ -1804,6 +3268,8 @@ mod tests {
             type_definitions: vec![],
             elements,
             imports: vec![],
+            variable_definitions: vec![],
+            style_definitions: vec![],
         };
 
         let result = builder.build(&diagram);
@@ -1836,6 +3302,7 @@ mod tests {
                     attributes: vec![],
                 },
                 content: parser_types::ComponentContent::None,
+                comments: Vec::new(),
             },
             parser_types::Element::Component {
                 name: Spanned::new(Id::new("server"), Span::new(0..6)),
@@ -1845,6 +3312,7 @@ mod tests {
                     attributes: vec![],
                 },
                 content: parser_types::ComponentContent::None,
+                comments: Vec::new(),
             },
             parser_types::Element::Component {
                 name: Spanned::new(Id::new("database"), Span::new(0..8)),
@@ -1854,6 +3322,7 @@ mod tests {
                     attributes: vec![],
                 },
                 content: parser_types::ComponentContent::None,
+                comments: Vec::new(),
             },
             // activations and relations
             parser_types::Element::Activate {
@@ -1872,6 +3341,7 @@ mod tests {
                     attributes: vec![],
                 },
                 label: Some(Spanned::new("First request".to_string(), Span::new(0..13))),
+                comments: Vec::new(),
             },
             parser_types::Element::Activate {
                 component: Spanned::new(Id::new("server"), Span::new(0..6)),
@@ -1889,6 +3359,7 @@ mod tests {
                     attributes: vec![],
                 },
                 label: Some(Spanned::new("Nested query".to_string(), Span::new(0..12))),
+                comments: Vec::new(),
             },
             parser_types::Element::Relation {
                 source: Spanned::new(Id::new("database"), Span::new(0..8)),
@@ -1902,6 +3373,7 @@ mod tests {
                     "Nested response".to_string(),
                     Span::new(0..15),
                 )),
+                comments: Vec::new(),
             },
             parser_types::Element::Deactivate {
                 component: Spanned::new(Id::new("server"), Span::new(0..6)),
@@ -1915,6 +3387,7 @@ mod tests {
                     attributes: vec![],
                 },
                 label: Some(Spanned::new("First response".to_string(), Span::new(0..14))),
+                comments: Vec::new(),
             },
             parser_types::Element::Activate {
                 component: Spanned::new(Id::new("user"), Span::new(0..4)),
@@ -1932,6 +3405,7 @@ mod tests {
                     attributes: vec![],
                 },
                 label: Some(Spanned::new("Second request".to_string(), Span::new(0..14))),
+                comments: Vec::new(),
             },
             parser_types::Element::Deactivate {
                 component: Spanned::new(Id::new("user"), Span::new(0..4)),
@@ -1950,6 +3424,8 @@ mod tests {
             type_definitions: vec![],
             elements,
             imports: vec![],
+            variable_definitions: vec![],
+            style_definitions: vec![],
         };
 
         let config = ElaborateConfig::default();
@@ -2196,7 +3672,7 @@ mod tests {
             value: AttributeValue::String(Spanned::new("red".to_string(), Span::new(11..16))),
         };
 
-        let result = Builder::extract_color(&attr, "fill_color");
+        let result = Builder::extract_color(&attr, "fill_color", &Palette::default());
         assert!(result.is_ok());
     }
 
@@ -2209,7 +3685,7 @@ mod tests {
             value: AttributeValue::Float(Spanned::new(42.0, Span::new(11..13))),
         };
 
-        let result = Builder::extract_color(&attr, "fill_color");
+        let result = Builder::extract_color(&attr, "fill_color", &Palette::default());
         assert!(result.is_err());
         let err = result.unwrap_err();
         assert!(err.to_string().contains("expected string value"));
@@ -2227,7 +3703,7 @@ mod tests {
             )),
         };
 
-        let result = Builder::extract_color(&attr, "fill_color");
+        let result = Builder::extract_color(&attr, "fill_color", &Palette::default());
         assert!(result.is_err());
         let err = result.unwrap_err();
         assert!(err.to_string().contains("invalid fill_color"));
@@ -2297,6 +3773,175 @@ mod tests {
         assert!(err.to_string().contains("expected"));
     }
 
+    #[test]
+    fn test_parse_arrow_segments_success() {
+        use crate::parser_types::{Attribute, AttributeValue};
+
+        let value = "solid:0.3, dashed:0.7";
+        let attr = Attribute {
+            name: Spanned::new("segments", Span::new(0..8)),
+            value: AttributeValue::String(Spanned::new(value.to_string(), Span::new(9..32))),
+        };
+
+        let segments = Builder::parse_arrow_segments(value, &attr).unwrap();
+        assert_eq!(
+            segments,
+            vec![
+                ArrowSegment::new(StrokeStyle::Solid, 0.3),
+                ArrowSegment::new(StrokeStyle::Dashed, 0.7),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_arrow_segments_errors() {
+        use crate::parser_types::{Attribute, AttributeValue};
+
+        let attr = Attribute {
+            name: Spanned::new("segments", Span::new(0..8)),
+            value: AttributeValue::String(Spanned::new(String::new(), Span::new(9..11))),
+        };
+
+        let err = Builder::parse_arrow_segments("solid:0.5, dashed:0.2", &attr).unwrap_err();
+        assert_eq!(err.code(), Some(ErrorCode::E302));
+        assert!(err.message().contains("sum to 0.7"));
+
+        let err = Builder::parse_arrow_segments("solid, dashed:0.5", &attr).unwrap_err();
+        assert_eq!(err.code(), Some(ErrorCode::E302));
+        assert!(err.message().contains("invalid arrow segment"));
+    }
+
+    #[test]
+    fn test_parse_arrowhead() {
+        use crate::parser_types::{Attribute, AttributeValue};
+
+        let attr = |value: &str| Attribute {
+            name: Spanned::new("arrowhead", Span::new(0..9)),
+            value: AttributeValue::String(Spanned::new(value.to_string(), Span::new(10..20))),
+        };
+
+        let arrowhead = Builder::parse_arrowhead(&attr("diamond"), "arrowhead").unwrap();
+        assert_eq!(arrowhead, Arrowhead::Diamond);
+
+        let err = Builder::parse_arrowhead(&attr("square"), "arrowhead").unwrap_err();
+        assert_eq!(err.code(), Some(ErrorCode::E302));
+        assert!(err.message().contains("invalid arrowhead `square`"));
+    }
+
+    #[test]
+    fn test_extract_opacity() {
+        use crate::parser_types::{Attribute, AttributeValue};
+
+        let attr = |value: AttributeValue<'static>| Attribute {
+            name: Spanned::new("fill_opacity", Span::new(0..12)),
+            value,
+        };
+
+        let opacity = Builder::extract_opacity(
+            &attr(AttributeValue::Float(Spanned::new(0.25, Span::new(13..17)))),
+            "fill_opacity",
+        )
+        .unwrap();
+        assert_eq!(opacity, 0.25);
+
+        let percent = attr(AttributeValue::Dimension {
+            value: Spanned::new(40.0, Span::new(13..15)),
+            unit: Spanned::new("%", Span::new(15..16)),
+        });
+        let opacity = Builder::extract_opacity(&percent, "fill_opacity").unwrap();
+        assert!((opacity - 0.4).abs() < f32::EPSILON);
+
+        let err = Builder::extract_opacity(
+            &attr(AttributeValue::Float(Spanned::new(1.5, Span::new(13..16)))),
+            "fill_opacity",
+        )
+        .unwrap_err();
+        assert_eq!(err.code(), Some(ErrorCode::E302));
+        assert!(err.message().contains("out of range"));
+    }
+
+    #[test]
+    fn test_extract_shadow() {
+        use crate::parser_types::{Attribute, AttributeValue, TypeSpec};
+
+        let shadow_attr = |value: AttributeValue<'static>| Attribute {
+            name: Spanned::new("shadow", Span::new(0..6)),
+            value,
+        };
+        let inline = |attributes: Vec<Attribute<'static>>| {
+            shadow_attr(AttributeValue::TypeSpec(TypeSpec {
+                type_name: None,
+                attributes,
+            }))
+        };
+        let palette = Palette::default();
+
+        let enabled = shadow_attr(AttributeValue::String(Spanned::new(
+            "true".to_string(),
+            Span::new(7..13),
+        )));
+        let shadow = Builder::extract_shadow(&enabled, None, &palette).unwrap();
+        assert_eq!(shadow, Some(ShadowDefinition::default()));
+
+        let disabled = shadow_attr(AttributeValue::String(Spanned::new(
+            "false".to_string(),
+            Span::new(7..14),
+        )));
+        let shadow = Builder::extract_shadow(&disabled, shadow.as_ref(), &palette).unwrap();
+        assert_eq!(shadow, None);
+
+        let blurred = inline(vec![Attribute {
+            name: Spanned::new("blur", Span::new(8..12)),
+            value: AttributeValue::Float(Spanned::new(6.0, Span::new(13..14))),
+        }]);
+        let shadow = Builder::extract_shadow(&blurred, None, &palette)
+            .unwrap()
+            .unwrap();
+        assert_eq!(shadow.blur(), 6.0);
+        assert_eq!(shadow.dx(), ShadowDefinition::default().dx());
+
+        let unknown = inline(vec![Attribute {
+            name: Spanned::new("spread", Span::new(8..14)),
+            value: AttributeValue::Float(Spanned::new(1.0, Span::new(15..16))),
+        }]);
+        let err = Builder::extract_shadow(&unknown, None, &palette).unwrap_err();
+        assert_eq!(err.code(), Some(ErrorCode::E303));
+    }
+
+    #[test]
+    fn test_extract_image() {
+        use crate::parser_types::{Attribute, AttributeValue, TypeSpec};
+
+        let string = |name: &'static str, value: &str| Attribute {
+            name: Spanned::new(name, Span::new(0..4)),
+            value: AttributeValue::String(Spanned::new(value.to_string(), Span::new(5..15))),
+        };
+        let inline = |attributes: Vec<Attribute<'static>>| Attribute {
+            name: Spanned::new("icon", Span::new(0..4)),
+            value: AttributeValue::TypeSpec(TypeSpec {
+                type_name: None,
+                attributes,
+            }),
+        };
+
+        let image = Builder::extract_image(&string("icon", "logo.png"), None).unwrap();
+        assert_eq!(image, ImageDefinition::new("logo.png"));
+
+        // Inline attributes refine the inherited image
+        let aligned = inline(vec![string("align", "bottom-right")]);
+        let image = Builder::extract_image(&aligned, Some(&image)).unwrap();
+        assert_eq!(image.source(), "logo.png");
+        assert_eq!(image.align(), "bottom-right".parse().unwrap());
+
+        let err = Builder::extract_image(&aligned, None).unwrap_err();
+        assert_eq!(err.code(), Some(ErrorCode::E302));
+        assert!(err.message().contains("icon has no source"));
+
+        let err = Builder::extract_image(&inline(vec![string("align", "middle")]), Some(&image))
+            .unwrap_err();
+        assert_eq!(err.code(), Some(ErrorCode::E302));
+    }
+
     #[test]
     fn test_fragment_with_both_text_attributes() {
         use crate::parser_types::{Attribute, AttributeValue, TypeSpec};
@@ -2386,8 +4031,11 @@ mod tests {
                     attributes: vec![],
                 },
                 content: parser_types::ComponentContent::None,
+                comments: Vec::new(),
             }],
             imports: vec![],
+            variable_definitions: vec![],
+            style_definitions: vec![],
         };
 
         // --- Build the parent diagram AST ---
@@ -2424,6 +4072,7 @@ mod tests {
                         attributes: vec![],
                     },
                     content: parser_types::ComponentContent::None,
+                    comments: Vec::new(),
                 },
                 parser_types::Element::Component {
                     name: Spanned::new(Id::new("auth_overview"), Span::new(0..13)),
@@ -2435,6 +4084,7 @@ mod tests {
                     content: parser_types::ComponentContent::Diagram(
                         parser_types::DiagramSource::Inline(Rc::new(RefCell::new(child_ast))),
                     ),
+                    comments: Vec::new(),
                 },
                 parser_types::Element::Relation {
                     source: Spanned::new(Id::new("gateway"), Span::new(0..7)),
@@ -2445,9 +4095,12 @@ mod tests {
                         attributes: vec![],
                     },
                     label: Some(Spanned::new("Auth detail".to_string(), Span::new(0..11))),
+                    comments: Vec::new(),
                 },
             ],
             imports: vec![],
+            variable_definitions: vec![],
+            style_definitions: vec![],
         };
 
         let config = ElaborateConfig::default();
@@ -7,11 +7,11 @@
 use std::{rc::Rc, str::FromStr};
 
 use orrery_core::{
-    color::Color,
+    color::{Color, Palette},
     draw::{
-        ActivationBoxDefinition, ArrowDefinition, DiagramDefinition, FragmentDefinition,
-        LifelineDefinition, NoteDefinition, ShapeDefinition, StrokeCap, StrokeDefinition,
-        StrokeJoin, StrokeStyle, TextDefinition,
+        ActivationBoxDefinition, ArrowDefinition, DiagramDefinition, FontStyle, FragmentDefinition,
+        GroupDefinition, HighlightDefinition, LifelineDefinition, NoteDefinition, ShapeDefinition,
+        StrokeCap, StrokeDefinition, StrokeJoin, StrokeStyle, TextDefinition,
     },
     geometry::Insets,
     identifier::Id,
@@ -29,6 +29,8 @@ pub enum DrawDefinition {
     Arrow(Rc<ArrowDefinition>),
     Fragment(Rc<FragmentDefinition>),
     Note(Rc<NoteDefinition>),
+    Highlight(Rc<HighlightDefinition>),
+    Group(Rc<GroupDefinition>),
     ActivationBox(Rc<ActivationBoxDefinition>),
     Lifeline(Rc<LifelineDefinition>),
     Diagram(Rc<DiagramDefinition>),
@@ -71,6 +73,16 @@ impl TypeDefinition {
         Self::new(id, DrawDefinition::Note(note_definition))
     }
 
+    /// Construct a concrete highlight type definition from a highlight definition.
+    pub fn new_highlight(id: Id, highlight_definition: Rc<HighlightDefinition>) -> Self {
+        Self::new(id, DrawDefinition::Highlight(highlight_definition))
+    }
+
+    /// Construct a concrete group type definition from a group definition.
+    pub fn new_group(id: Id, group_definition: Rc<GroupDefinition>) -> Self {
+        Self::new(id, DrawDefinition::Group(group_definition))
+    }
+
     /// Construct a concrete activation box type definition from a activation box definition.
     pub fn new_activation_box(
         id: Id,
@@ -141,6 +153,22 @@ impl TypeDefinition {
         }
     }
 
+    /// Borrow the highlight definition if this type is a highlight; otherwise returns an error.
+    pub fn highlight_definition(&self) -> Result<&Rc<HighlightDefinition>, String> {
+        match &self.draw_definition {
+            DrawDefinition::Highlight(highlight) => Ok(highlight),
+            _ => Err(format!("Type '{}' is not a highlight type", self.id)),
+        }
+    }
+
+    /// Borrow the group definition if this type is a group; otherwise returns an error.
+    pub fn group_definition(&self) -> Result<&Rc<GroupDefinition>, String> {
+        match &self.draw_definition {
+            DrawDefinition::Group(group) => Ok(group),
+            _ => Err(format!("Type '{}' is not a group type", self.id)),
+        }
+    }
+
     /// Borrow the activation box definition if this type is an activation box; otherwise returns an error.
     pub fn activation_box_definition(&self) -> Result<&Rc<ActivationBoxDefinition>, String> {
         match &self.draw_definition {
@@ -182,20 +210,53 @@ impl TypeDefinition {
     }
 }
 
+/// Resolves a color string against `palette`, falling back to CSS parsing.
+///
+/// Palette names take precedence over CSS color names. A value shaped like a
+/// palette reference (e.g. `brand.primary`) that the palette does not define is
+/// reported as an undefined palette color; any other unparsable value is turned
+/// into a diagnostic by `css_error`, which receives the CSS parse error.
+pub fn resolve_color(
+    color_str: &str,
+    attr: &parser_types::Attribute,
+    palette: &Palette,
+    css_error: impl FnOnce(String) -> Diagnostic,
+) -> DiagnosticResult<Color> {
+    if let Some(color) = palette.get(color_str) {
+        return Ok(color);
+    }
+
+    Color::new(color_str).map_err(|err| {
+        if Palette::is_reference(color_str) {
+            Diagnostic::error(format!("undefined palette color `{color_str}`"))
+                .with_code(ErrorCode::E302)
+                .with_label(attr.span(), "undefined palette color")
+                .with_help("define the color in the loaded palette, or use a CSS color")
+        } else {
+            css_error(err)
+        }
+    })
+}
+
 /// Extractor for text-related attributes that can be applied to TextDefinition
 pub struct TextAttributeExtractor;
 
 impl TextAttributeExtractor {
     /// Extract and apply text-related attributes to a TextDefinition from a group of nested attributes.
     ///
+    /// A `padding` percentage resolves against `parent_width`, the width of
+    /// the parent element, or `0.0` when it is unknown.
+    ///
     /// Returns `Ok(())` if all attributes were processed successfully,
     /// `Err(...)` if any attribute has an invalid value or is not a valid text attribute.
     pub fn extract_text_attributes(
         text_def: &mut TextDefinition,
         attrs: &[parser_types::Attribute],
+        palette: &Palette,
+        parent_width: f32,
     ) -> DiagnosticResult<()> {
         for attr in attrs {
-            Self::extract_single_attribute(text_def, attr)?;
+            Self::extract_single_attribute(text_def, attr, palette, parent_width)?;
         }
         Ok(())
     }
@@ -207,13 +268,21 @@ impl TextAttributeExtractor {
     fn extract_single_attribute(
         text_def: &mut TextDefinition,
         attr: &parser_types::Attribute,
+        palette: &Palette,
+        parent_width: f32,
     ) -> DiagnosticResult<()> {
         let name = attr.name.inner();
         let value = &attr.value;
 
         match *name {
             "font_size" => {
-                let val = value.as_u16().map_err(|_| {
+                // Font sizes are stored in points: bare numbers keep that meaning,
+                // while `px` and `pt` values are converted.
+                let val = match value {
+                    parser_types::AttributeValue::Float(_) => value.as_u16(),
+                    _ => value.as_float().map(|px| (px * 0.75).round() as u16),
+                }
+                .map_err(|_| {
                         Diagnostic::error(format!("invalid `font_size` value `{value}`"))
                             .with_code(ErrorCode::E302)
                             .with_label(attr.span(), "invalid number")
@@ -231,24 +300,44 @@ impl TextAttributeExtractor {
                 })?);
                 Ok(())
             }
+            "font_style" => {
+                let style_str = value.as_str().map_err(|err| {
+                    Diagnostic::error(err.to_string())
+                        .with_code(ErrorCode::E302)
+                        .with_label(attr.span(), "invalid font style value")
+                        .with_help("font style must be a string")
+                })?;
+                let style = FontStyle::from_str(style_str).map_err(|err| {
+                    Diagnostic::error(err)
+                        .with_code(ErrorCode::E302)
+                        .with_label(attr.span(), "invalid font style")
+                        .with_help("valid values are: `normal`, `italic`")
+                })?;
+                text_def.set_font_style(style);
+                Ok(())
+            }
             "background_color" => {
-                let val = Color::new(value.as_str().map_err(|err| {
+                let color_str = value.as_str().map_err(|err| {
                     Diagnostic::error(err.to_string())
                         .with_code(ErrorCode::E302)
                         .with_label(attr.span(), "invalid color value")
                         .with_help("color values must be strings")
-                })?)
-                .map_err(|err| {
+                })?;
+                if color_str == "none" {
+                    text_def.set_background_color(None);
+                    return Ok(());
+                }
+                let val = resolve_color(color_str, attr, palette, |err| {
                     Diagnostic::error(format!("invalid `background_color`: {err}"))
                         .with_code(ErrorCode::E302)
                         .with_label(attr.span(), "invalid color")
-                        .with_help("use a CSS color")
+                        .with_help("use a CSS color, or \"none\" for no background")
                 })?;
                 text_def.set_background_color(Some(val));
                 Ok(())
             }
             "padding" => {
-                let val = value.as_float().map_err(|err| {
+                let val = value.as_length(parent_width).map_err(|err| {
                     Diagnostic::error(format!("invalid `padding` value: {err}"))
                         .with_code(ErrorCode::E302)
                         .with_label(attr.span(), "invalid number")
@@ -258,13 +347,13 @@ impl TextAttributeExtractor {
                 Ok(())
             }
             "color" => {
-                let val = Color::new(value.as_str().map_err(|err| {
+                let color_str = value.as_str().map_err(|err| {
                     Diagnostic::error(err.to_string())
                         .with_code(ErrorCode::E302)
                         .with_label(attr.span(), "invalid color value")
                         .with_help("color values must be strings")
-                })?)
-                .map_err(|err| {
+                })?;
+                let val = resolve_color(color_str, attr, palette, |err| {
                     Diagnostic::error(format!("invalid `color`: {err}"))
                         .with_code(ErrorCode::E302)
                         .with_label(attr.span(), "invalid color")
@@ -277,7 +366,7 @@ impl TextAttributeExtractor {
                 .with_code(ErrorCode::E303)
                 .with_label(attr.span(), "unknown attribute")
                 .with_help(
-                    "valid text attributes are: `font_size`, `font_family`, `background_color`, `padding`, `color`",
+                    "valid text attributes are: `font_size`, `font_family`, `font_style`, `background_color`, `padding`, `color`",
                 )),
         }
     }
@@ -291,9 +380,10 @@ impl StrokeAttributeExtractor {
     pub fn extract_stroke_attributes(
         stroke_def: &mut StrokeDefinition,
         attrs: &[parser_types::Attribute],
+        palette: &Palette,
     ) -> DiagnosticResult<()> {
         for attr in attrs {
-            Self::extract_single_attribute(stroke_def, attr)?;
+            Self::extract_single_attribute(stroke_def, attr, palette)?;
         }
         Ok(())
     }
@@ -302,6 +392,7 @@ impl StrokeAttributeExtractor {
     fn extract_single_attribute(
         stroke_def: &mut StrokeDefinition,
         attr: &parser_types::Attribute,
+        palette: &Palette,
     ) -> DiagnosticResult<()> {
         let name = *attr.name.inner();
         let value = &attr.value;
@@ -314,7 +405,7 @@ impl StrokeAttributeExtractor {
                         .with_label(attr.span(), "invalid color value")
                         .with_help("color values must be strings")
                 })?;
-                let val = Color::new(color_str).map_err(|err| {
+                let val = resolve_color(color_str, attr, palette, |err| {
                     Diagnostic::error(format!("invalid stroke `color`: {err}"))
                         .with_code(ErrorCode::E302)
                         .with_label(attr.span(), "invalid color")
@@ -351,6 +442,22 @@ impl StrokeAttributeExtractor {
                 stroke_def.set_style(style);
                 Ok(())
             }
+            "dash_pattern" => {
+                let pattern = value.as_str().map_err(|err| {
+                    Diagnostic::error(err.to_string())
+                        .with_code(ErrorCode::E302)
+                        .with_label(attr.span(), "invalid dash pattern value")
+                        .with_help("dash pattern must be a string")
+                })?;
+                let style = StrokeStyle::from_dash_pattern(pattern).map_err(|err| {
+                    Diagnostic::error(format!("invalid stroke `dash_pattern`: {err}"))
+                        .with_code(ErrorCode::E302)
+                        .with_label(attr.span(), "invalid dash pattern")
+                        .with_help("list dash and gap lengths, e.g. `\"4 2\"`")
+                })?;
+                stroke_def.set_style(style);
+                Ok(())
+            }
             "cap" => {
                 let cap_str = value.as_str().map_err(|err| {
                     Diagnostic::error(err.to_string())
@@ -388,7 +495,7 @@ impl StrokeAttributeExtractor {
                     .with_code(ErrorCode::E303)
                     .with_label(attr.span(), "unknown attribute")
                     .with_help(
-                        "valid stroke attributes are: `color`, `width`, `style`, `cap`, `join`",
+                        "valid stroke attributes are: `color`, `width`, `style`, `dash_pattern`, `cap`, `join`",
                     ),
             ),
         }
@@ -457,11 +564,17 @@ mod elaborate_tests {
         let attributes = vec![
             create_test_attribute("font_size", create_float_value(16.0)),
             create_test_attribute("font_family", create_string_value("Helvetica")),
+            create_test_attribute("font_style", create_string_value("italic")),
             create_test_attribute("background_color", create_string_value("red")),
             create_test_attribute("padding", create_float_value(5.0)),
             create_test_attribute("color", create_string_value("blue")),
         ];
-        let result = TextAttributeExtractor::extract_text_attributes(&mut text_def, &attributes);
+        let result = TextAttributeExtractor::extract_text_attributes(
+            &mut text_def,
+            &attributes,
+            &Palette::default(),
+            0.0,
+        );
         assert!(result.is_ok());
     }
 
@@ -469,22 +582,67 @@ mod elaborate_tests {
     fn test_text_attribute_extractor_color_attribute() {
         let mut text_def = TextDefinition::new();
         let attributes = vec![create_test_attribute("color", create_string_value("red"))];
-        let result = TextAttributeExtractor::extract_text_attributes(&mut text_def, &attributes);
+        let result = TextAttributeExtractor::extract_text_attributes(
+            &mut text_def,
+            &attributes,
+            &Palette::default(),
+            0.0,
+        );
         assert!(result.is_ok());
 
         // Test with invalid color value (should be string)
         let mut text_def = TextDefinition::new();
         let attributes = vec![create_test_attribute("color", create_float_value(255.0))];
-        let result = TextAttributeExtractor::extract_text_attributes(&mut text_def, &attributes);
+        let result = TextAttributeExtractor::extract_text_attributes(
+            &mut text_def,
+            &attributes,
+            &Palette::default(),
+            0.0,
+        );
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_text_attribute_extractor_font_style() {
+        let mut text_def = TextDefinition::new();
+        let attributes = vec![create_test_attribute(
+            "font_style",
+            create_string_value("italic"),
+        )];
+        let result = TextAttributeExtractor::extract_text_attributes(
+            &mut text_def,
+            &attributes,
+            &Palette::default(),
+            0.0,
+        );
+        assert!(result.is_ok());
+        assert_eq!(text_def.font_style(), FontStyle::Italic);
+
+        let attributes = vec![create_test_attribute(
+            "font_style",
+            create_string_value("oblique"),
+        )];
+        let err = TextAttributeExtractor::extract_text_attributes(
+            &mut text_def,
+            &attributes,
+            &Palette::default(),
+            0.0,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("invalid font style `oblique`"));
+    }
+
     #[test]
     fn test_text_attribute_extractor_empty_attributes() {
         let mut text_def = TextDefinition::new();
         let attributes = vec![];
 
-        let result = TextAttributeExtractor::extract_text_attributes(&mut text_def, &attributes);
+        let result = TextAttributeExtractor::extract_text_attributes(
+            &mut text_def,
+            &attributes,
+            &Palette::default(),
+            0.0,
+        );
         assert!(result.is_ok());
     }
 
@@ -496,7 +654,12 @@ mod elaborate_tests {
             create_test_attribute("invalid_attribute", create_string_value("test")),
         ];
 
-        let result = TextAttributeExtractor::extract_text_attributes(&mut text_def, &attributes);
+        let result = TextAttributeExtractor::extract_text_attributes(
+            &mut text_def,
+            &attributes,
+            &Palette::default(),
+            0.0,
+        );
         assert!(result.is_err());
 
         if let Err(err) = result {
@@ -513,7 +676,12 @@ mod elaborate_tests {
             "font_size",
             create_string_value("not_a_number"),
         )];
-        let result = TextAttributeExtractor::extract_text_attributes(&mut text_def, &attributes);
+        let result = TextAttributeExtractor::extract_text_attributes(
+            &mut text_def,
+            &attributes,
+            &Palette::default(),
+            0.0,
+        );
         assert!(result.is_err());
 
         // Test font_family with float value (should be string)
@@ -522,7 +690,12 @@ mod elaborate_tests {
             "font_family",
             create_float_value(123.0),
         )];
-        let result = TextAttributeExtractor::extract_text_attributes(&mut text_def, &attributes);
+        let result = TextAttributeExtractor::extract_text_attributes(
+            &mut text_def,
+            &attributes,
+            &Palette::default(),
+            0.0,
+        );
         assert!(result.is_err());
     }
 
@@ -537,7 +710,11 @@ mod elaborate_tests {
         ];
 
         let mut stroke_def = StrokeDefinition::default();
-        let result = StrokeAttributeExtractor::extract_stroke_attributes(&mut stroke_def, &attrs);
+        let result = StrokeAttributeExtractor::extract_stroke_attributes(
+            &mut stroke_def,
+            &attrs,
+            &Palette::default(),
+        );
 
         assert!(result.is_ok());
         assert_eq!(stroke_def.color().to_string(), "blue");
@@ -552,7 +729,11 @@ mod elaborate_tests {
         let attrs = vec![create_test_attribute("color", create_string_value("red"))];
 
         let mut stroke_def = StrokeDefinition::default();
-        let result = StrokeAttributeExtractor::extract_stroke_attributes(&mut stroke_def, &attrs);
+        let result = StrokeAttributeExtractor::extract_stroke_attributes(
+            &mut stroke_def,
+            &attrs,
+            &Palette::default(),
+        );
 
         assert!(result.is_ok());
         assert_eq!(stroke_def.color().to_string(), "red");
@@ -566,7 +747,11 @@ mod elaborate_tests {
         )];
 
         let mut stroke_def = StrokeDefinition::default();
-        let result = StrokeAttributeExtractor::extract_stroke_attributes(&mut stroke_def, &attrs);
+        let result = StrokeAttributeExtractor::extract_stroke_attributes(
+            &mut stroke_def,
+            &attrs,
+            &Palette::default(),
+        );
 
         assert!(result.is_err());
         if let Err(err) = result {
@@ -584,7 +769,11 @@ mod elaborate_tests {
         )];
 
         let mut stroke_def = StrokeDefinition::default();
-        let result = StrokeAttributeExtractor::extract_stroke_attributes(&mut stroke_def, &attrs);
+        let result = StrokeAttributeExtractor::extract_stroke_attributes(
+            &mut stroke_def,
+            &attrs,
+            &Palette::default(),
+        );
 
         assert!(result.is_err());
         if let Err(err) = result {
@@ -598,7 +787,11 @@ mod elaborate_tests {
         let attrs = vec![create_test_attribute("cap", create_string_value("invalid"))];
 
         let mut stroke_def = StrokeDefinition::default();
-        let result = StrokeAttributeExtractor::extract_stroke_attributes(&mut stroke_def, &attrs);
+        let result = StrokeAttributeExtractor::extract_stroke_attributes(
+            &mut stroke_def,
+            &attrs,
+            &Palette::default(),
+        );
 
         assert!(result.is_err());
         if let Err(err) = result {
@@ -615,7 +808,11 @@ mod elaborate_tests {
         )];
 
         let mut stroke_def = StrokeDefinition::default();
-        let result = StrokeAttributeExtractor::extract_stroke_attributes(&mut stroke_def, &attrs);
+        let result = StrokeAttributeExtractor::extract_stroke_attributes(
+            &mut stroke_def,
+            &attrs,
+            &Palette::default(),
+        );
 
         assert!(result.is_err());
         if let Err(err) = result {
@@ -640,11 +837,50 @@ mod elaborate_tests {
                 create_string_value(style_str),
             )];
             let mut stroke_def = StrokeDefinition::default();
-            let result =
-                StrokeAttributeExtractor::extract_stroke_attributes(&mut stroke_def, &attrs);
+            let result = StrokeAttributeExtractor::extract_stroke_attributes(
+                &mut stroke_def,
+                &attrs,
+                &Palette::default(),
+            );
 
             assert!(result.is_ok());
             assert_eq!(*stroke_def.style(), expected_style);
         }
     }
+
+    #[test]
+    fn test_resolve_color_prefers_palette() {
+        let mut palette = Palette::new();
+        palette.insert("brand.primary", Color::new("#1e88e5").unwrap());
+        let attr = create_test_attribute("color", create_string_value("brand.primary"));
+
+        let color = resolve_color("brand.primary", &attr, &palette, Diagnostic::error).unwrap();
+        assert_eq!(color, Color::new("#1e88e5").unwrap());
+
+        // CSS colors still resolve when the palette does not define them.
+        let color = resolve_color("red", &attr, &palette, Diagnostic::error).unwrap();
+        assert_eq!(color, Color::new("red").unwrap());
+    }
+
+    #[test]
+    fn test_resolve_color_undefined_palette_reference() {
+        let attr = create_test_attribute("color", create_string_value("brand.missing"));
+
+        let err = resolve_color(
+            "brand.missing",
+            &attr,
+            &Palette::default(),
+            Diagnostic::error,
+        )
+        .unwrap_err();
+        assert_eq!(err.code(), Some(ErrorCode::E302));
+        assert_eq!(err.message(), "undefined palette color `brand.missing`");
+
+        // Non-reference values report the caller's CSS error instead.
+        let err = resolve_color("not-a-color", &attr, &Palette::default(), |_| {
+            Diagnostic::error("css error")
+        })
+        .unwrap_err();
+        assert_eq!(err.message(), "css error");
+    }
 }
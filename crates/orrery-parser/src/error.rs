@@ -31,6 +31,7 @@
 mod collector;
 mod diagnostic;
 mod error_code;
+mod json;
 mod label;
 mod parse_error;
 mod severity;
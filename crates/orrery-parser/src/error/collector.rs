@@ -57,17 +57,23 @@ impl DiagnosticCollector {
         self.diagnostics.push(diagnostic);
     }
 
+    /// Borrow all diagnostics emitted so far, in emission order.
+    #[cfg(test)]
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
     /// Finish collection and return a result.
     ///
-    /// - If there are errors, returns `Err(ParseError)` with all diagnostics.
-    /// - If there are no errors, returns `Ok(())`.
-    ///
-    /// Note: Warnings are currently discarded in the success case.
-    pub fn finish(self) -> Result<(), Vec<Diagnostic>> {
+    /// - If there are errors, returns `Err` with all diagnostics, warnings
+    ///   included.
+    /// - If there are no errors, returns `Ok` with the warnings, which is
+    ///   empty when nothing was emitted.
+    pub fn finish(self) -> Result<Vec<Diagnostic>, Vec<Diagnostic>> {
         if self.has_errors {
             Err(self.diagnostics)
         } else {
-            Ok(())
+            Ok(self.diagnostics)
         }
     }
 }
@@ -138,7 +144,8 @@ mod tests {
         collector.emit(Diagnostic::warning("warning 1"));
         collector.emit(Diagnostic::warning("warning 2"));
 
-        let result = collector.finish();
-        assert!(result.is_ok());
+        let warnings = collector.finish().unwrap();
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(warnings[1].message(), "warning 2");
     }
 }
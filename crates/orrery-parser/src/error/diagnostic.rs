@@ -6,7 +6,8 @@
 use std::fmt;
 
 use crate::{
-    error::{Severity, error_code::ErrorCode, label::Label},
+    error::{Severity, error_code::ErrorCode, json::JsonDiagnostic, label::Label},
+    source_map::SourceMap,
     span::Span,
 };
 
@@ -125,6 +126,42 @@ impl Diagnostic {
         self
     }
 
+    /// Set the severity, e.g. to promote a warning to an error.
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// Serialize the diagnostic as a single-line JSON object.
+    ///
+    /// The object has the shape
+    /// `{severity, code, message, file, span: {start, end}, range, help}`,
+    /// where `span` holds byte offsets local to `file` and `range` holds the
+    /// same location as LSP-style 0-based `line`/`character` positions. The
+    /// location is taken from the primary label and is `null` when the
+    /// diagnostic has no label in `source_map`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use orrery_parser::error::{Diagnostic, ErrorCode};
+    /// # use orrery_parser::{Span, source_map::SourceMap};
+    /// let mut source_map = SourceMap::new();
+    /// source_map.add_file("app.orr", "diagram component;\napp: Foo;", None);
+    ///
+    /// let diag = Diagnostic::error("undefined type `Foo`")
+    ///     .with_code(ErrorCode::E300)
+    ///     .with_label(Span::new(24..27), "not found");
+    ///
+    /// let json = diag.to_json(&source_map);
+    /// assert!(json.contains(r#""code":"E300""#));
+    /// assert!(json.contains(r#""start":{"line":1,"character":5}"#));
+    /// ```
+    pub fn to_json(&self, source_map: &SourceMap<'_>) -> String {
+        serde_json::to_string(&JsonDiagnostic::new(self, source_map))
+            .expect("diagnostic JSON serialization is infallible")
+    }
+
     /// Create a new diagnostic with the given severity and message.
     fn new(severity: Severity, message: impl Into<String>) -> Self {
         Self {
@@ -194,6 +231,17 @@ mod tests {
         assert!(diag.labels()[1].is_secondary());
     }
 
+    #[test]
+    fn test_diagnostic_with_severity() {
+        let diag = Diagnostic::warning("unused type")
+            .with_code(ErrorCode::E300)
+            .with_severity(Severity::Error);
+
+        assert!(diag.severity().is_error());
+        assert_eq!(diag.code(), Some(ErrorCode::E300));
+        assert_eq!(diag.message(), "unused type");
+    }
+
     #[test]
     fn test_diagnostic_with_help() {
         let diag = Diagnostic::new(Severity::Warning, "unused variable")
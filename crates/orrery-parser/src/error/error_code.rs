@@ -6,6 +6,7 @@
 //! - `E2xx` - Validation errors
 //! - `E3xx` - Elaboration errors
 //! - `E4xx` - Resolver errors
+//! - `E5xx` - Import errors (conversion from other diagram languages)
 
 use std::fmt;
 
@@ -48,6 +49,12 @@ pub enum ErrorCode {
     /// A unicode escape `\u{}` was found with no hexadecimal digits.
     E006,
 
+    /// Unterminated block comment.
+    ///
+    /// A block comment was opened with `/*` but never closed. Block comments
+    /// nest, so every `/*` inside it needs its own `*/`.
+    E007,
+
     // =========================================================================
     // Parser Errors (E1xx)
     // =========================================================================
@@ -61,6 +68,11 @@ pub enum ErrorCode {
     /// The input ended unexpectedly before a complete construct was parsed.
     E101,
 
+    /// Missing diagram header.
+    ///
+    /// The file does not start with `diagram <kind>;` (or `library;`).
+    E102,
+
     // =========================================================================
     // Validation Errors (E2xx)
     // =========================================================================
@@ -96,6 +108,28 @@ pub enum ErrorCode {
     /// built-in type nor a previously defined `type`.
     E205,
 
+    /// Unknown unit.
+    ///
+    /// A numeric attribute value has a unit suffix other than `px`, `pt` or `%`.
+    E206,
+
+    /// Undefined variable.
+    ///
+    /// An attribute value references a `$name` variable that no `let`
+    /// definition in scope declares.
+    E207,
+
+    /// Undefined style.
+    ///
+    /// A component's `class` attribute names a style that no `style`
+    /// definition in the file declares.
+    E208,
+
+    /// Duplicate component.
+    ///
+    /// A component is declared more than once in the same scope.
+    E209,
+
     // =========================================================================
     // Elaboration Errors (E3xx)
     // =========================================================================
@@ -170,6 +204,21 @@ pub enum ErrorCode {
     /// A namespace identifier could not be derived from the import path
     /// (e.g., the path has no file stem or contains non-UTF-8 components).
     E403,
+
+    // =========================================================================
+    // Import Errors (E5xx)
+    // =========================================================================
+    /// Unsupported diagram type.
+    ///
+    /// A diagram written in another language is missing its diagram type or
+    /// declares a type that cannot be converted.
+    E500,
+
+    /// Unsupported construct.
+    ///
+    /// A line of a diagram written in another language uses a construct that
+    /// has no Orrery conversion and must be converted by hand.
+    E501,
 }
 
 impl ErrorCode {
@@ -183,9 +232,11 @@ impl ErrorCode {
             ErrorCode::E004 => "E004",
             ErrorCode::E005 => "E005",
             ErrorCode::E006 => "E006",
+            ErrorCode::E007 => "E007",
             // Parser errors
             ErrorCode::E100 => "E100",
             ErrorCode::E101 => "E101",
+            ErrorCode::E102 => "E102",
             // Validation errors
             ErrorCode::E200 => "E200",
             ErrorCode::E201 => "E201",
@@ -193,6 +244,10 @@ impl ErrorCode {
             ErrorCode::E203 => "E203",
             ErrorCode::E204 => "E204",
             ErrorCode::E205 => "E205",
+            ErrorCode::E206 => "E206",
+            ErrorCode::E207 => "E207",
+            ErrorCode::E208 => "E208",
+            ErrorCode::E209 => "E209",
             // Elaboration errors
             ErrorCode::E300 => "E300",
             ErrorCode::E301 => "E301",
@@ -208,6 +263,9 @@ impl ErrorCode {
             ErrorCode::E401 => "E401",
             ErrorCode::E402 => "E402",
             ErrorCode::E403 => "E403",
+            // Import errors
+            ErrorCode::E500 => "E500",
+            ErrorCode::E501 => "E501",
         }
     }
 
@@ -221,9 +279,11 @@ impl ErrorCode {
             ErrorCode::E004 => "invalid unicode escape",
             ErrorCode::E005 => "invalid unicode codepoint",
             ErrorCode::E006 => "empty unicode escape",
+            ErrorCode::E007 => "unterminated block comment",
             // Parser errors
             ErrorCode::E100 => "unexpected token",
             ErrorCode::E101 => "incomplete input",
+            ErrorCode::E102 => "missing diagram header",
             // Validation errors
             ErrorCode::E200 => "undefined component",
             ErrorCode::E201 => "unpaired activate",
@@ -231,6 +291,10 @@ impl ErrorCode {
             ErrorCode::E203 => "invalid align value",
             ErrorCode::E204 => "unknown embed reference",
             ErrorCode::E205 => "unknown base type",
+            ErrorCode::E206 => "unknown unit",
+            ErrorCode::E207 => "undefined variable",
+            ErrorCode::E208 => "undefined style",
+            ErrorCode::E209 => "duplicate component",
             // Elaboration errors
             ErrorCode::E300 => "undefined type",
             ErrorCode::E301 => "unresolved embed reference",
@@ -246,6 +310,9 @@ impl ErrorCode {
             ErrorCode::E401 => "circular dependency",
             ErrorCode::E402 => "invalid import path",
             ErrorCode::E403 => "invalid namespace",
+            // Import errors
+            ErrorCode::E500 => "unsupported diagram type",
+            ErrorCode::E501 => "unsupported construct",
         }
     }
 }
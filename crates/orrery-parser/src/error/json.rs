@@ -0,0 +1,146 @@
+//! JSON representation of diagnostics for editors and other tools.
+//!
+//! [`JsonDiagnostic`] mirrors a [`Diagnostic`] with its primary location
+//! resolved against a [`SourceMap`], both as file-local byte offsets and as
+//! LSP-style line/character positions.
+
+use serde::Serialize;
+
+use crate::{error::Diagnostic, source_map::SourceMap, span::LineIndex};
+
+/// Serializable view of a [`Diagnostic`].
+#[derive(Debug, Serialize)]
+pub(super) struct JsonDiagnostic<'a> {
+    severity: String,
+    code: Option<String>,
+    message: &'a str,
+    file: Option<&'a str>,
+    span: Option<JsonSpan>,
+    range: Option<JsonRange>,
+    help: Option<&'a str>,
+}
+
+/// Byte offsets of a location, local to its file.
+#[derive(Debug, Serialize)]
+struct JsonSpan {
+    start: usize,
+    end: usize,
+}
+
+/// Start and end positions of a location.
+#[derive(Debug, Serialize)]
+struct JsonRange {
+    start: JsonPosition,
+    end: JsonPosition,
+}
+
+/// 0-based line and UTF-16 character of a position.
+#[derive(Debug, Serialize)]
+struct JsonPosition {
+    line: u32,
+    character: u32,
+}
+
+impl<'a> JsonDiagnostic<'a> {
+    /// Resolves the primary label of `diag`, or its first label when none is
+    /// primary, against `source_map`.
+    pub(super) fn new(diag: &'a Diagnostic, source_map: &'a SourceMap<'_>) -> Self {
+        let labels = diag.labels();
+        let location = labels
+            .iter()
+            .find(|label| label.is_primary())
+            .or_else(|| labels.first())
+            .and_then(|label| {
+                let span = label.span();
+                source_map
+                    .lookup_file_by_span(span)
+                    .map(|file| (file, span))
+            });
+
+        let (file, span, range) = match location {
+            Some((file, span)) => {
+                let start = span.start() - file.start_offset();
+                let end = span.end() - file.start_offset();
+                let line_index = LineIndex::new(file.source());
+                let position = |offset| {
+                    let (line, character) = line_index.offset_to_line_col(offset);
+                    JsonPosition { line, character }
+                };
+                (
+                    Some(file.name()),
+                    Some(JsonSpan { start, end }),
+                    Some(JsonRange {
+                        start: position(start),
+                        end: position(end),
+                    }),
+                )
+            }
+            None => (None, None, None),
+        };
+
+        Self {
+            severity: diag.severity().to_string(),
+            code: diag.code().map(|code| code.to_string()),
+            message: diag.message(),
+            file,
+            span,
+            range,
+            help: diag.help(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::{error::ErrorCode, span::Span};
+
+    fn to_value(diag: &Diagnostic, source_map: &SourceMap<'_>) -> Value {
+        serde_json::from_str(&diag.to_json(source_map)).unwrap()
+    }
+
+    #[test]
+    fn test_to_json_resolves_primary_label_in_imported_file() {
+        let mut source_map = SourceMap::new();
+        source_map.add_file("main.orr", "import \"lib\";", None);
+        let base = source_map.add_file("lib.orr", "type A = Rectangle;\ntype B = Foo;", None);
+
+        let diag = Diagnostic::error("undefined type `Foo`")
+            .with_code(ErrorCode::E300)
+            .with_secondary_label(Span::new(0..6), "imported here")
+            .with_label(Span::new(base + 29..base + 32), "not found")
+            .with_help("define `Foo` first");
+
+        assert_eq!(
+            to_value(&diag, &source_map),
+            json!({
+                "severity": "error",
+                "code": "E300",
+                "message": "undefined type `Foo`",
+                "file": "lib.orr",
+                "span": {"start": 29, "end": 32},
+                "range": {
+                    "start": {"line": 1, "character": 9},
+                    "end": {"line": 1, "character": 12},
+                },
+                "help": "define `Foo` first",
+            })
+        );
+    }
+
+    #[test]
+    fn test_to_json_without_labels_has_null_location() {
+        let diag = Diagnostic::warning("unused import");
+
+        let value = to_value(&diag, &SourceMap::new());
+
+        assert_eq!(value["severity"], "warning");
+        assert_eq!(value["code"], Value::Null);
+        assert_eq!(value["file"], Value::Null);
+        assert_eq!(value["span"], Value::Null);
+        assert_eq!(value["range"], Value::Null);
+        assert_eq!(value["help"], Value::Null);
+    }
+}
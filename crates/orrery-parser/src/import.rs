@@ -0,0 +1,9 @@
+//! Conversion of diagrams written in other diagram languages.
+//!
+//! Each submodule reads one foreign syntax and builds the Orrery AST that the
+//! usual desugar, validate and elaborate phases turn into a semantic
+//! [`Diagram`](orrery_core::semantic::Diagram). Diagnostics point into the
+//! foreign source, so constructs that cannot be converted are reported on the
+//! line they appear in.
+
+pub mod mermaid;
@@ -0,0 +1,894 @@
+//! Conversion of Mermaid diagrams.
+//!
+//! A subset of two Mermaid diagram types is converted:
+//!
+//! - **`sequenceDiagram`**: `participant` and `actor` declarations with
+//!   optional `as` aliases, messages with labels, and activations, both as
+//!   `activate`/`deactivate` lines and as the `+`/`-` message shorthand.
+//!   Participants that a message uses without declaring them are declared
+//!   implicitly, as Mermaid does.
+//! - **`flowchart`** and **`graph`**: nodes with `[rectangle]`, `(rounded)`,
+//!   `([stadium])` and `((circle))` shapes, and links with optional labels,
+//!   chained as in `a --> b --> c`. The flow direction is ignored.
+//!
+//! Arrows map to the Orrery relation of the same direction, and dotted
+//! Mermaid arrows become dashed strokes. Comments (`%%`) are skipped. Every
+//! other construct, such as `note`, `loop` or `subgraph`, is reported as an
+//! [`ErrorCode::E501`] error on the line it appears in, so that it can be
+//! converted by hand.
+//!
+//! # Examples
+//!
+//! ```
+//! # use bumpalo::Bump;
+//! # use orrery_parser::{ElaborateConfig, import::mermaid};
+//! let arena = Bump::new();
+//! let source = "sequenceDiagram
+//!     participant A as Alice
+//!     A->>B: Hello";
+//!
+//! let parsed = mermaid::parse(&arena, "chat.mmd", source, ElaborateConfig::default())
+//!     .expect("supported Mermaid subset");
+//! assert_eq!(parsed.diagram().scope().elements().len(), 3);
+//! ```
+
+use std::collections::HashSet;
+
+use bumpalo::Bump;
+
+use orrery_core::{identifier::Id, semantic::DiagramKind};
+
+use crate::{
+    ElaborateConfig, Parsed, builtin_types, desugar,
+    elaborate::Builder,
+    error::{Diagnostic, DiagnosticCollector, ErrorCode, ParseError},
+    parser_types::{
+        Attribute, AttributeValue, ComponentContent, Element, FileAst, FileHeader, TypeSpec,
+    },
+    source_map::SourceMap,
+    span::{Span, Spanned},
+    validate,
+};
+
+/// A sequence diagram arrow, as `(mermaid, orrery relation, dashed)`.
+type MessageArrow = (&'static str, &'static str, bool);
+
+/// Sequence diagram arrows. Arrows that share a prefix are listed longest
+/// first.
+const MESSAGE_ARROWS: &[MessageArrow] = &[
+    ("-->>", "->", true),
+    ("->>", "->", false),
+    ("--)", "->>", true),
+    ("-)", "->>", false),
+    ("-->", "-", true),
+    ("->", "-", false),
+];
+
+/// Flowchart link openings that take their label between two arrow parts,
+/// as in `a -- label --> b`, with the arrow parts that close them.
+const LABELED_LINKS: &[(&str, &[&str])] = &[
+    ("--", &["-->", "---"]),
+    ("==", &["==>", "==="]),
+    ("-.", &[".->", ".-"]),
+];
+
+/// Shape of a flowchart node.
+#[derive(Debug, Clone, Copy)]
+enum NodeShape {
+    Rectangle,
+    Rounded,
+    Circle,
+}
+
+/// Flowchart node delimiters, as `(open, close, shape)`.
+///
+/// Delimiters that share a prefix are listed longest first. Shapes without an
+/// Orrery equivalent have no [`NodeShape`].
+const NODE_SHAPES: &[(&str, &str, Option<NodeShape>)] = &[
+    ("((", "))", Some(NodeShape::Circle)),
+    ("([", "])", Some(NodeShape::Rounded)),
+    ("[[", "]]", None),
+    ("[(", ")]", None),
+    ("[/", "]", None),
+    ("[\\", "]", None),
+    ("{{", "}}", None),
+    ("[", "]", Some(NodeShape::Rectangle)),
+    ("(", ")", Some(NodeShape::Rounded)),
+    ("{", "}", None),
+    (">", "]", None),
+];
+
+/// Flowchart statements that have no Orrery equivalent.
+const UNSUPPORTED_FLOWCHART_KEYWORDS: &[&str] = &[
+    "subgraph",
+    "end",
+    "direction",
+    "classDef",
+    "class",
+    "style",
+    "linkStyle",
+    "click",
+];
+
+/// Converts Mermaid `source` into a semantic diagram.
+///
+/// The source is converted to the Orrery AST, which then goes through the
+/// same desugar, validate and elaborate phases as an Orrery file.
+///
+/// # Arguments
+///
+/// * `arena` — A [`Bump`] arena that owns the source text. The arena must
+///   outlive the returned error (if any).
+/// * `name` — Name of the source, shown in diagnostics.
+/// * `source` — The Mermaid source text.
+/// * `config` — Configuration for the elaboration phase.
+///
+/// # Errors
+///
+/// Returns a [`ParseError`] with an [`ErrorCode::E500`] diagnostic if the
+/// diagram type is not supported, with one [`ErrorCode::E501`] diagnostic
+/// for every line that cannot be converted, or with the diagnostics of the
+/// later phases.
+pub fn parse<'a>(
+    arena: &'a Bump,
+    name: &str,
+    source: &str,
+    config: ElaborateConfig,
+) -> Result<Parsed<'a>, ParseError<'a>> {
+    let source = arena.alloc_str(source);
+    let mut source_map = SourceMap::new();
+    let base = source_map.add_file(name, source, None);
+
+    let file_ast = match convert(source, base) {
+        Ok(file_ast) => file_ast,
+        Err(diags) => return Err(ParseError::new(diags, source_map)),
+    };
+
+    let desugared = desugar::desugar(file_ast, &config.theme);
+    let warnings = match validate::validate(&desugared, &config) {
+        Ok(warnings) => warnings,
+        Err(diags) => return Err(ParseError::new(diags, source_map)),
+    };
+
+    match Builder::new(config).build(&desugared) {
+        Ok(diagram) => Ok(Parsed::new(diagram, warnings, source_map)),
+        Err(diag) => Err(ParseError::from_diagnostic(diag, source_map)),
+    }
+}
+
+/// Converts Mermaid `source`, starting at virtual offset `base`, into an
+/// Orrery file AST.
+fn convert<'a>(source: &str, base: usize) -> Result<FileAst<'a>, Vec<Diagnostic>> {
+    let mut lines = lines(source, base);
+
+    let Some(header) = lines.next() else {
+        return Err(vec![
+            Diagnostic::error("missing Mermaid diagram type")
+                .with_code(ErrorCode::E500)
+                .with_label(Span::new(base..base + source.len()), "no diagram declared")
+                .with_help("start the diagram with `sequenceDiagram` or `flowchart`"),
+        ]);
+    };
+    let (keyword, _) = header.word(0);
+    let kind = match header.text(keyword.clone()) {
+        "sequenceDiagram" => DiagramKind::Sequence,
+        "flowchart" | "graph" => DiagramKind::Component,
+        other => {
+            return Err(vec![
+                Diagnostic::error(format!("unsupported Mermaid diagram type `{other}`"))
+                    .with_code(ErrorCode::E500)
+                    .with_label(header.span(keyword), "unsupported diagram type")
+                    .with_help(
+                        "only `sequenceDiagram`, `flowchart` and `graph` diagrams can be converted",
+                    ),
+            ]);
+        }
+    };
+
+    let mut converter = Converter::default();
+    for line in lines {
+        match kind {
+            DiagramKind::Sequence => converter.sequence_line(&line),
+            DiagramKind::Component => converter.flowchart_line(&line),
+        }
+    }
+    converter.diagnostics.finish()?;
+
+    Ok(FileAst {
+        header: FileHeader::Diagram {
+            kind: Spanned::new(kind, header.span(keyword)),
+            attributes: Vec::new(),
+        },
+        import_decls: Vec::new(),
+        type_definitions: Vec::new(),
+        elements: converter.elements,
+        imports: Vec::new(),
+        variable_definitions: vec![],
+        style_definitions: vec![],
+    })
+}
+
+/// Returns the non-empty lines of `source` that are not comments, trimmed.
+fn lines(source: &str, base: usize) -> impl Iterator<Item = Line<'_>> {
+    let mut offset = base;
+    source.split_inclusive('\n').filter_map(move |raw| {
+        let start = offset + raw.len() - raw.trim_start().len();
+        offset += raw.len();
+        let text = raw.trim();
+        let text = text.strip_suffix(';').unwrap_or(text).trim_end();
+        (!text.is_empty() && !text.starts_with("%%")).then_some(Line { text, start })
+    })
+}
+
+/// One trimmed line of Mermaid source.
+struct Line<'s> {
+    text: &'s str,
+    /// Virtual offset of the first byte of `text`.
+    start: usize,
+}
+
+impl Line<'_> {
+    /// Returns the text of `range`, in bytes relative to the line.
+    fn text(&self, range: std::ops::Range<usize>) -> &str {
+        &self.text[range]
+    }
+
+    /// Returns the span of `range`, in bytes relative to the line.
+    fn span(&self, range: std::ops::Range<usize>) -> Span {
+        Span::new(self.start + range.start..self.start + range.end)
+    }
+
+    /// Returns the span of the whole line.
+    fn full_span(&self) -> Span {
+        self.span(0..self.text.len())
+    }
+
+    /// Returns the range of the whitespace-separated word starting at or
+    /// after `from`, and the offset just past it.
+    fn word(&self, from: usize) -> (std::ops::Range<usize>, usize) {
+        let start = from + skip_whitespace(&self.text[from..]);
+        let end = self.text[start..]
+            .find(char::is_whitespace)
+            .map_or(self.text.len(), |len| start + len);
+        (start..end, end)
+    }
+
+    /// Returns the range of the text after `from`, without surrounding
+    /// whitespace.
+    fn trimmed(&self, from: usize) -> std::ops::Range<usize> {
+        let start = from + skip_whitespace(&self.text[from..]);
+        let end = start + self.text[start..].trim_end().len();
+        start..end
+    }
+}
+
+/// Returns the length of the leading whitespace of `text`.
+fn skip_whitespace(text: &str) -> usize {
+    text.len() - text.trim_start().len()
+}
+
+/// Returns the first message arrow in the part of `text` before the label,
+/// with its byte offset.
+fn find_message_arrow(text: &str) -> Option<(usize, MessageArrow)> {
+    let head = &text[..text.find(':').unwrap_or(text.len())];
+    head.char_indices().find_map(|(i, _)| {
+        MESSAGE_ARROWS
+            .iter()
+            .find(|(arrow, ..)| head[i..].starts_with(arrow))
+            .map(|arrow| (i, *arrow))
+    })
+}
+
+/// Returns `true` if `name` can be used as an Orrery identifier.
+fn is_identifier(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// Returns `text` without surrounding double quotes, with `<br>` line breaks
+/// turned into newlines.
+fn label_text(text: &str) -> String {
+    let text = text
+        .strip_prefix('"')
+        .and_then(|text| text.strip_suffix('"'))
+        .unwrap_or(text);
+    text.replace("<br/>", "\n").replace("<br>", "\n")
+}
+
+/// Returns a diagnostic for a Mermaid construct that cannot be converted.
+fn unsupported(what: impl std::fmt::Display, span: Span, help: &str) -> Diagnostic {
+    Diagnostic::error(format!("unsupported Mermaid {what}"))
+        .with_code(ErrorCode::E501)
+        .with_label(span, "cannot be converted")
+        .with_help(help.to_string())
+}
+
+/// A link between two flowchart nodes.
+struct Link {
+    relation_type: &'static str,
+    dashed: bool,
+    label: Option<Spanned<String>>,
+    span: Span,
+}
+
+/// Accumulates the converted elements and the diagnostics of the lines that
+/// could not be converted.
+#[derive(Default)]
+struct Converter<'a> {
+    elements: Vec<Element<'a>>,
+    declared: HashSet<Id>,
+    diagnostics: DiagnosticCollector,
+}
+
+impl<'a> Converter<'a> {
+    /// Declares component `name` unless it is already declared.
+    fn declare(
+        &mut self,
+        name: Spanned<Id>,
+        display_name: Option<Spanned<String>>,
+        type_name: &str,
+        attributes: Vec<Attribute<'a>>,
+    ) {
+        if !self.declared.insert(*name.inner()) {
+            return;
+        }
+        let type_spec = TypeSpec {
+            type_name: Some(Spanned::new(Id::new(type_name), name.span())),
+            attributes,
+        };
+        self.elements.push(Element::Component {
+            name,
+            display_name,
+            type_spec,
+            content: ComponentContent::None,
+            comments: Vec::new(),
+        });
+    }
+
+    /// Declares the participant named by `range` of `line` as a rectangle,
+    /// returning its identifier.
+    fn participant(
+        &mut self,
+        line: &Line<'_>,
+        range: std::ops::Range<usize>,
+    ) -> Result<Spanned<Id>, Diagnostic> {
+        let name = line.text(range.clone());
+        if !is_identifier(name) {
+            return Err(unsupported(
+                format_args!("participant name `{name}`"),
+                line.span(range),
+                "participant names may only contain letters, digits and `_`",
+            ));
+        }
+        let id = Spanned::new(Id::new(name), line.span(range));
+        self.declare(id, None, builtin_types::RECTANGLE, Vec::new());
+        Ok(id)
+    }
+
+    /// Converts one line of a `sequenceDiagram`.
+    fn sequence_line(&mut self, line: &Line<'_>) {
+        if let Err(diag) = self.try_sequence_line(line) {
+            self.diagnostics.emit(diag);
+        }
+    }
+
+    fn try_sequence_line(&mut self, line: &Line<'_>) -> Result<(), Diagnostic> {
+        let (keyword, rest) = line.word(0);
+        match line.text(keyword.clone()) {
+            "participant" | "actor" => {
+                let type_name = if line.text(keyword) == "actor" {
+                    builtin_types::ACTOR
+                } else {
+                    builtin_types::RECTANGLE
+                };
+                let (name, rest) = line.word(rest);
+                if !is_identifier(line.text(name.clone())) {
+                    return Err(unsupported(
+                        format_args!("participant name `{}`", line.text(name.clone())),
+                        line.span(name),
+                        "participant names may only contain letters, digits and `_`",
+                    ));
+                }
+                let (alias_keyword, alias_start) = line.word(rest);
+                let display_name = match line.text(alias_keyword.clone()) {
+                    "" => None,
+                    "as" => {
+                        let alias = line.trimmed(alias_start);
+                        Some(Spanned::new(
+                            label_text(line.text(alias.clone())),
+                            line.span(alias),
+                        ))
+                    }
+                    _ => {
+                        return Err(unsupported(
+                            "participant declaration",
+                            line.full_span(),
+                            "declare participants as `participant <name>` or `participant <name> as <label>`",
+                        ));
+                    }
+                };
+                let id = Spanned::new(Id::new(line.text(name.clone())), line.span(name));
+                self.declare(id, display_name, type_name, Vec::new());
+                Ok(())
+            }
+            "activate" | "deactivate" => {
+                let activate = line.text(keyword) == "activate";
+                let component = self.participant(line, line.trimmed(rest))?;
+                self.elements.push(if activate {
+                    Element::Activate {
+                        component,
+                        type_spec: TypeSpec::default(),
+                    }
+                } else {
+                    Element::Deactivate { component }
+                });
+                Ok(())
+            }
+            word => {
+                let Some(arrow) = find_message_arrow(line.text) else {
+                    return Err(unsupported(
+                        format_args!("sequence diagram statement `{word}`"),
+                        line.full_span(),
+                        "only participants, actors, messages and activations are converted; \
+                         convert this line by hand",
+                    ));
+                };
+                self.message(line, arrow)
+            }
+        }
+    }
+
+    /// Converts a message line such as `A->>+B: Hello`, whose arrow starts at
+    /// byte `arrow_start`.
+    fn message(
+        &mut self,
+        line: &Line<'_>,
+        (arrow_start, (arrow, relation_type, dashed)): (usize, MessageArrow),
+    ) -> Result<(), Diagnostic> {
+        let text = line.text;
+        let head_end = text.find(':').unwrap_or(text.len());
+        let arrow_end = arrow_start + arrow.len();
+
+        let source = self.participant(line, 0..text[..arrow_start].trim_end().len())?;
+
+        let mut target_start = arrow_end + skip_whitespace(&text[arrow_end..head_end]);
+        let activation = match text[target_start..head_end].chars().next() {
+            Some(sign @ ('+' | '-')) => {
+                target_start += 1;
+                Some(sign == '+')
+            }
+            _ => None,
+        };
+        let target_end = target_start + text[target_start..head_end].trim_end().len();
+        let target = self.participant(line, target_start..target_end)?;
+
+        let label = (head_end < text.len()).then(|| {
+            let range = line.trimmed(head_end + 1);
+            Spanned::new(label_text(line.text(range.clone())), line.span(range))
+        });
+
+        let span = line.span(arrow_start..arrow_end);
+        self.elements.push(Element::Relation {
+            source,
+            target,
+            relation_type: Spanned::new(relation_type, span),
+            type_spec: relation_type_spec(dashed, span),
+            label,
+            comments: Vec::new(),
+        });
+        match activation {
+            Some(true) => self.elements.push(Element::Activate {
+                component: target,
+                type_spec: TypeSpec::default(),
+            }),
+            Some(false) => self
+                .elements
+                .push(Element::Deactivate { component: source }),
+            None => {}
+        }
+        Ok(())
+    }
+
+    /// Converts one line of a `flowchart`.
+    fn flowchart_line(&mut self, line: &Line<'_>) {
+        if let Err(diag) = self.try_flowchart_line(line) {
+            self.diagnostics.emit(diag);
+        }
+    }
+
+    fn try_flowchart_line(&mut self, line: &Line<'_>) -> Result<(), Diagnostic> {
+        let (keyword, _) = line.word(0);
+        let word = line.text(keyword.clone());
+        if UNSUPPORTED_FLOWCHART_KEYWORDS.contains(&word) {
+            return Err(unsupported(
+                format_args!("flowchart statement `{word}`"),
+                line.full_span(),
+                "only nodes and links are converted; convert this line by hand",
+            ));
+        }
+
+        let mut cursor = 0;
+        let mut previous = self.flowchart_node(line, &mut cursor)?;
+        loop {
+            cursor += skip_whitespace(&line.text[cursor..]);
+            if cursor == line.text.len() {
+                return Ok(());
+            }
+            let link = Self::flowchart_link(line, &mut cursor)?;
+            cursor += skip_whitespace(&line.text[cursor..]);
+            let next = self.flowchart_node(line, &mut cursor)?;
+
+            self.elements.push(Element::Relation {
+                source: previous,
+                target: next,
+                relation_type: Spanned::new(link.relation_type, link.span),
+                type_spec: relation_type_spec(link.dashed, link.span),
+                label: link.label,
+                comments: Vec::new(),
+            });
+            previous = next;
+        }
+    }
+
+    /// Parses and declares the node at `cursor`, such as `a` or `a[Label]`.
+    fn flowchart_node(
+        &mut self,
+        line: &Line<'_>,
+        cursor: &mut usize,
+    ) -> Result<Spanned<Id>, Diagnostic> {
+        let start = *cursor;
+        let len = line.text[start..]
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(line.text.len() - start);
+        if len == 0 {
+            return Err(unsupported(
+                "flowchart syntax",
+                line.span(start..line.text.len()),
+                "expected a node such as `a` or `a[Label]`; convert this line by hand",
+            ));
+        }
+        *cursor += len;
+        let id = Spanned::new(
+            Id::new(line.text(start..start + len)),
+            line.span(start..start + len),
+        );
+
+        let rest = &line.text[*cursor..];
+        let Some((open, close, shape)) =
+            NODE_SHAPES.iter().find(|(open, ..)| rest.starts_with(open))
+        else {
+            self.declare(id, None, builtin_types::RECTANGLE, Vec::new());
+            return Ok(id);
+        };
+        let shape_start = *cursor;
+        let label_start = shape_start + open.len();
+        let Some(label_len) = line.text[label_start..].find(close) else {
+            return Err(unsupported(
+                "node shape",
+                line.span(shape_start..line.text.len()),
+                "the node label is not closed",
+            ));
+        };
+        *cursor = label_start + label_len + close.len();
+        let Some(shape) = shape else {
+            return Err(unsupported(
+                format_args!("node shape `{open}...{close}`"),
+                line.span(shape_start..*cursor),
+                "only `[rectangle]`, `(rounded)`, `([stadium])` and `((circle))` nodes are converted",
+            ));
+        };
+
+        let label_range = label_start..label_start + label_len;
+        let label = Spanned::new(
+            label_text(line.text(label_range.clone()).trim()),
+            line.span(label_range),
+        );
+        let display_name = (label.inner().as_str() != id.inner().name()).then_some(label);
+        let (type_name, attributes) = match shape {
+            NodeShape::Rectangle => (builtin_types::RECTANGLE, Vec::new()),
+            NodeShape::Rounded => (
+                builtin_types::RECTANGLE,
+                vec![Attribute {
+                    name: Spanned::new("rounded", id.span()),
+                    value: AttributeValue::Float(Spanned::new(10.0, id.span())),
+                }],
+            ),
+            NodeShape::Circle => (builtin_types::OVAL, Vec::new()),
+        };
+        self.declare(id, display_name, type_name, attributes);
+        Ok(id)
+    }
+
+    /// Parses the link at `cursor`, such as `-->`, `-.->|label|` or
+    /// `-- label -->`.
+    fn flowchart_link(line: &Line<'_>, cursor: &mut usize) -> Result<Link, Diagnostic> {
+        let start = *cursor;
+        let text = line.text;
+
+        let tail = text[start..].starts_with('<');
+        let body_start = start + usize::from(tail);
+        let body_len = text[body_start..]
+            .find(|c| !matches!(c, '-' | '=' | '.'))
+            .unwrap_or(text.len() - body_start);
+        let body = &text[body_start..body_start + body_len];
+        let mut end = body_start + body_len;
+        if body_len < 2 {
+            return Err(unsupported(
+                "flowchart syntax",
+                line.span(start..text.len()),
+                "expected a link such as `-->`; convert this line by hand",
+            ));
+        }
+
+        let mut head = text[end..].starts_with('>');
+        let mut label = None;
+        if head {
+            end += 1;
+        } else if text[end..].starts_with(['x', 'o'])
+            && text[end + 1..].starts_with(char::is_whitespace)
+        {
+            return Err(unsupported(
+                format_args!("link `{}`", &text[start..end + 1]),
+                line.span(start..end + 1),
+                "only arrow and line links are converted",
+            ));
+        } else if let Some((_, closings)) = LABELED_LINKS
+            .iter()
+            .find(|(open, _)| !tail && *open == body)
+        {
+            // `a -- label --> b`: the label sits between the two arrow parts
+            let closing = closings
+                .iter()
+                .filter_map(|closing| text[end..].find(closing).map(|i| (end + i, *closing)))
+                .min_by_key(|(i, _)| *i);
+            if let Some((closing_start, closing)) = closing {
+                let range = line.trimmed(end);
+                let range =
+                    range.start..range.start + text[range.start..closing_start].trim_end().len();
+                label = Some(Spanned::new(
+                    label_text(line.text(range.clone())),
+                    line.span(range),
+                ));
+                end = closing_start + closing.len();
+                head = closing.ends_with('>');
+            }
+        }
+
+        let span = line.span(start..end);
+        let label_start = end + skip_whitespace(&text[end..]);
+        if label.is_none()
+            && let Some(inner) = text[label_start..].strip_prefix('|')
+        {
+            let Some(len) = inner.find('|') else {
+                return Err(unsupported(
+                    "link label",
+                    line.span(label_start..text.len()),
+                    "the link label is not closed with `|`",
+                ));
+            };
+            let range = label_start + 1..label_start + 1 + len;
+            label = Some(Spanned::new(
+                label_text(line.text(range.clone()).trim()),
+                line.span(range),
+            ));
+            end = label_start + len + 2;
+        }
+        *cursor = end;
+
+        let relation_type = match (tail, head) {
+            (true, true) => "<->",
+            (false, true) => "->",
+            (true, false) => "<-",
+            (false, false) => "-",
+        };
+        Ok(Link {
+            relation_type,
+            dashed: body.contains('.'),
+            label,
+            span,
+        })
+    }
+}
+
+/// Returns the type specifier of a relation, with a dashed stroke if
+/// `dashed` is set.
+fn relation_type_spec<'a>(dashed: bool, span: Span) -> TypeSpec<'a> {
+    if !dashed {
+        return TypeSpec::default();
+    }
+    let style = Attribute {
+        name: Spanned::new("style", span),
+        value: AttributeValue::String(Spanned::new("dashed".to_string(), span)),
+    };
+    TypeSpec {
+        type_name: None,
+        attributes: vec![Attribute {
+            name: Spanned::new("stroke", span),
+            value: AttributeValue::TypeSpec(TypeSpec {
+                type_name: None,
+                attributes: vec![style],
+            }),
+        }],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use orrery_core::{
+        draw::{ArrowDirection, StrokeStyle},
+        semantic::{Diagram, Element as SemanticElement},
+    };
+
+    use super::*;
+
+    fn convert_source(source: &str) -> Result<Diagram, Vec<(ErrorCode, String)>> {
+        let arena = Bump::new();
+        parse(&arena, "test.mmd", source, ElaborateConfig::default())
+            .map(Parsed::into_diagram)
+            .map_err(|err| {
+                let source_map = err.source_map();
+                err.diagnostics()
+                    .iter()
+                    .map(|diag| {
+                        let span = diag.labels()[0].span();
+                        let text = source_map.source_slice(span).unwrap_or_default();
+                        (diag.code().expect("diagnostic code"), text.to_string())
+                    })
+                    .collect()
+            })
+    }
+
+    /// Summarizes the elements of `diagram`, one line each.
+    fn summary(diagram: &Diagram) -> Vec<String> {
+        diagram
+            .scope()
+            .elements()
+            .iter()
+            .map(|element| match element {
+                SemanticElement::Node(node) => format!("{}: {}", node.id(), node.display_text()),
+                SemanticElement::Relation(relation) => {
+                    let arrow = match relation.arrow_direction() {
+                        ArrowDirection::Forward => "->",
+                        ArrowDirection::Backward => "<-",
+                        ArrowDirection::Bidirectional => "<->",
+                        ArrowDirection::Plain => "-",
+                        ArrowDirection::AsyncForward => "->>",
+                        ArrowDirection::AsyncBackward => "<<-",
+                    };
+                    let dashed = match relation.arrow_definition().stroke().style() {
+                        StrokeStyle::Dashed => " dashed",
+                        _ => "",
+                    };
+                    let label = relation.label().unwrap_or_default();
+                    format!(
+                        "{} {arrow} {}{dashed} {label:?}",
+                        relation.source(),
+                        relation.target()
+                    )
+                }
+                SemanticElement::Activate(activate) => format!("activate {}", activate.component()),
+                SemanticElement::Deactivate(id) => format!("deactivate {id}"),
+                other => panic!("unexpected element {other:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_sequence_diagram() {
+        let diagram = convert_source(
+            "%% checkout flow
+            sequenceDiagram
+                actor U as \"Customer\"
+                participant S as Shop
+                U->>+S: Buy<br>now
+                S-->>-U: Receipt
+                S-)Bank: charge
+                Bank--)S: ok;
+                U->S
+                activate Bank
+                deactivate Bank
+            ",
+        )
+        .expect("supported sequence diagram");
+
+        assert_eq!(diagram.kind(), DiagramKind::Sequence);
+        assert_eq!(
+            summary(&diagram),
+            [
+                "U: Customer",
+                "S: Shop",
+                "U -> S \"Buy\\nnow\"",
+                "activate S",
+                "S -> U dashed \"Receipt\"",
+                "deactivate S",
+                "Bank: Bank",
+                "S ->> Bank \"charge\"",
+                "Bank ->> S dashed \"ok\"",
+                "U - S \"\"",
+                "activate Bank",
+                "deactivate Bank",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_flowchart() {
+        let diagram = convert_source(
+            "flowchart LR
+                start((Start)) --> check(Check) -->|\"valid\"| done[Done]
+                check -. retry .-> start
+                done <--> archive([Archive])
+                archive --- start",
+        )
+        .expect("supported flowchart");
+
+        assert_eq!(diagram.kind(), DiagramKind::Component);
+        assert_eq!(
+            summary(&diagram),
+            [
+                "start: Start",
+                "check: Check",
+                "start -> check \"\"",
+                "done: Done",
+                "check -> done \"valid\"",
+                "check -> start dashed \"retry\"",
+                "archive: Archive",
+                "done <-> archive \"\"",
+                "archive - start \"\"",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unsupported_constructs_are_reported_per_line() {
+        let errors = convert_source(
+            "sequenceDiagram
+                participant A
+                Note right of A: thinking
+                A->>B: hi
+                loop Every minute
+                A-xB: lost
+            ",
+        )
+        .expect_err("unsupported constructs");
+
+        assert_eq!(
+            errors,
+            [
+                (ErrorCode::E501, "Note right of A: thinking".to_string()),
+                (ErrorCode::E501, "loop Every minute".to_string()),
+                (ErrorCode::E501, "A-xB: lost".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unsupported_flowchart_constructs() {
+        let errors = convert_source(
+            "graph TD
+                subgraph one
+                a{Decide} --> b
+                a --x c
+                end",
+        )
+        .expect_err("unsupported constructs");
+
+        assert_eq!(
+            errors,
+            [
+                (ErrorCode::E501, "subgraph one".to_string()),
+                (ErrorCode::E501, "{Decide}".to_string()),
+                (ErrorCode::E501, "--x".to_string()),
+                (ErrorCode::E501, "end".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unsupported_diagram_type() {
+        let errors = convert_source("classDiagram\n  Animal <|-- Duck").expect_err("class diagram");
+        assert_eq!(errors, [(ErrorCode::E500, "classDiagram".to_string())]);
+
+        let errors = convert_source("%% only a comment\n").expect_err("empty diagram");
+        assert_eq!(errors[0].0, ErrorCode::E500);
+    }
+}
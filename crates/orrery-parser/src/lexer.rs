@@ -6,22 +6,23 @@
 //!
 //! The public entry point is [`tokenize`], which performs error-recovering
 //! lexical analysis and collects all diagnostics in a single pass.
+//! [`highlight`] builds on it to classify every byte of a file for editors.
 
 use std::char;
 
 use winnow::{
     Parser as _,
-    ascii::{float, multispace1},
-    combinator::{alt, cut_err, delimited, not, peek, preceded, repeat, terminated},
+    ascii::{digit0, digit1, float, multispace1},
+    combinator::{alt, cut_err, not, opt, peek, preceded, repeat, terminated},
     error::{AddContext, ContextError, ErrMode, ModalResult},
     stream::{LocatingSlice, Location, Stream},
-    token::{literal, none_of, one_of, take_while},
+    token::{any, literal, none_of, one_of, take_while},
 };
 
 use crate::{
     error::{Diagnostic, DiagnosticCollector, ErrorCode},
     span::Span,
-    tokens::{PositionedToken, Token},
+    tokens::{PositionedToken, Token, TokenCategory},
 };
 
 /// Rich diagnostic information for lexer errors.
@@ -40,6 +41,14 @@ struct LexerDiagnostic {
 type Input<'a> = LocatingSlice<&'a str>;
 type IResult<'a, O> = ModalResult<O, ContextError<LexerDiagnostic>>;
 
+/// Builds a committed lexer error ending at the current input position.
+fn cut_error<'a>(
+    input: &Input<'a>,
+    diagnostic: LexerDiagnostic,
+) -> ErrMode<ContextError<LexerDiagnostic>> {
+    ErrMode::Cut(ContextError::new().add_context(input, &input.checkpoint(), diagnostic))
+}
+
 /// Parse a unicode escape sequence in a string: `\u{XXXX}` where XXXX is 1-6 hex digits.
 ///
 /// This parser handles the portion after the backslash, starting with 'u'.
@@ -49,48 +58,55 @@ type IResult<'a, O> = ModalResult<O, ContextError<LexerDiagnostic>>;
 /// - Codepoint: must be valid Unicode (0x0000-0xD7FF or 0xE000-0x10FFFF)
 ///
 /// Takes `escape_start` position (before `\`) for error span calculation.
-/// Uses `cut_err` after 'u' to commit and preserve diagnostic context.
+/// After 'u' every failure is a cut error whose span covers the whole escape
+/// read so far, e.g. all of `\u{GG}`.
 fn string_escape_unicode<'a>(input: &mut Input<'a>, escape_start: usize) -> IResult<'a, char> {
-    preceded(
-        'u',
-        cut_err(
-            delimited(
-                '{',
-                take_while(1..=6, |c: char| c.is_ascii_hexdigit()).context(LexerDiagnostic {
-                    code: ErrorCode::E006,
-                    message: "empty unicode escape",
-                    help: Some("provide 1-6 hex digits: `\\u{1F602}`"),
+    'u'.parse_next(input)?;
+
+    let invalid = LexerDiagnostic {
+        code: ErrorCode::E004,
+        message: "invalid unicode escape",
+        help: Some("use format `\\u{XXXX}` with 1-6 hex digits"),
+        start: escape_start,
+    };
+
+    if opt('{').parse_next(input)?.is_none() {
+        return Err(cut_error(input, invalid));
+    }
+    let digits = take_while(0.., |c: char| c.is_ascii_alphanumeric()).parse_next(input)?;
+    if opt('}').parse_next(input)?.is_none() {
+        return Err(cut_error(input, invalid));
+    }
+
+    if digits.is_empty() {
+        return Err(cut_error(
+            input,
+            LexerDiagnostic {
+                code: ErrorCode::E006,
+                message: "empty unicode escape",
+                help: Some("provide 1-6 hex digits: `\\u{1F602}`"),
+                start: escape_start,
+            },
+        ));
+    }
+    if digits.len() > 6 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(cut_error(input, invalid));
+    }
+
+    u32::from_str_radix(digits, 16)
+        .ok()
+        .and_then(char::from_u32)
+        .ok_or_else(|| {
+            cut_error(
+                input,
+                LexerDiagnostic {
+                    code: ErrorCode::E005,
+                    message: "invalid unicode codepoint",
+                    help: Some("valid range: `0x0000`-`0xD7FF` or `0xE000`-`0x10FFFF`"),
                     start: escape_start,
-                }),
-                '}',
+                },
             )
-            .context(LexerDiagnostic {
-                code: ErrorCode::E004,
-                message: "invalid unicode escape",
-                help: Some("use format `\\u{XXXX}` with 1-6 hex digits"),
-                start: escape_start,
-            })
-            .verify(|hex_str: &str| {
-                u32::from_str_radix(hex_str, 16)
-                    .ok()
-                    .and_then(char::from_u32)
-                    .is_some()
-            })
-            .context(LexerDiagnostic {
-                code: ErrorCode::E005,
-                message: "invalid unicode codepoint",
-                help: Some("valid range: `0x0000`-`0xD7FF` or `0xE000`-`0x10FFFF`"),
-                start: escape_start,
-            })
-            .map(|hex_str: &str| {
-                u32::from_str_radix(hex_str, 16)
-                    .ok()
-                    .and_then(char::from_u32)
-                    .expect("verified hex digits form valid unicode codepoint")
-            }),
-        ),
-    )
-    .parse_next(input)
+        })
 }
 
 /// Parse a standard escape character in a string after the backslash.
@@ -146,10 +162,10 @@ fn string_escape<'a>(input: &mut Input<'a>) -> IResult<'a, char> {
         return Ok(ch);
     }
 
-    // None matched - return error with context for invalid escape
-    Err(ErrMode::Cut(ContextError::new().add_context(
+    // None matched - include the offending character in the error span
+    let _ = opt(any).parse_next(input)?;
+    Err(cut_error(
         input,
-        &input.checkpoint(),
         LexerDiagnostic {
             code: ErrorCode::E003,
             message: "invalid escape sequence",
@@ -158,7 +174,7 @@ fn string_escape<'a>(input: &mut Input<'a>) -> IResult<'a, char> {
             ),
             start: escape_start,
         },
-    )))
+    ))
 }
 
 /// Parse a complete string literal with double quotes.
@@ -204,6 +220,104 @@ fn string_literal<'a>(input: &mut Input<'a>) -> IResult<'a, Token<'a>> {
         .map(Token::StringLiteral)
 }
 
+/// Parse a triple-quoted string literal that can span several lines.
+///
+/// The content keeps its line breaks and supports the same escape sequences
+/// as [`string_literal`]; see [`dedent`] for how indentation is handled.
+fn multiline_string_literal<'a>(input: &mut Input<'a>) -> IResult<'a, Token<'a>> {
+    let string_content = repeat(0.., alt((string_escape, preceded(not(r#"""""#), any)))).fold(
+        String::new,
+        |mut acc, ch| {
+            if ch != '\u{E000}' {
+                // Filter out escaped whitespace placeholders
+                acc.push(ch);
+            }
+            acc
+        },
+    );
+
+    let start_pos = input.current_token_start();
+
+    r#"""""#.parse_next(input)?;
+
+    cut_err(terminated(string_content, r#"""""#))
+        .context(LexerDiagnostic {
+            code: ErrorCode::E001,
+            message: "unterminated multiline string literal",
+            help: Some(r#"add closing `"""`"#),
+            start: start_pos,
+        })
+        .parse_next(input)
+        .map(|content| Token::StringLiteral(dedent(&content)))
+}
+
+/// Strips the common indentation from the lines of a multiline string.
+///
+/// A line break right after the opening quotes and the last line before the
+/// closing quotes are dropped when they are blank, so that both delimiters
+/// can sit on their own lines. Lines are then dedented by the indentation of
+/// the least-indented non-blank line, ignoring a first line that follows the
+/// opening quotes directly. Blank lines are kept as empty lines.
+fn dedent(content: &str) -> String {
+    let content = content.replace("\r\n", "\n");
+    let (content, skip_first) = match content.strip_prefix('\n') {
+        Some(rest) => (rest, false),
+        None => (content.as_str(), true),
+    };
+    let content = match content.rfind('\n') {
+        Some(last) if content[last + 1..].trim().is_empty() => &content[..last],
+        _ => content,
+    };
+
+    let is_indent = |c: char| c == ' ' || c == '\t';
+    let indent = content
+        .lines()
+        .skip(usize::from(skip_first))
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start_matches(is_indent).len())
+        .min()
+        .unwrap_or(0);
+
+    content
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            if line.trim().is_empty() {
+                ""
+            } else if i == 0 && skip_first {
+                line
+            } else {
+                &line[indent..]
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Parse a dimension literal: a float immediately followed by a unit suffix,
+/// such as `2px`, `-10pt` or `50%`.
+///
+/// Any alphabetic suffix is accepted here so that validation can report an
+/// unknown unit with a precise span.
+fn dimension_literal<'a>(input: &mut Input<'a>) -> IResult<'a, Token<'a>> {
+    // The number is recognized by hand rather than with `float`, so that the
+    // `e` of a unit like `em` is not mistaken for an exponent.
+    let number = (
+        opt('-'),
+        alt(((digit1, opt(('.', digit0))).void(), ('.', digit1).void())),
+        opt((one_of(['e', 'E']), opt(one_of(['+', '-'])), digit1)),
+    )
+        .take()
+        .try_map(str::parse::<f32>);
+    (
+        number,
+        alt(("%", take_while(1.., |c: char| c.is_ascii_alphabetic()))),
+        peek(not(one_of(|c: char| c.is_alphanumeric() || c == '_'))),
+    )
+        .map(|(f, unit, _)| Token::DimensionLiteral(f, unit))
+        .parse_next(input)
+}
+
 /// Parse a float literal
 fn float_literal<'a>(input: &mut Input<'a>) -> IResult<'a, Token<'a>> {
     // Parse float but ensure it's not followed by identifier characters
@@ -223,6 +337,46 @@ fn line_comment<'a>(input: &mut Input<'a>) -> IResult<'a, Token<'a>> {
         .parse_next(input)
 }
 
+/// Parse a block comment delimited by `/*` and `*/`.
+///
+/// Block comments nest, so a region that already contains comments can be
+/// commented out. An unterminated comment is a cut error pointing at its
+/// opening `/*`.
+fn block_comment<'a>(input: &mut Input<'a>) -> IResult<'a, Token<'a>> {
+    let start_pos = input.current_token_start();
+    "/*".parse_next(input)?;
+
+    let body_start = input.checkpoint();
+    let body = input.peek_finish();
+    let mut depth = 1_usize;
+    while depth > 0 {
+        if input.is_empty() {
+            input.reset(&body_start);
+            return Err(cut_error(
+                input,
+                LexerDiagnostic {
+                    code: ErrorCode::E007,
+                    message: "unterminated block comment",
+                    help: Some("add closing `*/`"),
+                    start: start_pos,
+                },
+            ));
+        }
+        if input.starts_with("/*") {
+            input.next_slice(2);
+            depth += 1;
+        } else if input.starts_with("*/") {
+            input.next_slice(2);
+            depth -= 1;
+        } else {
+            input.next_token();
+        }
+    }
+
+    let body_len = body.len() - input.eof_offset() - "*/".len();
+    Ok(Token::BlockComment(&body[..body_len]))
+}
+
 /// Parse keywords with word boundary checking
 fn keyword<'a>(input: &mut Input<'a>) -> IResult<'a, Token<'a>> {
     terminated(
@@ -295,10 +449,22 @@ fn identifier<'a>(input: &mut Input<'a>) -> IResult<'a, Token<'a>> {
     .parse_next(input)
 }
 
+/// Parse a variable reference: `$name`
+fn variable<'a>(input: &mut Input<'a>) -> IResult<'a, Token<'a>> {
+    preceded('$', identifier)
+        .map(|token| match token {
+            Token::Identifier(name) => Token::Variable(name),
+            _ => unreachable!(),
+        })
+        .parse_next(input)
+}
+
 /// Parses multi-character operators (order matters — longest first).
 fn multi_char_operator<'a>(input: &mut Input<'a>) -> IResult<'a, Token<'a>> {
     alt((
         literal("<->").value(Token::DoubleArrow),
+        literal("->>").value(Token::AsyncArrow),
+        literal("<<-").value(Token::AsyncLeftArrow),
         literal("->").value(Token::Arrow_),
         literal("<-").value(Token::LeftArrow),
         literal("::").value(Token::DoubleColon),
@@ -342,15 +508,18 @@ fn positioned_token<'a>(input: &mut Input<'a>) -> IResult<'a, PositionedToken<'a
     let start_pos = input.current_token_start();
 
     let token = alt((
-        line_comment,        // Must come before single char '-'
-        string_literal,      // Must come before any single char
+        // Comments must come before single char '-' and '*'
+        alt((line_comment, block_comment)),
+        // Triple quotes must come before plain strings, both before any single char
+        alt((multiline_string_literal, string_literal)),
         multi_char_operator, // Must come before single char operators
         keyword,             // Must come before identifier
-        float_literal,       // Must come before identifier
-        identifier,          // Must come before single chars
-        single_char_token,   // Single character tokens
-        newline,             // Must come before whitespace
-        whitespace,          // General whitespace
+        // Dimensions must come before plain floats, both before identifier
+        alt((dimension_literal, float_literal)),
+        alt((identifier, variable)), // Must come before single chars
+        single_char_token,           // Single character tokens
+        newline,                     // Must come before whitespace
+        whitespace,                  // General whitespace
     ))
     .parse_next(input)?;
 
@@ -360,6 +529,26 @@ fn positioned_token<'a>(input: &mut Input<'a>) -> IResult<'a, PositionedToken<'a
     Ok(PositionedToken::new(token, span))
 }
 
+/// Skips the rest of a string literal up to and including its closing quotes.
+///
+/// A regular string also ends at a line break, which is left in the input.
+fn skip_string_rest(input: &mut Input<'_>, multiline: bool) {
+    let closing = if multiline { r#"""""# } else { "\"" };
+    while !input.is_empty() {
+        if input.starts_with(closing) {
+            input.next_slice(closing.len());
+            return;
+        }
+        if !multiline && input.starts_with('\n') {
+            return;
+        }
+        if input.starts_with('\\') {
+            input.next_token();
+        }
+        input.next_token();
+    }
+}
+
 /// Lexer that accumulates tokens and diagnostics during tokenization.
 struct Lexer<'a> {
     tokens: Vec<PositionedToken<'a>>,
@@ -383,6 +572,7 @@ impl<'a> Lexer<'a> {
     /// Token and error spans are positioned relative to `base_offset`.
     fn tokenize(&mut self, mut input: Input<'a>) {
         while !input.is_empty() {
+            let at_multiline_string = input.starts_with(r#"""""#);
             match positioned_token(&mut input) {
                 Ok(mut token) => {
                     token.span = token.span.shift(self.base_offset);
@@ -393,13 +583,20 @@ impl<'a> Lexer<'a> {
                     let error_pos = input.current_token_start();
 
                     let diagnostic = self.convert_err_mode(e, error_pos);
+                    let code = diagnostic.code();
                     self.diagnostics.emit(diagnostic);
 
-                    // FIXME: Simple single-character skip causes cascading errors for
-                    // string escape failures. E.g., `"test\u{}"` produces E006 (empty
-                    // unicode escape) then E001 (unterminated string) because after
-                    // skipping one char, the closing `"` starts a new unterminated string.
-                    if !input.is_empty() {
+                    if code == Some(ErrorCode::E007) {
+                        // An unterminated block comment runs to the end of the file.
+                        input.finish();
+                    } else if matches!(
+                        code,
+                        Some(ErrorCode::E003 | ErrorCode::E004 | ErrorCode::E005 | ErrorCode::E006)
+                    ) {
+                        // An invalid escape leaves the rest of its string behind;
+                        // skip it so the closing quote does not open a new string.
+                        skip_string_rest(&mut input, at_multiline_string);
+                    } else if !input.is_empty() {
                         input.next_token();
                     }
                 }
@@ -407,9 +604,15 @@ impl<'a> Lexer<'a> {
         }
     }
 
+    /// Finishes lexing and returns the tokens, discarding any errors.
+    fn into_tokens(self) -> Vec<PositionedToken<'a>> {
+        self.tokens
+    }
+
     /// Finishes lexing and returns tokens or collected errors.
     fn finish(self) -> Result<Vec<PositionedToken<'a>>, Vec<Diagnostic>> {
-        self.diagnostics.finish().map(|()| self.tokens)
+        // The lexer only reports errors, so there are no warnings to return.
+        self.diagnostics.finish().map(|_| self.tokens)
     }
 
     /// Converts a winnow `ErrMode` and file-local error position to a [`Diagnostic`].
@@ -480,6 +683,44 @@ pub fn tokenize(
     lexer.finish()
 }
 
+/// Splits source text into highlighting categories.
+///
+/// Unlike [`tokenize`], this never fails: comments and whitespace are
+/// included, and text the lexer rejects is reported as
+/// [`TokenCategory::Invalid`], so the returned spans cover the whole of
+/// `source` in order.
+///
+/// # Examples
+///
+/// ```
+/// use orrery_parser::{Span, highlight, tokens::TokenCategory};
+///
+/// let spans = highlight("a: Oval; // db");
+/// assert_eq!(spans[0], (Span::new(0..1), TokenCategory::Identifier));
+/// assert_eq!(spans.last(), Some(&(Span::new(9..14), TokenCategory::Comment)));
+/// ```
+pub fn highlight(source: &str) -> Vec<(Span, TokenCategory)> {
+    let mut lexer = Lexer::new(0);
+    lexer.tokenize(LocatingSlice::new(source));
+
+    let mut spans = Vec::new();
+    let mut covered = 0;
+    for token in lexer.into_tokens() {
+        if token.span.start() > covered {
+            spans.push((
+                Span::new(covered..token.span.start()),
+                TokenCategory::Invalid,
+            ));
+        }
+        covered = token.span.end();
+        spans.push((token.span, token.category()));
+    }
+    if covered < source.len() {
+        spans.push((Span::new(covered..source.len()), TokenCategory::Invalid));
+    }
+    spans
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -516,6 +757,15 @@ mod tests {
         test_single_token("note", Token::Note);
     }
 
+    #[test]
+    fn test_variables() {
+        test_single_token("$brand", Token::Variable("brand"));
+        test_single_token("$_gap2", Token::Variable("_gap2"));
+        test_single_token("letter", Token::Identifier("letter"));
+        assert!(tokenize("$", 0).is_err());
+        assert!(tokenize("$1", 0).is_err());
+    }
+
     #[test]
     fn test_identifiers() {
         test_single_token("hello", Token::Identifier("hello"));
@@ -551,6 +801,8 @@ mod tests {
         test_single_token("<->", Token::DoubleArrow);
         test_single_token("->", Token::Arrow_);
         test_single_token("<-", Token::LeftArrow);
+        test_single_token("->>", Token::AsyncArrow);
+        test_single_token("<<-", Token::AsyncLeftArrow);
         test_single_token("-", Token::Plain);
         test_single_token("=", Token::Equals);
         test_single_token(":", Token::Colon);
@@ -611,6 +863,61 @@ mod tests {
         test_single_token("123", Token::FloatLiteral(123.0));
     }
 
+    #[test]
+    fn test_dimension_literals() {
+        test_single_token("2px", Token::DimensionLiteral(2.0, "px"));
+        test_single_token("1.5pt", Token::DimensionLiteral(1.5, "pt"));
+        test_single_token("50%", Token::DimensionLiteral(50.0, "%"));
+
+        // Unknown units still lex so that validation can report them
+        test_single_token("2em", Token::DimensionLiteral(2.0, "em"));
+
+        // The exponent belongs to the number, not to the unit
+        test_single_token("1e2px", Token::DimensionLiteral(100.0, "px"));
+        test_single_token("1e2", Token::FloatLiteral(100.0));
+
+        // A leading minus belongs to the number
+        test_single_token("-2px", Token::DimensionLiteral(-2.0, "px"));
+        test_single_token("-1.5e1pt", Token::DimensionLiteral(-15.0, "pt"));
+    }
+
+    #[test]
+    fn test_negative_and_scientific_floats_span_the_whole_literal() {
+        for (input, expected) in [("-2.5", -2.5), ("1e3", 1e3), ("1.5e-2", 1.5e-2)] {
+            let source = format!("label_offset={input};");
+            let tokens = tokenize(&source, 0).expect("Should tokenize");
+            let tokens: Vec<_> = tokens.iter().map(|t| (&t.token, t.span)).collect();
+
+            let start = "label_offset=".len();
+            assert_eq!(
+                tokens[2],
+                (
+                    &Token::FloatLiteral(expected),
+                    Span::new(start..start + input.len())
+                ),
+                "{input}"
+            );
+            assert_eq!(tokens[3].0, &Token::Semicolon);
+        }
+    }
+
+    #[test]
+    fn test_minus_between_identifiers_is_a_plain_relation() {
+        for source in ["a-b", "a - b"] {
+            let tokens: Vec<_> = tokenize(source, 0)
+                .expect("Should tokenize")
+                .into_iter()
+                .map(|t| t.token)
+                .filter(|token| *token != Token::Whitespace)
+                .collect();
+            assert_eq!(
+                tokens,
+                [Token::Identifier("a"), Token::Plain, Token::Identifier("b")],
+                "{source}"
+            );
+        }
+    }
+
     #[test]
     fn test_float_inf_vs_identifiers() {
         // Special float values when standalone (followed by non-identifier chars)
@@ -657,6 +964,18 @@ mod tests {
             "\"backslash: \\\\\"",
             Token::StringLiteral("backslash: \\".to_string()),
         );
+        test_single_token(
+            "\"cr:\\rnul:\\0\"",
+            Token::StringLiteral("cr:\rnul:\0".to_string()),
+        );
+        test_single_token(
+            "\"\\b\\f\\/\\'\"",
+            Token::StringLiteral("\u{08}\u{0C}/'".to_string()),
+        );
+        test_single_token(
+            "\"name\\t\\\"quoted\\\"\\u{2192}\"",
+            Token::StringLiteral("name\t\"quoted\"\u{2192}".to_string()),
+        );
     }
 
     #[test]
@@ -743,6 +1062,42 @@ mod tests {
         test_single_token("\"\"", Token::StringLiteral("".to_string()));
     }
 
+    #[test]
+    fn test_multiline_string_literals() {
+        // Single line, quotes and escapes inside
+        test_single_token(
+            r#""""say "hi"\tthere""""#,
+            Token::StringLiteral("say \"hi\"\tthere".to_string()),
+        );
+
+        // Delimiters on their own lines, common indentation stripped
+        test_single_token(
+            "\"\"\"\n        First line\n          indented\n        last\n    \"\"\"",
+            Token::StringLiteral("First line\n  indented\nlast".to_string()),
+        );
+
+        // Blank lines between paragraphs are kept, whitespace-only ones emptied
+        test_single_token(
+            "\"\"\"\n    One\n\n      \n    Two\n\"\"\"",
+            Token::StringLiteral("One\n\n\nTwo".to_string()),
+        );
+
+        // A first line right after the opening quotes does not count for indentation
+        test_single_token(
+            "\"\"\"Title\n    body\n    more\"\"\"",
+            Token::StringLiteral("Title\nbody\nmore".to_string()),
+        );
+
+        // CRLF line endings are normalized
+        test_single_token(
+            "\"\"\"\r\n  a\r\n  b\r\n\"\"\"",
+            Token::StringLiteral("a\nb".to_string()),
+        );
+
+        // An empty pair of quotes is still an empty string
+        test_single_token("\"\"", Token::StringLiteral("".to_string()));
+    }
+
     #[test]
     fn test_mixed_content_advanced() {
         // Test combination of escaped whitespace, unicode, and regular escapes
@@ -792,6 +1147,21 @@ mod tests {
         test_single_token("//no space", Token::LineComment("no space"));
     }
 
+    #[test]
+    fn test_block_comments() {
+        test_single_token("/* note */", Token::BlockComment(" note "));
+        test_single_token("/**/", Token::BlockComment(""));
+        test_single_token("/* a\nb */", Token::BlockComment(" a\nb "));
+        test_single_token(
+            "/* outer /* inner */ still outer */",
+            Token::BlockComment(" outer /* inner */ still outer "),
+        );
+
+        let tokens = tokenize("a /* x */* b", 0).unwrap();
+        assert_eq!(tokens[2].token, Token::BlockComment(" x "));
+        assert_eq!(tokens[3].token, Token::Star);
+    }
+
     #[test]
     fn test_whitespace() {
         test_single_token(" ", Token::Whitespace);
@@ -1006,6 +1376,9 @@ mod tests {
 
             // Unicode escape spanning lines
             test_lexer_error_at_position("\"test\\u{\n1F602}\"", 8);
+
+            // Unterminated triple-quoted string - error at end of input
+            test_lexer_error_at_position("\"\"\"hello\nworld\"\"", 16);
         }
 
         #[test]
@@ -1054,27 +1427,71 @@ mod tests {
 
         #[test]
         fn test_error_code_e003_invalid_escape_sequence() {
-            // Invalid escape produces E003, then cascading E001 (see FIXME in recovery code)
-            assert_error_codes("\"test\\x\"", &[ErrorCode::E003, ErrorCode::E001]);
-            assert_error_codes("\"test\\q\"", &[ErrorCode::E003, ErrorCode::E001]);
-            assert_error_codes("\"test\\z\"", &[ErrorCode::E003, ErrorCode::E001]);
-            assert_error_codes("\"test\\1\"", &[ErrorCode::E003, ErrorCode::E001]);
+            assert_error_codes("\"test\\x\"", &[ErrorCode::E003]);
+            assert_error_codes("\"test\\q\"", &[ErrorCode::E003]);
+            assert_error_codes("\"test\\z\"", &[ErrorCode::E003]);
+            assert_error_codes("\"test\\1\"", &[ErrorCode::E003]);
+        }
+
+        #[test]
+        fn test_error_code_e004_invalid_unicode_escape() {
+            assert_error_codes("\"test\\u{GG}\"", &[ErrorCode::E004]);
+            assert_error_codes("\"test\\u{1234567}\"", &[ErrorCode::E004]);
+            assert_error_codes("\"test\\u{41\"", &[ErrorCode::E004]);
+            assert_error_codes("\"test\\u41\"", &[ErrorCode::E004]);
         }
 
         #[test]
         fn test_error_code_e005_invalid_unicode_codepoint() {
-            // Invalid codepoint produces E005, then cascading E001 (see FIXME in recovery code)
-            assert_error_codes("\"test\\u{110000}\"", &[ErrorCode::E005, ErrorCode::E001]);
-            assert_error_codes("\"test\\u{FFFFFF}\"", &[ErrorCode::E005, ErrorCode::E001]);
+            assert_error_codes("\"test\\u{110000}\"", &[ErrorCode::E005]);
+            assert_error_codes("\"test\\u{FFFFFF}\"", &[ErrorCode::E005]);
             // Surrogate range
-            assert_error_codes("\"test\\u{D800}\"", &[ErrorCode::E005, ErrorCode::E001]);
-            assert_error_codes("\"test\\u{DFFF}\"", &[ErrorCode::E005, ErrorCode::E001]);
+            assert_error_codes("\"test\\u{D800}\"", &[ErrorCode::E005]);
+            assert_error_codes("\"test\\u{DFFF}\"", &[ErrorCode::E005]);
         }
 
         #[test]
         fn test_error_code_e006_empty_unicode_escape() {
-            // Empty unicode escape produces E006, then cascading E001 (see FIXME in recovery code)
-            assert_error_codes("\"test\\u{}\"", &[ErrorCode::E006, ErrorCode::E001]);
+            assert_error_codes("\"test\\u{}\"", &[ErrorCode::E006]);
+        }
+
+        #[test]
+        fn test_error_code_e007_unterminated_block_comment() {
+            assert_error_codes("a: Rectangle; /* never closed", &[ErrorCode::E007]);
+            // The inner comment is closed, the outer one is not.
+            assert_error_codes("/* outer /* inner */ \"x", &[ErrorCode::E007]);
+
+            let diagnostics = tokenize("a; /* x\n/* y */", 0).unwrap_err();
+            let span = diagnostics[0].labels()[0].span();
+            assert_eq!((span.start(), span.end()), (3, 5));
+        }
+
+        #[test]
+        fn test_invalid_escape_spans_cover_the_escape() {
+            let span_of = |input: &str| {
+                let diagnostics = tokenize(input, 0).unwrap_err();
+                assert_eq!(diagnostics.len(), 1, "input: {input}");
+                diagnostics[0].labels()[0].span()
+            };
+
+            // `\x` in `"a\x"`
+            assert_eq!(span_of("\"a\\x\""), Span::new(2..4));
+            // `\u{GG}` in `"a\u{GG}"`
+            assert_eq!(span_of("\"a\\u{GG}\""), Span::new(2..8));
+            // `\u{110000}` in `"a\u{110000}"`
+            assert_eq!(span_of("\"a\\u{110000}\""), Span::new(2..12));
+        }
+
+        #[test]
+        fn test_invalid_escape_recovers_after_string() {
+            // The rest of the string is skipped, so the tokens after it still lex
+            // and a second invalid escape is reported on its own.
+            let diagnostics = tokenize("a: \"x\\q\\\"y\"; b: \"\\u{}\";", 0).unwrap_err();
+            let codes: Vec<_> = diagnostics.iter().map(|d| d.code()).collect();
+            assert_eq!(codes, [Some(ErrorCode::E003), Some(ErrorCode::E006)]);
+
+            let diagnostics = tokenize("\"\"\"one\\q\ntwo \" three\"\"\" x", 0).unwrap_err();
+            assert_eq!(diagnostics.len(), 1);
         }
 
         #[test]
@@ -1183,6 +1600,27 @@ mod tests {
             label.span().start()
         );
     }
+
+    #[test]
+    fn highlight_classifies_tokens_and_covers_invalid_text() {
+        let spans = highlight("diagram $ \"x\" 2px;");
+        let categories: Vec<_> = spans.iter().map(|(_, category)| *category).collect();
+
+        assert_eq!(
+            categories,
+            vec![
+                TokenCategory::Keyword,
+                TokenCategory::Whitespace,
+                TokenCategory::Invalid,
+                TokenCategory::Whitespace,
+                TokenCategory::String,
+                TokenCategory::Whitespace,
+                TokenCategory::Number,
+                TokenCategory::Punctuation,
+            ]
+        );
+        assert_eq!(spans[2].0, Span::new(8..9));
+    }
 }
 
 #[cfg(test)]
@@ -1205,6 +1643,7 @@ mod proptest_tests {
                     | "component"
                     | "sequence"
                     | "note"
+                    | "highlight"
                     | "on"
                     | "left"
                     | "right"
@@ -1264,6 +1703,18 @@ mod proptest_tests {
         Ok(())
     }
 
+    /// Highlighting spans should tile the source without gaps or overlaps.
+    fn check_highlight_covers_source(source: &str) -> Result<(), TestCaseError> {
+        let mut covered = 0;
+        for (span, _) in highlight(source) {
+            prop_assert_eq!(span.start(), covered, "gap or overlap in {:?}", source);
+            prop_assert!(!span.is_empty(), "empty span in {:?}", source);
+            covered = span.end();
+        }
+        prop_assert_eq!(covered, source.len());
+        Ok(())
+    }
+
     // ===================
     // Proptest Wrappers
     // ===================
@@ -1278,5 +1729,10 @@ mod proptest_tests {
         fn float_literals_parse(float_literal in float_literal_strategy()) {
             check_float_literals_parse(&float_literal)?;
         }
+
+        #[test]
+        fn highlight_covers_source(source in "\\PC{0,40}") {
+            check_highlight_covers_source(&source)?;
+        }
     }
 }
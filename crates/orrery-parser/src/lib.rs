@@ -21,43 +21,50 @@
 //! let mut provider = InMemorySourceProvider::new();
 //! provider.add_file("main.orr", source);
 //!
-//! let diagram = parse(&arena, Path::new("main.orr"), provider, ElaborateConfig::default())
+//! let parsed = parse(&arena, Path::new("main.orr"), provider, ElaborateConfig::default())
 //!     .map_err(|e| e.to_string())?;
+//! assert!(parsed.warnings().is_empty());
+//! let diagram = parsed.into_diagram();
 //! # Ok(())
 //! # }
 //! ```
 
+pub mod attributes;
 pub mod error;
+pub mod import;
+pub mod lexer;
+pub mod lint;
 pub mod source_map;
 pub mod source_provider;
+pub mod tokens;
 
 mod builtin_types;
 mod desugar;
 mod elaborate;
 mod elaborate_utils;
 mod file_id;
-mod lexer;
+mod parsed;
 mod parser;
 #[cfg(test)]
 mod parser_tests;
 mod parser_types;
 mod resolver;
 mod span;
-mod tokens;
 mod validate;
 
-pub use elaborate::ElaborateConfig;
+pub use elaborate::{ElaborateConfig, ImplicitNodes};
+pub use lexer::highlight;
+pub use parsed::Parsed;
 pub use source_provider::{InMemorySourceProvider, SourceProvider};
-pub use span::Span;
+pub use span::{ColumnEncoding, LineIndex, Span};
 
 use std::path::Path;
 
 use bumpalo::Bump;
 
-use orrery_core::semantic::Diagram;
-
 use elaborate::Builder;
 use error::ParseError;
+use lint::{LintConfig, LintReport};
 use resolver::Resolver;
 
 /// Parse an Orrery file into a semantic diagram.
@@ -74,6 +81,11 @@ use resolver::Resolver;
 /// 3. **Validate** — Check semantic validity
 /// 4. **Elaborate** — Transform to semantic model
 ///
+/// Identifiers are interned in the global
+/// [`interner`](orrery_core::interner), which deduplicates strings. Parsing
+/// the same source again reuses the existing entries, so repeated parses do
+/// not grow the interner.
+///
 /// # Arguments
 ///
 /// * `arena` — A [`Bump`] arena that owns all source text. The arena must
@@ -86,8 +98,10 @@ use resolver::Resolver;
 ///
 /// # Returns
 ///
-/// Returns the parsed [`orrery_core::semantic::Diagram`] on success,
-/// or a [`ParseError`] with location information on failure.
+/// Returns the parsed [`orrery_core::semantic::Diagram`] together with any
+/// warnings as a [`Parsed`] on success, or a [`ParseError`] with location
+/// information on failure. Warnings never cause a failure; they include the
+/// type definitions that nothing uses, unless marked `allow_unused="true"`.
 ///
 /// # Example
 ///
@@ -100,8 +114,9 @@ use resolver::Resolver;
 /// let mut provider = InMemorySourceProvider::new();
 /// provider.add_file("main.orr", "diagram component; box: Rectangle;");
 ///
-/// let diagram = parse(&arena, Path::new("main.orr"), provider, ElaborateConfig::default())
+/// let parsed = parse(&arena, Path::new("main.orr"), provider, ElaborateConfig::default())
 ///     .map_err(|e| e.to_string())?;
+/// assert_eq!(parsed.diagram().scope().elements().len(), 1);
 /// # Ok(())
 /// # }
 /// ```
@@ -110,23 +125,96 @@ pub fn parse<'a, P: SourceProvider>(
     root_path: &Path,
     provider: P,
     config: ElaborateConfig,
-) -> Result<Diagram, ParseError<'a>> {
+) -> Result<Parsed<'a>, ParseError<'a>> {
     // Step 1: Resolve — load all files recursively via the provider
     let resolver = Resolver::new(arena, provider);
     let resolved = resolver.resolve(root_path)?;
     let (file_ast, source_map) = resolved.into_parts();
 
     // Step 2: Desugar — normalize syntax sugar, flatten imported types
-    let desugared = desugar::desugar(file_ast);
+    let desugared = desugar::desugar(file_ast, &config.theme);
 
     // Step 3: Validate — check semantic validity
-    if let Err(diags) = validate::validate(&desugared) {
-        return Err(ParseError::new(diags, source_map));
-    }
+    let mut warnings = match validate::validate(&desugared, &config) {
+        Ok(warnings) => warnings,
+        Err(diags) => return Err(ParseError::new(diags, source_map)),
+    };
 
     // Step 4: Elaborate — transform to semantic model
     let builder = Builder::new(config);
-    builder
-        .build(&desugared)
-        .map_err(|diag| ParseError::from_diagnostic(diag, source_map))
+    match builder.build(&desugared) {
+        Ok(diagram) => {
+            warnings.extend(lint::unused_types(&desugared, &source_map));
+            Ok(Parsed::new(diagram, warnings, source_map))
+        }
+        Err(diag) => Err(ParseError::from_diagnostic(diag, source_map)),
+    }
+}
+
+/// Lint an Orrery file against the rules of a [`LintConfig`].
+///
+/// Runs the complete pipeline of [`parse`] first, so any error that would
+/// prevent rendering is returned as a [`ParseError`]. The lint rules are then
+/// applied to the desugared AST, and their findings are returned as a
+/// [`LintReport`] with the severity configured for each rule, after the
+/// warnings that [`parse`] reports.
+///
+/// # Arguments
+///
+/// * `arena` — A [`Bump`] arena that owns all source text.
+/// * `root_path` — Path to the root/entry Orrery file.
+/// * `provider` — A [`SourceProvider`] implementation that resolves import
+///   paths and reads source text.
+/// * `config` — Configuration for the elaboration phase.
+/// * `lint_config` — The level of each lint rule.
+///
+/// # Example
+///
+/// ```
+/// # use std::path::Path;
+/// # use bumpalo::Bump;
+/// # use orrery_parser::{lint, ElaborateConfig, InMemorySourceProvider, lint::LintConfig};
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let arena = Bump::new();
+/// let mut provider = InMemorySourceProvider::new();
+/// provider.add_file("main.orr", "diagram component; a: Rectangle; b: Rectangle; a -> b;");
+///
+/// let report = lint(
+///     &arena,
+///     Path::new("main.orr"),
+///     provider,
+///     ElaborateConfig::default(),
+///     &LintConfig::default(),
+/// )
+/// .map_err(|e| e.to_string())?;
+/// assert!(report.diagnostics().is_empty());
+/// # Ok(())
+/// # }
+/// ```
+pub fn lint<'a, P: SourceProvider>(
+    arena: &'a Bump,
+    root_path: &Path,
+    provider: P,
+    config: ElaborateConfig,
+    lint_config: &LintConfig,
+) -> Result<LintReport<'a>, ParseError<'a>> {
+    let resolver = Resolver::new(arena, provider);
+    let resolved = resolver.resolve(root_path)?;
+    let (file_ast, source_map) = resolved.into_parts();
+
+    let desugared = desugar::desugar(file_ast, &config.theme);
+
+    let mut diagnostics = match validate::validate(&desugared, &config) {
+        Ok(warnings) => warnings,
+        Err(diags) => return Err(ParseError::new(diags, source_map)),
+    };
+
+    // Elaborate only to surface errors; the semantic model is not linted.
+    let builder = Builder::new(config);
+    if let Err(diag) = builder.build(&desugared) {
+        return Err(ParseError::from_diagnostic(diag, source_map));
+    }
+
+    diagnostics.extend(lint::lint(&desugared, lint_config, &source_map));
+    Ok(LintReport::new(diagnostics, source_map))
 }
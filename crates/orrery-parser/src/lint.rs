@@ -0,0 +1,338 @@
+//! Lint rules for Orrery diagrams.
+//!
+//! Linting runs after a diagram has passed the full parsing pipeline and
+//! reports issues that do not prevent rendering but usually indicate a
+//! mistake. Each rule is toggled through [`LintConfig`] to one of the
+//! [`LintLevel`]s `off`, `warn`, or `error`.
+//!
+//! ## Rules
+//!
+//! - **`orphan_components`**: A leaf component that is never referenced by a
+//!   relation, note, or activation in its diagram.
+//! - **`unused_types`**: A `type` defined in a diagram file that is never used
+//!   by an element or another type definition. Types brought in through
+//!   imports, built-in overrides, and definitions marked
+//!   `allow_unused="true"` are not reported. [`parse`](crate::parse) reports
+//!   the same findings as warnings regardless of the lint configuration.
+//!
+//! Unbalanced activations (`E201`/`E202`) and unknown attributes (`E303`)
+//! remain hard errors of the pipeline and are reported regardless of the lint
+//! configuration.
+
+use std::{collections::HashSet, mem};
+
+use serde::{Deserialize, Serialize};
+
+use orrery_core::identifier::Id;
+
+use crate::{
+    builtin_types,
+    error::Diagnostic,
+    parser_types::{ComponentContent, FileAst, TypeDefinition, TypeSpec},
+    source_map::SourceMap,
+    span::{Span, Spanned},
+    validate::{self, Visitor},
+};
+
+/// How a lint rule reports its findings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LintLevel {
+    /// The rule is disabled.
+    Off,
+    /// Findings are reported as warnings.
+    #[default]
+    Warn,
+    /// Findings are reported as errors.
+    Error,
+}
+
+/// Per-rule [`LintLevel`] configuration.
+///
+/// Every rule defaults to [`LintLevel::Warn`].
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LintConfig {
+    orphan_components: LintLevel,
+    unused_types: LintLevel,
+}
+
+impl LintConfig {
+    /// Sets the level of the `orphan_components` rule.
+    pub fn with_orphan_components(mut self, level: LintLevel) -> Self {
+        self.orphan_components = level;
+        self
+    }
+
+    /// Sets the level of the `unused_types` rule.
+    pub fn with_unused_types(mut self, level: LintLevel) -> Self {
+        self.unused_types = level;
+        self
+    }
+
+    /// Returns the level of the `orphan_components` rule.
+    pub fn orphan_components(&self) -> LintLevel {
+        self.orphan_components
+    }
+
+    /// Returns the level of the `unused_types` rule.
+    pub fn unused_types(&self) -> LintLevel {
+        self.unused_types
+    }
+}
+
+/// The findings of a lint run, together with the [`SourceMap`] their spans
+/// refer to.
+#[derive(Debug)]
+pub struct LintReport<'a> {
+    diagnostics: Vec<Diagnostic>,
+    source_map: SourceMap<'a>,
+}
+
+impl<'a> LintReport<'a> {
+    pub(crate) fn new(diagnostics: Vec<Diagnostic>, source_map: SourceMap<'a>) -> Self {
+        Self {
+            diagnostics,
+            source_map,
+        }
+    }
+
+    /// Returns the warnings of the parsing pipeline followed by the lint
+    /// findings in traversal order.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// Returns the source map for resolving diagnostic spans.
+    pub fn source_map(&self) -> &SourceMap<'a> {
+        &self.source_map
+    }
+
+    /// Returns `true` if any rule configured as [`LintLevel::Error`] fired.
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics.iter().any(|d| d.severity().is_error())
+    }
+
+    /// Consumes the report, returning its diagnostics and source map.
+    pub fn into_parts(self) -> (Vec<Diagnostic>, SourceMap<'a>) {
+        (self.diagnostics, self.source_map)
+    }
+}
+
+/// Lint state for a single diagram file.
+#[derive(Default)]
+struct FileAstState {
+    /// Start offset of the file that declares the diagram header.
+    file_start: Option<usize>,
+    /// Leaf components in declaration order.
+    leaf_components: Vec<Spanned<Id>>,
+    /// Components referenced by relations, notes, or activations.
+    referenced_components: HashSet<Id>,
+    /// Type definitions declared in this diagram's own file.
+    local_types: Vec<Spanned<Id>>,
+    /// Types used as a base type, excluding self-references.
+    used_types: HashSet<Id>,
+    /// Name of the type definition currently being visited.
+    current_type: Option<Id>,
+}
+
+/// Visitor that applies the lint rules of a [`LintConfig`].
+struct Linter<'c, 's> {
+    config: &'c LintConfig,
+    source_map: &'s SourceMap<'s>,
+    builtin_types: HashSet<Id>,
+    state: FileAstState,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'c, 's> Linter<'c, 's> {
+    fn new(config: &'c LintConfig, source_map: &'s SourceMap<'s>) -> Self {
+        Self {
+            config,
+            source_map,
+            builtin_types: builtin_types::ids().into_iter().collect(),
+            state: FileAstState::default(),
+            diagnostics: Vec::new(),
+        }
+    }
+
+    fn file_start(&self, span: Span) -> Option<usize> {
+        if span.is_empty() {
+            return None;
+        }
+        self.source_map
+            .lookup_file_by_span(span)
+            .map(|file| file.start_offset())
+    }
+
+    fn emit(&mut self, level: LintLevel, message: String, span: Span, label: &str, rule: &str) {
+        let diagnostic = match level {
+            LintLevel::Off => return,
+            LintLevel::Warn => Diagnostic::warning(message),
+            LintLevel::Error => Diagnostic::error(message),
+        };
+        self.diagnostics.push(
+            diagnostic
+                .with_label(span, label)
+                .with_help(format!("reported by the `{rule}` lint rule")),
+        );
+    }
+
+    fn check_orphan_components(&mut self) {
+        let orphans: Vec<_> = self
+            .state
+            .leaf_components
+            .iter()
+            .filter(|name| !self.state.referenced_components.contains(name.inner()))
+            .copied()
+            .collect();
+        for name in orphans {
+            self.emit(
+                self.config.orphan_components,
+                format!(
+                    "component `{name}` is not referenced by any relation, note, or activation"
+                ),
+                name.span(),
+                "orphan component",
+                "orphan_components",
+            );
+        }
+    }
+
+    fn check_unused_types(&mut self) {
+        let unused: Vec<_> = self
+            .state
+            .local_types
+            .iter()
+            .filter(|name| !self.state.used_types.contains(name.inner()))
+            .copied()
+            .collect();
+        for name in unused {
+            self.emit(
+                self.config.unused_types,
+                format!("type `{name}` is never used"),
+                name.span(),
+                "unused type",
+                "unused_types",
+            );
+        }
+    }
+}
+
+impl<'a> Visitor<'a> for Linter<'_, '_> {
+    /// Pushes fresh lint state for the diagram and checks its rules afterwards.
+    fn visit_file_ast(&mut self, file_ast: &FileAst<'a>) {
+        let state = FileAstState {
+            file_start: self.file_start(file_ast.header.span()),
+            ..FileAstState::default()
+        };
+        let last_state = mem::replace(&mut self.state, state);
+
+        self.visit_header(&file_ast.header);
+        self.visit_imports(&file_ast.imports);
+        self.visit_type_definitions(&file_ast.type_definitions);
+        self.visit_elements(&file_ast.elements);
+
+        self.check_orphan_components();
+        self.check_unused_types();
+
+        self.state = last_state;
+    }
+
+    /// Records types declared in the diagram's own file, skipping built-in
+    /// overrides and definitions merged in from imports or the prelude.
+    fn visit_type_definition(&mut self, type_def: &TypeDefinition<'a>) {
+        let name = *type_def.name.inner();
+        self.state.current_type = Some(name);
+        self.visit_type_spec(&type_def.type_spec);
+        self.state.current_type = None;
+
+        let is_local = self.state.file_start.is_some()
+            && self.file_start(type_def.name.span()) == self.state.file_start;
+        if is_local && !self.builtin_types.contains(&name) && !type_def.allows_unused() {
+            self.state.local_types.push(type_def.name);
+        }
+    }
+
+    fn visit_base_type(&mut self, base_type: &Spanned<Id>) {
+        if self.state.current_type != Some(*base_type.inner()) {
+            self.state.used_types.insert(*base_type.inner());
+        }
+    }
+
+    /// Records leaf components; containers group other components and are
+    /// never orphans.
+    fn visit_component(
+        &mut self,
+        name: &Spanned<Id>,
+        _display_name: &Option<Spanned<String>>,
+        type_spec: &TypeSpec<'a>,
+        content: &ComponentContent<'a>,
+    ) {
+        if matches!(content, ComponentContent::None) {
+            self.state.leaf_components.push(*name);
+        }
+        self.visit_type_spec(type_spec);
+        self.visit_component_content(content);
+    }
+
+    fn visit_activate_component(&mut self, component: &Spanned<Id>) {
+        self.visit_identifier(component);
+    }
+
+    fn visit_identifier(&mut self, identifier: &Spanned<Id>) {
+        self.state.referenced_components.insert(*identifier.inner());
+    }
+}
+
+/// Runs the lint rules of `config` over a desugared [`FileAst`].
+///
+/// The `source_map` is used to tell type definitions declared in a diagram's
+/// own file apart from ones merged in from imports.
+pub(crate) fn lint(
+    ast: &FileAst<'_>,
+    config: &LintConfig,
+    source_map: &SourceMap<'_>,
+) -> Vec<Diagnostic> {
+    let mut linter = Linter::new(config, source_map);
+    validate::visit_file_ast(&mut linter, ast);
+    linter.diagnostics
+}
+
+/// Reports the type definitions of a desugared [`FileAst`] that are never
+/// used, as warnings.
+///
+/// This is the `unused_types` rule as run by [`parse`](crate::parse), which
+/// does not take a [`LintConfig`].
+pub(crate) fn unused_types(ast: &FileAst<'_>, source_map: &SourceMap<'_>) -> Vec<Diagnostic> {
+    let config = LintConfig::default()
+        .with_orphan_components(LintLevel::Off)
+        .with_unused_types(LintLevel::Warn);
+    lint(ast, &config, source_map)
+        .into_iter()
+        .map(|diagnostic| {
+            diagnostic.with_help("remove the type, or mark it with `allow_unused=\"true\"`")
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lint_config_defaults_to_warn() {
+        let config = LintConfig::default();
+        assert_eq!(config.orphan_components(), LintLevel::Warn);
+        assert_eq!(config.unused_types(), LintLevel::Warn);
+    }
+
+    #[test]
+    fn test_lint_config_builders() {
+        let config = LintConfig::default()
+            .with_orphan_components(LintLevel::Off)
+            .with_unused_types(LintLevel::Error);
+        assert_eq!(config.orphan_components(), LintLevel::Off);
+        assert_eq!(config.unused_types(), LintLevel::Error);
+    }
+}
@@ -0,0 +1,57 @@
+//! The result of a successful parse.
+//!
+//! [`Parsed`] pairs the semantic [`Diagram`] with the warnings reported while
+//! producing it, so callers can show non-fatal diagnostics instead of them
+//! only reaching the log.
+
+use orrery_core::semantic::Diagram;
+
+use crate::{error::Diagnostic, source_map::SourceMap};
+
+/// A parsed diagram together with its warnings and the [`SourceMap`] their
+/// spans refer to.
+#[derive(Debug)]
+pub struct Parsed<'a> {
+    diagram: Diagram,
+    warnings: Vec<Diagnostic>,
+    source_map: SourceMap<'a>,
+}
+
+impl<'a> Parsed<'a> {
+    pub(crate) fn new(
+        diagram: Diagram,
+        warnings: Vec<Diagnostic>,
+        source_map: SourceMap<'a>,
+    ) -> Self {
+        Self {
+            diagram,
+            warnings,
+            source_map,
+        }
+    }
+
+    /// Returns the parsed diagram.
+    pub fn diagram(&self) -> &Diagram {
+        &self.diagram
+    }
+
+    /// Returns the warnings reported while parsing, in source order.
+    pub fn warnings(&self) -> &[Diagnostic] {
+        &self.warnings
+    }
+
+    /// Returns the source map for resolving warning spans.
+    pub fn source_map(&self) -> &SourceMap<'a> {
+        &self.source_map
+    }
+
+    /// Consumes the result, discarding the warnings.
+    pub fn into_diagram(self) -> Diagram {
+        self.diagram
+    }
+
+    /// Consumes the result, returning the diagram, warnings and source map.
+    pub fn into_parts(self) -> (Diagram, Vec<Diagnostic>, SourceMap<'a>) {
+        (self.diagram, self.warnings, self.source_map)
+    }
+}
@@ -8,7 +8,7 @@ use std::{cell::RefCell, rc::Rc};
 
 use winnow::{
     Parser as _,
-    combinator::{alt, delimited, opt, preceded, repeat, separated},
+    combinator::{alt, delimited, opt, peek, preceded, repeat, separated, terminated},
     error::{ContextError, ErrMode},
     stream::{Stream, TokenSlice},
     token::any,
@@ -20,7 +20,8 @@ use crate::{
     error::{Diagnostic, ErrorCode},
     parser_types::{
         Attribute, AttributeValue, ComponentContent, DiagramSource, Element, FileAst, FileHeader,
-        Fragment, FragmentSection, ImportDecl, ImportForm, Note, TypeDefinition, TypeSpec,
+        Fragment, FragmentSection, Group, Highlight, ImportDecl, ImportForm, Note, StyleDefinition,
+        TypeDefinition, TypeSpec, VariableDefinition,
     },
     span::{Span, Spanned},
     tokens::{PositionedToken, Token},
@@ -35,6 +36,8 @@ pub(crate) enum Context {
     ///
     /// Used to calculate start_offset as: `tokens.len() - start_offset_value`
     StartOffset(usize),
+    /// The file does not start with a `diagram` or `library` header
+    MissingHeader,
 }
 
 type Input<'tok, 'src> = OrreryTokenSlice<'tok, 'src>;
@@ -89,7 +92,7 @@ fn ws_comment<'tok, 'src>(input: &mut Input<'tok, 'src>) -> IResult<()> {
     any.verify(|token: &PositionedToken<'_>| {
         matches!(
             token.token,
-            Token::Whitespace | Token::Newline | Token::LineComment(_)
+            Token::Whitespace | Token::Newline | Token::LineComment(_) | Token::BlockComment(_)
         )
     })
     .void()
@@ -101,6 +104,37 @@ fn ws_comments0<'tok, 'src>(input: &mut Input<'tok, 'src>) -> IResult<()> {
     repeat(0.., ws_comment).parse_next(input)
 }
 
+/// Parse whitespace and comments, collecting the line comments
+fn leading_comments<'tok, 'src>(input: &mut Input<'tok, 'src>) -> IResult<Vec<Spanned<String>>> {
+    repeat(
+        0..,
+        any.verify_map(|token: &PositionedToken<'_>| match &token.token {
+            Token::Whitespace | Token::Newline | Token::BlockComment(_) => Some(None),
+            Token::LineComment(comment) => {
+                Some(Some(Spanned::new(comment.to_string(), token.span)))
+            }
+            _ => None,
+        }),
+    )
+    .map(|comments: Vec<_>| comments.into_iter().flatten().collect())
+    .parse_next(input)
+}
+
+/// Parse a line comment that follows on the same line, skipping spaces before it
+fn trailing_comment<'tok, 'src>(input: &mut Input<'tok, 'src>) -> IResult<Option<Spanned<String>>> {
+    opt(preceded(
+        repeat::<_, _, (), _, _>(
+            0..,
+            any.verify(|token: &PositionedToken<'_>| matches!(token.token, Token::Whitespace)),
+        ),
+        any.verify_map(|token: &PositionedToken<'_>| match &token.token {
+            Token::LineComment(comment) => Some(Spanned::new(comment.to_string(), token.span)),
+            _ => None,
+        }),
+    ))
+    .parse_next(input)
+}
+
 /// Parse one or more whitespace/comments
 fn ws_comments1<'tok, 'src>(input: &mut Input<'tok, 'src>) -> IResult<()> {
     repeat(1.., ws_comment).parse_next(input)
@@ -283,6 +317,7 @@ fn empty_brackets<'tok, 'src>(input: &mut Input<'tok, 'src>) -> IResult<()> {
 /// 3. **TypeSpec** - `TypeName[attr=val]`, `TypeName`, or `[attr=val]`
 /// 4. **String** - `"value"` - Text values (colors, names, alignment)
 /// 5. **Float** - `2.5` or `10` - Numeric values (widths, sizes, dimensions)
+/// 6. **Dimension** - `2px`, `10pt` or `50%` - Numeric values with a unit suffix
 fn attribute_value<'tok, 'src>(input: &mut Input<'tok, 'src>) -> IResult<AttributeValue<'src>> {
     alt((
         // Parse empty brackets [] first - can be interpreted as either empty identifiers or empty attributes
@@ -298,6 +333,17 @@ fn attribute_value<'tok, 'src>(input: &mut Input<'tok, 'src>) -> IResult<Attribu
                 Some(AttributeValue::String(Spanned::new(s.clone(), token.span)))
             }
             Token::FloatLiteral(f) => Some(AttributeValue::Float(Spanned::new(*f, token.span))),
+            Token::DimensionLiteral(f, unit) => {
+                let unit_start = token.span.end() - unit.len();
+                Some(AttributeValue::Dimension {
+                    value: Spanned::new(*f, Span::new(token.span.start()..unit_start)),
+                    unit: Spanned::new(*unit, Span::new(unit_start..token.span.end())),
+                })
+            }
+            Token::Variable(name) => Some(AttributeValue::Variable(Spanned::new(
+                Id::new(name),
+                token.span,
+            ))),
             _ => None,
         }),
     ))
@@ -473,11 +519,110 @@ fn type_definition<'tok, 'src>(input: &mut Input<'tok, 'src>) -> IResult<TypeDef
     Ok(TypeDefinition { name, type_spec })
 }
 
-/// Parse type definitions section
-fn type_definitions<'tok, 'src>(
+/// Parse a variable definition
+///
+/// Syntax: `let name = value;`
+///
+/// `let` is not a reserved word, so that it can still name components; it
+/// only starts a definition when followed by a name and `=`.
+///
+/// Examples:
+/// - `let brand = "#3366cc";`
+/// - `let gap = 12px;`
+fn variable_definition<'tok, 'src>(
     input: &mut Input<'tok, 'src>,
-) -> IResult<Vec<TypeDefinition<'src>>> {
-    repeat(0.., preceded(ws_comments0, type_definition)).parse_next(input)
+) -> IResult<VariableDefinition<'src>> {
+    any.verify(|token: &PositionedToken<'_>| matches!(token.token, Token::Identifier("let")))
+        .parse_next(input)?;
+
+    ws_comments1.parse_next(input)?;
+    let name = identifier.parse_next(input)?;
+
+    preceded(
+        ws_comments0,
+        any.verify(|token: &PositionedToken<'_>| matches!(token.token, Token::Equals)),
+    )
+    .parse_next(input)?;
+
+    cut_err(input, |input| {
+        ws_comments0.parse_next(input)?;
+
+        let value = attribute_value.parse_next(input)?;
+
+        semicolon.parse_next(input)?;
+
+        Ok(VariableDefinition { name, value })
+    })
+}
+
+/// Parse a style definition
+///
+/// Syntax: `style Name { attr=value; ... }`
+///
+/// `style` is not a reserved word, so that it can still name attributes and
+/// components; it only starts a definition when followed by a name and `{`.
+///
+/// Examples:
+/// - `style Highlighted { fill_color="yellow"; stroke=[width=2]; }`
+fn style_definition<'tok, 'src>(input: &mut Input<'tok, 'src>) -> IResult<StyleDefinition<'src>> {
+    any.verify(|token: &PositionedToken<'_>| matches!(token.token, Token::Identifier("style")))
+        .parse_next(input)?;
+
+    ws_comments1.parse_next(input)?;
+    let name = identifier.parse_next(input)?;
+
+    preceded(
+        ws_comments0,
+        any.verify(|token: &PositionedToken<'_>| matches!(token.token, Token::LeftBrace)),
+    )
+    .parse_next(input)?;
+
+    cut_err(input, |input| {
+        let attributes = repeat(
+            0..,
+            preceded(ws_comments0, terminated(attribute, semicolon)),
+        )
+        .parse_next(input)?;
+
+        preceded(
+            ws_comments0,
+            any.verify(|token: &PositionedToken<'_>| matches!(token.token, Token::RightBrace)),
+        )
+        .context(Context::Label("closing brace '}'"))
+        .parse_next(input)?;
+
+        opt(semicolon).parse_next(input)?;
+
+        Ok(StyleDefinition { name, attributes })
+    })
+}
+
+/// Definitions that precede a file's elements.
+#[derive(Default)]
+struct Definitions<'src> {
+    types: Vec<TypeDefinition<'src>>,
+    variables: Vec<VariableDefinition<'src>>,
+    styles: Vec<StyleDefinition<'src>>,
+}
+
+/// Parse the definitions section: type, variable and style definitions in any order
+fn definitions<'tok, 'src>(input: &mut Input<'tok, 'src>) -> IResult<Definitions<'src>> {
+    let mut definitions = Definitions::default();
+    loop {
+        let checkpoint = input.checkpoint();
+        ws_comments0.parse_next(input)?;
+        if let Some(type_def) = opt(type_definition).parse_next(input)? {
+            definitions.types.push(type_def);
+        } else if let Some(variable_def) = opt(variable_definition).parse_next(input)? {
+            definitions.variables.push(variable_def);
+        } else if let Some(style_def) = opt(style_definition).parse_next(input)? {
+            definitions.styles.push(style_def);
+        } else {
+            input.reset(&checkpoint);
+            break;
+        }
+    }
+    Ok(definitions)
 }
 
 /// Parse relation type (arrow with optional type specification)
@@ -487,6 +632,8 @@ fn relation_type<'tok, 'src>(input: &mut Input<'tok, 'src>) -> IResult<&'src str
             Token::Arrow_ => Some("->"),
             Token::LeftArrow => Some("<-"),
             Token::DoubleArrow => Some("<->"),
+            Token::AsyncArrow => Some("->>"),
+            Token::AsyncLeftArrow => Some("<<-"),
             Token::Plain => Some("-"),
             _ => None,
         })
@@ -541,6 +688,7 @@ fn component<'tok, 'src>(input: &mut Input<'tok, 'src>) -> IResult<Element<'src>
         display_name,
         type_spec,
         content,
+        comments: Vec::new(),
     })
 }
 
@@ -591,6 +739,7 @@ fn relation<'tok, 'src>(input: &mut Input<'tok, 'src>) -> IResult<Element<'src>>
             relation_type: make_spanned(relation_type, Span::new(0..0)), // TODO: track proper span
             type_spec,
             label,
+            comments: Vec::new(),
         })
     })
 }
@@ -635,8 +784,6 @@ fn activate_block<'tok, 'src>(input: &mut Input<'tok, 'src>) -> IResult<Element<
         .context(Context::Label("opening brace '{'"))
         .parse_next(input)?;
 
-    ws_comments0.parse_next(input)?;
-
     // Parse nested elements
     let nested_elements = elements
         .context(Context::Label("activate block content"))
@@ -685,8 +832,6 @@ fn section_block<'tok, 'src>(input: &mut Input<'tok, 'src>) -> IResult<FragmentS
             .context(Context::Label("opening brace '{'"))
             .parse_next(input)?;
 
-        ws_comments0.parse_next(input)?;
-
         // Parse nested elements inside the section
         let elems = elements
             .context(Context::Label("section content"))
@@ -729,8 +874,6 @@ fn parse_section_content<'tok, 'src>(
         .context(Context::Label("opening brace '{'"))
         .parse_next(input)?;
 
-    ws_comments0.parse_next(input)?;
-
     let elems = elements.parse_next(input)?;
 
     ws_comments0.parse_next(input)?;
@@ -914,6 +1057,89 @@ fn fragment_block<'tok, 'src>(input: &mut Input<'tok, 'src>) -> IResult<Element<
     })
 }
 
+/// Parse a keyword followed by a labeled block:
+/// `keyword @TypeSpec "label" { elements };`
+///
+/// The keyword is contextual: it only starts a block when followed by a type
+/// spec or a label, so components may still be named after it.
+fn labeled_block<'tok, 'src>(
+    input: &mut Input<'tok, 'src>,
+    keyword: &'static str,
+) -> IResult<(TypeSpec<'src>, Spanned<String>, Vec<Element<'src>>)> {
+    // Parse the keyword
+    any.verify(|token: &PositionedToken<'_>| token.token == Token::Identifier(keyword))
+        .context(Context::Label("block keyword"))
+        .parse_next(input)?;
+
+    ws_comments0.parse_next(input)?;
+    peek(any.verify(|token: &PositionedToken<'_>| {
+        matches!(
+            token.token,
+            Token::At | Token::LeftBracket | Token::StringLiteral(_)
+        )
+    }))
+    .parse_next(input)?;
+
+    cut_err(input, |input| {
+        ws_comments0.parse_next(input)?;
+
+        let type_spec = opt(invocation_type_spec)
+            .parse_next(input)?
+            .unwrap_or_default();
+
+        ws_comments0.parse_next(input)?;
+
+        let label = string_literal
+            .context(Context::Label("block label string literal"))
+            .parse_next(input)?;
+
+        ws_comments0.parse_next(input)?;
+
+        // Parse opening brace
+        any.verify(|token: &PositionedToken<'_>| matches!(token.token, Token::LeftBrace))
+            .context(Context::Label("opening brace '{'"))
+            .parse_next(input)?;
+
+        let elements = elements.parse_next(input)?;
+
+        ws_comments0.parse_next(input)?;
+
+        // Parse closing brace
+        any.verify(|token: &PositionedToken<'_>| matches!(token.token, Token::RightBrace))
+            .context(Context::Label("closing brace '}'"))
+            .parse_next(input)?;
+
+        ws_comments0.parse_next(input)?;
+
+        // Parse semicolon after the block
+        semicolon
+            .context(Context::Label("semicolon after block"))
+            .parse_next(input)?;
+
+        Ok((type_spec, label, elements))
+    })
+}
+
+/// Parse a highlight block: `highlight @TypeSpec "label" { elements };`
+fn highlight_block<'tok, 'src>(input: &mut Input<'tok, 'src>) -> IResult<Element<'src>> {
+    let (type_spec, label, elements) = labeled_block(input, "highlight")?;
+    Ok(Element::Highlight(Highlight {
+        label,
+        type_spec,
+        elements,
+    }))
+}
+
+/// Parse a group block: `group @TypeSpec "label" { elements };`
+fn group_block<'tok, 'src>(input: &mut Input<'tok, 'src>) -> IResult<Element<'src>> {
+    let (type_spec, label, elements) = labeled_block(input, "group")?;
+    Ok(Element::Group(Group {
+        label,
+        type_spec,
+        elements,
+    }))
+}
+
 /// Parse an explicit activate statement
 ///
 /// ## Grammar:
@@ -1041,39 +1267,119 @@ fn note_element<'tok, 'src>(input: &mut Input<'tok, 'src>) -> IResult<Element<'s
 
     Ok(Element::Note(Note { type_spec, content }))
 }
-/// Parses zero or more diagram elements.
+/// Parses a single diagram element, preceded by optional whitespace.
+///
+/// Line comments above the element and a comment following it on the same
+/// line are attached to components and relations; they are dropped for other
+/// elements.
 ///
 /// An invalid-statement catch-all provides better error reporting when no
 /// valid parser matches.
+fn element<'tok, 'src>(input: &mut Input<'tok, 'src>) -> IResult<Element<'src>> {
+    let mut leading = leading_comments.parse_next(input)?;
+
+    // Prioritize keyword-based items; explicit activate/deactivate first,
+    // then blocks, then relations and components.
+    let mut element = alt((
+        alt((
+            activate_element,
+            deactivate_statement,
+            note_element,
+            alt_else_block,
+            par_block,
+            opt_block,
+            loop_block,
+            break_block,
+            critical_block,
+        )),
+        alt((
+            fragment_block,
+            highlight_block,
+            group_block,
+            relation,
+            component,
+            invalid_statement_with_semicolon,
+        )),
+    ))
+    .parse_next(input)?;
+
+    leading.extend(trailing_comment.parse_next(input)?);
+    if let Element::Component { comments, .. } | Element::Relation { comments, .. } = &mut element {
+        *comments = leading;
+    }
+
+    Ok(element)
+}
+
+/// Parses zero or more diagram elements.
 fn elements<'tok, 'src>(input: &mut Input<'tok, 'src>) -> IResult<Vec<Element<'src>>> {
-    repeat(
-        0..,
-        preceded(
-            ws_comments0,
-            // Prioritize keyword-based items; explicit activate/deactivate first,
-            // then blocks, then relations and components.
-            alt((
-                alt((
-                    activate_element,
-                    deactivate_statement,
-                    note_element,
-                    alt_else_block,
-                    par_block,
-                    opt_block,
-                    loop_block,
-                    break_block,
-                    critical_block,
-                )),
-                alt((
-                    fragment_block,
-                    relation,
-                    component,
-                    invalid_statement_with_semicolon,
-                )),
-            )),
-        ),
-    )
-    .parse_next(input)
+    repeat(0.., element).parse_next(input)
+}
+
+/// An element statement that failed to parse, recorded by
+/// [`recovering_elements`].
+struct RecoveredError {
+    error: ErrMode<ContextError<Context>>,
+    /// Remaining token count (`eof_offset()`) where parsing failed
+    remaining: usize,
+}
+
+/// Parses zero or more diagram elements, recovering from invalid statements.
+///
+/// When a statement fails with a cut error, the error is recorded in `errors`
+/// and the statement is skipped with [`skip_statement`], so the statements
+/// after it are still parsed.
+fn recovering_elements<'tok, 'src>(
+    input: &mut Input<'tok, 'src>,
+    errors: &mut Vec<RecoveredError>,
+) -> IResult<Vec<Element<'src>>> {
+    let mut elements = Vec::new();
+    loop {
+        let checkpoint = input.checkpoint();
+        match element.parse_next(input) {
+            Ok(element) => elements.push(element),
+            Err(ErrMode::Cut(e)) => {
+                errors.push(RecoveredError {
+                    error: ErrMode::Cut(e),
+                    remaining: input.eof_offset(),
+                });
+                input.reset(&checkpoint);
+                if !skip_statement(input) {
+                    break;
+                }
+            }
+            Err(ErrMode::Backtrack(_)) => {
+                input.reset(&checkpoint);
+                break;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(elements)
+}
+
+/// Skips the tokens of an invalid statement.
+///
+/// Consumes tokens through the next semicolon outside of braces, or up to
+/// (but not including) an unmatched closing brace. Returns `false` if no
+/// token was consumed.
+fn skip_statement(input: &mut Input<'_, '_>) -> bool {
+    let start_remaining = input.eof_offset();
+    let mut depth = 0_usize;
+    while let Some(token) = input.peek_token() {
+        match token.token {
+            Token::LeftBrace => depth += 1,
+            Token::RightBrace if depth == 0 => break,
+            Token::RightBrace => depth -= 1,
+            Token::Semicolon if depth == 0 => {
+                input.next_token();
+                break;
+            }
+            _ => {}
+        }
+        input.next_token();
+    }
+    input.eof_offset() != start_remaining
 }
 
 /// Catch-all parser for invalid syntax.
@@ -1166,8 +1472,18 @@ fn library_header<'tok, 'src>(input: &mut Input<'tok, 'src>) -> IResult<FileHead
 /// Parses the file header that begins every `.orr` file.
 ///
 /// Dispatches to [`library_header`] or [`diagram_header`] based on the
-/// leading token, then consumes the required trailing semicolon.
+/// leading token, then consumes the required trailing semicolon. A file that
+/// starts with any other token fails with [`Context::MissingHeader`].
 fn file_header<'tok, 'src>(input: &mut Input<'tok, 'src>) -> IResult<FileHeader<'src>> {
+    if !matches!(
+        input.peek_token().map(|token| &token.token),
+        Some(Token::Diagram | Token::Library)
+    ) {
+        let mut e = ContextError::new();
+        e.push(Context::StartOffset(input.eof_offset()));
+        e.push(Context::MissingHeader);
+        return Err(ErrMode::Cut(e));
+    }
     let header = alt((library_header, diagram_header))
         .context(Context::Label("file header"))
         .parse_next(input)?;
@@ -1239,20 +1555,30 @@ fn import_decls<'tok, 'src>(input: &mut Input<'tok, 'src>) -> IResult<Vec<Spanne
 
 /// Parses the body of a complete `.orr` file (without EOF checking).
 ///
-/// Expects the sequence: file header → import declarations → type definitions
-/// → elements.
+/// Expects the sequence: file header → import declarations → type, variable
+/// and style definitions → elements.
 fn file<'tok, 'src>(input: &mut Input<'tok, 'src>) -> IResult<FileAst<'src>> {
+    file_with_elements(input, elements)
+}
+
+/// Parses the body of a file, using `elements` to parse its elements.
+fn file_with_elements<'tok, 'src>(
+    input: &mut Input<'tok, 'src>,
+    mut elements: impl FnMut(&mut Input<'tok, 'src>) -> IResult<Vec<Element<'src>>>,
+) -> IResult<FileAst<'src>> {
     ws_comments0.parse_next(input)?;
     let header = file_header.parse_next(input)?;
     let import_decls = import_decls.parse_next(input)?;
-    let type_definitions = type_definitions.parse_next(input)?;
-    let elements = elements.parse_next(input)?;
+    let definitions = definitions.parse_next(input)?;
+    let elements = elements(input)?;
     ws_comments0.parse_next(input)?;
 
     Ok(FileAst {
         header,
         import_decls,
-        type_definitions,
+        type_definitions: definitions.types,
+        variable_definitions: definitions.variables,
+        style_definitions: definitions.styles,
         elements,
         imports: vec![],
     })
@@ -1274,10 +1600,7 @@ fn component_content<'tok, 'src>(input: &mut Input<'tok, 'src>) -> IResult<Compo
         embedded_diagram,
         // Fall back to nested elements in braces
         delimited(
-            (
-                any.verify(|token: &PositionedToken<'_>| matches!(token.token, Token::LeftBrace)),
-                ws_comments0,
-            ),
+            any.verify(|token: &PositionedToken<'_>| matches!(token.token, Token::LeftBrace)),
             elements,
             (
                 ws_comments0,
@@ -1347,6 +1670,12 @@ fn convert_error(
     let end_offset = tokens.len() - current_remaining;
     let start_offset = start_remaining.map(|r| tokens.len() - r).unwrap_or(0);
 
+    if let ErrMode::Cut(e) = &error
+        && e.context().any(|ctx| *ctx == Context::MissingHeader)
+    {
+        return missing_header_error(tokens, start_offset);
+    }
+
     match error {
         ErrMode::Backtrack(e) | ErrMode::Cut(e) => {
             // Extract context information for better error messages
@@ -1429,12 +1758,29 @@ fn convert_error(
     }
 }
 
+/// Builds the diagnostic for a file whose first token at `offset` is not a
+/// file header, labelling that token (or the end of the file).
+fn missing_header_error(tokens: &[PositionedToken], offset: usize) -> Diagnostic {
+    let span = tokens
+        .get(offset)
+        .or_else(|| tokens.last())
+        .map_or_else(Span::empty, |token| token.span);
+
+    Diagnostic::error("expected diagram header (`diagram component;` or `diagram sequence;`)")
+        .with_code(ErrorCode::E102)
+        .with_label(span, "expected diagram header")
+        .with_help(
+            "start the file with `diagram component;` or `diagram sequence;`, or with `library;` for a file of shared definitions",
+        )
+}
+
 /// Parses a token stream into a [`FileAst`].
 ///
 /// This is the public entry point for parsing a complete source file.
-/// After the inner [`file()`] combinator succeeds, this
-/// function verifies that all tokens have been consumed; leftover tokens
-/// indicate a syntax error.
+/// Invalid element statements of the file are skipped so that every one of
+/// them is reported, not just the first. After parsing, this function
+/// verifies that all tokens have been consumed; leftover tokens indicate a
+/// syntax error.
 ///
 /// # Arguments
 ///
@@ -1447,27 +1793,42 @@ fn convert_error(
 ///
 /// # Errors
 ///
-/// Returns a [`Diagnostic`] when:
+/// Returns a [`Diagnostic`] for each failure when:
 /// - The token stream does not match the expected Orrery grammar.
 /// - Tokens remain after parsing (unconsumed trailing input).
-pub fn build_file<'src>(tokens: &[PositionedToken<'src>]) -> Result<FileAst<'src>, Diagnostic> {
+pub fn build_file<'src>(
+    tokens: &[PositionedToken<'src>],
+) -> Result<FileAst<'src>, Vec<Diagnostic>> {
     let mut token_slice = TokenSlice::new(tokens);
+    let mut recovered = Vec::new();
 
-    match file.parse_next(&mut token_slice) {
+    let result = file_with_elements(&mut token_slice, |input| {
+        recovering_elements(input, &mut recovered)
+    });
+
+    let mut diagnostics: Vec<_> = recovered
+        .into_iter()
+        .map(|recovered| convert_error(recovered.error, tokens, recovered.remaining))
+        .collect();
+
+    match result {
         Ok(file_ast) => {
             // Check if we've consumed all tokens (EOF)
             if !token_slice.is_empty() {
                 let current_remaining = token_slice.eof_offset();
                 let e = cut_error_with_offset(&token_slice);
-                return Err(convert_error(e, tokens, current_remaining));
+                diagnostics.push(convert_error(e, tokens, current_remaining));
+            }
+            if diagnostics.is_empty() {
+                return Ok(file_ast);
             }
-            Ok(file_ast)
         }
         Err(e) => {
             let current_remaining = token_slice.eof_offset();
-            Err(convert_error(e, tokens, current_remaining))
+            diagnostics.push(convert_error(e, tokens, current_remaining));
         }
     }
+    Err(diagnostics)
 }
 
 #[cfg(test)]
@@ -2444,6 +2805,27 @@ mod tests {
         assert!(type_definition(&mut input).is_err());
     }
 
+    #[test]
+    fn test_style_definition_function() {
+        let tokens = parse_tokens("style Warning { fill_color=\"yellow\"; stroke=[width=2]; }");
+        let mut input = OrreryTokenSlice::new(&tokens);
+        let style_def = style_definition(&mut input).expect("style definition should parse");
+        assert_eq!(*style_def.name.inner(), "Warning");
+        assert_eq!(style_def.attributes.len(), 2);
+        assert_eq!(*style_def.attributes[0].name.inner(), "fill_color");
+        assert_eq!(*style_def.attributes[1].name.inner(), "stroke");
+
+        // `style` still names components
+        let tokens = parse_tokens("style: Rectangle;");
+        let mut input = OrreryTokenSlice::new(&tokens);
+        assert!(style_definition(&mut input).is_err());
+
+        // Every attribute ends with a semicolon
+        let tokens = parse_tokens("style Warning { fill_color=\"yellow\" }");
+        let mut input = OrreryTokenSlice::new(&tokens);
+        assert!(style_definition(&mut input).is_err());
+    }
+
     #[test]
     fn test_type_spec_function() {
         // Test: TypeName only
@@ -3134,6 +3516,59 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_highlight_block() {
+        let input = r#"highlight [fill_color="lavender"] "during transaction" {
+            a -> b;
+            b -> a;
+        };"#;
+        let tokens = parse_tokens(input);
+        let mut token_slice = TokenSlice::new(&tokens);
+
+        let result = highlight_block(&mut token_slice);
+        assert!(
+            result.is_ok(),
+            "Failed to parse highlight block: {:?}",
+            result
+        );
+
+        let element = result.unwrap();
+        if let Element::Highlight(highlight) = element {
+            assert_eq!(highlight.label.inner(), "during transaction");
+            assert_eq!(highlight.type_spec.attributes.len(), 1);
+            assert_eq!(highlight.elements.len(), 2);
+        } else {
+            panic!("Expected Highlight element");
+        }
+    }
+
+    #[test]
+    fn test_group_block_nested() {
+        let input = r#"group "Backend" {
+            api: Service;
+            group "Storage" {
+                db: Database;
+            };
+            api -> db;
+        };"#;
+        let tokens = parse_tokens(input);
+        let mut token_slice = TokenSlice::new(&tokens);
+
+        let result = group_block(&mut token_slice);
+        assert!(result.is_ok(), "Failed to parse group block: {:?}", result);
+
+        let Element::Group(group) = result.unwrap() else {
+            panic!("Expected Group element");
+        };
+        assert_eq!(group.label.inner(), "Backend");
+        assert_eq!(group.elements.len(), 3);
+        let Element::Group(inner) = &group.elements[1] else {
+            panic!("Expected nested Group element");
+        };
+        assert_eq!(inner.label.inner(), "Storage");
+        assert_eq!(inner.elements.len(), 1);
+    }
+
     #[test]
     fn test_note_element_simple() {
         let input = r#"note: "This is a simple note";"#;
@@ -3259,6 +3694,79 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_build_file_reports_every_invalid_statement() {
+        let input = "diagram component;\na: Rectangle;\nb Rectangle;\nc: Rectangle;\nd: Rectangle\ne: Rectangle;\nf: Rectangle;";
+        let tokens = parse_tokens(input);
+
+        let errors = build_file(&tokens).expect_err("invalid statements should fail");
+        assert_eq!(errors.len(), 2);
+
+        let error_text = |index: usize| {
+            let span = errors[index].labels()[0].span();
+            &input[span.start()..span.end()]
+        };
+        assert!(error_text(0).starts_with("b Rectangle"));
+        assert!(error_text(1).starts_with("d: Rectangle"));
+        assert!(
+            errors
+                .iter()
+                .all(|error| error.code() == Some(ErrorCode::E100))
+        );
+    }
+
+    #[test]
+    fn test_build_file_recovers_after_invalid_block() {
+        let input = r#"diagram component;
+        a: Rectangle {
+            x y;
+            z: Rectangle;
+        };
+        b: Rectangle;
+        c c;
+        "#;
+        let tokens = parse_tokens(input);
+
+        let errors = build_file(&tokens).expect_err("invalid statements should fail");
+        assert_eq!(errors.len(), 2);
+    }
+
+    #[test]
+    fn test_build_file_reports_missing_header() {
+        let error_for = |input: &str| {
+            let tokens = parse_tokens(input);
+            let mut errors = build_file(&tokens).expect_err("missing header should fail");
+            assert_eq!(errors.len(), 1);
+            errors.remove(0)
+        };
+
+        let input = "// services\napp: Rectangle;\ndb: Oval;";
+        let error = error_for(input);
+        assert_eq!(error.code(), Some(ErrorCode::E102));
+        assert_eq!(
+            error.message(),
+            "expected diagram header (`diagram component;` or `diagram sequence;`)"
+        );
+        let span = error.labels()[0].span();
+        assert_eq!(&input[span.start()..span.end()], "app");
+
+        let input = "diagramm component;";
+        let span = error_for(input).labels()[0].span();
+        assert_eq!(&input[span.start()..span.end()], "diagramm");
+
+        assert_eq!(error_for("").code(), Some(ErrorCode::E102));
+        assert_eq!(error_for("// only a comment").code(), Some(ErrorCode::E102));
+    }
+
+    #[test]
+    fn test_build_file_unmatched_brace_after_invalid_statement() {
+        let input = "diagram component;\nb Rectangle;\n};";
+        let tokens = parse_tokens(input);
+
+        let errors = build_file(&tokens).expect_err("invalid statements should fail");
+        assert_eq!(errors.len(), 2);
+    }
+
     #[test]
     fn test_convert_error_consumed_tokens() {
         // Test Case: Parser consumed tokens before failing
@@ -4121,4 +4629,48 @@ type Cache = Rectangle;"#;
         assert_eq!(file_ast.type_definitions.len(), 3);
         assert!(file_ast.elements.is_empty());
     }
+
+    #[test]
+    fn test_comments_attach_to_nearest_element() {
+        let input = r#"diagram component;
+// The public API
+// Serves HTTP traffic
+api: Rectangle { // nested below
+    // Worker pool
+    worker: Oval;
+};
+api -> api; // loopback
+activate api;
+"#;
+        let tokens = parse_tokens(input);
+        let file_ast = build_file(&tokens).expect("file should parse");
+
+        let texts = |element: &Element<'_>| -> Vec<String> {
+            element
+                .comments()
+                .iter()
+                .map(|comment| comment.inner().clone())
+                .collect()
+        };
+
+        let api = &file_ast.elements[0];
+        assert_eq!(texts(api), vec![" The public API", " Serves HTTP traffic"]);
+        let first = &api.comments()[0];
+        assert_eq!(
+            &input[first.span().start()..first.span().end()],
+            "// The public API"
+        );
+
+        let Element::Component {
+            content: ComponentContent::Scope(children),
+            ..
+        } = api
+        else {
+            panic!("expected a component with nested elements");
+        };
+        assert_eq!(texts(&children[0]), vec![" nested below", " Worker pool"]);
+
+        assert_eq!(texts(&file_ast.elements[1]), vec![" loopback"]);
+        assert!(file_ast.elements[2].comments().is_empty());
+    }
 }
@@ -14,7 +14,7 @@ use crate::{ElaborateConfig, InMemorySourceProvider, lexer, parse, parser};
 /// Helper function to parse a source string and return success/failure
 fn parse_source(source: &str) -> Result<(), String> {
     let tokens = lexer::tokenize(source, 0).map_err(|err| format!("Lexer error: {:?}", err))?;
-    let _ast = parser::build_file(&tokens).map_err(|err| format!("Parser error: {}", err))?;
+    let _ast = parser::build_file(&tokens).map_err(|errs| format!("Parser error: {}", errs[0]))?;
     Ok(())
 }
 
@@ -702,6 +702,16 @@ mod whitespace_and_comments_tests {
         assert_parses_successfully(source);
     }
 
+    #[test]
+    fn test_trailing_line_comment_without_newline() {
+        assert_parses_successfully("diagram component;\napp: Rectangle;\n// final note");
+        assert_parses_successfully("diagram component;\r\napp: Rectangle;\r\n// final note");
+        assert_parses_successfully("diagram component; // final note");
+        assert_parses_successfully(
+            "diagram component;\napp: Rectangle embed {\n    diagram sequence;\n    // inner note\n};\n// final note",
+        );
+    }
+
     #[test]
     fn test_various_whitespace_patterns() {
         let source = r#"diagram component;
@@ -728,6 +738,21 @@ mod whitespace_and_comments_tests {
         "#;
         assert_parses_successfully(source);
     }
+
+    #[test]
+    fn test_block_comments_in_attributes() {
+        let source = r#"
+            diagram component; /* header /* nested */ done */
+            app: Rectangle [
+                color="blue", /* the border
+                   is wider than usual */ width="2"
+            ];
+            /* commented out:
+            db: Oval; // with a line comment
+            */
+        "#;
+        assert_parses_successfully(source);
+    }
 }
 
 #[cfg(test)]
@@ -1514,7 +1539,8 @@ mod import_tests {
             provider,
             ElaborateConfig::default(),
         )
-        .expect("Failed to parse");
+        .expect("Failed to parse")
+        .into_diagram();
 
         let elements = diagram.scope().elements();
         assert_eq!(elements.len(), 2);
@@ -1559,7 +1585,8 @@ mod import_tests {
             provider,
             ElaborateConfig::default(),
         )
-        .expect("Failed to parse");
+        .expect("Failed to parse")
+        .into_diagram();
 
         let elements = diagram.scope().elements();
         assert_eq!(elements.len(), 2);
@@ -1614,7 +1641,8 @@ mod import_tests {
             provider,
             ElaborateConfig::default(),
         )
-        .expect("Failed to parse");
+        .expect("Failed to parse")
+        .into_diagram();
 
         let elements = diagram.scope().elements();
         assert_eq!(elements.len(), 2);
@@ -1651,7 +1679,8 @@ mod import_tests {
             provider,
             ElaborateConfig::default(),
         )
-        .expect("Failed to parse");
+        .expect("Failed to parse")
+        .into_diagram();
 
         let elements = diagram.scope().elements();
         assert_eq!(elements.len(), 2);
@@ -1694,7 +1723,8 @@ mod import_tests {
             provider,
             ElaborateConfig::default(),
         )
-        .expect("Failed to parse");
+        .expect("Failed to parse")
+        .into_diagram();
 
         let elements = diagram.scope().elements();
         assert_eq!(elements.len(), 1);
@@ -1738,7 +1768,8 @@ mod import_tests {
             provider,
             ElaborateConfig::default(),
         )
-        .expect("Failed to parse");
+        .expect("Failed to parse")
+        .into_diagram();
 
         let elements = diagram.scope().elements();
         assert_eq!(elements.len(), 2);
@@ -1775,7 +1806,8 @@ mod import_tests {
             provider,
             ElaborateConfig::default(),
         )
-        .expect("Failed to parse");
+        .expect("Failed to parse")
+        .into_diagram();
 
         let elements = diagram.scope().elements();
         assert_eq!(elements.len(), 2);
@@ -1820,7 +1852,8 @@ mod import_tests {
             provider,
             ElaborateConfig::default(),
         )
-        .expect("Failed to parse");
+        .expect("Failed to parse")
+        .into_diagram();
 
         let elements = diagram.scope().elements();
         assert_eq!(elements.len(), 2);
@@ -1864,7 +1897,8 @@ mod import_tests {
             provider,
             ElaborateConfig::default(),
         )
-        .expect("Failed to parse");
+        .expect("Failed to parse")
+        .into_diagram();
 
         let elements = diagram.scope().elements();
         assert_eq!(elements.len(), 2);
@@ -8,7 +8,7 @@
 //! Leaf values are wrapped in [`Spanned<T>`] to preserve source location information
 //! for error reporting. Composite types derive their spans from their contents.
 
-use std::{cell::RefCell, fmt, rc::Rc};
+use std::{cell::RefCell, fmt, rc::Rc, str::FromStr};
 
 use orrery_core::{identifier::Id, semantic::DiagramKind};
 
@@ -77,8 +77,12 @@ static EMPTY_TYPE_SPEC: TypeSpec<'static> = TypeSpec {
 /// - `Float` - Numeric values for dimensions, widths, sizes, etc.
 /// - `TypeSpec` - Type specifiers for complex attributes supporting named types
 /// - `Identifiers` - Lists of element identifiers (used in note `on` attribute)
+/// - `Dimension` - A number with a unit suffix such as `2px` or `50%`. The unit is
+///   kept as written so that validation can reject unknown units.
 /// - `Empty` - Ambiguous empty brackets `[]` that can be interpreted as either
 ///   empty identifiers or empty type specs depending on context
+/// - `Variable` - A `$name` reference to a [`VariableDefinition`], replaced by
+///   its value during desugaring
 ///
 /// **Empty Variant Design:**
 /// The `Empty` variant elegantly solves the `[]` ambiguity problem:
@@ -89,9 +93,14 @@ static EMPTY_TYPE_SPEC: TypeSpec<'static> = TypeSpec {
 pub enum AttributeValue<'a> {
     String(Spanned<String>),
     Float(Spanned<f32>),
+    Dimension {
+        value: Spanned<f32>,
+        unit: Spanned<&'a str>,
+    },
     TypeSpec(TypeSpec<'a>),
     Identifiers(Vec<Spanned<Id>>),
     Empty,
+    Variable(Spanned<Id>),
 }
 
 impl<'a> PartialEq for AttributeValue<'a> {
@@ -99,6 +108,16 @@ impl<'a> PartialEq for AttributeValue<'a> {
         match (self, other) {
             (AttributeValue::String(s1), AttributeValue::String(s2)) => s1.inner() == s2.inner(),
             (AttributeValue::Float(f1), AttributeValue::Float(f2)) => f1.inner() == f2.inner(),
+            (
+                AttributeValue::Dimension {
+                    value: v1,
+                    unit: u1,
+                },
+                AttributeValue::Dimension {
+                    value: v2,
+                    unit: u2,
+                },
+            ) => v1.inner() == v2.inner() && u1.inner() == u2.inner(),
             (AttributeValue::TypeSpec(t1), AttributeValue::TypeSpec(t2)) => {
                 t1.type_name.as_ref().map(|s| s.inner()) == t2.type_name.as_ref().map(|s| s.inner())
                     && t1.attributes == t2.attributes
@@ -108,6 +127,9 @@ impl<'a> PartialEq for AttributeValue<'a> {
                 .map(|s| s.inner())
                 .eq(l2.iter().map(|s| s.inner())),
             (AttributeValue::Empty, AttributeValue::Empty) => true,
+            (AttributeValue::Variable(v1), AttributeValue::Variable(v2)) => {
+                v1.inner() == v2.inner()
+            }
             _ => false,
         }
     }
@@ -118,6 +140,9 @@ impl<'a> fmt::Display for AttributeValue<'a> {
         match self {
             AttributeValue::String(s) => write!(f, "\"{}\"", s.inner()),
             AttributeValue::Float(n) => write!(f, "{}", n.inner()),
+            AttributeValue::Dimension { value, unit } => {
+                write!(f, "{}{}", value.inner(), unit.inner())
+            }
             AttributeValue::TypeSpec(type_spec) => {
                 write!(f, "{}", type_spec)
             }
@@ -132,6 +157,7 @@ impl<'a> fmt::Display for AttributeValue<'a> {
                 write!(f, "]")
             }
             AttributeValue::Empty => write!(f, "[]"),
+            AttributeValue::Variable(name) => write!(f, "${}", name.inner()),
         }
     }
 }
@@ -142,6 +168,7 @@ impl<'a> AttributeValue<'a> {
         match self {
             AttributeValue::String(spanned) => spanned.span(),
             AttributeValue::Float(spanned) => spanned.span(),
+            AttributeValue::Dimension { value, unit } => value.span().union(unit.span()),
             AttributeValue::TypeSpec(type_spec) => type_spec.span(),
             AttributeValue::Identifiers(ids) => {
                 if ids.is_empty() {
@@ -154,6 +181,24 @@ impl<'a> AttributeValue<'a> {
                 }
             }
             AttributeValue::Empty => Span::default(),
+            AttributeValue::Variable(name) => name.span(),
+        }
+    }
+
+    /// Moves the span of a literal value to `span`, leaving other values as
+    /// they are.
+    ///
+    /// Used when a variable is substituted, so that errors about the value
+    /// point at the reference rather than at the definition.
+    pub fn with_span(self, span: Span) -> Self {
+        match self {
+            AttributeValue::String(s) => AttributeValue::String(Spanned::new(s.into_inner(), span)),
+            AttributeValue::Float(f) => AttributeValue::Float(Spanned::new(f.into_inner(), span)),
+            AttributeValue::Dimension { value, unit } => AttributeValue::Dimension {
+                value: Spanned::new(value.into_inner(), span),
+                unit: Spanned::new(unit.into_inner(), span),
+            },
+            value => value,
         }
     }
 
@@ -166,31 +211,47 @@ impl<'a> AttributeValue<'a> {
         }
     }
 
-    /// Extract a float value, returning an error if this is not a float value
+    /// Extract a float value in pixels, returning an error if this is not a
+    /// float or an absolute dimension value
+    ///
+    /// Bare floats are pixels; `pt` dimensions are converted to pixels.
+    /// Percentages need a reference value, see [`as_length`](Self::as_length).
     pub fn as_float(&self) -> Result<f32, &'static str> {
-        if let AttributeValue::Float(f) = self {
-            Ok(*f.inner())
-        } else {
-            Err("expected float value")
+        match self {
+            AttributeValue::Float(f) => Ok(*f.inner()),
+            AttributeValue::Dimension { value, unit } => match Unit::from_str(unit.inner())? {
+                Unit::Percent => Err("percentages are not supported for this attribute"),
+                unit => Ok(unit.to_px(*value.inner(), 0.0)),
+            },
+            _ => Err("expected float value"),
+        }
+    }
+
+    /// Extract a length in pixels, resolving a percentage against `reference`
+    ///
+    /// `reference` is the size of the parent element along the attribute's
+    /// axis, so `50%` is half of it. A percentage is an error when the
+    /// reference is not positive, i.e. the parent size is unknown.
+    pub fn as_length(&self, reference: f32) -> Result<f32, &'static str> {
+        match self {
+            AttributeValue::Dimension { value, unit } => match Unit::from_str(unit.inner())? {
+                Unit::Percent if reference <= 0.0 => {
+                    Err("percentages need the size of the parent element")
+                }
+                unit => Ok(unit.to_px(*value.inner(), reference)),
+            },
+            _ => self.as_float(),
         }
     }
 
     /// Extract a numeric value as usize (casting f32 if necessary)
     pub fn as_usize(&self) -> Result<usize, &'static str> {
-        if let AttributeValue::Float(f) = self {
-            Ok(*f.inner() as usize)
-        } else {
-            Err("expected float value")
-        }
+        self.as_float().map(|f| f as usize)
     }
 
     /// Extract a numeric value as u16 (casting f32 if necessary)
     pub fn as_u16(&self) -> Result<u16, &'static str> {
-        if let AttributeValue::Float(f) = self {
-            Ok(*f.inner() as u16)
-        } else {
-            Err("expected float value")
-        }
+        self.as_float().map(|f| f as u16)
     }
 
     /// Extract a type spec, returning an error if this is not a type spec value
@@ -221,6 +282,42 @@ impl<'a> AttributeValue<'a> {
     }
 }
 
+/// Unit suffix of an [`AttributeValue::Dimension`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unit {
+    /// Pixels (`px`); bare numbers are pixels as well.
+    Px,
+    /// Points (`pt`), 1/72 inch, or 4/3 pixels.
+    Pt,
+    /// Percent (`%`) of the parent element's size.
+    Percent,
+}
+
+impl Unit {
+    /// Converts `value` in this unit to pixels, resolving a percentage against
+    /// `reference`.
+    pub fn to_px(self, value: f32, reference: f32) -> f32 {
+        match self {
+            Unit::Px => value,
+            Unit::Pt => value * 4.0 / 3.0,
+            Unit::Percent => reference * value / 100.0,
+        }
+    }
+}
+
+impl FromStr for Unit {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "px" => Ok(Unit::Px),
+            "pt" => Ok(Unit::Pt),
+            "%" => Ok(Unit::Percent),
+            _ => Err("unknown unit"),
+        }
+    }
+}
+
 /// Key-value attribute pair on a type specifier or element.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Attribute<'a> {
@@ -245,6 +342,50 @@ impl TypeDefinition<'_> {
     pub fn span(&self) -> Span {
         self.name.span().union(self.type_spec.span())
     }
+
+    /// Returns `true` if the definition is marked `allow_unused="true"`.
+    pub fn allows_unused(&self) -> bool {
+        self.type_spec
+            .attributes
+            .iter()
+            .rfind(|attr| *attr.name.inner() == "allow_unused")
+            .is_some_and(|attr| attr.value.as_str() == Ok("true"))
+    }
+}
+
+/// Variable Definition - binds a name to an attribute value with `let`
+///
+/// References to the variable, written `$name`, are replaced by the value
+/// during desugaring.
+#[derive(Debug, Clone)]
+pub struct VariableDefinition<'a> {
+    pub name: Spanned<Id>,
+    pub value: AttributeValue<'a>,
+}
+
+impl VariableDefinition<'_> {
+    pub fn span(&self) -> Span {
+        self.name.span().union(self.value.span())
+    }
+}
+
+/// Style Definition - a named set of attributes declared with `style`
+///
+/// Unlike a [`TypeDefinition`], a style has no base type: components opt in
+/// with `class="Name"`, and the style's attributes are merged beneath the
+/// component's own during elaboration.
+#[derive(Debug, Clone)]
+pub struct StyleDefinition<'a> {
+    pub name: Spanned<Id>,
+    pub attributes: Vec<Attribute<'a>>,
+}
+
+impl StyleDefinition<'_> {
+    pub fn span(&self) -> Span {
+        self.attributes
+            .iter()
+            .fold(self.name.span(), |acc, attr| acc.union(attr.span()))
+    }
 }
 
 /// File header — the first declaration in an Orrery file.
@@ -373,6 +514,10 @@ pub struct FileAst<'a> {
     pub import_decls: Vec<Spanned<ImportDecl>>,
     /// Named type aliases declared with `type Name = TypeSpec;`.
     pub type_definitions: Vec<TypeDefinition<'a>>,
+    /// Variables declared with `let name = value;`, in source order.
+    pub variable_definitions: Vec<VariableDefinition<'a>>,
+    /// Styles declared with `style Name { attr=value; ... }`, in source order.
+    pub style_definitions: Vec<StyleDefinition<'a>>,
     /// Diagram body elements (components, relations, fragments, etc.).
     /// Always empty for library files because they have no renderable body.
     pub elements: Vec<Element<'a>>,
@@ -388,10 +533,14 @@ impl FileAst<'_> {
         let header_span = self.header.span();
         let import_spans = self.import_decls.iter().map(Spanned::span);
         let type_def_spans = self.type_definitions.iter().map(|td| td.span());
+        let variable_spans = self.variable_definitions.iter().map(|vd| vd.span());
+        let style_spans = self.style_definitions.iter().map(|sd| sd.span());
         let element_spans = self.elements.iter().map(|elem| elem.span());
 
         import_spans
             .chain(type_def_spans)
+            .chain(variable_spans)
+            .chain(style_spans)
             .chain(element_spans)
             .fold(header_span, |acc, span| acc.union(span))
     }
@@ -459,6 +608,51 @@ impl Note<'_> {
     }
 }
 
+/// Highlight block that tints the time window spanned by its elements.
+#[derive(Debug, Clone)]
+pub struct Highlight<'a> {
+    /// The highlight label as a string literal.
+    pub label: Spanned<String>,
+    /// type specification.
+    pub type_spec: TypeSpec<'a>,
+    /// Elements enclosed by the highlight band.
+    pub elements: Vec<Element<'a>>,
+}
+
+impl Highlight<'_> {
+    pub fn span(&self) -> Span {
+        let span = self.label.span().union(self.type_spec.span());
+        self.elements
+            .iter()
+            .map(|elem| elem.span())
+            .fold(span, |acc, s| acc.union(s))
+    }
+}
+
+/// Group block that draws a labelled boundary around its elements.
+///
+/// Unlike a nested component, a group introduces no scope: its children are
+/// laid out alongside the group's siblings.
+#[derive(Debug, Clone)]
+pub struct Group<'a> {
+    /// The group label as a string literal.
+    pub label: Spanned<String>,
+    /// type specification.
+    pub type_spec: TypeSpec<'a>,
+    /// Elements enclosed by the group boundary.
+    pub elements: Vec<Element<'a>>,
+}
+
+impl Group<'_> {
+    pub fn span(&self) -> Span {
+        let span = self.label.span().union(self.type_spec.span());
+        self.elements
+            .iter()
+            .map(|elem| elem.span())
+            .fold(span, |acc, s| acc.union(s))
+    }
+}
+
 /// What content a component declaration carries.
 ///
 /// Used in the [`Element::Component`] variant to represent the component's
@@ -536,6 +730,9 @@ pub enum Element<'a> {
         type_spec: TypeSpec<'a>,
         /// The component's body — children, embedded diagram, or nothing.
         content: ComponentContent<'a>,
+        /// Line comments written above the declaration or after it on the same
+        /// line, in source order. Each holds the text following `//`.
+        comments: Vec<Spanned<String>>,
     },
     /// Directed relation between two components with an optional label.
     Relation {
@@ -544,6 +741,8 @@ pub enum Element<'a> {
         relation_type: Spanned<&'a str>,
         type_spec: TypeSpec<'a>,
         label: Option<Spanned<String>>,
+        /// Line comments attached to the relation, as for [`Element::Component`].
+        comments: Vec<Spanned<String>>,
     },
     /// Explicit fragment block declared.
     Fragment(Fragment<'a>),
@@ -599,9 +798,25 @@ pub enum Element<'a> {
     },
     /// Note element with optional attributes and text content.
     Note(Note<'a>),
+    /// Highlight band wrapping a list of elements.
+    Highlight(Highlight<'a>),
+    /// Group boundary wrapping a list of elements.
+    Group(Group<'a>),
 }
 
 impl Element<'_> {
+    /// Returns the line comments attached to this element.
+    ///
+    /// Only components and relations carry comments; other elements return an
+    /// empty slice.
+    #[allow(dead_code)]
+    pub fn comments(&self) -> &[Spanned<String>] {
+        match self {
+            Element::Component { comments, .. } | Element::Relation { comments, .. } => comments,
+            _ => &[],
+        }
+    }
+
     pub fn span(&self) -> Span {
         match self {
             Element::Component {
@@ -609,6 +824,7 @@ impl Element<'_> {
                 display_name,
                 type_spec,
                 content,
+                ..
             } => {
                 let mut span = name.span().union(type_spec.span()).union(content.span());
 
@@ -624,6 +840,7 @@ impl Element<'_> {
                 relation_type,
                 type_spec,
                 label,
+                ..
             } => {
                 let mut span = source
                     .span()
@@ -698,6 +915,8 @@ impl Element<'_> {
                 .union(section.span()),
 
             Element::Note(note) => note.span(),
+            Element::Highlight(highlight) => highlight.span(),
+            Element::Group(group) => group.span(),
         }
     }
 }
@@ -203,7 +203,7 @@ impl<'arena, P: SourceProvider> Resolver<'arena, P> {
         let tokens = lexer::tokenize(source, base_offset)?;
 
         // 8. Parse.
-        let mut file_ast = parser::build_file(&tokens).map_err(|diag| vec![diag])?;
+        let mut file_ast = parser::build_file(&tokens)?;
 
         // 9. Resolve each import declaration and populate `file_ast.imports`.
         for import_decl in &file_ast.import_decls {
@@ -6,6 +6,7 @@
 //!
 //! - [`Span`] - A byte range within source text.
 //! - [`Spanned<T>`] - A value with associated source location.
+//! - [`LineIndex`] - Converts between byte offsets and line/column positions.
 //!
 //! # Example
 //!
@@ -17,7 +18,7 @@
 //! assert_eq!(span.len(), 15);
 //! ```
 
-use std::{fmt, ops::Deref};
+use std::{fmt, iter, ops::Deref};
 
 /// A byte range representing a location in source text.
 ///
@@ -232,6 +233,125 @@ impl<T: PartialEq> PartialEq for Spanned<T> {
     }
 }
 
+/// The unit in which a [`LineIndex`] counts columns.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ColumnEncoding {
+    /// UTF-16 code units, the default position encoding of the Language
+    /// Server Protocol.
+    #[default]
+    Utf16,
+    /// Unicode scalar values, i.e. Rust `char`s.
+    Scalar,
+}
+
+impl ColumnEncoding {
+    /// Returns the number of columns `c` occupies.
+    fn width(self, c: char) -> usize {
+        match self {
+            ColumnEncoding::Utf16 => c.len_utf16(),
+            ColumnEncoding::Scalar => 1,
+        }
+    }
+}
+
+/// Converts between byte offsets and 0-based line/column positions.
+///
+/// The index records where each line starts, so it is built once per source
+/// text and then answers conversions in either direction. Lines are separated
+/// by `\n`; columns are counted in the index's [`ColumnEncoding`].
+///
+/// # Examples
+///
+/// ```
+/// # use orrery_parser::{ColumnEncoding, LineIndex};
+/// let index = LineIndex::new("diagram component;\napp: \"🚀\";");
+///
+/// assert_eq!(index.offset_to_line_col(24), (1, 5));
+/// assert_eq!(index.line_col_to_offset(1, 5), Some(24));
+///
+/// // `🚀` is four bytes, two UTF-16 code units, and one scalar value.
+/// assert_eq!(index.offset_to_line_col(29), (1, 8));
+/// let index = index.with_encoding(ColumnEncoding::Scalar);
+/// assert_eq!(index.offset_to_line_col(29), (1, 7));
+/// ```
+#[derive(Debug, Clone)]
+pub struct LineIndex<'a> {
+    source: &'a str,
+    /// Byte offset at which each line starts; the first is always `0`.
+    line_starts: Vec<usize>,
+    encoding: ColumnEncoding,
+}
+
+impl<'a> LineIndex<'a> {
+    /// Indexes the lines of `source`, counting columns in UTF-16 code units.
+    pub fn new(source: &'a str) -> Self {
+        let line_starts = iter::once(0)
+            .chain(source.match_indices('\n').map(|(index, _)| index + 1))
+            .collect();
+        Self {
+            source,
+            line_starts,
+            encoding: ColumnEncoding::default(),
+        }
+    }
+
+    /// Sets the unit in which columns are counted.
+    pub fn with_encoding(mut self, encoding: ColumnEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Returns the unit in which columns are counted.
+    pub fn encoding(&self) -> ColumnEncoding {
+        self.encoding
+    }
+
+    /// Returns the number of lines, counting the one after a trailing `\n`.
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Converts a byte offset into a 0-based `(line, column)` position.
+    ///
+    /// Offsets past the end of the source are clamped to it, and offsets
+    /// inside a multi-byte character resolve to the start of that character.
+    pub fn offset_to_line_col(&self, offset: usize) -> (u32, u32) {
+        let mut offset = offset.min(self.source.len());
+        while !self.source.is_char_boundary(offset) {
+            offset -= 1;
+        }
+
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        let column: usize = self.source[self.line_starts[line]..offset]
+            .chars()
+            .map(|c| self.encoding.width(c))
+            .sum();
+        (line as u32, column as u32)
+    }
+
+    /// Converts a 0-based `(line, column)` position into a byte offset.
+    ///
+    /// Returns `None` if `line` does not exist, or if `column` lies past the
+    /// end of the line or inside a character.
+    pub fn line_col_to_offset(&self, line: u32, column: u32) -> Option<usize> {
+        let line = line as usize;
+        let start = *self.line_starts.get(line)?;
+        let end = self
+            .line_starts
+            .get(line + 1)
+            .map_or(self.source.len(), |next| next - 1);
+
+        let mut remaining = column as usize;
+        for (index, c) in self.source[start..end].char_indices() {
+            if remaining == 0 {
+                return Some(start + index);
+            }
+            remaining = remaining.checked_sub(self.encoding.width(c))?;
+        }
+        (remaining == 0).then_some(end)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -365,4 +485,54 @@ mod tests {
         let spanned = Spanned::new(42, Span::new(0..5));
         assert_eq!(format!("{spanned}"), "42");
     }
+
+    #[test]
+    fn test_line_index_offset_to_line_col() {
+        let index = LineIndex::new("one\ntwo é😀x\n");
+
+        assert_eq!(index.line_count(), 3);
+        assert_eq!(index.offset_to_line_col(0), (0, 0));
+        assert_eq!(index.offset_to_line_col(3), (0, 3));
+        assert_eq!(index.offset_to_line_col(4), (1, 0));
+        // `é` is 2 bytes and 1 UTF-16 unit, `😀` is 4 bytes and 2 units.
+        assert_eq!(index.offset_to_line_col(14), (1, 7));
+        assert_eq!(index.offset_to_line_col(100), (2, 0));
+        // Inside `😀`, resolves to its start.
+        assert_eq!(index.offset_to_line_col(12), (1, 5));
+    }
+
+    #[test]
+    fn test_line_index_scalar_columns() {
+        let index = LineIndex::new("one\ntwo é😀x\n").with_encoding(ColumnEncoding::Scalar);
+
+        assert_eq!(index.encoding(), ColumnEncoding::Scalar);
+        assert_eq!(index.offset_to_line_col(14), (1, 6));
+        assert_eq!(index.line_col_to_offset(1, 6), Some(14));
+    }
+
+    #[test]
+    fn test_line_index_line_col_to_offset() {
+        let index = LineIndex::new("one\ntwo é😀x\n");
+
+        assert_eq!(index.line_col_to_offset(0, 0), Some(0));
+        assert_eq!(index.line_col_to_offset(1, 7), Some(14));
+        // End of line, before the `\n`.
+        assert_eq!(index.line_col_to_offset(1, 8), Some(15));
+        assert_eq!(index.line_col_to_offset(2, 0), Some(16));
+        // Inside the surrogate pair of `😀`.
+        assert_eq!(index.line_col_to_offset(1, 6), None);
+        assert_eq!(index.line_col_to_offset(1, 9), None);
+        assert_eq!(index.line_col_to_offset(3, 0), None);
+    }
+
+    #[test]
+    fn test_line_index_round_trips() {
+        let source = "diagram component;\r\napp: \"日本\";\n\nb: Oval;";
+        let index = LineIndex::new(source);
+
+        for (offset, _) in source.char_indices() {
+            let (line, column) = index.offset_to_line_col(offset);
+            assert_eq!(index.line_col_to_offset(line, column), Some(offset));
+        }
+    }
 }
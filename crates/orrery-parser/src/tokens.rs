@@ -5,6 +5,7 @@
 //!
 //! - [`Token`] - The token type enum covering keywords, literals, operators, and punctuation.
 //! - [`PositionedToken`] - A token paired with its [`Span`] in the source text.
+//! - [`TokenCategory`] - A coarse classification of tokens for syntax highlighting.
 
 use std::fmt;
 
@@ -40,18 +41,24 @@ pub enum Token<'src> {
     // Literals
     StringLiteral(String),
     FloatLiteral(f32),
+    /// A number with a unit suffix, e.g. `2px` or `50%`.
+    DimensionLiteral(f32, &'src str),
     Identifier(&'src str),
+    /// A variable reference such as `$brand`, without the `$`.
+    Variable(&'src str),
 
     // Operators
-    Arrow_,      // ->
-    LeftArrow,   // <-
-    DoubleArrow, // <->
-    Plain,       // -
-    Equals,      // =
-    Colon,       // :
-    DoubleColon, // ::
-    Star,        // *
-    At,          // @
+    Arrow_,         // ->
+    LeftArrow,      // <-
+    DoubleArrow,    // <->
+    AsyncArrow,     // ->>
+    AsyncLeftArrow, // <<-
+    Plain,          // -
+    Equals,         // =
+    Colon,          // :
+    DoubleColon,    // ::
+    Star,           // *
+    At,             // @
 
     // Punctuation
     LeftBrace,    // {
@@ -62,17 +69,94 @@ pub enum Token<'src> {
     Comma,        // ,
 
     // Comments
-    LineComment(&'src str), // // comment
+    LineComment(&'src str),  // // comment
+    BlockComment(&'src str), // /* comment */, may be nested
 
     // Whitespace
     Whitespace,
     Newline,
 }
 
+/// Coarse classification of a [`Token`], used to map tokens to editor scopes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenCategory {
+    /// A reserved word such as `diagram` or `activate`.
+    Keyword,
+    /// A component, type, or attribute name.
+    Identifier,
+    /// A string literal, including multi-line strings.
+    String,
+    /// A plain number or a number with a unit, such as `2px`.
+    Number,
+    /// An operator, bracket, or separator.
+    Punctuation,
+    /// A `//` line comment or a `/* */` block comment.
+    Comment,
+    /// Spaces, tabs, and line breaks.
+    Whitespace,
+    /// Text the lexer could not turn into a token.
+    Invalid,
+}
+
+impl Token<'_> {
+    /// Returns the highlighting category of this token.
+    pub fn category(&self) -> TokenCategory {
+        match self {
+            Token::Diagram
+            | Token::Component
+            | Token::Sequence
+            | Token::Type
+            | Token::Embed
+            | Token::As
+            | Token::Import
+            | Token::Library
+            | Token::Activate
+            | Token::Deactivate
+            | Token::Fragment
+            | Token::Section
+            | Token::Alt
+            | Token::Else
+            | Token::Opt
+            | Token::Loop
+            | Token::Par
+            | Token::Break
+            | Token::Critical
+            | Token::Note => TokenCategory::Keyword,
+
+            Token::StringLiteral(_) => TokenCategory::String,
+            Token::FloatLiteral(_) | Token::DimensionLiteral(..) => TokenCategory::Number,
+            Token::Identifier(_) | Token::Variable(_) => TokenCategory::Identifier,
+
+            Token::Arrow_
+            | Token::LeftArrow
+            | Token::DoubleArrow
+            | Token::AsyncArrow
+            | Token::AsyncLeftArrow
+            | Token::Plain
+            | Token::Equals
+            | Token::Colon
+            | Token::DoubleColon
+            | Token::Star
+            | Token::At
+            | Token::LeftBrace
+            | Token::RightBrace
+            | Token::LeftBracket
+            | Token::RightBracket
+            | Token::Semicolon
+            | Token::Comma => TokenCategory::Punctuation,
+
+            Token::LineComment(_) | Token::BlockComment(_) => TokenCategory::Comment,
+            Token::Whitespace | Token::Newline => TokenCategory::Whitespace,
+        }
+    }
+}
+
 /// A token with position information for winnow integration.
 #[derive(Debug, Clone, PartialEq)]
 pub struct PositionedToken<'src> {
+    /// The token itself.
     pub token: Token<'src>,
+    /// Where the token appears in the source text.
     pub span: Span,
 }
 
@@ -144,11 +228,15 @@ impl fmt::Display for Token<'_> {
 
             Token::StringLiteral(s) => write!(f, "\"{s}\""),
             Token::FloatLiteral(n) => write!(f, "{n}"),
+            Token::DimensionLiteral(n, unit) => write!(f, "{n}{unit}"),
             Token::Identifier(name) => write!(f, "{name}"),
+            Token::Variable(name) => write!(f, "${name}"),
 
             Token::Arrow_ => write!(f, "->"),
             Token::LeftArrow => write!(f, "<-"),
             Token::DoubleArrow => write!(f, "<->"),
+            Token::AsyncArrow => write!(f, "->>"),
+            Token::AsyncLeftArrow => write!(f, "<<-"),
             Token::Plain => write!(f, "-"),
             Token::Equals => write!(f, "="),
             Token::Colon => write!(f, ":"),
@@ -164,6 +252,7 @@ impl fmt::Display for Token<'_> {
             Token::Comma => write!(f, ","),
 
             Token::LineComment(comment) => write!(f, "//{comment}"),
+            Token::BlockComment(comment) => write!(f, "/*{comment}*/"),
             Token::Whitespace => write!(f, " "),
             Token::Newline => write!(f, "\\n"),
         }
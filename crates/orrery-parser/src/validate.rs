@@ -7,12 +7,23 @@
 //! ## Validations Performed
 //!
 //! - **Component Identifier References**: Validates that all component identifiers referenced
-//!   in relations, notes, and activation statements are defined in the diagram.
+//!   in relations, notes, and activation statements are defined in the diagram, suggesting
+//!   the closest declared name for a likely typo. With the `implicit_nodes="true"` diagram
+//!   attribute, undeclared relation endpoints are only warned about; the
+//!   [`ImplicitNodes`] policy of the [`ElaborateConfig`] sets the default and
+//!   can deny them outright.
 //! - **Activate/Deactivate Pairing**: Ensures activate statements have corresponding deactivate
 //!   statements in sequence diagrams.
 //! - **Note Alignment**: Validates that note alignment values are appropriate for the diagram type.
 //! - **Embed Reference Resolution**: Validates that all `DiagramSource::Ref` nodes were resolved
 //!   during desugaring. Surviving refs indicate an unknown embed reference.
+//! - **Blank Labels**: Warns about relation labels and display names that contain only
+//!   whitespace. An explicitly empty label (`""`) is intentional and renders no text.
+//! - **Units**: Validates that numeric attribute values only use the `px`, `pt` and `%` units.
+//! - **Variables**: Reports `$name` references that desugaring could not resolve, and warns
+//!   when a file defines the same variable twice.
+//! - **Styles**: Validates that each component's `class` attribute names a style defined in
+//!   the file, and warns when a file defines the same style twice.
 
 use std::{
     collections::{HashMap, HashSet},
@@ -22,11 +33,13 @@ use std::{
 use orrery_core::{identifier::Id, semantic::DiagramKind};
 
 use crate::{
-    builtin_types,
+    ElaborateConfig, builtin_types,
+    elaborate::ImplicitNodes,
     error::{Diagnostic, DiagnosticCollector, ErrorCode},
     parser_types::{
         Attribute, AttributeValue, ComponentContent, DiagramSource, Element, FileAst, FileHeader,
-        Fragment, FragmentSection, Import, Note, TypeDefinition, TypeSpec,
+        Fragment, FragmentSection, Group, Highlight, Import, Note, StyleDefinition, TypeDefinition,
+        TypeSpec, Unit, VariableDefinition,
     },
     span::{Span, Spanned},
 };
@@ -36,11 +49,13 @@ use crate::{
 /// Each method takes a reference to its input and can accumulate state or errors.
 /// Default implementations perform recursive traversal so implementors can override
 /// only the methods they care about.
-trait Visitor<'a> {
+pub(crate) trait Visitor<'a> {
     /// Walks a complete [`FileAst`].
     fn visit_file_ast(&mut self, file_ast: &FileAst<'a>) {
         self.visit_header(&file_ast.header);
         self.visit_imports(&file_ast.imports);
+        self.visit_variable_definitions(&file_ast.variable_definitions);
+        self.visit_style_definitions(&file_ast.style_definitions);
         self.visit_type_definitions(&file_ast.type_definitions);
         self.visit_elements(&file_ast.elements);
     }
@@ -87,9 +102,21 @@ trait Visitor<'a> {
         match value {
             AttributeValue::String(s) => self.visit_string_value(s),
             AttributeValue::Float(f) => self.visit_float_value(f),
+            AttributeValue::Dimension { value, unit } => self.visit_dimension_value(value, unit),
             AttributeValue::TypeSpec(type_spec) => self.visit_type_spec(type_spec),
             AttributeValue::Identifiers(ids) => self.visit_identifiers(ids),
             AttributeValue::Empty => {}
+            AttributeValue::Variable(name) => self.visit_variable_reference(name),
+        }
+    }
+
+    /// Visits a `$name` variable reference left unresolved by desugaring.
+    fn visit_variable_reference(&mut self, _name: &Spanned<Id>) {}
+
+    /// Visits a list of variable definitions.
+    fn visit_variable_definitions(&mut self, variable_definitions: &[VariableDefinition<'a>]) {
+        for variable_def in variable_definitions {
+            self.visit_attribute_value(&variable_def.value);
         }
     }
 
@@ -99,6 +126,9 @@ trait Visitor<'a> {
     /// Visits a float attribute value.
     fn visit_float_value(&mut self, _value: &Spanned<f32>) {}
 
+    /// Visits a dimension attribute value and its unit suffix.
+    fn visit_dimension_value(&mut self, _value: &Spanned<f32>, _unit: &Spanned<&'a str>) {}
+
     /// Visits a single identifier (component reference).
     fn visit_identifier(&mut self, _identifier: &Spanned<Id>) {}
 
@@ -109,6 +139,13 @@ trait Visitor<'a> {
         }
     }
 
+    /// Visits a list of style definitions.
+    fn visit_style_definitions(&mut self, style_definitions: &[StyleDefinition<'a>]) {
+        for style_def in style_definitions {
+            self.visit_attributes(&style_def.attributes);
+        }
+    }
+
     /// Visits a list of type definitions.
     fn visit_type_definitions(&mut self, type_definitions: &[TypeDefinition<'a>]) {
         for td in type_definitions {
@@ -153,6 +190,7 @@ trait Visitor<'a> {
                 ref display_name,
                 ref type_spec,
                 ref content,
+                ..
             } => self.visit_component(name, display_name, type_spec, content),
             Element::Relation {
                 ref source,
@@ -160,6 +198,7 @@ trait Visitor<'a> {
                 ref relation_type,
                 ref type_spec,
                 ref label,
+                ..
             } => self.visit_relation(source, target, relation_type, type_spec, label),
             Element::ActivateBlock {
                 ref component,
@@ -227,9 +266,25 @@ trait Visitor<'a> {
             Element::Note(ref note) => {
                 self.visit_note(note);
             }
+            Element::Highlight(ref highlight) => self.visit_highlight(highlight),
+            Element::Group(ref group) => self.visit_group(group),
         }
     }
 
+    /// Visits a highlight block.
+    fn visit_highlight(&mut self, highlight: &Highlight<'a>) {
+        self.visit_type_spec(&highlight.type_spec);
+        self.visit_string_value(&highlight.label);
+        self.visit_elements(&highlight.elements);
+    }
+
+    /// Visits a group block.
+    fn visit_group(&mut self, group: &Group<'a>) {
+        self.visit_type_spec(&group.type_spec);
+        self.visit_string_value(&group.label);
+        self.visit_elements(&group.elements);
+    }
+
     /// Visits a fragment.
     fn visit_fragment(&mut self, fragment: &Fragment<'a>) {
         for section in &fragment.sections {
@@ -356,7 +411,7 @@ trait Visitor<'a> {
 }
 
 /// Entry point for running a visitor on a file AST.
-fn visit_file_ast<'a, V: Visitor<'a>>(visitor: &mut V, file_ast: &FileAst<'a>) {
+pub(crate) fn visit_file_ast<'a, V: Visitor<'a>>(visitor: &mut V, file_ast: &FileAst<'a>) {
     visitor.visit_file_ast(file_ast)
 }
 
@@ -364,7 +419,14 @@ struct FileAstState {
     type_registry: HashSet<Id>,
     activation_stack: HashMap<Id, Vec<Span>>,
     component_registry: HashMap<Id, Span>,
+    /// Styles defined in the file, which components reference with `class`.
+    style_registry: HashMap<Id, Span>,
     diagram_kind: Option<DiagramKind>,
+    /// Whether undeclared relation endpoints become implicit components.
+    implicit_nodes: bool,
+    /// Whether the diagram asks for implicit components that the
+    /// configuration denies.
+    implicit_nodes_denied: bool,
 }
 
 impl FileAstState {
@@ -375,7 +437,10 @@ impl FileAstState {
             type_registry,
             activation_stack: HashMap::new(),
             component_registry: HashMap::new(),
+            style_registry: HashMap::new(),
             diagram_kind: None,
+            implicit_nodes: false,
+            implicit_nodes_denied: false,
         }
     }
 }
@@ -400,6 +465,10 @@ impl FileAstState {
 pub struct Validator {
     state: FileAstState,
     diagnostics: DiagnosticCollector,
+    /// How undeclared relation endpoints are treated.
+    implicit_nodes: ImplicitNodes,
+    /// Type that implicit components are drawn as.
+    implicit_node_type: Id,
 }
 
 impl Validator {
@@ -408,9 +477,19 @@ impl Validator {
         Self {
             state: FileAstState::new(),
             diagnostics: DiagnosticCollector::new(),
+            implicit_nodes: ImplicitNodes::default(),
+            implicit_node_type: Id::new(builtin_types::RECTANGLE),
         }
     }
 
+    /// Sets how undeclared relation endpoints are treated and the type that
+    /// implicit components are drawn as.
+    pub fn with_implicit_nodes(mut self, implicit_nodes: ImplicitNodes, type_name: Id) -> Self {
+        self.implicit_nodes = implicit_nodes;
+        self.implicit_node_type = type_name;
+        self
+    }
+
     /// Validates that an `align` value is appropriate for the current diagram type.
     ///
     /// Sequence diagrams support: over, left, right
@@ -455,26 +534,111 @@ impl Validator {
         }
     }
 
+    /// Warns when a label contains only whitespace.
+    ///
+    /// An empty label (`""`) is treated as an intentional request for no text,
+    /// but a whitespace-only label still renders an invisible text element
+    /// and is most likely a mistake.
+    fn validate_label(&mut self, label: &Spanned<String>, kind: &str) {
+        if !label.is_empty() && label.trim().is_empty() {
+            self.diagnostics.emit(
+                Diagnostic::warning(format!("{kind} contains only whitespace"))
+                    .with_label(label.span(), "blank label")
+                    .with_help("use `\"\"` for an intentionally empty label, or add text"),
+            );
+        }
+    }
+
+    /// Checks that a referenced component is declared.
+    ///
+    /// An undeclared component is an `E200` error, or a warning when
+    /// `implicit` is set because elaboration then creates it. Either way the
+    /// components of the same name in scopes the reference cannot see are
+    /// listed, or else the closest declared name is suggested when it looks
+    /// like a typo. A reference matching several such components is reported
+    /// as ambiguous.
+    fn validate_component_reference(&mut self, identifier: &Spanned<Id>, implicit: bool) {
+        let id = identifier.inner();
+        if self.state.component_registry.contains_key(id) {
+            return;
+        }
+
+        let mut namesakes: Vec<String> = self
+            .state
+            .component_registry
+            .keys()
+            .filter(|candidate| candidate.name() == id.name())
+            .map(|candidate| format!("`{candidate}`"))
+            .collect();
+        namesakes.sort();
+
+        let name = id.to_string();
+        let suggestion = self
+            .state
+            .component_registry
+            .keys()
+            .map(|candidate| candidate.to_string())
+            .filter_map(|candidate| {
+                let distance = edit_distance(&name, &candidate);
+                (distance <= (name.chars().count() / 3).max(1)).then_some((distance, candidate))
+            })
+            .min();
+
+        let diagnostic = if implicit {
+            Diagnostic::warning(format!("component `{id}` is not declared"))
+                .with_label(identifier.span(), "implicit component")
+        } else if namesakes.len() > 1 {
+            Diagnostic::error(format!("ambiguous component reference `{}`", id.name()))
+                .with_code(ErrorCode::E200)
+                .with_label(identifier.span(), "matches components in other scopes")
+        } else {
+            Diagnostic::error(format!("component `{id}` not found"))
+                .with_code(ErrorCode::E200)
+                .with_label(identifier.span(), "undefined component")
+        };
+        let help = match (namesakes.as_slice(), suggestion) {
+            ([namesake], _) => format!("did you mean {namesake}?"),
+            ([_, _, ..], _) => format!(
+                "`{}` may refer to {}; use a qualified name",
+                id.name(),
+                namesakes.join(", ")
+            ),
+            (_, Some((_, candidate))) => format!("did you mean `{candidate}`?"),
+            _ if implicit => format!("`{id}` is drawn as a default `{}`", self.implicit_node_type),
+            _ if self.state.implicit_nodes_denied => format!(
+                "implicit components are disabled by the configuration; declare `{id}` before referencing it"
+            ),
+            _ => "component must be defined before it can be referenced".to_string(),
+        };
+        self.diagnostics.emit(diagnostic.with_help(help));
+    }
+
     fn validate_file_ast_state(&mut self) {
         self.validate_activation_stack_pairs();
     }
 
-    // Validate any remaining unpaired activations.
+    /// Reports every activation that is still open at the end of the diagram,
+    /// in source order.
     fn validate_activation_stack_pairs(&mut self) {
-        for (component_id, spans) in self.state.activation_stack.iter() {
-            if !spans.is_empty() {
-                let span = spans.last().cloned().unwrap_or_default();
-                self.diagnostics.emit(
-                    Diagnostic::error(format!(
-                        "component `{component_id}` was activated but never deactivated"
-                    ))
-                    .with_code(ErrorCode::E201)
-                    .with_label(span, "unpaired activate")
-                    .with_help(
-                        "every activate statement must have a corresponding deactivate statement",
-                    ),
-                );
-            }
+        let mut unpaired: Vec<(Id, Span)> = self
+            .state
+            .activation_stack
+            .iter()
+            .flat_map(|(component_id, spans)| spans.iter().map(|span| (*component_id, *span)))
+            .collect();
+        unpaired.sort_by_key(|(_, span)| span.start());
+
+        for (component_id, span) in unpaired {
+            self.diagnostics.emit(
+                Diagnostic::error(format!(
+                    "component `{component_id}` was activated but never deactivated"
+                ))
+                .with_code(ErrorCode::E201)
+                .with_label(span, "unpaired activate")
+                .with_help(format!(
+                    "add `deactivate {component_id};` after its messages, or use the block form `activate {component_id} {{ ... }};`"
+                )),
+            );
         }
     }
 }
@@ -483,10 +647,21 @@ impl<'a> Visitor<'a> for Validator {
     /// Pushes a fresh component registry scope before visiting the file's children.
     fn visit_file_ast(&mut self, file_ast: &FileAst<'a>) {
         let last_state = mem::replace(&mut self.state, FileAstState::new());
+        if let FileHeader::Diagram { attributes, .. } = &file_ast.header {
+            let attribute = attributes
+                .iter()
+                .rfind(|attr| *attr.name.inner() == "implicit_nodes")
+                .map(|attr| attr.value.as_str() == Ok("true"));
+            self.state.implicit_nodes = self.implicit_nodes.resolve(attribute);
+            self.state.implicit_nodes_denied =
+                self.implicit_nodes == ImplicitNodes::Deny && attribute == Some(true);
+        }
 
         // Call default traversal
         self.visit_header(&file_ast.header);
         self.visit_imports(&file_ast.imports);
+        self.visit_variable_definitions(&file_ast.variable_definitions);
+        self.visit_style_definitions(&file_ast.style_definitions);
         self.visit_type_definitions(&file_ast.type_definitions);
         self.visit_elements(&file_ast.elements);
 
@@ -521,13 +696,61 @@ impl<'a> Visitor<'a> for Validator {
         }
     }
 
-    /// Registers the component name in the current diagram's component registry.
+    /// Checks that the unit suffix is supported, emitting `E206` if it is unknown.
+    fn visit_dimension_value(&mut self, _value: &Spanned<f32>, unit: &Spanned<&'a str>) {
+        if unit.inner().parse::<Unit>().is_err() {
+            self.diagnostics.emit(
+                Diagnostic::error(format!("unknown unit `{unit}`"))
+                    .with_code(ErrorCode::E206)
+                    .with_label(unit.span(), "unknown unit")
+                    .with_help(
+                        "supported units are `px`, `pt` and `%`; numbers without a unit are pixels",
+                    ),
+            );
+        }
+    }
+
+    /// Registers the component name in the current diagram's component registry,
+    /// emitting `E209` if it is already declared.
+    ///
+    /// Names are fully qualified, so components of different scopes may share
+    /// a short name.
     fn visit_component_name(&mut self, name: &Spanned<Id>) {
+        if let Some(&first) = self.state.component_registry.get(name.inner()) {
+            self.diagnostics.emit(
+                Diagnostic::error(format!("component `{name}` is declared more than once"))
+                    .with_code(ErrorCode::E209)
+                    .with_label(name.span(), "declared again here")
+                    .with_secondary_label(first, "first declared here")
+                    .with_help("rename one of the components, or remove the duplicate declaration"),
+            );
+            return;
+        }
         self.state
             .component_registry
             .insert(*name.inner(), name.span());
     }
 
+    /// Warns about a whitespace-only display name.
+    fn visit_display_name(&mut self, display_name: &Spanned<String>) {
+        self.validate_label(display_name, "display name");
+    }
+
+    /// Checks the relation source, which may be an implicit component.
+    fn visit_relation_source(&mut self, source: &Spanned<Id>) {
+        self.validate_component_reference(source, self.state.implicit_nodes);
+    }
+
+    /// Checks the relation target, which may be an implicit component.
+    fn visit_relation_target(&mut self, target: &Spanned<Id>) {
+        self.validate_component_reference(target, self.state.implicit_nodes);
+    }
+
+    /// Warns about a whitespace-only relation label.
+    fn visit_relation_label(&mut self, label: &Spanned<String>) {
+        self.validate_label(label, "relation label");
+    }
+
     /// Validates the activation target and pushes it onto the activation stack.
     fn visit_activate(&mut self, component: &Spanned<Id>, type_spec: &TypeSpec<'a>) {
         // Validate component identifier exists
@@ -618,21 +841,123 @@ impl<'a> Visitor<'a> for Validator {
         }
     }
 
+    /// Emits `E207` for a `$name` reference that survived desugaring.
+    fn visit_variable_reference(&mut self, name: &Spanned<Id>) {
+        self.diagnostics.emit(
+            Diagnostic::error(format!("variable `${}` is not defined", name.inner()))
+                .with_code(ErrorCode::E207)
+                .with_label(name.span(), ErrorCode::E207.description())
+                .with_help(format!(
+                    "define it before the diagram elements: `let {} = ...;`",
+                    name.inner()
+                )),
+        );
+    }
+
+    /// Warns when a file defines the same variable more than once.
+    ///
+    /// The later definition wins for everything that follows it.
+    fn visit_variable_definitions(&mut self, variable_definitions: &[VariableDefinition<'a>]) {
+        let mut defined: HashMap<Id, Span> = HashMap::new();
+        for variable_def in variable_definitions {
+            let name = variable_def.name.inner();
+            if let Some(first) = defined.insert(*name, variable_def.name.span()) {
+                self.diagnostics.emit(
+                    Diagnostic::warning(format!("variable `{name}` is redefined"))
+                        .with_label(variable_def.name.span(), "redefined here")
+                        .with_secondary_label(first, "previously defined here")
+                        .with_help("references after this point use the new value"),
+                );
+            }
+            self.visit_attribute_value(&variable_def.value);
+        }
+    }
+
+    /// Registers the file's styles, warning when a file defines the same style
+    /// more than once.
+    ///
+    /// The later definition replaces the earlier one.
+    fn visit_style_definitions(&mut self, style_definitions: &[StyleDefinition<'a>]) {
+        for style_def in style_definitions {
+            let name = style_def.name.inner();
+            if let Some(first) = self
+                .state
+                .style_registry
+                .insert(*name, style_def.name.span())
+            {
+                self.diagnostics.emit(
+                    Diagnostic::warning(format!("style `{name}` is redefined"))
+                        .with_label(style_def.name.span(), "redefined here")
+                        .with_secondary_label(first, "previously defined here")
+                        .with_help("components use the attributes of the last definition"),
+                );
+            }
+            self.visit_attributes(&style_def.attributes);
+        }
+    }
+
+    /// Visits a component, emitting `E208` if its `class` names an unknown style.
+    fn visit_component(
+        &mut self,
+        name: &Spanned<Id>,
+        display_name: &Option<Spanned<String>>,
+        type_spec: &TypeSpec<'a>,
+        content: &ComponentContent<'a>,
+    ) {
+        for attr in type_spec
+            .attributes
+            .iter()
+            .filter(|attr| *attr.name.inner() == "class")
+        {
+            match attr.value.as_str() {
+                Ok(class) if self.state.style_registry.contains_key(&Id::new(class)) => {}
+                Ok(class) => self.diagnostics.emit(
+                    Diagnostic::error(format!("style `{class}` is not defined"))
+                        .with_code(ErrorCode::E208)
+                        .with_label(attr.value.span(), ErrorCode::E208.description())
+                        .with_help(format!(
+                            "define it before the diagram elements: `style {class} {{ ... }}`"
+                        )),
+                ),
+                Err(_) => self.diagnostics.emit(
+                    Diagnostic::error("`class` must name a style")
+                        .with_code(ErrorCode::E208)
+                        .with_label(attr.value.span(), "expected a style name")
+                        .with_help(
+                            "write the style name as a string, e.g. `class=\"Highlighted\"`",
+                        ),
+                ),
+            }
+        }
+
+        self.visit_component_name(name);
+        if let Some(dn) = display_name {
+            self.visit_display_name(dn);
+        }
+        self.visit_type_spec(type_spec);
+        self.visit_component_content(content);
+    }
+
     /// Checks that the referenced component exists in the registry, emitting `E200` if not found.
     fn visit_identifier(&mut self, identifier: &Spanned<Id>) {
-        if !self
-            .state
-            .component_registry
-            .contains_key(identifier.inner())
-        {
-            self.diagnostics.emit(
-                Diagnostic::error(format!("component `{}` not found", identifier.inner()))
-                    .with_code(ErrorCode::E200)
-                    .with_label(identifier.span(), "undefined component")
-                    .with_help("component must be defined before it can be referenced"),
-            );
+        self.validate_component_reference(identifier, false);
+    }
+}
+
+/// Returns the Levenshtein distance between two strings, counted in characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
         }
     }
+    row[b.len()]
 }
 
 /// Convenience function to run all file AST validations.
@@ -643,17 +968,24 @@ impl<'a> Visitor<'a> for Validator {
 /// # Arguments
 ///
 /// * `ast` - The parsed and desugared [`FileAst`] to validate.
+/// * `config` - The elaboration configuration, whose implicit node settings
+///   decide whether undeclared relation endpoints are errors.
 ///
 /// # Returns
 ///
-/// - `Ok(())` when no validation issues are found.
+/// - `Ok(Vec<Diagnostic>)` with the warnings found, if any, when no check
+///   fails.
 /// - `Err(Vec<Diagnostic>)` with all collected diagnostics otherwise.
 ///
 /// # Errors
 ///
 /// Returns `Vec<Diagnostic>` if one or more semantic validation checks fail.
-pub fn validate(ast: &FileAst<'_>) -> Result<(), Vec<Diagnostic>> {
-    let mut validator = Validator::new();
+pub fn validate(
+    ast: &FileAst<'_>,
+    config: &ElaborateConfig,
+) -> Result<Vec<Diagnostic>, Vec<Diagnostic>> {
+    let mut validator =
+        Validator::new().with_implicit_nodes(config.implicit_nodes, config.implicit_node_type);
     visit_file_ast(&mut validator, ast);
     validator.diagnostics.finish()
 }
@@ -749,6 +1081,7 @@ mod tests {
                         attributes: vec![],
                     },
                     content: ComponentContent::None,
+                    comments: Vec::new(),
                 },
                 Element::Activate {
                     component: Spanned::new(Id::new("user"), Span::new(30..34)),
@@ -760,12 +1093,15 @@ mod tests {
                     relation_type: Spanned::new("->", Span::new(45..47)),
                     type_spec: TypeSpec::default(),
                     label: None,
+                    comments: Vec::new(),
                 },
                 Element::Deactivate {
                     component: Spanned::new(Id::new("user"), Span::new(60..64)),
                 },
             ],
             imports: vec![],
+            variable_definitions: vec![],
+            style_definitions: vec![],
         };
 
         let mut visitor = CountingVisitor::new();
@@ -795,6 +1131,7 @@ mod tests {
                         attributes: vec![],
                     },
                     content: ComponentContent::None,
+                    comments: Vec::new(),
                 },
                 Element::Activate {
                     component: Spanned::new(Id::new("user"), Span::new(17..21)),
@@ -805,9 +1142,11 @@ mod tests {
                 },
             ],
             imports: vec![],
+            variable_definitions: vec![],
+            style_definitions: vec![],
         };
 
-        let result = validate(&diagram);
+        let result = validate(&diagram, &ElaborateConfig::default());
         assert!(result.is_ok());
     }
 
@@ -824,9 +1163,11 @@ mod tests {
                 component: Spanned::new(Id::new("user"), Span::new(0..4)),
             }],
             imports: vec![],
+            variable_definitions: vec![],
+            style_definitions: vec![],
         };
 
-        let result = validate(&diagram);
+        let result = validate(&diagram, &ElaborateConfig::default());
         assert!(result.is_err());
     }
 
@@ -844,9 +1185,11 @@ mod tests {
                 type_spec: TypeSpec::default(),
             }],
             imports: vec![],
+            variable_definitions: vec![],
+            style_definitions: vec![],
         };
 
-        let result = validate(&diagram);
+        let result = validate(&diagram, &ElaborateConfig::default());
         assert!(result.is_err());
     }
 
@@ -868,6 +1211,7 @@ mod tests {
                         attributes: vec![],
                     },
                     content: ComponentContent::None,
+                    comments: Vec::new(),
                 },
                 Element::Activate {
                     component: Spanned::new(Id::new("user"), Span::new(17..21)),
@@ -885,9 +1229,11 @@ mod tests {
                 },
             ],
             imports: vec![],
+            variable_definitions: vec![],
+            style_definitions: vec![],
         };
 
-        let result = validate(&diagram);
+        let result = validate(&diagram, &ElaborateConfig::default());
         assert!(result.is_ok());
     }
 
@@ -909,6 +1255,7 @@ mod tests {
                         attributes: vec![],
                     },
                     content: ComponentContent::None,
+                    comments: Vec::new(),
                 },
                 Element::Component {
                     name: Spanned::new(Id::new("server"), Span::new(17..23)),
@@ -918,6 +1265,7 @@ mod tests {
                         attributes: vec![],
                     },
                     content: ComponentContent::None,
+                    comments: Vec::new(),
                 },
                 Element::Activate {
                     component: Spanned::new(Id::new("user"), Span::new(36..40)),
@@ -935,9 +1283,11 @@ mod tests {
                 },
             ],
             imports: vec![],
+            variable_definitions: vec![],
+            style_definitions: vec![],
         };
 
-        let result = validate(&diagram);
+        let result = validate(&diagram, &ElaborateConfig::default());
         assert!(result.is_ok());
     }
 
@@ -960,9 +1310,11 @@ mod tests {
                 },
             ],
             imports: vec![],
+            variable_definitions: vec![],
+            style_definitions: vec![],
         };
 
-        let result = validate(&diagram);
+        let result = validate(&diagram, &ElaborateConfig::default());
         assert!(result.is_err());
     }
 }
@@ -986,7 +1338,7 @@ mod note_validation_tests {
 
         let tokens = tokenize(input, 0).expect("Failed to tokenize");
         let ast = build_file(&tokens).expect("Failed to parse");
-        let result = validate(&ast);
+        let result = validate(&ast, &ElaborateConfig::default());
         assert!(result.is_ok(), "Valid notes should pass validation");
     }
 
@@ -1004,7 +1356,7 @@ mod note_validation_tests {
 
         let tokens = tokenize(input, 0).expect("Failed to tokenize");
         let ast = build_file(&tokens).expect("Failed to parse");
-        let result = validate(&ast);
+        let result = validate(&ast, &ElaborateConfig::default());
         assert!(result.is_ok(), "Valid notes should pass validation");
     }
 
@@ -1019,7 +1371,7 @@ mod note_validation_tests {
 
         let tokens = tokenize(input, 0).expect("Failed to tokenize");
         let ast = build_file(&tokens).expect("Failed to parse");
-        let result = validate(&ast);
+        let result = validate(&ast, &ElaborateConfig::default());
         assert!(result.is_err(), "Invalid align should fail validation");
 
         let err = result.unwrap_err();
@@ -1037,7 +1389,7 @@ mod note_validation_tests {
 
         let tokens = tokenize(input, 0).expect("Failed to tokenize");
         let ast = build_file(&tokens).expect("Failed to parse");
-        let result = validate(&ast);
+        let result = validate(&ast, &ElaborateConfig::default());
         assert!(result.is_err(), "Invalid align should fail validation");
 
         let err = result.unwrap_err();
@@ -1056,7 +1408,7 @@ mod note_validation_tests {
 
         let tokens = tokenize(input, 0).expect("Failed to tokenize");
         let ast = build_file(&tokens).expect("Failed to parse");
-        let result = validate(&ast);
+        let result = validate(&ast, &ElaborateConfig::default());
         assert!(result.is_ok(), "Valid spanning note should pass validation");
     }
 
@@ -1071,7 +1423,7 @@ mod note_validation_tests {
 
         let tokens = tokenize(input, 0).expect("Failed to tokenize");
         let ast = build_file(&tokens).expect("Failed to parse");
-        let result = validate(&ast);
+        let result = validate(&ast, &ElaborateConfig::default());
         assert!(
             result.is_ok(),
             "Empty on attribute should be valid (margin note)"
@@ -1134,6 +1486,7 @@ mod identifier_validation_tests {
                                 attributes: vec![],
                             },
                             content: ComponentContent::None,
+                            comments: Vec::new(),
                         },
                         Element::Component {
                             name: Spanned::new(Id::new("frontend::ui"), Span::new(45..57)),
@@ -1146,8 +1499,10 @@ mod identifier_validation_tests {
                                 attributes: vec![],
                             },
                             content: ComponentContent::None,
+                            comments: Vec::new(),
                         },
                     ]),
+                    comments: Vec::new(),
                 },
                 Element::Component {
                     name: Spanned::new(Id::new("backend"), Span::new(69..76)),
@@ -1167,10 +1522,14 @@ mod identifier_validation_tests {
                             attributes: vec![],
                         },
                         content: ComponentContent::None,
+                        comments: Vec::new(),
                     }]),
+                    comments: Vec::new(),
                 },
             ],
             imports: vec![],
+            variable_definitions: vec![],
+            style_definitions: vec![],
         };
 
         let mut validator = Validator::new();
@@ -1256,6 +1615,7 @@ mod identifier_validation_tests {
                         attributes: vec![],
                     },
                     content: ComponentContent::None,
+                    comments: Vec::new(),
                 },
                 Element::Component {
                     name: Spanned::new(Id::new("db"), Span::new(15..17)),
@@ -1265,6 +1625,7 @@ mod identifier_validation_tests {
                         attributes: vec![],
                     },
                     content: ComponentContent::None,
+                    comments: Vec::new(),
                 },
                 Element::Relation {
                     source: Spanned::new(Id::new("app"), Span::new(30..33)),
@@ -1272,15 +1633,82 @@ mod identifier_validation_tests {
                     relation_type: Spanned::new("->", Span::new(34..36)),
                     type_spec: TypeSpec::default(),
                     label: None,
+                    comments: Vec::new(),
                 },
             ],
             imports: vec![],
+            variable_definitions: vec![],
+            style_definitions: vec![],
         };
 
-        let result = validate(&diagram);
+        let result = validate(&diagram, &ElaborateConfig::default());
         assert!(result.is_ok(), "Valid relation should pass validation");
     }
 
+    /// Builds a component diagram `a -> b` with the given relation label.
+    fn relation_label_diagram(label: &str) -> FileAst<'static> {
+        let component = |name: &str, start: usize| Element::Component {
+            name: Spanned::new(Id::new(name), Span::new(start..start + 1)),
+            display_name: None,
+            type_spec: TypeSpec::default(),
+            content: ComponentContent::None,
+            comments: Vec::new(),
+        };
+
+        FileAst {
+            header: FileHeader::Diagram {
+                kind: Spanned::new(DiagramKind::Component, Span::new(0..9)),
+                attributes: vec![],
+            },
+            import_decls: vec![],
+            type_definitions: vec![],
+            elements: vec![
+                component("a", 10),
+                component("b", 12),
+                Element::Relation {
+                    source: Spanned::new(Id::new("a"), Span::new(14..15)),
+                    target: Spanned::new(Id::new("b"), Span::new(19..20)),
+                    relation_type: Spanned::new("->", Span::new(16..18)),
+                    type_spec: TypeSpec::default(),
+                    label: Some(Spanned::new(label.to_string(), Span::new(22..30))),
+                    comments: Vec::new(),
+                },
+            ],
+            imports: vec![],
+            variable_definitions: vec![],
+            style_definitions: vec![],
+        }
+    }
+
+    #[test]
+    fn test_relation_empty_label_no_warning() {
+        let diagram = relation_label_diagram("");
+
+        let mut validator = Validator::new();
+        visit_file_ast(&mut validator, &diagram);
+
+        assert!(validator.diagnostics.diagnostics().is_empty());
+    }
+
+    #[test]
+    fn test_relation_blank_label_warns() {
+        let diagram = relation_label_diagram("   ");
+
+        let mut validator = Validator::new();
+        visit_file_ast(&mut validator, &diagram);
+
+        let diagnostics = validator.diagnostics.diagnostics();
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].severity().is_warning());
+        assert_eq!(
+            diagnostics[0].message(),
+            "relation label contains only whitespace"
+        );
+
+        // Warnings do not fail validation
+        assert!(validate(&diagram, &ElaborateConfig::default()).is_ok());
+    }
+
     #[test]
     fn test_relation_with_invalid_source() {
         let diagram = FileAst {
@@ -1299,6 +1727,7 @@ mod identifier_validation_tests {
                         attributes: vec![],
                     },
                     content: ComponentContent::None,
+                    comments: Vec::new(),
                 },
                 Element::Relation {
                     source: Spanned::new(Id::new("unknown"), Span::new(30..37)),
@@ -1306,12 +1735,15 @@ mod identifier_validation_tests {
                     relation_type: Spanned::new("->", Span::new(38..40)),
                     type_spec: TypeSpec::default(),
                     label: None,
+                    comments: Vec::new(),
                 },
             ],
             imports: vec![],
+            variable_definitions: vec![],
+            style_definitions: vec![],
         };
 
-        let result = validate(&diagram);
+        let result = validate(&diagram, &ElaborateConfig::default());
         assert!(result.is_err(), "Invalid source should fail validation");
         let err = result.unwrap_err();
         assert!(err[0].to_string().contains("component `unknown` not found"));
@@ -1335,6 +1767,7 @@ mod identifier_validation_tests {
                         attributes: vec![],
                     },
                     content: ComponentContent::None,
+                    comments: Vec::new(),
                 },
                 Element::Relation {
                     source: Spanned::new(Id::new("app"), Span::new(30..33)),
@@ -1342,12 +1775,15 @@ mod identifier_validation_tests {
                     relation_type: Spanned::new("->", Span::new(34..36)),
                     type_spec: TypeSpec::default(),
                     label: None,
+                    comments: Vec::new(),
                 },
             ],
             imports: vec![],
+            variable_definitions: vec![],
+            style_definitions: vec![],
         };
 
-        let result = validate(&diagram);
+        let result = validate(&diagram, &ElaborateConfig::default());
         assert!(result.is_err(), "Invalid target should fail validation");
         let err = result.unwrap_err();
         assert!(err[0].to_string().contains("component `missing` not found"));
@@ -1371,6 +1807,7 @@ mod identifier_validation_tests {
                         attributes: vec![],
                     },
                     content: ComponentContent::None,
+                    comments: Vec::new(),
                 },
                 Element::Activate {
                     component: Spanned::new(Id::new("server"), Span::new(20..26)),
@@ -1381,9 +1818,11 @@ mod identifier_validation_tests {
                 },
             ],
             imports: vec![],
+            variable_definitions: vec![],
+            style_definitions: vec![],
         };
 
-        let result = validate(&diagram);
+        let result = validate(&diagram, &ElaborateConfig::default());
         assert!(result.is_ok(), "Valid activate should pass validation");
     }
 
@@ -1405,6 +1844,7 @@ mod identifier_validation_tests {
                         attributes: vec![],
                     },
                     content: ComponentContent::None,
+                    comments: Vec::new(),
                 },
                 Element::Activate {
                     component: Spanned::new(Id::new("unknown"), Span::new(20..27)),
@@ -1412,9 +1852,11 @@ mod identifier_validation_tests {
                 },
             ],
             imports: vec![],
+            variable_definitions: vec![],
+            style_definitions: vec![],
         };
 
-        let result = validate(&diagram);
+        let result = validate(&diagram, &ElaborateConfig::default());
         assert!(result.is_err(), "Invalid activate should fail validation");
         let err = result.unwrap_err();
         assert!(err[0].to_string().contains("component `unknown` not found"));
@@ -1438,15 +1880,18 @@ mod identifier_validation_tests {
                         attributes: vec![],
                     },
                     content: ComponentContent::None,
+                    comments: Vec::new(),
                 },
                 Element::Deactivate {
                     component: Spanned::new(Id::new("missing"), Span::new(20..27)),
                 },
             ],
             imports: vec![],
+            variable_definitions: vec![],
+            style_definitions: vec![],
         };
 
-        let result = validate(&diagram);
+        let result = validate(&diagram, &ElaborateConfig::default());
         assert!(result.is_err(), "Invalid deactivate should fail validation");
         let err = result.unwrap_err();
         assert!(err[0].to_string().contains("component `missing` not found"));
@@ -1470,6 +1915,7 @@ mod identifier_validation_tests {
                         attributes: vec![],
                     },
                     content: ComponentContent::None,
+                    comments: Vec::new(),
                 },
                 Element::Note(Note {
                     type_spec: TypeSpec {
@@ -1486,9 +1932,11 @@ mod identifier_validation_tests {
                 }),
             ],
             imports: vec![],
+            variable_definitions: vec![],
+            style_definitions: vec![],
         };
 
-        let result = validate(&diagram);
+        let result = validate(&diagram, &ElaborateConfig::default());
         assert!(result.is_err(), "Note with invalid component should fail");
         let err = result.unwrap_err();
         assert!(err[0].to_string().contains("component `unknown` not found"));
@@ -1512,6 +1960,7 @@ mod identifier_validation_tests {
                         attributes: vec![],
                     },
                     content: ComponentContent::None,
+                    comments: Vec::new(),
                 },
                 Element::Component {
                     name: Spanned::new(Id::new("server"), Span::new(19..25)),
@@ -1521,6 +1970,7 @@ mod identifier_validation_tests {
                         attributes: vec![],
                     },
                     content: ComponentContent::None,
+                    comments: Vec::new(),
                 },
                 Element::Note(Note {
                     type_spec: TypeSpec {
@@ -1537,9 +1987,11 @@ mod identifier_validation_tests {
                 }),
             ],
             imports: vec![],
+            variable_definitions: vec![],
+            style_definitions: vec![],
         };
 
-        let result = validate(&diagram);
+        let result = validate(&diagram, &ElaborateConfig::default());
         assert!(
             result.is_ok(),
             "Note with multiple valid components should pass"
@@ -1564,6 +2016,7 @@ mod identifier_validation_tests {
                         attributes: vec![],
                     },
                     content: ComponentContent::None,
+                    comments: Vec::new(),
                 },
                 Element::Note(Note {
                     type_spec: TypeSpec {
@@ -1577,9 +2030,11 @@ mod identifier_validation_tests {
                 }),
             ],
             imports: vec![],
+            variable_definitions: vec![],
+            style_definitions: vec![],
         };
 
-        let result = validate(&diagram);
+        let result = validate(&diagram, &ElaborateConfig::default());
         assert!(result.is_ok(), "Note with empty on attribute should pass");
     }
 
@@ -1619,6 +2074,7 @@ mod identifier_validation_tests {
                         }],
                     },
                     content: ComponentContent::None,
+                    comments: Vec::new(),
                 },
                 Element::Component {
                     name: Spanned::new(Id::new("server"), Span::new(85..91)),
@@ -1628,6 +2084,7 @@ mod identifier_validation_tests {
                         attributes: vec![],
                     },
                     content: ComponentContent::None,
+                    comments: Vec::new(),
                 },
                 Element::Activate {
                     component: Spanned::new(Id::new("server"), Span::new(110..116)),
@@ -1654,6 +2111,7 @@ mod identifier_validation_tests {
                         }],
                     },
                     label: Some(Spanned::new("request".to_string(), Span::new(190..199))),
+                    comments: Vec::new(),
                 },
                 Element::Note(Note {
                     type_spec: TypeSpec {
@@ -1682,9 +2140,11 @@ mod identifier_validation_tests {
                 },
             ],
             imports: vec![],
+            variable_definitions: vec![],
+            style_definitions: vec![],
         };
 
-        let result = validate(&diagram);
+        let result = validate(&diagram, &ElaborateConfig::default());
         assert!(
             result.is_ok(),
             "Diagram with comprehensive TypeSpec usage should pass validation"
@@ -1708,7 +2168,7 @@ mod embed_ref_validation_tests {
         let ast = build_file(&tokens).expect("Failed to parse");
         // Note: desugar is NOT called here — we test the validator directly
         // against an AST with an unresolved DiagramSource::Ref.
-        let result = validate(&ast);
+        let result = validate(&ast, &ElaborateConfig::default());
         assert!(
             result.is_err(),
             "Unresolved embed ref should fail validation"
@@ -1733,7 +2193,7 @@ mod embed_ref_validation_tests {
 
         let tokens = tokenize(input, 0).expect("Failed to tokenize");
         let ast = build_file(&tokens).expect("Failed to parse");
-        let result = validate(&ast);
+        let result = validate(&ast, &ElaborateConfig::default());
         assert!(
             result.is_ok(),
             "Inline embed should pass validation: {:?}",
@@ -1751,7 +2211,7 @@ mod embed_ref_validation_tests {
 
         let tokens = tokenize(input, 0).expect("Failed to tokenize");
         let ast = build_file(&tokens).expect("Failed to parse");
-        let result = validate(&ast);
+        let result = validate(&ast, &ElaborateConfig::default());
         assert!(
             result.is_err(),
             "Unresolved embed refs should fail validation"
@@ -1787,11 +2247,14 @@ mod base_type_validation_tests {
                     attributes: vec![],
                 },
                 content: ComponentContent::None,
+                comments: Vec::new(),
             }],
             imports: vec![],
+            variable_definitions: vec![],
+            style_definitions: vec![],
         };
 
-        let result = validate(&diagram);
+        let result = validate(&diagram, &ElaborateConfig::default());
         assert!(result.is_err(), "Unknown base type should fail validation");
 
         let err = result.unwrap_err();
@@ -1822,9 +2285,11 @@ mod base_type_validation_tests {
             }],
             elements: vec![],
             imports: vec![],
+            variable_definitions: vec![],
+            style_definitions: vec![],
         };
 
-        let result = validate(&diagram);
+        let result = validate(&diagram, &ElaborateConfig::default());
         assert!(result.is_err());
 
         let err = result.unwrap_err();
@@ -1850,11 +2315,14 @@ mod base_type_validation_tests {
                     attributes: vec![],
                 },
                 content: ComponentContent::None,
+                comments: Vec::new(),
             }],
             imports: vec![],
+            variable_definitions: vec![],
+            style_definitions: vec![],
         };
 
-        let result = validate(&diagram);
+        let result = validate(&diagram, &ElaborateConfig::default());
         assert!(
             result.is_ok(),
             "Built-in base type should pass: {:?}",
@@ -1898,11 +2366,14 @@ mod base_type_validation_tests {
                     attributes: vec![],
                 },
                 content: ComponentContent::None,
+                comments: Vec::new(),
             }],
             imports: vec![],
+            variable_definitions: vec![],
+            style_definitions: vec![],
         };
 
-        let result = validate(&diagram);
+        let result = validate(&diagram, &ElaborateConfig::default());
         assert!(
             result.is_ok(),
             "User-defined types used as base types should pass: {:?}",
@@ -1933,9 +2404,11 @@ mod base_type_validation_tests {
             }],
             elements: vec![],
             imports: vec![],
+            variable_definitions: vec![],
+            style_definitions: vec![],
         };
 
-        let result = validate(&diagram);
+        let result = validate(&diagram, &ElaborateConfig::default());
         assert!(
             result.is_err(),
             "Self-reference without a prior definition should fail validation"
@@ -1980,9 +2453,11 @@ mod base_type_validation_tests {
             ],
             elements: vec![],
             imports: vec![],
+            variable_definitions: vec![],
+            style_definitions: vec![],
         };
 
-        let result = validate(&diagram);
+        let result = validate(&diagram, &ElaborateConfig::default());
         assert!(
             result.is_ok(),
             "Self-reference on a previously defined type should pass validation: {:?}",
@@ -1990,3 +2465,143 @@ mod base_type_validation_tests {
         );
     }
 }
+
+#[cfg(test)]
+mod unit_validation_tests {
+    use super::*;
+
+    fn diagram_with_width(value: AttributeValue<'static>) -> FileAst<'static> {
+        FileAst {
+            header: FileHeader::Diagram {
+                kind: Spanned::new(DiagramKind::Component, Span::new(0..9)),
+                attributes: vec![],
+            },
+            import_decls: vec![],
+            type_definitions: vec![TypeDefinition {
+                name: Spanned::new(Id::new("Thick"), Span::new(15..20)),
+                type_spec: TypeSpec {
+                    type_name: Some(Spanned::new(Id::new("Stroke"), Span::new(23..29))),
+                    attributes: vec![Attribute {
+                        name: Spanned::new("width", Span::new(30..35)),
+                        value,
+                    }],
+                },
+            }],
+            elements: vec![],
+            imports: vec![],
+            variable_definitions: vec![],
+            style_definitions: vec![],
+        }
+    }
+
+    fn dimension(unit: &'static str) -> AttributeValue<'static> {
+        AttributeValue::Dimension {
+            value: Spanned::new(2.0, Span::new(37..38)),
+            unit: Spanned::new(unit, Span::new(38..38 + unit.len())),
+        }
+    }
+
+    #[test]
+    fn test_supported_units_ok() {
+        for unit in ["px", "pt", "%"] {
+            let result = validate(
+                &diagram_with_width(dimension(unit)),
+                &ElaborateConfig::default(),
+            );
+            assert!(result.is_ok(), "`{unit}` should pass validation");
+        }
+    }
+
+    #[test]
+    fn test_unknown_unit_produces_e206() {
+        let result = validate(
+            &diagram_with_width(dimension("em")),
+            &ElaborateConfig::default(),
+        );
+
+        let err = result.unwrap_err();
+        assert_eq!(err.len(), 1);
+        assert_eq!(err[0].code(), Some(ErrorCode::E206));
+        assert_eq!(err[0].labels()[0].span(), Span::new(38..40));
+    }
+}
+
+#[cfg(test)]
+mod component_reference_tests {
+    use super::*;
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("server", "server"), 0);
+        assert_eq!(edit_distance("servr", "server"), 1);
+        assert_eq!(edit_distance("db", "api"), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("café", "cafe"), 1);
+    }
+
+    fn diagram_with_relation(implicit_nodes: bool) -> FileAst<'static> {
+        let attributes = if implicit_nodes {
+            vec![Attribute {
+                name: Spanned::new("implicit_nodes", Span::new(10..24)),
+                value: AttributeValue::String(Spanned::new("true".to_string(), Span::new(25..31))),
+            }]
+        } else {
+            vec![]
+        };
+        FileAst {
+            header: FileHeader::Diagram {
+                kind: Spanned::new(DiagramKind::Component, Span::new(0..9)),
+                attributes,
+            },
+            import_decls: vec![],
+            type_definitions: vec![],
+            elements: vec![
+                Element::Component {
+                    name: Spanned::new(Id::new("api"), Span::new(40..43)),
+                    display_name: None,
+                    type_spec: TypeSpec {
+                        type_name: Some(Spanned::new(Id::new("Rectangle"), Span::new(45..54))),
+                        attributes: vec![],
+                    },
+                    content: ComponentContent::None,
+                    comments: Vec::new(),
+                },
+                Element::Relation {
+                    source: Spanned::new(Id::new("api"), Span::new(60..63)),
+                    target: Spanned::new(Id::new("apj"), Span::new(67..70)),
+                    relation_type: Spanned::new("->", Span::new(64..66)),
+                    type_spec: TypeSpec::default(),
+                    label: None,
+                    comments: Vec::new(),
+                },
+            ],
+            imports: vec![],
+            variable_definitions: vec![],
+            style_definitions: vec![],
+        }
+    }
+
+    #[test]
+    fn test_undeclared_endpoint_is_an_error_with_suggestion() {
+        let err = validate(&diagram_with_relation(false), &ElaborateConfig::default()).unwrap_err();
+        assert_eq!(err.len(), 1);
+        assert_eq!(err[0].code(), Some(ErrorCode::E200));
+        assert_eq!(err[0].help(), Some("did you mean `api`?"));
+    }
+
+    #[test]
+    fn test_implicit_nodes_downgrades_undeclared_endpoint() {
+        assert!(validate(&diagram_with_relation(true), &ElaborateConfig::default()).is_ok());
+    }
+
+    #[test]
+    fn test_implicit_nodes_policy_sets_default_and_denies() {
+        let enabled = ElaborateConfig::default().with_implicit_nodes(ImplicitNodes::Enabled);
+        assert!(validate(&diagram_with_relation(false), &enabled).is_ok());
+
+        let deny = ElaborateConfig::default().with_implicit_nodes(ImplicitNodes::Deny);
+        let err = validate(&diagram_with_relation(true), &deny).unwrap_err();
+        assert_eq!(err.len(), 1);
+        assert_eq!(err[0].code(), Some(ErrorCode::E200));
+    }
+}
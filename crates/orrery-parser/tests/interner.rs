@@ -0,0 +1,45 @@
+//! Integration test for interner growth across repeated parses.
+//!
+//! Kept in its own test binary because the interner is global: tests running
+//! in parallel in the same process would intern strings concurrently.
+
+use std::path::Path;
+
+use bumpalo::Bump;
+
+use orrery_core::interner;
+use orrery_parser::{ElaborateConfig, InMemorySourceProvider, parse};
+
+const SOURCE: &str = r#"
+    diagram component;
+    type Service = Rectangle[fill_color="lightblue"];
+    api: Service[stroke=[color="red"]];
+    db: Oval[fill_color="lightgreen"];
+    group: Rectangle {
+        inner: Rectangle;
+    };
+    api -> [stroke=[style="dashed"]] db: "query";
+    api -> group::inner;
+"#;
+
+fn parse_once() {
+    let arena = Bump::new();
+    let mut provider = InMemorySourceProvider::new();
+    provider.add_file("test.orr", SOURCE);
+    parse(
+        &arena,
+        Path::new("test.orr"),
+        provider,
+        ElaborateConfig::default(),
+    )
+    .expect("Failed to parse");
+}
+
+#[test]
+fn test_repeated_parse_does_not_grow_interner() {
+    parse_once();
+    let interned = interner::interner().len();
+
+    parse_once();
+    assert_eq!(interner::interner().len(), interned);
+}
@@ -11,10 +11,19 @@ use std::path::Path;
 use bumpalo::Bump;
 
 use orrery_core::{
+    color::{Color, Palette},
+    draw::{ArrowDirection, ContentAlign, Density, EdgeRouting, HorizontalAlign, VerticalAlign},
+    geometry::{Point, Size},
     identifier::Id,
-    semantic::{Block, Diagram, DiagramKind, Element, LayoutEngine, NoteAlign},
+    semantic::{Block, Diagram, DiagramKind, Element, LayoutEngine, NoteAlign, Relation, Visitor},
+};
+use orrery_parser::{
+    ElaborateConfig, ImplicitNodes, InMemorySourceProvider,
+    error::{Diagnostic, ErrorCode},
+    lint,
+    lint::{LintConfig, LintLevel},
+    parse,
 };
-use orrery_parser::{ElaborateConfig, InMemorySourceProvider, error::ErrorCode, parse};
 
 /// Helper: parse a single source string through the full pipeline.
 fn parse_source(source: &str) -> Diagram {
@@ -28,6 +37,23 @@ fn parse_source(source: &str) -> Diagram {
         ElaborateConfig::default(),
     )
     .expect("parse_source: unexpected parse failure")
+    .into_diagram()
+}
+
+/// Helper: lint a single source string, returning the lint findings.
+fn lint_source(source: &str, lint_config: &LintConfig) -> Vec<Diagnostic> {
+    let arena = Bump::new();
+    let mut provider = InMemorySourceProvider::new();
+    provider.add_file("test.orr", source);
+    let report = lint(
+        &arena,
+        Path::new("test.orr"),
+        provider,
+        ElaborateConfig::default(),
+        lint_config,
+    )
+    .expect("lint_source: unexpected parse failure");
+    report.into_parts().0
 }
 
 #[test]
@@ -147,96 +173,1062 @@ fn test_with_relations() {
     let diagram = parse_source(
         r#"
         diagram component;
-        a: Rectangle;
-        b: Rectangle;
-        a -> b;
-        b <- a;
-        a <-> b;
+        a: Rectangle;
+        b: Rectangle;
+        a -> b;
+        b <- a;
+        a <-> b;
+        a - b;
+    "#,
+    );
+
+    let elements = diagram.scope().elements();
+    assert_eq!(elements.len(), 6); // 2 nodes + 4 relations
+
+    // Check nodes
+    assert!(matches!(&elements[0], Element::Node(n) if n.id() == Id::new("a")));
+    assert!(matches!(&elements[1], Element::Node(n) if n.id() == Id::new("b")));
+
+    // Check relations
+    match &elements[2] {
+        Element::Relation(r) => {
+            assert_eq!(r.source(), Id::new("a"));
+            assert_eq!(r.target(), Id::new("b"));
+            assert_eq!(r.arrow_direction(), ArrowDirection::Forward);
+        }
+        _ => panic!("Expected Relation"),
+    }
+
+    match &elements[3] {
+        Element::Relation(r) => {
+            assert_eq!(r.source(), Id::new("b"));
+            assert_eq!(r.target(), Id::new("a"));
+            assert_eq!(r.arrow_direction(), ArrowDirection::Backward);
+        }
+        _ => panic!("Expected Relation"),
+    }
+
+    match &elements[4] {
+        Element::Relation(r) => {
+            assert_eq!(r.source(), Id::new("a"));
+            assert_eq!(r.target(), Id::new("b"));
+            assert_eq!(r.arrow_direction(), ArrowDirection::Bidirectional);
+        }
+        _ => panic!("Expected Relation"),
+    }
+
+    // A plain association has no arrowhead at either end
+    match &elements[5] {
+        Element::Relation(r) => {
+            assert_eq!(r.source(), Id::new("a"));
+            assert_eq!(r.target(), Id::new("b"));
+            assert_eq!(r.arrow_direction(), ArrowDirection::Plain);
+            assert_eq!(r.arrow_definition().arrowhead(), None);
+        }
+        _ => panic!("Expected Relation"),
+    }
+}
+
+#[test]
+fn test_syntax_error() {
+    let arena = Bump::new();
+    let mut provider = InMemorySourceProvider::new();
+    provider.add_file(
+        "test.orr",
+        r#"
+        diagram component
+        missing_semicolon: Rectangle;
+    "#,
+    );
+    let result = parse(
+        &arena,
+        Path::new("test.orr"),
+        provider,
+        ElaborateConfig::default(),
+    );
+    assert!(result.is_err(), "Should fail on syntax error");
+
+    let err = result.unwrap_err();
+    assert!(!err.diagnostics().is_empty());
+    let diag = &err.diagnostics()[0];
+    assert!(!diag.message().is_empty());
+    assert!(!diag.labels().is_empty() && !diag.labels()[0].span().is_empty());
+}
+
+#[test]
+fn test_with_custom_config() {
+    let arena = Bump::new();
+    let mut provider = InMemorySourceProvider::new();
+    provider.add_file("test.orr", "diagram component;\nbox: Rectangle;");
+
+    let config = ElaborateConfig::new(LayoutEngine::Sugiyama, LayoutEngine::Basic);
+    let diagram = parse(&arena, Path::new("test.orr"), provider, config)
+        .expect("Failed to parse")
+        .into_diagram();
+
+    assert_eq!(diagram.layout_engine(), LayoutEngine::Sugiyama);
+}
+
+#[test]
+fn test_palette_color_resolves() {
+    let arena = Bump::new();
+    let mut provider = InMemorySourceProvider::new();
+    provider.add_file(
+        "test.orr",
+        r#"
+        diagram component;
+        a: Rectangle;
+        b: Rectangle;
+        a -> [stroke=[color="brand.primary"]] b;
+    "#,
+    );
+
+    let mut palette = Palette::new();
+    palette.insert("brand.primary", Color::new("#1e88e5").unwrap());
+    let config = ElaborateConfig::default().with_palette(palette);
+
+    let diagram = parse(&arena, Path::new("test.orr"), provider, config)
+        .expect("Failed to parse")
+        .into_diagram();
+
+    let relation = diagram
+        .scope()
+        .elements()
+        .iter()
+        .find_map(|element| match element {
+            Element::Relation(relation) => Some(relation),
+            _ => None,
+        })
+        .expect("Expected a relation");
+    assert_eq!(
+        relation.arrow_definition().stroke().color(),
+        Color::new("#1e88e5").unwrap()
+    );
+}
+
+#[test]
+fn test_palette_unknown_color_errors() {
+    let arena = Bump::new();
+    let mut provider = InMemorySourceProvider::new();
+    provider.add_file(
+        "test.orr",
+        r#"
+        diagram component;
+        a: Rectangle[fill_color="brand.missing"];
+    "#,
+    );
+
+    let mut palette = Palette::new();
+    palette.insert("brand.primary", Color::new("#1e88e5").unwrap());
+    let config = ElaborateConfig::default().with_palette(palette);
+
+    let err = parse(&arena, Path::new("test.orr"), provider, config)
+        .expect_err("Undefined palette color should fail");
+
+    let diag = &err.diagnostics()[0];
+    assert_eq!(diag.code(), Some(ErrorCode::E302));
+    assert!(
+        diag.message()
+            .contains("undefined palette color `brand.missing`"),
+        "unexpected message: {}",
+        diag.message()
+    );
+}
+
+#[test]
+fn test_parse_reports_every_syntax_error() {
+    let arena = Bump::new();
+    let mut provider = InMemorySourceProvider::new();
+    provider.add_file(
+        "test.orr",
+        r#"
+        diagram component;
+        a: Rectangle;
+        b Rectangle;
+        c: Rectangle[fill_color="red"
+        d: Rectangle;
+        a -> d;
+        e e;
+    "#,
+    );
+
+    let err = parse(
+        &arena,
+        Path::new("test.orr"),
+        provider,
+        ElaborateConfig::default(),
+    )
+    .expect_err("syntax errors should fail");
+
+    let diagnostics = err.diagnostics();
+    assert_eq!(diagnostics.len(), 3);
+    assert!(
+        diagnostics
+            .iter()
+            .all(|diag| diag.code() == Some(ErrorCode::E100))
+    );
+}
+
+#[test]
+fn test_undefined_relation_endpoint_suggests_declared_name() {
+    let arena = Bump::new();
+    let mut provider = InMemorySourceProvider::new();
+    provider.add_file(
+        "test.orr",
+        r#"
+        diagram component;
+        user: Rectangle;
+        server: Rectangle;
+        user -> servr;
+    "#,
+    );
+
+    let err = parse(
+        &arena,
+        Path::new("test.orr"),
+        provider,
+        ElaborateConfig::default(),
+    )
+    .expect_err("An undeclared endpoint should fail");
+
+    let diag = &err.diagnostics()[0];
+    assert_eq!(diag.code(), Some(ErrorCode::E200));
+    assert_eq!(diag.help(), Some("did you mean `server`?"));
+}
+
+#[test]
+fn test_duplicate_component_reports_both_declarations() {
+    let arena = Bump::new();
+    let source = r#"
+        diagram component;
+        app: Rectangle;
+        backend: Rectangle {
+            app: Rectangle;
+        };
+        app: Oval;
+    "#;
+    let mut provider = InMemorySourceProvider::new();
+    provider.add_file("test.orr", source);
+    let err = parse(
+        &arena,
+        Path::new("test.orr"),
+        provider,
+        ElaborateConfig::default(),
+    )
+    .expect_err("A duplicate component should fail");
+
+    let [diag] = err.diagnostics() else {
+        panic!("Expected a single diagnostic, got {:?}", err.diagnostics());
+    };
+    assert_eq!(diag.code(), Some(ErrorCode::E209));
+    assert_eq!(diag.message(), "component `app` is declared more than once");
+    let starts: Vec<_> = diag
+        .labels()
+        .iter()
+        .map(|label| label.span().start())
+        .collect();
+    assert_eq!(
+        starts,
+        [
+            source.find("app: Oval").unwrap(),
+            source.find("app: Rectangle").unwrap()
+        ]
+    );
+}
+
+#[test]
+fn test_implicit_nodes_attribute_creates_undeclared_endpoints() {
+    let diagram = parse_source(
+        r#"
+        diagram sequence [implicit_nodes="true"];
+        user: Rectangle;
+        user -> server: "request";
+        server -> db;
+    "#,
+    );
+
+    let node_ids: Vec<String> = diagram
+        .scope()
+        .elements()
+        .iter()
+        .filter_map(|element| match element {
+            Element::Node(node) => Some(node.id().to_string()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(node_ids, ["user", "server", "db"]);
+}
+
+#[test]
+fn test_elaborate_config_sets_implicit_node_and_autonumber_defaults() {
+    let parse_with = |source: &str, config: ElaborateConfig| {
+        let arena = Bump::new();
+        let mut provider = InMemorySourceProvider::new();
+        provider.add_file("test.orr", source);
+        parse(&arena, Path::new("test.orr"), provider, config)
+            .map(|parsed| parsed.into_diagram())
+            .map_err(|err| err.diagnostics()[0].code())
+    };
+    let config = ElaborateConfig::default()
+        .with_implicit_nodes(ImplicitNodes::Enabled)
+        .with_implicit_node_type(Id::new("Oval"))
+        .with_autonumber(Some(5));
+
+    let diagram = parse_with(
+        r#"
+        diagram sequence;
+        user: Oval;
+        user -> server: "request";
+    "#,
+        config.clone(),
+    )
+    .expect("Failed to parse");
+    let elements = diagram.scope().elements();
+    let shape = |index: usize| {
+        let Element::Node(node) = &elements[index] else {
+            panic!("expected a node at {index}");
+        };
+        node.shape_definition()
+            .render_to_svg(Size::new(10.0, 10.0), Point::new(0.0, 0.0))
+            .to_string()
+    };
+    assert_eq!(shape(0), shape(1), "the implicit `server` is an `Oval`");
+    let Element::Relation(relation) = &elements[2] else {
+        panic!("expected a relation");
+    };
+    assert_eq!(relation.label(), Some("5. request"));
+
+    // Diagram attributes still take precedence over the configured defaults.
+    let diagram = parse_with(
+        r#"
+        diagram sequence [autonumber="false"];
+        user: Oval;
+        user -> server: "request";
+    "#,
+        config.clone(),
+    )
+    .expect("Failed to parse");
+    let Element::Relation(relation) = &diagram.scope().elements()[2] else {
+        panic!("expected a relation");
+    };
+    assert_eq!(relation.label(), Some("request"));
+    assert_eq!(
+        parse_with(
+            r#"diagram sequence [implicit_nodes="false"]; user: Oval; user -> server;"#,
+            config,
+        )
+        .err(),
+        Some(Some(ErrorCode::E200))
+    );
+
+    // Deny rejects implicit components even when a diagram asks for them.
+    assert_eq!(
+        parse_with(
+            r#"diagram sequence [implicit_nodes="true"]; user: Oval; user -> server;"#,
+            ElaborateConfig::default().with_implicit_nodes(ImplicitNodes::Deny),
+        )
+        .err(),
+        Some(Some(ErrorCode::E200))
+    );
+}
+
+#[test]
+fn test_diagram_layout_attribute() {
+    let diagram = parse_source(
+        r#"
+        diagram component [layout_engine="sugiyama"];
+        box: Rectangle;
+    "#,
+    );
+
+    // Diagram-level attribute overrides config
+    assert_eq!(diagram.layout_engine(), LayoutEngine::Sugiyama);
+}
+
+#[test]
+fn test_embedded_diagrams_pick_their_own_layout_engine() {
+    let diagram = parse_source(
+        r#"
+        diagram component [layout_engine="basic"];
+        layered: Rectangle embed {
+            diagram component [layout_engine="sugiyama"];
+            a: Rectangle;
+        };
+        flow: Rectangle embed {
+            diagram sequence;
+            b: Rectangle;
+        };
+    "#,
+    );
+
+    assert_eq!(diagram.layout_engine(), LayoutEngine::Basic);
+    let engines: Vec<_> = diagram
+        .scope()
+        .elements()
+        .iter()
+        .map(|element| match element {
+            Element::Node(node) => match node.block() {
+                Block::Diagram(embedded) => embedded.layout_engine(),
+                block => panic!("Expected an embedded diagram, got: {block:?}"),
+            },
+            element => panic!("Expected a node, got: {element:?}"),
+        })
+        .collect();
+    assert_eq!(
+        engines,
+        [
+            LayoutEngine::Sugiyama,
+            ElaborateConfig::default().sequence_layout
+        ]
+    );
+}
+
+#[test]
+fn test_unknown_layout_engine_lists_supported_engines() {
+    let arena = Bump::new();
+    let mut provider = InMemorySourceProvider::new();
+    provider.add_file(
+        "test.orr",
+        r#"
+        diagram component;
+        inner: Rectangle embed {
+            diagram component [layout_engine="force"];
+            a: Rectangle;
+        };
+    "#,
+    );
+
+    let err = parse(
+        &arena,
+        Path::new("test.orr"),
+        provider,
+        ElaborateConfig::default(),
+    )
+    .expect_err("Unknown layout engine should fail");
+
+    let diag = &err.diagnostics()[0];
+    assert_eq!(diag.code(), Some(ErrorCode::E302));
+    assert!(diag.message().contains("`force`"), "{}", diag.message());
+    let help = diag.help().expect("help lists the engines");
+    for engine in LayoutEngine::ALL {
+        assert!(help.contains(&format!("`{engine}`")), "{help}");
+    }
+}
+
+#[test]
+fn test_diagram_rank_spacing_attributes() {
+    let diagram = parse_source(
+        r#"
+        diagram component [layout_engine="sugiyama", rank_spacing=120, intra_rank_spacing=30];
+        box: Rectangle;
+    "#,
+    );
+
+    let definition = diagram.definition();
+    assert_eq!(definition.rank_spacing(), Some(120.0));
+    assert_eq!(definition.intra_rank_spacing(), Some(30.0));
+}
+
+#[test]
+fn test_diagram_edge_routing_attribute() {
+    let routing = |header: &str| {
+        parse_source(&format!("diagram component {header}; box: Rectangle;"))
+            .definition()
+            .edge_routing()
+    };
+    assert_eq!(routing(""), EdgeRouting::Straight);
+    assert_eq!(
+        routing(r#"[edge_routing="orthogonal"]"#),
+        EdgeRouting::Orthogonal
+    );
+
+    let parse_error = |source: &str| {
+        let arena = Bump::new();
+        let mut provider = InMemorySourceProvider::new();
+        provider.add_file("test.orr", source);
+        let err = parse(
+            &arena,
+            Path::new("test.orr"),
+            provider,
+            ElaborateConfig::default(),
+        )
+        .expect_err("edge_routing should be rejected");
+        err.diagnostics()[0].code()
+    };
+    assert_eq!(
+        parse_error(r#"diagram component [edge_routing="diagonal"]; a: Rectangle;"#),
+        Some(ErrorCode::E302)
+    );
+    assert_eq!(
+        parse_error(r#"diagram sequence [edge_routing="orthogonal"]; a: Rectangle;"#),
+        Some(ErrorCode::E304)
+    );
+}
+
+#[test]
+fn test_diagram_grid_layout_columns() {
+    let diagram =
+        parse_source(r#"diagram component [layout_engine="grid", columns=3]; a: Rectangle;"#);
+    assert_eq!(diagram.layout_engine(), LayoutEngine::Grid);
+    assert_eq!(diagram.definition().columns(), Some(3));
+
+    let automatic = parse_source(r#"diagram component [layout_engine="grid"]; a: Rectangle;"#);
+    assert_eq!(automatic.definition().columns(), None);
+
+    let parse_error = |source: &str| {
+        let arena = Bump::new();
+        let mut provider = InMemorySourceProvider::new();
+        provider.add_file("test.orr", source);
+        let err = parse(
+            &arena,
+            Path::new("test.orr"),
+            provider,
+            ElaborateConfig::default(),
+        )
+        .expect_err("columns should be rejected");
+        err.diagnostics()[0].code()
+    };
+    assert_eq!(
+        parse_error(r#"diagram component [columns=0]; a: Rectangle;"#),
+        Some(ErrorCode::E302)
+    );
+    assert_eq!(
+        parse_error(r#"diagram sequence [columns=2]; a: Rectangle;"#),
+        Some(ErrorCode::E304)
+    );
+}
+
+#[test]
+fn test_diagram_density_attribute() {
+    let density = |header: &str| {
+        parse_source(&format!("diagram sequence {header}; a: Rectangle;"))
+            .definition()
+            .density()
+    };
+    assert_eq!(density(""), None);
+    assert_eq!(density(r#"[density="compact"]"#), Some(Density::Compact));
+    assert_eq!(density(r#"[density="spacious"]"#), Some(Density::Spacious));
+
+    let arena = Bump::new();
+    let mut provider = InMemorySourceProvider::new();
+    provider.add_file(
+        "test.orr",
+        r#"diagram component [density="roomy"]; a: Rectangle;"#,
+    );
+    let err = parse(
+        &arena,
+        Path::new("test.orr"),
+        provider,
+        ElaborateConfig::default(),
+    )
+    .expect_err("invalid density should be rejected");
+    assert_eq!(err.diagnostics()[0].code(), Some(ErrorCode::E302));
+}
+
+#[test]
+fn test_diagram_title_and_caption_attributes() {
+    let diagram =
+        parse_source(r#"diagram component [title="Checkout", caption="Figure 1"]; a: Rectangle;"#);
+    let definition = diagram.definition();
+    assert_eq!(definition.title(), Some("Checkout"));
+    assert_eq!(definition.caption(), Some("Figure 1"));
+    assert!(
+        definition.title_text().font_size() > definition.caption_text().font_size(),
+        "the title should be set in a larger font than the caption"
+    );
+
+    let untitled = parse_source("diagram sequence; a: Rectangle;");
+    assert_eq!(untitled.definition().title(), None);
+    assert_eq!(untitled.definition().caption(), None);
+}
+
+#[test]
+fn test_diagram_autocolor_fills_top_level_components() {
+    let source = |autocolor: &str| {
+        format!(
+            r#"
+            diagram component [autocolor="{autocolor}"];
+            type Brand = Rectangle [fill_color="blue"];
+            api: Rectangle;
+            db: Oval;
+            themed: Brand;
+            explicit: Rectangle [fill_color="red"];
+            outer: Rectangle {{
+                inner: Rectangle;
+            }};
+        "#
+        )
+    };
+    let shapes = |diagram: &Diagram| -> Vec<String> {
+        let mut shapes = Vec::new();
+        for element in diagram.scope().elements() {
+            let Element::Node(node) = element else {
+                continue;
+            };
+            let render = |node: &orrery_core::semantic::Node| {
+                node.shape_definition()
+                    .render_to_svg(Size::new(10.0, 10.0), Point::new(0.0, 0.0))
+                    .to_string()
+            };
+            shapes.push(render(node));
+            if let Block::Scope(scope) = node.block() {
+                for nested in scope.elements() {
+                    if let Element::Node(nested) = nested {
+                        shapes.push(render(nested));
+                    }
+                }
+            }
+        }
+        shapes
+    };
+
+    let plain = shapes(&parse_source(&source("false")));
+    let auto = shapes(&parse_source(&source("true")));
+
+    assert_ne!(auto[0], plain[0]);
+    assert_ne!(auto[1], plain[1]);
+    assert_eq!(auto[2], plain[2], "a fill from the type wins");
+    assert_eq!(auto[3], plain[3], "an inline fill wins");
+    assert_ne!(auto[4], plain[4]);
+    assert_eq!(auto[5], plain[5], "nested components keep their fill");
+    assert_eq!(auto, shapes(&parse_source(&source("true"))));
+}
+
+#[test]
+fn test_autonumber_numbers_messages_in_visual_order() {
+    struct Labels(Vec<String>);
+
+    impl Visitor for Labels {
+        fn visit_relation(&mut self, relation: &Relation) {
+            self.0
+                .push(relation.label().unwrap_or_default().to_string());
+        }
+    }
+
+    let labels = |autonumber: &str| {
+        let diagram = parse_source(&format!(
+            r#"
+            diagram sequence [autonumber="{autonumber}"];
+            client: Rectangle;
+            server: Rectangle;
+            client -> server: "login";
+            alt "ok" {{
+                server -> client: "token";
+                loop "retry" {{
+                    client -> server;
+                }};
+            }} else "denied" {{
+                server -> client: "error";
+            }};
+            client -> server: "logout";
+        "#
+        ));
+        let mut labels = Labels(Vec::new());
+        diagram.accept(&mut labels);
+        labels.0
+    };
+
+    assert_eq!(
+        labels("true"),
+        ["1. login", "2. token", "3.", "4. error", "5. logout"]
+    );
+    assert_eq!(
+        labels("10"),
+        ["10. login", "11. token", "12.", "13. error", "14. logout"]
+    );
+    assert_eq!(labels("false"), ["login", "token", "", "error", "logout"]);
+}
+
+#[test]
+fn test_autonumber_rejects_invalid_values_and_component_diagrams() {
+    let parse_error = |source: &str| {
+        let arena = Bump::new();
+        let mut provider = InMemorySourceProvider::new();
+        provider.add_file("test.orr", source);
+        let err = parse(
+            &arena,
+            Path::new("test.orr"),
+            provider,
+            ElaborateConfig::default(),
+        )
+        .expect_err("autonumber should be rejected");
+        err.diagnostics()[0].code()
+    };
+
+    assert_eq!(
+        parse_error(r#"diagram sequence [autonumber="first"]; a: Rectangle;"#),
+        Some(ErrorCode::E302)
+    );
+    assert_eq!(
+        parse_error(r#"diagram component [autonumber="true"]; a: Rectangle;"#),
+        Some(ErrorCode::E304)
+    );
+}
+
+#[test]
+fn test_unbalanced_activations_are_reported() {
+    let parse_errors = |source: &str| {
+        let arena = Bump::new();
+        let mut provider = InMemorySourceProvider::new();
+        provider.add_file("test.orr", source);
+        let err = parse(
+            &arena,
+            Path::new("test.orr"),
+            provider,
+            ElaborateConfig::default(),
+        )
+        .expect_err("unbalanced activations should fail");
+        err.diagnostics().to_vec()
+    };
+
+    // The single `deactivate` closes the most recent activation, leaving the
+    // first one open.
+    let source = "diagram sequence;\nserver: Rectangle;\nactivate server;\nactivate server;\ndeactivate server;\n";
+    let diagnostics = parse_errors(source);
+    assert_eq!(diagnostics.len(), 1);
+    let diagnostic = &diagnostics[0];
+    assert_eq!(diagnostic.code(), Some(ErrorCode::E201));
+    assert_eq!(
+        diagnostic.message(),
+        "component `server` was activated but never deactivated"
+    );
+    assert_eq!(
+        diagnostic.labels()[0].span().start(),
+        source.find("activate server;").unwrap() + "activate ".len()
+    );
+
+    let source =
+        "diagram sequence;\nserver: Rectangle;\nactivate server {\n};\ndeactivate server;\n";
+    let diagnostics = parse_errors(source);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code(), Some(ErrorCode::E202));
+    assert_eq!(
+        diagnostics[0].labels()[0].span().start(),
+        source.find("deactivate server;").unwrap() + "deactivate ".len()
+    );
+
+    // The block form balances itself.
+    parse_source("diagram sequence;\nserver: Rectangle;\nactivate server {\n};\n");
+}
+
+#[test]
+fn test_stroke_width_units() {
+    let render = |width: &str| {
+        let source = format!("diagram component;\na: Rectangle [stroke=[width={width}]];");
+        let diagram = parse_source(&source);
+        let Element::Node(node) = &diagram.scope().elements()[0] else {
+            panic!("expected a node");
+        };
+        node.shape_definition()
+            .render_to_svg(Size::new(10.0, 10.0), Point::new(0.0, 0.0))
+            .to_string()
+    };
+
+    // The built-in rectangle stroke is 2px wide.
+    assert_eq!(render("4px"), render("4"));
+    assert_eq!(render("3pt"), render("4"));
+}
+
+/// Helper: parse a source string that must fail, returning its first diagnostic.
+fn parse_error(source: &str) -> Diagnostic {
+    let arena = Bump::new();
+    let mut provider = InMemorySourceProvider::new();
+    provider.add_file("test.orr", source);
+    let err = parse(
+        &arena,
+        Path::new("test.orr"),
+        provider,
+        ElaborateConfig::default(),
+    )
+    .expect_err("source should fail");
+    err.diagnostics()[0].clone()
+}
+
+#[test]
+fn test_percentages_resolve_against_parent_size() {
+    let diagram = parse_source(
+        r#"
+        diagram component [width=400, height=300];
+        outer: Rectangle [min_width=50%, min_height=10%] {
+            inner: Rectangle [min_width=50%, min_height=100%];
+        };
+        "#,
+    );
+    let Element::Node(outer) = &diagram.scope().elements()[0] else {
+        panic!("expected a node");
+    };
+    assert_eq!(outer.shape_definition().min_size(), Size::new(200.0, 30.0));
+
+    let Block::Scope(scope) = outer.block() else {
+        panic!("expected a scope");
+    };
+    let Element::Node(inner) = &scope.elements()[0] else {
+        panic!("expected a node");
+    };
+    assert_eq!(inner.shape_definition().min_size(), Size::new(100.0, 30.0));
+}
+
+#[test]
+fn test_non_positive_lengths_are_rejected() {
+    for value in ["0", "0px", "0%", "-5"] {
+        let diag = parse_error(&format!(
+            "diagram component [width=400];\na: Rectangle [min_width={value}];"
+        ));
+        assert_eq!(diag.code(), Some(ErrorCode::E302), "min_width={value}");
+    }
+}
+
+#[test]
+fn test_percentages_without_parent_size_are_rejected() {
+    // The diagram has no `width`, so there is nothing to take 50% of.
+    let diag = parse_error("diagram component;\na: Rectangle [min_width=50%];");
+    assert_eq!(diag.code(), Some(ErrorCode::E302));
+    assert!(
+        diag.message().contains("size of the parent element"),
+        "unexpected message: {}",
+        diag.message()
+    );
+
+    // Type definitions have no parent element at all.
+    let diag = parse_error(
+        "diagram component [width=400];\ntype Wide = Rectangle [min_width=50%];\na: Wide;",
+    );
+    assert_eq!(diag.code(), Some(ErrorCode::E302));
+
+    // Stroke widths take no percentages.
+    let diag = parse_error("diagram component [width=400];\na: Rectangle [stroke=[width=200%]];");
+    assert_eq!(diag.code(), Some(ErrorCode::E302));
+}
+
+#[test]
+fn test_unknown_unit_errors() {
+    let arena = Bump::new();
+    let mut provider = InMemorySourceProvider::new();
+    provider.add_file(
+        "test.orr",
+        r#"
+        diagram component;
+        a: Rectangle [stroke=[width=2em]];
     "#,
     );
 
-    let elements = diagram.scope().elements();
-    assert_eq!(elements.len(), 5); // 2 nodes + 3 relations
-
-    // Check nodes
-    assert!(matches!(&elements[0], Element::Node(n) if n.id() == Id::new("a")));
-    assert!(matches!(&elements[1], Element::Node(n) if n.id() == Id::new("b")));
-
-    // Check relations
-    match &elements[2] {
-        Element::Relation(r) => {
-            assert_eq!(r.source(), Id::new("a"));
-            assert_eq!(r.target(), Id::new("b"));
-        }
-        _ => panic!("Expected Relation"),
-    }
+    let err = parse(
+        &arena,
+        Path::new("test.orr"),
+        provider,
+        ElaborateConfig::default(),
+    )
+    .expect_err("An unknown unit should fail");
 
-    match &elements[3] {
-        Element::Relation(r) => {
-            assert_eq!(r.source(), Id::new("b"));
-            assert_eq!(r.target(), Id::new("a"));
-        }
-        _ => panic!("Expected Relation"),
-    }
+    let diag = &err.diagnostics()[0];
+    assert_eq!(diag.code(), Some(ErrorCode::E206));
+    assert!(
+        diag.message().contains("unknown unit `em`"),
+        "unexpected message: {}",
+        diag.message()
+    );
+}
 
-    match &elements[4] {
-        Element::Relation(r) => {
-            assert_eq!(r.source(), Id::new("a"));
-            assert_eq!(r.target(), Id::new("b"));
-        }
-        _ => panic!("Expected Relation"),
-    }
+#[test]
+fn test_variables_are_substituted_into_attributes() {
+    let render = |source: &str| {
+        let diagram = parse_source(source);
+        diagram
+            .scope()
+            .elements()
+            .iter()
+            .map(|element| {
+                let Element::Node(node) = element else {
+                    panic!("expected a node");
+                };
+                node.shape_definition()
+                    .render_to_svg(Size::new(10.0, 10.0), Point::new(0.0, 0.0))
+                    .to_string()
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let with_variables = render(
+        r##"
+        diagram component;
+        let brand = "#3366cc";
+        let thick = 3px;
+        type Brand = Rectangle [fill_color=$brand];
+        let outline = [width=$thick];
+        a: Brand;
+        b: Oval [fill_color=$brand, stroke=$outline];
+    "##,
+    );
+    let literal = render(
+        r##"
+        diagram component;
+        a: Rectangle [fill_color="#3366cc"];
+        b: Oval [fill_color="#3366cc", stroke=[width=3px]];
+    "##,
+    );
+    assert_eq!(with_variables, literal);
 }
 
 #[test]
-fn test_syntax_error() {
+fn test_variable_errors_and_redefinition_warnings() {
     let arena = Bump::new();
+    let source = r#"
+        diagram component;
+        a: Rectangle [fill_color=$missing];
+    "#;
+    let mut provider = InMemorySourceProvider::new();
+    provider.add_file("test.orr", source);
+    let err = parse(
+        &arena,
+        Path::new("test.orr"),
+        provider,
+        ElaborateConfig::default(),
+    )
+    .expect_err("An undefined variable should fail");
+
+    let diag = &err.diagnostics()[0];
+    assert_eq!(diag.code(), Some(ErrorCode::E207));
+    let span = diag.labels()[0].span();
+    assert_eq!(&source[span.start()..span.end()], "$missing");
+
     let mut provider = InMemorySourceProvider::new();
     provider.add_file(
         "test.orr",
         r#"
-        diagram component
-        missing_semicolon: Rectangle;
+        diagram component;
+        let color = "red";
+        let color = "blue";
+        a: Rectangle [fill_color=$color];
     "#,
     );
-    let result = parse(
+    let parsed = parse(
         &arena,
         Path::new("test.orr"),
         provider,
         ElaborateConfig::default(),
+    )
+    .expect("A redefinition should only warn");
+    assert_eq!(parsed.warnings().len(), 1);
+    assert!(
+        parsed.warnings()[0]
+            .message()
+            .contains("variable `color` is redefined")
     );
-    assert!(result.is_err(), "Should fail on syntax error");
+}
 
-    let err = result.unwrap_err();
-    assert!(!err.diagnostics().is_empty());
-    let diag = &err.diagnostics()[0];
-    assert!(!diag.message().is_empty());
-    assert!(!diag.labels().is_empty() && !diag.labels()[0].span().is_empty());
+#[test]
+fn test_styles_merge_beneath_inline_attributes() {
+    let render = |source: &str| {
+        let diagram = parse_source(source);
+        diagram
+            .scope()
+            .elements()
+            .iter()
+            .map(|element| {
+                let Element::Node(node) = element else {
+                    panic!("expected a node");
+                };
+                node.shape_definition()
+                    .render_to_svg(Size::new(10.0, 10.0), Point::new(0.0, 0.0))
+                    .to_string()
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let with_styles = render(
+        r#"
+        diagram component;
+        style Warning {
+            fill_color="yellow";
+            stroke=[color="orange", width=2];
+        }
+        a: Rectangle [class="Warning"];
+        b: Oval [class="Warning", fill_color="red"];
+    "#,
+    );
+    let literal = render(
+        r#"
+        diagram component;
+        a: Rectangle [fill_color="yellow", stroke=[color="orange", width=2]];
+        b: Oval [fill_color="red", stroke=[color="orange", width=2]];
+    "#,
+    );
+    assert_eq!(with_styles, literal);
 }
 
 #[test]
-fn test_with_custom_config() {
+fn test_undefined_style_is_an_error() {
     let arena = Bump::new();
+    let source = r#"
+        diagram component;
+        style Warning { fill_color="yellow"; }
+        a: Rectangle [class="Warn"];
+    "#;
     let mut provider = InMemorySourceProvider::new();
-    provider.add_file("test.orr", "diagram component;\nbox: Rectangle;");
-
-    let config = ElaborateConfig::new(LayoutEngine::Sugiyama, LayoutEngine::Basic);
-    let diagram = parse(&arena, Path::new("test.orr"), provider, config).expect("Failed to parse");
+    provider.add_file("test.orr", source);
+    let err = parse(
+        &arena,
+        Path::new("test.orr"),
+        provider,
+        ElaborateConfig::default(),
+    )
+    .expect_err("An undefined style should fail");
 
-    assert_eq!(diagram.layout_engine(), LayoutEngine::Sugiyama);
+    let diag = &err.diagnostics()[0];
+    assert_eq!(diag.code(), Some(ErrorCode::E208));
+    let span = diag.labels()[0].span();
+    assert_eq!(&source[span.start()..span.end()], "\"Warn\"");
 }
 
 #[test]
-fn test_diagram_layout_attribute() {
+fn test_component_position_attributes() {
     let diagram = parse_source(
         r#"
-        diagram component [layout_engine="sugiyama"];
-        box: Rectangle;
+        diagram component;
+        pinned: Rectangle [x=120, y=40, fill_color="red"];
+        column: Rectangle [x=60];
+        free: Rectangle;
     "#,
     );
 
-    // Diagram-level attribute overrides config
-    assert_eq!(diagram.layout_engine(), LayoutEngine::Sugiyama);
+    let pins: Vec<_> = diagram
+        .scope()
+        .elements()
+        .iter()
+        .map(|element| match element {
+            Element::Node(node) => node.pin(),
+            _ => panic!("Expected Node element"),
+        })
+        .collect();
+    assert_eq!((pins[0].x(), pins[0].y()), (Some(120.0), Some(40.0)));
+    assert_eq!((pins[1].x(), pins[1].y()), (Some(60.0), None));
+    assert!(pins[2].is_empty());
+
+    let arena = Bump::new();
+    let mut provider = InMemorySourceProvider::new();
+    provider.add_file("test.orr", "diagram sequence; a: Rectangle [x=10];");
+    let err = parse(
+        &arena,
+        Path::new("test.orr"),
+        provider,
+        ElaborateConfig::default(),
+    )
+    .expect_err("positions should be rejected in sequence diagrams");
+    assert_eq!(err.diagnostics()[0].code(), Some(ErrorCode::E304));
+
+    let arena = Bump::new();
+    let mut provider = InMemorySourceProvider::new();
+    provider.add_file("test.orr", "diagram component; a: Rectangle [x=-10];");
+    let err = parse(
+        &arena,
+        Path::new("test.orr"),
+        provider,
+        ElaborateConfig::default(),
+    )
+    .expect_err("negative positions should be rejected");
+    let diag = &err.diagnostics()[0];
+    assert_eq!(diag.code(), Some(ErrorCode::E302));
+    assert_eq!(diag.message(), "position `x` cannot be negative");
 }
 
 #[test]
@@ -263,6 +1255,30 @@ fn test_with_notes() {
     }
 }
 
+#[test]
+fn test_note_with_multiline_content() {
+    let diagram = parse_source(
+        r#"
+        diagram sequence;
+        client: Rectangle;
+        note [on=[client]]: """
+            First paragraph
+              continues here.
+
+            Second paragraph
+        """;
+    "#,
+    );
+
+    let Element::Note(note) = &diagram.scope().elements()[1] else {
+        panic!("Expected Note element");
+    };
+    assert_eq!(
+        note.content(),
+        "First paragraph\n  continues here.\n\nSecond paragraph"
+    );
+}
+
 #[test]
 fn test_note_with_alignment() {
     let diagram = parse_source(
@@ -350,6 +1366,150 @@ fn test_alt_fragment_with_sections() {
     }
 }
 
+#[test]
+fn test_highlight_block() {
+    let diagram = parse_source(
+        r#"
+        diagram sequence;
+        client: Rectangle;
+        server: Rectangle;
+
+        highlight [fill_color="lavender"] "during transaction" {
+            client -> server: "BEGIN";
+            server -> client: "COMMIT";
+        };
+    "#,
+    );
+
+    let elements = diagram.scope().elements();
+    assert_eq!(elements.len(), 3); // 2 nodes + 1 highlight
+
+    match &elements[2] {
+        Element::Highlight(highlight) => {
+            assert_eq!(highlight.label(), "during transaction");
+            assert_eq!(highlight.elements().len(), 2);
+            assert_eq!(
+                highlight.definition().fill_color(),
+                Color::new("lavender").unwrap()
+            );
+        }
+        _ => panic!("Expected Highlight element"),
+    }
+
+    let arena = Bump::new();
+    let mut provider = InMemorySourceProvider::new();
+    provider.add_file(
+        "test.orr",
+        r#"diagram component; a: Rectangle; highlight "tx" { };"#,
+    );
+    let err = parse(
+        &arena,
+        Path::new("test.orr"),
+        provider,
+        ElaborateConfig::default(),
+    )
+    .expect_err("highlight should be rejected in component diagrams");
+    assert_eq!(err.diagnostics()[0].code(), Some(ErrorCode::E304));
+}
+
+#[test]
+fn test_highlight_can_name_a_component() {
+    let diagram = parse_source(
+        r#"
+        diagram sequence;
+        highlight: Rectangle;
+        server: Rectangle;
+        highlight -> server;
+        highlight "tx" {
+            server -> highlight;
+        };
+    "#,
+    );
+
+    let elements = diagram.scope().elements();
+    assert_eq!(elements.len(), 4); // 2 nodes + 1 relation + 1 highlight
+    let names: Vec<_> = elements
+        .iter()
+        .filter_map(|element| match element {
+            Element::Node(node) => Some(node.id().to_string()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(names, ["highlight", "server"]);
+    assert!(matches!(elements[3], Element::Highlight(_)));
+}
+
+#[test]
+fn test_let_can_name_a_component() {
+    let diagram = parse_source(
+        r#"
+        diagram component;
+        let gap = 8;
+        let: Rectangle;
+        api: Rectangle;
+        let -> api;
+    "#,
+    );
+
+    let elements = diagram.scope().elements();
+    assert_eq!(elements.len(), 3); // 2 nodes + 1 relation
+    assert!(matches!(&elements[0], Element::Node(node) if node.id() == "let"));
+}
+
+#[test]
+fn test_group_block() {
+    let diagram = parse_source(
+        r#"
+        diagram component;
+        client: Rectangle;
+
+        group [stroke=[color="teal"]] "Backend" {
+            api: Rectangle;
+            group "Storage" {
+                db: Rectangle;
+            };
+            api -> db;
+        };
+    "#,
+    );
+
+    let elements = diagram.scope().elements();
+    assert_eq!(elements.len(), 2); // 1 node + 1 group
+
+    let Element::Group(group) = &elements[1] else {
+        panic!("Expected Group element");
+    };
+    assert_eq!(group.label(), "Backend");
+    assert_eq!(group.elements().len(), 3);
+    assert_eq!(
+        group.definition().stroke().color(),
+        Color::new("teal").unwrap()
+    );
+    // Groups do not introduce a namespace.
+    let Element::Group(storage) = &group.elements()[1] else {
+        panic!("Expected nested Group element");
+    };
+    let Element::Node(db) = &storage.elements()[0] else {
+        panic!("Expected Node element");
+    };
+    assert_eq!(db.id(), Id::new("db"));
+
+    let arena = Bump::new();
+    let mut provider = InMemorySourceProvider::new();
+    provider.add_file(
+        "test.orr",
+        r#"diagram sequence; a: Rectangle; group "g" { };"#,
+    );
+    let err = parse(
+        &arena,
+        Path::new("test.orr"),
+        provider,
+        ElaborateConfig::default(),
+    )
+    .expect_err("group should be rejected in sequence diagrams");
+    assert_eq!(err.diagnostics()[0].code(), Some(ErrorCode::E304));
+}
+
 #[test]
 fn test_nested_components() {
     let diagram = parse_source(
@@ -395,6 +1555,34 @@ fn test_nested_components() {
     }
 }
 
+#[test]
+fn test_diagram_stats() {
+    let diagram = parse_source(
+        r#"
+        diagram component;
+        frontend: Rectangle;
+        backend: Rectangle {
+            api: Rectangle;
+            storage: Rectangle {
+                db: Rectangle;
+                cache: Rectangle;
+            };
+            api -> storage;
+        };
+        frontend -> backend: "HTTPS";
+        frontend -> backend::api;
+        note [on=[frontend]]: "Public entry point";
+    "#,
+    );
+
+    let stats = diagram.stats();
+    assert_eq!(stats.kind(), DiagramKind::Component);
+    assert_eq!(stats.components(), 6);
+    assert_eq!(stats.relations(), 3);
+    assert_eq!(stats.notes(), 1);
+    assert_eq!(stats.max_depth(), 3);
+}
+
 #[test]
 fn test_activation() {
     let diagram = parse_source(
@@ -483,6 +1671,93 @@ fn test_cross_level_relation() {
     }
 }
 
+/// Returns the `source -> target` pairs of every relation in `diagram`,
+/// including those in nested scopes.
+fn relation_endpoints(diagram: &Diagram) -> Vec<String> {
+    struct Endpoints(Vec<String>);
+
+    impl Visitor for Endpoints {
+        fn visit_relation(&mut self, relation: &Relation) {
+            self.0
+                .push(format!("{} -> {}", relation.source(), relation.target()));
+        }
+    }
+
+    let mut endpoints = Endpoints(Vec::new());
+    diagram.accept(&mut endpoints);
+    endpoints.0
+}
+
+#[test]
+fn test_nested_references_resolve_through_enclosing_scopes() {
+    let diagram = parse_source(
+        r#"
+        diagram component;
+        db: Rectangle;
+        api: Rectangle;
+        cache: Rectangle { store: Rectangle; };
+        outer: Rectangle {
+            db: Oval;
+            inner: Rectangle {
+                worker: Rectangle;
+                worker -> db;
+                worker -> api;
+                worker -> outer::db;
+            };
+            cache: Rectangle { store: Rectangle; };
+            inner::worker -> cache::store;
+            cache -> inner::worker;
+        };
+    "#,
+    );
+
+    assert_eq!(
+        relation_endpoints(&diagram),
+        [
+            // The closest `db` shadows the top-level one
+            "outer::inner::worker -> outer::db",
+            // Names not declared nearby come from outer scopes
+            "outer::inner::worker -> api",
+            "outer::inner::worker -> outer::db",
+            // Qualified names are looked up from the root first, and
+            // otherwise relative to the enclosing scopes
+            "outer::inner::worker -> cache::store",
+            "outer::cache -> outer::inner::worker",
+        ]
+    );
+}
+
+#[test]
+fn test_ambiguous_nested_reference_lists_candidates() {
+    let arena = Bump::new();
+    let mut provider = InMemorySourceProvider::new();
+    provider.add_file(
+        "test.orr",
+        r#"
+        diagram component;
+        a: Rectangle { db: Rectangle; };
+        b: Rectangle { db: Rectangle; };
+        c: Rectangle { worker: Rectangle; worker -> db; };
+    "#,
+    );
+    let err = parse(
+        &arena,
+        Path::new("test.orr"),
+        provider,
+        ElaborateConfig::default(),
+    )
+    .expect_err("`db` should be ambiguous");
+
+    let diag = &err.diagnostics()[0];
+    assert_eq!(diag.code(), Some(ErrorCode::E200));
+    assert!(diag.message().contains("ambiguous"), "{}", diag.message());
+    let help = diag.help().expect("candidates should be listed");
+    assert!(
+        help.contains("`a::db`") && help.contains("`b::db`"),
+        "{help}"
+    );
+}
+
 #[test]
 fn test_empty_diagram() {
     let diagram = parse_source("diagram component;");
@@ -520,7 +1795,8 @@ fn test_import_library_types() {
         provider,
         ElaborateConfig::default(),
     )
-    .expect("Failed to parse");
+    .expect("Failed to parse")
+    .into_diagram();
 
     let elements = diagram.scope().elements();
     assert_eq!(elements.len(), 2);
@@ -566,7 +1842,8 @@ fn test_import_transitive_libraries() {
         provider,
         ElaborateConfig::default(),
     )
-    .expect("Failed to parse");
+    .expect("Failed to parse")
+    .into_diagram();
 
     let elements = diagram.scope().elements();
     assert_eq!(elements.len(), 2);
@@ -622,7 +1899,8 @@ fn test_import_diamond_dependency() {
         provider,
         ElaborateConfig::default(),
     )
-    .expect("Failed to parse");
+    .expect("Failed to parse")
+    .into_diagram();
 
     let elements = diagram.scope().elements();
     assert_eq!(elements.len(), 2);
@@ -659,6 +1937,38 @@ fn test_error_file_not_found() {
     );
 }
 
+#[test]
+fn test_error_cyclic_type_aliases() {
+    let arena = Bump::new();
+    let mut provider = InMemorySourceProvider::new();
+    provider.add_file(
+        "test.orr",
+        r#"
+        diagram component;
+        type A = B;
+        type B = A;
+        a: A;
+    "#,
+    );
+
+    let err = parse(
+        &arena,
+        Path::new("test.orr"),
+        provider,
+        ElaborateConfig::default(),
+    )
+    .expect_err("Cyclic type aliases should fail");
+
+    // Types resolve in source order, so the forward reference is the error.
+    let diag = &err.diagnostics()[0];
+    assert_eq!(diag.code(), Some(ErrorCode::E205));
+    assert!(
+        diag.message().contains("`B`"),
+        "unexpected message: {}",
+        diag.message()
+    );
+}
+
 #[test]
 fn test_error_circular_dependency() {
     let mut provider = InMemorySourceProvider::new();
@@ -746,7 +2056,8 @@ fn test_import_embed_ref_happy_path() {
         provider,
         ElaborateConfig::default(),
     )
-    .expect("Failed to parse");
+    .expect("Failed to parse")
+    .into_diagram();
 
     assert_eq!(diagram.kind(), DiagramKind::Component);
     let elements = diagram.scope().elements();
@@ -868,7 +2179,8 @@ fn test_import_embed_ref_with_other_components() {
         provider,
         ElaborateConfig::default(),
     )
-    .expect("Failed to parse");
+    .expect("Failed to parse")
+    .into_diagram();
 
     let elements = diagram.scope().elements();
     // 3 nodes + 2 relations = 5 elements
@@ -924,3 +2236,571 @@ fn test_import_embed_ref_glob_import_does_not_resolve() {
         diag.code()
     );
 }
+
+const LINT_SOURCE: &str = r#"
+    diagram component;
+    type Service = Rectangle[fill_color="lightblue"];
+    type Unused = Oval;
+    type Kept = Oval[allow_unused="true"];
+    api: Service;
+    db: Rectangle;
+    idle: Rectangle;
+    group: Rectangle {
+        inner: Rectangle;
+    };
+    api -> db;
+    note [on=[group::inner]]: "Referenced by a note";
+"#;
+
+#[test]
+fn test_lint_reports_orphans_and_unused_types() {
+    let diagnostics = lint_source(LINT_SOURCE, &LintConfig::default());
+
+    let messages: Vec<_> = diagnostics.iter().map(|d| d.message()).collect();
+    assert_eq!(
+        messages,
+        [
+            "component `idle` is not referenced by any relation, note, or activation",
+            "type `Unused` is never used",
+        ]
+    );
+    assert!(diagnostics.iter().all(|d| !d.severity().is_error()));
+}
+
+#[test]
+fn test_lint_level_error_and_off() {
+    let config = LintConfig::default()
+        .with_orphan_components(LintLevel::Error)
+        .with_unused_types(LintLevel::Off);
+    let diagnostics = lint_source(LINT_SOURCE, &config);
+
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0].severity().is_error());
+    assert!(diagnostics[0].message().contains("`idle`"));
+}
+
+#[test]
+fn test_parse_returns_warnings_with_diagram() {
+    let arena = Bump::new();
+    let mut provider = InMemorySourceProvider::new();
+    provider.add_file(
+        "test.orr",
+        r#"diagram component; a: Rectangle; b: Rectangle; a -> b: "  ";"#,
+    );
+
+    let parsed = parse(
+        &arena,
+        Path::new("test.orr"),
+        provider,
+        ElaborateConfig::default(),
+    )
+    .expect("warnings should not fail parsing");
+
+    assert_eq!(parsed.diagram().scope().elements().len(), 3);
+    let [warning] = parsed.warnings() else {
+        panic!("Expected one warning, got {:?}", parsed.warnings());
+    };
+    assert!(!warning.severity().is_error());
+    assert_eq!(warning.message(), "relation label contains only whitespace");
+    let span = warning.labels()[0].span();
+    assert_eq!(parsed.source_map().source_slice(span), Some(r#""  ""#));
+}
+
+#[test]
+fn test_lint_report_includes_parse_warnings() {
+    let source = r#"diagram component; a: Rectangle; b: Rectangle; a -> b: " ";"#;
+    let diagnostics = lint_source(source, &LintConfig::default());
+
+    let messages: Vec<_> = diagnostics.iter().map(|d| d.message()).collect();
+    assert_eq!(messages, ["relation label contains only whitespace"]);
+}
+
+#[test]
+fn test_lint_ignores_imported_types() {
+    let mut provider = InMemorySourceProvider::new();
+    provider.add_file(
+        "styles.orr",
+        r#"
+        library;
+        type Service = Rectangle;
+        type Database = Oval;
+    "#,
+    );
+    provider.add_file(
+        "main.orr",
+        r#"
+        diagram sequence;
+        import "styles"::*;
+        a: Service;
+        b: Rectangle;
+        activate a {
+            a -> b;
+        };
+    "#,
+    );
+
+    let arena = Bump::new();
+    let report = lint(
+        &arena,
+        Path::new("main.orr"),
+        provider,
+        ElaborateConfig::default(),
+        &LintConfig::default(),
+    )
+    .expect("Failed to lint");
+
+    assert!(
+        report.diagnostics().is_empty(),
+        "Unexpected lint findings: {:?}",
+        report.diagnostics()
+    );
+}
+
+/// Parses `source` as `test.orr`, returning the messages of its warnings.
+fn parse_warning_messages(source: &str) -> Vec<String> {
+    let arena = Bump::new();
+    let mut provider = InMemorySourceProvider::new();
+    provider.add_file("test.orr", source);
+
+    let parsed = parse(
+        &arena,
+        Path::new("test.orr"),
+        provider,
+        ElaborateConfig::default(),
+    )
+    .expect("Failed to parse");
+    parsed
+        .warnings()
+        .iter()
+        .map(|warning| warning.message().to_string())
+        .collect()
+}
+
+#[test]
+fn test_parse_warns_about_unused_types() {
+    let messages = parse_warning_messages(
+        r#"
+        diagram component;
+        type Base = Rectangle[fill_color="lightblue"];
+        type Service = Base;
+        type Unused = Oval;
+        type Kept = Oval[allow_unused="true", fill_color="red"];
+        type Dashed = Stroke[style="dashed"];
+        api: Service[stroke=Dashed];
+    "#,
+    );
+
+    assert_eq!(messages, ["type `Unused` is never used"]);
+}
+
+#[test]
+fn test_allow_unused_is_not_applied_to_the_type() {
+    // `allow_unused` is not a shape attribute, so applying it to the
+    // `Rectangle` would fail elaboration.
+    let diagram = parse_source(
+        r#"
+        diagram component;
+        type Kept = Rectangle[allow_unused="true", fill_color="red"];
+        type Derived = Kept;
+        api: Derived;
+    "#,
+    );
+
+    assert_eq!(diagram.scope().elements().len(), 1);
+}
+
+#[test]
+fn test_allow_unused_rejects_invalid_values() {
+    for source in [
+        r#"diagram component; type Kept = Oval[allow_unused="yes"];"#,
+        r#"diagram component; a: Rectangle[allow_unused="true"];"#,
+    ] {
+        let arena = Bump::new();
+        let mut provider = InMemorySourceProvider::new();
+        provider.add_file("test.orr", source);
+        let err = parse(
+            &arena,
+            Path::new("test.orr"),
+            provider,
+            ElaborateConfig::default(),
+        )
+        .expect_err("invalid `allow_unused` should fail");
+        assert!(err.diagnostics()[0].severity().is_error(), "{source}");
+    }
+}
+
+#[test]
+fn test_async_message_arrows() {
+    let diagram = parse_source(
+        r#"
+        diagram sequence;
+        a: Rectangle;
+        b: Rectangle;
+        a ->> b: "Fire and forget";
+        a <<- b;
+        a -> b;
+    "#,
+    );
+
+    let directions: Vec<_> = diagram
+        .scope()
+        .elements()
+        .iter()
+        .filter_map(|element| match element {
+            Element::Relation(relation) => Some(relation.arrow_direction()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        directions,
+        [
+            ArrowDirection::AsyncForward,
+            ArrowDirection::AsyncBackward,
+            ArrowDirection::Forward,
+        ]
+    );
+}
+
+#[test]
+fn test_content_align_attribute() {
+    let diagram = parse_source(
+        r#"
+        diagram component;
+        card: Rectangle[content_align="top-left"];
+    "#,
+    );
+
+    let Element::Node(node) = &diagram.scope().elements()[0] else {
+        panic!("Expected Node element");
+    };
+    assert_eq!(
+        node.shape_definition().content_align(),
+        ContentAlign::new(VerticalAlign::Top, HorizontalAlign::Left)
+    );
+
+    let parse_error = |source: &str| {
+        let arena = Bump::new();
+        let mut provider = InMemorySourceProvider::new();
+        provider.add_file("test.orr", source);
+        let err = parse(
+            &arena,
+            Path::new("test.orr"),
+            provider,
+            ElaborateConfig::default(),
+        )
+        .expect_err("content_align should be rejected");
+        err.diagnostics()[0].code()
+    };
+    assert_eq!(
+        parse_error(r#"diagram component; card: Rectangle[content_align="middle"];"#),
+        Some(ErrorCode::E302)
+    );
+    assert_eq!(
+        parse_error(r#"diagram component; user: Actor[content_align="top"];"#),
+        Some(ErrorCode::E304)
+    );
+}
+
+#[test]
+fn test_href_and_target_attributes() {
+    let diagram = parse_source(
+        r#"
+        diagram component;
+        type Doc = Rectangle[href="https://example.com/docs"];
+        api: Doc[target="_blank"];
+        db: Rectangle;
+        api -> [href="https://example.com/sql"] db;
+    "#,
+    );
+
+    let links: Vec<_> = diagram
+        .scope()
+        .elements()
+        .iter()
+        .map(|element| {
+            let link = match element {
+                Element::Node(node) => node.shape_definition().link(),
+                Element::Relation(relation) => relation.arrow_definition().link(),
+                _ => None,
+            };
+            link.map(|link| (link.href(), link.target()))
+        })
+        .collect();
+    assert_eq!(
+        links,
+        [
+            Some((Some("https://example.com/docs"), Some("_blank"))),
+            None,
+            Some((Some("https://example.com/sql"), None)),
+        ]
+    );
+
+    let arena = Bump::new();
+    let mut provider = InMemorySourceProvider::new();
+    provider.add_file(
+        "test.orr",
+        r#"diagram component; api: Rectangle[target="_blank"];"#,
+    );
+    let err = parse(
+        &arena,
+        Path::new("test.orr"),
+        provider,
+        ElaborateConfig::default(),
+    )
+    .expect_err("target without href should be rejected");
+    assert_eq!(err.diagnostics()[0].code(), Some(ErrorCode::E302));
+}
+
+#[test]
+fn test_tooltip_attribute() {
+    let diagram = parse_source(
+        r#"
+        diagram component;
+        api: Rectangle[tooltip="Public API\nowned by team A"];
+        user: Actor[tooltip="Customer"];
+        api -> [tooltip="HTTPS"] user;
+    "#,
+    );
+
+    let elements = diagram.scope().elements();
+    let tooltips: Vec<_> = elements
+        .iter()
+        .map(|element| match element {
+            Element::Node(node) => node.shape_definition().tooltip(),
+            Element::Relation(relation) => relation.arrow_definition().tooltip(),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        tooltips,
+        [
+            Some("Public API\nowned by team A"),
+            Some("Customer"),
+            Some("HTTPS")
+        ]
+    );
+
+    let arena = Bump::new();
+    let mut provider = InMemorySourceProvider::new();
+    provider.add_file("test.orr", "diagram component; api: Rectangle[tooltip=3];");
+    let err = parse(
+        &arena,
+        Path::new("test.orr"),
+        provider,
+        ElaborateConfig::default(),
+    )
+    .expect_err("non-string tooltip should be rejected");
+    assert_eq!(err.diagnostics()[0].code(), Some(ErrorCode::E302));
+}
+
+#[test]
+fn test_negative_and_scientific_float_attributes() {
+    let diagram = parse_source(
+        r#"
+        diagram component;
+        a: Rectangle [min_width=1e3];
+        b: Rectangle;
+        a -> [label_offset=-2.5, curvature=1.5e-2] b;
+        a-b;
+    "#,
+    );
+
+    let relations: Vec<_> = diagram
+        .scope()
+        .elements()
+        .iter()
+        .filter_map(|element| match element {
+            Element::Relation(relation) => Some(relation),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(relations.len(), 2);
+    assert_eq!(relations[0].arrow_definition().label_offset(), -2.5);
+    assert_eq!(relations[0].arrow_definition().curvature(), 1.5e-2);
+    assert_eq!(relations[1].arrow_direction(), ArrowDirection::Plain);
+}
+
+#[test]
+fn test_min_length_attribute() {
+    let diagram = parse_source(
+        r#"
+        diagram component;
+        type Far = Arrow[min_length=3];
+        a: Rectangle;
+        b: Rectangle;
+        a -> b;
+        a -> @Far b;
+        a -> [min_length=2] b;
+    "#,
+    );
+
+    let min_lengths: Vec<_> = diagram
+        .scope()
+        .elements()
+        .iter()
+        .filter_map(|element| match element {
+            Element::Relation(relation) => Some(relation.arrow_definition().min_length()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(min_lengths, [1, 3, 2]);
+
+    let arena = Bump::new();
+    let mut provider = InMemorySourceProvider::new();
+    provider.add_file(
+        "test.orr",
+        "diagram component; a: Rectangle; b: Rectangle; a -> [min_length=0] b;",
+    );
+    let err = parse(
+        &arena,
+        Path::new("test.orr"),
+        provider,
+        ElaborateConfig::default(),
+    )
+    .expect_err("zero min_length should be rejected");
+    assert_eq!(err.diagnostics()[0].code(), Some(ErrorCode::E302));
+}
+
+#[test]
+fn test_to_source_round_trips() {
+    let mut sources = vec![
+        r#"
+        diagram component [layout_engine="sugiyama"];
+        type Service = Rectangle [fill_color="lightblue"];
+        client as "Web \"Client\"": Actor;
+        backend: Service {
+            api: Component;
+            db: Oval [x=40, y=20];
+            api <-> db: "line 1\nline 2";
+        };
+        group "Edge" {
+            cache: Entity;
+            gate: Interface;
+        };
+        client -> backend::api: "";
+        client - cache;
+        note [on=[backend], align="right"]: "Stateful";
+        "#
+        .to_string(),
+        r#"
+        diagram component [layout_engine="grid", columns=2];
+        a: Boundary;
+        b: Control;
+        c: Rectangle embed {
+            diagram sequence;
+            x: Rectangle;
+            y: Rectangle;
+            x ->> y: "async";
+        };
+        a <- b;
+        "#
+        .to_string(),
+        r#"
+        diagram sequence;
+        user: Actor;
+        server: Rectangle;
+        db: Rectangle;
+        activate user {
+            user -> server: "login";
+            alt "valid" {
+                server -> db: "query";
+                db <<- server;
+            } else {
+                server -> user: "denied";
+            };
+        };
+        highlight "transaction" {
+            server -> server: "retry";
+        };
+        note [on=[user, db]]: "spans";
+        note: "margin";
+        "#
+        .to_string(),
+        r##"
+        diagram component [
+            layout_engine="basic", background_color="ivory", canvas_padding=[top=4, left=8],
+            width=640, fit="none", title="Styled", caption="All of it", rank_spacing=30,
+            edge_routing="orthogonal", density="compact", autocolor="true"
+        ];
+        type Faded = Rectangle [
+            fill_color="#336699", fill_opacity=0.5, stroke_opacity=50%,
+            stroke=[color="navy", width=3, style="4 2", cap="round", join="bevel"],
+            corner_radius=6.5, min_width=80, min_height=40, content_align="top-left",
+            text=[font_size=14, font_family="Courier", font_style="italic",
+                background_color="white", padding=6, color="gray"]
+        ];
+        type Link = Arrow [
+            stroke=[color="red", style="dotted"], style="orthogonal",
+            text=[background_color="none"], segments="solid:0.25, dashed:0.75",
+            arrowhead="diamond", arrowhead_start="circle", label_pos="end", label_offset=4,
+            curvature=0.2, min_length=2, tooltip="calls", href="https://example.com",
+            target="_blank"
+        ];
+        a: Faded;
+        b: Actor [fill_color="pink", shadow="true", tooltip="who"];
+        c: Oval [shadow=[blur=2, color="red"]];
+        d: Component [rounded=3, href="https://example.com/d", icon=[source="logo.png", align="bottom-right", size=24]];
+        a -> @Link b: "uses";
+        b -> [stroke=[width=2]] c;
+        group [stroke=[style="solid"], text=[font_size=9], tab_fill_color="khaki", padding=5] "G" {
+            e: Entity [fill_opacity=0.3];
+        };
+        note [background_color="lavender", stroke=[color="purple"], text=[color="purple"]]: "styled";
+        "##
+        .to_string(),
+        r#"
+        diagram sequence [lifeline=[stroke=[color="gray", style="solid"]]];
+        a: Rectangle;
+        b: Rectangle;
+        activate [width=12, nesting_offset=6, fill_color="lightgreen", stroke=[color="green"]] a;
+        a -> b;
+        deactivate a;
+        fragment [
+            border_stroke=[width=2], separator_stroke=[color="blue"], background_color="aliceblue",
+            operation_label_text=[font_size=12], section_title_text=[color="navy"]
+        ] "alt" {
+            section "ok" {
+                b -> a;
+            };
+        };
+        highlight [fill_color="mistyrose", text=[font_size=7]] "hot" {
+            a -> b;
+        };
+        "#
+        .to_string(),
+    ];
+
+    // Every self-contained example file round-trips too.
+    let examples = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../examples");
+    for entry in std::fs::read_dir(examples).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().is_some_and(|ext| ext == "orr") {
+            let source = std::fs::read_to_string(&path).unwrap();
+            if !source.contains("import") {
+                sources.push(source);
+            }
+        }
+    }
+
+    for source in &sources {
+        let diagram = parse_source(source);
+        // Parsed diagrams always pass the structural checks.
+        diagram
+            .validate()
+            .unwrap_or_else(|err| panic!("{err}\nsource:\n{source}"));
+        let emitted = diagram.to_source();
+        let reparsed = parse_source(&emitted);
+
+        assert_eq!(reparsed.to_source(), emitted, "source:\n{source}");
+        assert_eq!(reparsed.outline(), diagram.outline(), "emitted:\n{emitted}");
+        assert_eq!(
+            format!("{:?}", reparsed.stats()),
+            format!("{:?}", diagram.stats())
+        );
+        assert_eq!(
+            serde_json::to_value(&reparsed).unwrap(),
+            serde_json::to_value(&diagram).unwrap(),
+            "emitted:\n{emitted}"
+        );
+    }
+}
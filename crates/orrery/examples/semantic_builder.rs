@@ -0,0 +1,52 @@
+//! Example: Building a diagram with the fluent semantic builder
+//!
+//! This example demonstrates how to declare components and relations with
+//! `semantic::DiagramBuilder` and render the result, without parsing source
+//! code or assembling the semantic model types by hand.
+
+use orrery::{
+    DiagramBuilder, InMemorySourceProvider,
+    config::AppConfig,
+    semantic::{self, DiagramKind, LayoutEngine},
+};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("Building diagram with the semantic builder...\n");
+
+    // Declare three components and the relations between them. `label`
+    // applies to the element declared just before it.
+    let diagram = semantic::DiagramBuilder::new(DiagramKind::Component)
+        .layout_engine(LayoutEngine::Basic)
+        .component("client")
+        .label("Web Client")
+        .component("server")
+        .label("API Server")
+        .component("database")
+        .label("Database")
+        .relation("client", "server")
+        .label("HTTP requests")
+        .relation("server", "database")
+        .label("SQL queries")
+        .build()?;
+
+    // Print diagram info
+    let stats = diagram.stats();
+    println!("Created diagram:");
+    println!("  Kind: {}", stats.kind());
+    println!("  Components: {}", stats.components());
+    println!("  Relations: {}", stats.relations());
+    println!();
+
+    // Render the diagram to SVG using the pipeline DiagramBuilder
+    println!("Rendering to SVG...");
+    let provider = InMemorySourceProvider::new();
+    let builder = DiagramBuilder::new(AppConfig::default(), &provider);
+    let svg = builder.render_svg(&diagram)?;
+
+    // Write to file
+    let output_path = "semantic_builder_output.svg";
+    std::fs::write(output_path, &svg)?;
+    println!("SVG written to: {}", output_path);
+
+    Ok(())
+}
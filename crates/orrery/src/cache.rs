@@ -0,0 +1,128 @@
+//! Caching of parsed diagrams across repeated parses.
+//!
+//! Editors re-parse the open file on every keystroke, and most of those parses
+//! see the same sources as the previous one. [`ParseCache`] remembers the last
+//! parsed [`Diagram`] together with a hash of every source file it was built
+//! from, so [`DiagramBuilder::parse_cached`](crate::DiagramBuilder::parse_cached)
+//! only needs to re-read the files to detect that nothing changed.
+
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+};
+
+use orrery_core::semantic::Diagram;
+use orrery_parser::SourceProvider;
+
+/// The last parsed diagram, keyed on a hash of its sources.
+///
+/// A cache belongs to one [`DiagramBuilder`](crate::DiagramBuilder)
+/// configuration: the key covers the root path and the contents of the root
+/// file and all of its imports, but not the configuration used to elaborate
+/// them.
+///
+/// # Examples
+///
+/// ```
+/// # use std::path::Path;
+/// # use bumpalo::Bump;
+/// # use orrery::{DiagramBuilder, InMemorySourceProvider, ParseCache, config::AppConfig};
+/// let mut provider = InMemorySourceProvider::new();
+/// provider.add_file("app.orr", "diagram component; app: Rectangle;");
+/// let builder = DiagramBuilder::new(AppConfig::default(), &provider);
+///
+/// let arena = Bump::new();
+/// let mut cache = ParseCache::new();
+/// builder.parse_cached(&arena, Path::new("app.orr"), &mut cache).unwrap();
+/// builder.parse_cached(&arena, Path::new("app.orr"), &mut cache).unwrap();
+/// assert_eq!((cache.hits(), cache.misses()), (1, 1));
+/// ```
+#[derive(Debug, Default)]
+pub struct ParseCache {
+    entry: Option<CacheEntry>,
+    hits: usize,
+    misses: usize,
+}
+
+/// A cached diagram and the sources it was parsed from.
+#[derive(Debug)]
+struct CacheEntry {
+    hash: u64,
+    root_path: PathBuf,
+    /// Every file the diagram was parsed from, the root file first.
+    files: Vec<PathBuf>,
+    diagram: Diagram,
+}
+
+impl ParseCache {
+    /// Creates an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of parses answered from the cache.
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    /// Returns the number of parses that had to run the pipeline.
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    /// Forgets the cached diagram.
+    pub fn clear(&mut self) {
+        self.entry = None;
+    }
+
+    /// Returns the cached diagram if `root_path` and all the files it was
+    /// parsed from still have the same contents, as read through `provider`.
+    pub(crate) fn lookup(
+        &mut self,
+        provider: &dyn SourceProvider,
+        root_path: &Path,
+    ) -> Option<&Diagram> {
+        let is_fresh = self.entry.as_ref().is_some_and(|entry| {
+            entry.root_path == root_path
+                && entry
+                    .files
+                    .iter()
+                    .map(|path| Some((path.as_path(), provider.read_source(path).ok()?)))
+                    .collect::<Option<Vec<_>>>()
+                    .is_some_and(|files| {
+                        let files = files.iter().map(|(path, source)| (*path, source.as_str()));
+                        hash_sources(root_path, files) == entry.hash
+                    })
+        });
+
+        if is_fresh {
+            self.hits += 1;
+            self.entry.as_ref().map(|entry| &entry.diagram)
+        } else {
+            self.misses += 1;
+            None
+        }
+    }
+
+    /// Replaces the cached diagram with `diagram`, parsed from `files`.
+    pub(crate) fn store(&mut self, root_path: &Path, files: &[(&Path, &str)], diagram: Diagram) {
+        self.entry = Some(CacheEntry {
+            hash: hash_sources(root_path, files.iter().copied()),
+            root_path: root_path.to_path_buf(),
+            files: files.iter().map(|(path, _)| path.to_path_buf()).collect(),
+            diagram,
+        });
+    }
+}
+
+/// Hashes a root path together with the path and contents of each source file.
+fn hash_sources<'s>(root_path: &Path, files: impl Iterator<Item = (&'s Path, &'s str)>) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    root_path.hash(&mut hasher);
+    for (path, source) in files {
+        path.hash(&mut hasher);
+        source.hash(&mut hasher);
+    }
+    hasher.finish()
+}
@@ -1,36 +1,230 @@
 //! Configuration controlling how diagrams are laid out.
 //!
-//! All types implement [`serde::Deserialize`] for loading from external sources.
+//! All types implement [`serde::Deserialize`] for loading from external sources
+//! and [`serde::Serialize`] for writing the effective configuration back out,
+//! see [`AppConfig::to_toml_string`].
 
-use serde::Deserialize;
+use std::{
+    num::NonZeroU16,
+    path::{Path, PathBuf},
+};
 
-use orrery_core::semantic::LayoutEngine;
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error as _};
+
+use orrery_core::{
+    color::{Color, Palette, Theme},
+    draw::{Density, FontStyle},
+    semantic::LayoutEngine,
+};
+use orrery_parser::{ImplicitNodes, lint::LintConfig};
+
+use crate::SpacingPreset;
 
 /// Top-level application configuration.
 ///
-/// Wraps the [`LayoutConfig`] that controls layout-engine selection.
-#[derive(Debug, Clone, Default, Deserialize)]
+/// Wraps the [`LayoutConfig`] that controls layout-engine selection, the
+/// [`ExportConfig`] that controls rendering, the [`StyleConfig`] that picks
+/// the default colors, the [`ParseConfig`] that sets the defaults of diagram
+/// attributes, the [`LintConfig`] that sets the level of each lint rule, and
+/// the [`Palette`] of named colors available to diagrams.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
 pub struct AppConfig {
     #[serde(default)]
     layout: LayoutConfig,
+
+    #[serde(default)]
+    export: ExportConfig,
+
+    #[serde(default)]
+    style: StyleConfig,
+
+    #[serde(default)]
+    parse: ParseConfig,
+
+    #[serde(default)]
+    lint: LintConfig,
+
+    /// Named colors loaded from an external palette file.
+    #[serde(skip)]
+    palette: Palette,
 }
 
 impl AppConfig {
     /// Creates an [`AppConfig`] with the given layout configuration.
     pub fn new(layout: LayoutConfig) -> Self {
-        Self { layout }
+        Self {
+            layout,
+            export: ExportConfig::default(),
+            style: StyleConfig::default(),
+            parse: ParseConfig::default(),
+            lint: LintConfig::default(),
+            palette: Palette::default(),
+        }
+    }
+
+    /// Sets the [`ExportConfig`] used when rendering diagrams.
+    pub fn with_export(mut self, export: ExportConfig) -> Self {
+        self.export = export;
+        self
+    }
+
+    /// Sets the [`StyleConfig`] that picks the default diagram colors.
+    pub fn with_style(mut self, style: StyleConfig) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Sets the [`ParseConfig`] that sets the defaults of diagram attributes.
+    pub fn with_parse(mut self, parse: ParseConfig) -> Self {
+        self.parse = parse;
+        self
+    }
+
+    /// Sets the [`LintConfig`] used by [`DiagramBuilder::lint`](crate::DiagramBuilder::lint).
+    pub fn with_lint(mut self, lint: LintConfig) -> Self {
+        self.lint = lint;
+        self
+    }
+
+    /// Sets the [`Palette`] of named colors that diagrams may reference.
+    pub fn with_palette(mut self, palette: Palette) -> Self {
+        self.palette = palette;
+        self
     }
 
     /// Returns the layout configuration.
     pub fn layout(&self) -> &LayoutConfig {
         &self.layout
     }
+
+    /// Returns the export configuration.
+    pub fn export(&self) -> &ExportConfig {
+        &self.export
+    }
+
+    /// Returns the style configuration.
+    pub fn style(&self) -> &StyleConfig {
+        &self.style
+    }
+
+    /// Returns the parse configuration.
+    pub fn parse(&self) -> &ParseConfig {
+        &self.parse
+    }
+
+    /// Returns the lint configuration.
+    pub fn lint(&self) -> &LintConfig {
+        &self.lint
+    }
+
+    /// Returns the palette of named colors.
+    pub fn palette(&self) -> &Palette {
+        &self.palette
+    }
+
+    /// Serializes the effective configuration as a TOML document.
+    ///
+    /// Unset spacing and color keys are written with the values they resolve
+    /// to, so the output can serve as a starting template for a configuration
+    /// file. The palette is not part of the output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orrery::config::AppConfig;
+    ///
+    /// let toml = AppConfig::default().to_toml_string();
+    /// assert!(toml.contains("[layout]"));
+    /// assert!(toml.contains("padding = 35.0"));
+    /// ```
+    pub fn to_toml_string(&self) -> String {
+        let effective = Self {
+            layout: self.layout.effective(),
+            style: self.style.effective(),
+            ..self.clone()
+        };
+        toml::to_string_pretty(&effective).expect("Configuration serializes to TOML")
+    }
+
+    /// Returns the dotted paths of the keys in `table` that are not part of
+    /// the configuration schema, such as `layout.padingg`.
+    ///
+    /// Unknown keys are otherwise ignored on load, so callers use this to
+    /// warn about likely typos.
+    pub fn unknown_keys(table: &toml::Table) -> Vec<String> {
+        let schema =
+            toml::Table::try_from(Self::schema()).expect("Configuration serializes to TOML");
+        let mut unknown = Vec::new();
+        collect_unknown_keys(&mut unknown, "", table, &schema);
+        unknown
+    }
+
+    /// Returns a configuration with every optional key set, used as the
+    /// reference for [`Self::unknown_keys`].
+    fn schema() -> Self {
+        let layout = LayoutConfig::default()
+            .with_rank_spacing(0.0)
+            .with_intra_rank_spacing(0.0);
+        let color = Color::default();
+        let style = StyleConfig::default()
+            .with_background_color(color)
+            .with_fill_color(color)
+            .with_stroke_color(color)
+            .with_text_color(color)
+            .with_label_background(None)
+            .with_font_family("")
+            .with_font_size(NonZeroU16::MIN)
+            .with_font_path("")
+            .with_min_width(0.0)
+            .with_min_height(0.0)
+            .with_relation_text(
+                RelationTextConfig::default()
+                    .with_font_family("")
+                    .with_font_size(NonZeroU16::MIN)
+                    .with_font_style(FontStyle::default())
+                    .with_color(color),
+            );
+        let parse = ParseConfig::default()
+            .with_implicit_node_type("")
+            .with_autonumber(Some(0));
+        Self::new(layout.effective())
+            .with_style(style)
+            .with_parse(parse)
+    }
+}
+
+/// Recursively pushes the keys of `table` that `schema` does not contain
+/// onto `unknown`, prefixing each with `prefix`.
+fn collect_unknown_keys(
+    unknown: &mut Vec<String>,
+    prefix: &str,
+    table: &toml::Table,
+    schema: &toml::Table,
+) {
+    for (key, value) in table {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{prefix}.{key}")
+        };
+
+        match (value, schema.get(key)) {
+            (_, None) => unknown.push(path),
+            (toml::Value::Table(nested), Some(toml::Value::Table(nested_schema))) => {
+                collect_unknown_keys(unknown, &path, nested, nested_schema);
+            }
+            _ => {}
+        }
+    }
 }
 
 /// Layout engine configuration for different diagram types.
 ///
-/// Controls which [`LayoutEngine`] variant is used for each diagram type.
-#[derive(Debug, Default, Clone, Deserialize)]
+/// Controls which [`LayoutEngine`] variant is used for each diagram type and
+/// the spacing the engines apply. Unset spacing keys take their value from the
+/// preset of the [`Density`]; a diagram's `density` attribute selects the
+/// preset in place of this configuration's.
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
 pub struct LayoutConfig {
     /// Default [`LayoutEngine`] for component diagrams.
     #[serde(default)]
@@ -39,6 +233,49 @@ pub struct LayoutConfig {
     /// Default [`LayoutEngine`] for sequence diagrams.
     #[serde(default)]
     sequence: LayoutEngine,
+
+    /// Whether spacing, padding, and margins are minimized; shorthand for
+    /// the compact density.
+    #[serde(default)]
+    compact: bool,
+
+    /// Spacing preset; comfortable by default, or compact when `compact` is
+    /// enabled.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_density",
+        serialize_with = "serialize_density",
+        skip_serializing_if = "Option::is_none"
+    )]
+    density: Option<Density>,
+
+    /// Padding inside shapes; 35 by default, 10 when compact.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    padding: Option<f32>,
+
+    /// Spacing between components and between sequence participants; 50 by
+    /// default, 20 when compact.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    spacing: Option<f32>,
+
+    /// Vertical spacing between the events of sequence diagrams; 15 by
+    /// default, 5 when compact.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    event_padding: Option<f32>,
+
+    /// Margin around the rendered content; 50 by default, 5 when compact.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    margin: Option<f32>,
+
+    /// Default spacing between ranks of layered layouts; falls back to
+    /// `spacing`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rank_spacing: Option<f32>,
+
+    /// Default spacing between elements within a rank of layered layouts;
+    /// falls back to `spacing`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    intra_rank_spacing: Option<f32>,
 }
 
 impl LayoutConfig {
@@ -52,9 +289,69 @@ impl LayoutConfig {
         Self {
             component,
             sequence,
+            compact: false,
+            density: None,
+            padding: None,
+            spacing: None,
+            event_padding: None,
+            margin: None,
+            rank_spacing: None,
+            intra_rank_spacing: None,
         }
     }
 
+    /// Sets the padding inside shapes.
+    pub fn with_padding(mut self, padding: f32) -> Self {
+        self.padding = Some(padding);
+        self
+    }
+
+    /// Sets the spacing between components and between sequence participants.
+    pub fn with_spacing(mut self, spacing: f32) -> Self {
+        self.spacing = Some(spacing);
+        self
+    }
+
+    /// Sets the vertical spacing between the events of sequence diagrams.
+    pub fn with_event_padding(mut self, padding: f32) -> Self {
+        self.event_padding = Some(padding);
+        self
+    }
+
+    /// Sets the margin around the rendered content.
+    pub fn with_margin(mut self, margin: f32) -> Self {
+        self.margin = Some(margin);
+        self
+    }
+
+    /// Sets the default spacing between ranks of layered layouts.
+    pub fn with_rank_spacing(mut self, spacing: f32) -> Self {
+        self.rank_spacing = Some(spacing);
+        self
+    }
+
+    /// Sets the default spacing between elements within a rank of layered
+    /// layouts.
+    pub fn with_intra_rank_spacing(mut self, spacing: f32) -> Self {
+        self.intra_rank_spacing = Some(spacing);
+        self
+    }
+
+    /// Sets whether spacing, padding, and margins are minimized.
+    ///
+    /// Compact mode produces the smallest reasonable output, for example to
+    /// embed small diagrams inline.
+    pub fn with_compact(mut self, compact: bool) -> Self {
+        self.compact = compact;
+        self
+    }
+
+    /// Sets the spacing preset, taking precedence over `compact`.
+    pub fn with_density(mut self, density: Density) -> Self {
+        self.density = Some(density);
+        self
+    }
+
     /// Returns the [`LayoutEngine`] for component diagrams.
     pub fn component(&self) -> LayoutEngine {
         self.component
@@ -64,4 +361,776 @@ impl LayoutConfig {
     pub fn sequence(&self) -> LayoutEngine {
         self.sequence
     }
+
+    /// Returns the padding inside shapes, if set.
+    pub fn padding(&self) -> Option<f32> {
+        self.padding
+    }
+
+    /// Returns the spacing between components and between sequence
+    /// participants, if set.
+    pub fn spacing(&self) -> Option<f32> {
+        self.spacing
+    }
+
+    /// Returns the vertical spacing between the events of sequence diagrams,
+    /// if set.
+    pub fn event_padding(&self) -> Option<f32> {
+        self.event_padding
+    }
+
+    /// Returns the margin around the rendered content, if set.
+    pub fn margin(&self) -> Option<f32> {
+        self.margin
+    }
+
+    /// Returns the default spacing between ranks of layered layouts, if set.
+    pub fn rank_spacing(&self) -> Option<f32> {
+        self.rank_spacing
+    }
+
+    /// Returns the default spacing between elements within a rank of layered
+    /// layouts, if set.
+    pub fn intra_rank_spacing(&self) -> Option<f32> {
+        self.intra_rank_spacing
+    }
+
+    /// Returns `true` if spacing, padding, and margins are minimized.
+    pub fn compact(&self) -> bool {
+        self.compact
+    }
+
+    /// Returns the spacing preset: the configured density, or the compact
+    /// density when only `compact` is enabled.
+    pub fn density(&self) -> Density {
+        self.density.unwrap_or(if self.compact {
+            Density::Compact
+        } else {
+            Density::Comfortable
+        })
+    }
+
+    /// Returns a copy with the density and the unset spacing keys filled in
+    /// from the preset.
+    fn effective(&self) -> Self {
+        let preset = SpacingPreset::for_config(self);
+        Self {
+            density: Some(self.density()),
+            padding: Some(preset.padding),
+            spacing: Some(preset.spacing),
+            event_padding: Some(preset.event_padding),
+            margin: Some(preset.margin),
+            ..self.clone()
+        }
+    }
+}
+
+/// Rendering options applied when exporting a laid-out diagram.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ExportConfig {
+    /// Whether relations are drawn on top of components (`true`, the default)
+    /// or behind them (`false`).
+    relations_on_top: bool,
+}
+
+impl ExportConfig {
+    /// Sets whether relations are drawn on top of components.
+    pub fn with_relations_on_top(mut self, relations_on_top: bool) -> Self {
+        self.relations_on_top = relations_on_top;
+        self
+    }
+
+    /// Returns `true` if relations are drawn on top of components.
+    pub fn relations_on_top(&self) -> bool {
+        self.relations_on_top
+    }
+}
+
+impl Default for ExportConfig {
+    fn default() -> Self {
+        Self {
+            relations_on_top: true,
+        }
+    }
+}
+
+/// Defaults of the diagram attributes that control elaboration.
+///
+/// Each key applies to diagrams that do not set the matching attribute.
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ParseConfig {
+    /// How relation endpoints that are not declared are treated; the
+    /// default of the `implicit_nodes` diagram attribute.
+    implicit_nodes: ImplicitNodes,
+
+    /// Type of implicit components, `Rectangle` when unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    implicit_node_type: Option<String>,
+
+    /// Number of the first message of sequence diagrams; the default of the
+    /// `autonumber` diagram attribute.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    autonumber: Option<usize>,
+}
+
+impl ParseConfig {
+    /// Sets how relation endpoints that are not declared are treated.
+    pub fn with_implicit_nodes(mut self, implicit_nodes: ImplicitNodes) -> Self {
+        self.implicit_nodes = implicit_nodes;
+        self
+    }
+
+    /// Sets the type of implicit components.
+    pub fn with_implicit_node_type(mut self, type_name: impl Into<String>) -> Self {
+        self.implicit_node_type = Some(type_name.into());
+        self
+    }
+
+    /// Sets the number of the first message of sequence diagrams, or `None`
+    /// to leave them unnumbered.
+    pub fn with_autonumber(mut self, start: Option<usize>) -> Self {
+        self.autonumber = start;
+        self
+    }
+
+    /// Returns how relation endpoints that are not declared are treated.
+    pub fn implicit_nodes(&self) -> ImplicitNodes {
+        self.implicit_nodes
+    }
+
+    /// Returns the type of implicit components, if set.
+    pub fn implicit_node_type(&self) -> Option<&str> {
+        self.implicit_node_type.as_deref()
+    }
+
+    /// Returns the number of the first message of sequence diagrams, if set.
+    pub fn autonumber(&self) -> Option<usize> {
+        self.autonumber
+    }
+}
+
+/// Built-in color theme selected by the `theme` key of [`StyleConfig`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemeName {
+    /// Dark shapes and text on a transparent background.
+    #[default]
+    Light,
+    /// Light shapes and text on a dark background.
+    Dark,
+}
+
+/// Default colors, font and minimum shape size of rendered diagrams.
+///
+/// Starts from the built-in [`Theme`] named by `theme`; each color, font or size key
+/// that is set replaces the matching theme value. Attributes written in a
+/// diagram still take precedence over these defaults.
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct StyleConfig {
+    /// Built-in theme the colors start from.
+    theme: ThemeName,
+
+    /// Canvas color behind the diagram.
+    #[serde(
+        deserialize_with = "deserialize_color",
+        serialize_with = "serialize_color",
+        skip_serializing_if = "Option::is_none"
+    )]
+    background_color: Option<Color>,
+
+    /// Fill color of icon shapes, activation boxes and fragment labels.
+    #[serde(
+        deserialize_with = "deserialize_color",
+        serialize_with = "serialize_color",
+        skip_serializing_if = "Option::is_none"
+    )]
+    fill_color: Option<Color>,
+
+    /// Color of outlines and lines.
+    #[serde(
+        deserialize_with = "deserialize_color",
+        serialize_with = "serialize_color",
+        skip_serializing_if = "Option::is_none"
+    )]
+    stroke_color: Option<Color>,
+
+    /// Color of text.
+    #[serde(
+        deserialize_with = "deserialize_color",
+        serialize_with = "serialize_color",
+        skip_serializing_if = "Option::is_none"
+    )]
+    text_color: Option<Color>,
+
+    /// Background color behind relation labels, `Some(None)` for `"none"`.
+    #[serde(
+        deserialize_with = "deserialize_label_background",
+        serialize_with = "serialize_label_background",
+        skip_serializing_if = "Option::is_none"
+    )]
+    label_background: Option<Option<Color>>,
+
+    /// Font family of component labels, relation labels and notes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    font_family: Option<String>,
+
+    /// Font size, in points, of component labels, relation labels and notes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    font_size: Option<NonZeroU16>,
+
+    /// Font file embedded into SVG output under the font family.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    font_path: Option<PathBuf>,
+
+    /// Minimum width of rectangles, ovals and components.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    min_width: Option<f32>,
+
+    /// Minimum height of rectangles, ovals and components.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    min_height: Option<f32>,
+
+    /// Font and color of relation labels, in place of the shared text keys.
+    #[serde(skip_serializing_if = "RelationTextConfig::is_empty")]
+    relation_text: RelationTextConfig,
+}
+
+impl StyleConfig {
+    /// Creates a [`StyleConfig`] that uses the dark theme without overrides.
+    pub fn dark_default() -> Self {
+        Self {
+            theme: ThemeName::Dark,
+            ..Self::default()
+        }
+    }
+
+    /// Sets the canvas color behind the diagram.
+    pub fn with_background_color(mut self, color: Color) -> Self {
+        self.background_color = Some(color);
+        self
+    }
+
+    /// Sets the fill color of shapes drawn with a fill by default.
+    pub fn with_fill_color(mut self, color: Color) -> Self {
+        self.fill_color = Some(color);
+        self
+    }
+
+    /// Sets the color of outlines and lines.
+    pub fn with_stroke_color(mut self, color: Color) -> Self {
+        self.stroke_color = Some(color);
+        self
+    }
+
+    /// Sets the color of text.
+    pub fn with_text_color(mut self, color: Color) -> Self {
+        self.text_color = Some(color);
+        self
+    }
+
+    /// Sets the background color behind relation labels, or `None` to draw
+    /// labels without a background.
+    pub fn with_label_background(mut self, color: Option<Color>) -> Self {
+        self.label_background = Some(color);
+        self
+    }
+
+    /// Sets the font family of text.
+    pub fn with_font_family(mut self, family: impl Into<String>) -> Self {
+        self.font_family = Some(family.into());
+        self
+    }
+
+    /// Sets the font size of text, in points.
+    pub fn with_font_size(mut self, size: NonZeroU16) -> Self {
+        self.font_size = Some(size);
+        self
+    }
+
+    /// Sets the font file to embed into SVG output.
+    pub fn with_font_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.font_path = Some(path.into());
+        self
+    }
+
+    /// Sets the minimum width of rectangles, ovals and components.
+    pub fn with_min_width(mut self, width: f32) -> Self {
+        self.min_width = Some(width);
+        self
+    }
+
+    /// Sets the minimum height of rectangles, ovals and components.
+    pub fn with_min_height(mut self, height: f32) -> Self {
+        self.min_height = Some(height);
+        self
+    }
+
+    /// Sets the font and color of relation labels.
+    pub fn with_relation_text(mut self, relation_text: RelationTextConfig) -> Self {
+        self.relation_text = relation_text;
+        self
+    }
+
+    /// Returns the font family of text.
+    ///
+    /// Falls back to the file stem of the font path, so an embedded font is
+    /// used without also naming its family.
+    pub fn font_family(&self) -> Option<&str> {
+        self.font_family.as_deref().or_else(|| {
+            self.font_path
+                .as_deref()
+                .and_then(Path::file_stem)
+                .and_then(|stem| stem.to_str())
+        })
+    }
+
+    /// Returns the font file to embed into SVG output, if any.
+    pub fn font_path(&self) -> Option<&Path> {
+        self.font_path.as_deref()
+    }
+
+    /// Returns the selected built-in theme.
+    pub fn theme_name(&self) -> ThemeName {
+        self.theme
+    }
+
+    /// Returns the [`Theme`] with every configured color, font and size
+    /// override applied.
+    pub fn theme(&self) -> Theme {
+        let mut theme = match self.theme {
+            ThemeName::Light => Theme::light(),
+            ThemeName::Dark => Theme::dark(),
+        };
+        if let Some(color) = self.background_color {
+            theme = theme.with_background(color);
+        }
+        if let Some(color) = self.fill_color {
+            theme = theme.with_fill(color);
+        }
+        if let Some(color) = self.stroke_color {
+            theme = theme.with_stroke(color);
+        }
+        if let Some(color) = self.text_color {
+            theme = theme.with_text(color);
+        }
+        if let Some(color) = self.label_background {
+            theme = theme.with_label_background(color);
+        }
+        if let Some(family) = self.font_family() {
+            theme = theme.with_font_family(family);
+        }
+        if let Some(size) = self.font_size {
+            theme = theme.with_font_size(size.get());
+        }
+        if let Some(width) = self.min_width {
+            theme = theme.with_min_width(width);
+        }
+        if let Some(height) = self.min_height {
+            theme = theme.with_min_height(height);
+        }
+        self.relation_text.apply(theme)
+    }
+
+    /// Returns a copy with every color set to its resolved theme color.
+    fn effective(&self) -> Self {
+        let theme = self.theme();
+        Self {
+            theme: self.theme,
+            background_color: theme.background(),
+            fill_color: Some(theme.fill()),
+            stroke_color: Some(theme.stroke()),
+            text_color: Some(theme.text()),
+            label_background: Some(theme.label_background()),
+            font_family: self.font_family.clone(),
+            font_size: self.font_size,
+            font_path: self.font_path.clone(),
+            min_width: self.min_width,
+            min_height: self.min_height,
+            relation_text: self.relation_text.clone(),
+        }
+    }
+}
+
+/// Font and color of relation labels, the `[style.relation_text]` table.
+///
+/// Each key that is set replaces the matching [`StyleConfig`] text setting
+/// for relation labels only; component labels and notes keep the shared
+/// values. A `text` attribute on a relation still takes precedence.
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct RelationTextConfig {
+    /// Font family of relation labels.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    font_family: Option<String>,
+
+    /// Font size, in points, of relation labels.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    font_size: Option<NonZeroU16>,
+
+    /// Whether relation labels are drawn upright or in italics.
+    #[serde(
+        deserialize_with = "deserialize_font_style",
+        serialize_with = "serialize_font_style",
+        skip_serializing_if = "Option::is_none"
+    )]
+    font_style: Option<FontStyle>,
+
+    /// Color of relation labels.
+    #[serde(
+        deserialize_with = "deserialize_color",
+        serialize_with = "serialize_color",
+        skip_serializing_if = "Option::is_none"
+    )]
+    color: Option<Color>,
+}
+
+impl RelationTextConfig {
+    /// Sets the font family of relation labels.
+    pub fn with_font_family(mut self, family: impl Into<String>) -> Self {
+        self.font_family = Some(family.into());
+        self
+    }
+
+    /// Sets the font size of relation labels, in points.
+    pub fn with_font_size(mut self, size: NonZeroU16) -> Self {
+        self.font_size = Some(size);
+        self
+    }
+
+    /// Sets whether relation labels are drawn upright or in italics.
+    pub fn with_font_style(mut self, style: FontStyle) -> Self {
+        self.font_style = Some(style);
+        self
+    }
+
+    /// Sets the color of relation labels.
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Returns `true` when no key is set.
+    fn is_empty(&self) -> bool {
+        self.font_family.is_none()
+            && self.font_size.is_none()
+            && self.font_style.is_none()
+            && self.color.is_none()
+    }
+
+    /// Returns `theme` with the relation label overrides that are set.
+    fn apply(&self, mut theme: Theme) -> Theme {
+        if let Some(family) = &self.font_family {
+            theme = theme.with_relation_font_family(family);
+        }
+        if let Some(size) = self.font_size {
+            theme = theme.with_relation_font_size(size.get());
+        }
+        if let Some(style) = self.font_style {
+            theme = theme.with_relation_font_style(style);
+        }
+        if let Some(color) = self.color {
+            theme = theme.with_relation_text(color);
+        }
+        theme
+    }
+}
+
+/// Deserializes an optional CSS color string into a [`Color`].
+fn deserialize_color<'de, D>(deserializer: D) -> Result<Option<Color>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let color = String::deserialize(deserializer)?;
+    Color::new(&color).map(Some).map_err(D::Error::custom)
+}
+
+/// Deserializes a label background, either a CSS color string or `"none"`.
+fn deserialize_label_background<'de, D>(deserializer: D) -> Result<Option<Option<Color>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let color = String::deserialize(deserializer)?;
+    if color == "none" {
+        return Ok(Some(None));
+    }
+    Color::new(&color)
+        .map(|color| Some(Some(color)))
+        .map_err(D::Error::custom)
+}
+
+/// Deserializes an optional density name into a [`Density`].
+fn deserialize_density<'de, D>(deserializer: D) -> Result<Option<Density>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let density = String::deserialize(deserializer)?;
+    density.parse().map(Some).map_err(|_| {
+        D::Error::custom(format!(
+            "invalid density `{density}`, expected `compact`, `comfortable` or `spacious`"
+        ))
+    })
+}
+
+/// Deserializes an optional font style name into a [`FontStyle`].
+fn deserialize_font_style<'de, D>(deserializer: D) -> Result<Option<FontStyle>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let style = String::deserialize(deserializer)?;
+    style.parse().map(Some).map_err(D::Error::custom)
+}
+
+/// Serializes an optional [`FontStyle`] as its name.
+fn serialize_font_style<S>(style: &Option<FontStyle>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match style {
+        Some(style) => serializer.serialize_str(style.to_svg_value()),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// Serializes an optional [`Density`] as its name.
+fn serialize_density<S>(density: &Option<Density>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match density {
+        Some(density) => serializer.collect_str(density),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// Serializes a label background as its CSS string, or `"none"`.
+fn serialize_label_background<S>(
+    color: &Option<Option<Color>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match color {
+        Some(Some(color)) => serializer.collect_str(color),
+        Some(None) => serializer.serialize_str("none"),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// Serializes an optional [`Color`] as its CSS string.
+fn serialize_color<S>(color: &Option<Color>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match color {
+        Some(color) => serializer.collect_str(color),
+        None => serializer.serialize_none(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Asserts that `schema` has a key for every listed field of `$ty`.
+    ///
+    /// The fields are destructured without `..`, so adding a field to the
+    /// struct fails to compile until it is listed here, and then fails the
+    /// test until [`AppConfig::schema`] sets it.
+    macro_rules! assert_schema_has_fields {
+        ($schema:expr, $ty:ident { $($field:ident),* $(,)? } $(, skip $skipped:ident)*) => {{
+            let _ = |value: &$ty| {
+                let $ty { $($field: _,)* $($skipped: _,)* } = value;
+            };
+            let schema: &toml::Table = $schema;
+            $(
+                assert!(
+                    schema.contains_key(stringify!($field)),
+                    "`{}` is missing from AppConfig::schema()",
+                    stringify!($field)
+                );
+            )*
+        }};
+    }
+
+    fn table<'a>(schema: &'a toml::Table, key: &str) -> &'a toml::Table {
+        schema
+            .get(key)
+            .and_then(toml::Value::as_table)
+            .unwrap_or_else(|| panic!("`{key}` is missing from AppConfig::schema()"))
+    }
+
+    #[test]
+    fn test_to_toml_string_round_trips() {
+        let config = AppConfig::new(
+            LayoutConfig::default()
+                .with_compact(true)
+                .with_spacing(30.0),
+        );
+        let toml = config.to_toml_string();
+
+        assert!(toml.contains("spacing = 30.0"), "{toml}");
+        assert!(toml.contains("margin = 5.0"), "{toml}");
+        assert!(toml.contains("stroke_color = "), "{toml}");
+
+        let table: toml::Table = toml.parse().unwrap();
+        assert!(AppConfig::unknown_keys(&table).is_empty());
+    }
+
+    #[test]
+    fn test_unknown_keys_accepts_optional_style_keys() {
+        let table: toml::Table = r#"
+            [style]
+            font_family = "Inter"
+            font_size = 12
+            font_path = "fonts/Inter.woff2"
+            min_width = 120.0
+            min_height = 40.0
+            label_background = "none"
+
+            [style.relation_text]
+            font_family = "Georgia"
+            font_size = 8
+            font_style = "italic"
+            color = "navy"
+
+            [parse]
+            implicit_nodes = "deny"
+            implicit_node_type = "Oval"
+            autonumber = 10
+        "#
+        .parse()
+        .unwrap();
+
+        assert!(AppConfig::unknown_keys(&table).is_empty());
+    }
+
+    #[test]
+    fn test_unknown_keys_reports_dotted_paths() {
+        let table: toml::Table = r#"
+            theme = "dark"
+
+            [layout]
+            compact = true
+            padingg = 4.0
+
+            [style]
+            stroke_color = "red"
+
+            [style.lifeline]
+            width = 2.0
+
+            [style.relation_text]
+            font_weight = "bold"
+        "#
+        .parse()
+        .unwrap();
+
+        assert_eq!(
+            AppConfig::unknown_keys(&table),
+            [
+                "layout.padingg",
+                "style.lifeline",
+                "style.relation_text.font_weight",
+                "theme"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_config_reads_keys() {
+        let config: AppConfig = toml::from_str(
+            r#"
+            [parse]
+            implicit_nodes = "enabled"
+            implicit_node_type = "Oval"
+            autonumber = 1
+            "#,
+        )
+        .expect("Failed to parse config");
+
+        assert_eq!(config.parse().implicit_nodes(), ImplicitNodes::Enabled);
+        assert_eq!(config.parse().implicit_node_type(), Some("Oval"));
+        assert_eq!(config.parse().autonumber(), Some(1));
+    }
+
+    #[test]
+    fn test_zero_font_size_is_rejected() {
+        let error = toml::from_str::<AppConfig>("[style]\nfont_size = 0\n").unwrap_err();
+        assert!(error.to_string().contains("nonzero"), "{error}");
+
+        let error =
+            toml::from_str::<AppConfig>("[style.relation_text]\nfont_size = 0\n").unwrap_err();
+        assert!(error.to_string().contains("nonzero"), "{error}");
+    }
+
+    #[test]
+    fn test_schema_has_every_field() {
+        let schema = toml::Table::try_from(AppConfig::schema()).expect("schema serializes");
+
+        assert_schema_has_fields!(
+            &schema,
+            AppConfig {
+                layout,
+                export,
+                style,
+                parse,
+                lint,
+            },
+            skip palette
+        );
+        assert_schema_has_fields!(
+            table(&schema, "layout"),
+            LayoutConfig {
+                component,
+                sequence,
+                compact,
+                density,
+                padding,
+                spacing,
+                event_padding,
+                margin,
+                rank_spacing,
+                intra_rank_spacing,
+            }
+        );
+        assert_schema_has_fields!(table(&schema, "export"), ExportConfig { relations_on_top });
+        assert_schema_has_fields!(
+            table(&schema, "style"),
+            StyleConfig {
+                theme,
+                background_color,
+                fill_color,
+                stroke_color,
+                text_color,
+                label_background,
+                font_family,
+                font_size,
+                font_path,
+                min_width,
+                min_height,
+                relation_text,
+            }
+        );
+        assert_schema_has_fields!(
+            table(table(&schema, "style"), "relation_text"),
+            RelationTextConfig {
+                font_family,
+                font_size,
+                font_style,
+                color,
+            }
+        );
+        assert_schema_has_fields!(
+            table(&schema, "parse"),
+            ParseConfig {
+                implicit_nodes,
+                implicit_node_type,
+                autonumber,
+            }
+        );
+    }
 }
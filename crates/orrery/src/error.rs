@@ -25,6 +25,11 @@ pub enum RenderError {
     /// An export/rendering error from the output backend.
     #[error("Export error: {0}")]
     Export(Box<dyn std::error::Error>),
+
+    /// An error loading a diagram from an input other than `.orr` source,
+    /// such as a serialized semantic diagram.
+    #[error("Input error: {0}")]
+    Input(Box<dyn std::error::Error>),
 }
 
 impl From<crate::export::Error> for RenderError {
@@ -15,12 +15,15 @@
 //!     ↓ layout
 //! Positioned Elements (LayeredLayout)
 //!     ↓ export (this module)
-//! Output File
+//! Output (any `io::Write`)
 //! ```
 //!
 //! # Available Backends
 //!
 //! - [`svg`] — SVG output via [`svg::SvgBuilder`] and [`svg::Svg`]
+//! - [`dot`] — Graphviz DOT output of a component diagram's structure. It
+//!   works on the semantic model, before layout, and does not implement
+//!   [`Exporter`].
 //!
 //! # Error Handling
 //!
@@ -30,9 +33,13 @@
 //!
 //! [`RenderError::Export`]: crate::RenderError::Export
 
+/// Graphviz DOT export backend.
+pub mod dot;
 /// SVG export backend.
 pub mod svg;
 
+use std::io::Write;
+
 use crate::layout::layer::LayeredLayout;
 
 /// Abstraction for diagram export backends.
@@ -42,7 +49,8 @@ use crate::layout::layer::LayeredLayout;
 ///
 /// See the [`svg`] module for the built-in SVG implementation.
 pub trait Exporter {
-    /// Exports a layered layout to the backend's output format.
+    /// Exports a layered layout to the backend's output format, streaming
+    /// the output into `writer`.
     ///
     /// A [`LayeredLayout`] contains positioned diagram elements organized into
     /// rendering layers. Each layer holds either component or sequence diagram
@@ -51,12 +59,17 @@ pub trait Exporter {
     /// # Arguments
     ///
     /// * `layout` - The positioned diagram layers to export.
+    /// * `writer` - The destination for the exported bytes.
     ///
     /// # Errors
     ///
     /// Returns [`Error::Render`] if the layout cannot be converted to the
     /// target format, or [`Error::Io`] if writing the output fails.
-    fn export_layered_layout(&mut self, layout: &LayeredLayout) -> Result<(), Error>;
+    fn export_layered_layout<W: Write>(
+        &mut self,
+        layout: &LayeredLayout,
+        writer: W,
+    ) -> Result<(), Error>;
 }
 
 /// Errors that can occur during diagram export.
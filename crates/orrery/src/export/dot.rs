@@ -0,0 +1,188 @@
+//! Graphviz DOT export of a component diagram's structure.
+//!
+//! Unlike the [`svg`](super::svg) backend, this export works on the semantic
+//! [`Diagram`] rather than on a laid-out diagram, so that Graphviz tooling can
+//! apply its own layout. Components become nodes labeled with their display
+//! text, components with nested content become `cluster_` subgraphs, groups
+//! become dashed `cluster_group_` subgraphs, and relations become edges that
+//! keep their label and direction. Orthogonal edge routing is exported as
+//! `splines=ortho`. Embedded diagrams are exported as plain nodes; notes are
+//! not exported.
+
+use std::{collections::HashSet, fmt::Write};
+
+use orrery_core::{
+    draw::{ArrowDirection, EdgeRouting},
+    identifier::Id,
+    semantic::{Block, Diagram, DiagramKind, Element, Relation},
+};
+
+use super::Error;
+
+/// Renders `diagram` as a Graphviz `digraph`.
+///
+/// # Errors
+///
+/// Returns [`Error::Render`] if `diagram` is not a component diagram.
+pub(crate) fn render(diagram: &Diagram) -> Result<String, Error> {
+    if diagram.kind() != DiagramKind::Component {
+        return Err(Error::Render(format!(
+            "DOT export only supports component diagrams, not {} diagrams",
+            diagram.kind()
+        )));
+    }
+
+    let mut containers = HashSet::new();
+    collect_containers(diagram.scope().elements(), &mut containers);
+
+    let mut output = String::from("digraph {\n    compound=true;\n");
+    if diagram.definition().edge_routing() == EdgeRouting::Orthogonal {
+        output.push_str("    splines=ortho;\n");
+    }
+    let mut groups = 0;
+    write_elements(
+        &mut output,
+        diagram.scope().elements(),
+        &containers,
+        &mut groups,
+        1,
+    );
+    output.push_str("}\n");
+    Ok(output)
+}
+
+/// Inserts the ids of the components in `elements` that have a nested scope.
+fn collect_containers(elements: &[Element], containers: &mut HashSet<Id>) {
+    for element in elements {
+        if let Element::Node(node) = element
+            && let Block::Scope(scope) = node.block()
+        {
+            containers.insert(node.id());
+            collect_containers(scope.elements(), containers);
+        }
+    }
+}
+
+fn write_elements(
+    output: &mut String,
+    elements: &[Element],
+    containers: &HashSet<Id>,
+    groups: &mut usize,
+    depth: usize,
+) {
+    let indent = "    ".repeat(depth);
+    for element in elements {
+        match element {
+            Element::Node(node) => {
+                let id = node.id().as_str();
+                let label = quote(node.display_text());
+                if let Block::Scope(scope) = node.block() {
+                    // Edges cannot end at a cluster, so each cluster holds an
+                    // invisible anchor node that relations to the container
+                    // attach to.
+                    writeln!(output, "{indent}subgraph {} {{", cluster_name(node.id()))
+                        .expect("Writing to String buffer is infallible");
+                    writeln!(output, "{indent}    label={label};")
+                        .expect("Writing to String buffer is infallible");
+                    writeln!(
+                        output,
+                        "{indent}    {} [shape=point, style=invis];",
+                        quote(id)
+                    )
+                    .expect("Writing to String buffer is infallible");
+                    write_elements(output, scope.elements(), containers, groups, depth + 1);
+                    writeln!(output, "{indent}}}").expect("Writing to String buffer is infallible");
+                } else {
+                    writeln!(output, "{indent}{} [label={label}];", quote(id))
+                        .expect("Writing to String buffer is infallible");
+                }
+            }
+            Element::Relation(relation) => {
+                write_relation(output, relation, containers, &indent);
+            }
+            Element::Highlight(highlight) => {
+                write_elements(output, highlight.elements(), containers, groups, depth);
+            }
+            Element::Group(group) => {
+                writeln!(output, "{indent}subgraph \"cluster_group_{groups}\" {{")
+                    .expect("Writing to String buffer is infallible");
+                *groups += 1;
+                writeln!(output, "{indent}    label={};", quote(group.label()))
+                    .expect("Writing to String buffer is infallible");
+                writeln!(output, "{indent}    style=dashed;")
+                    .expect("Writing to String buffer is infallible");
+                write_elements(output, group.elements(), containers, groups, depth + 1);
+                writeln!(output, "{indent}}}").expect("Writing to String buffer is infallible");
+            }
+            Element::Fragment(fragment) => {
+                for section in fragment.sections() {
+                    write_elements(output, section.elements(), containers, groups, depth);
+                }
+            }
+            Element::Activate(_) | Element::Deactivate(_) | Element::Note(_) => {}
+        }
+    }
+}
+
+fn write_relation(
+    output: &mut String,
+    relation: &Relation,
+    containers: &HashSet<Id>,
+    indent: &str,
+) {
+    let mut attributes = Vec::new();
+    if let Some(label) = relation.label() {
+        attributes.push(format!("label={}", quote(label)));
+    }
+    match relation.arrow_direction() {
+        ArrowDirection::Forward => {}
+        ArrowDirection::Backward => attributes.push("dir=back".to_string()),
+        ArrowDirection::Bidirectional => attributes.push("dir=both".to_string()),
+        ArrowDirection::Plain => attributes.push("dir=none".to_string()),
+        ArrowDirection::AsyncForward => attributes.push("arrowhead=vee".to_string()),
+        ArrowDirection::AsyncBackward => {
+            attributes.push("dir=back".to_string());
+            attributes.push("arrowtail=vee".to_string());
+        }
+    }
+    if containers.contains(&relation.source()) {
+        attributes.push(format!("ltail={}", cluster_name(relation.source())));
+    }
+    if containers.contains(&relation.target()) {
+        attributes.push(format!("lhead={}", cluster_name(relation.target())));
+    }
+
+    write!(
+        output,
+        "{indent}{} -> {}",
+        quote(relation.source().as_str()),
+        quote(relation.target().as_str())
+    )
+    .expect("Writing to String buffer is infallible");
+    if !attributes.is_empty() {
+        write!(output, " [{}]", attributes.join(", "))
+            .expect("Writing to String buffer is infallible");
+    }
+    output.push_str(";\n");
+}
+
+/// Returns the quoted name of the cluster subgraph of container `id`.
+fn cluster_name(id: Id) -> String {
+    quote(&format!("cluster_{id}"))
+}
+
+/// Returns `value` as a DOT double-quoted string.
+fn quote(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
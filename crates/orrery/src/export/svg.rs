@@ -1,53 +1,110 @@
 //! SVG export backend for Orrery diagrams.
 //!
 //! This module provides `SvgBuilder` for configuring and `Svg` for rendering
-//! laid-out diagrams to SVG files. It delegates to submodules for
-//! diagram-kind-specific rendering.
+//! laid-out diagrams to SVG. The output is streamed into any [`Write`]
+//! implementation. It delegates to submodules for diagram-kind-specific
+//! rendering.
 
+mod base64;
+mod canvas_text;
 mod component;
+mod font;
+mod image;
 mod layer;
 mod sequence;
 
-use std::{fs::File, io::Write};
+use std::{
+    collections::{BTreeMap, HashMap},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    rc::Rc,
+};
 
-use log::{debug, error, info};
-use svg::{Document, node::element::Rectangle};
+use log::{debug, error, info, warn};
+use svg::{
+    Document,
+    node::element::{Definitions, Rectangle},
+};
 
 use orrery_core::{
     color::Color,
-    draw::ArrowWithTextDrawer,
-    geometry::{Insets, Size},
-    semantic::Diagram,
+    draw::{
+        ArrowWithTextDrawer, CanvasFit, PositionedDrawable, ShadowDefinition, ShapeWithText,
+        TextMeasurer, with_text_measurer,
+    },
+    geometry::{Bounds, Insets, Point, Size},
+    semantic::{Diagram, DiagramKind},
 };
 
+use canvas_text::CanvasText;
+use font::FontFace;
+
 use crate::{
     export::{Error, Exporter},
     layout::layer::LayeredLayout,
 };
 
+/// Reads an asset file, such as a font or an icon, to inline into the SVG.
+#[cfg(feature = "std-fs")]
+pub(crate) fn read_asset(path: &Path) -> io::Result<Vec<u8>> {
+    std::fs::read(path)
+}
+
+/// Without the `std-fs` feature there is no file system to read assets from,
+/// so only `data:` URIs can be embedded.
+#[cfg(not(feature = "std-fs"))]
+pub(crate) fn read_asset(path: &Path) -> io::Result<Vec<u8>> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!(
+            "cannot read `{}`, reading files requires the `std-fs` feature",
+            path.display()
+        ),
+    ))
+}
+
 /// SVG exporter builder to configure and build the SVG exporter.
 pub struct SvgBuilder<'a> {
-    file_name: String,
     diagram: Option<&'a Diagram>,
+    relations_on_top: bool,
+    margin: f32,
+    font_face: Option<(String, PathBuf)>,
+    text_measurer: Option<Rc<dyn TextMeasurer>>,
 }
 
 /// Base SVG exporter structure with common properties and methods.
 pub struct Svg {
-    file_name: String,
     background_color: Option<Color>,
+    relations_on_top: bool,
+    padding: Insets,
+    width: Option<f32>,
+    height: Option<f32>,
+    fit: CanvasFit,
+    title: Option<CanvasText>,
+    caption: Option<CanvasText>,
+    /// Accessible name of the document, emitted as its `<title>`.
+    accessible_name: String,
+    /// Whether any element is wrapped in an `<a>` link, which needs the
+    /// `xlink` namespace declared on the document.
+    has_links: bool,
+    font_face: Option<FontFace>,
+    /// Shadow filters in use, keyed by their SVG id.
+    shadow_filters: BTreeMap<String, ShadowDefinition>,
+    /// Inlined `href`s of the images in use, or `None` for images that failed to load.
+    image_hrefs: HashMap<String, Option<String>>,
     arrow_with_text_drawer: ArrowWithTextDrawer, // NOTE: Does it need to be in this level or should it be in the SvgBuilder level?
+    text_measurer: Option<Rc<dyn TextMeasurer>>,
 }
 
 impl<'a> SvgBuilder<'a> {
-    /// Creates a new builder with the given output file name.
-    ///
-    /// # Arguments
-    ///
-    /// * `file_name` - The destination file path for the SVG output.
-    pub fn new(file_name: &str) -> Self {
+    /// Creates a new builder with default options.
+    pub fn new() -> Self {
         Self {
-            file_name: file_name.to_string(),
             diagram: None,
+            relations_on_top: true,
+            margin: 50.0,
+            font_face: None,
+            text_measurer: None,
         }
     }
 
@@ -61,26 +118,128 @@ impl<'a> SvgBuilder<'a> {
         self
     }
 
+    /// Sets whether relations are drawn on top of components or behind them.
+    ///
+    /// # Arguments
+    ///
+    /// * `relations_on_top` - Draw the relation layer above the component layer.
+    pub fn with_relations_on_top(mut self, relations_on_top: bool) -> Self {
+        self.relations_on_top = relations_on_top;
+        self
+    }
+
+    /// Sets the margin around the diagram content.
+    ///
+    /// A diagram's `canvas_padding` takes precedence over the margin.
+    ///
+    /// # Arguments
+    ///
+    /// * `margin` - The margin on each side of the content.
+    pub fn with_margin(mut self, margin: f32) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    /// Embeds a font file into the SVG as an `@font-face` rule.
+    ///
+    /// The file is read when the exporter is built. If it cannot be read, a
+    /// warning is logged and the SVG only references `family` by name.
+    ///
+    /// # Arguments
+    ///
+    /// * `family` - The font family the embedded font is registered under.
+    /// * `path` - The TTF, OTF, WOFF or WOFF2 file to embed.
+    pub fn with_font_face(mut self, family: impl Into<String>, path: impl Into<PathBuf>) -> Self {
+        self.font_face = Some((family.into(), path.into()));
+        self
+    }
+
+    /// Sets the measurer that sizes text while rendering.
+    ///
+    /// It must be the measurer the layout was calculated with, so labels are
+    /// placed where the layout made room for them. Defaults to the shared
+    /// system fonts measurer.
+    pub fn with_text_measurer(mut self, measurer: Rc<dyn TextMeasurer>) -> Self {
+        self.text_measurer = Some(measurer);
+        self
+    }
+
     /// Builds the SVG exporter with the configured options.
     pub fn build(self) -> Svg {
+        // A fully transparent canvas needs no background rectangle
         let background_color = self
             .diagram
-            .and_then(|diagram| diagram.definition().canvas_color());
+            .and_then(|diagram| diagram.definition().canvas_color())
+            .filter(|color| color.alpha() > 0.0);
+        let padding = self
+            .diagram
+            .and_then(|diagram| diagram.definition().canvas_padding())
+            .unwrap_or_else(|| Insets::uniform(self.margin));
+        let definition = self.diagram.map(|diagram| diagram.definition());
+        let width = definition.and_then(|definition| definition.width());
+        let height = definition.and_then(|definition| definition.height());
+        let fit = definition
+            .map(|definition| definition.fit())
+            .unwrap_or_default();
+        let title = definition.and_then(|definition| {
+            CanvasText::new(definition.title()?, Rc::clone(definition.title_text()))
+        });
+        let caption = definition.and_then(|definition| {
+            CanvasText::new(definition.caption()?, Rc::clone(definition.caption_text()))
+        });
+        // Untitled diagrams are still named after their kind for screen readers
+        let accessible_name = match (&title, self.diagram.map(|diagram| diagram.kind())) {
+            (Some(title), _) => title.content().to_string(),
+            (None, Some(DiagramKind::Component)) => "Component diagram".to_string(),
+            (None, Some(DiagramKind::Sequence)) => "Sequence diagram".to_string(),
+            (None, None) => "Diagram".to_string(),
+        };
+
+        let font_face =
+            self.font_face
+                .and_then(|(family, path)| match FontFace::load(&family, &path) {
+                    Ok(font_face) => Some(font_face),
+                    Err(err) => {
+                        warn!(
+                            path:? = path, err:err;
+                            "Failed to embed font, falling back to the `{family}` font family"
+                        );
+                        None
+                    }
+                });
 
         let arrow_with_text_drawer = ArrowWithTextDrawer::new();
 
         Svg {
-            file_name: self.file_name,
             background_color,
+            relations_on_top: self.relations_on_top,
+            padding,
+            width,
+            height,
+            fit,
+            title,
+            caption,
+            accessible_name,
+            has_links: false,
+            font_face,
+            shadow_filters: BTreeMap::new(),
+            image_hrefs: HashMap::new(),
             arrow_with_text_drawer,
+            text_measurer: self.text_measurer,
         }
     }
 }
 
+impl Default for SvgBuilder<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Svg {
     /// Calculates the optimal size for the SVG based on content dimensions.
     ///
-    /// Adds a small margin around the content.
+    /// Adds the canvas padding around the content.
     ///
     /// # Arguments
     ///
@@ -88,11 +247,9 @@ impl Svg {
     ///
     /// # Returns
     ///
-    /// A [`Size`] that includes uniform margin padding around the content.
+    /// A [`Size`] that includes the canvas padding around the content.
     pub fn calculate_svg_dimensions(&self, content_size: Size) -> Size {
-        // Add some margin to the content size
-        let margin: f32 = 50.0;
-        let svg_size = content_size.add_padding(Insets::uniform(margin));
+        let svg_size = content_size.add_padding(self.padding);
 
         debug!(
             "Final SVG dimensions: {}x{}",
@@ -103,22 +260,58 @@ impl Svg {
         svg_size
     }
 
+    /// Calculates the outer size of the SVG and the region of the canvas its
+    /// `viewBox` shows.
+    ///
+    /// Without an explicit width or height, the SVG is sized to the canvas. A
+    /// single explicit dimension derives the other from the canvas aspect
+    /// ratio. With [`CanvasFit::Contain`] the `viewBox` shows the whole canvas,
+    /// scaled into the outer size; with [`CanvasFit::None`] it keeps the
+    /// canvas scale and is centered on it, cropping or extending it.
+    ///
+    /// # Arguments
+    ///
+    /// * `canvas_size` - The size of the content including the canvas padding.
+    pub fn calculate_viewport(&self, canvas_size: Size) -> (Size, Bounds) {
+        let aspect_ratio = if canvas_size.height() > 0.0 {
+            canvas_size.width() / canvas_size.height()
+        } else {
+            1.0
+        };
+        let outer_size = match (self.width, self.height) {
+            (Some(width), Some(height)) => Size::new(width, height),
+            (Some(width), None) => Size::new(width, width / aspect_ratio),
+            (None, Some(height)) => Size::new(height * aspect_ratio, height),
+            (None, None) => canvas_size,
+        };
+
+        let view_box = match self.fit {
+            CanvasFit::Contain => Bounds::new_from_top_left(Point::zero(), canvas_size),
+            CanvasFit::None => Bounds::new_from_center(
+                Point::new(canvas_size.width() / 2.0, canvas_size.height() / 2.0),
+                outer_size,
+            ),
+        };
+        (outer_size, view_box)
+    }
+
     /// Adds a background color rectangle to an SVG document if one is configured.
     ///
     /// # Arguments
     ///
     /// * `doc` - The SVG document to add the background to.
-    /// * `size` - The dimensions of the background rectangle.
+    /// * `bounds` - The region the background rectangle covers.
     ///
     /// # Returns
     ///
     /// The document, with a background rectangle prepended when a color is set.
-    pub fn add_background(&self, mut doc: Document, size: Size) -> Document {
+    pub fn add_background(&self, mut doc: Document, bounds: Bounds) -> Document {
         // Add background if specified in the SVG exporter
         if let Some(bg_color) = &self.background_color {
+            let size = bounds.to_size();
             let bg = Rectangle::new()
-                .set("x", 0)
-                .set("y", 0)
+                .set("x", bounds.min_x())
+                .set("y", bounds.min_y())
                 .set("width", size.width())
                 .set("height", size.height())
                 .set("fill", bg_color.to_string())
@@ -129,29 +322,75 @@ impl Svg {
         doc
     }
 
-    /// Writes an SVG document to the configured output file.
+    /// Adds the `@font-face` rule of the embedded font, if one is configured.
     ///
     /// # Arguments
     ///
-    /// * `doc` - The completed SVG document to persist.
+    /// * `doc` - The SVG document to add the style block to.
+    pub fn add_font_face(&self, doc: Document) -> Document {
+        match &self.font_face {
+            Some(font_face) => doc.add(font_face.to_style()),
+            None => doc,
+        }
+    }
+
+    /// Records the shadow filter used by `shape`, if any, so that it is
+    /// defined once in the document.
+    fn register_shadow(&mut self, shape: &ShapeWithText<'_>) {
+        if let Some(shadow) = shape.shadow() {
+            self.shadow_filters
+                .entry(shadow.filter_id())
+                .or_insert_with(|| shadow.clone());
+        }
+    }
+
+    /// Renders the image drawn inside `shape`, if any.
+    ///
+    /// Each image source is loaded once. A source that cannot be loaded is
+    /// logged and skipped instead of failing the export.
+    fn render_image(
+        &mut self,
+        shape: &PositionedDrawable<ShapeWithText<'_>>,
+    ) -> Option<Box<dyn svg::Node>> {
+        let image = shape.inner().image()?;
+        let bounds = shape.inner().image_bounds(shape.position())?;
+        let href = self
+            .image_hrefs
+            .entry(image.source().to_string())
+            .or_insert_with(|| match image::load_href(image.source()) {
+                Ok(href) => Some(href),
+                Err(err) => {
+                    warn!(source = image.source(), err:err; "Failed to embed icon, skipping it");
+                    None
+                }
+            })
+            .as_deref()?;
+        Some(Box::new(image::to_image(href, bounds)))
+    }
+
+    /// Generates SVG filter definitions for all collected shadows.
+    fn draw_shadow_filters(&self) -> Definitions {
+        self.shadow_filters
+            .values()
+            .fold(Definitions::new(), |defs, shadow| {
+                defs.add(shadow.to_filter())
+            })
+    }
+
+    /// Streams an SVG document into `writer`.
+    ///
+    /// # Arguments
+    ///
+    /// * `doc` - The completed SVG document to write.
+    /// * `writer` - The destination for the SVG bytes.
     ///
     /// # Errors
     ///
-    /// Returns [`Error::Io`] if file creation or writing fails.
-    pub fn write_document(&self, doc: Document) -> Result<(), Error> {
-        info!(file_name = self.file_name; "Creating SVG file");
-        // Create the output file
-        let f = match File::create(&self.file_name) {
-            Ok(file) => file,
-            Err(err) => {
-                error!(file_name=self.file_name, err:err; "Failed to create SVG file");
-                return Err(Error::Io(err));
-            }
-        };
-
-        // Write the SVG content to the file
-        if let Err(err) = write!(&f, "{doc}") {
-            error!(file_name=self.file_name, err:err; "Failed to write SVG content");
+    /// Returns [`Error::Io`] if writing fails.
+    pub fn write_document<W: Write>(&self, doc: Document, writer: W) -> Result<(), Error> {
+        info!("Writing SVG document");
+        if let Err(err) = svg::write(writer, &doc) {
+            error!(err:err; "Failed to write SVG content");
             return Err(Error::Io(err));
         }
 
@@ -161,10 +400,17 @@ impl Svg {
 
 // Implementation of Exporter trait for SVG
 impl Exporter for Svg {
-    fn export_layered_layout(&mut self, layout: &LayeredLayout) -> Result<(), Error> {
-        let doc = self.render_layered_layout(layout);
+    fn export_layered_layout<W: Write>(
+        &mut self,
+        layout: &LayeredLayout,
+        writer: W,
+    ) -> Result<(), Error> {
+        let doc = match self.text_measurer.clone() {
+            Some(measurer) => with_text_measurer(measurer, || self.render_layered_layout(layout)),
+            None => self.render_layered_layout(layout),
+        };
         debug!("SVG document rendered for layered layout");
 
-        self.write_document(doc)
+        self.write_document(doc, writer)
     }
 }
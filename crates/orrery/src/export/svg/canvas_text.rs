@@ -0,0 +1,87 @@
+//! Title and caption text drawn on the canvas around the diagram content.
+//!
+//! A [`CanvasText`] is laid out by the exporter rather than the layout
+//! engines: the canvas grows to fit it above or below the content, and its
+//! content doubles as the accessible name or `<desc>` of the document.
+
+use std::rc::Rc;
+
+use svg::node::element::Description;
+
+use orrery_core::{
+    draw::{Drawable, Text, TextDefinition},
+    geometry::{Point, Size},
+};
+
+/// Space between a title or caption and the diagram content.
+pub const GAP: f32 = 12.0;
+
+/// A line of text, such as the diagram title, centered on the canvas.
+#[derive(Debug)]
+pub struct CanvasText {
+    content: String,
+    definition: Rc<TextDefinition>,
+}
+
+impl CanvasText {
+    /// Creates the text, or `None` when `content` is empty.
+    pub fn new(content: &str, definition: Rc<TextDefinition>) -> Option<Self> {
+        (!content.is_empty()).then(|| Self {
+            content: content.to_string(),
+            definition,
+        })
+    }
+
+    /// Returns the content of the text.
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    /// Returns the size of the text.
+    pub fn size(&self) -> Size {
+        self.text().size()
+    }
+
+    /// Returns the height the text takes on the canvas, including the gap
+    /// that separates it from the content.
+    pub fn block_height(&self) -> f32 {
+        self.size().height() + GAP
+    }
+
+    /// Renders the text centered on `center`.
+    pub fn render(&self, center: Point) -> Vec<Box<dyn svg::Node>> {
+        self.text().render_to_layers(center).render()
+    }
+
+    /// Returns the `<desc>` element holding the text.
+    pub fn to_description(&self) -> Description {
+        Description::new().add(svg::node::Text::new(self.content.as_str()))
+    }
+
+    fn text(&self) -> Text<'_> {
+        Text::new(&self.definition, &self.content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_skips_empty_content() {
+        let definition = Rc::new(TextDefinition::default());
+
+        assert!(CanvasText::new("", Rc::clone(&definition)).is_none());
+        assert!(CanvasText::new("Checkout", definition).is_some());
+    }
+
+    #[test]
+    fn test_description_escapes_content() {
+        let text = CanvasText::new("A < B & C", Rc::new(TextDefinition::default())).unwrap();
+
+        assert_eq!(
+            text.to_description().to_string(),
+            "<desc>A &lt; B &amp; C</desc>"
+        );
+    }
+}
@@ -1,49 +1,97 @@
 //! SVG rendering for component diagrams.
 
-use orrery_core::{
-    draw::{LayeredOutput, PositionedArrowWithText},
-    geometry::Bounds,
+use svg::node::element as svg_element;
+
+use orrery_core::draw::{
+    Group, LayeredOutput, LinkDefinition, PositionedArrowWithText, PositionedDrawable, RenderLayer,
 };
 
 use super::Svg;
-use crate::layout::{
-    component::{Component, Layout},
-    layer::ContentStack,
-};
+use crate::layout::component::Component;
 
 impl Svg {
     /// Renders a positioned component to layered SVG output.
-    pub fn render_component(&self, component: &Component) -> LayeredOutput {
-        component.drawable().render_to_layers()
+    pub fn render_component(&mut self, component: &Component) -> LayeredOutput {
+        self.register_shadow(component.drawable().inner());
+        let mut output = component.drawable().render_to_layers();
+        if let Some(image) = self.render_image(component.drawable()) {
+            output.add_to_layer(RenderLayer::Content, image);
+        }
+        add_component_title(&mut output, component);
+        self.add_link(
+            &mut output,
+            component.drawable().inner().link(),
+            RenderLayer::Content,
+        );
+        output
     }
 
     /// Renders a positioned relation arrow to layered SVG output.
     pub fn render_relation(&mut self, relation: &PositionedArrowWithText) -> LayeredOutput {
-        relation.render_to_layers(&mut self.arrow_with_text_drawer)
+        let mut output = relation.render_to_layers(&mut self.arrow_with_text_drawer);
+        add_relation_label(&mut output, relation);
+        self.add_link(&mut output, relation.link(), RenderLayer::Arrow);
+        output
+    }
+
+    /// Renders a group boundary to layered SVG output.
+    pub fn render_group(&self, group: &PositionedDrawable<Group>) -> LayeredOutput {
+        group.render_to_layers()
     }
 
-    pub fn calculate_component_diagram_bounds(
-        &self,
-        content_stack: &ContentStack<Layout>,
-    ) -> Bounds {
-        let last_positioned_content = content_stack.iter().last();
-        last_positioned_content
-            .map(|positioned_content| {
-                let layout = &positioned_content.content();
-
-                if layout.components().is_empty() {
-                    return Bounds::default();
-                }
-
-                layout
-                    .components()
-                    .iter()
-                    .skip(1)
-                    .map(|component| component.bounds())
-                    .fold(layout.components()[0].bounds(), |acc, bounds| {
-                        acc.merge(&bounds)
-                    })
-            })
-            .unwrap_or_default()
+    /// Wraps the `layer` group of an element and its label in `<a>` elements
+    /// opening `link`, so both the shape or path and the text are clickable.
+    ///
+    /// Does nothing if the element has no link with an `href`.
+    pub(super) fn add_link(
+        &mut self,
+        output: &mut LayeredOutput,
+        link: Option<&LinkDefinition>,
+        layer: RenderLayer,
+    ) {
+        let Some(anchor) = link.and_then(LinkDefinition::to_anchor) else {
+            return;
+        };
+        output.wrap_layer(layer, anchor.clone());
+        output.wrap_layer(RenderLayer::Text, anchor);
+        self.has_links = true;
+    }
+}
+
+/// Wraps the shape of `component` in a group whose `<title>` is the
+/// component's display name, so screen readers announce it.
+///
+/// A tooltip replaces the display name as the `<title>`, which browsers show
+/// on hover; the display name then moves to the group's `aria-label`.
+pub(super) fn add_component_title(output: &mut LayeredOutput, component: &Component) {
+    let shape = component.drawable().inner();
+    let name = match shape.text() {
+        Some(text) => text.content().to_string(),
+        None => component.node_id().name().to_string(),
+    };
+    let group = svg_element::Group::new().set("role", "graphics-symbol");
+    let group = match shape.tooltip() {
+        Some(tooltip) => group
+            .set("aria-label", name)
+            .add(svg_element::Title::new(tooltip)),
+        None => group.add(svg_element::Title::new(name)),
+    };
+    output.wrap_layer(RenderLayer::Content, group);
+}
+
+/// Wraps the path of `relation` in a group labelled with the relation's
+/// label and titled with its tooltip. Relations with neither are left as
+/// they are.
+pub(super) fn add_relation_label(output: &mut LayeredOutput, relation: &PositionedArrowWithText) {
+    if relation.label().is_none() && relation.tooltip().is_none() {
+        return;
+    }
+    let mut group = svg_element::Group::new().set("role", "graphics-symbol");
+    if let Some(label) = relation.label() {
+        group = group.set("aria-label", label);
+    }
+    if let Some(tooltip) = relation.tooltip() {
+        group = group.add(svg_element::Title::new(tooltip));
     }
+    output.wrap_layer(RenderLayer::Arrow, group);
 }
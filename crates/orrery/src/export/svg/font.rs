@@ -0,0 +1,74 @@
+//! Embedding font files into SVG output.
+//!
+//! A [`FontFace`] reads a TTF, OTF, WOFF or WOFF2 file and inlines it as a
+//! base64 `data:` URL inside an `@font-face` rule, so the SVG renders with the
+//! same font on machines that do not have it installed.
+
+use std::{io, path::Path};
+
+use svg::node::element::Style;
+
+use super::{base64, read_asset};
+
+/// A font file inlined into the SVG under a family name.
+#[derive(Debug)]
+pub struct FontFace {
+    family: String,
+    mime_type: &'static str,
+    format: &'static str,
+    data: String,
+}
+
+impl FontFace {
+    /// Reads the font file at `path` and registers it under `family`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::Error`] if the file cannot be read or its extension is
+    /// not a supported font format.
+    pub fn load(family: &str, path: &Path) -> io::Result<Self> {
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_ascii_lowercase);
+        let (mime_type, format) = match extension.as_deref() {
+            Some("ttf") => ("font/ttf", "truetype"),
+            Some("otf") => ("font/otf", "opentype"),
+            Some("woff") => ("font/woff", "woff"),
+            Some("woff2") => ("font/woff2", "woff2"),
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "unsupported font format, expected a .ttf, .otf, .woff or .woff2 file",
+                ));
+            }
+        };
+
+        let bytes = read_asset(path)?;
+        Ok(Self {
+            family: family.to_string(),
+            mime_type,
+            format,
+            data: base64::encode(&bytes),
+        })
+    }
+
+    /// Returns the `<style>` element holding the `@font-face` rule.
+    pub fn to_style(&self) -> Style {
+        Style::new(format!(
+            "@font-face {{ font-family: \"{}\"; src: url(data:{};base64,{}) format(\"{}\"); }}",
+            self.family, self.mime_type, self.data, self.format
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_rejects_unknown_formats() {
+        let err = FontFace::load("Sans", Path::new("font.txt")).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+}
@@ -0,0 +1,78 @@
+//! Embedding images into SVG output.
+//!
+//! Icons drawn inside shapes reference either a `data:` URI, used as is, or a
+//! local image file, which is read and inlined as a base64 `data:` URI so the
+//! SVG stays self-contained.
+
+use std::{io, path::Path};
+
+use svg::node::element::Image;
+
+use orrery_core::geometry::Bounds;
+
+use super::{base64, read_asset};
+
+/// Resolves an image source into the `href` of an SVG `<image>`.
+///
+/// # Errors
+///
+/// Returns an [`io::Error`] if the file cannot be read or its extension is
+/// not a supported image format.
+pub fn load_href(source: &str) -> io::Result<String> {
+    if source.starts_with("data:") {
+        return Ok(source.to_string());
+    }
+
+    let path = Path::new(source);
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_ascii_lowercase);
+    let mime_type = match extension.as_deref() {
+        Some("png") => "image/png",
+        Some("jpg" | "jpeg") => "image/jpeg",
+        Some("gif") => "image/gif",
+        Some("webp") => "image/webp",
+        Some("svg") => "image/svg+xml",
+        _ => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "unsupported image format, expected a .png, .jpg, .jpeg, .gif, .webp or .svg file",
+            ));
+        }
+    };
+
+    let bytes = read_asset(path)?;
+    Ok(format!(
+        "data:{mime_type};base64,{}",
+        base64::encode(&bytes)
+    ))
+}
+
+/// Creates the `<image>` element drawing `href` inside `bounds`.
+pub fn to_image(href: &str, bounds: Bounds) -> Image {
+    let size = bounds.to_size();
+    Image::new()
+        .set("href", href)
+        .set("x", bounds.min_x())
+        .set("y", bounds.min_y())
+        .set("width", size.width())
+        .set("height", size.height())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_href_keeps_data_uris() {
+        let uri = "data:image/png;base64,iVBORw0KGgo=";
+        assert_eq!(load_href(uri).unwrap(), uri);
+    }
+
+    #[test]
+    fn test_load_href_rejects_unknown_formats() {
+        let err = load_href("logo.txt").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+}
@@ -3,9 +3,12 @@
 use log::debug;
 use svg::{self, node::element as svg_element};
 
-use orrery_core::{draw::LayeredOutput, geometry::Bounds};
+use orrery_core::{
+    draw::{LayeredOutput, RenderLayer},
+    geometry::{Bounds, Point, Size},
+};
 
-use super::Svg;
+use super::{Svg, canvas_text};
 use crate::layout::{
     component,
     layer::{ContentStack, Layer, LayeredLayout, LayoutContent},
@@ -17,23 +20,53 @@ impl Svg {
     /// Renders the complete layered layout to an SVG document.
     pub fn render_layered_layout(&mut self, layout: &LayeredLayout) -> svg::Document {
         // Calculate content bounds
-        let content_bounds = self.calculate_layered_layout_bounds(layout);
+        let content_bounds = layout.bounds();
         let content_size = content_bounds.to_size();
 
-        // Calculate final SVG dimensions with margins
-        let svg_size = self.calculate_svg_dimensions(content_size);
+        // Frame the content with the title above and the caption below
+        let title_height = self
+            .title
+            .as_ref()
+            .map_or(0.0, |title| title.block_height());
+        let caption_height = self
+            .caption
+            .as_ref()
+            .map_or(0.0, |caption| caption.block_height());
+        let framed_width = [&self.title, &self.caption]
+            .into_iter()
+            .flatten()
+            .fold(content_size.width(), |width, text| {
+                width.max(text.size().width())
+            });
+        let framed_size = Size::new(
+            framed_width,
+            content_size.height() + title_height + caption_height,
+        );
+
+        // Calculate final SVG dimensions with padding
+        let svg_size = self.calculate_svg_dimensions(framed_size);
 
-        // Create the SVG document with calculated dimensions
+        // Create the SVG document, fitting the canvas into the requested outer size
+        let (outer_size, view_box) = self.calculate_viewport(svg_size);
+        let view_box_size = view_box.to_size();
         let doc = svg::Document::new()
             .set(
                 "viewBox",
-                format!("0 0 {} {}", svg_size.width(), svg_size.height()),
+                format!(
+                    "{} {} {} {}",
+                    view_box.min_x(),
+                    view_box.min_y(),
+                    view_box_size.width(),
+                    view_box_size.height()
+                ),
             )
-            .set("width", svg_size.width())
-            .set("height", svg_size.height());
+            .set("width", outer_size.width())
+            .set("height", outer_size.height());
 
-        // Add background
-        let mut doc = self.add_background(doc, svg_size);
+        // Add the accessible title and description, the embedded font and background
+        let doc = self.add_accessible_text(doc);
+        let doc = self.add_font_face(doc);
+        let mut doc = self.add_background(doc, view_box);
 
         // Add clip paths for all layers that need clipping
         // Each clip path gets a unique ID based on the layer's z-index
@@ -45,17 +78,14 @@ impl Svg {
             }
         }
 
-        // Calculate margins for centering
-        let margin_x = (svg_size.width() - content_size.width()) / 2.0;
-        let margin_y = (svg_size.height() - content_size.height()) / 2.0;
-
-        // Create a main group with translation to center content and adjust for min bounds
+        // Create a main group with translation to offset content by the padding and adjust for min bounds
         let mut main_group = svg_element::Group::new().set(
             "transform",
             format!(
                 "translate({}, {})",
-                margin_x - content_bounds.min_x(),
-                margin_y - content_bounds.min_y()
+                self.padding.left() + (framed_width - content_size.width()) / 2.0
+                    - content_bounds.min_x(),
+                self.padding.top() + title_height - content_bounds.min_y()
             ),
         );
 
@@ -68,8 +98,47 @@ impl Svg {
         let arrow_markers_defs = self.arrow_with_text_drawer.draw_marker_definitions();
         doc = doc.add(arrow_markers_defs);
 
-        // Add the main group to the document
-        doc.add(main_group)
+        // Add shadow filter definitions for all layers
+        if !self.shadow_filters.is_empty() {
+            doc = doc.add(self.draw_shadow_filters());
+        }
+
+        // Add the main group, then the title and caption around it
+        doc = doc.add(main_group);
+        if self.has_links {
+            doc = doc.set("xmlns:xlink", "http://www.w3.org/1999/xlink");
+        }
+        let center_x = self.padding.left() + framed_width / 2.0;
+        if let Some(title) = &self.title {
+            let center = Point::new(center_x, self.padding.top() + title.size().height() / 2.0);
+            for node in title.render(center) {
+                doc = doc.add(node);
+            }
+        }
+        if let Some(caption) = &self.caption {
+            let top = self.padding.top() + title_height + content_size.height() + canvas_text::GAP;
+            let center = Point::new(center_x, top + caption.size().height() / 2.0);
+            for node in caption.render(center) {
+                doc = doc.add(node);
+            }
+        }
+
+        doc
+    }
+
+    /// Marks the document as an image and adds its accessible name as the
+    /// `<title>` and the caption, if any, as its `<desc>`.
+    ///
+    /// Both must be the first children of the root element to be picked up by
+    /// assistive technology.
+    fn add_accessible_text(&self, doc: svg::Document) -> svg::Document {
+        let mut doc = doc
+            .set("role", "img")
+            .add(svg_element::Title::new(self.accessible_name.as_str()));
+        if let Some(caption) = &self.caption {
+            doc = doc.add(caption.to_description());
+        }
+        doc
     }
 
     /// Creates an SVG clip path for a layer.
@@ -98,50 +167,6 @@ impl Svg {
         defs.add(clip_path)
     }
 
-    /// Calculates the combined bounds of all layers, considering their offsets.
-    ///
-    /// Computes the total bounding box that contains all layers in the layout,
-    /// accounting for layer offsets. This is used to determine the overall size needed
-    /// for the SVG document.
-    ///
-    /// # Returns
-    ///
-    /// A `Bounds` object that encompasses all content in all layers.
-    fn calculate_layered_layout_bounds(&self, layout: &LayeredLayout) -> Bounds {
-        let mut layout_iter = layout.iter_from_bottom();
-        // Start with the bounds of the first (bottom) layer.
-        // Note: `iter_from_bottom().next()` returning `None` is equivalent to `is_empty()`..
-        let Some(first_layer) = layout_iter.next() else {
-            return Bounds::default();
-        };
-        let mut combined_bounds = self.calculate_layer_bounds(first_layer);
-
-        // Merge with bounds of additional layers, adjusting for layer offset
-        for layer in layout_iter {
-            let layer_bounds = self.calculate_layer_bounds(layer);
-
-            // Adjust bounds for layer offset by creating a translated copy
-            let offset_bounds = layer_bounds.translate(layer.offset());
-
-            // Merge with the combined bounds to include this layer
-            combined_bounds = combined_bounds.merge(&offset_bounds);
-        }
-
-        combined_bounds
-    }
-
-    /// Calculates bounds for a single layer.
-    fn calculate_layer_bounds(&self, layer: &Layer) -> Bounds {
-        match layer.content() {
-            LayoutContent::Component(comp_layout) => {
-                self.calculate_component_diagram_bounds(comp_layout)
-            }
-            LayoutContent::Sequence(seq_layout) => {
-                self.calculate_sequence_diagram_bounds(seq_layout)
-            }
-        }
-    }
-
     /// Renders a single layer to SVG.
     ///
     /// Creates an SVG group for the layer, applies transformations and clipping,
@@ -228,10 +253,28 @@ impl Svg {
         groups
     }
 
+    /// Renders collected drawables, placing the arrow layer directly below
+    /// the content layer unless relations are drawn on top.
+    fn render_output(&self, output: LayeredOutput) -> Vec<Box<dyn svg::Node>> {
+        if self.relations_on_top {
+            return output.render();
+        }
+        output.render_with_layer_order(|layer| match layer {
+            RenderLayer::Arrow => (RenderLayer::Content, 0),
+            layer => (layer, 1),
+        })
+    }
+
     /// Renders component-specific content.
     fn render_component_content(&mut self, content: &component::Layout) -> Vec<Box<dyn svg::Node>> {
         let mut output = LayeredOutput::new();
 
+        // Render all group boundaries within this positioned content
+        for group in content.groups() {
+            let group_output = self.render_group(group);
+            output.merge(group_output);
+        }
+
         // Render all components within this positioned content
         for component in content.components() {
             let component_output = self.render_component(component);
@@ -244,7 +287,13 @@ impl Svg {
             output.merge(relation_output);
         }
 
-        output.render()
+        // Render all notes within this positioned content
+        for note in content.notes() {
+            let note_output = self.render_note(note);
+            output.merge(note_output);
+        }
+
+        self.render_output(output)
     }
 
     /// Renders sequence-specific content.
@@ -257,6 +306,12 @@ impl Svg {
             output.merge(participant_output);
         }
 
+        // Render all highlight bands within this positioned content
+        for highlight in content.highlights() {
+            let highlight_output = self.render_highlight(highlight);
+            output.merge(highlight_output);
+        }
+
         // Render all fragments within this positioned content
         for fragment in content.fragments() {
             let fragment_output = self.render_fragment(fragment);
@@ -285,6 +340,6 @@ impl Svg {
             output.merge(message_output);
         }
 
-        output.render()
+        self.render_output(output)
     }
 }
@@ -1,22 +1,38 @@
 //! SVG rendering for sequence diagrams.
 
 use orrery_core::{
-    draw::{Drawable, Fragment, LayeredOutput, Note, PositionedArrowWithText, PositionedDrawable},
-    geometry::{Bounds, Point},
+    draw::{
+        Drawable, Fragment, Highlight, LayeredOutput, Note, PositionedArrowWithText,
+        PositionedDrawable, RenderLayer,
+    },
+    geometry::Point,
 };
 
-use super::Svg;
-use crate::layout::{layer::ContentStack, sequence};
+use super::{
+    Svg,
+    component::{add_component_title, add_relation_label},
+};
+use crate::layout::sequence;
 
 impl Svg {
     /// Renders a sequence diagram participant to layered SVG output.
-    pub fn render_participant(&self, participant: &sequence::Participant) -> LayeredOutput {
+    pub fn render_participant(&mut self, participant: &sequence::Participant) -> LayeredOutput {
         let mut output = LayeredOutput::new();
         let component = participant.component();
+        self.register_shadow(component.drawable().inner());
 
         // Use the renderer to generate the SVG for the participant
         let shape_output = component.drawable().render_to_layers();
         output.merge(shape_output);
+        if let Some(image) = self.render_image(component.drawable()) {
+            output.add_to_layer(RenderLayer::Content, image);
+        }
+        add_component_title(&mut output, component);
+        self.add_link(
+            &mut output,
+            component.drawable().inner().link(),
+            RenderLayer::Content,
+        );
 
         // Render the pre-positioned lifeline from the participant
         let lifeline_output = participant.lifeline().render_to_layers();
@@ -27,7 +43,10 @@ impl Svg {
 
     /// Renders a positioned message arrow to layered SVG output.
     pub fn render_message(&mut self, message: &PositionedArrowWithText) -> LayeredOutput {
-        message.render_to_layers(&mut self.arrow_with_text_drawer)
+        let mut output = message.render_to_layers(&mut self.arrow_with_text_drawer);
+        add_relation_label(&mut output, message);
+        self.add_link(&mut output, message.link(), RenderLayer::Arrow);
+        output
     }
 
     /// Renders a fragment box in a sequence diagram.
@@ -45,7 +64,20 @@ impl Svg {
         fragment.render_to_layers()
     }
 
-    /// Renders a note in a sequence diagram.
+    /// Renders a highlight band in a sequence diagram.
+    ///
+    /// # Arguments
+    ///
+    /// * `highlight` - The positioned highlight band to render.
+    ///
+    /// # Returns
+    ///
+    /// A [`LayeredOutput`] representing the highlight band.
+    pub fn render_highlight(&self, highlight: &PositionedDrawable<Highlight>) -> LayeredOutput {
+        highlight.render_to_layers()
+    }
+
+    /// Renders a note in a sequence or component diagram.
     ///
     /// Converts a note into its SVG representation.
     ///
@@ -66,35 +98,4 @@ impl Svg {
 
         activation_box.drawable().render_to_layers(position)
     }
-
-    pub fn calculate_sequence_diagram_bounds(
-        &self,
-        content_stack: &ContentStack<sequence::Layout>,
-    ) -> Bounds {
-        let last_positioned_content = content_stack.iter().last();
-        last_positioned_content
-            .map(|positioned_content| {
-                let layout = &positioned_content.content();
-
-                if layout.participants().is_empty() {
-                    return Bounds::default();
-                }
-
-                let mut content_bounds = layout
-                    .participants()
-                    .values()
-                    .map(|p| p.component().bounds())
-                    .reduce(|acc, bounds| acc.merge(&bounds))
-                    .unwrap_or_default();
-
-                // Include notes in bounds calculation
-                for note in layout.notes() {
-                    let note_bounds = note.position().to_bounds(note.size());
-                    content_bounds = content_bounds.merge(&note_bounds);
-                }
-
-                content_bounds.with_max_y(layout.max_lifeline_end())
-            })
-            .unwrap_or_default()
-    }
 }
@@ -16,17 +16,17 @@
 //! Output
 //! ```
 //!
+//! The resulting [`LayeredLayout`](layer::LayeredLayout) is available through
+//! [`DiagramBuilder::layout`](crate::DiagramBuilder::layout) for callers that
+//! want the computed geometry without rendering.
+//!
 //! # Submodules
 //!
 //! - [`component`] - Positioned diagram elements and their relationships (used across
 //!   all diagram kinds)
 //! - [`layer`] - Layer organization and z-ordering for rendering
-//! - [`positioning`] - Reusable positioning algorithms for layout engines
+//! - [`positioning`] - Layout bounds and reusable positioning algorithms for layout engines
 //! - [`sequence`] - Sequence diagram layout (participants, messages, activations)
-//!
-//! # Re-exports
-//!
-//! - [`EngineBuilder`] - Builder for creating and configuring layout engines
 
 pub mod component;
 pub mod layer;
@@ -34,6 +34,7 @@ pub mod positioning;
 pub mod sequence;
 
 mod engines;
+mod routing;
 
-// Public re-export of the engine builder for easier access
-pub use engines::EngineBuilder;
+// Crate-wide re-export of the engine builder for easier access
+pub(crate) use engines::EngineBuilder;
@@ -1,29 +1,31 @@
 //! Positioned diagram elements and arrow placement.
 //!
 //! A [`Component`] wraps a semantic node with its computed position and shape.
-//! An [`ArrowPlacer`] decides how relations between the same component pair
+//! Group boundaries are placed after their members, enclosing their bounds.
+//! An `ArrowPlacer` decides how relations between the same component pair
 //! are turned into visually distinct arrow paths (straight overlap vs. offset
 //! cubic-Bézier lanes).
 
 use std::{collections::HashMap, rc::Rc};
 
+use indexmap::IndexMap;
 use itertools::Itertools;
 use log::{debug, error};
 
 use orrery_core::{
     draw::{
-        Arrow, ArrowPath, ArrowStyle, ArrowWithText, PositionedArrowWithText, PositionedDrawable,
-        ShapeWithText,
+        Arrow, ArrowDefinition, ArrowPath, ArrowStyle, ArrowWithText, Drawable, Group,
+        Note as DrawNote, PositionedArrowWithText, PositionedDrawable, ShapeWithText,
     },
-    geometry::{Bounds, Point},
+    geometry::{Bounds, Point, Size},
     identifier::Id,
-    semantic::{Node, Relation},
+    semantic::{Node, Note, NoteAlign, Relation},
 };
 
 use crate::{
     error::RenderError,
     layout::{layer::ContentStack, positioning::LayoutBounds},
-    structure::ComponentGraph,
+    structure::{ComponentGraph, ScopeGroup},
 };
 
 // TODO: Do I need Clone?!
@@ -37,7 +39,11 @@ pub struct Component<'a> {
 
 impl<'a> Component<'a> {
     /// Creates a new component with the specified properties.
-    pub fn new(node: &Node, shape_with_text: ShapeWithText<'a>, position: Point) -> Component<'a> {
+    pub(crate) fn new(
+        node: &Node,
+        shape_with_text: ShapeWithText<'a>,
+        position: Point,
+    ) -> Component<'a> {
         let drawable = Rc::new(PositionedDrawable::new(shape_with_text).with_position(position));
         Component {
             node_id: node.id(),
@@ -45,6 +51,16 @@ impl<'a> Component<'a> {
         }
     }
 
+    /// Returns a copy of this component moved by `offset`.
+    fn translated(&self, offset: Point) -> Component<'a> {
+        let drawable = PositionedDrawable::clone(&self.drawable)
+            .with_position(self.position().add_point(offset));
+        Component {
+            node_id: self.node_id,
+            drawable: Rc::new(drawable),
+        }
+    }
+
     /// Returns a reference to the component's shape.
     pub fn drawable(&self) -> &PositionedDrawable<ShapeWithText<'_>> {
         &self.drawable
@@ -66,6 +82,12 @@ impl<'a> Component<'a> {
         self.drawable.bounds()
     }
 
+    /// Returns the center position of the component's label, or `None` if the
+    /// component has no label.
+    pub fn label_position(&self) -> Option<Point> {
+        self.drawable.inner().text_position(self.position())
+    }
+
     /// Returns the unique identifier of the AST node this component represents.
     // TODO: Can I get rid of this method?
     pub fn node_id(&self) -> Id {
@@ -100,7 +122,7 @@ impl<'a> Component<'a> {
 /// - Relations within the bucket may go either direction; inspect
 ///   [`Relation::source`] to determine orientation.
 /// - Return exactly one arrow per input relation.
-pub trait ArrowPlacer {
+pub(crate) trait ArrowPlacer {
     /// Places a bucket of relations between the same component pair.
     fn place<'a>(
         &self,
@@ -115,7 +137,7 @@ pub trait ArrowPlacer {
 /// Parallel/reverse relations overlap into a single line. Exists as the
 /// behaviour-preserving baseline; [`CurvedArrowPlacer`] is the default.
 #[derive(Debug, Default, Clone, Copy)]
-pub struct StraightArrowPlacer;
+pub(crate) struct StraightArrowPlacer;
 
 impl StraightArrowPlacer {
     /// Straight-line arrow from `source` boundary to `target` boundary.
@@ -162,12 +184,13 @@ impl ArrowPlacer for StraightArrowPlacer {
 ///
 /// Lane endpoints are found by aiming each shape's intersection ray at the
 /// offset center of the other shape; control points sit at 1/3 and 2/3 of
-/// the resulting lane line. For `N == 1` the offset is zero, producing a
-/// straight path identical to [`StraightArrowPlacer`].
+/// the resulting lane line, bowed out by the relation's
+/// [`curvature`](ArrowDefinition::curvature). For `N == 1` the offset is
+/// zero, producing a straight path identical to [`StraightArrowPlacer`].
 /// Self-loop buckets produce lobes distributed evenly around the component
 /// boundary (see [`Self::self_loop_angle`]).
 #[derive(Debug, Clone, Copy)]
-pub struct CurvedArrowPlacer {
+pub(crate) struct CurvedArrowPlacer {
     lane_spacing: f32,
     self_loop_radius: f32,
     /// Angular spread (radians) between self-loop source and destination
@@ -195,7 +218,10 @@ impl CurvedArrowPlacer {
         target: &Component<'_>,
         lane_offset: f32,
     ) -> PositionedArrowWithText<'a> {
-        let Some((path, label_position)) = Self::lane_geometry(source, target, lane_offset) else {
+        let curvature = relation.arrow_definition().curvature();
+        let Some((path, label_position)) =
+            Self::lane_geometry(source, target, lane_offset, curvature)
+        else {
             // Degenerate (zero-length centerline): fall back to straight.
             return StraightArrowPlacer::place_one(relation, source, target);
         };
@@ -209,11 +235,16 @@ impl CurvedArrowPlacer {
 
     /// Computes the cubic-Bézier path and label position for a lane offset.
     ///
+    /// The control points bow out from the lane line in proportion to
+    /// `curvature`; at [`ArrowDefinition::DEFAULT_CURVATURE`] they are offset
+    /// by the lane offset itself, and at `0` the path is straight.
+    ///
     /// Returns `None` when component centers coincide (zero-length centerline).
     fn lane_geometry(
         source: &Component<'_>,
         target: &Component<'_>,
         lane_offset: f32,
+        curvature: f32,
     ) -> Option<(ArrowPath, Point)> {
         let src_center = source.position();
         let tgt_center = target.position();
@@ -233,9 +264,10 @@ impl CurvedArrowPlacer {
         let tgt_edge = target.find_intersection(midpoint);
 
         // Control points at 1/3 and 2/3 of src_edge→tgt_edge, offset perpendicular.
+        let bow = perp.scale(curvature / ArrowDefinition::DEFAULT_CURVATURE);
         let (third, two_thirds) = line_segment_thirds(src_edge, tgt_edge);
-        let cp1 = third.add_point(perp);
-        let cp2 = two_thirds.add_point(perp);
+        let cp1 = third.add_point(bow);
+        let cp2 = two_thirds.add_point(bow);
 
         let path = ArrowPath::new(src_edge, tgt_edge, vec![cp1, cp2]);
         let label_position = cubic_bezier_midpoint(src_edge, cp1, cp2, tgt_edge);
@@ -362,11 +394,11 @@ impl ArrowPlacer for CurvedArrowPlacer {
 /// [`StraightArrowPlacer`], parallel/reverse relations overlap into a single
 /// path; it does not offset them onto separate lanes.
 #[derive(Debug, Default, Clone, Copy)]
-pub struct OrthogonalArrowPlacer;
+pub(crate) struct OrthogonalArrowPlacer;
 
 impl OrthogonalArrowPlacer {
     /// Builds the right-angle [`ArrowPath`] between two boundary points.
-    fn path(source: Point, target: Point) -> ArrowPath {
+    pub(super) fn path(source: Point, target: Point) -> ArrowPath {
         let abs_dist = target.sub_point(source).abs();
         let mid = source.midpoint(target);
 
@@ -429,7 +461,7 @@ impl ArrowPlacer for OrthogonalArrowPlacer {
 /// between the same pair may overlap. Output order is not preserved across
 /// style groups.
 #[derive(Debug, Default, Clone, Copy)]
-pub struct SmartArrowPlacer {
+pub(crate) struct SmartArrowPlacer {
     straight: StraightArrowPlacer,
     curved: CurvedArrowPlacer,
     orthogonal: OrthogonalArrowPlacer,
@@ -476,12 +508,17 @@ impl ArrowPlacer for SmartArrowPlacer {
 pub struct Layout<'a> {
     components: Vec<Component<'a>>,
     relations: Vec<PositionedArrowWithText<'a>>,
+    groups: Vec<PositionedDrawable<Group>>,
+    notes: Vec<PositionedDrawable<DrawNote>>,
     bounds: Bounds,
 }
 
+/// Gap between a note and the components it is placed beside.
+const NOTE_SPACING: f32 = 20.0;
+
 impl<'a> Layout<'a> {
     /// Creates a new layout with the given components and relations.
-    pub fn new(
+    pub(crate) fn new(
         components: Vec<Component<'a>>,
         relations: Vec<PositionedArrowWithText<'a>>,
     ) -> Self {
@@ -499,10 +536,123 @@ impl<'a> Layout<'a> {
         Self {
             components,
             relations,
+            groups: Vec::new(),
+            notes: Vec::new(),
             bounds,
         }
     }
 
+    /// Places the boundaries of `groups` around their member components.
+    ///
+    /// Each boundary encloses its components and nested groups, grown by the
+    /// group's padding and title tab, and the layout bounds grow to include
+    /// it. Groups that enclose no component are not drawn.
+    pub(crate) fn with_groups(mut self, groups: &[ScopeGroup<'_>]) -> Self {
+        let mut group_bounds: Vec<Option<Bounds>> = Vec::with_capacity(groups.len());
+        for scope_group in groups {
+            let members = scope_group.node_ids().iter().filter_map(|&node_id| {
+                self.components
+                    .iter()
+                    .find(|component| component.node_id == node_id)
+                    .map(Component::bounds)
+            });
+            let nested = scope_group
+                .nested()
+                .iter()
+                .filter_map(|&idx| group_bounds[idx]);
+            let Some(content_bounds) = members.chain(nested).reduce(|acc, b| acc.merge(&b)) else {
+                group_bounds.push(None);
+                continue;
+            };
+
+            let group = scope_group.group();
+            let definition = group.definition();
+            let bounds = content_bounds.add_padding(definition.content_insets(group.label()));
+            // Keep the boundary at least as wide as its title tab.
+            let size = Size::new(
+                bounds
+                    .width()
+                    .max(definition.label_size(group.label()).width()),
+                bounds.height(),
+            );
+            let bounds = Bounds::new_from_top_left(bounds.min_point(), size);
+
+            let drawable = Group::new(Rc::clone(definition), group.label().to_string(), size);
+            self.groups
+                .push(PositionedDrawable::new(drawable).with_position(bounds.center()));
+            self.bounds = self.bounds.merge(&bounds);
+            group_bounds.push(Some(bounds));
+        }
+        self
+    }
+
+    /// Places `notes` beside the components they are attached to.
+    ///
+    /// A note sits on the side of its anchors named by its alignment, with
+    /// top and bottom notes stretched to span all of them. An anchor nested
+    /// inside one of this layout's components resolves to that component, and
+    /// a note without anchors in this layout is placed beside the whole
+    /// layout. The layout bounds grow to include every note.
+    pub(crate) fn with_notes(mut self, notes: &[&Note]) -> Self {
+        for note in notes {
+            let anchors = note
+                .on()
+                .iter()
+                .filter_map(|&id| self.anchor_bounds(id))
+                .reduce(|acc, bounds| acc.merge(&bounds))
+                .unwrap_or(self.bounds);
+
+            let mut definition = Rc::clone(note.definition());
+            if matches!(note.align(), NoteAlign::Top | NoteAlign::Bottom) {
+                Rc::make_mut(&mut definition).set_min_width(Some(anchors.width()));
+            }
+            let drawable = DrawNote::new(definition, note.content().to_string());
+
+            let size = drawable.size();
+            let center = anchors.center();
+            let position = match note.align() {
+                NoteAlign::Over => center,
+                NoteAlign::Left => Point::new(
+                    anchors.min_x() - NOTE_SPACING - size.width() / 2.0,
+                    center.y(),
+                ),
+                NoteAlign::Right => Point::new(
+                    anchors.max_x() + NOTE_SPACING + size.width() / 2.0,
+                    center.y(),
+                ),
+                NoteAlign::Top => Point::new(
+                    center.x(),
+                    anchors.min_y() - NOTE_SPACING - size.height() / 2.0,
+                ),
+                NoteAlign::Bottom => Point::new(
+                    center.x(),
+                    anchors.max_y() + NOTE_SPACING + size.height() / 2.0,
+                ),
+            };
+
+            let note = PositionedDrawable::new(drawable).with_position(position);
+            self.bounds = self.bounds.merge(&note.bounds());
+            self.notes.push(note);
+        }
+        self
+    }
+
+    /// Returns the bounds of the component `id` resolves to in this layout:
+    /// the component itself or the one it is nested inside.
+    fn anchor_bounds(&self, id: Id) -> Option<Bounds> {
+        std::iter::successors(Some(id), Id::parent).find_map(|id| {
+            self.components
+                .iter()
+                .find(|component| component.node_id == id)
+                .map(Component::bounds)
+        })
+    }
+
+    /// Adds already positioned relations to this layout.
+    fn add_relations(&mut self, relations: Vec<PositionedArrowWithText<'a>>) {
+        self.relations.extend(relations);
+    }
+
     /// Returns a reference to the components in this layout.
     pub fn components(&self) -> &[Component<'a>] {
         &self.components
@@ -512,6 +662,18 @@ impl<'a> Layout<'a> {
     pub fn relations(&self) -> &[PositionedArrowWithText<'a>] {
         &self.relations
     }
+
+    /// Returns the group boundaries in this layout.
+    ///
+    /// Nested groups come before the group that encloses them.
+    pub fn groups(&self) -> &[PositionedDrawable<Group>] {
+        &self.groups
+    }
+
+    /// Returns the notes in this layout.
+    pub fn notes(&self) -> &[PositionedDrawable<DrawNote>] {
+        &self.notes
+    }
 }
 
 impl<'a> LayoutBounds for Layout<'a> {
@@ -539,9 +701,9 @@ impl<'a> LayoutBounds for Layout<'a> {
 /// Returns `RenderError::Layout` if a component referenced in the containment graph
 /// is not found in its corresponding layout layer.
 // TODO: Once added enough abstractions, make this a method on ContentStack.
-pub fn adjust_positioned_contents_offset<'a>(
+pub(crate) fn adjust_positioned_contents_offset<'a>(
     content_stack: &mut ContentStack<Layout>,
-    graph: &'a ComponentGraph<'a, '_>,
+    graph: &ComponentGraph<'a, '_>,
 ) -> Result<(), RenderError> {
     let container_indices: HashMap<_, _> = graph
         .containment_scopes()
@@ -593,6 +755,10 @@ pub fn adjust_positioned_contents_offset<'a>(
                 "Adjusting positioned content offset [source]",
             );
             let target = content_stack.get_mut_unchecked(destination_idx);
+            // Align the nested layout's own origin (which group boundaries can
+            // push past zero) with the container's content area.
+            let target_offset =
+                target_offset.sub_point(target.content().layout_bounds().min_point());
             debug!(
                 node_id:% = node,
                 original_offset:? = target.offset(),
@@ -605,6 +771,72 @@ pub fn adjust_positioned_contents_offset<'a>(
     Ok(())
 }
 
+/// Places the relations that connect components of different containment
+/// scopes.
+///
+/// Must run after [`adjust_positioned_contents_offset`], once every layer has
+/// its final offset. Relations between the same component pair share a
+/// bucket, as within a scope, and are routed by [`SmartArrowPlacer`]. Each
+/// bucket is added to the layer of the endpoint that is rendered last, i.e.
+/// the lower index, so the arrows are drawn above both endpoints' containers.
+/// Relations with an endpoint outside these layers, such as a component of an
+/// embedded diagram, are skipped.
+pub(crate) fn place_cross_scope_relations<'a>(
+    content_stack: &mut ContentStack<Layout<'a>>,
+    graph: &ComponentGraph<'a, '_>,
+) {
+    // Every component with the index and offset of its layer.
+    let located: HashMap<Id, (usize, Point, Component<'a>)> = content_stack
+        .iter()
+        .enumerate()
+        .flat_map(|(idx, positioned_content)| {
+            let offset = positioned_content.offset();
+            positioned_content
+                .content()
+                .components()
+                .iter()
+                .map(move |component| (component.node_id(), (idx, offset, component.clone())))
+        })
+        .collect();
+
+    let mut buckets: IndexMap<(Id, Id), Vec<&'a Relation>> = IndexMap::new();
+    for relation in graph.cross_scope_relations() {
+        let source_id = relation.source();
+        let target_id = relation.target();
+        if !located.contains_key(&source_id) || !located.contains_key(&target_id) {
+            debug!(
+                source:% = source_id,
+                target:% = target_id;
+                "Skipping cross-scope relation outside the diagram layers",
+            );
+            continue;
+        }
+
+        // Canonicalize the pair key so (a,b) and (b,a) land in the same bucket.
+        let key = if buckets.contains_key(&(target_id, source_id)) {
+            (target_id, source_id)
+        } else {
+            (source_id, target_id)
+        };
+        buckets.entry(key).or_default().push(relation);
+    }
+
+    let placer = SmartArrowPlacer::new();
+    for ((src_id, tgt_id), bucket) in buckets {
+        let (src_layer, src_offset, src) = &located[&src_id];
+        let (tgt_layer, tgt_offset, tgt) = &located[&tgt_id];
+        let layer = (*src_layer).min(*tgt_layer);
+        let positioned_content = content_stack.get_mut_unchecked(layer);
+        let origin = positioned_content.offset();
+
+        // Move both endpoints into the coordinates of the chosen layer.
+        let src = src.translated(src_offset.sub_point(origin));
+        let tgt = tgt.translated(tgt_offset.sub_point(origin));
+        let relations = placer.place(&bucket, &src, &tgt);
+        positioned_content.content_mut().add_relations(relations);
+    }
+}
+
 /// Returns `(source, target)` or `(target, source)` depending on which
 /// component matches `relation.source()`.
 fn align_to_relation<'a, 'b>(
@@ -642,7 +874,7 @@ mod tests {
     use float_cmp::{approx_eq, assert_approx_eq};
 
     use orrery_core::{
-        draw::{ArrowDefinition, ArrowDirection, RectangleDefinition, Shape, ShapeDefinition},
+        draw::{ArrowDirection, NoteDefinition, RectangleDefinition, Shape, ShapeDefinition},
         semantic::Block,
     };
 
@@ -694,6 +926,88 @@ mod tests {
         Component::new(node, shape_with_text, position)
     }
 
+    fn make_note(on: &[&str], align: NoteAlign) -> Note {
+        Note::new(
+            on.iter().map(|name| Id::new(name)).collect(),
+            align,
+            "note".to_string(),
+            Rc::new(NoteDefinition::new()),
+        )
+    }
+
+    #[test]
+    fn with_notes_places_note_on_aligned_side() {
+        let a_node = make_node("a");
+        let a = make_component(&a_node, Point::new(100.0, 100.0));
+        let anchor = a.bounds();
+        let notes = [
+            make_note(&["a"], NoteAlign::Left),
+            make_note(&["a"], NoteAlign::Right),
+            make_note(&["a"], NoteAlign::Top),
+            make_note(&["a"], NoteAlign::Bottom),
+        ];
+
+        let layout = Layout::new(vec![a], Vec::new()).with_notes(&notes.iter().collect_vec());
+        let [left, right, top, bottom] = layout.notes() else {
+            panic!("expected four notes");
+        };
+
+        assert!(left.position().x() < anchor.center().x());
+        assert_approx_eq!(f32, left.bounds().max_x(), anchor.min_x() - NOTE_SPACING);
+        assert_approx_eq!(f32, left.position().y(), anchor.center().y());
+        assert_approx_eq!(f32, right.bounds().min_x(), anchor.max_x() + NOTE_SPACING);
+        assert_approx_eq!(f32, top.bounds().max_y(), anchor.min_y() - NOTE_SPACING);
+        assert_approx_eq!(f32, bottom.bounds().min_y(), anchor.max_y() + NOTE_SPACING);
+        assert_approx_eq!(f32, bottom.position().x(), anchor.center().x());
+
+        let all = [left, right, top, bottom]
+            .into_iter()
+            .fold(anchor, |acc, note| acc.merge(&note.bounds()));
+        assert_eq!(layout.layout_bounds(), all);
+    }
+
+    #[test]
+    fn with_notes_spans_all_anchors() {
+        let a_node = make_node("a");
+        let b_node = make_node("b");
+        let a = make_component(&a_node, Point::new(0.0, 0.0));
+        let b = make_component(&b_node, Point::new(300.0, 40.0));
+        let anchors = a.bounds().merge(&b.bounds());
+        let note = make_note(&["a", "b"], NoteAlign::Bottom);
+
+        let layout = Layout::new(vec![a, b], Vec::new()).with_notes(&[&note]);
+        let bounds = layout.notes()[0].bounds();
+
+        assert_approx_eq!(f32, bounds.min_x(), anchors.min_x());
+        assert_approx_eq!(f32, bounds.max_x(), anchors.max_x());
+        assert_approx_eq!(f32, bounds.min_y(), anchors.max_y() + NOTE_SPACING);
+    }
+
+    #[test]
+    fn with_notes_resolves_nested_anchor_to_container() {
+        let a_node = make_node("a");
+        let b_node = make_node("b");
+        let a = make_component(&a_node, Point::new(0.0, 0.0));
+        let b = make_component(&b_node, Point::new(300.0, 0.0));
+        let b_bounds = b.bounds();
+        let nested = make_note(&["b::inner"], NoteAlign::Right);
+        let unanchored = make_note(&[], NoteAlign::Right);
+
+        let layout = Layout::new(vec![a, b], Vec::new()).with_notes(&[&nested, &unanchored]);
+        let [nested, unanchored] = layout.notes() else {
+            panic!("expected two notes");
+        };
+
+        assert_approx_eq!(
+            f32,
+            nested.bounds().min_x(),
+            b_bounds.max_x() + NOTE_SPACING
+        );
+        assert_approx_eq!(f32, nested.position().y(), b_bounds.center().y());
+        // Without anchors the note sits beside everything placed before it.
+        assert!(unanchored.bounds().min_x() > nested.bounds().max_x());
+    }
+
     #[test]
     fn line_segment_thirds_returns_third_points() {
         let (cp1, cp2) = line_segment_thirds(Point::new(0.0, 0.0), Point::new(30.0, 60.0));
@@ -767,7 +1081,8 @@ mod tests {
         let b = make_component(&b_node, Point::new(1000.0, 0.0));
 
         let (path, label) =
-            CurvedArrowPlacer::lane_geometry(&a, &b, 18.0).expect("non-degenerate centerline");
+            CurvedArrowPlacer::lane_geometry(&a, &b, 18.0, ArrowDefinition::DEFAULT_CURVATURE)
+                .expect("non-degenerate centerline");
 
         assert_eq!(path.control_points().len(), 2);
 
@@ -785,8 +1100,12 @@ mod tests {
         let a = make_component(&a_node, Point::new(0.0, 0.0));
         let b = make_component(&b_node, Point::new(1000.0, 0.0));
 
-        let (path_pos, _) = CurvedArrowPlacer::lane_geometry(&a, &b, 18.0).unwrap();
-        let (path_neg, _) = CurvedArrowPlacer::lane_geometry(&a, &b, -18.0).unwrap();
+        let (path_pos, _) =
+            CurvedArrowPlacer::lane_geometry(&a, &b, 18.0, ArrowDefinition::DEFAULT_CURVATURE)
+                .unwrap();
+        let (path_neg, _) =
+            CurvedArrowPlacer::lane_geometry(&a, &b, -18.0, ArrowDefinition::DEFAULT_CURVATURE)
+                .unwrap();
 
         let cp1_pos = path_pos.control_points()[0];
         let cp1_neg = path_neg.control_points()[0];
@@ -800,8 +1119,12 @@ mod tests {
         let a = make_component(&a_node, Point::new(0.0, 0.0));
         let b = make_component(&b_node, Point::new(1000.0, 0.0));
 
-        let (path_small, _) = CurvedArrowPlacer::lane_geometry(&a, &b, 10.0).unwrap();
-        let (path_large, _) = CurvedArrowPlacer::lane_geometry(&a, &b, 40.0).unwrap();
+        let (path_small, _) =
+            CurvedArrowPlacer::lane_geometry(&a, &b, 10.0, ArrowDefinition::DEFAULT_CURVATURE)
+                .unwrap();
+        let (path_large, _) =
+            CurvedArrowPlacer::lane_geometry(&a, &b, 40.0, ArrowDefinition::DEFAULT_CURVATURE)
+                .unwrap();
 
         let cp1_small = path_small.control_points()[0];
         let cp1_large = path_large.control_points()[0];
@@ -809,11 +1132,61 @@ mod tests {
         assert_approx_eq!(f32, cp1_large.y() / cp1_small.y(), 4.0);
     }
 
+    #[test]
+    fn lane_geometry_zero_curvature_is_straight() {
+        let a_node = make_node("a");
+        let b_node = make_node("b");
+        let a = make_component(&a_node, Point::new(0.0, 0.0));
+        let b = make_component(&b_node, Point::new(1000.0, 0.0));
+
+        let (path, label) = CurvedArrowPlacer::lane_geometry(&a, &b, 18.0, 0.0).unwrap();
+
+        // The lane stays offset, but the control points lie on it.
+        let lane_y = path.source().y();
+        assert_approx_eq!(f32, lane_y, 0.216, epsilon = 0.01);
+        assert_approx_eq!(f32, path.destination().y(), lane_y, epsilon = 0.01);
+        for cp in path.control_points() {
+            assert_approx_eq!(f32, cp.y(), lane_y, epsilon = 0.01);
+        }
+        assert_approx_eq!(f32, label.y(), lane_y, epsilon = 0.01);
+    }
+
+    #[test]
+    fn lane_geometry_curvature_scales_bow() {
+        let a_node = make_node("a");
+        let b_node = make_node("b");
+        let a = make_component(&a_node, Point::new(0.0, 0.0));
+        let b = make_component(&b_node, Point::new(1000.0, 0.0));
+
+        let (default, _) =
+            CurvedArrowPlacer::lane_geometry(&a, &b, 18.0, ArrowDefinition::DEFAULT_CURVATURE)
+                .unwrap();
+        let (strong, _) = CurvedArrowPlacer::lane_geometry(&a, &b, 18.0, 1.0).unwrap();
+
+        // Bow measured from the lane line: 18 by default, twice that at 1.
+        let lane_y = default.source().y();
+        assert_approx_eq!(
+            f32,
+            default.control_points()[0].y() - lane_y,
+            18.0,
+            epsilon = 0.01
+        );
+        assert_approx_eq!(
+            f32,
+            strong.control_points()[0].y() - lane_y,
+            36.0,
+            epsilon = 0.01
+        );
+    }
+
     #[test]
     fn lane_geometry_returns_none_for_zero_length_centerline() {
         let a_node = make_node("a");
         let a = make_component(&a_node, Point::new(0.0, 0.0));
-        assert!(CurvedArrowPlacer::lane_geometry(&a, &a, 18.0).is_none());
+        assert!(
+            CurvedArrowPlacer::lane_geometry(&a, &a, 18.0, ArrowDefinition::DEFAULT_CURVATURE)
+                .is_none()
+        );
     }
 
     #[test]
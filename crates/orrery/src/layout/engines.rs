@@ -12,27 +12,39 @@
 mod basic;
 #[cfg(feature = "graphviz")]
 mod graphviz;
+mod grid;
 mod sugiyama;
 
-use std::collections::HashMap;
+use std::{
+    cmp::Reverse,
+    collections::HashMap,
+    hash::{Hash, Hasher},
+    rc::Rc,
+};
 
+use indexmap::IndexMap;
 use log::trace;
 
 use orrery_core::{
-    geometry::{Insets, Size},
+    draw::{
+        Drawable, EdgeRouting, PositionedArrowWithText, Shape, ShapeWithText, Text, TextMeasurer,
+        with_text_measurer,
+    },
+    geometry::{Insets, Point, Size},
     identifier::Id,
-    semantic::LayoutEngine,
+    semantic::{Block, Diagram, LayoutEngine, Relation},
 };
 
 use super::layer::ContentStack;
 use crate::{
     error::RenderError,
     layout::{
-        component::Layout as ComponentLayout,
+        component::{ArrowPlacer, Component, Layout as ComponentLayout, SmartArrowPlacer},
         layer::{LayeredLayout, LayoutContent},
+        routing::OrthogonalRouter,
         sequence::Layout as SequenceLayout,
     },
-    structure::{ComponentGraph, DiagramHierarchy, GraphKind, SequenceGraph},
+    structure::{ComponentGraph, ContainmentScope, DiagramHierarchy, GraphKind, SequenceGraph},
 };
 
 /// Enum to store different layout results based on diagram type.
@@ -79,7 +91,7 @@ pub trait ComponentEngine {
     /// Returns [`RenderError::Layout`] if the layout engine fails to calculate positions.
     fn calculate<'a>(
         &self,
-        graph: &'a ComponentGraph<'a, '_>,
+        graph: &ComponentGraph<'a, '_>,
         embedded_layouts: &EmbeddedLayouts<'a>,
     ) -> Result<ContentStack<ComponentLayout<'a>>, RenderError>;
 }
@@ -104,18 +116,250 @@ pub trait SequenceEngine {
     /// Returns [`RenderError::Layout`] if the layout engine fails to calculate positions.
     fn calculate<'a>(
         &self,
-        graph: &'a SequenceGraph<'a>,
+        graph: &SequenceGraph<'a>,
         embedded_layouts: &EmbeddedLayouts<'a>,
     ) -> Result<ContentStack<SequenceLayout<'a>>, RenderError>;
 }
 
+/// Calculate the shapes of the components in a containment scope, sized to
+/// fit their label and any nested scope or embedded diagram.
+///
+/// Nested scopes and embedded diagrams are laid out first, so their sizes
+/// are looked up in `positioned_content_sizes` and `embedded_layouts`.
+fn calculate_component_shapes<'a>(
+    graph: &ComponentGraph<'a, '_>,
+    containment_scope: &ContainmentScope,
+    padding: Insets,
+    positioned_content_sizes: &HashMap<Id, Size>,
+    embedded_layouts: &EmbeddedLayouts<'a>,
+) -> Result<IndexMap<Id, ShapeWithText<'a>>, RenderError> {
+    let mut component_shapes: IndexMap<Id, ShapeWithText<'a>> = IndexMap::new();
+
+    for node in graph.scope_nodes(containment_scope) {
+        let mut shape = Shape::new(Rc::clone(node.shape_definition()));
+        shape.set_padding(padding);
+        let text = Text::new(node.shape_definition().text(), node.display_text());
+        let mut shape_with_text = ShapeWithText::new(shape, Some(text));
+
+        match node.block() {
+            Block::Diagram(_) => {
+                // Since we process in post-order (innermost to outermost),
+                // embedded diagram layouts should already be calculated and available
+                let layout = embedded_layouts.get(&node.id()).ok_or_else(|| {
+                    RenderError::Layout(format!(
+                        "Embedded layout not found for diagram block '{node}'"
+                    ))
+                })?;
+
+                let content_size = layout.calculate_size();
+                shape_with_text
+                    .set_inner_content_size(content_size)
+                    .map_err(|err| {
+                        RenderError::Layout(format!(
+                            "Failed to set content size for diagram block '{node}': {err}"
+                        ))
+                    })?;
+            }
+            Block::Scope(_) => {
+                let content_size = *positioned_content_sizes.get(&node.id()).ok_or_else(|| {
+                    RenderError::Layout(format!("Scope size not found for node '{node}'"))
+                })?;
+                shape_with_text
+                    .set_inner_content_size(content_size)
+                    .map_err(|err| {
+                        RenderError::Layout(format!(
+                            "Failed to set content size for scope block '{node}': {err}"
+                        ))
+                    })?;
+            }
+            Block::None => {
+                // No content to size, so don't call set_inner_content_size
+            }
+        };
+        component_shapes.insert(node.id(), shape_with_text);
+    }
+
+    Ok(component_shapes)
+}
+
+/// Builds positioned arrows for all visible relations in a containment scope.
+///
+/// Parallel/reverse relations between the same component pair are placed
+/// together so the [`ArrowPlacer`] can offset them. With orthogonal edge
+/// routing, an [`OrthogonalRouter`] routes them around the scope's
+/// components. Relations whose endpoints are not visible at this scope are
+/// skipped.
+fn place_scope_relations<'a>(
+    graph: &ComponentGraph<'a, '_>,
+    containment_scope: &ContainmentScope,
+    components: &[Component<'a>],
+    component_indices: &HashMap<Id, usize>,
+    edge_routing: EdgeRouting,
+    arrow_placer: &SmartArrowPlacer,
+) -> Vec<PositionedArrowWithText<'a>> {
+    let mut buckets: IndexMap<(Id, Id), Vec<&'a Relation>> = IndexMap::new();
+
+    for relation in graph.scope_relations(containment_scope) {
+        let source_id = relation.source();
+        let target_id = relation.target();
+
+        if !component_indices.contains_key(&source_id)
+            || !component_indices.contains_key(&target_id)
+        {
+            continue;
+        }
+
+        // Canonicalize the pair key so (a,b) and (b,a) land in the same bucket.
+        let key = if buckets.contains_key(&(target_id, source_id)) {
+            (target_id, source_id)
+        } else {
+            (source_id, target_id)
+        };
+        buckets.entry(key).or_default().push(relation);
+    }
+
+    let router;
+    let placer: &dyn ArrowPlacer = match edge_routing {
+        EdgeRouting::Straight => arrow_placer,
+        EdgeRouting::Orthogonal => {
+            router = OrthogonalRouter::new(components);
+            &router
+        }
+    };
+
+    buckets
+        .into_iter()
+        .flat_map(|((src_id, tgt_id), bucket)| {
+            let src = &components[component_indices[&src_id]];
+            let tgt = &components[component_indices[&tgt_id]];
+            placer.place(&bucket, src, tgt)
+        })
+        .collect()
+}
+
+/// The axis along which [`place_pinned_components`] moves components.
+#[derive(Debug, Clone, Copy)]
+enum Axis {
+    Horizontal,
+    Vertical,
+}
+
+/// Applies the pins of the components in a containment scope to their
+/// computed `positions`, then moves the other components out of the way.
+///
+/// Components pinned along `axis` keep their position. Every other component
+/// is taken in declaration order and pushed along `axis`, past each pinned or
+/// already placed component it overlaps, leaving `spacing` between them.
+/// Scopes without pins are left untouched.
+fn place_pinned_components<'a>(
+    graph: &ComponentGraph<'a, '_>,
+    containment_scope: &ContainmentScope,
+    positions: &mut HashMap<Id, Point>,
+    component_shapes: &IndexMap<Id, ShapeWithText<'a>>,
+    axis: Axis,
+    spacing: f32,
+) {
+    if graph
+        .scope_nodes(containment_scope)
+        .all(|node| node.pin().is_empty())
+    {
+        return;
+    }
+
+    let mut placed = Vec::new();
+    let mut unpinned = Vec::new();
+    for node in graph.scope_nodes(containment_scope) {
+        let (Some(position), Some(shape)) = (
+            positions.get_mut(&node.id()),
+            component_shapes.get(&node.id()),
+        ) else {
+            continue;
+        };
+        let pin = node.pin();
+        *position = pin.apply(*position);
+
+        let pinned = match axis {
+            Axis::Horizontal => pin.x().is_some(),
+            Axis::Vertical => pin.y().is_some(),
+        };
+        if pinned {
+            placed.push(position.to_bounds(shape.size()));
+        } else {
+            unpinned.push((node.id(), shape.size()));
+        }
+    }
+
+    for (id, size) in unpinned {
+        let position = positions
+            .get_mut(&id)
+            .expect("unpinned components have a position");
+        // Each push clears a blocker for good, so this ends after at most
+        // one push per placed component.
+        while let Some(blocker) = placed
+            .iter()
+            .find(|bounds| position.to_bounds(size).intersects(bounds))
+        {
+            *position = match axis {
+                Axis::Horizontal => position.with_x(blocker.max_x() + spacing + size.width() / 2.0),
+                Axis::Vertical => position.with_y(blocker.max_y() + spacing + size.height() / 2.0),
+            };
+        }
+        placed.push(position.to_bounds(size));
+    }
+}
+
+/// Spacing between and within the ranks of a layered layout.
+///
+/// Unset values fall back to the engine's vertical and horizontal spacing.
+#[derive(Debug, Clone, Copy, Default)]
+struct RankSpacing {
+    rank: Option<f32>,
+    intra_rank: Option<f32>,
+}
+
+impl RankSpacing {
+    /// Returns the spacing of `diagram`, with unset values taken from `self`.
+    fn for_diagram(self, diagram: &Diagram) -> Self {
+        let definition = diagram.definition();
+        Self {
+            rank: definition.rank_spacing().or(self.rank),
+            intra_rank: definition.intra_rank_spacing().or(self.intra_rank),
+        }
+    }
+
+    fn bits(&self) -> (Option<u32>, Option<u32>) {
+        (
+            self.rank.map(f32::to_bits),
+            self.intra_rank.map(f32::to_bits),
+        )
+    }
+}
+
+impl PartialEq for RankSpacing {
+    fn eq(&self, other: &Self) -> bool {
+        self.bits() == other.bits()
+    }
+}
+
+impl Eq for RankSpacing {}
+
+impl Hash for RankSpacing {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bits().hash(state);
+    }
+}
+
+/// Cache key of a configured component engine: the engine type, rank
+/// spacing, edge routing and grid column count.
+type ComponentEngineKey = (LayoutEngine, RankSpacing, EdgeRouting, Option<usize>);
+
 /// Builder for creating and configuring layout engines.
 ///
 /// Builder is not reusable after `build()` is called.
 #[derive(Default)]
 pub struct EngineBuilder {
     // Cache for reusing engines with the same configuration
-    component_engines: HashMap<LayoutEngine, Box<dyn ComponentEngine>>,
+    component_engines: HashMap<ComponentEngineKey, Box<dyn ComponentEngine>>,
     sequence_engines: HashMap<LayoutEngine, Box<dyn SequenceEngine>>,
 
     // Configuration options
@@ -124,6 +368,8 @@ pub struct EngineBuilder {
     horizontal_spacing: f32,
     vertical_spacing: f32,
     event_padding: f32,
+    rank_spacing: RankSpacing,
+    text_measurer: Option<Rc<dyn TextMeasurer>>,
 }
 
 impl EngineBuilder {
@@ -162,11 +408,47 @@ impl EngineBuilder {
         self
     }
 
+    /// Set the spacing between ranks of layered layouts.
+    ///
+    /// Defaults to the vertical spacing. A diagram's `rank_spacing` attribute
+    /// takes precedence.
+    pub fn with_rank_spacing(mut self, spacing: f32) -> Self {
+        self.rank_spacing.rank = Some(spacing);
+        self
+    }
+
+    /// Set the spacing between elements within a rank of layered layouts.
+    ///
+    /// Defaults to the horizontal spacing. A diagram's `intra_rank_spacing`
+    /// attribute takes precedence.
+    pub fn with_intra_rank_spacing(mut self, spacing: f32) -> Self {
+        self.rank_spacing.intra_rank = Some(spacing);
+        self
+    }
+
+    /// Set the measurer every engine sizes text with.
+    ///
+    /// Defaults to the shared system fonts measurer.
+    pub fn with_text_measurer(mut self, measurer: Rc<dyn TextMeasurer>) -> Self {
+        self.text_measurer = Some(measurer);
+        self
+    }
+
     /// Get a component engine of the specified type with configured options.
-    pub fn component_engine(&mut self, engine_type: LayoutEngine) -> &dyn ComponentEngine {
+    ///
+    /// `rank_spacing` overrides the builder's rank spacing for layered engines,
+    /// `edge_routing` selects how every engine routes relations, and `columns`
+    /// sets the number of columns of the grid engine.
+    fn component_engine(
+        &mut self,
+        engine_type: LayoutEngine,
+        rank_spacing: RankSpacing,
+        edge_routing: EdgeRouting,
+        columns: Option<usize>,
+    ) -> &dyn ComponentEngine {
         let engine = self
             .component_engines
-            .entry(engine_type)
+            .entry((engine_type, rank_spacing, edge_routing, columns))
             .or_insert_with(|| {
                 let engine: Box<dyn ComponentEngine> = match engine_type {
                     LayoutEngine::Basic => {
@@ -174,20 +456,35 @@ impl EngineBuilder {
                         // Configure the engine with our settings
                         e.set_padding(self.padding);
                         e.set_min_spacing(self.min_spacing);
+                        e.set_edge_routing(edge_routing);
                         Box::new(e)
                     }
                     LayoutEngine::Sugiyama => {
                         let mut e = sugiyama::Component::new();
                         // Configure the hierarchical engine
+                        // Ranks are stacked vertically
+                        e.set_horizontal_spacing(
+                            rank_spacing.intra_rank.unwrap_or(self.horizontal_spacing),
+                        );
+                        e.set_vertical_spacing(rank_spacing.rank.unwrap_or(self.vertical_spacing));
+                        e.set_container_padding(self.padding);
+                        e.set_edge_routing(edge_routing);
+                        Box::new(e)
+                    }
+                    LayoutEngine::Grid => {
+                        let mut e = grid::Component::new();
+                        e.set_columns(columns);
                         e.set_horizontal_spacing(self.horizontal_spacing);
                         e.set_vertical_spacing(self.vertical_spacing);
                         e.set_container_padding(self.padding);
+                        e.set_edge_routing(edge_routing);
                         Box::new(e)
                     }
                     #[cfg(feature = "graphviz")]
                     LayoutEngine::Graphviz => {
                         let mut e = graphviz::Component::new();
                         e.set_container_padding(self.padding);
+                        e.set_edge_routing(edge_routing);
                         Box::new(e)
                     }
                 };
@@ -222,8 +519,20 @@ impl EngineBuilder {
     /// # Errors
     /// Returns `RenderError::Layout` if any layout engine fails to calculate positions.
     pub fn build<'a>(
+        self,
+        collection: &DiagramHierarchy<'a, '_>,
+    ) -> Result<LayeredLayout<'a>, RenderError> {
+        match self.text_measurer.clone() {
+            Some(measurer) => with_text_measurer(measurer, || self.build_layers(collection)),
+            None => self.build_layers(collection),
+        }
+    }
+
+    /// Calculates the layouts of `collection` and flattens them into layers,
+    /// as described in [`build`](Self::build).
+    fn build_layers<'a>(
         mut self,
-        collection: &'a DiagramHierarchy<'a, '_>,
+        collection: &DiagramHierarchy<'a, '_>,
     ) -> Result<LayeredLayout<'a>, RenderError> {
         let mut layered_layout = LayeredLayout::new();
 
@@ -246,7 +555,15 @@ impl EngineBuilder {
             let diagram = graphed_diagram.diagram();
             let layout_result = match graphed_diagram.graph_kind() {
                 GraphKind::ComponentGraph(graph) => {
-                    let engine = self.component_engine(diagram.layout_engine());
+                    let rank_spacing = self.rank_spacing.for_diagram(diagram);
+                    let edge_routing = diagram.definition().edge_routing();
+                    let columns = diagram.definition().columns();
+                    let engine = self.component_engine(
+                        diagram.layout_engine(),
+                        rank_spacing,
+                        edge_routing,
+                        columns,
+                    );
 
                     let layout = engine.calculate(graph, &layout_info)?;
                     LayoutResult::Component(layout)
@@ -278,8 +595,10 @@ impl EngineBuilder {
             }
         }
 
-        // Second phase: populate embedded_diagrams by checking all layouts for embedded content
-        for (layer_idx, layout_result) in root_layout
+        // Second phase: populate embedded_diagrams by checking all layouts for embedded content.
+        // Layers are added innermost first, so walking them by descending index adjusts every
+        // container before the diagrams embedded in it, whose offsets build on the container's.
+        let mut layouts: Vec<_> = root_layout
             .iter()
             .map(|(idx, result)| (*idx, result))
             .chain(
@@ -287,7 +606,10 @@ impl EngineBuilder {
                     .iter()
                     .map(|(&id, result)| (container_element_to_layer[&id], result)),
             )
-        {
+            .collect();
+        layouts.sort_unstable_by_key(|&(layer_idx, _)| Reverse(layer_idx));
+
+        for (layer_idx, layout_result) in layouts {
             match layout_result {
                 LayoutResult::Component(layout) => {
                     for positioned_content in layout.iter() {
@@ -3,23 +3,25 @@
 //! This module provides a layout engine for component diagrams
 //! using a simple, deterministic algorithm.
 
-use std::{
-    collections::{HashMap, HashSet, VecDeque},
-    rc::Rc,
-};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use indexmap::IndexMap;
 
 use orrery_core::{
-    draw::{Drawable, PositionedArrowWithText, Shape, ShapeWithText, Text},
+    draw::{Drawable, EdgeRouting, ShapeWithText},
     geometry::{Insets, Point, Size},
     identifier::Id,
-    semantic::{Block, Relation},
+    semantic::Relation,
 };
 
 use crate::{
     error::RenderError,
     layout::{
-        component::{self, ArrowPlacer, Component, Layout, SmartArrowPlacer},
-        engines::{ComponentEngine, EmbeddedLayouts},
+        component::{self, Component, Layout, SmartArrowPlacer},
+        engines::{
+            Axis, ComponentEngine, EmbeddedLayouts, calculate_component_shapes,
+            place_pinned_components, place_scope_relations,
+        },
         layer::{ContentStack, PositionedContent},
     },
     structure::{ComponentGraph, ContainmentScope},
@@ -39,6 +41,8 @@ pub struct Engine {
     text_padding: f32,
     /// Minimum spacing between adjacent components.
     min_spacing: f32,
+    /// How relations are routed between components.
+    edge_routing: EdgeRouting,
     arrow_placer: SmartArrowPlacer,
 }
 
@@ -70,6 +74,12 @@ impl Engine {
         self
     }
 
+    /// Set how relations are routed between components.
+    pub fn set_edge_routing(&mut self, routing: EdgeRouting) -> &mut Self {
+        self.edge_routing = routing;
+        self
+    }
+
     /// Calculate the layout for a component diagram.
     ///
     /// # Arguments
@@ -82,7 +92,7 @@ impl Engine {
     /// Returns [`RenderError::Layout`] if position or shape calculation fails.
     pub fn calculate_layout<'a>(
         &self,
-        graph: &'a ComponentGraph<'a, '_>,
+        graph: &ComponentGraph<'a, '_>,
         embedded_layouts: &EmbeddedLayouts<'a>,
     ) -> Result<ContentStack<Layout<'a>>, RenderError> {
         let mut content_stack = ContentStack::<Layout<'a>>::new();
@@ -90,15 +100,24 @@ impl Engine {
 
         for containment_scope in graph.containment_scopes() {
             // Calculate component shapes - they contain all sizing information
-            let mut component_shapes = self.calculate_component_shapes(
+            let mut component_shapes = calculate_component_shapes(
                 graph,
                 containment_scope,
+                self.padding,
                 &positioned_content_sizes,
                 embedded_layouts,
             )?;
 
             // Calculate positions for components
-            let positions = self.positions(graph, containment_scope, &component_shapes)?;
+            let mut positions = self.positions(graph, containment_scope, &component_shapes)?;
+            place_pinned_components(
+                graph,
+                containment_scope,
+                &mut positions,
+                &component_shapes,
+                Axis::Vertical,
+                self.padding.vertical_sum() / 2.0,
+            );
 
             // Build the final component list using the pre-configured shapes
             let components: Vec<Component> = graph
@@ -109,11 +128,12 @@ impl Engine {
                             "Position not found for node '{node}' during component layout"
                         ))
                     })?;
-                    let shape_with_text = component_shapes.remove(&node.id()).ok_or_else(|| {
-                        RenderError::Layout(format!(
-                            "Shape not found for node '{node}' during component layout"
-                        ))
-                    })?;
+                    let shape_with_text =
+                        component_shapes.shift_remove(&node.id()).ok_or_else(|| {
+                            RenderError::Layout(format!(
+                                "Shape not found for node '{node}' during component layout"
+                            ))
+                        })?;
 
                     Ok(Component::new(node, shape_with_text, position))
                 })
@@ -126,14 +146,20 @@ impl Engine {
                 .map(|(idx, component)| (component.node_id(), idx))
                 .collect();
 
-            let relations = self.place_scope_relations(
+            let relations = place_scope_relations(
                 graph,
                 containment_scope,
                 &components,
                 &component_indices,
+                self.edge_routing,
+                &self.arrow_placer,
             );
 
-            let positioned_content = PositionedContent::new(Layout::new(components, relations));
+            let positioned_content = PositionedContent::new(
+                Layout::new(components, relations)
+                    .with_groups(containment_scope.groups())
+                    .with_notes(containment_scope.notes()),
+            );
 
             if let Some(container) = containment_scope.container() {
                 // If this layer is a container, we need to adjust its size based on its contents
@@ -144,118 +170,17 @@ impl Engine {
         }
 
         component::adjust_positioned_contents_offset(&mut content_stack, graph)?;
+        component::place_cross_scope_relations(&mut content_stack, graph);
 
         Ok(content_stack)
     }
 
-    /// Builds positioned arrows for all visible relations in a containment scope.
-    ///
-    /// Parallel/reverse relations between the same component pair are placed
-    /// together so the [`ArrowPlacer`] can offset them. Relations whose
-    /// endpoints are not visible at this scope are skipped.
-    fn place_scope_relations<'a>(
-        &self,
-        graph: &'a ComponentGraph<'a, '_>,
-        containment_scope: &ContainmentScope,
-        components: &[Component<'a>],
-        component_indices: &HashMap<Id, usize>,
-    ) -> Vec<PositionedArrowWithText<'a>> {
-        let mut buckets: HashMap<(Id, Id), Vec<&'a Relation>> = HashMap::new();
-
-        for relation in graph.scope_relations(containment_scope) {
-            let source_id = relation.source();
-            let target_id = relation.target();
-
-            if !component_indices.contains_key(&source_id)
-                || !component_indices.contains_key(&target_id)
-            {
-                continue;
-            }
-
-            // Canonicalize the pair key so (a,b) and (b,a) land in the same bucket.
-            let key = if buckets.contains_key(&(target_id, source_id)) {
-                (target_id, source_id)
-            } else {
-                (source_id, target_id)
-            };
-            buckets.entry(key).or_default().push(relation);
-        }
-
-        buckets
-            .into_iter()
-            .flat_map(|((src_id, tgt_id), bucket)| {
-                let src = &components[component_indices[&src_id]];
-                let tgt = &components[component_indices[&tgt_id]];
-                self.arrow_placer.place(&bucket, src, tgt)
-            })
-            .collect()
-    }
-
-    /// Calculate component shapes with proper content size and padding.
-    fn calculate_component_shapes<'a>(
-        &self,
-        graph: &'a ComponentGraph<'a, '_>,
-        containment_scope: &ContainmentScope,
-        positioned_content_sizes: &HashMap<Id, Size>,
-        embedded_layouts: &EmbeddedLayouts<'a>,
-    ) -> Result<HashMap<Id, ShapeWithText<'a>>, RenderError> {
-        let mut component_shapes: HashMap<Id, ShapeWithText<'a>> = HashMap::new();
-
-        // TODO: move it to the best place.
-        for node in graph.scope_nodes(containment_scope) {
-            let mut shape = Shape::new(Rc::clone(node.shape_definition()));
-            shape.set_padding(self.padding);
-            let text = Text::new(node.shape_definition().text(), node.display_text());
-            let mut shape_with_text = ShapeWithText::new(shape, Some(text));
-
-            match node.block() {
-                Block::Diagram(_) => {
-                    // Since we process in post-order (innermost to outermost),
-                    // embedded diagram layouts should already be calculated and available
-                    let layout = embedded_layouts.get(&node.id()).ok_or_else(|| {
-                        RenderError::Layout(format!(
-                            "Embedded layout not found for diagram block '{node}'"
-                        ))
-                    })?;
-
-                    let content_size = layout.calculate_size();
-                    shape_with_text
-                        .set_inner_content_size(content_size)
-                        .map_err(|err| {
-                            RenderError::Layout(format!(
-                                "Failed to set content size for diagram block '{node}': {err}"
-                            ))
-                        })?;
-                }
-                Block::Scope(_) => {
-                    let content_size =
-                        *positioned_content_sizes.get(&node.id()).ok_or_else(|| {
-                            RenderError::Layout(format!("Scope size not found for node '{node}'"))
-                        })?;
-                    shape_with_text
-                        .set_inner_content_size(content_size)
-                        .map_err(|err| {
-                            RenderError::Layout(format!(
-                                "Failed to set content size for scope block '{node}': {err}"
-                            ))
-                        })?;
-                }
-                Block::None => {
-                    // No content to size, so don't call set_inner_content_size
-                }
-            };
-            component_shapes.insert(node.id(), shape_with_text);
-        }
-
-        Ok(component_shapes)
-    }
-
     /// Calculate positions for components in a containment scope.
     fn positions<'a>(
         &self,
-        graph: &'a ComponentGraph<'a, '_>,
+        graph: &ComponentGraph<'a, '_>,
         containment_scope: &ContainmentScope,
-        component_shapes: &HashMap<Id, ShapeWithText<'a>>,
+        component_shapes: &IndexMap<Id, ShapeWithText<'a>>,
     ) -> Result<HashMap<Id, Point>, RenderError> {
         // Step 1: Assign layers for the top-level nodes
         let layers = Self::assign_layers_for_containment_scope_graph(graph, containment_scope)?;
@@ -271,13 +196,13 @@ impl Engine {
     /// Calculate metrics for each layer: widths and spacings between layers.
     fn calculate_layer_metrics<'a>(
         &self,
-        graph: &'a ComponentGraph<'a, '_>,
+        graph: &ComponentGraph<'a, '_>,
         containment_scope: &ContainmentScope,
         layers: &[Vec<Id>],
-        component_shapes: &HashMap<Id, ShapeWithText<'a>>,
+        component_shapes: &IndexMap<Id, ShapeWithText<'a>>,
     ) -> Result<(Vec<f32>, Vec<f32>), RenderError> {
         // Calculate max width for each layer
-        let layer_widths: Vec<f32> = layers
+        let mut layer_widths: Vec<f32> = layers
             .iter()
             .map(|layer| {
                 layer.iter().try_fold(0.0_f32, |max_width, &node_idx| {
@@ -295,6 +220,14 @@ impl Engine {
             })
             .collect::<Result<Vec<f32>, RenderError>>()?;
 
+        // Layers left empty by `min_length` are as wide as the widest layer
+        let widest = layer_widths.iter().copied().fold(0.0, f32::max);
+        for (width, layer) in layer_widths.iter_mut().zip(layers) {
+            if layer.is_empty() {
+                *width = widest;
+            }
+        }
+
         // Initialize spacings with default padding
         let mut layer_spacings =
             vec![self.padding.horizontal_sum() / 2.0; layers.len().saturating_sub(1)];
@@ -376,7 +309,7 @@ impl Engine {
         &self,
         layers: &[Vec<Id>],
         layer_x_positions: &[f32],
-        component_shapes: &HashMap<Id, ShapeWithText<'a>>,
+        component_shapes: &IndexMap<Id, ShapeWithText<'a>>,
     ) -> Result<HashMap<Id, Point>, RenderError> {
         let mut positions = HashMap::new();
 
@@ -493,14 +426,74 @@ impl Engine {
             }
         }
 
-        Ok(layers)
+        Ok(Self::apply_min_lengths(graph, containment_scope, layers))
+    }
+
+    /// Move nodes to later layers until every relation spans at least its
+    /// `min_length` layers.
+    ///
+    /// Relations that already pointed to a later layer keep their span, so
+    /// the nodes after a stretched relation move along with its target.
+    /// Layers left empty by the move are kept to show the distance.
+    fn apply_min_lengths(
+        graph: &ComponentGraph,
+        containment_scope: &ContainmentScope,
+        layers: Vec<Vec<Id>>,
+    ) -> Vec<Vec<Id>> {
+        let relations: Vec<_> = graph.scope_relations(containment_scope).collect();
+        if relations
+            .iter()
+            .all(|relation| relation.arrow_definition().min_length() <= 1)
+        {
+            return layers;
+        }
+
+        let mut node_layers: HashMap<Id, usize> = layers
+            .iter()
+            .enumerate()
+            .flat_map(|(layer, ids)| ids.iter().map(move |&id| (id, layer)))
+            .collect();
+        let spans: Vec<_> = relations
+            .iter()
+            .filter_map(|relation| {
+                let source = *node_layers.get(&relation.source())?;
+                let target = *node_layers.get(&relation.target())?;
+                let span = relation
+                    .arrow_definition()
+                    .min_length()
+                    .max(target.saturating_sub(source));
+                (relation.source() != relation.target() && (target > source || span > 1))
+                    .then_some((relation.source(), relation.target(), span))
+            })
+            .collect();
+
+        // Bounded by the node count so that cycles cannot stretch forever.
+        for _ in 0..node_layers.len() {
+            let mut changed = false;
+            for &(source, target, span) in &spans {
+                let needed = node_layers[&source] + span;
+                if node_layers[&target] < needed {
+                    node_layers.insert(target, needed);
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        let mut stretched = vec![Vec::new(); node_layers.values().max().map_or(0, |max| max + 1)];
+        for id in layers.into_iter().flatten() {
+            stretched[node_layers[&id]].push(id);
+        }
+        stretched
     }
 }
 
 impl ComponentEngine for Engine {
     fn calculate<'a>(
         &self,
-        graph: &'a ComponentGraph<'a, '_>,
+        graph: &ComponentGraph<'a, '_>,
         embedded_layouts: &EmbeddedLayouts<'a>,
     ) -> Result<ContentStack<Layout<'a>>, RenderError> {
         self.calculate_layout(graph, embedded_layouts)
@@ -4,14 +4,17 @@
 
 use std::{cell::RefCell, cmp::Ordering, collections::HashMap, f32, rc::Rc};
 
+use indexmap::IndexMap;
+
 use orrery_core::{
     draw::{
-        Arrow, ArrowPath, ArrowStyle, ArrowWithText, Drawable, Fragment, Lifeline,
-        Note as DrawNote, PositionedArrowWithText, PositionedDrawable, Shape, ShapeWithText, Text,
+        Arrow, ArrowPath, ArrowStyle, ArrowWithText, Drawable, Fragment,
+        Highlight as DrawHighlight, Lifeline, Note as DrawNote, PositionedArrowWithText,
+        PositionedDrawable, Shape, ShapeWithText, Text,
     },
     geometry::{Insets, Point, Size},
     identifier::Id,
-    semantic::{Block, Note, NoteAlign, Relation},
+    semantic::{Block, Highlight, Note, NoteAlign, Relation},
 };
 
 use crate::{
@@ -138,11 +141,13 @@ impl<'a, 'b> Message<'a, 'b> {
 }
 
 /// Collected output from [`Engine::process_events`]: positioned arrows,
-/// activation boxes, fragments, notes, and the final lifeline Y coordinate.
+/// activation boxes, fragments, highlight bands, notes, and the final lifeline
+/// Y coordinate.
 type ProcessEventsResult<'a> = (
     Vec<PositionedArrowWithText<'a>>,
     Vec<ActivationBox>,
     Vec<PositionedDrawable<Fragment>>,
+    Vec<PositionedDrawable<DrawHighlight>>,
     Vec<PositionedDrawable<DrawNote>>,
     f32,
 );
@@ -293,7 +298,7 @@ impl Engine {
     /// Returns [`RenderError::Layout`] if position or shape calculation fails.
     pub fn calculate_layout<'a>(
         &self,
-        graph: &'a SequenceGraph<'a>,
+        graph: &SequenceGraph<'a>,
         embedded_layouts: &EmbeddedLayouts<'a>,
     ) -> Result<ContentStack<Layout<'a>>, RenderError> {
         // Create shapes with text for participants
@@ -363,7 +368,7 @@ impl Engine {
         );
 
         // Create participants and store their indices
-        let mut components: HashMap<Id, Component> = HashMap::new();
+        let mut components: IndexMap<Id, Component> = IndexMap::new();
         for (i, node) in graph.nodes().enumerate() {
             let shape_with_text = participant_shapes.remove(&node.id()).ok_or_else(|| {
                 RenderError::Layout(format!("Participant shape not found for node '{node}'"))
@@ -381,11 +386,11 @@ impl Engine {
             .max_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal))
             .unwrap_or_default();
 
-        let (arrows, activations, fragments, notes, lifeline_end) =
+        let (arrows, activations, fragments, highlights, notes, lifeline_end) =
             self.process_events(graph, participants_height, &components)?;
 
         // Update lifeline ends to match diagram height and finalize lifelines
-        let participants: HashMap<Id, Participant<'a>> = components
+        let participants: IndexMap<Id, Participant<'a>> = components
             .into_iter()
             .map(|(id, component)| {
                 // Rebuild the positioned lifeline with the final height
@@ -407,6 +412,7 @@ impl Engine {
             arrows,
             activations,
             fragments,
+            highlights,
             notes,
             lifeline_end,
         );
@@ -622,6 +628,9 @@ impl Engine {
     /// - FragmentStart / FragmentSectionStart / FragmentSectionEnd /
     ///   FragmentEnd: maintain a [`FragmentTiming`] stack; the closing event
     ///   emits a positioned [`Fragment`].
+    /// - HighlightStart / HighlightEnd: maintain a stack of open highlight
+    ///   bands; the closing event emits a positioned [`DrawHighlight`] spanning
+    ///   all participants.
     /// - Note: emits a positioned [`DrawNote`] at `current_y`.
     ///
     /// After all events are processed, queued messages are converted into
@@ -641,15 +650,17 @@ impl Engine {
         &self,
         graph: &SequenceGraph<'a>,
         participants_height: f32,
-        components: &HashMap<Id, Component<'a>>,
+        components: &IndexMap<Id, Component<'a>>,
     ) -> Result<ProcessEventsResult<'a>, RenderError> {
         let mut messages: Vec<Message<'a, '_>> = Vec::new();
         let mut activation_boxes: Vec<ActivationBox> = Vec::new();
         let mut fragments: Vec<PositionedDrawable<Fragment>> = Vec::new();
+        let mut highlights: Vec<PositionedDrawable<DrawHighlight>> = Vec::new();
         let mut notes: Vec<PositionedDrawable<DrawNote>> = Vec::new();
 
         let mut activation_stack: HashMap<Id, Vec<Rc<RefCell<ActivationTiming>>>> = HashMap::new();
         let mut fragment_stack: Vec<FragmentTiming> = Vec::new();
+        let mut highlight_stack: Vec<(&Highlight, f32)> = Vec::new();
 
         // Initial Y is the top edge of the first event area.
         let mut current_y = self.top_margin + participants_height + self.event_padding;
@@ -762,6 +773,22 @@ impl Engine {
                     fragments.push(fragment);
                     current_y += fragment_bottom_padding + self.event_padding;
                 }
+                SequenceEvent::HighlightStart(highlight) => {
+                    highlight_stack.push((highlight, current_y));
+                    let label_height = highlight
+                        .definition()
+                        .label_size(highlight.label())
+                        .height();
+                    current_y += label_height + self.event_padding;
+                }
+                SequenceEvent::HighlightEnd => {
+                    let (highlight, start_y) =
+                        highlight_stack.pop().expect("highlight stack is empty");
+                    highlights.push(Self::create_positioned_highlight(
+                        highlight, components, start_y, current_y,
+                    ));
+                    current_y += self.event_padding;
+                }
                 SequenceEvent::Note(note) => {
                     let positioned_note =
                         self.create_positioned_note(note, components, current_y)?;
@@ -774,7 +801,14 @@ impl Engine {
         }
         let arrows = self.position_messages(messages);
 
-        Ok((arrows, activation_boxes, fragments, notes, current_y))
+        Ok((
+            arrows,
+            activation_boxes,
+            fragments,
+            highlights,
+            notes,
+            current_y,
+        ))
     }
 
     /// Computes the minimum bounding [`Size`] for a message slot.
@@ -823,6 +857,33 @@ impl Engine {
         Size::new(self.self_loop_min_size.width(), height)
     }
 
+    /// Create a positioned highlight band for a sequence diagram.
+    ///
+    /// The band spans the full width of all participants and the vertical range
+    /// between `start_y` and `end_y`.
+    fn create_positioned_highlight(
+        highlight: &Highlight,
+        components: &IndexMap<Id, Component<'_>>,
+        start_y: f32,
+        end_y: f32,
+    ) -> PositionedDrawable<DrawHighlight> {
+        let (min_x, max_x) = components
+            .values()
+            .map(|component| component.bounds())
+            .fold((f32::MAX, f32::MIN), |(min_x, max_x), bounds| {
+                (min_x.min(bounds.min_x()), max_x.max(bounds.max_x()))
+            });
+
+        let drawable = DrawHighlight::new(
+            Rc::clone(highlight.definition()),
+            highlight.label().to_string(),
+            Size::new(max_x - min_x, end_y - start_y),
+        );
+        let position = Point::new((min_x + max_x) / 2.0, (start_y + end_y) / 2.0);
+
+        PositionedDrawable::new(drawable).with_position(position)
+    }
+
     /// Create a positioned note drawable for a sequence diagram.
     ///
     /// Calculates the appropriate position and width for a note based on the participants
@@ -840,7 +901,7 @@ impl Engine {
     fn create_positioned_note<'a>(
         &self,
         note: &Note,
-        components: &HashMap<Id, Component<'a>>,
+        components: &IndexMap<Id, Component<'a>>,
         current_y: f32,
     ) -> Result<PositionedDrawable<DrawNote>, RenderError> {
         const NOTE_SPACING: f32 = 20.0; // Spacing between note and participant lifeline
@@ -909,7 +970,7 @@ impl Engine {
 impl SequenceEngine for Engine {
     fn calculate<'a>(
         &self,
-        graph: &'a SequenceGraph<'a>,
+        graph: &SequenceGraph<'a>,
         embedded_layouts: &EmbeddedLayouts<'a>,
     ) -> Result<ContentStack<Layout<'a>>, RenderError> {
         self.calculate_layout(graph, embedded_layouts)
@@ -936,10 +997,15 @@ mod tests {
     use super::*;
 
     use orrery_core::{
-        draw::{ActivationBoxDefinition, ArrowDefinition, ArrowDirection, RectangleDefinition},
-        semantic::Node,
+        draw::{
+            ActivationBoxDefinition, ArrowDefinition, ArrowDirection, DiagramDefinition,
+            HighlightDefinition, RectangleDefinition,
+        },
+        semantic::{Diagram, DiagramKind, Element, LayoutEngine, Node, Scope},
     };
 
+    use crate::structure::{DiagramHierarchy, GraphKind};
+
     fn make_relation(source: Id, target: Id, label: Option<&str>) -> Relation {
         let mut def = ArrowDefinition::default();
         def.set_style(ArrowStyle::Straight);
@@ -966,6 +1032,66 @@ mod tests {
         Component::new(node, shape_with_text, position)
     }
 
+    #[test]
+    fn test_highlight_spans_enclosed_messages_and_all_participants() {
+        let (a, b, c) = (Id::new("a"), Id::new("b"), Id::new("c"));
+        let highlight = Highlight::new(
+            "during transaction".to_string(),
+            vec![
+                Element::Relation(make_relation(a, b, Some("begin"))),
+                Element::Relation(make_relation(b, c, Some("commit"))),
+            ],
+            Rc::new(HighlightDefinition::new()),
+        );
+        let diagram = Diagram::new(
+            DiagramKind::Sequence,
+            Scope::new(vec![
+                Element::Node(make_node("a")),
+                Element::Node(make_node("b")),
+                Element::Node(make_node("c")),
+                Element::Relation(make_relation(a, b, Some("before"))),
+                Element::Highlight(highlight),
+                Element::Relation(make_relation(c, a, Some("after"))),
+            ]),
+            LayoutEngine::Basic,
+            Rc::new(DiagramDefinition::default()),
+        );
+
+        let hierarchy = DiagramHierarchy::from_diagram(&diagram).unwrap();
+        let (_, graphed_diagram) = hierarchy.iter_post_order().next().unwrap();
+        let GraphKind::SequenceGraph(graph) = graphed_diagram.graph_kind() else {
+            panic!("Expected a sequence graph");
+        };
+
+        let components: IndexMap<Id, Component> = graph
+            .nodes()
+            .zip([50.0, 200.0, 350.0])
+            .map(|(node, x)| (node.id(), make_component(node, Point::new(x, 20.0))))
+            .collect();
+
+        let engine = Engine::new();
+        let (arrows, _, _, highlights, _, _) =
+            engine.process_events(graph, 40.0, &components).unwrap();
+        assert_eq!(arrows.len(), 4);
+        assert_eq!(highlights.len(), 1);
+
+        let band = highlights[0].bounds();
+        assert_eq!(band.min_x(), components[&a].bounds().min_x());
+        assert_eq!(band.max_x(), components[&c].bounds().max_x());
+
+        let message_y = |index: usize| arrows[index].path().source().y();
+        assert!(message_y(0) < band.min_y());
+        assert!(band.min_y() < message_y(1) && message_y(1) < band.max_y());
+        assert!(band.min_y() < message_y(2) && message_y(2) < band.max_y());
+        assert!(band.max_y() < message_y(3));
+
+        // The label is reserved above the first enclosed message.
+        let label_height = HighlightDefinition::new()
+            .label_size("during transaction")
+            .height();
+        assert!(message_y(1) - band.min_y() > label_height);
+    }
+
     #[test]
     fn test_position_messages_multiple() {
         let a_id = Id::new("a");
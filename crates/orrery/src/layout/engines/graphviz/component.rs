@@ -7,19 +7,26 @@
 
 use std::{collections::HashMap, rc::Rc};
 
+use indexmap::IndexMap;
+use log::warn;
+
 use orrery_core::{
-    draw::{Arrow, ArrowWithText, Drawable, PositionedArrowWithText, Shape, ShapeWithText, Text},
+    draw::{Arrow, ArrowWithText, Drawable, EdgeRouting, PositionedArrowWithText},
     geometry::{Insets, Size},
     identifier::Id,
-    semantic::Block,
+    semantic::Relation,
 };
 
 use crate::{
     error::RenderError,
     layout::{
-        component::{self, Component, Layout},
-        engines::{ComponentEngine, EmbeddedLayouts, graphviz::dot_bridge::DotBridge},
+        component::{self, ArrowPlacer, Component, Layout},
+        engines::{
+            ComponentEngine, EmbeddedLayouts, calculate_component_shapes,
+            graphviz::dot_bridge::DotBridge,
+        },
         layer::{ContentStack, PositionedContent},
+        routing::OrthogonalRouter,
     },
     structure::{ComponentGraph, ContainmentScope},
 };
@@ -41,6 +48,8 @@ use crate::{
 pub struct Engine {
     /// Padding inside container components.
     container_padding: Insets,
+    /// How relations are routed between components.
+    edge_routing: EdgeRouting,
 }
 
 impl Engine {
@@ -48,6 +57,7 @@ impl Engine {
     pub fn new() -> Self {
         Self {
             container_padding: Insets::uniform(20.0),
+            edge_routing: EdgeRouting::default(),
         }
     }
 
@@ -57,6 +67,15 @@ impl Engine {
         self
     }
 
+    /// Sets how relations are routed between components.
+    ///
+    /// With [`EdgeRouting::Orthogonal`] the Graphviz splines are discarded and
+    /// relations are routed by an [`OrthogonalRouter`] instead.
+    pub fn set_edge_routing(&mut self, routing: EdgeRouting) -> &mut Self {
+        self.edge_routing = routing;
+        self
+    }
+
     /// Calculates a component layout by delegating to Graphviz.
     ///
     /// Iterates containment scopes in post-order: inner scopes are laid out
@@ -78,7 +97,7 @@ impl Engine {
     /// cannot be parsed, or an embedded layout is missing.
     fn calculate_layout<'a>(
         &self,
-        graph: &'a ComponentGraph<'a, '_>,
+        graph: &ComponentGraph<'a, '_>,
         embedded_layouts: &EmbeddedLayouts<'a>,
     ) -> Result<ContentStack<Layout<'a>>, RenderError> {
         let mut content_stack = ContentStack::<Layout<'a>>::new();
@@ -101,6 +120,7 @@ impl Engine {
         }
 
         component::adjust_positioned_contents_offset(&mut content_stack, graph)?;
+        component::place_cross_scope_relations(&mut content_stack, graph);
 
         Ok(content_stack)
     }
@@ -108,7 +128,7 @@ impl Engine {
     /// Lays out a single containment scope.
     ///
     /// First computes sized shapes for every node in the scope via
-    /// [`calculate_component_shapes`](Self::calculate_component_shapes), then
+    /// [`calculate_component_shapes`], then
     /// feeds the resulting sizes into [`DotBridge`] to obtain Graphviz-computed
     /// node positions and edge spline paths. Finally, assembles the positioned
     /// components and relations into a [`Layout`] wrapped in
@@ -131,17 +151,18 @@ impl Engine {
     /// invocation, or result assembly fails.
     fn layout_containment_scope<'a>(
         &self,
-        graph: &'a ComponentGraph<'a, '_>,
-        containment_scope: &ContainmentScope<'a, 'a>,
+        graph: &ComponentGraph<'a, '_>,
+        containment_scope: &ContainmentScope<'a, '_>,
         positioned_content_sizes: &HashMap<Id, Size>,
         embedded_layouts: &EmbeddedLayouts<'a>,
     ) -> Result<PositionedContent<Layout<'a>>, RenderError> {
         if containment_scope.nodes_count() == 0 {
             return Ok(PositionedContent::new(Layout::new(vec![], vec![])));
         }
-        let mut component_shapes = self.calculate_component_shapes(
+        let mut component_shapes = calculate_component_shapes(
             graph,
             containment_scope,
+            self.container_padding,
             positioned_content_sizes,
             embedded_layouts,
         )?;
@@ -160,11 +181,15 @@ impl Engine {
         let components: Vec<Component> = graph
             .scope_nodes(containment_scope)
             .map(|node| {
+                if !node.pin().is_empty() {
+                    // Graphviz routes edges itself, so moving a node afterwards would detach them
+                    warn!(node_id:% = node; "Graphviz layout ignores component `x`/`y` positions");
+                }
                 let position = layout_result.position(node.id()).ok_or_else(|| {
                     RenderError::Layout(format!("position not found for `{node}`"))
                 })?;
                 let shape_with_text = component_shapes
-                    .remove(&node.id())
+                    .shift_remove(&node.id())
                     .ok_or_else(|| RenderError::Layout(format!("shape not found for `{node}`")))?;
 
                 Ok(Component::new(node, shape_with_text, position))
@@ -172,77 +197,70 @@ impl Engine {
             .collect::<Result<_, RenderError>>()?;
 
         // Build relations from the Graphviz edge paths
-        let relations: Vec<PositionedArrowWithText> = layout_result
-            .into_edge_paths()
-            .into_iter()
-            .map(|(relation, path)| {
-                let arrow_def = Rc::clone(relation.arrow_definition());
-                let arrow = Arrow::new(arrow_def, relation.arrow_direction());
-                let arrow_with_text = ArrowWithText::new(arrow, relation.text());
-                PositionedArrowWithText::new(arrow_with_text, path)
-            })
-            .collect();
+        let edge_paths = layout_result.into_edge_paths();
+        let relations: Vec<PositionedArrowWithText> = match self.edge_routing {
+            EdgeRouting::Straight => edge_paths
+                .into_iter()
+                .map(|(relation, path)| {
+                    let arrow_def = Rc::clone(relation.arrow_definition());
+                    let arrow = Arrow::new(arrow_def, relation.arrow_direction());
+                    let arrow_with_text = ArrowWithText::new(arrow, relation.text());
+                    PositionedArrowWithText::new(arrow_with_text, path)
+                })
+                .collect(),
+            EdgeRouting::Orthogonal => Self::route_orthogonal(
+                edge_paths.into_iter().map(|(relation, _)| relation),
+                &components,
+            ),
+        };
 
-        Ok(PositionedContent::new(Layout::new(components, relations)))
+        Ok(PositionedContent::new(
+            Layout::new(components, relations)
+                .with_groups(containment_scope.groups())
+                .with_notes(containment_scope.notes()),
+        ))
     }
 
-    /// Calculates sized shapes for all components in a containment scope.
+    /// Routes `relations` around `components` with an [`OrthogonalRouter`].
     ///
-    /// Embedded diagram and inner scope sizes are resolved from previously
-    /// computed layouts so that container nodes reserve the correct area.
-    fn calculate_component_shapes<'a>(
-        &self,
-        graph: &'a ComponentGraph<'a, '_>,
-        containment_scope: &ContainmentScope,
-        positioned_content_sizes: &HashMap<Id, Size>,
-        embedded_layouts: &EmbeddedLayouts<'a>,
-    ) -> Result<HashMap<Id, ShapeWithText<'a>>, RenderError> {
-        let mut component_shapes: HashMap<Id, ShapeWithText<'a>> = HashMap::new();
-
-        for node in graph.scope_nodes(containment_scope) {
-            let mut shape = Shape::new(Rc::clone(node.shape_definition()));
-            shape.set_padding(self.container_padding);
-            let text = Text::new(node.shape_definition().text(), node.display_text());
-            let mut shape_with_text = ShapeWithText::new(shape, Some(text));
-
-            match node.block() {
-                Block::Diagram(_) => {
-                    // Since we process in post-order (innermost to outermost),
-                    // embedded diagram layouts should already be calculated and available
-                    let layout = embedded_layouts.get(&node.id()).ok_or_else(|| {
-                        RenderError::Layout(format!("embedded layout not found for `{node}`"))
-                    })?;
+    /// Parallel/reverse relations between the same component pair are routed
+    /// together so that they get separate lanes.
+    fn route_orthogonal<'a>(
+        relations: impl Iterator<Item = &'a Relation>,
+        components: &[Component<'a>],
+    ) -> Vec<PositionedArrowWithText<'a>> {
+        let component_indices: HashMap<Id, usize> = components
+            .iter()
+            .enumerate()
+            .map(|(idx, component)| (component.node_id(), idx))
+            .collect();
 
-                    let content_size = layout.calculate_size();
-                    shape_with_text
-                        .set_inner_content_size(content_size)
-                        .map_err(|err| {
-                            RenderError::Layout(format!(
-                                "cannot set content size for diagram block `{node}`: {err}"
-                            ))
-                        })?;
-                }
-                Block::Scope(_) => {
-                    let content_size =
-                        *positioned_content_sizes.get(&node.id()).ok_or_else(|| {
-                            RenderError::Layout(format!("scope size not found for `{node}`"))
-                        })?;
-                    shape_with_text
-                        .set_inner_content_size(content_size)
-                        .map_err(|err| {
-                            RenderError::Layout(format!(
-                                "cannot set content size for scope block `{node}`: {err}"
-                            ))
-                        })?;
-                }
-                Block::None => {
-                    // No content to size, so don't call set_inner_content_size
-                }
+        let mut buckets: IndexMap<(Id, Id), Vec<&'a Relation>> = IndexMap::new();
+        for relation in relations {
+            let (source_id, target_id) = (relation.source(), relation.target());
+            if !component_indices.contains_key(&source_id)
+                || !component_indices.contains_key(&target_id)
+            {
+                continue;
+            }
+            // Canonicalize the pair key so (a,b) and (b,a) land in the same bucket.
+            let key = if buckets.contains_key(&(target_id, source_id)) {
+                (target_id, source_id)
+            } else {
+                (source_id, target_id)
             };
-            component_shapes.insert(node.id(), shape_with_text);
+            buckets.entry(key).or_default().push(relation);
         }
 
-        Ok(component_shapes)
+        let router = OrthogonalRouter::new(components);
+        buckets
+            .into_iter()
+            .flat_map(|((src_id, tgt_id), bucket)| {
+                let src = &components[component_indices[&src_id]];
+                let tgt = &components[component_indices[&tgt_id]];
+                router.place(&bucket, src, tgt)
+            })
+            .collect()
     }
 }
 
@@ -252,7 +270,7 @@ impl Engine {
 impl ComponentEngine for Engine {
     fn calculate<'a>(
         &self,
-        graph: &'a ComponentGraph<'a, '_>,
+        graph: &ComponentGraph<'a, '_>,
         embedded_layouts: &EmbeddedLayouts<'a>,
     ) -> Result<ContentStack<Layout<'a>>, RenderError> {
         self.calculate_layout(graph, embedded_layouts)
@@ -11,7 +11,11 @@
 //! * [`DotOutput`] — holds the computed node positions and per-relation edge
 //!   paths.
 
-use std::{collections::HashMap, fmt, io::ErrorKind};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    io::ErrorKind,
+};
 
 use dot_structures::{
     Attribute, Edge, EdgeTy, Graph as DotGraph, Id as DotId, Node as DotNode, NodeId, Stmt, Vertex,
@@ -86,7 +90,7 @@ impl<'a> DotBridge<'a> {
     ///
     /// Returns [`RenderError::Layout`] if a component size is missing.
     pub fn new(
-        graph: &'a ComponentGraph<'a, '_>,
+        graph: &ComponentGraph<'a, '_>,
         containment_scope: &ContainmentScope<'a, '_>,
         component_sizes: &HashMap<Id, Size>,
     ) -> Result<Self, RenderError> {
@@ -142,7 +146,9 @@ impl<'a> DotBridge<'a> {
     /// Resolves parsed edge paths back to their semantic relations.
     ///
     /// Pairs each extracted edge path with the [`Relation`] it belongs to,
-    /// using the index mapping built during DOT graph construction.
+    /// using the index mapping built during DOT graph construction. Paths of
+    /// backward relations, emitted from target to source, are reversed so
+    /// that every path runs from the relation's source to its target.
     fn resolve_edge_paths(
         graph: &DotGraph,
         edge_map: HashMap<usize, &'a Relation>,
@@ -154,7 +160,13 @@ impl<'a> DotBridge<'a> {
             .map(|(idx, path)| {
                 edge_map
                     .get(&idx)
-                    .map(|edge| (*edge, path))
+                    .map(|edge| {
+                        if edge.arrow_direction().is_backward() {
+                            (*edge, reverse_path(&path))
+                        } else {
+                            (*edge, path)
+                        }
+                    })
                     .ok_or(RenderError::Layout(format!(
                         "edge path not found for relation index {idx}"
                     )))
@@ -183,10 +195,16 @@ fn node_stmt(node: &Node, size: Size) -> Stmt {
 }
 
 /// Creates a DOT `edge` statement tagged with an `id`.
+///
+/// `a <- b` flows from `b` to `a`, so it is emitted as `b -> a` to rank `b`
+/// above `a`, as the other engines do. Its path is reversed back when read
+/// in [`DotBridge::resolve_edge_paths`].
 fn edge_stmt(idx: usize, relation: &Relation) -> Stmt {
     let mut attributes = match relation.arrow_direction() {
-        ArrowDirection::Forward => vec![],
-        ArrowDirection::Backward => vec![dot_attr("dir", "back")],
+        ArrowDirection::Forward
+        | ArrowDirection::AsyncForward
+        | ArrowDirection::Backward
+        | ArrowDirection::AsyncBackward => vec![],
         ArrowDirection::Bidirectional => {
             vec![dot_attr("dir", "both"), dot_attr("constraint", "false")]
         }
@@ -194,18 +212,33 @@ fn edge_stmt(idx: usize, relation: &Relation) -> Stmt {
             vec![dot_attr("dir", "none"), dot_attr("constraint", "false")]
         }
     };
+    let min_length = relation.arrow_definition().min_length();
+    if min_length > 1 {
+        attributes.push(dot_attr("minlen", &min_length.to_string()));
+    }
     attributes.push(dot_attr("id", &format!("e_{idx}")));
 
+    let (from, to) = if relation.arrow_direction().is_backward() {
+        (relation.target(), relation.source())
+    } else {
+        (relation.source(), relation.target())
+    };
     let edge = Edge {
         ty: EdgeTy::Pair(
-            Vertex::N(NodeId(into_dot_id(relation.source()), None)),
-            Vertex::N(NodeId(into_dot_id(relation.target()), None)),
+            Vertex::N(NodeId(into_dot_id(from), None)),
+            Vertex::N(NodeId(into_dot_id(to), None)),
         ),
         attributes,
     };
     Stmt::Edge(edge)
 }
 
+/// Returns `path` running from its destination back to its source.
+fn reverse_path(path: &ArrowPath) -> ArrowPath {
+    let control_points = path.control_points().iter().rev().copied().collect();
+    ArrowPath::new(path.destination(), path.source(), control_points)
+}
+
 /// Creates a DOT [`Attribute`] from a key-value string pair.
 fn dot_attr(key: &str, value: &str) -> Attribute {
     Attribute(DotId::Plain(key.into()), DotId::Plain(value.into()))
@@ -326,15 +359,16 @@ fn extract_positions_from_graph(graph: &DotGraph) -> Result<HashMap<Id, Point>,
 
 /// Extracts edge B-spline paths from a Graphviz-annotated DOT graph.
 ///
-/// Returns a map from the sequential edge index to the corresponding [`ArrowPath`].
+/// Returns a map from the sequential edge index to the corresponding [`ArrowPath`],
+/// ordered by index so that relations keep their declaration order.
 fn extract_edge_paths_from_graph(
     graph: &DotGraph,
-) -> Result<HashMap<usize, ArrowPath>, RenderError> {
+) -> Result<BTreeMap<usize, ArrowPath>, RenderError> {
     let stmts = match graph {
         DotGraph::Graph { stmts, .. } | DotGraph::DiGraph { stmts, .. } => stmts,
     };
 
-    let mut edge_paths = HashMap::new();
+    let mut edge_paths = BTreeMap::new();
 
     for edge in stmts.iter().filter_map(|stmt| match stmt {
         Stmt::Edge(edge) => Some(edge),
@@ -445,6 +479,10 @@ fn parse_edge_pos(pos_str: &str) -> Result<ArrowPath, RenderError> {
 
 #[cfg(test)]
 mod tests {
+    use std::rc::Rc;
+
+    use orrery_core::draw::ArrowDefinition;
+
     use super::*;
 
     #[test]
@@ -580,4 +618,64 @@ mod tests {
         assert_eq!(paths[&0].control_points().len(), 2);
         assert_eq!(paths[&1].control_points().len(), 2);
     }
+    #[test]
+    fn test_edge_stmt_sets_minlen_above_one() {
+        let minlen = |min_length: usize| {
+            let mut definition = ArrowDefinition::default();
+            definition.set_min_length(min_length);
+            let relation = Relation::new(
+                Id::new("a"),
+                Id::new("b"),
+                ArrowDirection::Forward,
+                None,
+                Rc::new(definition),
+            );
+            let Stmt::Edge(edge) = edge_stmt(0, &relation) else {
+                panic!("Expected an edge statement");
+            };
+            find_attribute(&edge.attributes, "minlen").map(str::to_string)
+        };
+
+        assert_eq!(minlen(1), None);
+        assert_eq!(minlen(3).as_deref(), Some("3"));
+    }
+
+    #[test]
+    fn test_edge_stmt_emits_backward_relations_from_target() {
+        let relation = Relation::new(
+            Id::new("a"),
+            Id::new("b"),
+            ArrowDirection::Backward,
+            None,
+            Rc::new(ArrowDefinition::default()),
+        );
+        let Stmt::Edge(edge) = edge_stmt(0, &relation) else {
+            panic!("Expected an edge statement");
+        };
+
+        let EdgeTy::Pair(Vertex::N(from), Vertex::N(to)) = &edge.ty else {
+            panic!("Expected a node pair");
+        };
+        assert_eq!(dot_id_to_str(&from.0), "\"b\"");
+        assert_eq!(dot_id_to_str(&to.0), "\"a\"");
+        assert_eq!(find_attribute(&edge.attributes, "dir"), None);
+    }
+
+    #[test]
+    fn test_reverse_path() {
+        let path = ArrowPath::new(
+            Point::new(0.0, 0.0),
+            Point::new(30.0, 30.0),
+            vec![Point::new(10.0, 0.0), Point::new(20.0, 30.0)],
+        );
+
+        let reversed = reverse_path(&path);
+
+        assert_eq!(reversed.source(), Point::new(30.0, 30.0));
+        assert_eq!(reversed.destination(), Point::new(0.0, 0.0));
+        assert_eq!(
+            reversed.control_points(),
+            [Point::new(20.0, 30.0), Point::new(10.0, 0.0)]
+        );
+    }
 }
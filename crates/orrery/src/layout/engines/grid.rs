@@ -0,0 +1,9 @@
+//! Grid layout engines
+//!
+//! This module contains layout engines that place components on a regular
+//! grid of uniform cells, for matrix-like arrangements where the position of
+//! a component matters more than the shape of the graph.
+
+mod component;
+
+pub use component::Engine as Component;
@@ -0,0 +1,329 @@
+//! Grid layout engine for component diagrams.
+
+use std::collections::{HashMap, HashSet};
+
+use indexmap::IndexMap;
+
+use orrery_core::{
+    draw::{Drawable, EdgeRouting, ShapeWithText},
+    geometry::{Insets, Point, Size},
+    identifier::Id,
+};
+
+use crate::{
+    error::RenderError,
+    layout::{
+        component::{self, Component, Layout, SmartArrowPlacer},
+        engines::{
+            Axis, ComponentEngine, EmbeddedLayouts, calculate_component_shapes,
+            place_pinned_components, place_scope_relations,
+        },
+        layer::{ContentStack, PositionedContent},
+    },
+    structure::{ComponentGraph, ContainmentScope},
+};
+
+/// Grid layout engine for component diagrams.
+///
+/// Packs the components of each containment scope row by row into cells of
+/// uniform size, sized to the largest component of the scope. Relations are
+/// placed once all components are positioned.
+///
+/// Components pinned on both axes keep their position, and the cells they
+/// overlap are skipped; components pinned on one axis take the next free
+/// cell and then have that axis replaced.
+pub struct Engine {
+    /// Number of columns, or `None` for the square root of the component count.
+    columns: Option<usize>,
+    /// Horizontal spacing between columns.
+    horizontal_spacing: f32,
+    /// Vertical spacing between rows.
+    vertical_spacing: f32,
+    /// Container padding for nested components.
+    container_padding: Insets,
+    /// How relations are routed between components.
+    edge_routing: EdgeRouting,
+    arrow_placer: SmartArrowPlacer,
+}
+
+impl Engine {
+    /// Creates a new grid component layout engine.
+    pub fn new() -> Self {
+        Self {
+            columns: None,
+            horizontal_spacing: 50.0,
+            vertical_spacing: 50.0,
+            container_padding: Insets::uniform(20.0),
+            edge_routing: EdgeRouting::default(),
+            arrow_placer: SmartArrowPlacer::new(),
+        }
+    }
+
+    /// Set the number of columns.
+    ///
+    /// `None` picks the smallest number of columns that makes the grid
+    /// square or wider than tall.
+    pub fn set_columns(&mut self, columns: Option<usize>) -> &mut Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Set the horizontal spacing between columns.
+    pub fn set_horizontal_spacing(&mut self, spacing: f32) -> &mut Self {
+        self.horizontal_spacing = spacing;
+        self
+    }
+
+    /// Set the vertical spacing between rows.
+    pub fn set_vertical_spacing(&mut self, spacing: f32) -> &mut Self {
+        self.vertical_spacing = spacing;
+        self
+    }
+
+    /// Set the padding inside container components.
+    pub fn set_container_padding(&mut self, padding: Insets) -> &mut Self {
+        self.container_padding = padding;
+        self
+    }
+
+    /// Set how relations are routed between components.
+    pub fn set_edge_routing(&mut self, routing: EdgeRouting) -> &mut Self {
+        self.edge_routing = routing;
+        self
+    }
+
+    fn calculate_layout<'a>(
+        &self,
+        graph: &ComponentGraph<'a, '_>,
+        embedded_layouts: &EmbeddedLayouts<'a>,
+    ) -> Result<ContentStack<Layout<'a>>, RenderError> {
+        let mut content_stack = ContentStack::<Layout<'a>>::new();
+        let mut positioned_content_sizes = HashMap::<Id, Size>::new();
+
+        for containment_scope in graph.containment_scopes() {
+            // Calculate component shapes - they contain all sizing information
+            let mut component_shapes = calculate_component_shapes(
+                graph,
+                containment_scope,
+                self.container_padding,
+                &positioned_content_sizes,
+                embedded_layouts,
+            )?;
+
+            // Calculate positions for components in this scope
+            let mut positions = self.positions(graph, containment_scope, &component_shapes);
+            place_pinned_components(
+                graph,
+                containment_scope,
+                &mut positions,
+                &component_shapes,
+                Axis::Horizontal,
+                self.horizontal_spacing,
+            );
+
+            // Build the final component list using the pre-configured shapes
+            let mut components: Vec<Component> = Vec::new();
+            for node in graph.scope_nodes(containment_scope) {
+                let position = *positions.get(&node.id()).ok_or_else(|| {
+                    RenderError::Layout(format!("Position not found for node {node}"))
+                })?;
+                let shape_with_text =
+                    component_shapes.shift_remove(&node.id()).ok_or_else(|| {
+                        RenderError::Layout(format!("Shape not found for node {node}"))
+                    })?;
+                components.push(Component::new(node, shape_with_text, position));
+            }
+
+            // Map node IDs to their component indices
+            let component_indices: HashMap<_, _> = components
+                .iter()
+                .enumerate()
+                .map(|(idx, component)| (component.node_id(), idx))
+                .collect();
+
+            let relations = place_scope_relations(
+                graph,
+                containment_scope,
+                &components,
+                &component_indices,
+                self.edge_routing,
+                &self.arrow_placer,
+            );
+
+            let positioned_content = PositionedContent::new(
+                Layout::new(components, relations)
+                    .with_groups(containment_scope.groups())
+                    .with_notes(containment_scope.notes()),
+            );
+
+            if let Some(container) = containment_scope.container() {
+                // If this layer is a container, we need to adjust its size based on its contents
+                let size = positioned_content.layout_size();
+                positioned_content_sizes.insert(container, size);
+            }
+            content_stack.push(positioned_content);
+        }
+
+        component::adjust_positioned_contents_offset(&mut content_stack, graph)?;
+        component::place_cross_scope_relations(&mut content_stack, graph);
+
+        Ok(content_stack)
+    }
+
+    /// Calculate the center of every component in a containment scope.
+    ///
+    /// Components pinned on both axes are placed at their pin, and every
+    /// cell their bounds overlap is taken out of the grid before the other
+    /// components flow into the free cells in declaration order.
+    fn positions<'a>(
+        &self,
+        graph: &ComponentGraph<'a, '_>,
+        containment_scope: &ContainmentScope,
+        component_shapes: &IndexMap<Id, ShapeWithText<'a>>,
+    ) -> HashMap<Id, Point> {
+        let cell = component_shapes
+            .values()
+            .fold(Size::zero(), |cell, shape| cell.max(shape.size()));
+        let pitch = Size::new(
+            cell.width() + self.horizontal_spacing,
+            cell.height() + self.vertical_spacing,
+        );
+
+        let mut positions = HashMap::new();
+        let mut occupied = HashSet::new();
+        let mut flowing = Vec::new();
+        for node in graph.scope_nodes(containment_scope) {
+            let pin = node.pin();
+            match (pin.x(), pin.y(), component_shapes.get(&node.id())) {
+                (Some(x), Some(y), Some(shape)) => {
+                    let center = Point::new(x, y);
+                    occupied.extend(overlapped_cells(center, shape.size(), cell, pitch));
+                    positions.insert(node.id(), center);
+                }
+                _ => flowing.push(node.id()),
+            }
+        }
+
+        let columns = self
+            .columns
+            .unwrap_or_else(|| square_columns(flowing.len()))
+            .max(1);
+        for (id, (row, column)) in flowing.into_iter().zip(free_cells(columns, &occupied)) {
+            let center = Point::new(
+                column as f32 * pitch.width() + cell.width() / 2.0,
+                row as f32 * pitch.height() + cell.height() / 2.0,
+            );
+            positions.insert(id, center);
+        }
+
+        positions
+    }
+}
+
+impl ComponentEngine for Engine {
+    fn calculate<'a>(
+        &self,
+        graph: &ComponentGraph<'a, '_>,
+        embedded_layouts: &EmbeddedLayouts<'a>,
+    ) -> Result<ContentStack<Layout<'a>>, RenderError> {
+        self.calculate_layout(graph, embedded_layouts)
+    }
+}
+
+/// Returns the smallest number of columns whose grid holds `count` cells
+/// without having more rows than columns.
+fn square_columns(count: usize) -> usize {
+    let mut columns = count.isqrt();
+    if columns * columns < count {
+        columns += 1;
+    }
+    columns
+}
+
+/// Returns the free `(row, column)` cells of a grid with `columns` columns,
+/// row by row, skipping the `occupied` ones.
+fn free_cells(
+    columns: usize,
+    occupied: &HashSet<(usize, usize)>,
+) -> impl Iterator<Item = (usize, usize)> + '_ {
+    (0..)
+        .map(move |index| (index / columns, index % columns))
+        .filter(|cell| !occupied.contains(cell))
+}
+
+/// Returns the `(row, column)` cells overlapped by a component of `size`
+/// centered on `center`, for cells of `cell` size laid out every `pitch`.
+///
+/// Cells at negative coordinates are ignored, since nothing flows into them.
+fn overlapped_cells(
+    center: Point,
+    size: Size,
+    cell: Size,
+    pitch: Size,
+) -> impl Iterator<Item = (usize, usize)> {
+    // Cell `i` spans `i * pitch .. i * pitch + cell` along an axis, so the
+    // overlapped ones are those starting before `end` and ending after `start`.
+    let span = |center: f32, size: f32, cell: f32, pitch: f32| {
+        let first = ((center - size / 2.0 - cell) / pitch).floor() as i64 + 1;
+        let last = ((center + size / 2.0) / pitch).ceil() as i64 - 1;
+        first.max(0)..=last
+    };
+    let rows = span(center.y(), size.height(), cell.height(), pitch.height());
+    let columns = span(center.x(), size.width(), cell.width(), pitch.width());
+
+    rows.flat_map(move |row| {
+        columns
+            .clone()
+            .map(move |column| (row as usize, column as usize))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_square_columns() {
+        let columns: Vec<_> = [0, 1, 2, 4, 5, 9, 10].map(square_columns).into();
+        assert_eq!(columns, [0, 1, 2, 2, 3, 3, 4]);
+    }
+
+    #[test]
+    fn test_free_cells_skip_occupied() {
+        let occupied = HashSet::from([(0, 1), (1, 0)]);
+        let cells: Vec<_> = free_cells(2, &occupied).take(3).collect();
+        assert_eq!(cells, [(0, 0), (1, 1), (2, 0)]);
+    }
+
+    #[test]
+    fn test_overlapped_cells() {
+        let cell = Size::new(100.0, 50.0);
+        let pitch = Size::new(150.0, 100.0);
+
+        // Centered in the second column of the first row
+        let cells: Vec<_> =
+            overlapped_cells(Point::new(200.0, 25.0), Size::new(40.0, 40.0), cell, pitch).collect();
+        assert_eq!(cells, [(0, 1)]);
+
+        // In the gap between two columns
+        let cells: Vec<_> =
+            overlapped_cells(Point::new(125.0, 25.0), Size::new(40.0, 40.0), cell, pitch).collect();
+        assert!(cells.is_empty());
+
+        // Straddling four cells
+        let cells: Vec<_> = overlapped_cells(
+            Point::new(125.0, 75.0),
+            Size::new(100.0, 100.0),
+            cell,
+            pitch,
+        )
+        .collect();
+        assert_eq!(cells, [(0, 0), (0, 1), (1, 0), (1, 1)]);
+
+        // Outside the grid
+        let cells: Vec<_> =
+            overlapped_cells(Point::new(-100.0, 25.0), Size::new(40.0, 40.0), cell, pitch)
+                .collect();
+        assert!(cells.is_empty());
+    }
+}
@@ -1,22 +1,25 @@
 //! Sugiyama layout engine for component diagrams.
 
-use std::{collections::HashMap, rc::Rc};
+use std::collections::HashMap;
 
+use indexmap::IndexMap;
 use log::debug;
 use rust_sugiyama::configure::Config;
 
 use orrery_core::{
-    draw::{Drawable, PositionedArrowWithText, Shape, ShapeWithText, Text},
+    draw::{Drawable, EdgeRouting},
     geometry::{Insets, Point, Size},
     identifier::Id,
-    semantic::{Block, Relation},
 };
 
 use crate::{
     error::RenderError,
     layout::{
-        component::{self, ArrowPlacer, Component, Layout, SmartArrowPlacer},
-        engines::{ComponentEngine, EmbeddedLayouts},
+        component::{self, Component, Layout, SmartArrowPlacer},
+        engines::{
+            Axis, ComponentEngine, EmbeddedLayouts, calculate_component_shapes,
+            place_pinned_components, place_scope_relations,
+        },
         layer::{ContentStack, PositionedContent},
     },
     structure::{ComponentGraph, ContainmentScope},
@@ -36,6 +39,8 @@ pub struct Engine {
     vertical_spacing: f32,
     /// Container padding for nested components.
     container_padding: Insets,
+    /// How relations are routed between components.
+    edge_routing: EdgeRouting,
     arrow_placer: SmartArrowPlacer,
 }
 
@@ -47,6 +52,7 @@ impl Engine {
             horizontal_spacing: 50.0,
             vertical_spacing: 80.0,
             container_padding: Insets::uniform(20.0),
+            edge_routing: EdgeRouting::default(),
             arrow_placer: SmartArrowPlacer::new(),
         }
     }
@@ -76,9 +82,15 @@ impl Engine {
         self
     }
 
+    /// Set how relations are routed between components.
+    pub fn set_edge_routing(&mut self, routing: EdgeRouting) -> &mut Self {
+        self.edge_routing = routing;
+        self
+    }
+
     fn calculate_layout<'a>(
         &self,
-        graph: &'a ComponentGraph<'a, '_>,
+        graph: &ComponentGraph<'a, '_>,
         embedded_layouts: &EmbeddedLayouts<'a>,
     ) -> Result<ContentStack<Layout<'a>>, RenderError> {
         let mut content_stack = ContentStack::<Layout<'a>>::new();
@@ -86,21 +98,30 @@ impl Engine {
 
         for containment_scope in graph.containment_scopes() {
             // Calculate component shapes - they contain all sizing information
-            let mut component_shapes = self.calculate_component_shapes(
+            let mut component_shapes = calculate_component_shapes(
                 graph,
                 containment_scope,
+                self.container_padding,
                 &positioned_content_sizes,
                 embedded_layouts,
             )?;
 
             // Extract sizes from shapes for position calculation
-            let component_sizes: HashMap<Id, Size> = component_shapes
+            let component_sizes: IndexMap<Id, Size> = component_shapes
                 .iter()
                 .map(|(idx, shape_with_text)| (*idx, shape_with_text.size()))
                 .collect();
 
             // Calculate positions for components in this scope
-            let positions = self.positions(graph, containment_scope, &component_sizes)?;
+            let mut positions = self.positions(graph, containment_scope, &component_sizes)?;
+            place_pinned_components(
+                graph,
+                containment_scope,
+                &mut positions,
+                &component_shapes,
+                Axis::Horizontal,
+                self.horizontal_spacing,
+            );
 
             // Build the final component list using the pre-configured shapes
             let mut components: Vec<Component> = Vec::new();
@@ -108,9 +129,10 @@ impl Engine {
                 let position = *positions.get(&node.id()).ok_or_else(|| {
                     RenderError::Layout(format!("Position not found for node {node}"))
                 })?;
-                let shape_with_text = component_shapes.remove(&node.id()).ok_or_else(|| {
-                    RenderError::Layout(format!("Shape not found for node {node}"))
-                })?;
+                let shape_with_text =
+                    component_shapes.shift_remove(&node.id()).ok_or_else(|| {
+                        RenderError::Layout(format!("Shape not found for node {node}"))
+                    })?;
                 components.push(Component::new(node, shape_with_text, position));
             }
 
@@ -121,14 +143,20 @@ impl Engine {
                 .map(|(idx, component)| (component.node_id(), idx))
                 .collect();
 
-            let relations = self.place_scope_relations(
+            let relations = place_scope_relations(
                 graph,
                 containment_scope,
                 &components,
                 &component_indices,
+                self.edge_routing,
+                &self.arrow_placer,
             );
 
-            let positioned_content = PositionedContent::new(Layout::new(components, relations));
+            let positioned_content = PositionedContent::new(
+                Layout::new(components, relations)
+                    .with_groups(containment_scope.groups())
+                    .with_notes(containment_scope.notes()),
+            );
 
             if let Some(container) = containment_scope.container() {
                 // If this layer is a container, we need to adjust its size based on its contents
@@ -139,116 +167,17 @@ impl Engine {
         }
 
         component::adjust_positioned_contents_offset(&mut content_stack, graph)?;
+        component::place_cross_scope_relations(&mut content_stack, graph);
 
         Ok(content_stack)
     }
 
-    /// Builds positioned arrows for all visible relations in a containment scope.
-    ///
-    /// Parallel/reverse relations between the same component pair are placed
-    /// together so the [`ArrowPlacer`] can offset them. Relations whose
-    /// endpoints are not visible at this scope are skipped.
-    fn place_scope_relations<'a>(
-        &self,
-        graph: &'a ComponentGraph<'a, '_>,
-        containment_scope: &ContainmentScope,
-        components: &[Component<'a>],
-        component_indices: &HashMap<Id, usize>,
-    ) -> Vec<PositionedArrowWithText<'a>> {
-        let mut buckets: HashMap<(Id, Id), Vec<&'a Relation>> = HashMap::new();
-
-        for relation in graph.scope_relations(containment_scope) {
-            let source_id = relation.source();
-            let target_id = relation.target();
-
-            if !component_indices.contains_key(&source_id)
-                || !component_indices.contains_key(&target_id)
-            {
-                continue;
-            }
-
-            // Canonicalize the pair key so (a,b) and (b,a) land in the same bucket.
-            let key = if buckets.contains_key(&(target_id, source_id)) {
-                (target_id, source_id)
-            } else {
-                (source_id, target_id)
-            };
-            buckets.entry(key).or_default().push(relation);
-        }
-
-        buckets
-            .into_iter()
-            .flat_map(|((src_id, tgt_id), bucket)| {
-                let src = &components[component_indices[&src_id]];
-                let tgt = &components[component_indices[&tgt_id]];
-                self.arrow_placer.place(&bucket, src, tgt)
-            })
-            .collect()
-    }
-
-    /// Calculate component shapes with proper sizing and padding
-    fn calculate_component_shapes<'a>(
-        &self,
-        graph: &'a ComponentGraph<'a, '_>,
-        containment_scope: &ContainmentScope,
-        positioned_content_sizes: &HashMap<Id, Size>,
-        embedded_layouts: &EmbeddedLayouts<'a>,
-    ) -> Result<HashMap<Id, ShapeWithText<'a>>, RenderError> {
-        let mut component_shapes: HashMap<Id, ShapeWithText<'a>> = HashMap::new();
-
-        // TODO: move it to the best place.
-        for node in graph.scope_nodes(containment_scope) {
-            let mut shape = Shape::new(Rc::clone(node.shape_definition()));
-            shape.set_padding(self.container_padding);
-            let text = Text::new(node.shape_definition().text(), node.display_text());
-            let mut shape_with_text = ShapeWithText::new(shape, Some(text));
-
-            match node.block() {
-                Block::Diagram(_) => {
-                    // Since we process in post-order (innermost to outermost),
-                    // embedded diagram layouts should already be calculated and available
-                    let layout = embedded_layouts.get(&node.id()).ok_or_else(|| {
-                        RenderError::Layout(format!("Embedded layout not found for node {node}"))
-                    })?;
-
-                    let content_size = layout.calculate_size();
-                    shape_with_text
-                        .set_inner_content_size(content_size)
-                        .map_err(|err| {
-                            RenderError::Layout(format!(
-                                "Failed to set content size for diagram block {node}: {err}"
-                            ))
-                        })?;
-                }
-                Block::Scope(_) => {
-                    let content_size =
-                        *positioned_content_sizes.get(&node.id()).ok_or_else(|| {
-                            RenderError::Layout(format!("Scope size not found for node {node}"))
-                        })?;
-                    shape_with_text
-                        .set_inner_content_size(content_size)
-                        .map_err(|err| {
-                            RenderError::Layout(format!(
-                                "Failed to set content size for scope block {node}: {err}"
-                            ))
-                        })?;
-                }
-                Block::None => {
-                    // No content to size, so don't call set_inner_content_size
-                }
-            };
-            component_shapes.insert(node.id(), shape_with_text);
-        }
-
-        Ok(component_shapes)
-    }
-
     /// Calculate positions for components in a containment scope
     fn positions<'a>(
         &self,
         graph: &ComponentGraph<'a, '_>,
         containment_scope: &ContainmentScope,
-        component_sizes: &HashMap<Id, Size>,
+        component_sizes: &IndexMap<Id, Size>,
     ) -> Result<HashMap<Id, Point>, RenderError> {
         // Prepare layout
         let mut positions = HashMap::new();
@@ -266,6 +195,9 @@ impl Engine {
             node_ids.insert(node.id(), id);
         }
 
+        // Vertices past the scope nodes are hidden, and only lengthen edges
+        let mut next_hidden_id = node_ids.len() as u32;
+
         // Extract edges for this containment scope
         for relation in graph.scope_relations(containment_scope) {
             if let (Some(&source_id), Some(&target_id)) = (
@@ -273,9 +205,23 @@ impl Engine {
                 node_ids.get(&relation.target()),
             ) {
                 // Skip self-loops
-                if source_id != target_id {
-                    edges.push((source_id, target_id));
+                if source_id == target_id {
+                    continue;
                 }
+                // `a <- b` flows from `b` to `a`, so it ranks like `b -> a`.
+                let (mut from, to) = if relation.arrow_direction().is_backward() {
+                    (target_id, source_id)
+                } else {
+                    (source_id, target_id)
+                };
+                // A `min_length` of `n` routes the edge through `n - 1` hidden
+                // vertices, each of which takes a rank of its own.
+                for _ in 1..relation.arrow_definition().min_length() {
+                    edges.push((from, next_hidden_id));
+                    from = next_hidden_id;
+                    next_hidden_id += 1;
+                }
+                edges.push((from, to));
             }
         }
 
@@ -414,7 +360,7 @@ impl Engine {
     fn center_layout(
         &self,
         positions: &mut HashMap<Id, Point>,
-        component_sizes: &HashMap<Id, Size>,
+        component_sizes: &IndexMap<Id, Size>,
     ) -> Result<(), RenderError> {
         if positions.is_empty() {
             return Ok(());
@@ -453,7 +399,7 @@ impl Engine {
 impl ComponentEngine for Engine {
     fn calculate<'a>(
         &self,
-        graph: &'a ComponentGraph<'a, '_>,
+        graph: &ComponentGraph<'a, '_>,
         embedded_layouts: &EmbeddedLayouts<'a>,
     ) -> Result<ContentStack<Layout<'a>>, RenderError> {
         self.calculate_layout(graph, embedded_layouts)
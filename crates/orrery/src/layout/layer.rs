@@ -11,12 +11,27 @@
 //! - [`LayeredLayout`] - Complete layout organized into ordered layers
 //! - [`ContentStack`] - A stack of positioned content items for layout management
 //! - [`PositionedContent`] - Content item with an associated position offset
+//!
+//! # Coordinates
+//!
+//! Geometry stored inside a layer's content is local to its
+//! [`PositionedContent`]. Adding the content's [`offset`](PositionedContent::offset)
+//! and the layer's [`offset`](Layer::offset) yields diagram coordinates, which
+//! [`Layer::to_diagram_point`] and [`Layer::to_diagram_bounds`] do for you.
+//!
+//! # Stacking
+//!
+//! Layers and the positioned contents of a [`ContentStack`] are both ordered
+//! from the innermost to the outermost diagram or scope, and are drawn in
+//! reverse, so earlier entries appear on top. [`LayeredLayout::hit_test`]
+//! relies on this to find the innermost element under a point.
 
 use log::debug;
 
 use orrery_core::{
     draw::{PositionedDrawable, ShapeWithText},
     geometry::{Bounds, Point, Size},
+    identifier::Id,
 };
 
 use crate::{
@@ -77,6 +92,90 @@ impl<'a> Layer<'a> {
         &self.content
     }
 
+    /// Returns the bounds of this layer's content in diagram coordinates.
+    ///
+    /// Only the outermost positioned content is considered, since it encloses
+    /// any nested content of the same layer.
+    pub fn bounds(&self) -> Bounds {
+        let bounds = match &self.content {
+            LayoutContent::Component(content_stack) => content_stack
+                .iter()
+                .last()
+                .map(|positioned_content| positioned_content.content().layout_bounds())
+                .unwrap_or_default(),
+            LayoutContent::Sequence(content_stack) => content_stack
+                .iter()
+                .last()
+                .map(|positioned_content| sequence_bounds(positioned_content.content()))
+                .unwrap_or_default(),
+        };
+        bounds.translate(self.offset)
+    }
+
+    /// Returns the bounds of the components or participants of this layer in
+    /// diagram coordinates, topmost first.
+    ///
+    /// Nested components come before their containers.
+    pub fn element_bounds(&self) -> Vec<(Id, Bounds)> {
+        match &self.content {
+            LayoutContent::Component(content_stack) => {
+                content_stack
+                    .iter()
+                    .flat_map(|positioned_content| {
+                        positioned_content.content().components().iter().rev().map(
+                            move |component| {
+                                let bounds =
+                                    self.to_diagram_bounds(positioned_content, component.bounds());
+                                (component.node_id(), bounds)
+                            },
+                        )
+                    })
+                    .collect()
+            }
+            LayoutContent::Sequence(content_stack) => content_stack
+                .iter()
+                .flat_map(|positioned_content| {
+                    positioned_content
+                        .content()
+                        .participants()
+                        .values()
+                        .rev()
+                        .map(move |participant| {
+                            let component = participant.component();
+                            let bounds =
+                                self.to_diagram_bounds(positioned_content, component.bounds());
+                            (component.node_id(), bounds)
+                        })
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns `true` if `point`, in diagram coordinates, is not clipped
+    /// away by this layer's clipping bounds.
+    fn is_visible_at(&self, point: Point) -> bool {
+        self.clip_bounds
+            .is_none_or(|clip_bounds| clip_bounds.translate(self.offset).contains(point))
+    }
+
+    /// Converts a point local to `content` of this layer into diagram coordinates.
+    pub fn to_diagram_point<T: LayoutBounds>(
+        &self,
+        content: &PositionedContent<T>,
+        point: Point,
+    ) -> Point {
+        point.add_point(content.offset()).add_point(self.offset)
+    }
+
+    /// Converts bounds local to `content` of this layer into diagram coordinates.
+    pub fn to_diagram_bounds<T: LayoutBounds>(
+        &self,
+        content: &PositionedContent<T>,
+        bounds: Bounds,
+    ) -> Bounds {
+        bounds.translate(content.offset().add_point(self.offset))
+    }
+
     /// Set the global offset applied to this layer.
     fn set_offset(&mut self, offset: Point) {
         self.offset = offset;
@@ -101,7 +200,7 @@ pub struct LayeredLayout<'a> {
 
 impl<'a> LayeredLayout<'a> {
     /// Creates a new empty layered layout
-    pub fn new() -> Self {
+    pub(crate) fn new() -> Self {
         Self { layers: Vec::new() }
     }
 
@@ -109,7 +208,7 @@ impl<'a> LayeredLayout<'a> {
     ///
     /// The z_index is assigned based on the layer's position in the stack,
     /// with higher indices (newer layers) appearing on top.
-    pub fn add_layer(&mut self, content: LayoutContent<'a>) -> usize {
+    pub(crate) fn add_layer(&mut self, content: LayoutContent<'a>) -> usize {
         let z_index = self.layers.len();
 
         self.layers.push(Layer::new(z_index, content));
@@ -125,7 +224,7 @@ impl<'a> LayeredLayout<'a> {
     /// # Errors
     /// Returns an error if either index is invalid, if they refer to the same layer,
     /// or if the container shape doesn't have content bounds set.
-    pub fn adjust_relative_position(
+    pub(crate) fn adjust_relative_position(
         &mut self,
         container_idx: usize,
         positioned_shape: &PositionedDrawable<ShapeWithText>,
@@ -195,15 +294,51 @@ impl<'a> LayeredLayout<'a> {
     }
 
     /// Returns the number of layers
-    pub fn len(&self) -> usize {
+    pub(crate) fn len(&self) -> usize {
         self.layers.len()
     }
 
+    /// Returns the layers ordered by z-index.
+    pub fn layers(&self) -> &[Layer<'a>] {
+        &self.layers
+    }
+
     /// Returns an iterator over the layers, starting from the bottom (background) layer
     /// This ordering is appropriate for rendering, where bottom layers should be drawn first
-    pub fn iter_from_bottom(&'a self) -> impl Iterator<Item = &'a Layer<'a>> {
+    pub fn iter_from_bottom(&self) -> impl Iterator<Item = &Layer<'a>> {
         self.layers.iter().rev()
     }
+
+    /// Returns the bounds of every component and sequence participant in
+    /// diagram coordinates, keyed by id and topmost first.
+    pub fn element_bounds(&self) -> impl Iterator<Item = (Id, Bounds)> + '_ {
+        self.layers.iter().flat_map(Layer::element_bounds)
+    }
+
+    /// Returns the id of the topmost component or sequence participant
+    /// under `point`, in diagram coordinates.
+    ///
+    /// Nested components and the contents of embedded diagrams take
+    /// precedence over their containers. Parts of embedded diagrams clipped
+    /// away by their container are ignored.
+    pub fn hit_test(&self, point: Point) -> Option<Id> {
+        self.layers
+            .iter()
+            .filter(|layer| layer.is_visible_at(point))
+            .flat_map(Layer::element_bounds)
+            .find_map(|(id, bounds)| bounds.contains(point).then_some(id))
+    }
+
+    /// Returns the bounding box of all layers in diagram coordinates.
+    ///
+    /// An empty layout has default (zero) bounds.
+    pub fn bounds(&self) -> Bounds {
+        self.layers
+            .iter()
+            .map(Layer::bounds)
+            .reduce(|acc, bounds| acc.merge(&bounds))
+            .unwrap_or_default()
+    }
 }
 
 /// A stack of positioned content items for layout management.
@@ -218,7 +353,7 @@ where
     T: LayoutBounds,
 {
     /// Creates a new empty content stack.
-    pub fn new() -> Self {
+    pub(crate) fn new() -> Self {
         Self(Vec::new())
     }
 
@@ -226,7 +361,7 @@ where
     ///
     /// # Safety
     /// The caller must ensure that the index is within bounds.
-    pub fn get_unchecked(&self, index: usize) -> &PositionedContent<T> {
+    pub(crate) fn get_unchecked(&self, index: usize) -> &PositionedContent<T> {
         &self.0[index]
     }
 
@@ -234,12 +369,12 @@ where
     ///
     /// # Safety
     /// The caller must ensure that the index is within bounds.
-    pub fn get_mut_unchecked(&mut self, index: usize) -> &mut PositionedContent<T> {
+    pub(crate) fn get_mut_unchecked(&mut self, index: usize) -> &mut PositionedContent<T> {
         &mut self.0[index]
     }
 
     /// Adds a positioned content item to the stack.
-    pub fn push(&mut self, positioned_content: PositionedContent<T>) {
+    pub(crate) fn push(&mut self, positioned_content: PositionedContent<T>) {
         self.0.push(positioned_content);
     }
 
@@ -262,7 +397,7 @@ where
     }
 
     /// Returns the number of positioned content items in the stack.
-    pub fn len(&self) -> usize {
+    pub(crate) fn len(&self) -> usize {
         self.0.len()
     }
 }
@@ -285,7 +420,7 @@ where
     T: LayoutBounds,
 {
     /// Creates new positioned content with the given content and default (zero) offset.
-    pub fn new(content: T) -> Self {
+    pub(crate) fn new(content: T) -> Self {
         Self {
             content,
             offset: Point::zero(),
@@ -302,8 +437,13 @@ where
         &self.content
     }
 
+    /// Returns a mutable reference to the content.
+    pub(crate) fn content_mut(&mut self) -> &mut T {
+        &mut self.content
+    }
+
     /// Sets the position offset for this content.
-    pub fn set_offset(&mut self, offset: Point) {
+    pub(crate) fn set_offset(&mut self, offset: Point) {
         self.offset = offset;
     }
 
@@ -312,3 +452,21 @@ where
         self.content.layout_size()
     }
 }
+
+/// Bounds of a sequence layout's participants and notes, extended down to
+/// the end of the lifelines.
+fn sequence_bounds(layout: &SequenceLayout) -> Bounds {
+    if layout.participants().is_empty() {
+        return Bounds::default();
+    }
+
+    let content_bounds = layout
+        .participants()
+        .values()
+        .map(|participant| participant.component().bounds())
+        .chain(layout.notes().iter().map(|note| note.bounds()))
+        .reduce(|acc, bounds| acc.merge(&bounds))
+        .unwrap_or_default();
+
+    content_bounds.with_max_y(layout.max_lifeline_end())
+}
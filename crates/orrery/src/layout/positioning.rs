@@ -31,7 +31,7 @@ pub trait LayoutBounds {
 ///
 /// # Returns
 /// A vector of X positions for the centers of each element
-pub fn distribute_horizontally(
+pub(crate) fn distribute_horizontally(
     sizes: &[Size],
     min_spacing: f32,
     extra_spacings: Option<&[f32]>,
@@ -0,0 +1,687 @@
+//! Obstacle-avoiding orthogonal edge routing.
+//!
+//! When a component diagram sets `edge_routing="orthogonal"`, relations are
+//! routed by an [`OrthogonalRouter`] instead of the per-style placers in
+//! [`component`](super::component). Each route leaves its source and enters
+//! its target perpendicular to one of their sides, runs only horizontally and
+//! vertically, and keeps a margin around the bounding box of every component
+//! in the scope.
+//!
+//! # Algorithm
+//!
+//! The router builds a sparse grid from the edges of the inflated component
+//! bounds and the port stubs of the pair being routed, then runs Dijkstra's
+//! algorithm over `(grid point, heading)` states. Each turn adds a fixed
+//! penalty to the path length, so the cheapest route is the shortest one with
+//! the fewest bends. Collinear grid points are dropped from the result, and
+//! the remaining bends are rounded when the arrow is drawn.
+
+use std::{cmp::Ordering, collections::BinaryHeap, rc::Rc};
+
+use orrery_core::{
+    draw::{Arrow, ArrowDefinition, ArrowPath, ArrowStyle, ArrowWithText, PositionedArrowWithText},
+    geometry::{Bounds, Insets, Point},
+    semantic::Relation,
+};
+
+use crate::layout::component::{ArrowPlacer, Component, OrthogonalArrowPlacer};
+
+/// Distance below which two coordinates are considered equal.
+const EPSILON: f32 = 0.01;
+
+/// One of the four axis-aligned headings of a route segment.
+///
+/// A side of a component is named by its outward heading.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Heading {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Heading {
+    const ALL: [Heading; 4] = [Heading::Up, Heading::Right, Heading::Down, Heading::Left];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    fn opposite(self) -> Self {
+        match self {
+            Heading::Up => Heading::Down,
+            Heading::Right => Heading::Left,
+            Heading::Down => Heading::Up,
+            Heading::Left => Heading::Right,
+        }
+    }
+
+    /// Unit vector of the heading, with y growing downwards.
+    fn unit(self) -> Point {
+        match self {
+            Heading::Up => Point::new(0.0, -1.0),
+            Heading::Right => Point::new(1.0, 0.0),
+            Heading::Down => Point::new(0.0, 1.0),
+            Heading::Left => Point::new(-1.0, 0.0),
+        }
+    }
+}
+
+/// Where a route attaches to a component side.
+#[derive(Debug, Clone, Copy)]
+struct Port {
+    /// The point on the component's bounds.
+    point: Point,
+    /// The end of the stub leaving the port perpendicular to the side.
+    stub: Point,
+    /// The outward heading of the side.
+    side: Heading,
+}
+
+/// An entry in the Dijkstra frontier, ordered by lowest cost first.
+#[derive(Debug, Clone, Copy)]
+struct Frontier {
+    cost: f32,
+    state: usize,
+}
+
+impl PartialEq for Frontier {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Frontier {}
+
+impl PartialOrd for Frontier {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Frontier {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed so that `BinaryHeap` pops the cheapest state first.
+        other
+            .cost
+            .total_cmp(&self.cost)
+            .then_with(|| other.state.cmp(&self.state))
+    }
+}
+
+/// The routing grid of one relation.
+///
+/// Grid points are the crossings of `xs` and `ys`; a point is blocked when it
+/// lies strictly inside an inflated obstacle.
+struct Grid<'o> {
+    xs: Vec<f32>,
+    ys: Vec<f32>,
+    obstacles: &'o [Bounds],
+    open: Vec<bool>,
+}
+
+impl<'o> Grid<'o> {
+    fn new(mut xs: Vec<f32>, mut ys: Vec<f32>, obstacles: &'o [Bounds]) -> Self {
+        for coords in [&mut xs, &mut ys] {
+            coords.sort_by(f32::total_cmp);
+            coords.dedup_by(|a, b| (*a - *b).abs() < EPSILON);
+        }
+        let mut grid = Self {
+            xs,
+            ys,
+            obstacles,
+            open: Vec::new(),
+        };
+        grid.open = (0..grid.ys.len())
+            .flat_map(|j| (0..grid.xs.len()).map(move |i| (i, j)))
+            .map(|(i, j)| !grid.is_blocked(grid.point(i, j)))
+            .collect();
+        grid
+    }
+
+    fn node_count(&self) -> usize {
+        self.xs.len() * self.ys.len()
+    }
+
+    fn point(&self, i: usize, j: usize) -> Point {
+        Point::new(self.xs[i], self.ys[j])
+    }
+
+    fn node_point(&self, node: usize) -> Point {
+        self.point(node % self.xs.len(), node / self.xs.len())
+    }
+
+    /// Returns the grid node at `point`, if it is an open grid point.
+    fn node_at(&self, point: Point) -> Option<usize> {
+        let i = self
+            .xs
+            .iter()
+            .position(|x| (x - point.x()).abs() < EPSILON)?;
+        let j = self
+            .ys
+            .iter()
+            .position(|y| (y - point.y()).abs() < EPSILON)?;
+        let node = j * self.xs.len() + i;
+        self.open[node].then_some(node)
+    }
+
+    /// Returns the node next to `node` in `heading`, if the segment between
+    /// them is free.
+    fn neighbor(&self, node: usize, heading: Heading) -> Option<usize> {
+        let (i, j) = (node % self.xs.len(), node / self.xs.len());
+        let (i, j) = match heading {
+            Heading::Up => (i, j.checked_sub(1)?),
+            Heading::Down => (i, j + 1),
+            Heading::Left => (i.checked_sub(1)?, j),
+            Heading::Right => (i + 1, j),
+        };
+        if i >= self.xs.len() || j >= self.ys.len() {
+            return None;
+        }
+        let next = j * self.xs.len() + i;
+        // Every obstacle edge is a grid line, so a segment between adjacent
+        // grid points is blocked exactly when its midpoint is.
+        let midpoint = self.node_point(node).midpoint(self.node_point(next));
+        (self.open[next] && !self.is_blocked(midpoint)).then_some(next)
+    }
+
+    fn is_blocked(&self, point: Point) -> bool {
+        self.obstacles.iter().any(|bounds| {
+            point.x() > bounds.min_x() + EPSILON
+                && point.x() < bounds.max_x() - EPSILON
+                && point.y() > bounds.min_y() + EPSILON
+                && point.y() < bounds.max_y() - EPSILON
+        })
+    }
+}
+
+/// [`ArrowPlacer`] that routes relations with horizontal and vertical
+/// segments around the components of a scope.
+///
+/// Every relation is drawn as an [`ArrowStyle::Orthogonal`] polyline with
+/// rounded bends, whatever the style of its arrow. Parallel relations between
+/// the same pair attach to their sides at offset ports, so they run side by
+/// side instead of overlapping. Self-loops wrap around the top-right corner
+/// of their component, nesting outwards.
+///
+/// When no route avoids the obstacles, for example because components
+/// overlap, the relation falls back to the single-bend path of
+/// [`OrthogonalArrowPlacer`].
+#[derive(Debug, Clone)]
+pub(crate) struct OrthogonalRouter {
+    /// Component bounds, inflated by `margin`.
+    obstacles: Vec<Bounds>,
+    margin: f32,
+    lane_spacing: f32,
+    corner_radius: f32,
+    bend_penalty: f32,
+}
+
+impl OrthogonalRouter {
+    const DEFAULT_MARGIN: f32 = 12.0;
+    const MIN_MARGIN: f32 = 2.0;
+    const DEFAULT_LANE_SPACING: f32 = 10.0;
+    const DEFAULT_CORNER_RADIUS: f32 = 6.0;
+    const DEFAULT_BEND_PENALTY: f32 = 40.0;
+
+    /// Creates a router that avoids every component in `components`.
+    ///
+    /// The margin kept around components shrinks when they are closer together
+    /// than twice the default, so that routes still fit between them.
+    pub fn new(components: &[Component<'_>]) -> Self {
+        let bounds: Vec<Bounds> = components.iter().map(Component::bounds).collect();
+        let margin = Self::margin_for(&bounds);
+        let obstacles = bounds
+            .iter()
+            .map(|bounds| bounds.add_padding(Insets::uniform(margin)))
+            .collect();
+        Self {
+            obstacles,
+            margin,
+            lane_spacing: Self::DEFAULT_LANE_SPACING,
+            corner_radius: Self::DEFAULT_CORNER_RADIUS,
+            bend_penalty: Self::DEFAULT_BEND_PENALTY,
+        }
+    }
+
+    /// Returns the margin that fits between the closest pair of separate
+    /// components.
+    fn margin_for(bounds: &[Bounds]) -> f32 {
+        let mut margin = Self::DEFAULT_MARGIN;
+        for (k, a) in bounds.iter().enumerate() {
+            for b in &bounds[k + 1..] {
+                let gap_x = (a.min_x() - b.max_x()).max(b.min_x() - a.max_x());
+                let gap_y = (a.min_y() - b.max_y()).max(b.min_y() - a.max_y());
+                let gap = gap_x.max(gap_y);
+                // Overlapping components have no gap to keep clear.
+                if gap > 0.0 {
+                    margin = margin.min(gap / 2.0);
+                }
+            }
+        }
+        margin.max(Self::MIN_MARGIN)
+    }
+
+    /// Returns the port on the `side` of `bounds`, moved along the side by
+    /// `offset`.
+    ///
+    /// The offset is clamped to the middle half of the side.
+    fn port(&self, bounds: Bounds, side: Heading, offset: f32) -> Port {
+        let center = bounds.center();
+        let point = match side {
+            Heading::Up | Heading::Down => {
+                let limit = bounds.width() / 4.0;
+                let x = center.x() + offset.clamp(-limit, limit);
+                let y = if side == Heading::Up {
+                    bounds.min_y()
+                } else {
+                    bounds.max_y()
+                };
+                Point::new(x, y)
+            }
+            Heading::Left | Heading::Right => {
+                let limit = bounds.height() / 4.0;
+                let y = center.y() + offset.clamp(-limit, limit);
+                let x = if side == Heading::Left {
+                    bounds.min_x()
+                } else {
+                    bounds.max_x()
+                };
+                Point::new(x, y)
+            }
+        };
+        Port {
+            point,
+            stub: point.add_point(side.unit().scale(self.margin)),
+            side,
+        }
+    }
+
+    /// Routes a path from `source` to `target`, with ports moved along their
+    /// sides by `lane_offset`.
+    fn route(&self, source: &Component<'_>, target: &Component<'_>, lane_offset: f32) -> ArrowPath {
+        self.shortest_route(source.bounds(), target.bounds(), lane_offset)
+            .unwrap_or_else(|| {
+                let source_edge = source.find_intersection(target.position());
+                let target_edge = target.find_intersection(source.position());
+                OrthogonalArrowPlacer::path(source_edge, target_edge)
+            })
+            .with_corner_radius(self.corner_radius)
+    }
+
+    /// Finds the cheapest obstacle-free route between two bounds, or `None`
+    /// if every route is blocked.
+    fn shortest_route(
+        &self,
+        source: Bounds,
+        target: Bounds,
+        lane_offset: f32,
+    ) -> Option<ArrowPath> {
+        let source_ports = Heading::ALL.map(|side| self.port(source, side, lane_offset));
+        let target_ports = Heading::ALL.map(|side| self.port(target, side, lane_offset));
+
+        let mut xs: Vec<f32> = Vec::new();
+        let mut ys: Vec<f32> = Vec::new();
+        for bounds in &self.obstacles {
+            xs.extend([bounds.min_x(), bounds.max_x()]);
+            ys.extend([bounds.min_y(), bounds.max_y()]);
+        }
+        for port in source_ports.iter().chain(&target_ports) {
+            xs.push(port.stub.x());
+            ys.push(port.stub.y());
+        }
+        // A line halfway between the pair lets simple routes bend in the middle.
+        let middle = source.center().midpoint(target.center());
+        xs.push(middle.x());
+        ys.push(middle.y());
+
+        let grid = Grid::new(xs, ys, &self.obstacles);
+        let state = |node: usize, heading: Heading| node * 4 + heading.index();
+
+        let mut costs = vec![f32::INFINITY; grid.node_count() * 4];
+        let mut previous = vec![usize::MAX; grid.node_count() * 4];
+        let mut frontier = BinaryHeap::new();
+
+        let mut starts = Vec::new();
+        for port in &source_ports {
+            if let Some(node) = grid.node_at(port.stub) {
+                let start = state(node, port.side);
+                costs[start] = 0.0;
+                starts.push((start, *port));
+                frontier.push(Frontier {
+                    cost: 0.0,
+                    state: start,
+                });
+            }
+        }
+        let goals: Vec<(usize, Port)> = target_ports
+            .iter()
+            .filter_map(|port| Some((grid.node_at(port.stub)?, *port)))
+            .collect();
+
+        let mut best: Option<(f32, usize, Port)> = None;
+        while let Some(Frontier {
+            cost,
+            state: current,
+        }) = frontier.pop()
+        {
+            if cost > costs[current] {
+                continue;
+            }
+            if best.is_some_and(|(best_cost, _, _)| cost >= best_cost) {
+                break;
+            }
+            let node = current / 4;
+            let heading = Heading::ALL[current % 4];
+
+            for &(goal, port) in &goals {
+                if goal != node {
+                    continue;
+                }
+                // The final stub enters the target against its side's heading.
+                let turn = if heading == port.side.opposite() {
+                    0.0
+                } else {
+                    self.bend_penalty
+                };
+                if best.is_none_or(|(best_cost, _, _)| cost + turn < best_cost) {
+                    best = Some((cost + turn, current, port));
+                }
+            }
+
+            for next_heading in Heading::ALL {
+                if next_heading == heading.opposite() {
+                    continue;
+                }
+                let Some(next) = grid.neighbor(node, next_heading) else {
+                    continue;
+                };
+                let length = grid
+                    .node_point(node)
+                    .sub_point(grid.node_point(next))
+                    .hypot();
+                let turn = if next_heading == heading {
+                    0.0
+                } else {
+                    self.bend_penalty
+                };
+                let next_state = state(next, next_heading);
+                let next_cost = cost + length + turn;
+                if next_cost < costs[next_state] {
+                    costs[next_state] = next_cost;
+                    previous[next_state] = current;
+                    frontier.push(Frontier {
+                        cost: next_cost,
+                        state: next_state,
+                    });
+                }
+            }
+        }
+
+        let (_, end, target_port) = best?;
+        let mut points = vec![target_port.point];
+        let mut current = end;
+        loop {
+            points.push(grid.node_point(current / 4));
+            match starts.iter().find(|(start, _)| *start == current) {
+                Some((_, source_port)) => {
+                    points.push(source_port.point);
+                    break;
+                }
+                None => current = previous[current],
+            }
+        }
+        points.reverse();
+
+        Some(Self::path_through(&points))
+    }
+
+    /// Builds an [`ArrowPath`] through `points`, dropping repeated and
+    /// collinear points so that only the bends remain.
+    fn path_through(points: &[Point]) -> ArrowPath {
+        let mut corners: Vec<Point> = Vec::with_capacity(points.len());
+        for &point in points {
+            if corners
+                .last()
+                .is_some_and(|last| last.sub_point(point).hypot() < EPSILON)
+            {
+                continue;
+            }
+            if let [.., before, last] = corners[..] {
+                let collinear_x = (before.x() - last.x()).abs() < EPSILON
+                    && (last.x() - point.x()).abs() < EPSILON;
+                let collinear_y = (before.y() - last.y()).abs() < EPSILON
+                    && (last.y() - point.y()).abs() < EPSILON;
+                if collinear_x || collinear_y {
+                    corners.pop();
+                }
+            }
+            corners.push(point);
+        }
+
+        let source = corners[0];
+        let destination = corners[corners.len() - 1];
+        ArrowPath::new(source, destination, corners[1..corners.len() - 1].to_vec())
+    }
+
+    /// Rectangular self-loop around the top-right corner of `component`.
+    ///
+    /// The `k`-th loop of a component keeps `k` lane spacings further out, so
+    /// several loops nest instead of overlapping.
+    fn self_loop(&self, component: &Component<'_>, k: usize) -> ArrowPath {
+        let bounds = component.bounds();
+        let inset = bounds.width().min(bounds.height()) / 4.0;
+        let reach = self.margin + k as f32 * self.lane_spacing;
+
+        let exit = Point::new(bounds.max_x() - inset, bounds.min_y());
+        let entry = Point::new(bounds.max_x(), bounds.min_y() + inset);
+        let corners = vec![
+            exit.with_y(bounds.min_y() - reach),
+            Point::new(bounds.max_x() + reach, bounds.min_y() - reach),
+            entry.with_x(bounds.max_x() + reach),
+        ];
+        ArrowPath::new(exit, entry, corners).with_corner_radius(self.corner_radius)
+    }
+
+    /// Packages a routed path as an orthogonal arrow for `relation`.
+    fn arrow<'a>(relation: &'a Relation, path: ArrowPath) -> PositionedArrowWithText<'a> {
+        let mut arrow_def = ArrowDefinition::clone(relation.arrow_definition());
+        arrow_def.set_style(ArrowStyle::Orthogonal);
+        let arrow = Arrow::new(Rc::new(arrow_def), relation.arrow_direction());
+        let arrow_with_text = ArrowWithText::new(arrow, relation.text());
+        PositionedArrowWithText::new(arrow_with_text, path)
+    }
+}
+
+impl ArrowPlacer for OrthogonalRouter {
+    fn place<'a>(
+        &self,
+        relations: &[&'a Relation],
+        source: &Component<'_>,
+        target: &Component<'_>,
+    ) -> Vec<PositionedArrowWithText<'a>> {
+        if source.node_id() == target.node_id() {
+            return relations
+                .iter()
+                .enumerate()
+                .map(|(k, relation)| Self::arrow(relation, self.self_loop(source, k)))
+                .collect();
+        }
+
+        let n = relations.len();
+        relations
+            .iter()
+            .enumerate()
+            .map(|(k, relation)| {
+                // Lanes are offset along the sides' own axis, so reverse
+                // relations share the same set of lanes without flipping.
+                let lane_offset = ((k as f32) - ((n - 1) as f32) / 2.0) * self.lane_spacing;
+                let (rel_src, rel_tgt) = if relation.source() == source.node_id() {
+                    (source, target)
+                } else {
+                    (target, source)
+                };
+                Self::arrow(relation, self.route(rel_src, rel_tgt, lane_offset))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use orrery_core::{
+        draw::{ArrowDirection, RectangleDefinition, Shape, ShapeDefinition, ShapeWithText},
+        geometry::Size,
+        identifier::Id,
+        semantic::{Block, Node},
+    };
+
+    use super::*;
+
+    fn make_node(name: &str) -> Node {
+        let id = Id::new(name);
+        let shape_def = Rc::new(Box::new(RectangleDefinition::new()) as Box<dyn ShapeDefinition>);
+        Node::new(id, None, Block::None, shape_def)
+    }
+
+    fn make_component<'a>(node: &'a Node, position: Point) -> Component<'a> {
+        let mut shape = Shape::new(Rc::clone(node.shape_definition()));
+        shape
+            .expand_content_size_to(Size::new(40.0, 20.0))
+            .expect("rectangles have content");
+        let shape_with_text = ShapeWithText::new(shape, None);
+        Component::new(node, shape_with_text, position)
+    }
+
+    fn make_relation(source: &Node, target: &Node) -> Relation {
+        Relation::new(
+            source.id(),
+            target.id(),
+            ArrowDirection::Forward,
+            None,
+            Rc::new(ArrowDefinition::default()),
+        )
+    }
+
+    fn assert_axis_aligned(path: &ArrowPath) {
+        for (start, end) in path.segments() {
+            assert!(
+                (start.x() - end.x()).abs() < EPSILON || (start.y() - end.y()).abs() < EPSILON,
+                "segment {start:?} -> {end:?} is diagonal"
+            );
+        }
+    }
+
+    fn crosses(path: &ArrowPath, bounds: Bounds) -> bool {
+        path.segments().any(|(start, end)| {
+            // Sample each segment; they are short relative to the bounds.
+            (0..=20).any(|step| {
+                let t = step as f32 / 20.0;
+                let point = start.add_point(end.sub_point(start).scale(t));
+                point.x() > bounds.min_x()
+                    && point.x() < bounds.max_x()
+                    && point.y() > bounds.min_y()
+                    && point.y() < bounds.max_y()
+            })
+        })
+    }
+
+    #[test]
+    fn route_goes_around_blocking_component() {
+        let (a_node, b_node, c_node) = (make_node("a"), make_node("b"), make_node("c"));
+        let components = vec![
+            make_component(&a_node, Point::new(0.0, 0.0)),
+            make_component(&b_node, Point::new(200.0, 0.0)),
+            make_component(&c_node, Point::new(100.0, 0.0)),
+        ];
+        let router = OrthogonalRouter::new(&components);
+        let relation = make_relation(&a_node, &b_node);
+
+        let arrows = router.place(&[&relation], &components[0], &components[1]);
+        assert_eq!(arrows.len(), 1);
+        let path = arrows[0].path();
+
+        assert_axis_aligned(path);
+        assert!(!crosses(path, components[2].bounds()));
+        assert!(!path.control_points().is_empty());
+        assert_eq!(
+            path.corner_radius(),
+            OrthogonalRouter::DEFAULT_CORNER_RADIUS
+        );
+    }
+
+    #[test]
+    fn unobstructed_aligned_route_is_straight() {
+        let (a_node, b_node) = (make_node("a"), make_node("b"));
+        let components = vec![
+            make_component(&a_node, Point::new(0.0, 0.0)),
+            make_component(&b_node, Point::new(200.0, 0.0)),
+        ];
+        let router = OrthogonalRouter::new(&components);
+        let relation = make_relation(&a_node, &b_node);
+
+        let arrows = router.place(&[&relation], &components[0], &components[1]);
+        let path = arrows[0].path();
+        assert!(path.control_points().is_empty());
+        assert_eq!(
+            path.source(),
+            Point::new(components[0].bounds().max_x(), 0.0)
+        );
+        assert_eq!(
+            path.destination(),
+            Point::new(components[1].bounds().min_x(), 0.0)
+        );
+    }
+
+    #[test]
+    fn parallel_relations_use_separate_lanes() {
+        let (a_node, b_node) = (make_node("a"), make_node("b"));
+        let components = vec![
+            make_component(&a_node, Point::new(0.0, 0.0)),
+            make_component(&b_node, Point::new(200.0, 80.0)),
+        ];
+        let router = OrthogonalRouter::new(&components);
+        let forward = make_relation(&a_node, &b_node);
+        let backward = make_relation(&b_node, &a_node);
+
+        let arrows = router.place(&[&forward, &backward], &components[0], &components[1]);
+        assert_eq!(arrows.len(), 2);
+        for arrow in &arrows {
+            assert_axis_aligned(arrow.path());
+        }
+        assert_ne!(arrows[0].path().source(), arrows[1].path().destination());
+    }
+
+    #[test]
+    fn self_loops_nest_around_the_corner() {
+        let a_node = make_node("a");
+        let components = vec![make_component(&a_node, Point::new(0.0, 0.0))];
+        let router = OrthogonalRouter::new(&components);
+        let (r1, r2) = (
+            make_relation(&a_node, &a_node),
+            make_relation(&a_node, &a_node),
+        );
+
+        let arrows = router.place(&[&r1, &r2], &components[0], &components[0]);
+        assert_eq!(arrows.len(), 2);
+        let bounds = components[0].bounds();
+        for arrow in &arrows {
+            assert_axis_aligned(arrow.path());
+            assert!(!crosses(arrow.path(), bounds));
+        }
+        let reach = |arrow: &PositionedArrowWithText<'_>| arrow.path().control_points()[1].x();
+        assert!(reach(&arrows[1]) > reach(&arrows[0]));
+    }
+
+    #[test]
+    fn margin_shrinks_between_close_components() {
+        let (a_node, b_node) = (make_node("a"), make_node("b"));
+        let a = make_component(&a_node, Point::new(0.0, 0.0));
+        let gap = 10.0;
+        let b_x = a.bounds().max_x() + gap + a.bounds().width() / 2.0;
+        let components = vec![a, make_component(&b_node, Point::new(b_x, 0.0))];
+
+        let router = OrthogonalRouter::new(&components);
+        assert_eq!(router.margin, gap / 2.0);
+    }
+}
@@ -3,12 +3,14 @@
 //! This module computes positions for participants, messages, activations,
 //! and fragments in sequence diagrams.
 
-use std::{collections::HashMap, rc::Rc};
+use std::rc::Rc;
+
+use indexmap::IndexMap;
 
 use orrery_core::{
     draw::{
         ActivationBox as DrawActivationBox, ActivationBoxDefinition, Fragment as DrawFragment,
-        FragmentSection as DrawFragmentSection, Lifeline, Note, PositionedArrowWithText,
+        FragmentSection as DrawFragmentSection, Highlight, Lifeline, Note, PositionedArrowWithText,
         PositionedDrawable,
     },
     geometry::{Bounds, Point, Size},
@@ -27,7 +29,7 @@ pub struct Participant<'a> {
 
 impl<'a> Participant<'a> {
     /// Create a participant from its component and lifeline.
-    pub fn new(component: Component<'a>, lifeline: PositionedDrawable<Lifeline>) -> Self {
+    pub(crate) fn new(component: Component<'a>, lifeline: PositionedDrawable<Lifeline>) -> Self {
         Self {
             component,
             lifeline,
@@ -58,7 +60,7 @@ pub struct ActivationBox {
 
 /// Start-side record of an activation, paired with an end Y to produce an [`ActivationBox`].
 #[derive(Debug, Clone)]
-pub struct ActivationTiming {
+pub(crate) struct ActivationTiming {
     participant_x: f32,
     start_y: f32,
     nesting_level: u32,
@@ -133,6 +135,12 @@ impl ActivationBox {
         &self.drawable
     }
 
+    /// Returns the bounds of this activation box, including its nesting offset.
+    pub fn bounds(&self) -> Bounds {
+        self.drawable
+            .calculate_bounds(Point::new(self.participant_x, self.center_y))
+    }
+
     /// Returns the activation edge facing a message endpoint at `target_x`.
     pub fn intersection_x(&self, participant_position: Point, target_x: f32) -> f32 {
         let bounds = self.calculate_bounds(participant_position);
@@ -167,7 +175,7 @@ impl ActivationBox {
 /// - `fragment`: Reference to the AST fragment being processed
 /// - `active_section`: Currently open section being processed (if any)
 /// - `sections`: Completed sections within this fragment
-pub struct FragmentTiming<'a> {
+pub(crate) struct FragmentTiming<'a> {
     start_y: f32,
     min_x: f32,
     max_x: f32,
@@ -290,13 +298,15 @@ impl<'a> FragmentTiming<'a> {
     }
 }
 
-/// Sequence layout containing participants, messages, activation boxes, notes and metrics.
+/// Sequence layout containing participants, messages, activation boxes, fragments, highlight
+/// bands, notes and metrics.
 #[derive(Debug, Clone)]
 pub struct Layout<'a> {
-    participants: HashMap<Id, Participant<'a>>,
+    participants: IndexMap<Id, Participant<'a>>,
     messages: Vec<PositionedArrowWithText<'a>>,
     activations: Vec<ActivationBox>,
     fragments: Vec<PositionedDrawable<DrawFragment>>,
+    highlights: Vec<PositionedDrawable<Highlight>>,
     notes: Vec<PositionedDrawable<Note>>,
     max_lifeline_end: f32, // TODO: Consider calculating on the fly.
     bounds: Bounds,
@@ -304,11 +314,12 @@ pub struct Layout<'a> {
 
 impl<'a> Layout<'a> {
     /// Construct a new sequence layout.
-    pub fn new(
-        participants: HashMap<Id, Participant<'a>>,
+    pub(crate) fn new(
+        participants: IndexMap<Id, Participant<'a>>,
         messages: Vec<PositionedArrowWithText<'a>>,
         activations: Vec<ActivationBox>,
         fragments: Vec<PositionedDrawable<DrawFragment>>,
+        highlights: Vec<PositionedDrawable<Highlight>>,
         notes: Vec<PositionedDrawable<Note>>,
         max_lifeline_end: f32,
     ) -> Self {
@@ -324,14 +335,15 @@ impl<'a> Layout<'a> {
             messages,
             activations,
             fragments,
+            highlights,
             notes,
             max_lifeline_end,
             bounds,
         }
     }
 
-    /// Borrow all participants in this sequence layout.
-    pub fn participants(&self) -> &HashMap<Id, Participant<'a>> {
+    /// Borrow all participants in this sequence layout, in declaration order.
+    pub fn participants(&self) -> &IndexMap<Id, Participant<'a>> {
         &self.participants
     }
 
@@ -350,6 +362,11 @@ impl<'a> Layout<'a> {
         &self.fragments
     }
 
+    /// Borrow all highlight bands in this sequence layout.
+    pub fn highlights(&self) -> &[PositionedDrawable<Highlight>] {
+        &self.highlights
+    }
+
     /// Borrow all notes in this sequence layout.
     pub fn notes(&self) -> &[PositionedDrawable<Note>] {
         &self.notes
@@ -5,26 +5,37 @@
 
 pub mod config;
 
+mod cache;
 mod error;
 mod export;
-mod layout;
+pub mod layout;
+mod outline;
 mod structure;
 
-pub use orrery_core::{color, draw, identifier, semantic};
-pub use orrery_parser::{InMemorySourceProvider, SourceProvider, error::ParseError};
+pub use orrery_core::{color, draw, geometry, identifier, semantic};
+pub use orrery_parser::{
+    ImplicitNodes, InMemorySourceProvider, Parsed, SourceProvider, error::ParseError, lint,
+};
 
+pub use cache::ParseCache;
 pub use error::RenderError;
+pub use outline::OutlineFormat;
 
-use std::{fs, path::Path};
+use std::{cell::OnceCell, io::Write, path::Path, rc::Rc};
 
 use bumpalo::Bump;
-use log::{debug, info, trace};
+use log::{debug, info, trace, warn};
 
-use orrery_core::geometry::Insets;
+use orrery_core::{
+    draw::{Density, FontTextMeasurer, TextMeasurer},
+    geometry::Insets,
+    identifier::Id,
+};
 use orrery_parser::ElaborateConfig;
 
-use config::AppConfig;
+use config::{AppConfig, LayoutConfig};
 use export::Exporter;
+use layout::layer::LayeredLayout;
 
 /// Builder for parsing and rendering Orrery diagrams.
 ///
@@ -56,6 +67,9 @@ use export::Exporter;
 pub struct DiagramBuilder<'a, P: SourceProvider> {
     config: AppConfig,
     provider: &'a P,
+    /// Measurer text is sized with, loaded on first use; `None` selects the
+    /// shared system fonts measurer.
+    text_measurer: OnceCell<Option<Rc<dyn TextMeasurer>>>,
 }
 
 impl<'a, P: SourceProvider> DiagramBuilder<'a, P> {
@@ -74,7 +88,36 @@ impl<'a, P: SourceProvider> DiagramBuilder<'a, P> {
     /// let builder = DiagramBuilder::new(config, &provider);
     /// ```
     pub fn new(config: AppConfig, provider: &'a P) -> Self {
-        Self { config, provider }
+        Self {
+            config,
+            provider,
+            text_measurer: OnceCell::new(),
+        }
+    }
+
+    /// Set the measurer that sizes text during layout and rendering.
+    ///
+    /// By default, text is measured with the system fonts, plus the
+    /// configured `font_path` font when there is one.
+    ///
+    /// # Arguments
+    ///
+    /// * `measurer` - The measurer to size every label with
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::rc::Rc;
+    /// # use orrery::{DiagramBuilder, InMemorySourceProvider, config::AppConfig, draw::HeuristicTextMeasurer};
+    /// let provider = InMemorySourceProvider::new();
+    /// let builder = DiagramBuilder::new(AppConfig::default(), &provider)
+    ///     .with_text_measurer(Rc::new(HeuristicTextMeasurer));
+    /// ```
+    pub fn with_text_measurer(self, measurer: Rc<dyn TextMeasurer>) -> Self {
+        Self {
+            text_measurer: OnceCell::from(Some(measurer)),
+            ..self
+        }
     }
 
     /// Parse an Orrery file into a semantic diagram.
@@ -83,6 +126,9 @@ impl<'a, P: SourceProvider> DiagramBuilder<'a, P> {
     /// and all its imports, then runs the full pipeline: resolve (tokenize →
     /// parse per file) → desugar → validate → elaborate.
     ///
+    /// Warnings are only logged; use
+    /// [`parse_with_warnings`](Self::parse_with_warnings) to receive them.
+    ///
     /// # Arguments
     ///
     /// * `root_path` — Path to the root/entry Orrery file
@@ -106,23 +152,173 @@ impl<'a, P: SourceProvider> DiagramBuilder<'a, P> {
     /// let diagram = builder.parse(&arena, Path::new("app.orr"))
     ///     .expect("Failed to parse diagram");
     /// ```
+    ///
+    /// The diagram's kind is available right after parsing, without laying it
+    /// out, for example to pick a toolbar for sequence or component diagrams:
+    ///
+    /// ```rust
+    /// # use std::path::Path;
+    /// # use bumpalo::Bump;
+    /// # use orrery::{DiagramBuilder, InMemorySourceProvider, config::AppConfig};
+    /// use orrery::semantic::DiagramKind;
+    ///
+    /// let arena = Bump::new();
+    /// let mut provider = InMemorySourceProvider::new();
+    /// provider.add_file("chat.orr", "diagram sequence; a: Rectangle; b: Rectangle; a -> b;");
+    ///
+    /// let builder = DiagramBuilder::new(AppConfig::default(), &provider);
+    /// let diagram = builder.parse(&arena, Path::new("chat.orr"))
+    ///     .expect("Failed to parse diagram");
+    ///
+    /// let toolbar = match diagram.kind() {
+    ///     DiagramKind::Component => "component-tools",
+    ///     DiagramKind::Sequence => "sequence-tools",
+    /// };
+    /// assert_eq!(toolbar, "sequence-tools");
+    /// ```
     pub fn parse<'b>(
         &self,
         arena: &'b Bump,
         root_path: &Path,
     ) -> Result<semantic::Diagram, ParseError<'b>> {
+        let parsed = self.parse_with_warnings(arena, root_path)?;
+        for warning in parsed.warnings() {
+            warn!("{}", warning.message());
+        }
+        Ok(parsed.into_diagram())
+    }
+
+    /// Parse an Orrery file, returning the diagram together with the warnings
+    /// reported while parsing it.
+    ///
+    /// Runs the same pipeline as [`parse`](Self::parse). Warnings do not
+    /// cause a failure; they are returned in the [`Parsed`] result along with
+    /// the source map their spans refer to.
+    ///
+    /// # Arguments
+    ///
+    /// * `root_path` — Path to the root/entry Orrery file
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError` for syntax errors, validation errors, or
+    /// elaboration errors.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::path::Path;
+    /// # use bumpalo::Bump;
+    /// # use orrery::{DiagramBuilder, InMemorySourceProvider, config::AppConfig};
+    /// let arena = Bump::new();
+    /// let mut provider = InMemorySourceProvider::new();
+    /// provider.add_file("app.orr", r#"diagram component; a: Rectangle; b: Rectangle; a -> b: " ";"#);
+    ///
+    /// let builder = DiagramBuilder::new(AppConfig::default(), &provider);
+    /// let parsed = builder.parse_with_warnings(&arena, Path::new("app.orr"))
+    ///     .expect("Failed to parse diagram");
+    /// assert_eq!(parsed.warnings().len(), 1);
+    /// ```
+    pub fn parse_with_warnings<'b>(
+        &self,
+        arena: &'b Bump,
+        root_path: &Path,
+    ) -> Result<Parsed<'b>, ParseError<'b>> {
         info!("Parsing diagram");
-        let elaborate_config = ElaborateConfig::new(
-            self.config.layout().component(),
-            self.config.layout().sequence(),
-        );
+        let parsed =
+            orrery_parser::parse(arena, root_path, self.provider, self.elaborate_config())?;
+
+        debug!(warnings = parsed.warnings().len(); "Diagram parsed successfully");
+        trace!(diagram:? = parsed.diagram(); "Parsed diagram");
 
-        let diagram = orrery_parser::parse(arena, root_path, self.provider, elaborate_config)?;
+        Ok(parsed)
+    }
 
-        debug!("Diagram parsed successfully");
-        trace!(diagram:?; "Parsed diagram");
+    /// Parse an Orrery file, reusing the diagram held in `cache` when none of
+    /// its sources changed since it was parsed.
+    ///
+    /// The root file and its imports are re-read through the builder's
+    /// [`SourceProvider`] and hashed. On a match the cached diagram is cloned;
+    /// otherwise the full pipeline runs as in [`parse`](Self::parse) and the
+    /// result replaces the cached one. Failed parses leave the cache untouched.
+    ///
+    /// # Arguments
+    ///
+    /// * `root_path` — Path to the root/entry Orrery file
+    /// * `cache` — Cache of the last diagram parsed by this builder
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError` for syntax errors, validation errors, or
+    /// elaboration errors.
+    pub fn parse_cached<'b>(
+        &self,
+        arena: &'b Bump,
+        root_path: &Path,
+        cache: &mut ParseCache,
+    ) -> Result<semantic::Diagram, ParseError<'b>> {
+        if let Some(diagram) = cache.lookup(self.provider, root_path) {
+            debug!("Reusing cached diagram");
+            return Ok(diagram.clone());
+        }
 
-        Ok(diagram)
+        let parsed = self.parse_with_warnings(arena, root_path)?;
+        for warning in parsed.warnings() {
+            warn!("{}", warning.message());
+        }
+        let files: Vec<_> = parsed
+            .source_map()
+            .files()
+            .iter()
+            .map(|file| (Path::new(file.name()), file.source()))
+            .collect();
+        cache.store(root_path, &files, parsed.diagram().clone());
+        Ok(parsed.into_diagram())
+    }
+
+    /// Lint an Orrery file against the rules of the configured
+    /// [`LintConfig`](lint::LintConfig).
+    ///
+    /// Runs the same pipeline as [`parse`](Self::parse), then applies the
+    /// lint rules at the level set in the `[lint]` configuration section.
+    ///
+    /// # Arguments
+    ///
+    /// * `root_path` — Path to the root/entry Orrery file
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError` if the file fails to parse. Lint findings,
+    /// including those configured as errors, are returned in the
+    /// [`LintReport`](lint::LintReport).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use std::path::Path;
+    /// # use bumpalo::Bump;
+    /// # use orrery::{DiagramBuilder, InMemorySourceProvider, config::AppConfig};
+    /// let arena = Bump::new();
+    /// let mut provider = InMemorySourceProvider::new();
+    /// provider.add_file("app.orr", "diagram component; app: Rectangle;");
+    ///
+    /// let builder = DiagramBuilder::new(AppConfig::default(), &provider);
+    /// let report = builder.lint(&arena, Path::new("app.orr"))
+    ///     .expect("Failed to parse diagram");
+    /// ```
+    pub fn lint<'b>(
+        &self,
+        arena: &'b Bump,
+        root_path: &Path,
+    ) -> Result<lint::LintReport<'b>, ParseError<'b>> {
+        info!("Linting diagram");
+        orrery_parser::lint(
+            arena,
+            root_path,
+            self.provider,
+            self.elaborate_config(),
+            self.config.lint(),
+        )
     }
 
     /// Render a semantic diagram to SVG string.
@@ -159,40 +355,319 @@ impl<'a, P: SourceProvider> DiagramBuilder<'a, P> {
     /// println!("{}", svg);
     /// ```
     pub fn render_svg(&self, diagram: &semantic::Diagram) -> Result<String, RenderError> {
+        let mut buffer = Vec::new();
+        self.render_svg_to_writer(diagram, &mut buffer)?;
+
+        String::from_utf8(buffer).map_err(|err| RenderError::Export(Box::new(err)))
+    }
+
+    /// Render a diagram to SVG, streaming the output into a writer.
+    ///
+    /// SVG bytes are written to `writer` as they are produced, so large
+    /// diagrams can be sent directly to a file, socket, or stdout without
+    /// buffering the whole document first.
+    ///
+    /// # Arguments
+    ///
+    /// * `diagram` - A semantic diagram to render
+    /// * `writer` - The destination for the SVG output
+    ///
+    /// # Errors
+    ///
+    /// Returns `RenderError` for layout or rendering errors, or if writing to
+    /// `writer` fails.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use std::{io, path::Path};
+    /// # use bumpalo::Bump;
+    /// # use orrery::{DiagramBuilder, InMemorySourceProvider, config::AppConfig};
+    /// let arena = Bump::new();
+    /// let mut provider = InMemorySourceProvider::new();
+    /// provider.add_file("app.orr", "diagram component; app: Rectangle;");
+    ///
+    /// let builder = DiagramBuilder::new(AppConfig::default(), &provider);
+    ///
+    /// let diagram = builder.parse(&arena, Path::new("app.orr"))
+    ///     .expect("Failed to parse");
+    ///
+    /// builder.render_svg_to_writer(&diagram, io::stdout().lock())
+    ///     .expect("Failed to render diagram");
+    /// ```
+    pub fn render_svg_to_writer<W: Write>(
+        &self,
+        diagram: &semantic::Diagram,
+        writer: W,
+    ) -> Result<(), RenderError> {
+        let layered_layout = self.layout(diagram)?;
+
+        let preset = SpacingPreset::for_diagram(self.config.layout(), diagram);
+        let style = self.config.style();
+        let mut svg_builder = export::svg::SvgBuilder::new()
+            .with_diagram(diagram)
+            .with_relations_on_top(self.config.export().relations_on_top())
+            .with_margin(preset.margin);
+        if let (Some(path), Some(family)) = (style.font_path(), style.font_family()) {
+            svg_builder = svg_builder.with_font_face(family, path);
+        }
+        if let Some(measurer) = self.text_measurer() {
+            svg_builder = svg_builder.with_text_measurer(measurer);
+        }
+        let mut svg_exporter = svg_builder.build();
+
+        svg_exporter.export_layered_layout(&layered_layout, writer)?;
+
+        info!("SVG rendered successfully");
+        Ok(())
+    }
+
+    /// Compute the layout of a diagram without rendering it.
+    ///
+    /// The returned [`LayeredLayout`] holds the positions computed by the
+    /// layout engines, which custom renderers can consume directly.
+    ///
+    /// # Arguments
+    ///
+    /// * `diagram` - A semantic diagram to lay out
+    ///
+    /// # Errors
+    ///
+    /// Returns `RenderError` for structure or layout errors.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::path::Path;
+    /// # use bumpalo::Bump;
+    /// # use orrery::{DiagramBuilder, InMemorySourceProvider, config::AppConfig};
+    /// let arena = Bump::new();
+    /// let mut provider = InMemorySourceProvider::new();
+    /// provider.add_file("app.orr", "diagram sequence; a: Rectangle; b: Rectangle; a -> b;");
+    ///
+    /// let builder = DiagramBuilder::new(AppConfig::default(), &provider);
+    ///
+    /// let diagram = builder.parse(&arena, Path::new("app.orr"))
+    ///     .expect("Failed to parse");
+    ///
+    /// let layout = builder.layout(&diagram)
+    ///     .expect("Failed to lay out diagram");
+    ///
+    /// let bounds = layout.bounds();
+    /// assert!(bounds.width() > 0.0);
+    /// ```
+    pub fn layout<'d>(
+        &self,
+        diagram: &'d semantic::Diagram,
+    ) -> Result<LayeredLayout<'d>, RenderError> {
         // Build the diagram structure/graph
         info!(diagram_kind:? = diagram.kind(); "Building diagram structure");
         let diagram_hierarchy = structure::DiagramHierarchy::from_diagram(diagram)?;
         debug!("Structure built successfully");
 
         // Create layout engine
-        let engine_builder = layout::EngineBuilder::new()
-            .with_padding(Insets::uniform(35.0))
-            .with_min_spacing(50.0)
-            .with_horizontal_spacing(50.0)
-            .with_vertical_spacing(50.0)
-            .with_event_padding(15.0);
+        let preset = SpacingPreset::for_diagram(self.config.layout(), diagram);
+        let mut engine_builder = layout::EngineBuilder::new()
+            .with_padding(Insets::uniform(preset.padding))
+            .with_min_spacing(preset.spacing)
+            .with_horizontal_spacing(preset.spacing)
+            .with_vertical_spacing(preset.spacing)
+            .with_event_padding(preset.event_padding);
+        if let Some(spacing) = self.config.layout().rank_spacing() {
+            engine_builder = engine_builder.with_rank_spacing(spacing);
+        }
+        if let Some(spacing) = self.config.layout().intra_rank_spacing() {
+            engine_builder = engine_builder.with_intra_rank_spacing(spacing);
+        }
+        if let Some(measurer) = self.text_measurer() {
+            engine_builder = engine_builder.with_text_measurer(measurer);
+        }
 
         // Calculate layout
         info!("Processing diagrams in hierarchy");
         let layered_layout = engine_builder.build(&diagram_hierarchy)?;
         info!(layers_count = layered_layout.len(); "Layout calculated");
 
-        // Render to SVG using a temporary file
-        // TODO: In the future, modify SvgBuilder to support in-memory rendering
-        let temp_file =
-            tempfile::NamedTempFile::new().map_err(|err| RenderError::Export(Box::new(err)))?;
-        let temp_path = temp_file.path().to_string_lossy().to_string();
+        Ok(layered_layout)
+    }
 
-        let mut svg_exporter = export::svg::SvgBuilder::new(&temp_path)
-            .with_diagram(diagram)
-            .build();
+    /// Render the component outline of a diagram.
+    ///
+    /// The outline lists every component as a nested entry under its
+    /// container. Components of embedded diagrams are listed under the
+    /// component that embeds them.
+    ///
+    /// # Arguments
+    ///
+    /// * `diagram` - A semantic diagram to outline
+    /// * `format` - The output format of the outline
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::path::Path;
+    /// # use bumpalo::Bump;
+    /// # use orrery::{DiagramBuilder, InMemorySourceProvider, OutlineFormat, config::AppConfig};
+    /// let arena = Bump::new();
+    /// let mut provider = InMemorySourceProvider::new();
+    /// provider.add_file("app.orr", "diagram component; app: Rectangle { db: Rectangle; };");
+    ///
+    /// let builder = DiagramBuilder::new(AppConfig::default(), &provider);
+    ///
+    /// let diagram = builder.parse(&arena, Path::new("app.orr"))
+    ///     .expect("Failed to parse");
+    ///
+    /// let outline = builder.render_outline(&diagram, OutlineFormat::Markdown);
+    /// assert_eq!(outline, "- app (`app`)\n  - db (`app::db`)\n");
+    /// ```
+    pub fn render_outline(&self, diagram: &semantic::Diagram, format: OutlineFormat) -> String {
+        outline::render(diagram, format)
+    }
+
+    /// Render a component diagram as a Graphviz DOT graph.
+    ///
+    /// Components become nodes, components with nested content become
+    /// clusters, and relations become edges with their labels and
+    /// directions, so the diagram can be processed by existing Graphviz
+    /// tooling. Embedded diagrams are exported as plain nodes.
+    ///
+    /// # Arguments
+    ///
+    /// * `diagram` - A semantic component diagram to export
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RenderError::Export`] if `diagram` is a sequence diagram.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::path::Path;
+    /// # use bumpalo::Bump;
+    /// # use orrery::{DiagramBuilder, InMemorySourceProvider, config::AppConfig};
+    /// let arena = Bump::new();
+    /// let mut provider = InMemorySourceProvider::new();
+    /// provider.add_file("app.orr", "diagram component; api: Rectangle; db: Rectangle; api -> db;");
+    ///
+    /// let builder = DiagramBuilder::new(AppConfig::default(), &provider);
+    ///
+    /// let diagram = builder.parse(&arena, Path::new("app.orr"))
+    ///     .expect("Failed to parse");
+    ///
+    /// let dot = builder.render_dot(&diagram).expect("Failed to export");
+    /// assert!(dot.contains("\"api\" -> \"db\";"));
+    /// ```
+    pub fn render_dot(&self, diagram: &semantic::Diagram) -> Result<String, RenderError> {
+        info!(diagram_kind:? = diagram.kind(); "Exporting diagram to DOT");
+        Ok(export::dot::render(diagram)?)
+    }
 
-        svg_exporter.export_layered_layout(&layered_layout)?;
+    /// Returns the measurer text is sized with, or `None` for the shared
+    /// system fonts measurer.
+    ///
+    /// A configured `font_path` font is loaded into a measurer of its own the
+    /// first time one is needed. If it cannot be loaded, a warning is logged
+    /// and the system fonts are used.
+    fn text_measurer(&self) -> Option<Rc<dyn TextMeasurer>> {
+        self.text_measurer
+            .get_or_init(|| {
+                let style = self.config.style();
+                let (path, family) = (style.font_path()?, style.font_family()?);
+                match export::svg::read_asset(path)
+                    .and_then(|data| FontTextMeasurer::with_font_data(family, data))
+                {
+                    Ok(measurer) => Some(Rc::new(measurer)),
+                    Err(err) => {
+                        warn!(
+                            path:? = path, err:err;
+                            "Failed to load font for measuring text, falling back to the system fonts"
+                        );
+                        None
+                    }
+                }
+            })
+            .clone()
+    }
 
-        // Read the SVG content back from the temp file
-        let svg_string = fs::read_to_string(&temp_path).map_err(RenderError::Io)?;
+    fn elaborate_config(&self) -> ElaborateConfig {
+        let config = ElaborateConfig::new(
+            self.config.layout().component(),
+            self.config.layout().sequence(),
+        )
+        .with_palette(self.config.palette().clone())
+        .with_theme(self.config.style().theme())
+        .with_implicit_nodes(self.config.parse().implicit_nodes())
+        .with_autonumber(self.config.parse().autonumber());
+        match self.config.parse().implicit_node_type() {
+            Some(type_name) => config.with_implicit_node_type(Id::new(type_name)),
+            None => config,
+        }
+    }
+}
 
-        info!("SVG rendered successfully");
-        Ok(svg_string)
+/// Spacing applied by [`DiagramBuilder`] to the layout engines and exporters.
+pub(crate) struct SpacingPreset {
+    /// Padding inside shapes.
+    pub(crate) padding: f32,
+    /// Spacing between elements.
+    pub(crate) spacing: f32,
+    /// Vertical padding between sequence diagram events.
+    pub(crate) event_padding: f32,
+    /// Margin around the rendered content.
+    pub(crate) margin: f32,
+}
+
+impl SpacingPreset {
+    const COMPACT: Self = Self {
+        padding: 10.0,
+        spacing: 20.0,
+        event_padding: 5.0,
+        margin: 5.0,
+    };
+
+    const COMFORTABLE: Self = Self {
+        padding: 35.0,
+        spacing: 50.0,
+        event_padding: 15.0,
+        margin: 50.0,
+    };
+
+    const SPACIOUS: Self = Self {
+        padding: 50.0,
+        spacing: 90.0,
+        event_padding: 25.0,
+        margin: 80.0,
+    };
+
+    /// Returns the preset of the density `config` selects, with the spacing
+    /// keys that `config` sets replacing the preset values.
+    pub(crate) fn for_config(config: &LayoutConfig) -> Self {
+        Self::with_overrides(config, config.density())
+    }
+
+    /// Like [`for_config`](Self::for_config), but a `density` set on
+    /// `diagram` selects the preset in place of the configured one.
+    pub(crate) fn for_diagram(config: &LayoutConfig, diagram: &semantic::Diagram) -> Self {
+        let density = diagram
+            .definition()
+            .density()
+            .unwrap_or_else(|| config.density());
+        Self::with_overrides(config, density)
+    }
+
+    /// Returns the preset of `density`, with the spacing keys that `config`
+    /// sets replacing the preset values.
+    fn with_overrides(config: &LayoutConfig, density: Density) -> Self {
+        let preset = match density {
+            Density::Compact => Self::COMPACT,
+            Density::Comfortable => Self::COMFORTABLE,
+            Density::Spacious => Self::SPACIOUS,
+        };
+        Self {
+            padding: config.padding().unwrap_or(preset.padding),
+            spacing: config.spacing().unwrap_or(preset.spacing),
+            event_padding: config.event_padding().unwrap_or(preset.event_padding),
+            margin: config.margin().unwrap_or(preset.margin),
+        }
     }
 }
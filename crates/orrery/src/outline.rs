@@ -0,0 +1,46 @@
+//! Outline export of a diagram's component hierarchy.
+//!
+//! The outline is a nested list of components, including those of embedded
+//! diagrams, that documentation can use to navigate a diagram's structure.
+
+use std::fmt::Write;
+
+use orrery_core::semantic::{Diagram, OutlineEntry};
+
+/// Output format of [`DiagramBuilder::render_outline`](crate::DiagramBuilder::render_outline).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutlineFormat {
+    /// A nested Markdown bullet list, indented by two spaces per level.
+    #[default]
+    Markdown,
+    /// A JSON array of entries with `id`, `label`, optional `diagram`, and
+    /// `children` fields.
+    Json,
+}
+
+/// Renders the outline of `diagram` in the given `format`.
+pub(crate) fn render(diagram: &Diagram, format: OutlineFormat) -> String {
+    let outline = diagram.outline();
+    match format {
+        OutlineFormat::Markdown => {
+            let mut output = String::new();
+            write_markdown(&mut output, &outline, 0);
+            output
+        }
+        OutlineFormat::Json => serde_json::to_string_pretty(&outline)
+            .expect("Serializing an outline to JSON is infallible"),
+    }
+}
+
+fn write_markdown(output: &mut String, entries: &[OutlineEntry], depth: usize) {
+    for entry in entries {
+        let indent = "  ".repeat(depth);
+        write!(output, "{indent}- {} (`{}`)", entry.label(), entry.id())
+            .expect("Writing to String buffer is infallible");
+        if let Some(kind) = entry.diagram() {
+            write!(output, " — {kind} diagram").expect("Writing to String buffer is infallible");
+        }
+        output.push('\n');
+        write_markdown(output, entry.children(), depth + 1);
+    }
+}
@@ -14,7 +14,7 @@ mod component;
 mod graph_base;
 mod sequence;
 
-pub use component::{ComponentGraph, ContainmentScope};
+pub use component::{ComponentGraph, ContainmentScope, ScopeGroup};
 pub use sequence::{SequenceEvent, SequenceGraph};
 
 use log::trace;
@@ -82,7 +82,7 @@ pub struct GraphedDiagram<'a, 'idx> {
 
 impl<'a, 'idx> GraphedDiagram<'a, 'idx> {
     /// Returns a reference to the underlying diagram.
-    pub fn diagram(&self) -> &Diagram {
+    pub fn diagram(&self) -> &'a Diagram {
         self.diagram
     }
 
@@ -219,7 +219,7 @@ impl<'a, 'idx> DiagramHierarchy<'a, 'idx> {
     /// - The optional container ID (None for the root diagram)
     /// - A reference to the graphed diagram
     // PERF: This allocates extra queue.
-    pub fn iter_post_order(&self) -> impl Iterator<Item = (Option<Id>, &GraphedDiagram<'_, '_>)> {
+    pub fn iter_post_order(&self) -> impl Iterator<Item = (Option<Id>, &GraphedDiagram<'a, 'idx>)> {
         let mut stack = Vec::new();
         stack.push(&self.root);
         let mut i = 0;
@@ -6,6 +6,7 @@
 //!
 //! - [`ComponentGraph`]: The main graph structure that stores nodes (components) and edges (relations)
 //! - [`ContainmentScope`]: Groups components at the same hierarchical level within their containers
+//! - [`ScopeGroup`]: A group boundary drawn around some of the components of a scope
 //!
 //! # Architecture
 //!
@@ -14,16 +15,22 @@
 //! containment scopes. Each scope tracks:
 //! - The components at that level
 //! - The relations between those components
+//! - The notes declared at that level
 //! - The optional container component (for nested scopes)
 //!
+//! Group blocks do not create containment scopes: their components and relations are
+//! flattened into the enclosing scope, and each group only records which of the scope's
+//! components it encloses.
+//!
 //! Relations can cross containment boundaries (e.g., a component at the root level can have
-//! a relation to a component nested inside another component).
+//! a relation to a component nested inside another component). Those relations belong to
+//! no containment scope and are kept aside, to be placed once every scope is laid out.
 
 use log::debug;
 
 use orrery_core::{
     identifier::Id,
-    semantic::{Block, Element, Node, Relation},
+    semantic::{Block, Element, Group, Node, Note, Relation},
 };
 
 use super::{
@@ -32,6 +39,34 @@ use super::{
 };
 use crate::RenderError;
 
+/// A group boundary within a containment scope.
+///
+/// Records the components enclosed directly by a group and the groups nested
+/// inside it, as indices into [`ContainmentScope::groups`].
+#[derive(Debug)]
+pub struct ScopeGroup<'a> {
+    group: &'a Group,
+    node_ids: Vec<Id>,
+    nested: Vec<usize>,
+}
+
+impl<'a> ScopeGroup<'a> {
+    /// Returns the semantic group this boundary is drawn for.
+    pub fn group(&self) -> &'a Group {
+        self.group
+    }
+
+    /// Returns the IDs of the components enclosed directly by this group.
+    pub fn node_ids(&self) -> &[Id] {
+        &self.node_ids
+    }
+
+    /// Returns the indices of the groups nested directly inside this group.
+    pub fn nested(&self) -> &[usize] {
+        &self.nested
+    }
+}
+
 /// A containment scope within a component diagram.
 ///
 /// A containment scope groups nodes and relations that belong to the same
@@ -45,6 +80,8 @@ use crate::RenderError;
 pub struct ContainmentScope<'a, 'idx> {
     container: Option<Id>,
     graph: GraphInternal<'a, Id, EdgeIndex<'idx>>,
+    groups: Vec<ScopeGroup<'a>>,
+    notes: Vec<&'a Note>,
 }
 
 impl<'a, 'idx> ContainmentScope<'a, 'idx> {
@@ -63,6 +100,18 @@ impl<'a, 'idx> ContainmentScope<'a, 'idx> {
         self.graph.nodes()
     }
 
+    /// Returns the groups declared in this containment scope.
+    ///
+    /// Nested groups come before the group that encloses them.
+    pub fn groups(&self) -> &[ScopeGroup<'a>] {
+        &self.groups
+    }
+
+    /// Returns the notes declared in this containment scope, in source order.
+    pub fn notes(&self) -> &[&'a Note] {
+        &self.notes
+    }
+
     /// Returns the number of components in this containment scope.
     pub fn nodes_count(&self) -> usize {
         self.graph.nodes_count()
@@ -73,6 +122,8 @@ impl<'a, 'idx> ContainmentScope<'a, 'idx> {
         ContainmentScope {
             container,
             graph: GraphInternal::new(),
+            groups: Vec::new(),
+            notes: Vec::new(),
         }
     }
 
@@ -128,9 +179,10 @@ impl<'a, 'idx> ContainmentScope<'a, 'idx> {
         let mut child_diagrams = vec![];
 
         let mut containment_scope = ContainmentScope::new(container);
+        let elements = Self::flatten_groups(elements, &mut containment_scope.groups);
 
         // First pass: add all nodes to the graph
-        for element in elements {
+        for &element in &elements {
             if let Element::Node(node) = element {
                 graph.add_node(&mut containment_scope, node);
 
@@ -162,17 +214,19 @@ impl<'a, 'idx> ContainmentScope<'a, 'idx> {
             }
         }
 
-        // Second pass: add all relations and activation statements to the graph
-        for element in elements {
+        // Second pass: add all relations to the graph and collect the notes
+        for &element in &elements {
             match element {
                 Element::Relation(relation) => {
                     graph.add_relation(&mut containment_scope, relation);
                 }
+                Element::Note(note) => containment_scope.notes.push(note),
                 Element::Node(..) => {}
                 Element::Activate(..)
                 | Element::Deactivate(..)
                 | Element::Fragment(..)
-                | Element::Note(..) => {
+                | Element::Highlight(..)
+                | Element::Group(..) => {
                     unreachable!("Unexpected element type")
                 }
             }
@@ -182,6 +236,51 @@ impl<'a, 'idx> ContainmentScope<'a, 'idx> {
 
         Ok(child_diagrams)
     }
+
+    /// Returns `elements` with the contents of group blocks spliced in place
+    /// of the groups, recording each group in `groups`.
+    fn flatten_groups(
+        elements: &'a [Element],
+        groups: &mut Vec<ScopeGroup<'a>>,
+    ) -> Vec<&'a Element> {
+        let mut flattened = Vec::new();
+        for element in elements {
+            match element {
+                Element::Group(group) => {
+                    Self::flatten_group(group, groups, &mut flattened);
+                }
+                _ => flattened.push(element),
+            }
+        }
+        flattened
+    }
+
+    /// Splices the contents of `group` into `flattened` and records it in
+    /// `groups` after its nested groups, returning its index.
+    fn flatten_group(
+        group: &'a Group,
+        groups: &mut Vec<ScopeGroup<'a>>,
+        flattened: &mut Vec<&'a Element>,
+    ) -> usize {
+        let mut node_ids = Vec::new();
+        let mut nested = Vec::new();
+        for element in group.elements() {
+            match element {
+                Element::Group(inner) => nested.push(Self::flatten_group(inner, groups, flattened)),
+                Element::Node(node) => {
+                    node_ids.push(node.id());
+                    flattened.push(element);
+                }
+                _ => flattened.push(element),
+            }
+        }
+        groups.push(ScopeGroup {
+            group,
+            node_ids,
+            nested,
+        });
+        groups.len() - 1
+    }
 }
 
 /// Main graph structure for component diagrams.
@@ -198,11 +297,12 @@ impl<'a, 'idx> ContainmentScope<'a, 'idx> {
 pub struct ComponentGraph<'a, 'idx> {
     graph: GraphInternal<'idx, &'a Node, &'a Relation>,
     containment_scopes: Vec<ContainmentScope<'a, 'idx>>,
+    cross_scope_relations: Vec<EdgeIndex<'idx>>,
 }
 
 impl<'a, 'idx> ComponentGraph<'a, 'idx> {
     /// Returns the AST node for a component with the given ID, if it exists.
-    pub fn node_by_id(&self, id: Id) -> Option<&Node> {
+    pub fn node_by_id(&self, id: Id) -> Option<&'a Node> {
         self.graph.node(id)
     }
 
@@ -217,7 +317,10 @@ impl<'a, 'idx> ComponentGraph<'a, 'idx> {
     ///
     /// This provides access to the AST nodes for all components at a particular
     /// hierarchical level in the diagram.
-    pub fn scope_nodes(&self, containment_scope: &ContainmentScope) -> impl Iterator<Item = &Node> {
+    pub fn scope_nodes(
+        &self,
+        containment_scope: &ContainmentScope,
+    ) -> impl Iterator<Item = &'a Node> {
         containment_scope
             .node_ids()
             .map(|id| self.graph.node_unchecked(id))
@@ -230,17 +333,28 @@ impl<'a, 'idx> ComponentGraph<'a, 'idx> {
     pub fn scope_relations(
         &self,
         containment_scope: &ContainmentScope,
-    ) -> impl Iterator<Item = &Relation> {
+    ) -> impl Iterator<Item = &'a Relation> {
         containment_scope
             .relation_indices()
             .map(|idx| self.graph.edge_unchecked(idx))
     }
 
+    /// Returns an iterator over the relations whose endpoints are not both in
+    /// the containment scope the relation is declared in, in source order.
+    pub fn cross_scope_relations(&self) -> impl Iterator<Item = &'a Relation> {
+        self.cross_scope_relations
+            .iter()
+            .map(|&idx| self.graph.edge_unchecked(idx))
+    }
+
     /// Returns an iterator over root nodes in a containment scope.
     ///
     /// Root nodes are components that have no incoming relations within the scope,
     /// typically representing top-level components in the hierarchy.
-    pub fn scope_roots(&self, containment_scope: &ContainmentScope) -> impl Iterator<Item = &Node> {
+    pub fn scope_roots(
+        &self,
+        containment_scope: &ContainmentScope,
+    ) -> impl Iterator<Item = &'a Node> {
         containment_scope
             .root_ids()
             .map(|id| self.graph.node_unchecked(id))
@@ -254,7 +368,7 @@ impl<'a, 'idx> ComponentGraph<'a, 'idx> {
         &self,
         containment_scope: &ContainmentScope,
         source_id: Id,
-    ) -> impl Iterator<Item = &Node> {
+    ) -> impl Iterator<Item = &'a Node> {
         containment_scope
             .outgoing_node_ids(source_id)
             .map(|id| self.graph.node_unchecked(id))
@@ -277,6 +391,7 @@ impl<'a, 'idx> ComponentGraph<'a, 'idx> {
         Self {
             graph: GraphInternal::new(),
             containment_scopes: Vec::new(),
+            cross_scope_relations: Vec::new(),
         }
     }
 
@@ -286,12 +401,12 @@ impl<'a, 'idx> ComponentGraph<'a, 'idx> {
         containment_scope.add_node(node);
     }
 
-    /// Adds a relation to the graph and potentially to its containment scope.
+    /// Adds a relation to the graph and to its containment scope.
     ///
     /// Relations are always added to the global graph, but only added to the
-    /// containment scope if both endpoints exist in that scope. This handles
-    /// cross-scope relations where components at different hierarchical levels
-    /// are connected.
+    /// containment scope if both endpoints exist in that scope. Otherwise the
+    /// relation connects components at different hierarchical levels and is
+    /// recorded as a cross-scope relation.
     fn add_relation(
         &mut self,
         containment_scope: &mut ContainmentScope<'a, 'idx>,
@@ -302,12 +417,12 @@ impl<'a, 'idx> ComponentGraph<'a, 'idx> {
         let idx = self.graph.add_edge(source_id, target_id, relation);
 
         // Only add relation to containment scope if both nodes exist in that scope
-        // This handles cross-scope relations (e.g., metrics -> backend::user_db)
-        // where the target node may be in a nested scope
-        // TODO: Store cross containment in another variable
+        // Cross-scope relations (e.g., metrics -> backend::user_db) are kept aside
         if containment_scope.contains_node(source_id) && containment_scope.contains_node(target_id)
         {
             containment_scope.add_relation(source_id, target_id, idx);
+        } else {
+            self.cross_scope_relations.push(idx);
         }
     }
 }
@@ -13,7 +13,8 @@
 //! - [`GraphInternal`]: Core graph implementation with nodes and edges
 //!
 //! Capabilities:
-//! - Node and edge storage via `HashMap` and `Vec`
+//! - Node storage via `IndexMap`, so nodes iterate in insertion order, and
+//!   edge storage via `Vec`
 //! - Tracking of both incoming and outgoing edges per node
 //! - Root detection (nodes with no incoming edges)
 //! - Type-safe node and edge access with lifetime guarantees
@@ -23,6 +24,8 @@
 
 use std::{collections::HashMap, marker::PhantomData};
 
+use indexmap::IndexMap;
+
 use orrery_core::identifier::Id;
 
 // =============================================================================
@@ -97,7 +100,7 @@ where
     N: Copy + std::fmt::Debug,
     E: Copy + std::fmt::Debug,
 {
-    nodes: HashMap<Id, N>,
+    nodes: IndexMap<Id, N>,
     edges: Vec<Edge<E>>,
     income_edges: HashMap<Id, Vec<EdgeIndex<'idx>>>,
     outgoing_edges: HashMap<Id, Vec<EdgeIndex<'idx>>>,
@@ -111,7 +114,7 @@ where
     /// Creates a new empty graph.
     pub(super) fn new() -> Self {
         GraphInternal {
-            nodes: HashMap::new(),
+            nodes: IndexMap::new(),
             edges: Vec::new(),
             income_edges: HashMap::new(),
             outgoing_edges: HashMap::new(),
@@ -131,7 +134,7 @@ where
         self.nodes[&id]
     }
 
-    /// Returns an iterator over all node data in the graph.
+    /// Returns an iterator over all node data in the graph, in insertion order.
     pub(super) fn nodes(&self) -> impl Iterator<Item = N> {
         self.nodes.values().copied()
     }
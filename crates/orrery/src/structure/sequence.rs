@@ -24,7 +24,8 @@ use orrery_core::{
     draw::LifelineDefinition,
     identifier::Id,
     semantic::{
-        Activate, Block, Diagram, Element, Fragment, FragmentSection, Node, Note, Relation,
+        Activate, Block, Diagram, Element, Fragment, FragmentSection, Highlight, Node, Note,
+        Relation,
     },
 };
 
@@ -45,6 +46,8 @@ use crate::{RenderError, structure::HierarchyNode};
 /// * [`SequenceEvent::FragmentSectionStart`] - Start of a section within a fragment
 /// * [`SequenceEvent::FragmentSectionEnd`] - End of a section within a fragment
 /// * [`SequenceEvent::FragmentEnd`] - End of a fragment block
+/// * [`SequenceEvent::HighlightStart`] - Start of a highlight band
+/// * [`SequenceEvent::HighlightEnd`] - End of a highlight band
 #[derive(Debug)]
 pub enum SequenceEvent<'a> {
     /// A message or relation between two participants.
@@ -88,6 +91,15 @@ pub enum SequenceEvent<'a> {
     /// Marks the end of a fragment's scope, closing the grouping of interactions.
     FragmentEnd,
 
+    /// Start of a highlight band.
+    ///
+    /// Highlight bands tint the time window covered by their enclosed events
+    /// across all lifelines.
+    HighlightStart(&'a Highlight),
+
+    /// End of a highlight band.
+    HighlightEnd,
+
     /// A note annotation in the diagram.
     ///
     /// Notes provide additional context or documentation without participating
@@ -128,7 +140,7 @@ impl<'a> SequenceGraph<'a> {
     ///
     /// # Returns
     /// An iterator yielding [`Relation`] items for message events only, in temporal order.
-    pub fn relations(&self) -> impl Iterator<Item = &Relation> {
+    pub fn relations(&self) -> impl Iterator<Item = &'a Relation> {
         self.events().filter_map(|event| match event {
             SequenceEvent::Relation(relation) => Some(*relation),
             _ => None,
@@ -136,7 +148,7 @@ impl<'a> SequenceGraph<'a> {
     }
 
     /// Returns an iterator over all participant nodes in the sequence diagram.
-    pub fn nodes(&self) -> impl Iterator<Item = &Node> {
+    pub fn nodes(&self) -> impl Iterator<Item = &'a Node> {
         self.nodes.values().cloned()
     }
 
@@ -256,9 +268,21 @@ impl<'a> SequenceGraph<'a> {
                     // Emit FragmentEnd event
                     graph.add_event(SequenceEvent::FragmentEnd);
                 }
+                Element::Highlight(highlight) => {
+                    graph.add_event(SequenceEvent::HighlightStart(highlight));
+
+                    let mut highlight_child_diagrams =
+                        Self::process_elements(highlight.elements(), graph)?;
+                    child_diagrams.append(&mut highlight_child_diagrams);
+
+                    graph.add_event(SequenceEvent::HighlightEnd);
+                }
                 Element::Note(note) => {
                     graph.add_event(SequenceEvent::Note(note));
                 }
+                Element::Group(..) => {
+                    unreachable!("Unexpected group block in sequence diagram")
+                }
             }
         }
 
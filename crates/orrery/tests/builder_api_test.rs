@@ -2,11 +2,94 @@
 //!
 //! These tests verify that the public API works and is usable.
 
-use std::path::Path;
+use std::{num::NonZeroU16, path::Path, rc::Rc};
 
 use bumpalo::Bump;
 
-use orrery::{DiagramBuilder, InMemorySourceProvider, config::AppConfig};
+use orrery::{
+    DiagramBuilder, ImplicitNodes, InMemorySourceProvider, OutlineFormat, ParseCache, RenderError,
+    color::Color,
+    config::{AppConfig, ExportConfig, LayoutConfig, ParseConfig, StyleConfig},
+    draw::{Density, TextDefinition, TextMeasurer},
+    geometry::{Bounds, Point, Size},
+    identifier::Id,
+    layout::{
+        component::Layout as ComponentLayout,
+        layer::{LayeredLayout, LayoutContent},
+    },
+    semantic::{self, DiagramKind, LayoutEngine},
+};
+
+/// Parses `source` with `config` as the file `test.orr`.
+fn parse_with(config: AppConfig, source: impl Into<String>) -> semantic::Diagram {
+    let mut provider = InMemorySourceProvider::new();
+    provider.add_file("test.orr", source);
+
+    let arena = Bump::new();
+    let builder = DiagramBuilder::new(config, &provider);
+    builder
+        .parse(&arena, Path::new("test.orr"))
+        .expect("Failed to parse diagram")
+}
+
+/// Parses `source` with the default configuration.
+fn parse(source: impl Into<String>) -> semantic::Diagram {
+    parse_with(AppConfig::default(), source)
+}
+
+/// Parses `source` with `config` and renders it as SVG.
+fn render_with(config: AppConfig, source: impl Into<String>) -> String {
+    let diagram = parse_with(config.clone(), source);
+    let provider = InMemorySourceProvider::new();
+    DiagramBuilder::new(config, &provider)
+        .render_svg(&diagram)
+        .expect("Failed to render")
+}
+
+/// Parses `source` with the default configuration and renders it as SVG.
+fn render(source: impl Into<String>) -> String {
+    render_with(AppConfig::default(), source)
+}
+
+/// Parses `source` with `config`, lays it out and passes the layout to `f`.
+fn with_layout<R>(
+    config: AppConfig,
+    source: impl Into<String>,
+    f: impl FnOnce(&LayeredLayout) -> R,
+) -> R {
+    let diagram = parse_with(config.clone(), source);
+    let provider = InMemorySourceProvider::new();
+    let layout = DiagramBuilder::new(config, &provider)
+        .layout(&diagram)
+        .expect("Failed to lay out diagram");
+    f(&layout)
+}
+
+/// Lays out the component diagram `source` and passes the layout of its
+/// top-level scope to `f`.
+fn with_component_layout<R>(source: impl Into<String>, f: impl FnOnce(&ComponentLayout) -> R) -> R {
+    with_layout(AppConfig::default(), source, |layout| {
+        let [layer] = layout.layers() else {
+            panic!("Expected a single layer");
+        };
+        let LayoutContent::Component(content_stack) = layer.content() else {
+            panic!("Expected component content");
+        };
+        f(content_stack
+            .iter()
+            .last()
+            .expect("component layout")
+            .content())
+    })
+}
+
+/// Returns the bounds of the element `name` in `layout`.
+fn bounds_of(layout: &LayeredLayout, name: &str) -> Bounds {
+    layout
+        .element_bounds()
+        .find_map(|(id, bounds)| (id == Id::new(name)).then_some(bounds))
+        .unwrap_or_else(|| panic!("No bounds for `{name}`"))
+}
 
 #[test]
 fn test_builder_api_exists() {
@@ -121,3 +204,1719 @@ fn test_builder_reusability() {
     assert!(svg1.contains("<svg"), "First SVG should be valid");
     assert!(svg2.contains("<svg"), "Second SVG should be valid");
 }
+
+#[test]
+fn test_empty_relation_label_renders_like_missing_label() {
+    let empty = render(r#"diagram sequence; a: Rectangle; b: Rectangle; a -> b: "";"#);
+    let missing = render("diagram sequence; a: Rectangle; b: Rectangle; a -> b;");
+
+    // Only the two participant names produce text; the empty label emits none
+    assert_eq!(empty.matches("<text").count(), 2);
+    // And it reserves no extra space: the canvas matches the unlabeled diagram
+    assert_eq!(empty.lines().next(), missing.lines().next());
+}
+
+#[test]
+fn test_async_message_renders_open_arrowhead() {
+    let svg = render("diagram sequence; a: Rectangle; b: Rectangle; a ->> b; b -> a;");
+
+    // The async message references the open arrowhead, the sync one the filled arrowhead
+    assert_eq!(
+        svg.matches("marker-end=\"url(#arrow-open-right-").count(),
+        1
+    );
+    assert_eq!(svg.matches("marker-end=\"url(#arrow-right-").count(), 1);
+    assert!(svg.contains("<marker id=\"arrow-open-right-"));
+}
+
+#[test]
+fn test_each_arrow_kind_renders_its_arrowheads() {
+    // Relation syntax, then the marker expected at the path's source and
+    // destination ends.
+    let kinds = [
+        ("->", None, Some("arrow-right-")),
+        ("<-", Some("arrow-left-"), None),
+        ("<->", Some("arrow-left-"), Some("arrow-right-")),
+        ("-", None, None),
+        ("->>", None, Some("arrow-open-right-")),
+        ("<<-", Some("arrow-open-left-"), None),
+    ];
+    for diagram_kind in ["component", "sequence"] {
+        for (arrow, start, end) in kinds {
+            let svg = render(format!(
+                "diagram {diagram_kind} [layout_engine=\"basic\"]; a: Rectangle; b: Rectangle; a {arrow} b;"
+            ));
+
+            for (attribute, marker) in [("marker-start", start), ("marker-end", end)] {
+                match marker {
+                    Some(marker) => assert!(
+                        svg.contains(&format!("{attribute}=\"url(#{marker}")),
+                        "{diagram_kind} `{arrow}`: expected {attribute} {marker}"
+                    ),
+                    None => assert!(
+                        !svg.contains(attribute),
+                        "{diagram_kind} `{arrow}`: unexpected {attribute}"
+                    ),
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_backward_relation_ranks_like_reversed_forward() {
+    let top_component = |relation: &str| {
+        with_component_layout(
+            format!(
+                "diagram component [layout_engine=\"sugiyama\"]; a: Rectangle; b: Rectangle; {relation};"
+            ),
+            |layout| {
+                layout
+                    .components()
+                    .iter()
+                    .min_by(|a, b| a.position().y().total_cmp(&b.position().y()))
+                    .expect("components")
+                    .node_id()
+            },
+        )
+    };
+
+    assert_eq!(top_component("a -> b"), "a");
+    assert_eq!(top_component("b -> a"), "b");
+    // `a <- b` flows from `b` to `a`.
+    assert_eq!(top_component("a <- b"), "b");
+    assert_eq!(top_component("a <<- b"), "b");
+}
+
+#[test]
+fn test_relation_stroke_styles_render_dasharray() {
+    let svg = render(
+        r#"diagram sequence;
+        a: Rectangle; b: Rectangle;
+        a -> @Arrow[stroke=[style="dashed"]] b;
+        b -> @Arrow[stroke=[style="dotted"]] a;
+        a -> @Arrow[stroke=[dash_pattern="4 2"]] b;"#,
+    );
+
+    let dasharrays: Vec<_> = svg
+        .lines()
+        .filter(|line| line.contains("<path") && line.contains("marker-end"))
+        .filter_map(|line| line.split("stroke-dasharray=\"").nth(1))
+        .filter_map(|rest| rest.split('"').next())
+        .collect();
+    assert_eq!(dasharrays, ["5,5", "2,3", "4,2"]);
+}
+
+#[test]
+fn test_plain_relation_renders_headless_in_both_kinds() {
+    for kind in ["component", "sequence"] {
+        let source = format!(
+            "diagram {kind} [layout_engine=\"basic\"]; a: Rectangle; b: Rectangle; a - b; b - a;"
+        );
+
+        let svg = render(&source);
+        assert!(
+            !svg.contains("marker-start"),
+            "{kind}: unexpected arrowhead"
+        );
+        assert!(!svg.contains("marker-end"), "{kind}: unexpected arrowhead");
+        assert!(!svg.contains("<marker"), "{kind}: unused marker definition");
+
+        // Each line still runs from its source to its target
+        let (a_x, b_x, paths): (f32, f32, Vec<_>) =
+            with_layout(AppConfig::default(), source, |layout| {
+                let [layer] = layout.layers() else {
+                    panic!("Expected a single layer");
+                };
+                match layer.content() {
+                    LayoutContent::Component(stack) => {
+                        let component = stack.iter().last().expect("component layout").content();
+                        let x = |name: &str| {
+                            component
+                                .components()
+                                .iter()
+                                .find(|component| component.node_id() == name)
+                                .expect("component")
+                                .position()
+                                .x()
+                        };
+                        let paths = component.relations().iter().map(|r| r.path().clone());
+                        (x("a"), x("b"), paths.collect())
+                    }
+                    LayoutContent::Sequence(stack) => {
+                        let sequence = stack.iter().last().expect("sequence layout").content();
+                        let x = |name: &str| {
+                            sequence.participants()[&Id::new(name)]
+                                .component()
+                                .position()
+                                .x()
+                        };
+                        let paths = sequence.messages().iter().map(|m| m.path().clone());
+                        (x("a"), x("b"), paths.collect())
+                    }
+                }
+            });
+        let [forward, backward] = paths.as_slice() else {
+            panic!("{kind}: expected two relations");
+        };
+        let a_to_b = (b_x - a_x).signum();
+        assert_eq!(
+            (forward.destination().x() - forward.source().x()).signum(),
+            a_to_b,
+            "{kind}"
+        );
+        assert_eq!(
+            (backward.destination().x() - backward.source().x()).signum(),
+            -a_to_b,
+            "{kind}"
+        );
+    }
+}
+
+#[test]
+fn test_relations_on_top_controls_layer_order() {
+    let render_with_export = |export: ExportConfig| {
+        render_with(
+            AppConfig::default().with_export(export),
+            "diagram sequence; a: Rectangle; b: Rectangle; a -> b;",
+        )
+    };
+    let arrow_before_content = |svg: &str| {
+        let content = svg.find("data-layer=\"content\"").expect("content layer");
+        let arrow = svg.find("data-layer=\"arrow\"").expect("arrow layer");
+        arrow < content
+    };
+
+    let on_top = render_with_export(ExportConfig::default());
+    assert!(!arrow_before_content(&on_top));
+
+    let behind = render_with_export(ExportConfig::default().with_relations_on_top(false));
+    assert!(arrow_before_content(&behind));
+}
+
+#[test]
+fn test_dark_style_recolors_builtin_defaults() {
+    let render_with_style = |style: StyleConfig| {
+        render_with(
+            AppConfig::default().with_style(style),
+            r#"
+            diagram sequence;
+            a: Actor;
+            b: Rectangle;
+            activate a {
+                a -> b: "request";
+            };
+            opt "cached" {
+                b -> a: "hit";
+            };
+            note [on=[a]]: "Dark mode";
+            "#,
+        )
+    };
+    let theme = StyleConfig::dark_default().theme();
+    let background = theme.background().expect("dark theme has a background");
+
+    let light = render_with_style(StyleConfig::default());
+    assert!(!light.contains(&background.to_string()));
+
+    let dark = render_with_style(StyleConfig::dark_default());
+    assert!(dark.contains(&format!("fill=\"{background}\"")));
+    assert!(dark.contains(&format!("stroke=\"{}\"", theme.stroke())));
+    assert!(dark.contains(&format!("fill=\"{}\"", theme.note_fill())));
+    assert!(!dark.contains("stroke=\"black\""));
+
+    let stroke = Color::new("orange").unwrap();
+    let custom = render_with_style(StyleConfig::dark_default().with_stroke_color(stroke));
+    assert!(custom.contains(&format!("stroke=\"{stroke}\"")));
+    assert!(!custom.contains(&format!("stroke=\"{}\"", theme.stroke())));
+}
+
+#[test]
+fn test_relation_text_style_applies_only_to_relation_labels() {
+    let render_styled = |style: &str, relation: &str| {
+        let style: StyleConfig = toml::from_str(style).expect("Failed to parse style");
+        render_with(
+            AppConfig::default().with_style(style),
+            format!(
+                r#"
+                diagram component [layout_engine="basic"];
+                a: Rectangle;
+                b: Rectangle;
+                {relation}
+                "#
+            ),
+        )
+    };
+    // The opening tag of the `<text>` element that renders `content`.
+    let text_tag = |svg: &str, content: &str| -> String {
+        let element = svg
+            .split("<text ")
+            .skip(1)
+            .find(|element| element.contains(&format!(">{content}</tspan>")))
+            .expect("text is rendered");
+        element[..element.find('>').unwrap()].to_string()
+    };
+
+    let style = r#"
+        [relation_text]
+        font_family = "Georgia"
+        font_size = 20
+        font_style = "italic"
+        color = "navy"
+    "#;
+    let plain = render_styled("", r#"a -> b: "calls";"#);
+    let styled = render_styled(style, r#"a -> b: "calls";"#);
+
+    let label = text_tag(&styled, "calls");
+    assert!(label.contains(r#"font-family="Georgia""#), "{label}");
+    assert!(label.contains(r#"font-size="20pt""#), "{label}");
+    assert!(label.contains(r#"font-style="italic""#), "{label}");
+    assert!(label.contains(r#"fill="navy""#), "{label}");
+    assert!(!text_tag(&plain, "calls").contains("font-style"));
+
+    // Component labels keep the shared text style; only their position
+    // moves to make room for the larger relation label.
+    for component in ["a", "b"] {
+        let label = text_tag(&styled, component);
+        assert!(label.contains(r#"font-family="Arial""#), "{label}");
+        assert!(label.contains(r#"font-size="11pt""#), "{label}");
+        assert!(!label.contains("font-style"), "{label}");
+        assert!(!label.contains("navy"), "{label}");
+    }
+
+    // A `text` attribute on the relation still wins over the configuration.
+    let overridden = render_styled(style, r#"a -> [text=[font_size=8]] b: "calls";"#);
+    let label = text_tag(&overridden, "calls");
+    assert!(label.contains(r#"font-size="8pt""#), "{label}");
+    assert!(label.contains(r#"font-family="Georgia""#), "{label}");
+}
+
+#[test]
+fn test_label_background_is_drawn_between_line_and_text() {
+    let render_styled = |style: &str| {
+        let style: StyleConfig = toml::from_str(style).expect("Failed to parse style");
+        render_with(
+            AppConfig::default().with_style(style),
+            r#"
+            diagram component [layout_engine="basic"];
+            a: Rectangle;
+            b: Rectangle;
+            a -> b: "calls";
+            "#,
+        )
+    };
+
+    let svg = render_styled("");
+    let line = svg.find("marker-end").expect("relation line is rendered");
+    let background = svg.find(r#"rx="3""#).expect("label background is rendered");
+    let text = svg.find(">calls<").expect("label is rendered");
+    assert!(line < background && background < text, "{svg}");
+
+    let svg = render_styled(r#"label_background = "navy""#);
+    assert!(svg.contains(r#"fill="navy""#), "{svg}");
+
+    let svg = render_styled(r#"label_background = "none""#);
+    assert!(svg.contains(">calls<"), "{svg}");
+    assert!(!svg.contains(r#"rx="3""#), "{svg}");
+}
+
+#[test]
+fn test_component_notes_are_placed_beside_their_anchors() {
+    for engine in ["basic", "sugiyama", "grid"] {
+        let svg = render(format!(
+            r#"
+            diagram component [layout_engine="{engine}"];
+            a: Rectangle;
+            b: Rectangle {{
+                c: Rectangle;
+            }};
+            a -> b;
+            note [on=[a], align="left"]: "left of a";
+            note [on=[a, b]]: "below both";
+            note [on=[b::c], align="top"]: "above c";
+            "#
+        ));
+
+        for content in ["left of a", "below both", "above c"] {
+            assert!(svg.contains(&format!(">{content}<")), "{engine}: {svg}");
+        }
+    }
+}
+
+#[test]
+fn test_style_font_size_resizes_components() {
+    let source = r#"
+        diagram component [layout_engine="basic"];
+        box: Rectangle;
+        pinned: Rectangle[text=[font_family="Arial", font_size=11]];
+        box -> pinned: "uses";
+    "#;
+    let sizes = |style: StyleConfig| {
+        with_layout(AppConfig::default().with_style(style), source, |layout| {
+            ["box", "pinned"].map(|name| bounds_of(layout, name).to_size())
+        })
+    };
+
+    let [small, small_pinned] = sizes(StyleConfig::default());
+    let style = StyleConfig::default()
+        .with_font_size(NonZeroU16::new(22).unwrap())
+        .with_font_family("Courier");
+    let [large, large_pinned] = sizes(style.clone());
+    let svg = render_with(AppConfig::default().with_style(style), source);
+
+    // The label is measured with the configured font size
+    assert!(large.width() > small.width());
+    assert!(large.height() > small.height());
+    assert!(svg.contains("font-size=\"22pt\""));
+    assert!(svg.contains("font-family=\"Courier\""));
+    // A font size set on the element still wins
+    assert_eq!(large_pinned, small_pinned);
+}
+
+#[test]
+fn test_style_min_width_enlarges_short_labels() {
+    let lay_out = |style: StyleConfig| {
+        with_layout(
+            AppConfig::default().with_style(style),
+            r#"
+            diagram component [layout_engine="basic"];
+            a: Rectangle;
+            b: Oval;
+            wide: Rectangle[min_width=200];
+            long as "A label much longer than the minimum width": Component;
+            "#,
+            |layout| ["a", "b", "wide", "long"].map(|name| bounds_of(layout, name).to_size()),
+        )
+    };
+
+    let [a, b, wide, long] = lay_out(StyleConfig::default());
+    assert!(a.width() < 100.0);
+    assert!(b.width() < 100.0);
+    assert!(wide.width() >= 200.0);
+
+    let [min_a, min_b, min_wide, min_long] = lay_out(
+        StyleConfig::default()
+            .with_min_width(100.0)
+            .with_min_height(60.0),
+    );
+    // A one-character label is enlarged to the configured minimum
+    assert!(min_a.width() >= 100.0);
+    assert!(min_a.height() >= 60.0);
+    assert!(min_b.width() >= 100.0);
+    // A minimum set on the element still wins
+    assert_eq!(min_wide.width(), wide.width());
+    // Labels wider than the minimum keep their measured size
+    assert_eq!(min_long.width(), long.width());
+}
+
+#[test]
+fn test_cjk_label_fits_inside_its_rectangle() {
+    let bounds = with_layout(
+        AppConfig::default(),
+        r#"
+        diagram component [layout_engine="basic"];
+        db as "数据库服务器": Rectangle;
+        "#,
+        |layout| bounds_of(layout, "db"),
+    );
+
+    // Each ideograph renders about one em (11pt) wide, inside the default
+    // padding of 35 on each side
+    let em = 11.0 * 96.0 / 72.0;
+    assert!(bounds.width() >= 6.0 * em + 2.0 * 35.0);
+}
+
+#[test]
+fn test_text_measurer_sizes_labels_in_layout_and_render() {
+    /// Measures every text as a 500px wide line.
+    struct WideMeasurer;
+
+    impl TextMeasurer for WideMeasurer {
+        fn measure(&self, _text: &str, _definition: &TextDefinition) -> Size {
+            Size::new(500.0, 20.0)
+        }
+    }
+
+    let source = r#"diagram component [layout_engine="basic"]; db: Rectangle;"#;
+    let diagram = parse(source);
+    let provider = InMemorySourceProvider::new();
+    let builder = DiagramBuilder::new(AppConfig::default(), &provider)
+        .with_text_measurer(Rc::new(WideMeasurer));
+
+    let layout = builder.layout(&diagram).expect("Failed to lay out diagram");
+    assert!(bounds_of(&layout, "db").width() >= 500.0);
+
+    // The exporter measures with the same measurer, so the canvas fits the label
+    let svg = builder.render_svg(&diagram).expect("Failed to render");
+    let width: f32 = svg
+        .split_once("width=\"")
+        .and_then(|(_, rest)| rest.split_once('"'))
+        .and_then(|(width, _)| width.parse().ok())
+        .expect("SVG has a width");
+    assert!(width >= 500.0, "unexpected width {width}");
+}
+
+#[test]
+fn test_font_path_embeds_font_face() {
+    let render_with_style = |style: StyleConfig| {
+        render_with(
+            AppConfig::default().with_style(style),
+            "diagram sequence; a: Rectangle;",
+        )
+    };
+
+    let font_path = std::env::temp_dir().join(format!("orrery-{}-Brand.ttf", std::process::id()));
+    std::fs::write(&font_path, b"foobar").expect("Failed to write font file");
+    let svg = render_with_style(StyleConfig::default().with_font_path(&font_path));
+    std::fs::remove_file(&font_path).expect("Failed to remove font file");
+
+    // The family defaults to the file stem and the font is inlined as base64
+    let family = font_path.file_stem().unwrap().to_str().unwrap();
+    assert!(svg.contains("<style>"));
+    assert!(svg.contains(&format!("@font-face {{ font-family: \"{family}\"")));
+    assert!(svg.contains("url(data:font/ttf;base64,Zm9vYmFy) format(\"truetype\")"));
+    assert!(svg.contains(&format!("font-family=\"{family}\"")));
+
+    // A missing font file still renders, referencing the family by name
+    let svg = render_with_style(
+        StyleConfig::default()
+            .with_font_family("Brand")
+            .with_font_path(&font_path),
+    );
+    assert!(!svg.contains("@font-face"));
+    assert!(svg.contains("font-family=\"Brand\""));
+}
+
+#[test]
+fn test_icon_inlines_local_images_and_skips_missing_files() {
+    let render_icon = |icon: &str| {
+        render(format!(
+            r#"
+            diagram component [layout_engine="basic"];
+            a as "Service with a long name": Component[icon={icon}];
+            "#
+        ))
+    };
+    let attr = |element: &str, name: &str| -> f32 {
+        let rest = element.split(&format!(" {name}=\"")).nth(1).unwrap();
+        rest.split('"').next().unwrap().parse().unwrap()
+    };
+
+    let icon_path = std::env::temp_dir().join(format!("orrery-{}-logo.png", std::process::id()));
+    std::fs::write(&icon_path, b"foobar").expect("Failed to write icon file");
+    let svg = render_icon(&format!(
+        r#"[source="{}", align="bottom-right", size=12]"#,
+        icon_path.display()
+    ));
+    std::fs::remove_file(&icon_path).expect("Failed to remove icon file");
+
+    // The file is inlined as base64 and drawn inside the component's corner
+    let image = svg.split("<image").nth(1).expect("icon is rendered");
+    assert!(image.contains("href=\"data:image/png;base64,Zm9vYmFy\""));
+    let rect = svg.split("<rect").nth(1).expect("component is rendered");
+    assert_eq!(attr(image, "width"), 12.0);
+    assert_eq!(
+        attr(image, "x") + 12.0 + 4.0,
+        attr(rect, "x") + attr(rect, "width")
+    );
+    assert_eq!(
+        attr(image, "y") + 12.0 + 4.0,
+        attr(rect, "y") + attr(rect, "height")
+    );
+
+    // Data URIs are used as is
+    let svg = render_icon(r#""data:image/png;base64,iVBORw0KGgo=""#);
+    assert!(svg.contains("href=\"data:image/png;base64,iVBORw0KGgo=\""));
+
+    // A missing file still renders the component, without the image
+    let svg = render_icon(&format!(r#""{}""#, icon_path.display()));
+    assert!(svg.contains("<rect"));
+    assert!(!svg.contains("<image"));
+}
+
+#[test]
+fn test_canvas_padding_expands_view_box_and_background() {
+    let render_sequence = |attributes: &str| {
+        render(format!(
+            "diagram sequence [{attributes}]; a: Rectangle; b: Rectangle; a -> b;"
+        ))
+    };
+    let view_box = |svg: &str| -> Vec<f32> {
+        let rest = svg.split("viewBox=\"").nth(1).expect("svg has a viewBox");
+        let value = rest.split('"').next().unwrap();
+        value.split(' ').map(|v| v.parse().unwrap()).collect()
+    };
+
+    let unpadded = view_box(&render_sequence("canvas_padding=0"));
+    let padded_svg = render_sequence(
+        r##"background_color="#fafafa", canvas_padding=[top=10, right=20, bottom=30, left=40]"##,
+    );
+    let padded = view_box(&padded_svg);
+
+    // The viewBox grows by the padding on every side
+    assert_eq!(padded[2], unpadded[2] + 20.0 + 40.0);
+    assert_eq!(padded[3], unpadded[3] + 10.0 + 30.0);
+
+    // The background covers the whole padded canvas
+    let background = padded_svg
+        .split("<rect")
+        .nth(1)
+        .expect("background is rendered");
+    assert!(background.contains("fill=\"rgb(250"));
+    assert!(background.contains(&format!("width=\"{}\"", padded[2])));
+    assert!(background.contains(&format!("height=\"{}\"", padded[3])));
+
+    // A transparent canvas has no background rectangle
+    let transparent = render_sequence(r#"background_color="transparent", canvas_padding=0"#);
+    assert_eq!(
+        transparent.matches("<rect").count(),
+        render_sequence("canvas_padding=0").matches("<rect").count()
+    );
+}
+
+#[test]
+fn test_diagram_width_and_height_set_root_attributes() {
+    let render_sequence = |attributes: &str| {
+        render(format!(
+            "diagram sequence [canvas_padding=0{attributes}]; a: Rectangle;"
+        ))
+    };
+    // The root element's `width`, `height` and `viewBox` values
+    let root = |svg: &str| -> (f32, f32, Vec<f32>) {
+        let root = svg.split('>').next().unwrap();
+        let attr = |name: &str| {
+            let rest = root.split(&format!(" {name}=\"")).nth(1).unwrap();
+            rest.split('"').next().unwrap().to_string()
+        };
+        let view_box = attr("viewBox")
+            .split(' ')
+            .map(|v| v.parse().unwrap())
+            .collect();
+        (
+            attr("width").parse().unwrap(),
+            attr("height").parse().unwrap(),
+            view_box,
+        )
+    };
+
+    // By default the SVG is sized to its content
+    let (width, height, natural) = root(&render_sequence(""));
+    assert_eq!(natural, vec![0.0, 0.0, width, height]);
+
+    // Explicit dimensions keep the viewBox, so the content scales to fit
+    let (width, height, view_box) = root(&render_sequence(", width=800, height=600"));
+    assert_eq!((width, height), (800.0, 600.0));
+    assert_eq!(view_box, natural);
+
+    // A single dimension keeps the content's aspect ratio
+    let (width, height, _) = root(&render_sequence(", width=800"));
+    assert_eq!(width, 800.0);
+    assert!((height - 800.0 * natural[3] / natural[2]).abs() < 0.01);
+
+    // `fit="none"` keeps the natural scale, centering the content
+    let (width, height, view_box) =
+        root(&render_sequence(r#", width=800, height=600, fit="none""#));
+    assert_eq!((width, height), (800.0, 600.0));
+    assert_eq!(view_box[2..], [800.0, 600.0]);
+    assert_eq!(view_box[0], (natural[2] - 800.0) / 2.0);
+    assert_eq!(view_box[1], (natural[3] - 600.0) / 2.0);
+}
+
+#[test]
+fn test_render_svg_to_writer_streams_svg() {
+    let diagram = parse("diagram sequence; a: Rectangle; b: Rectangle; a -> b: \"hello\";");
+
+    let provider = InMemorySourceProvider::new();
+    let mut buffer = Vec::new();
+    DiagramBuilder::new(AppConfig::default(), &provider)
+        .render_svg_to_writer(&diagram, &mut buffer)
+        .expect("Failed to render to writer");
+    let streamed = String::from_utf8(buffer).expect("SVG should be valid UTF-8");
+
+    assert!(streamed.starts_with("<svg"));
+    assert!(streamed.trim_end().ends_with("</svg>"));
+    assert!(streamed.contains(">hello<"));
+}
+
+#[test]
+fn test_layout_exposes_geometry_without_rendering() {
+    let source = "diagram sequence; a: Rectangle; b: Rectangle; a -> b: \"hello\";";
+    with_layout(AppConfig::default(), source, |layout| {
+        let bounds = layout.bounds();
+        assert!(bounds.width() > 0.0 && bounds.height() > 0.0);
+
+        let [layer] = layout.layers() else {
+            panic!("Expected a single layer");
+        };
+        let LayoutContent::Sequence(content_stack) = layer.content() else {
+            panic!("Expected sequence content");
+        };
+        let positioned = content_stack.iter().last().expect("sequence layout");
+        let sequence = positioned.content();
+
+        assert_eq!(sequence.participants().len(), 2);
+        for participant in sequence.participants().values() {
+            let component = participant.component();
+            let component_bounds = layer.to_diagram_bounds(positioned, component.bounds());
+            assert_eq!(component_bounds.merge(&bounds), bounds);
+            assert!(component.label_position().is_some());
+        }
+
+        let [message] = sequence.messages() else {
+            panic!("Expected a single message");
+        };
+        let path = message.path();
+        assert_eq!(path.source().y(), path.destination().y());
+        assert!(message.text_position().is_some());
+    });
+}
+
+#[test]
+fn test_start_positioned_label_sits_near_source() {
+    let source = r#"
+        diagram component [layout_engine="basic"];
+        source: Rectangle;
+        target: Rectangle;
+        source -> [label_pos="start"] target: "begins";
+    "#;
+    with_component_layout(source, |component_layout| {
+        let center_of = |name: &str| {
+            component_layout
+                .components()
+                .iter()
+                .find(|component| component.node_id() == Id::new(name))
+                .expect("component is laid out")
+                .position()
+        };
+        let [relation] = component_layout.relations() else {
+            panic!("Expected a single relation");
+        };
+        let label = relation.text_position().expect("relation has a label");
+        let path = relation.path();
+
+        let distance = |a: Point, b: Point| a.sub_point(b).hypot();
+        assert!(distance(label, center_of("source")) < distance(label, center_of("target")));
+        // The label sits on the first part of the path, not at its midpoint.
+        let path_length = distance(path.source(), path.destination());
+        assert!(distance(label, path.source()) < path_length / 3.0);
+    });
+}
+
+#[test]
+fn test_cross_scope_relations_are_rendered() {
+    let source = r#"
+        diagram component [layout_engine="basic"];
+        db: Rectangle;
+        backend: Rectangle {
+            api: Rectangle;
+            api -> db: "queries";
+        };
+        metrics: Rectangle;
+        metrics -> backend::api: "scrapes";
+    "#;
+
+    let svg = render(source);
+    assert!(svg.contains("queries"), "missing nested relation label");
+    assert!(svg.contains("scrapes"), "missing root relation label");
+
+    with_layout(AppConfig::default(), source, |layout| {
+        let [layer] = layout.layers() else {
+            panic!("Expected a single layer");
+        };
+        let LayoutContent::Component(content_stack) = layer.content() else {
+            panic!("Expected component content");
+        };
+        // Relation paths in diagram coordinates.
+        let paths: Vec<_> = content_stack
+            .iter()
+            .flat_map(|positioned_content| {
+                let offset = positioned_content.offset();
+                positioned_content
+                    .content()
+                    .relations()
+                    .iter()
+                    .map(move |relation| {
+                        let path = relation.path();
+                        (
+                            path.source().add_point(offset),
+                            path.destination().add_point(offset),
+                        )
+                    })
+            })
+            .collect();
+        assert_eq!(paths.len(), 2);
+
+        // Each path runs from the border of one endpoint to the other's.
+        let touches = |point: Point, name: &str| {
+            let bounds = bounds_of(layout, name);
+            (bounds.min_x() - 1.0..=bounds.max_x() + 1.0).contains(&point.x())
+                && (bounds.min_y() - 1.0..=bounds.max_y() + 1.0).contains(&point.y())
+        };
+        for (from, to) in [("backend::api", "db"), ("metrics", "backend::api")] {
+            assert!(
+                paths
+                    .iter()
+                    .any(|&(source, target)| touches(source, from) && touches(target, to)),
+                "no path from {from} to {to}: {paths:?}"
+            );
+        }
+    });
+}
+
+#[test]
+fn test_zero_curvature_keeps_parallel_relations_straight() {
+    // Largest distance of a path's control points from its source→destination line.
+    let max_bow = |curvature: &str| {
+        let source = format!(
+            r#"
+            diagram component [layout_engine="basic"];
+            a: Rectangle;
+            b: Rectangle;
+            a -> [curvature={curvature}] b;
+            b -> [curvature={curvature}] a;
+            "#
+        );
+        with_component_layout(source, |component_layout| {
+            let mut max_bow: f32 = 0.0;
+            for relation in component_layout.relations() {
+                let path = relation.path();
+                let line = path.destination().sub_point(path.source());
+                for cp in path.control_points() {
+                    let offset = cp.sub_point(path.source());
+                    let distance =
+                        (line.x() * offset.y() - line.y() * offset.x()).abs() / line.hypot();
+                    max_bow = max_bow.max(distance);
+                }
+            }
+            max_bow
+        })
+    };
+
+    assert!(max_bow("0.5") > 10.0, "default curvature bows the lanes");
+    assert!(
+        max_bow("0") < 0.01,
+        "zero curvature keeps the lanes straight"
+    );
+}
+
+#[test]
+fn test_min_length_increases_relation_distance() {
+    for engine in ["basic", "sugiyama"] {
+        let distance = |attributes: &str| {
+            let source = format!(
+                r#"
+                diagram component [layout_engine="{engine}"];
+                a: Rectangle;
+                b: Rectangle;
+                c: Rectangle;
+                a -> {attributes} b;
+                b -> c;
+                "#
+            );
+            with_layout(AppConfig::default(), source, |layout| {
+                let center_of = |name: &str| bounds_of(layout, name).center();
+                center_of("b").sub_point(center_of("a")).hypot()
+            })
+        };
+
+        let default = distance("");
+        assert_eq!(distance("[min_length=1]"), default, "{engine}");
+        assert!(
+            distance("[min_length=4]") > 2.0 * default,
+            "{engine}: min_length should stretch the relation"
+        );
+    }
+}
+
+#[test]
+fn test_orthogonal_edge_routing_uses_only_axis_aligned_segments() {
+    for engine in ["basic", "sugiyama"] {
+        let source = format!(
+            r#"
+            diagram component [layout_engine="{engine}", edge_routing="orthogonal"];
+            gateway: Rectangle;
+            auth: Rectangle;
+            users: Oval;
+            billing: Component;
+            gateway -> auth;
+            gateway -> users;
+            gateway -> [style="curved"] billing: "charges";
+            auth -> users;
+            users -> gateway;
+            "#
+        );
+
+        with_component_layout(&source, |component_layout| {
+            assert_eq!(component_layout.relations().len(), 5);
+
+            let mut bends = 0;
+            for relation in component_layout.relations() {
+                let path = relation.path();
+                bends += path.control_points().len();
+                for (start, end) in path.segments() {
+                    assert!(
+                        (start.x() - end.x()).abs() < 0.01 || (start.y() - end.y()).abs() < 0.01,
+                        "{engine}: segment {start:?} -> {end:?} is not horizontal or vertical"
+                    );
+                }
+            }
+            // The components are not all in a row, so some routes must turn.
+            assert!(bends > 0, "{engine}: expected bends");
+        });
+
+        // Bends are rounded when drawn.
+        let svg = render(source);
+        assert!(svg.contains(" Q "), "{engine}: expected rounded bends");
+    }
+
+    let dot = render_dot(r#"diagram component [edge_routing="orthogonal"]; a: Rectangle;"#)
+        .expect("Failed to export DOT");
+    assert!(dot.contains("splines=ortho;"));
+}
+
+#[test]
+fn test_group_boundaries_enclose_their_members() {
+    let source = r#"
+        diagram component [layout_engine="basic"];
+        client: Rectangle;
+        group "Backend" {
+            api: Rectangle;
+            group "Storage" {
+                db: Rectangle;
+            };
+            api -> db;
+        };
+        client -> api;
+    "#;
+
+    with_component_layout(source, |component_layout| {
+        // Group members stay in the enclosing scope.
+        assert_eq!(component_layout.components().len(), 3);
+        assert_eq!(component_layout.relations().len(), 2);
+
+        let bounds_of = |name: &str| {
+            component_layout
+                .components()
+                .iter()
+                .find(|component| component.node_id() == Id::new(name))
+                .expect("component is laid out")
+                .bounds()
+        };
+        let encloses = |outer: Bounds, inner: Bounds| {
+            outer.contains(inner.min_point())
+                && outer.contains(Point::new(inner.max_x(), inner.max_y()))
+        };
+
+        // Nested groups come first.
+        let [storage, backend] = component_layout.groups() else {
+            panic!("Expected two groups");
+        };
+        assert_eq!(storage.inner().label(), "Storage");
+        assert_eq!(backend.inner().label(), "Backend");
+        assert!(encloses(storage.bounds(), bounds_of("db")));
+        assert!(encloses(backend.bounds(), bounds_of("api")));
+        assert!(encloses(backend.bounds(), storage.bounds()));
+        assert!(!encloses(storage.bounds(), bounds_of("api")));
+    });
+
+    let svg = render(source);
+    assert!(svg.contains(r#"fill="none""#));
+    assert!(svg.contains("Storage"));
+}
+
+#[test]
+fn test_corner_radius_is_clamped_and_set_per_component() {
+    let svg = render(
+        r#"
+        diagram component [layout_engine="basic"];
+        type Card = Rectangle[corner_radius=8];
+        type Pill = Card[corner_radius=1000];
+        card: Card {
+            pill: Pill;
+            plain: Rectangle;
+        };
+        "#,
+    );
+
+    let attr = |rect: &str, name: &str| -> Option<f32> {
+        let rest = rect.split(&format!(" {name}=\"")).nth(1)?;
+        rest.split('"').next()?.parse().ok()
+    };
+    // (rx, ry, height) of each rectangle, outermost first
+    let rects: Vec<_> = svg
+        .split("<rect")
+        .skip(1)
+        .map(|rect| (attr(rect, "rx"), attr(rect, "ry"), attr(rect, "height")))
+        .collect();
+    assert_eq!(rects.len(), 3);
+
+    // The card keeps its radius
+    assert_eq!((rects[0].0, rects[0].1), (Some(8.0), Some(8.0)));
+    // The pill overrides it, clamped to half of its smaller side
+    let pill_height = rects[1].2.expect("pill has a height");
+    assert_eq!(rects[1].0, Some(pill_height / 2.0));
+    assert_eq!(rects[1].1, Some(pill_height / 2.0));
+    // The plain rectangle keeps sharp corners despite being nested in the card
+    assert_eq!((rects[2].0, rects[2].1), (None, None));
+}
+
+#[test]
+fn test_shadow_renders_shared_filter_without_changing_bounds() {
+    let header = |svg: &str| svg.split('>').next().unwrap_or_default().to_string();
+
+    let plain = render(
+        r#"
+        diagram component [layout_engine="basic"];
+        a: Rectangle;
+        b: Rectangle;
+        "#,
+    );
+    let shadowed = render(
+        r##"
+        diagram component [layout_engine="basic"];
+        type Raised = Rectangle[shadow=[blur=4, dx=2, dy=2, color="#0003"]];
+        a: Raised;
+        b: Raised;
+        "##,
+    );
+
+    // Both components share a single filter definition
+    assert_eq!(shadowed.matches("<filter").count(), 1);
+    assert_eq!(shadowed.matches("filter=\"url(#shadow-").count(), 2);
+    assert!(shadowed.contains("<feGaussianBlur"));
+    assert!(shadowed.contains("<feOffset"));
+    assert!(!plain.contains("<filter"));
+
+    // Shadows are purely visual and leave the document size untouched
+    assert_eq!(header(&plain), header(&shadowed));
+}
+
+#[test]
+fn test_hit_test_prefers_innermost_element() {
+    let source = r#"
+        diagram component [layout_engine="basic"];
+        outer: Rectangle {
+            inner: Rectangle {
+                core: Rectangle;
+            };
+        };
+        host: Rectangle embed {
+            diagram sequence;
+            guest: Rectangle;
+        };
+    "#;
+
+    with_layout(AppConfig::default(), source, |layout| {
+        let bounds_of = |name: &str| bounds_of(layout, name);
+        let hit = |point: Point| layout.hit_test(point).map(|id| id.to_string());
+
+        let outer = bounds_of("outer");
+        let inner = bounds_of("outer::inner");
+        let core = bounds_of("outer::inner::core");
+        assert_eq!(outer.merge(&inner), outer);
+        assert_eq!(inner.merge(&core), inner);
+
+        assert_eq!(hit(core.center()).as_deref(), Some("outer::inner::core"));
+        assert_eq!(
+            hit(Point::new(core.center().x(), inner.min_y() + 1.0)).as_deref(),
+            Some("outer::inner")
+        );
+        assert_eq!(
+            hit(Point::new(core.center().x(), outer.min_y() + 1.0)).as_deref(),
+            Some("outer")
+        );
+
+        let host = bounds_of("host");
+        let guest = bounds_of("host::guest");
+        assert_eq!(host.merge(&guest), host);
+        assert_eq!(hit(guest.center()).as_deref(), Some("host::guest"));
+        assert_eq!(
+            hit(Point::new(host.min_x() + 1.0, host.min_y() + 1.0)).as_deref(),
+            Some("host")
+        );
+
+        let layout_bounds = layout.bounds();
+        assert_eq!(
+            hit(Point::new(
+                layout_bounds.max_x() + 10.0,
+                layout_bounds.max_y()
+            )),
+            None
+        );
+    });
+}
+
+#[test]
+fn test_pinned_components_keep_their_position() {
+    for engine in ["basic", "sugiyama"] {
+        let source = format!(
+            "diagram component [layout_engine=\"{engine}\"];
+            a: Rectangle; b: Rectangle [x=400, y=300]; c: Rectangle [y=20];
+            a -> b; b -> c;"
+        );
+
+        with_component_layout(source, |component| {
+            let find = |name: &str| {
+                component
+                    .components()
+                    .iter()
+                    .find(|component| component.node_id() == name)
+                    .expect("component")
+            };
+
+            assert_eq!(find("b").position().x(), 400.0, "{engine}");
+            assert_eq!(find("b").position().y(), 300.0, "{engine}");
+            // Only the pinned axis of `c` is fixed
+            assert_eq!(find("c").position().y(), 20.0, "{engine}");
+
+            // Relations are routed to the pinned component
+            let b_bounds = find("b").bounds();
+            let touches_b = |point: Point| {
+                (b_bounds.min_x()..=b_bounds.max_x()).contains(&point.x())
+                    && (b_bounds.min_y()..=b_bounds.max_y()).contains(&point.y())
+            };
+            assert!(
+                component
+                    .relations()
+                    .iter()
+                    .any(|relation| touches_b(relation.path().destination())),
+                "{engine}"
+            );
+        });
+    }
+}
+
+#[test]
+fn test_pinned_components_do_not_overlap_others() {
+    let lay_out = |engine: &str, pin: &str| {
+        let source = format!(
+            "diagram component [layout_engine=\"{engine}\"];
+            a: Rectangle; b: Rectangle; c: Rectangle; d: Rectangle{pin};
+            a -> b; a -> c; c -> d;"
+        );
+        with_layout(AppConfig::default(), source, |layout| {
+            layout.element_bounds().collect::<Vec<_>>()
+        })
+    };
+
+    for engine in ["basic", "sugiyama", "grid"] {
+        // Pin `d` onto the spot `b` takes when nothing is pinned
+        let unpinned = lay_out(engine, "");
+        let (_, b_bounds) = unpinned
+            .iter()
+            .find(|(id, _)| *id == Id::new("b"))
+            .expect("component");
+        let center = b_bounds.center();
+
+        for pin in [
+            format!(" [x={}, y={}]", center.x(), center.y()),
+            format!(" [x={}]", center.x()),
+            format!(" [y={}]", center.y()),
+        ] {
+            let components = lay_out(engine, &pin);
+            for (i, (id, bounds)) in components.iter().enumerate() {
+                for (other_id, other_bounds) in &components[i + 1..] {
+                    assert!(
+                        !bounds.intersects(other_bounds),
+                        "{engine}{pin}: `{id}` overlaps `{other_id}`"
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_embedded_diagrams_use_their_own_layout_engine() {
+    let source = r#"
+        diagram component [layout_engine="sugiyama"];
+        layered: Rectangle embed {
+            diagram component [layout_engine="basic"];
+            a: Rectangle; b: Rectangle;
+            a -> b;
+        };
+        flow: Rectangle embed {
+            diagram sequence;
+            client: Rectangle; server: Rectangle;
+            client -> server: "request";
+        };
+        layered -> flow;
+    "#;
+
+    let kinds: Vec<_> = with_layout(AppConfig::default(), source, |layout| {
+        layout
+            .layers()
+            .iter()
+            .map(|layer| match layer.content() {
+                LayoutContent::Component(_) => "component",
+                LayoutContent::Sequence(_) => "sequence",
+            })
+            .collect()
+    });
+    assert_eq!(kinds.len(), 3);
+    assert_eq!(kinds.iter().filter(|kind| **kind == "sequence").count(), 1);
+}
+
+/// Lays out `source` and returns the bounds of every element.
+fn laid_out_bounds(source: &str) -> Vec<(Id, Bounds)> {
+    with_layout(AppConfig::default(), source, |layout| {
+        layout.element_bounds().collect()
+    })
+}
+
+#[test]
+fn test_grid_layout_packs_components_into_rows() {
+    for count in 2..=7 {
+        let mut source = String::from(r#"diagram component [layout_engine="grid", columns=2];"#);
+        for index in 0..count {
+            source.push_str(&format!("c{index}: Rectangle;"));
+        }
+        source.push_str("c0 -> c1;");
+
+        let bounds = laid_out_bounds(&source);
+        assert_eq!(bounds.len(), count);
+
+        let mut rows: Vec<f32> = Vec::new();
+        let mut columns: Vec<f32> = Vec::new();
+        for (_, bounds) in &bounds {
+            let center = bounds.center();
+            if !rows.iter().any(|y| (y - center.y()).abs() < 0.5) {
+                rows.push(center.y());
+            }
+            if !columns.iter().any(|x| (x - center.x()).abs() < 0.5) {
+                columns.push(center.x());
+            }
+        }
+        assert_eq!(rows.len(), count.div_ceil(2), "rows of {count} components");
+        assert_eq!(columns.len(), count.min(2), "columns of {count} components");
+    }
+}
+
+#[test]
+fn test_grid_layout_flows_around_pinned_components() {
+    let bounds = laid_out_bounds(
+        r#"
+        diagram component [layout_engine="grid", columns=2];
+        a: Rectangle;
+        b: Rectangle;
+        pinned: Rectangle [x=150, y=30];
+        c: Rectangle;
+        d: Rectangle;
+        "#,
+    );
+    let bounds_of = |name: &str| {
+        bounds
+            .iter()
+            .find_map(|(id, bounds)| (*id == Id::new(name)).then_some(*bounds))
+            .expect("component should be laid out")
+    };
+
+    let pinned = bounds_of("pinned");
+    for name in ["a", "b", "c", "d"] {
+        assert!(
+            !bounds_of(name).intersects(&pinned),
+            "`{name}` should flow around the pinned component"
+        );
+    }
+    assert!(bounds_of("d").center().y() > bounds_of("a").center().y());
+}
+
+/// Lays out a sugiyama diagram with a fan-out from `a` to `b` and `c`,
+/// returning the center positions of the three components.
+fn fan_out_positions(attributes: &str) -> [(f32, f32); 3] {
+    let source = format!(
+        "diagram component [layout_engine=\"sugiyama\"{attributes}];
+        a: Rectangle; b: Rectangle; c: Rectangle;
+        a -> b; a -> c;"
+    );
+
+    with_component_layout(source, |layout| {
+        ["a", "b", "c"].map(|name| {
+            let component = layout
+                .components()
+                .iter()
+                .find(|component| component.node_id() == name)
+                .expect("component is laid out");
+            let position = component.position();
+            (position.x(), position.y())
+        })
+    })
+}
+
+#[test]
+fn test_rank_spacing_only_changes_inter_rank_distance() {
+    let [a, b, c] = fan_out_positions("");
+    let [wide_a, wide_b, wide_c] = fan_out_positions(", rank_spacing=200");
+
+    // `b` and `c` share the rank below `a`
+    assert_eq!(b.1, c.1);
+    assert_eq!(wide_b.1, wide_c.1);
+
+    assert!(wide_b.1 - wide_a.1 > b.1 - a.1);
+    assert_eq!((wide_c.0 - wide_b.0).abs(), (c.0 - b.0).abs());
+}
+
+/// Renders `source` and returns the width and height of the SVG canvas.
+fn svg_canvas_size(source: &str, config: AppConfig) -> (f32, f32) {
+    let svg = render_with(config, source);
+
+    let attribute = |name: &str| -> f32 {
+        let start = svg.find(&format!(" {name}=\"")).expect("svg attribute") + name.len() + 3;
+        let end = start + svg[start..].find('"').expect("closing quote");
+        svg[start..end].parse().expect("numeric attribute")
+    };
+    (attribute("width"), attribute("height"))
+}
+
+#[test]
+fn test_compact_mode_shrinks_canvas() {
+    for source in [
+        r#"diagram component [layout_engine="basic"];
+        api: Rectangle { handler: Rectangle; };
+        db: Rectangle;
+        api -> db: "query";"#,
+        r#"diagram sequence;
+        client: Rectangle; server: Rectangle;
+        client -> server: "request";
+        server -> client: "response";"#,
+    ] {
+        let (width, height) = svg_canvas_size(source, AppConfig::default());
+        let compact = AppConfig::new(LayoutConfig::default().with_compact(true));
+        let (compact_width, compact_height) = svg_canvas_size(source, compact);
+
+        assert!(compact_width < width, "{compact_width} >= {width}");
+        assert!(compact_height < height, "{compact_height} >= {height}");
+    }
+}
+
+#[test]
+fn test_density_presets_order_canvas_size() {
+    for source in [
+        r#"diagram component [layout_engine="basic"];
+        api: Rectangle { handler: Rectangle; };
+        db: Rectangle;
+        api -> db: "query";"#,
+        r#"diagram sequence;
+        client: Rectangle; server: Rectangle;
+        client -> server: "request";
+        server -> client: "response";"#,
+    ] {
+        let size = |density| {
+            svg_canvas_size(
+                source,
+                AppConfig::new(LayoutConfig::default().with_density(density)),
+            )
+        };
+        let (compact_width, compact_height) = size(Density::Compact);
+        let (width, height) = size(Density::Comfortable);
+        let (spacious_width, spacious_height) = size(Density::Spacious);
+
+        assert_eq!(
+            (width, height),
+            svg_canvas_size(source, AppConfig::default())
+        );
+        assert!(compact_width < width, "{compact_width} >= {width}");
+        assert!(compact_height < height, "{compact_height} >= {height}");
+        assert!(width < spacious_width, "{width} >= {spacious_width}");
+        assert!(height < spacious_height, "{height} >= {spacious_height}");
+    }
+}
+
+#[test]
+fn test_density_attribute_overrides_config() {
+    let source = |density: &str| {
+        format!(
+            r#"diagram component [layout_engine="basic", density="{density}"];
+            a: Rectangle; b: Rectangle; a -> b;"#
+        )
+    };
+    let spacious_config = AppConfig::new(LayoutConfig::default().with_density(Density::Spacious));
+
+    let from_attribute = svg_canvas_size(&source("compact"), spacious_config.clone());
+    let from_config = svg_canvas_size(
+        &source("compact"),
+        AppConfig::new(LayoutConfig::default().with_compact(true)),
+    );
+    assert_eq!(from_attribute, from_config);
+
+    // Individual spacing keys still take precedence over the preset.
+    let margin = AppConfig::new(LayoutConfig::default().with_margin(0.0));
+    let (width, _) = svg_canvas_size(&source("spacious"), margin);
+    let (spacious_width, _) = svg_canvas_size(&source("spacious"), spacious_config);
+    assert_eq!(width, spacious_width - 160.0);
+}
+
+#[test]
+fn test_title_and_caption_expand_canvas() {
+    let plain = r#"diagram component [layout_engine="basic"]; a: Rectangle;"#;
+    let titled = r#"diagram component [layout_engine="basic", title="Checkout <flow>", caption="Figure 1: order & payment"];
+        a: Rectangle;"#;
+
+    let (width, height) = svg_canvas_size(plain, AppConfig::default());
+    let (titled_width, titled_height) = svg_canvas_size(titled, AppConfig::default());
+    assert!(titled_height > height, "{titled_height} <= {height}");
+    assert!(
+        titled_width > width,
+        "the caption is wider than the content"
+    );
+
+    let svg = render(titled);
+
+    assert!(
+        svg.contains("<title>Checkout &lt;flow&gt;</title>"),
+        "{svg}"
+    );
+    assert!(
+        svg.contains("<desc>Figure 1: order &amp; payment</desc>"),
+        "{svg}"
+    );
+}
+
+#[test]
+fn test_svg_is_accessible() {
+    let source = r#"diagram component [layout_engine="basic"];
+        api as "Public API": Rectangle;
+        db: Rectangle;
+        api -> db: "reads";"#;
+    let svg = render(source);
+
+    assert!(svg.contains(r#"role="img""#), "{svg}");
+    assert!(svg.contains("<title>Component diagram</title>"), "{svg}");
+    assert!(
+        svg.contains("<g role=\"graphics-symbol\">\n<title>Public API</title>"),
+        "{svg}"
+    );
+    assert!(svg.contains(r#"aria-label="reads""#), "{svg}");
+}
+
+#[test]
+fn test_tooltips_render_as_titles() {
+    let source = r#"diagram sequence;
+        api as "Public API": Rectangle [tooltip="Handles requests\nfor the web app"];
+        db: Rectangle;
+        api -> [tooltip="SQL over TLS"] db: "query";"#;
+    let svg = render(source);
+
+    assert!(
+        svg.contains(
+            "<g aria-label=\"Public API\" role=\"graphics-symbol\">\n<title>Handles requests\nfor the web app</title>"
+        ),
+        "{svg}"
+    );
+    assert!(
+        svg.contains(
+            "<g aria-label=\"query\" role=\"graphics-symbol\">\n<title>SQL over TLS</title>"
+        ),
+        "{svg}"
+    );
+    assert!(svg.contains("<title>db</title>"), "{svg}");
+}
+
+#[test]
+fn test_href_wraps_component_group_in_link() {
+    let source = r#"diagram component [layout_engine="basic"];
+        api: Rectangle [href="https://example.com/api?a=1&b=2", target="_blank"];
+        db: Rectangle;"#;
+    let svg = render(source);
+
+    assert!(
+        svg.contains(r#"xmlns:xlink="http://www.w3.org/1999/xlink""#),
+        "{svg}"
+    );
+    assert!(
+        svg.contains(
+            "<a target=\"_blank\" xlink:href=\"https://example.com/api?a=1&amp;b=2\">\n<g role=\"graphics-symbol\">\n<title>api</title>"
+        ),
+        "{svg}"
+    );
+    assert_eq!(svg.matches("<a ").count(), 2, "shape and label are linked");
+}
+
+#[test]
+fn test_margin_override_grows_canvas() {
+    let source = r#"diagram component [layout_engine="basic"]; a: Rectangle;"#;
+    let (width, height) = svg_canvas_size(source, AppConfig::default());
+    let wide = AppConfig::new(LayoutConfig::default().with_margin(100.0));
+    let (wide_width, wide_height) = svg_canvas_size(source, wide);
+
+    assert_eq!(wide_width, width + 100.0);
+    assert_eq!(wide_height, height + 100.0);
+}
+
+#[test]
+fn test_render_svg_is_deterministic() {
+    for source in [
+        r#"diagram component [layout_engine="basic"];
+        a: Rectangle; b: Oval; c: Rectangle { d: Rectangle; e: Rectangle; };
+        a -> [stroke=[color="red"]] b: "one";
+        b -> [stroke=[color="green"]] a: "two";
+        a <-> [stroke=[color="blue"]] c;
+        c::d -> c::e;
+        b ->> c;"#,
+        r#"diagram component [layout_engine="sugiyama"];
+        a: Rectangle; b: Rectangle; c: Rectangle; d: Rectangle;
+        a -> b; a -> c; b -> d; c -> d; d <- a;"#,
+        r#"diagram sequence;
+        a: Rectangle; b: Rectangle; c: Rectangle; d: Rectangle;
+        a -> [stroke=[color="red"]] b: "one";
+        b -> [stroke=[color="green"]] c: "two";
+        c ->> [stroke=[color="blue"]] d;
+        note [on=[]]: "all";"#,
+        r#"diagram component [layout_engine="basic"];
+        outer: Rectangle embed {
+            diagram component [layout_engine="basic"];
+            middle: Rectangle embed {
+                diagram component [layout_engine="sugiyama"];
+                inner: Rectangle embed {
+                    diagram component [layout_engine="basic"];
+                    x: Rectangle; y: Oval;
+                    x -> y: "nested";
+                };
+                other: Rectangle;
+                inner -> other;
+            };
+            flow: Rectangle embed {
+                diagram sequence;
+                client: Rectangle; server: Rectangle;
+                client -> server: "request";
+            };
+            middle -> flow;
+        };
+        last: Rectangle;
+        outer -> last;"#,
+    ] {
+        let diagram = parse(source);
+        let provider = InMemorySourceProvider::new();
+        let builder = DiagramBuilder::new(AppConfig::default(), &provider);
+        let first = builder.render_svg(&diagram).expect("Failed to render");
+        for _ in 0..10 {
+            let svg = builder.render_svg(&diagram).expect("Failed to render");
+            assert_eq!(svg, first, "render_svg output differs between runs");
+        }
+    }
+}
+
+#[test]
+fn test_parse_config_sets_diagram_attribute_defaults() {
+    let source = r#"
+        diagram sequence;
+        client: Oval;
+        client -> server: "request";
+    "#;
+    let config = AppConfig::default().with_parse(
+        ParseConfig::default()
+            .with_implicit_nodes(ImplicitNodes::Enabled)
+            .with_implicit_node_type("Oval")
+            .with_autonumber(Some(1)),
+    );
+
+    let diagram = parse_with(config.clone(), source);
+    let elements = diagram.scope().elements();
+    assert_eq!(elements.len(), 3, "`server` is declared implicitly");
+    let semantic::Element::Relation(relation) = &elements[2] else {
+        panic!("expected a relation");
+    };
+    assert_eq!(relation.label(), Some("1. request"));
+
+    let svg = render_with(config, source);
+    assert_eq!(svg.matches("<ellipse").count(), 2, "{svg}");
+
+    let mut provider = InMemorySourceProvider::new();
+    provider.add_file("test.orr", source);
+    let strict = AppConfig::default()
+        .with_parse(ParseConfig::default().with_implicit_nodes(ImplicitNodes::Deny));
+    let arena = Bump::new();
+    let builder = DiagramBuilder::new(strict, &provider);
+    assert!(builder.parse(&arena, Path::new("test.orr")).is_err());
+}
+
+#[test]
+fn test_self_message_adds_a_message_row() {
+    let source = r#"diagram sequence;
+        client: Rectangle; server: Rectangle;
+        client -> server: "request";"#;
+    let (width, height) = svg_canvas_size(source, AppConfig::default());
+    let (loop_width, loop_height) = svg_canvas_size(
+        &format!("{source}\nserver -> server: \"retry\";"),
+        AppConfig::default(),
+    );
+
+    assert!(loop_height > height, "{loop_height} <= {height}");
+    assert!(loop_width >= width, "{loop_width} < {width}");
+}
+
+#[test]
+fn test_render_outline_reflects_nesting() {
+    let diagram = parse(
+        r#"
+        diagram component;
+        backend as "Backend": Rectangle {
+            api: Rectangle {
+                handler: Rectangle;
+            };
+            db: Rectangle;
+        };
+        flow: Rectangle embed {
+            diagram sequence;
+            user: Rectangle;
+        };
+        "#,
+    );
+    let provider = InMemorySourceProvider::new();
+    let builder = DiagramBuilder::new(AppConfig::default(), &provider);
+
+    let markdown = builder.render_outline(&diagram, OutlineFormat::Markdown);
+    assert_eq!(
+        markdown,
+        "- Backend (`backend`)\n\
+         \x20 - api (`backend::api`)\n\
+         \x20   - handler (`backend::api::handler`)\n\
+         \x20 - db (`backend::db`)\n\
+         - flow (`flow`) — sequence diagram\n\
+         \x20 - user (`flow::user`)\n"
+    );
+
+    let json = builder.render_outline(&diagram, OutlineFormat::Json);
+    let value: serde_json::Value = serde_json::from_str(&json).expect("Outline should be JSON");
+    assert_eq!(value[0]["label"], "Backend");
+    assert_eq!(
+        value[0]["children"][0]["children"][0]["id"],
+        "backend::api::handler"
+    );
+    assert_eq!(value[1]["diagram"], "sequence");
+    assert!(value[0].get("diagram").is_none());
+}
+
+/// Parses `source` and exports it to DOT.
+fn render_dot(source: &str) -> Result<String, RenderError> {
+    let diagram = parse(source);
+    let provider = InMemorySourceProvider::new();
+    DiagramBuilder::new(AppConfig::default(), &provider).render_dot(&diagram)
+}
+
+#[test]
+fn test_render_dot_exports_components_and_relations() {
+    let dot = render_dot(
+        r#"
+        diagram component;
+        user as "End \"User\"": Actor;
+        backend as "Backend": Rectangle {
+            api: Rectangle;
+        };
+        db: Rectangle;
+        user -> backend::api: "request";
+        backend <-> db;
+        db - user;
+        "#,
+    )
+    .expect("Failed to export DOT");
+
+    assert_eq!(
+        dot,
+        r#"digraph {
+    compound=true;
+    "user" [label="End \"User\""];
+    subgraph "cluster_backend" {
+        label="Backend";
+        "backend" [shape=point, style=invis];
+        "backend::api" [label="api"];
+    }
+    "db" [label="db"];
+    "user" -> "backend::api" [label="request"];
+    "backend" -> "db" [dir=both, ltail="cluster_backend"];
+    "db" -> "user" [dir=none];
+}
+"#
+    );
+}
+
+#[test]
+fn test_render_dot_exports_groups_as_dashed_clusters() {
+    let dot = render_dot(
+        r#"
+        diagram component;
+        group "Backend" {
+            api: Rectangle;
+        };
+        "#,
+    )
+    .expect("Failed to export DOT");
+
+    assert_eq!(
+        dot,
+        r#"digraph {
+    compound=true;
+    subgraph "cluster_group_0" {
+        label="Backend";
+        style=dashed;
+        "api" [label="api"];
+    }
+}
+"#
+    );
+}
+
+#[test]
+fn test_render_dot_rejects_sequence_diagrams() {
+    let err = render_dot("diagram sequence; a: Rectangle; b: Rectangle; a -> b;")
+        .expect_err("DOT export of a sequence diagram should fail");
+
+    assert!(
+        err.to_string().contains("only supports component diagrams"),
+        "{err}"
+    );
+}
+
+#[test]
+fn test_parse_cached_reparses_only_changed_sources() {
+    let arena = Bump::new();
+    let mut cache = ParseCache::new();
+
+    let mut provider = InMemorySourceProvider::new();
+    provider.add_file("app.orr", "diagram component; a: Rectangle;");
+    let builder = DiagramBuilder::new(AppConfig::default(), &provider);
+
+    let first = builder
+        .parse_cached(&arena, Path::new("app.orr"), &mut cache)
+        .expect("Failed to parse");
+    let second = builder
+        .parse_cached(&arena, Path::new("app.orr"), &mut cache)
+        .expect("Failed to parse");
+    assert_eq!((cache.hits(), cache.misses()), (1, 1));
+    assert_eq!(format!("{first:?}"), format!("{second:?}"));
+
+    let mut edited = InMemorySourceProvider::new();
+    edited.add_file("app.orr", "diagram component; a: Rectangle; b: Oval;");
+    let builder = DiagramBuilder::new(AppConfig::default(), &edited);
+
+    let third = builder
+        .parse_cached(&arena, Path::new("app.orr"), &mut cache)
+        .expect("Failed to parse");
+    assert_eq!((cache.hits(), cache.misses()), (1, 2));
+    assert_ne!(format!("{first:?}"), format!("{third:?}"));
+}
+
+#[test]
+fn test_render_diagram_built_programmatically() {
+    let diagram = semantic::DiagramBuilder::new(DiagramKind::Component)
+        .layout_engine(LayoutEngine::Basic)
+        .component("client")
+        .label("Web Client")
+        .component("backend")
+        .component_in("backend", "api")
+        .component_in("backend", "db")
+        .relation("client", "backend")
+        .label("HTTP requests")
+        .relation("backend::api", "backend::db")
+        .label("SQL")
+        .build()
+        .expect("Failed to build diagram");
+
+    let provider = InMemorySourceProvider::new();
+    let builder = DiagramBuilder::new(AppConfig::default(), &provider);
+    let svg = builder.render_svg(&diagram).expect("Failed to render");
+
+    for text in ["Web Client", "api", "db", "HTTP requests", "SQL"] {
+        assert!(svg.contains(text), "SVG should contain `{text}`");
+    }
+}
@@ -0,0 +1,74 @@
+//! Minimal WebAssembly binding for Orrery.
+//!
+//! Exposes a single [`render`] function that turns Orrery source into an SVG
+//! string entirely in memory. `orrery` is built without its default features,
+//! so nothing here touches the file system or spawns processes.
+//!
+//! Build it for the browser with
+//! [`wasm-pack`](https://rustwasm.github.io/wasm-pack/):
+//!
+//! ```sh
+//! wasm-pack build examples/wasm --target web
+//! ```
+
+use std::path::Path;
+
+use bumpalo::Bump;
+use wasm_bindgen::prelude::*;
+
+use orrery::{
+    DiagramBuilder, InMemorySourceProvider,
+    config::{AppConfig, LayoutConfig},
+    semantic::LayoutEngine,
+};
+
+/// Name under which the source is registered with the in-memory provider.
+const ROOT_FILE: &str = "diagram.orr";
+
+/// Renders Orrery source to an SVG document.
+///
+/// # Errors
+///
+/// Returns the error message as a JavaScript string if the source fails to
+/// parse or the diagram fails to render.
+#[wasm_bindgen]
+pub fn render(source: &str) -> Result<String, JsValue> {
+    render_svg(source).map_err(|err| JsValue::from_str(&err))
+}
+
+/// Parses and renders `source`, reporting failures as plain strings so the
+/// pipeline can be exercised outside a JavaScript host.
+fn render_svg(source: &str) -> Result<String, String> {
+    let arena = Bump::new();
+    let mut provider = InMemorySourceProvider::new();
+    provider.add_file(ROOT_FILE, source);
+
+    // The Graphviz engine shells out to `dot`, which is unavailable in the
+    // browser, so both diagram kinds default to the built-in engine.
+    let config = AppConfig::new(LayoutConfig::new(LayoutEngine::Basic, LayoutEngine::Basic));
+    let builder = DiagramBuilder::new(config, &provider);
+    let diagram = builder
+        .parse(&arena, Path::new(ROOT_FILE))
+        .map_err(|err| err.to_string())?;
+    builder.render_svg(&diagram).map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_svg_returns_document() {
+        let svg = render_svg("diagram component; app: Rectangle; db: Oval; app -> db;").unwrap();
+
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("app"));
+    }
+
+    #[test]
+    fn test_render_svg_reports_parse_errors() {
+        let err = render_svg("diagram component; app: Unknown;").unwrap_err();
+
+        assert!(!err.is_empty());
+    }
+}